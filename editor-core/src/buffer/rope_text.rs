@@ -195,7 +195,33 @@ pub trait RopeText {
 
     /// Return the previous and end boundaries of the word under cursor.
     fn select_word(&self, offset: usize) -> (usize, usize) {
-        WordCursor::new(self.text(), offset).select_word()
+        self.select_word_with(offset, "")
+    }
+
+    /// Like [`Self::select_word`], with the characters of `word_chars` being part of words.
+    fn select_word_with(&self, offset: usize, word_chars: &str) -> (usize, usize) {
+        WordCursor::with_word_chars(self.text(), offset, word_chars).select_word()
+    }
+
+    /// Return the start and end of the paragraph under cursor, the lines around it which aren't
+    /// blank, including the line ending of the last line.
+    /// On a blank line, the blank lines around it are selected instead.
+    fn select_paragraph(&self, offset: usize) -> (usize, usize) {
+        let line = self.line_of_offset(offset);
+        let blank = self.is_line_whitespace(line);
+        let mut start_line = line;
+        while start_line > 0 && self.is_line_whitespace(start_line - 1) == blank {
+            start_line -= 1;
+        }
+        let last_line = self.last_line();
+        let mut end_line = line;
+        while end_line < last_line && self.is_line_whitespace(end_line + 1) == blank {
+            end_line += 1;
+        }
+        (
+            self.offset_of_line(start_line),
+            self.offset_of_line(end_line + 1),
+        )
     }
 
     /// Returns the offset of the first non-blank character on the given line.
@@ -327,11 +353,25 @@ pub trait RopeText {
     /// search.  The `find_next` function should return None when there is no
     /// more word found.  Despite the name, `find_next` can search in either
     /// direction.
-    fn find_nth_word<F>(&self, offset: usize, mut count: usize, mut find_next: F) -> usize
+    fn find_nth_word<F>(&self, offset: usize, count: usize, find_next: F) -> usize
+    where
+        F: FnMut(&mut WordCursor) -> Option<usize>,
+    {
+        self.find_nth_word_with(offset, count, "", find_next)
+    }
+
+    /// Like [`Self::find_nth_word`], with the characters of `word_chars` being part of words.
+    fn find_nth_word_with<F>(
+        &self,
+        offset: usize,
+        mut count: usize,
+        word_chars: &str,
+        mut find_next: F,
+    ) -> usize
     where
         F: FnMut(&mut WordCursor) -> Option<usize>,
     {
-        let mut cursor = WordCursor::new(self.text(), offset);
+        let mut cursor = WordCursor::with_word_chars(self.text(), offset, word_chars);
         let mut new_offset = offset;
         while count != 0 {
             // FIXME: wait for if-let-chain
@@ -346,11 +386,30 @@ pub trait RopeText {
     }
 
     fn move_n_words_forward(&self, offset: usize, count: usize) -> usize {
-        self.find_nth_word(offset, count, |cursor| cursor.next_boundary())
+        self.move_n_words_forward_with(offset, count, "")
+    }
+
+    /// Like [`Self::move_n_words_forward`], with the characters of `word_chars` being part of
+    /// words.
+    fn move_n_words_forward_with(&self, offset: usize, count: usize, word_chars: &str) -> usize {
+        self.find_nth_word_with(offset, count, word_chars, |cursor| cursor.next_boundary())
     }
 
     fn move_n_wordends_forward(&self, offset: usize, count: usize, inserting: bool) -> usize {
-        let mut new_offset = self.find_nth_word(offset, count, |cursor| cursor.end_boundary());
+        self.move_n_wordends_forward_with(offset, count, inserting, "")
+    }
+
+    /// Like [`Self::move_n_wordends_forward`], with the characters of `word_chars` being part of
+    /// words.
+    fn move_n_wordends_forward_with(
+        &self,
+        offset: usize,
+        count: usize,
+        inserting: bool,
+        word_chars: &str,
+    ) -> usize {
+        let mut new_offset =
+            self.find_nth_word_with(offset, count, word_chars, |cursor| cursor.end_boundary());
         if !inserting && new_offset != self.len() {
             new_offset = self.prev_grapheme_offset(new_offset, 1, 0);
         }
@@ -358,7 +417,21 @@ pub trait RopeText {
     }
 
     fn move_n_words_backward(&self, offset: usize, count: usize, mode: Mode) -> usize {
-        self.find_nth_word(offset, count, |cursor| cursor.prev_boundary(mode))
+        self.move_n_words_backward_with(offset, count, mode, "")
+    }
+
+    /// Like [`Self::move_n_words_backward`], with the characters of `word_chars` being part of
+    /// words.
+    fn move_n_words_backward_with(
+        &self,
+        offset: usize,
+        count: usize,
+        mode: Mode,
+        word_chars: &str,
+    ) -> usize {
+        self.find_nth_word_with(offset, count, word_chars, |cursor| {
+            cursor.prev_boundary(mode)
+        })
     }
 
     fn move_word_backward_deletion(&self, offset: usize) -> usize {
@@ -510,6 +583,18 @@ mod tests {
         assert_eq!(text.line_content(5), "");
     }
 
+    #[test]
+    fn test_select_paragraph() {
+        let text = Rope::from("a\nb\n\n  \nc\nd");
+        let text = RopeTextVal::new(text);
+
+        assert_eq!(text.select_paragraph(0), (0, 4));
+        assert_eq!(text.select_paragraph(2), (0, 4));
+        assert_eq!(text.select_paragraph(4), (4, 8));
+        assert_eq!(text.select_paragraph(6), (4, 8));
+        assert_eq!(text.select_paragraph(11), (8, 11));
+    }
+
     #[test]
     fn test_offset_of_line() {
         let text = Rope::from("");
@@ -126,9 +126,34 @@ pub enum EditCommand {
     #[strum(serialize = "duplicate_line_down")]
     DuplicateLineDown,
 
+    #[strum(message = "Transform to Uppercase")]
+    #[strum(serialize = "uppercase_selection")]
+    UppercaseSelection,
+    #[strum(message = "Transform to Lowercase")]
+    #[strum(serialize = "lowercase_selection")]
+    LowercaseSelection,
+    #[strum(message = "Transform to Title Case")]
+    #[strum(serialize = "title_case_selection")]
+    TitleCaseSelection,
+    #[strum(message = "Sort Lines")]
+    #[strum(serialize = "sort_lines")]
+    SortLines,
+    #[strum(message = "Sort Lines, Removing Duplicates")]
+    #[strum(serialize = "sort_lines_unique")]
+    SortLinesUnique,
+    #[strum(message = "Sort Lines Numerically")]
+    #[strum(serialize = "sort_lines_numeric")]
+    SortLinesNumeric,
+
     #[strum(message = "Normalize Line Endings")]
     #[strum(serialize = "normalize_line_endings")]
     NormalizeLineEndings,
+    #[strum(message = "Change Line Endings to LF")]
+    #[strum(serialize = "use_lf_line_endings")]
+    UseLfLineEndings,
+    #[strum(message = "Change Line Endings to CRLF")]
+    #[strum(serialize = "use_crlf_line_endings")]
+    UseCrlfLineEndings,
 }
 
 impl EditCommand {
@@ -501,6 +526,9 @@ pub enum MultiSelectionCommand {
     #[strum(message = "Select Current Line")]
     #[strum(serialize = "select_current_line")]
     SelectCurrentLine,
+    #[strum(message = "Select Paragraph")]
+    #[strum(serialize = "select_paragraph")]
+    SelectParagraph,
     #[strum(message = "Select All Current")]
     #[strum(serialize = "select_all_current")]
     SelectAllCurrent,
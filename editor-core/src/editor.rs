@@ -1,4 +1,9 @@
-use std::{collections::HashSet, iter, ops::Range};
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, HashSet},
+    iter,
+    ops::Range,
+};
 
 use itertools::Itertools;
 use lapce_xi_rope::{DeltaElement, Rope, RopeDelta};
@@ -7,12 +12,13 @@ use crate::{
     buffer::{rope_text::RopeText, Buffer, InvalLines},
     command::EditCommand,
     cursor::{get_first_selection_after, Cursor, CursorMode},
+    line_ending::LineEnding,
     mode::{Mode, MotionMode, VisualMode},
     register::{Clipboard, Register, RegisterData, RegisterKind},
     selection::{InsertDrift, SelRegion, Selection},
     util::{
         has_unmatched_pair, matching_char, matching_pair_direction, str_is_pair_left,
-        str_matching_pair,
+        str_matching_pair, surround_closing_char,
     },
     word::{get_char_property, CharClassification},
 };
@@ -52,6 +58,8 @@ pub enum EditType {
     InsertNewline,
     Cut,
     Paste,
+    /// A part of a paste inserted after the previous parts, which is undone with them
+    PasteContinuation,
     Indent,
     Outdent,
     ToggleComment,
@@ -71,7 +79,10 @@ pub enum EditType {
 impl EditType {
     /// Checks whether a new undo group should be created between two edits.
     pub fn breaks_undo_group(self, previous: EditType) -> bool {
-        !((self == EditType::InsertChars || self == EditType::Delete) && self == previous)
+        let continues_paste = self == EditType::PasteContinuation
+            && matches!(previous, EditType::Paste | EditType::PasteContinuation);
+        !(((self == EditType::InsertChars || self == EditType::Delete) && self == previous)
+            || continues_paste)
     }
 }
 
@@ -86,13 +97,18 @@ pub struct EditConf<'a> {
 pub struct Action {}
 
 impl Action {
+    /// Inserts `s` at the selections of `cursor`, like typing it.
+    ///
+    /// When a single character of `surround_chars` is typed, like a bracket or a quote, the
+    /// selections which aren't carets are wrapped with it and its closing character instead of
+    /// being replaced, see [`DEFAULT_SURROUND_CHARS`](crate::util::DEFAULT_SURROUND_CHARS).
     pub fn insert(
         cursor: &mut Cursor,
         buffer: &mut Buffer,
         s: &str,
         prev_unmatched: &dyn Fn(&Buffer, char, usize) -> Option<usize>,
         auto_closing_matching_pairs: bool,
-        auto_surround: bool,
+        surround_chars: &str,
     ) -> Vec<(Rope, RopeDelta, InvalLines)> {
         let mut deltas = Vec::new();
         if let CursorMode::Insert(selection) = &cursor.mode {
@@ -105,6 +121,11 @@ impl Action {
             } else {
                 let c = s.chars().next().unwrap();
                 let matching_pair_type = matching_pair_direction(c);
+                let surround_closing = if surround_chars.contains(c) {
+                    surround_closing_char(c)
+                } else {
+                    None
+                };
 
                 // The main edit operations
                 let mut edits = vec![];
@@ -123,22 +144,11 @@ impl Action {
                         None
                     };
 
-                    // when text is selected, and [,{,(,'," is inserted
+                    // when text is selected, and one of the surround chars is inserted
                     // wrap the text with that char and its corresponding closing pair
-                    if region.start != region.end
-                        && auto_surround
-                        && (matching_pair_type == Some(true) || c == '"' || c == '\'' || c == '`')
-                    {
+                    if let Some(closing) = surround_closing.filter(|_| !region.is_caret()) {
                         edits.push((Selection::region(region.min(), region.min()), c.to_string()));
-                        edits_after.push((
-                            idx,
-                            match c {
-                                '"' => '"',
-                                '\'' => '\'',
-                                '`' => '`',
-                                _ => matching_char(c).unwrap(),
-                            },
-                        ));
+                        edits_after.push((idx, closing));
                         continue;
                     }
 
@@ -269,13 +279,13 @@ impl Action {
                 for region in selection
                     .regions_mut()
                     .iter_mut()
-                    .sorted_by(|region_a, region_b| region_a.start.cmp(&region_b.start))
+                    .sorted_by(|region_a, region_b| region_a.min().cmp(&region_b.min()))
                 {
                     let new_region =
                         SelRegion::new(region.start + adjustment, region.end + adjustment, None);
 
                     if let Some(inserted) = edits_after.iter().find_map(|(selection, str)| {
-                        if selection.last_inserted().map(|r| r.start) == Some(region.start) {
+                        if selection.last_inserted().map(|r| r.start) == Some(region.max()) {
                             Some(str)
                         } else {
                             None
@@ -745,6 +755,148 @@ impl Action {
         vec![(text, delta, inval_lines)]
     }
 
+    /// Replaces the ranges of `replacements`, which are ordered by their key, in a single edit.
+    /// Ranges overlapping a previous one and replacements which don't change the text are
+    /// skipped.
+    ///
+    /// The cursor stays where it is when the lengths of the ranges don't change, otherwise the
+    /// selections are moved to cover the replacements.
+    fn replace_ranges(
+        cursor: &mut Cursor,
+        buffer: &mut Buffer,
+        replacements: BTreeMap<usize, (usize, String)>,
+    ) -> Vec<(Rope, RopeDelta, InvalLines)> {
+        let mut edits = Vec::new();
+        let mut same_length = true;
+        let mut last_end = 0;
+        for (start, (end, text)) in replacements {
+            if start < last_end || buffer.slice_to_cow(start..end) == text {
+                continue;
+            }
+            last_end = end;
+            same_length &= end - start == text.len();
+            edits.push((Selection::region(start, end), text));
+        }
+        if edits.is_empty() {
+            return vec![];
+        }
+
+        let edits = edits
+            .iter()
+            .map(|(sel, text)| (sel, text.as_str()))
+            .collect::<Vec<_>>();
+        let (text, delta, inval_lines) = buffer.edit(&edits, EditType::Other);
+
+        if !same_length {
+            match &cursor.mode {
+                CursorMode::Insert(selection) => {
+                    let selection = selection.apply_delta(&delta, false, InsertDrift::Inside);
+                    cursor.set_insert(selection);
+                }
+                _ => cursor.apply_delta(&delta),
+            }
+        }
+
+        vec![(text, delta, inval_lines)]
+    }
+
+    /// Replaces the text of each selection with `transform` of it, or the word under the caret
+    /// for carets.
+    fn transform_case(
+        cursor: &mut Cursor,
+        buffer: &mut Buffer,
+        transform: fn(&str) -> String,
+    ) -> Vec<(Rope, RopeDelta, InvalLines)> {
+        let selection = cursor.edit_selection(buffer);
+        let mut replacements = BTreeMap::new();
+        for region in selection.regions() {
+            let (start, end) = if region.is_caret() {
+                buffer.select_word(region.start)
+            } else {
+                (region.min(), region.max())
+            };
+            if start < end {
+                let text = transform(&buffer.slice_to_cow(start..end));
+                replacements.insert(start, (end, text));
+            }
+        }
+        Self::replace_ranges(cursor, buffer, replacements)
+    }
+
+    /// The lines covered by `region`, without the line it ends at the start of.
+    fn region_lines(buffer: &Buffer, region: &SelRegion) -> (usize, usize) {
+        let start_line = buffer.line_of_offset(region.min());
+        let mut end_line = buffer.line_of_offset(region.max());
+        if end_line > start_line && region.max() == buffer.offset_of_line(end_line) {
+            end_line -= 1;
+        }
+        (start_line, end_line)
+    }
+
+    /// Sorts the lines covered by each selection, or all the lines of the document when no
+    /// selection covers more than one line.
+    fn sort_lines(
+        cursor: &mut Cursor,
+        buffer: &mut Buffer,
+        sort: LineSort,
+    ) -> Vec<(Rope, RopeDelta, InvalLines)> {
+        let selection = cursor.edit_selection(buffer);
+        let mut line_ranges: Vec<(usize, usize)> = selection
+            .regions()
+            .iter()
+            .map(|region| Self::region_lines(buffer, region))
+            .filter(|(start_line, end_line)| end_line > start_line)
+            .collect();
+        if line_ranges.is_empty() {
+            let mut last_line = buffer.last_line();
+            // The empty line after the last line ending isn't sorted to the top
+            if last_line > 0 && buffer.line_content(last_line).is_empty() {
+                last_line -= 1;
+            }
+            line_ranges.push((0, last_line));
+        }
+
+        let line_ending = buffer.line_ending().get_chars();
+        let mut replacements = BTreeMap::new();
+        for (start_line, end_line) in line_ranges {
+            let mut lines: Vec<String> = (start_line..=end_line)
+                .map(|line| {
+                    let content = buffer.line_content(line);
+                    content.trim_end_matches(['\n', '\r']).to_string()
+                })
+                .collect();
+            sort.sort(&mut lines);
+            let start = buffer.offset_of_line(start_line);
+            let end = buffer.line_end_offset(end_line, true);
+            replacements.insert(start, (end, lines.join(line_ending)));
+        }
+        Self::replace_ranges(cursor, buffer, replacements)
+    }
+
+    /// Joins the lines covered by each selection, or the line of a caret with the next one,
+    /// replacing the line endings and the indentation of the joined lines with a space.
+    fn join_lines(cursor: &mut Cursor, buffer: &mut Buffer) -> Vec<(Rope, RopeDelta, InvalLines)> {
+        let selection = cursor.edit_selection(buffer);
+        let last_line = buffer.last_line();
+        let mut replacements = BTreeMap::new();
+        for region in selection.regions() {
+            let (start_line, end_line) = Self::region_lines(buffer, region);
+            let end_line = end_line.max(start_line + 1).min(last_line);
+            for line in start_line..end_line {
+                let start = buffer.line_end_offset(line, true);
+                let end = buffer.first_non_blank_character_on_line(line + 1);
+                // Blank lines are joined without adding a space
+                let separator = if end == buffer.line_end_offset(line + 1, true) {
+                    ""
+                } else {
+                    " "
+                };
+                replacements.insert(start, (end, separator.to_string()));
+            }
+        }
+        Self::replace_ranges(cursor, buffer, replacements)
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub fn do_edit<T: Clipboard>(
         cursor: &mut Cursor,
@@ -884,17 +1036,7 @@ impl Action {
                 cursor.apply_delta(&delta);
                 vec![(text, delta, inval_lines)]
             }
-            JoinLines => {
-                let offset = cursor.offset();
-                let (line, _col) = buffer.offset_to_line_col(offset);
-                if line < buffer.last_line() {
-                    let start = buffer.line_end_offset(line, true);
-                    let end = buffer.first_non_blank_character_on_line(line + 1);
-                    vec![buffer.edit([(&Selection::region(start, end), " ")], EditType::Other)]
-                } else {
-                    vec![]
-                }
-            }
+            JoinLines => Self::join_lines(cursor, buffer),
             OutdentLine => {
                 let selection = cursor.edit_selection(buffer);
                 let (text, delta, inval_lines) = Self::do_outdent(buffer, selection);
@@ -1466,7 +1608,18 @@ impl Action {
             }
             DuplicateLineUp => Self::duplicate_line(cursor, buffer, DuplicateDirection::Up),
             DuplicateLineDown => Self::duplicate_line(cursor, buffer, DuplicateDirection::Down),
-            NormalizeLineEndings => {
+            UppercaseSelection => Self::transform_case(cursor, buffer, str::to_uppercase),
+            LowercaseSelection => Self::transform_case(cursor, buffer, str::to_lowercase),
+            TitleCaseSelection => Self::transform_case(cursor, buffer, title_case),
+            SortLines => Self::sort_lines(cursor, buffer, LineSort::Text),
+            SortLinesUnique => Self::sort_lines(cursor, buffer, LineSort::Unique),
+            SortLinesNumeric => Self::sort_lines(cursor, buffer, LineSort::Numeric),
+            NormalizeLineEndings | UseLfLineEndings | UseCrlfLineEndings => {
+                match cmd {
+                    UseLfLineEndings => buffer.set_line_ending(LineEnding::Lf),
+                    UseCrlfLineEndings => buffer.set_line_ending(LineEnding::CrLf),
+                    _ => {}
+                }
                 let Some((text, delta, inval)) = buffer.normalize_line_endings() else {
                     return vec![];
                 };
@@ -1479,6 +1632,71 @@ impl Action {
     }
 }
 
+/// How [`EditCommand::SortLines`] and its variants order the lines.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum LineSort {
+    /// By their text
+    Text,
+    /// By their text, removing the duplicate lines
+    Unique,
+    /// By the number they start with, with the lines not starting with a number first
+    Numeric,
+}
+
+impl LineSort {
+    fn sort(self, lines: &mut Vec<String>) {
+        match self {
+            LineSort::Text => lines.sort(),
+            LineSort::Unique => {
+                lines.sort();
+                lines.dedup();
+            }
+            LineSort::Numeric => lines.sort_by(|a, b| {
+                let order = match (leading_number(a), leading_number(b)) {
+                    (Some(a), Some(b)) => a.partial_cmp(&b).unwrap_or(Ordering::Equal),
+                    (Some(_), None) => Ordering::Greater,
+                    (None, Some(_)) => Ordering::Less,
+                    (None, None) => Ordering::Equal,
+                };
+                order.then_with(|| a.cmp(b))
+            }),
+        }
+    }
+}
+
+/// The number at the start of `line`, after its indentation, like `-12` or `3.5`.
+fn leading_number(line: &str) -> Option<f64> {
+    let line = line.trim_start();
+    let mut end = 0;
+    let mut seen_dot = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            '-' | '+' if i == 0 => {}
+            '.' if !seen_dot => seen_dot = true,
+            '0'..='9' => {}
+            _ => break,
+        }
+        end = i + c.len_utf8();
+    }
+    line[..end].trim_end_matches('.').parse().ok()
+}
+
+/// `text` with the first letter of each word in uppercase and the other letters in lowercase.
+fn title_case(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut in_word = false;
+    for c in text.chars() {
+        if in_word {
+            result.extend(c.to_lowercase());
+        } else {
+            result.extend(c.to_uppercase());
+        }
+        // Apostrophes don't start a new word, like in "don't"
+        in_word = c.is_alphanumeric() || (in_word && c == '\'');
+    }
+    result
+}
+
 fn apply_undo_redo(
     cursor: &mut Cursor,
     buffer: &mut Buffer,
@@ -1520,9 +1738,13 @@ enum DuplicateDirection {
 mod test {
     use crate::{
         buffer::{rope_text::RopeText, Buffer},
+        command::EditCommand,
         cursor::{Cursor, CursorMode},
-        editor::{Action, DuplicateDirection},
+        editor::{leading_number, title_case, Action, DuplicateDirection, EditConf},
+        line_ending::LineEnding,
+        register::{Clipboard, Register},
         selection::{SelRegion, Selection},
+        util::DEFAULT_SURROUND_CHARS,
         word::WordCursor,
     };
 
@@ -1535,7 +1757,14 @@ mod test {
         let mut buffer = Buffer::new("abc");
         let mut cursor = Cursor::new(CursorMode::Insert(Selection::caret(1)), None, None);
 
-        Action::insert(&mut cursor, &mut buffer, "e", &prev_unmatched, true, true);
+        Action::insert(
+            &mut cursor,
+            &mut buffer,
+            "e",
+            &prev_unmatched,
+            true,
+            DEFAULT_SURROUND_CHARS,
+        );
         assert_eq!("aebc", buffer.slice_to_cow(0..buffer.len()));
     }
 
@@ -1547,7 +1776,14 @@ mod test {
         selection.add_region(SelRegion::caret(5));
         let mut cursor = Cursor::new(CursorMode::Insert(selection), None, None);
 
-        Action::insert(&mut cursor, &mut buffer, "i", &prev_unmatched, true, true);
+        Action::insert(
+            &mut cursor,
+            &mut buffer,
+            "i",
+            &prev_unmatched,
+            true,
+            DEFAULT_SURROUND_CHARS,
+        );
         assert_eq!("aibc\neifg\n", buffer.slice_to_cow(0..buffer.len()));
     }
 
@@ -1559,13 +1795,41 @@ mod test {
         selection.add_region(SelRegion::caret(5));
         let mut cursor = Cursor::new(CursorMode::Insert(selection), None, None);
 
-        Action::insert(&mut cursor, &mut buffer, "i", &prev_unmatched, true, true);
+        Action::insert(
+            &mut cursor,
+            &mut buffer,
+            "i",
+            &prev_unmatched,
+            true,
+            DEFAULT_SURROUND_CHARS,
+        );
         assert_eq!("aibc\neifg\n", buffer.slice_to_cow(0..buffer.len()));
-        Action::insert(&mut cursor, &mut buffer, "j", &prev_unmatched, true, true);
+        Action::insert(
+            &mut cursor,
+            &mut buffer,
+            "j",
+            &prev_unmatched,
+            true,
+            DEFAULT_SURROUND_CHARS,
+        );
         assert_eq!("aijbc\neijfg\n", buffer.slice_to_cow(0..buffer.len()));
-        Action::insert(&mut cursor, &mut buffer, "{", &prev_unmatched, true, true);
+        Action::insert(
+            &mut cursor,
+            &mut buffer,
+            "{",
+            &prev_unmatched,
+            true,
+            DEFAULT_SURROUND_CHARS,
+        );
         assert_eq!("aij{bc\neij{fg\n", buffer.slice_to_cow(0..buffer.len()));
-        Action::insert(&mut cursor, &mut buffer, " ", &prev_unmatched, true, true);
+        Action::insert(
+            &mut cursor,
+            &mut buffer,
+            " ",
+            &prev_unmatched,
+            true,
+            DEFAULT_SURROUND_CHARS,
+        );
         assert_eq!("aij{ bc\neij{ fg\n", buffer.slice_to_cow(0..buffer.len()));
     }
 
@@ -1577,9 +1841,23 @@ mod test {
         selection.add_region(SelRegion::caret(6));
         let mut cursor = Cursor::new(CursorMode::Insert(selection), None, None);
 
-        Action::insert(&mut cursor, &mut buffer, "{", &prev_unmatched, true, true);
+        Action::insert(
+            &mut cursor,
+            &mut buffer,
+            "{",
+            &prev_unmatched,
+            true,
+            DEFAULT_SURROUND_CHARS,
+        );
         assert_eq!("a{} bc\ne{} fg\n", buffer.slice_to_cow(0..buffer.len()));
-        Action::insert(&mut cursor, &mut buffer, "}", &prev_unmatched, true, true);
+        Action::insert(
+            &mut cursor,
+            &mut buffer,
+            "}",
+            &prev_unmatched,
+            true,
+            DEFAULT_SURROUND_CHARS,
+        );
         assert_eq!("a{} bc\ne{} fg\n", buffer.slice_to_cow(0..buffer.len()));
     }
 
@@ -1590,10 +1868,54 @@ mod test {
         selection.add_region(SelRegion::new(0, 4, None));
         selection.add_region(SelRegion::new(5, 9, None));
         let mut cursor = Cursor::new(CursorMode::Insert(selection), None, None);
-        Action::insert(&mut cursor, &mut buffer, "{", &prev_unmatched, true, true);
+        Action::insert(
+            &mut cursor,
+            &mut buffer,
+            "{",
+            &prev_unmatched,
+            true,
+            DEFAULT_SURROUND_CHARS,
+        );
         assert_eq!("{a bc}\n{e fg}\n", buffer.slice_to_cow(0..buffer.len()));
     }
 
+    #[test]
+    fn test_insert_markdown_emphasis_with_selection() {
+        let mut buffer = Buffer::new("a bc\ne fg\n");
+        let mut selection = Selection::new();
+        selection.add_region(SelRegion::new(0, 4, None));
+        selection.add_region(SelRegion::new(5, 9, None));
+        let mut cursor = Cursor::new(CursorMode::Insert(selection), None, None);
+        let surround_chars = format!("{DEFAULT_SURROUND_CHARS}*_");
+        Action::insert(
+            &mut cursor,
+            &mut buffer,
+            "*",
+            &prev_unmatched,
+            true,
+            &surround_chars,
+        );
+        Action::insert(
+            &mut cursor,
+            &mut buffer,
+            "*",
+            &prev_unmatched,
+            true,
+            &surround_chars,
+        );
+        assert_eq!("**a bc**\n**e fg**\n", buffer.slice_to_cow(0..buffer.len()));
+
+        // The selections stay on the surrounded text
+        let mut end_selection = Selection::new();
+        end_selection.add_region(SelRegion::new(2, 6, None));
+        end_selection.add_region(SelRegion::new(11, 15, None));
+        assert_eq!(cursor.mode, CursorMode::Insert(end_selection));
+
+        // Without surround chars, the selections are replaced
+        Action::insert(&mut cursor, &mut buffer, "_", &prev_unmatched, true, "");
+        assert_eq!("**_**\n**_**\n", buffer.slice_to_cow(0..buffer.len()));
+    }
+
     #[test]
     fn test_insert_pair_without_auto_closing() {
         let mut buffer = Buffer::new("a bc\ne fg\n");
@@ -1602,9 +1924,9 @@ mod test {
         selection.add_region(SelRegion::caret(6));
         let mut cursor = Cursor::new(CursorMode::Insert(selection), None, None);
 
-        Action::insert(&mut cursor, &mut buffer, "{", &prev_unmatched, false, false);
+        Action::insert(&mut cursor, &mut buffer, "{", &prev_unmatched, false, "");
         assert_eq!("a{ bc\ne{ fg\n", buffer.slice_to_cow(0..buffer.len()));
-        Action::insert(&mut cursor, &mut buffer, "}", &prev_unmatched, false, false);
+        Action::insert(&mut cursor, &mut buffer, "}", &prev_unmatched, false, "");
         assert_eq!("a{} bc\ne{} fg\n", buffer.slice_to_cow(0..buffer.len()));
     }
 
@@ -1746,7 +2068,14 @@ mod test {
         selection.add_region(SelRegion::caret(12));
         let mut cursor = Cursor::new(CursorMode::Insert(selection), None, None);
 
-        Action::insert(&mut cursor, &mut buffer, "(", &prev_unmatched, true, true);
+        Action::insert(
+            &mut cursor,
+            &mut buffer,
+            "(",
+            &prev_unmatched,
+            true,
+            DEFAULT_SURROUND_CHARS,
+        );
 
         assert_eq!(
             "() 123() 567() 9ab() def",
@@ -1761,5 +2090,142 @@ mod test {
         assert_eq!(cursor.mode, CursorMode::Insert(end_selection));
     }
 
+    #[test]
+    fn change_line_endings() {
+        struct NoClipboard;
+        impl Clipboard for NoClipboard {
+            fn get_string(&mut self) -> Option<String> {
+                None
+            }
+            fn put_string(&mut self, _s: impl AsRef<str>) {}
+        }
+
+        let mut buffer = Buffer::new("a\nb\n");
+        let mut cursor = Cursor::new(CursorMode::Insert(Selection::caret(2)), None, None);
+        let conf = || EditConf {
+            comment_token: "",
+            modal: false,
+            smart_tab: false,
+            keep_indent: false,
+            auto_indent: false,
+        };
+        let mut register = Register::default();
+        Action::do_edit(
+            &mut cursor,
+            &mut buffer,
+            &EditCommand::UseCrlfLineEndings,
+            &mut NoClipboard,
+            &mut register,
+            conf(),
+        );
+        assert_eq!(buffer.text().to_string(), "a\r\nb\r\n");
+        assert_eq!(buffer.line_ending(), LineEnding::CrLf);
+        assert_eq!(cursor.offset(), 3);
+
+        Action::do_edit(
+            &mut cursor,
+            &mut buffer,
+            &EditCommand::UseLfLineEndings,
+            &mut NoClipboard,
+            &mut register,
+            conf(),
+        );
+        assert_eq!(buffer.text().to_string(), "a\nb\n");
+    }
+
+    #[test]
+    fn transform_lines() {
+        struct NoClipboard;
+        impl Clipboard for NoClipboard {
+            fn get_string(&mut self) -> Option<String> {
+                None
+            }
+            fn put_string(&mut self, _s: impl AsRef<str>) {}
+        }
+        let edit = |text: &str, selection: Selection, cmd: EditCommand| {
+            let mut buffer = Buffer::new(text);
+            let mut cursor = Cursor::new(CursorMode::Insert(selection), None, None);
+            let conf = EditConf {
+                comment_token: "",
+                modal: false,
+                smart_tab: false,
+                keep_indent: false,
+                auto_indent: false,
+            };
+            let deltas = Action::do_edit(
+                &mut cursor,
+                &mut buffer,
+                &cmd,
+                &mut NoClipboard,
+                &mut Register::default(),
+                conf,
+            );
+            // Each command is a single undoable edit
+            assert!(deltas.len() <= 1);
+            (buffer.text().to_string(), cursor.mode)
+        };
+
+        let (text, mode) = edit(
+            "hello world",
+            Selection::region(0, 5),
+            EditCommand::UppercaseSelection,
+        );
+        assert_eq!(text, "HELLO world");
+        assert_eq!(mode, CursorMode::Insert(Selection::region(0, 5)));
+        // A caret changes the word it's in
+        let (text, _) = edit(
+            "HELLO WORLD",
+            Selection::caret(8),
+            EditCommand::LowercaseSelection,
+        );
+        assert_eq!(text, "HELLO world");
+        let (text, _) = edit(
+            "don't STOP me-now",
+            Selection::region(0, 17),
+            EditCommand::TitleCaseSelection,
+        );
+        assert_eq!(text, "Don't Stop Me-Now");
+
+        // The lines of the selection are sorted, including the line the selection ends in
+        let (text, _) = edit(
+            "c\nb\na\nb\nz\n",
+            Selection::region(0, 7),
+            EditCommand::SortLines,
+        );
+        assert_eq!(text, "a\nb\nb\nc\nz\n");
+        // A caret sorts the whole document
+        let (text, _) = edit(
+            "c\nb\na\nb\n",
+            Selection::caret(0),
+            EditCommand::SortLinesUnique,
+        );
+        assert_eq!(text, "a\nb\nc\n");
+        let (text, mode) = edit(
+            "10 x\n9 y\nnone\n-1.5 z\n",
+            Selection::caret(0),
+            EditCommand::SortLinesNumeric,
+        );
+        assert_eq!(text, "none\n-1.5 z\n9 y\n10 x\n");
+        assert_eq!(mode, CursorMode::Insert(Selection::caret(0)));
+
+        let (text, _) = edit(
+            "a\n    b\n\nc\nd",
+            Selection::region(0, 10),
+            EditCommand::JoinLines,
+        );
+        assert_eq!(text, "a b c\nd");
+        let (text, mode) = edit("a\n  b\nc", Selection::caret(1), EditCommand::JoinLines);
+        assert_eq!(text, "a b\nc");
+        assert_eq!(mode, CursorMode::Insert(Selection::caret(1)));
+    }
+
+    #[test]
+    fn line_transform_helpers() {
+        assert_eq!(leading_number("  42 apples"), Some(42.0));
+        assert_eq!(leading_number("-3.5."), Some(-3.5));
+        assert_eq!(leading_number("- item"), None);
+        assert_eq!(title_case("ÉCOLE du monde"), "École Du Monde");
+    }
+
     // TODO(dbuga): add tests duplicating selections (multiple line blocks)
 }
@@ -14,6 +14,20 @@ pub fn matching_pair_direction(c: char) -> Option<bool> {
     })
 }
 
+/// The characters which wrap the selections when they're typed with text selected, see
+/// [`Action::insert`](crate::editor::Action::insert).
+pub const DEFAULT_SURROUND_CHARS: &str = "([{\"'`";
+
+/// The character closing a selection surrounded with `c`, the matching bracket of an opening
+/// bracket or `c` itself for quotes and other characters. Closing brackets don't surround.
+pub fn surround_closing_char(c: char) -> Option<char> {
+    match matching_pair_direction(c) {
+        Some(true) => matching_char(c),
+        Some(false) => None,
+        None => Some(c),
+    }
+}
+
 pub fn matching_char(c: char) -> Option<char> {
     Some(match c {
         '{' => '}',
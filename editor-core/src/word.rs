@@ -53,12 +53,38 @@ impl WordBoundary {
 /// Boundaries can be the start of a word, its end, punctuation etc.
 pub struct WordCursor<'a> {
     pub(crate) inner: Cursor<'a, RopeInfo>,
+    /// The characters which are part of words besides letters, digits and `_`
+    word_chars: &'a str,
 }
 
 impl<'a> WordCursor<'a> {
     pub fn new(text: &'a Rope, pos: usize) -> WordCursor<'a> {
+        WordCursor::with_word_chars(text, pos, "")
+    }
+
+    /// A cursor for which the characters of `word_chars` are part of words, like `-` in CSS or
+    /// `$` in shell scripts, besides letters, digits and `_`.
+    ///
+    /// **Example:**
+    ///
+    /// ```rust
+    /// # use floem_editor_core::word::WordCursor;
+    /// # use lapce_xi_rope::Rope;
+    /// let rope = Rope::from("margin-top: 0");
+    /// assert_eq!(WordCursor::new(&rope, 2).select_word(), (0, 6));
+    /// assert_eq!(WordCursor::with_word_chars(&rope, 2, "-").select_word(), (0, 10));
+    ///```
+    pub fn with_word_chars(text: &'a Rope, pos: usize, word_chars: &'a str) -> WordCursor<'a> {
         let inner = Cursor::new(text, pos);
-        WordCursor { inner }
+        WordCursor { inner, word_chars }
+    }
+
+    fn char_property(&self, codepoint: char) -> CharClassification {
+        if self.word_chars.contains(codepoint) {
+            CharClassification::Other
+        } else {
+            get_char_property(codepoint)
+        }
     }
 
     /// Get the previous start boundary of a word, and set the cursor position to the boundary found.
@@ -78,10 +104,10 @@ impl<'a> WordCursor<'a> {
     ///```
     pub fn prev_boundary(&mut self, mode: Mode) -> Option<usize> {
         if let Some(ch) = self.inner.prev_codepoint() {
-            let mut prop = get_char_property(ch);
+            let mut prop = self.char_property(ch);
             let mut candidate = self.inner.pos();
             while let Some(prev) = self.inner.prev_codepoint() {
-                let prop_prev = get_char_property(prev);
+                let prop_prev = self.char_property(prev);
                 if classify_boundary(prop_prev, prop).is_start() {
                     break;
                 }
@@ -121,14 +147,14 @@ impl<'a> WordCursor<'a> {
     ///```
     pub fn prev_deletion_boundary(&mut self) -> Option<usize> {
         if let Some(ch) = self.inner.prev_codepoint() {
-            let mut prop = get_char_property(ch);
+            let mut prop = self.char_property(ch);
             let mut candidate = self.inner.pos();
 
             // Flag, determines if the word should be deleted or not
             // If not, erase only whitespace characters.
             let mut keep_word = false;
             while let Some(prev) = self.inner.prev_codepoint() {
-                let prop_prev = get_char_property(prev);
+                let prop_prev = self.char_property(prev);
 
                 // Stop if line beginning reached, without any non-whitespace characters
                 if prop_prev == CharClassification::Lf && prop == CharClassification::Space {
@@ -181,7 +207,7 @@ impl<'a> WordCursor<'a> {
     pub fn next_non_blank_char(&mut self) -> usize {
         let mut candidate = self.inner.pos();
         while let Some(next) = self.inner.next_codepoint() {
-            let prop = get_char_property(next);
+            let prop = self.char_property(next);
             if prop != CharClassification::Space {
                 break;
             }
@@ -204,10 +230,10 @@ impl<'a> WordCursor<'a> {
     ///```
     pub fn next_boundary(&mut self) -> Option<usize> {
         if let Some(ch) = self.inner.next_codepoint() {
-            let mut prop = get_char_property(ch);
+            let mut prop = self.char_property(ch);
             let mut candidate = self.inner.pos();
             while let Some(next) = self.inner.next_codepoint() {
-                let prop_next = get_char_property(next);
+                let prop_next = self.char_property(next);
                 if classify_boundary(prop, prop_next).is_start() {
                     break;
                 }
@@ -234,10 +260,10 @@ impl<'a> WordCursor<'a> {
     pub fn end_boundary(&mut self) -> Option<usize> {
         self.inner.next_codepoint();
         if let Some(ch) = self.inner.next_codepoint() {
-            let mut prop = get_char_property(ch);
+            let mut prop = self.char_property(ch);
             let mut candidate = self.inner.pos();
             while let Some(next) = self.inner.next_codepoint() {
-                let prop_next = get_char_property(next);
+                let prop_next = self.char_property(next);
                 if classify_boundary(prop, prop_next).is_end() {
                     break;
                 }
@@ -265,7 +291,7 @@ impl<'a> WordCursor<'a> {
     pub fn prev_code_boundary(&mut self) -> usize {
         let mut candidate = self.inner.pos();
         while let Some(prev) = self.inner.prev_codepoint() {
-            let prop_prev = get_char_property(prev);
+            let prop_prev = self.char_property(prev);
             if prop_prev != CharClassification::Other {
                 break;
             }
@@ -289,7 +315,7 @@ impl<'a> WordCursor<'a> {
     pub fn next_code_boundary(&mut self) -> usize {
         let mut candidate = self.inner.pos();
         while let Some(prev) = self.inner.next_codepoint() {
-            let prop_prev = get_char_property(prev);
+            let prop_prev = self.char_property(prev);
             if prop_prev != CharClassification::Other {
                 break;
             }
@@ -652,6 +678,20 @@ mod test {
         assert_eq!(&text[start..end], "are");
     }
 
+    #[test]
+    fn word_chars_should_be_part_of_words() {
+        let text = "echo $HOME-dir";
+        let rope = Rope::from(text);
+        let mut cursor = WordCursor::with_word_chars(&rope, 7, "$-");
+        let (start, end) = cursor.select_word();
+        assert_eq!(&text[start..end], "$HOME-dir");
+
+        let mut cursor = WordCursor::with_word_chars(&rope, 5, "$-");
+        assert_eq!(cursor.end_boundary(), Some(14));
+        let mut cursor = WordCursor::new(&rope, 5);
+        assert_eq!(cursor.end_boundary(), Some(10));
+    }
+
     #[test]
     fn should_get_deletion_boundary_backward() {
         let text = "violet are blue";
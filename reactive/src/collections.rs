@@ -0,0 +1,394 @@
+use std::{collections::HashMap, collections::VecDeque, hash::Hash};
+
+use crate::{
+    read::{SignalTrack, SignalWith},
+    signal::{create_rw_signal, RwSignal},
+    write::SignalUpdate,
+};
+
+/// The number of changes kept for consumers which didn't see them yet. Consumers which
+/// fall further behind, e.g. during a large batch, start over from the current values.
+const MAX_PENDING_DELTAS: usize = 256;
+
+/// A change made to a [`RwSignalVec`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum VecDelta<T> {
+    Insert { index: usize, value: T },
+    Remove { index: usize },
+    Set { index: usize, value: T },
+    Move { from: usize, to: usize },
+    Clear,
+}
+
+/// A change made to a [`RwSignalMap`], with the position of the entry in insertion order.
+#[derive(Clone, Debug, PartialEq)]
+pub enum MapDelta<K, V> {
+    Insert { index: usize, key: K, value: V },
+    Remove { index: usize, key: K },
+    Set { index: usize, key: K, value: V },
+    Clear,
+}
+
+/// Values together with the changes which produced the latest versions.
+struct Versioned<C, D> {
+    values: C,
+    version: u64,
+    deltas: VecDeque<D>,
+}
+
+impl<C, D: Clone> Versioned<C, D> {
+    fn new(values: C) -> Self {
+        Self {
+            values,
+            version: 0,
+            deltas: VecDeque::new(),
+        }
+    }
+
+    fn push(&mut self, delta: D) {
+        self.version += 1;
+        self.deltas.push_back(delta);
+        if self.deltas.len() > MAX_PENDING_DELTAS {
+            self.deltas.pop_front();
+        }
+    }
+
+    fn deltas_since(&self, version: u64) -> Option<Vec<D>> {
+        let missed = usize::try_from(self.version.checked_sub(version)?).ok()?;
+        if missed > self.deltas.len() {
+            return None;
+        }
+        Some(
+            self.deltas
+                .range(self.deltas.len() - missed..)
+                .cloned()
+                .collect(),
+        )
+    }
+}
+
+/// A reactive `Vec` which records the changes made to it, so that consumers like
+/// `dyn_stack_signal_vec` can update only what changed instead of diffing the whole `Vec`.
+///
+/// Reading the values with [`with`](Self::with) or [`get`](Self::get) subscribes to every
+/// change, like reading an [`RwSignal`].
+pub struct RwSignalVec<T: 'static> {
+    inner: RwSignal<Versioned<Vec<T>, VecDelta<T>>>,
+}
+
+impl<T> Copy for RwSignalVec<T> {}
+
+impl<T> Clone for RwSignalVec<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: Clone + 'static> RwSignalVec<T> {
+    pub fn new(values: Vec<T>) -> Self {
+        Self {
+            inner: create_rw_signal(Versioned::new(values)),
+        }
+    }
+
+    /// Reads the values and subscribes to changes.
+    pub fn with<O>(&self, f: impl FnOnce(&[T]) -> O) -> O {
+        self.inner.with(|inner| f(&inner.values))
+    }
+
+    /// Reads the values without subscribing to changes.
+    pub fn with_untracked<O>(&self, f: impl FnOnce(&[T]) -> O) -> O {
+        self.inner.with_untracked(|inner| f(&inner.values))
+    }
+
+    pub fn get(&self) -> Vec<T> {
+        self.with(|values| values.to_vec())
+    }
+
+    pub fn get_untracked(&self) -> Vec<T> {
+        self.with_untracked(|values| values.to_vec())
+    }
+
+    pub fn len(&self) -> usize {
+        self.with(|values| values.len())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.with(|values| values.is_empty())
+    }
+
+    /// Subscribes to changes without reading the values.
+    pub fn track(&self) {
+        self.inner.track();
+    }
+
+    pub fn push(&self, value: T) {
+        self.inner.update(|inner| {
+            let index = inner.values.len();
+            inner.values.push(value.clone());
+            inner.push(VecDelta::Insert { index, value });
+        });
+    }
+
+    pub fn pop(&self) -> Option<T> {
+        let mut popped = None;
+        self.inner.update(|inner| {
+            popped = inner.values.pop();
+            if popped.is_some() {
+                let index = inner.values.len();
+                inner.push(VecDelta::Remove { index });
+            }
+        });
+        popped
+    }
+
+    /// # Panics
+    /// Panics if `index > len`.
+    pub fn insert(&self, index: usize, value: T) {
+        self.inner.update(|inner| {
+            inner.values.insert(index, value.clone());
+            inner.push(VecDelta::Insert { index, value });
+        });
+    }
+
+    /// # Panics
+    /// Panics if `index` is out of bounds.
+    pub fn remove(&self, index: usize) -> T {
+        let mut removed = None;
+        self.inner.update(|inner| {
+            removed = Some(inner.values.remove(index));
+            inner.push(VecDelta::Remove { index });
+        });
+        removed.unwrap()
+    }
+
+    /// Replaces the value at `index`.
+    ///
+    /// # Panics
+    /// Panics if `index` is out of bounds.
+    pub fn set(&self, index: usize, value: T) {
+        self.inner.update(|inner| {
+            inner.values[index] = value.clone();
+            inner.push(VecDelta::Set { index, value });
+        });
+    }
+
+    /// Moves the value at `from` so that it ends up at `to`, shifting the values between.
+    ///
+    /// # Panics
+    /// Panics if `from` or `to` is out of bounds.
+    pub fn move_item(&self, from: usize, to: usize) {
+        self.inner.update(|inner| {
+            let value = inner.values.remove(from);
+            inner.values.insert(to, value);
+            inner.push(VecDelta::Move { from, to });
+        });
+    }
+
+    /// Keeps only the values for which `f` returns `true`, removing the others one by one.
+    pub fn retain(&self, mut f: impl FnMut(&T) -> bool) {
+        self.inner.update(|inner| {
+            let mut index = 0;
+            while index < inner.values.len() {
+                if f(&inner.values[index]) {
+                    index += 1;
+                } else {
+                    inner.values.remove(index);
+                    inner.push(VecDelta::Remove { index });
+                }
+            }
+        });
+    }
+
+    pub fn clear(&self) {
+        self.inner.update(|inner| {
+            inner.values.clear();
+            inner.push(VecDelta::Clear);
+        });
+    }
+
+    /// Replaces all values, which is recorded as clearing the `Vec` and inserting every
+    /// value.
+    pub fn replace(&self, values: Vec<T>) {
+        self.inner.update(|inner| {
+            inner.push(VecDelta::Clear);
+            for (index, value) in values.iter().enumerate() {
+                inner.push(VecDelta::Insert {
+                    index,
+                    value: value.clone(),
+                });
+            }
+            inner.values = values;
+        });
+    }
+
+    /// The version of the values, which increases with every change.
+    pub fn version(&self) -> u64 {
+        self.inner.with_untracked(|inner| inner.version)
+    }
+
+    /// Returns the changes made since `version` and subscribes to changes, or `None` if
+    /// the changes aren't recorded anymore and the consumer has to start over from the
+    /// current values.
+    pub fn deltas_since(&self, version: u64) -> Option<Vec<VecDelta<T>>> {
+        self.inner.with(|inner| inner.deltas_since(version))
+    }
+}
+
+/// Create a [`RwSignalVec`] with the given values.
+pub fn create_signal_vec<T: Clone + 'static>(values: Vec<T>) -> RwSignalVec<T> {
+    RwSignalVec::new(values)
+}
+
+struct MapValues<K, V> {
+    entries: Vec<(K, V)>,
+    indices: HashMap<K, usize>,
+}
+
+impl<K: Eq + Hash + Clone, V> MapValues<K, V> {
+    fn reindex(&mut self, from: usize) {
+        for (index, (key, _)) in self.entries.iter().enumerate().skip(from) {
+            self.indices.insert(key.clone(), index);
+        }
+    }
+}
+
+/// A reactive map which keeps its entries in insertion order and records the changes made
+/// to it, like [`RwSignalVec`].
+pub struct RwSignalMap<K: 'static, V: 'static> {
+    inner: RwSignal<Versioned<MapValues<K, V>, MapDelta<K, V>>>,
+}
+
+impl<K, V> Copy for RwSignalMap<K, V> {}
+
+impl<K, V> Clone for RwSignalMap<K, V> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<K: Eq + Hash + Clone + 'static, V: Clone + 'static> RwSignalMap<K, V> {
+    pub fn new(entries: impl IntoIterator<Item = (K, V)>) -> Self {
+        let mut values = MapValues {
+            entries: Vec::new(),
+            indices: HashMap::new(),
+        };
+        for (key, value) in entries {
+            if let Some(&index) = values.indices.get(&key) {
+                values.entries[index].1 = value;
+            } else {
+                values.indices.insert(key.clone(), values.entries.len());
+                values.entries.push((key, value));
+            }
+        }
+        Self {
+            inner: create_rw_signal(Versioned::new(values)),
+        }
+    }
+
+    /// Reads the entries in insertion order and subscribes to changes.
+    pub fn with<O>(&self, f: impl FnOnce(&[(K, V)]) -> O) -> O {
+        self.inner.with(|inner| f(&inner.values.entries))
+    }
+
+    /// Reads the entries in insertion order without subscribing to changes.
+    pub fn with_untracked<O>(&self, f: impl FnOnce(&[(K, V)]) -> O) -> O {
+        self.inner.with_untracked(|inner| f(&inner.values.entries))
+    }
+
+    /// Gets the value of `key` and subscribes to changes.
+    pub fn get(&self, key: &K) -> Option<V> {
+        self.inner.with(|inner| {
+            let index = *inner.values.indices.get(key)?;
+            Some(inner.values.entries[index].1.clone())
+        })
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.inner
+            .with(|inner| inner.values.indices.contains_key(key))
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner.with(|inner| inner.values.entries.len())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.with(|inner| inner.values.entries.is_empty())
+    }
+
+    /// Subscribes to changes without reading the entries.
+    pub fn track(&self) {
+        self.inner.track();
+    }
+
+    /// Inserts or replaces the value of `key`, returning the replaced value. A new key is
+    /// added at the end.
+    pub fn insert(&self, key: K, value: V) -> Option<V> {
+        let mut replaced = None;
+        self.inner.update(|inner| {
+            if let Some(&index) = inner.values.indices.get(&key) {
+                replaced = Some(std::mem::replace(
+                    &mut inner.values.entries[index].1,
+                    value.clone(),
+                ));
+                inner.push(MapDelta::Set { index, key, value });
+            } else {
+                let index = inner.values.entries.len();
+                inner.values.indices.insert(key.clone(), index);
+                inner.values.entries.push((key.clone(), value.clone()));
+                inner.push(MapDelta::Insert { index, key, value });
+            }
+        });
+        replaced
+    }
+
+    pub fn remove(&self, key: &K) -> Option<V> {
+        if !self
+            .inner
+            .with_untracked(|inner| inner.values.indices.contains_key(key))
+        {
+            return None;
+        }
+        let mut removed = None;
+        self.inner.update(|inner| {
+            let Some(index) = inner.values.indices.remove(key) else {
+                return;
+            };
+            let (key, value) = inner.values.entries.remove(index);
+            inner.values.reindex(index);
+            removed = Some(value);
+            inner.push(MapDelta::Remove { index, key });
+        });
+        removed
+    }
+
+    pub fn clear(&self) {
+        self.inner.update(|inner| {
+            inner.values.entries.clear();
+            inner.values.indices.clear();
+            inner.push(MapDelta::Clear);
+        });
+    }
+
+    /// The version of the entries, which increases with every change.
+    pub fn version(&self) -> u64 {
+        self.inner.with_untracked(|inner| inner.version)
+    }
+
+    /// Returns the changes made since `version` and subscribes to changes, or `None` if
+    /// the changes aren't recorded anymore and the consumer has to start over from the
+    /// current entries.
+    pub fn deltas_since(&self, version: u64) -> Option<Vec<MapDelta<K, V>>> {
+        self.inner.with(|inner| inner.deltas_since(version))
+    }
+}
+
+/// Create a [`RwSignalMap`] with the given entries.
+pub fn create_signal_map<K, V>(entries: impl IntoIterator<Item = (K, V)>) -> RwSignalMap<K, V>
+where
+    K: Eq + Hash + Clone + 'static,
+    V: Clone + 'static,
+{
+    RwSignalMap::new(entries)
+}
@@ -0,0 +1,296 @@
+//! Introspection of the reactive graph, to debug leaks and cycles.
+//!
+//! [`reactive_graph`] takes a snapshot of the scopes, signals and effects which are
+//! alive, and [`ScopeLeakDetector`] finds scopes which keep growing without being
+//! disposed, which is usually a view or an effect creating signals in a scope that lives
+//! longer than intended.
+//!
+//! With the `track-panic` feature the runtime also remembers where every node was
+//! created, which is shown in the snapshot and in the panic message when a disposed
+//! signal is used, and effects which keep triggering themselves panic with the
+//! location of the effect instead of overflowing the stack.
+
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    fmt,
+};
+
+use crate::{id::Id, runtime::RUNTIME, signal::Signal};
+
+/// What a node of the reactive graph is.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum NodeKind {
+    Scope,
+    Signal,
+    Effect,
+}
+
+/// Where a node was created, only recorded with the `track-panic` feature.
+#[derive(Clone, Copy, Debug)]
+pub struct Origin {
+    pub kind: NodeKind,
+    pub location: &'static std::panic::Location<'static>,
+    /// The type of the value of a signal
+    pub type_name: Option<&'static str>,
+}
+
+impl fmt::Display for Origin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.type_name {
+            Some(type_name) => write!(f, "{:?}<{type_name}> at {}", self.kind, self.location),
+            None => write!(f, "{:?} at {}", self.kind, self.location),
+        }
+    }
+}
+
+/// A node of a [`ReactiveGraph`].
+#[derive(Clone, Debug)]
+pub struct NodeInfo {
+    pub id: Id,
+    pub kind: NodeKind,
+    /// The scope the node belongs to, if it isn't a root scope
+    pub scope: Option<Id>,
+    /// The signals and effects created in this node, for scopes and effects
+    pub children: Vec<Id>,
+    /// The effects which run when this signal changes
+    pub subscribers: Vec<Id>,
+    pub origin: Option<Origin>,
+}
+
+/// A snapshot of the reactive graph of the current thread, see [`reactive_graph`].
+///
+/// Its `Display` implementation prints the scope tree with the subscribers of every
+/// signal.
+#[derive(Clone, Debug, Default)]
+pub struct ReactiveGraph {
+    pub nodes: BTreeMap<Id, NodeInfo>,
+    /// How often an effect ran while it was already running, e.g. because it writes a
+    /// signal it reads
+    pub reentrant_effects: HashMap<Id, usize>,
+}
+
+impl ReactiveGraph {
+    pub fn count(&self, kind: NodeKind) -> usize {
+        self.nodes.values().filter(|node| node.kind == kind).count()
+    }
+
+    /// The nodes which don't belong to a scope.
+    pub fn roots(&self) -> impl Iterator<Item = &NodeInfo> {
+        self.nodes.values().filter(|node| node.scope.is_none())
+    }
+
+    fn fmt_node(&self, f: &mut fmt::Formatter<'_>, node: &NodeInfo, depth: usize) -> fmt::Result {
+        write!(
+            f,
+            "{:indent$}{:?} {:?}",
+            "",
+            node.kind,
+            node.id,
+            indent = depth * 2
+        )?;
+        if let Some(origin) = &node.origin {
+            write!(f, " ({origin})")?;
+        }
+        if !node.subscribers.is_empty() {
+            write!(f, " -> {:?}", node.subscribers)?;
+        }
+        if let Some(count) = self.reentrant_effects.get(&node.id) {
+            write!(f, " [reentered {count} times]")?;
+        }
+        writeln!(f)?;
+        for child in &node.children {
+            if let Some(child) = self.nodes.get(child) {
+                self.fmt_node(f, child, depth + 1)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for ReactiveGraph {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} scopes, {} signals, {} effects",
+            self.count(NodeKind::Scope),
+            self.count(NodeKind::Signal),
+            self.count(NodeKind::Effect)
+        )?;
+        for root in self.roots() {
+            self.fmt_node(f, root, 0)?;
+        }
+        Ok(())
+    }
+}
+
+/// Takes a snapshot of the scopes, signals and effects which are alive on the current
+/// thread.
+pub fn reactive_graph() -> ReactiveGraph {
+    RUNTIME.with(|runtime| {
+        let children = runtime.children.borrow();
+        let signals = runtime.signals.borrow();
+
+        // Effects track their own id like a scope to be disposed with it, so everything
+        // which subscribes to a signal is an effect.
+        let mut effects = BTreeSet::new();
+        for signal in signals.values() {
+            effects.extend(signal.subscribers.borrow().keys().copied());
+        }
+
+        let mut graph = ReactiveGraph::default();
+        let kind = |id: &Id| {
+            if effects.contains(id) {
+                NodeKind::Effect
+            } else if signals.contains_key(id) && !children.contains_key(id) {
+                NodeKind::Signal
+            } else {
+                NodeKind::Scope
+            }
+        };
+        for (scope, scope_children) in children.iter() {
+            let scope_children: Vec<Id> = scope_children
+                .iter()
+                .copied()
+                .filter(|child| is_alive(&children, &signals, child))
+                .collect();
+            let scope_node = node_entry(&mut graph.nodes, *scope, kind(scope));
+            scope_node.children = scope_children.clone();
+            scope_node.children.sort();
+            for child in &scope_children {
+                node_entry(&mut graph.nodes, *child, kind(child)).scope = Some(*scope);
+            }
+        }
+        for (id, signal) in signals.iter() {
+            let signal_node = node_entry(&mut graph.nodes, *id, kind(id));
+            // Effects and scopes subscribe to themselves to be disposed with their scope
+            signal_node.subscribers = signal
+                .subscribers
+                .borrow()
+                .keys()
+                .copied()
+                .filter(|subscriber| subscriber != id)
+                .collect();
+            signal_node.subscribers.sort();
+        }
+        graph.reentrant_effects = runtime
+            .reentrant_effects
+            .borrow()
+            .iter()
+            .filter(|(id, _)| graph.nodes.contains_key(id))
+            .map(|(id, count)| (*id, *count))
+            .collect();
+        graph
+    })
+}
+
+/// Disposed ids stay in the children of their parent scope until it is disposed too.
+fn is_alive(children: &HashMap<Id, HashSet<Id>>, signals: &HashMap<Id, Signal>, id: &Id) -> bool {
+    children.contains_key(id) || signals.contains_key(id)
+}
+
+fn node_entry(nodes: &mut BTreeMap<Id, NodeInfo>, id: Id, kind: NodeKind) -> &mut NodeInfo {
+    nodes.entry(id).or_insert_with(|| NodeInfo {
+        id,
+        kind,
+        scope: None,
+        children: Vec::new(),
+        subscribers: Vec::new(),
+        origin: origin(id),
+    })
+}
+
+/// A scope which kept growing, see [`ScopeLeakDetector`].
+#[derive(Clone, Debug)]
+pub struct ScopeGrowth {
+    pub scope: Id,
+    /// The number of signals, effects and scopes directly in the scope
+    pub children: usize,
+    /// The number of checks in a row in which the scope had more children than before
+    pub grown_checks: u32,
+    pub origin: Option<Origin>,
+}
+
+/// Finds scopes which grow without being disposed.
+///
+/// Call [`check`](Self::check) periodically, e.g. after every time the user switched
+/// between two views, and it reports the scopes which had more children after each of
+/// the last `threshold` checks. A view which is removed and recreated disposes its
+/// scope, so a scope which keeps growing usually leaks the signals created in it.
+pub struct ScopeLeakDetector {
+    threshold: u32,
+    scopes: HashMap<Id, (usize, u32)>,
+}
+
+impl ScopeLeakDetector {
+    pub fn new(threshold: u32) -> Self {
+        Self {
+            threshold,
+            scopes: HashMap::new(),
+        }
+    }
+
+    /// Compares the size of every scope with the last check, and returns the scopes which
+    /// grew in the last `threshold` checks.
+    pub fn check(&mut self) -> Vec<ScopeGrowth> {
+        let sizes: HashMap<Id, usize> = RUNTIME.with(|runtime| {
+            let children = runtime.children.borrow();
+            let signals = runtime.signals.borrow();
+            children
+                .iter()
+                .map(|(scope, scope_children)| {
+                    let alive = scope_children
+                        .iter()
+                        .filter(|child| is_alive(&children, &signals, child))
+                        .count();
+                    (*scope, alive)
+                })
+                .collect()
+        });
+        let prev = std::mem::take(&mut self.scopes);
+        let mut growing = Vec::new();
+        for (scope, children) in sizes {
+            let grown_checks = match prev.get(&scope) {
+                Some((prev_children, grown_checks)) if children > *prev_children => {
+                    grown_checks + 1
+                }
+                Some((prev_children, grown_checks)) if children == *prev_children => *grown_checks,
+                _ => 0,
+            };
+            if grown_checks >= self.threshold {
+                growing.push(ScopeGrowth {
+                    scope,
+                    children,
+                    grown_checks,
+                    origin: origin(scope),
+                });
+            }
+            self.scopes.insert(scope, (children, grown_checks));
+        }
+        growing.sort_by_key(|growth| std::cmp::Reverse(growth.grown_checks));
+        growing
+    }
+}
+
+/// Where the node was created, if the `track-panic` feature is enabled.
+pub fn origin(id: Id) -> Option<Origin> {
+    #[cfg(feature = "track-panic")]
+    {
+        RUNTIME.with(|runtime| runtime.origins.borrow().get(&id).copied())
+    }
+    #[cfg(not(feature = "track-panic"))]
+    {
+        let _ = id;
+        None
+    }
+}
+
+#[cfg(feature = "track-panic")]
+#[track_caller]
+pub(crate) fn record_origin(id: Id, kind: NodeKind, type_name: Option<&'static str>) {
+    let origin = Origin {
+        kind,
+        location: std::panic::Location::caller(),
+        type_name,
+    };
+    RUNTIME.with(|runtime| runtime.origins.borrow_mut().insert(id, origin));
+}
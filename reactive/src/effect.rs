@@ -7,8 +7,29 @@ use crate::{
     signal::NotThreadSafe,
 };
 
+/// When an effect runs after the signals it subscribes to change.
+///
+/// Effects subscribed to the same change run in the order of their priority. Idle effects
+/// don't run right away, but are queued until the application has spare time, e.g. once
+/// a frame is done, see [`run_idle_effects`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum EffectPriority {
+    /// For effects which react to input, like updating the text of a text input.
+    Input,
+    /// For effects which drive animations.
+    Animation,
+    /// For effects which update views, which is what [`create_effect`] creates.
+    #[default]
+    Layout,
+    /// For work which can wait, like background layout or analytics.
+    Idle,
+}
+
 pub(crate) trait EffectTrait {
     fn id(&self) -> Id;
+    fn priority(&self) -> EffectPriority {
+        EffectPriority::Layout
+    }
     fn run(&self) -> bool;
     fn add_observer(&self, id: Id);
     fn clear_observers(&self) -> HashSet<Id>;
@@ -21,6 +42,7 @@ where
 {
     id: Id,
     f: F,
+    priority: EffectPriority,
     value: RefCell<Option<T>>,
     observers: RefCell<HashSet<Id>>,
     ts: PhantomData<NotThreadSafe>,
@@ -43,21 +65,77 @@ where
 /// subscribed in that run. And when these Signals update, it will rerun the function.
 /// And the effect re-tracks the signals in each run, so that it will only be re-run
 /// by the Signals that actually ran in the last effect run.
+#[cfg_attr(feature = "track-panic", track_caller)]
 pub fn create_effect<T>(f: impl Fn(Option<T>) -> T + 'static)
 where
     T: Any + 'static,
+{
+    create_effect_with_priority(EffectPriority::Layout, f);
+}
+
+/// Create an Effect like [`create_effect`], which runs before or after the other effects
+/// subscribed to the same change depending on its `priority`.
+///
+/// An [`EffectPriority::Idle`] effect doesn't run immediately, also not for the first time,
+/// but when the application has spare time, see [`create_idle_effect`].
+#[cfg_attr(feature = "track-panic", track_caller)]
+pub fn create_effect_with_priority<T>(
+    priority: EffectPriority,
+    f: impl Fn(Option<T>) -> T + 'static,
+) where
+    T: Any + 'static,
 {
     let id = Id::next();
     let effect = Rc::new(Effect {
         id,
         f,
+        priority,
         value: RefCell::new(None),
         observers: RefCell::new(HashSet::default()),
         ts: PhantomData,
     });
     id.set_scope();
+    #[cfg(feature = "track-panic")]
+    crate::debug::record_origin(id, crate::debug::NodeKind::Effect, None);
 
-    run_initial_effect(effect);
+    if priority == EffectPriority::Idle {
+        RUNTIME.with(|runtime| runtime.add_idle_effect(effect));
+    } else {
+        run_initial_effect(effect);
+    }
+}
+
+/// Create an Effect which runs when the application has spare time instead of right away,
+/// so that it doesn't compete with handling input and painting.
+///
+/// Changes made before it gets to run are coalesced into one run.
+#[cfg_attr(feature = "track-panic", track_caller)]
+pub fn create_idle_effect<T>(f: impl Fn(Option<T>) -> T + 'static)
+where
+    T: Any + 'static,
+{
+    create_effect_with_priority(EffectPriority::Idle, f);
+}
+
+/// Runs the queued idle effects until `should_yield` returns `true`, which is checked
+/// after every effect. Returns `true` if idle effects are left.
+///
+/// Floem calls this once it handled all events, with a small time budget.
+pub fn run_idle_effects(mut should_yield: impl FnMut() -> bool) -> bool {
+    loop {
+        let Some(effect) = RUNTIME.with(|runtime| runtime.pop_idle_effect()) else {
+            return false;
+        };
+        run_effect(effect);
+        if should_yield() {
+            return has_idle_effects();
+        }
+    }
+}
+
+/// Whether there are idle effects waiting to run.
+pub fn has_idle_effects() -> bool {
+    RUNTIME.with(|runtime| !runtime.idle_effects.borrow().is_empty())
 }
 
 struct UpdaterEffect<T, I, C, U>
@@ -84,6 +162,7 @@ where
 
 /// Create an effect updater that runs `on_change` when any signals `compute` subscribes to
 /// changes. `compute` is immediately run and its return value is returned from `create_updater`.
+#[cfg_attr(feature = "track-panic", track_caller)]
 pub fn create_updater<R>(compute: impl Fn() -> R + 'static, on_change: impl Fn(R) + 'static) -> R
 where
     R: 'static,
@@ -93,6 +172,7 @@ where
 
 /// Create an effect updater that runs `on_change` when any signals `compute` subscribes to
 /// changes. `compute` is immediately run and its return value is returned from `create_updater`.
+#[cfg_attr(feature = "track-panic", track_caller)]
 pub fn create_stateful_updater<T, R>(
     compute: impl Fn(Option<T>) -> (R, T) + 'static,
     on_change: impl Fn(R, T) -> T + 'static,
@@ -110,10 +190,45 @@ where
         observers: RefCell::new(HashSet::default()),
     });
     id.set_scope();
+    #[cfg(feature = "track-panic")]
+    crate::debug::record_origin(id, crate::debug::NodeKind::Effect, None);
 
     run_initial_updater_effect(effect)
 }
 
+/// Create an effect which calls `callback` with the previous and the new value of `source`
+/// whenever the signals `source` subscribes to change.
+///
+/// `callback` doesn't subscribe to the signals it reads. With `immediate` it's also called
+/// for the initial value, with no previous value.
+///
+/// ```
+/// # use floem_reactive::{create_rw_signal, create_watch, SignalGet, SignalUpdate};
+/// let count = create_rw_signal(0);
+/// create_watch(
+///     move || count.get(),
+///     |prev, next| println!("count changed from {prev:?} to {next}"),
+///     false,
+/// );
+/// count.set(1);
+/// ```
+#[cfg_attr(feature = "track-panic", track_caller)]
+pub fn create_watch<T>(
+    source: impl Fn() -> T + 'static,
+    callback: impl Fn(Option<&T>, &T) + 'static,
+    immediate: bool,
+) where
+    T: Any + 'static,
+{
+    create_effect(move |prev: Option<T>| {
+        let next = source();
+        if prev.is_some() || immediate {
+            untrack(|| callback(prev.as_ref(), &next));
+        }
+        next
+    });
+}
+
 /// Signals that's wrapped this untrack will not subscribe to any effect
 pub fn untrack<T>(f: impl FnOnce() -> T) -> T {
     let prev_effect = RUNTIME.with(|runtime| runtime.current_effect.borrow_mut().take());
@@ -150,6 +265,7 @@ pub(crate) fn run_initial_effect(effect: Rc<dyn EffectTrait>) {
 
     RUNTIME.with(|runtime| {
         *runtime.current_effect.borrow_mut() = Some(effect.clone());
+        runtime.enter_effect(effect_id);
 
         let effect_scope = Scope(effect_id, PhantomData);
         with_scope(effect_scope, || {
@@ -157,13 +273,32 @@ pub(crate) fn run_initial_effect(effect: Rc<dyn EffectTrait>) {
             effect.run();
         });
 
+        runtime.exit_effect();
         *runtime.current_effect.borrow_mut() = None;
     });
 }
 
 pub(crate) fn run_effect(effect: Rc<dyn EffectTrait>) {
     let effect_id = effect.id();
+    // Disposing the effect's scope forgets where it was created and how often it re-entered
+    #[cfg(feature = "track-panic")]
+    let origin = crate::debug::origin(effect_id);
+    let reentered =
+        RUNTIME.with(|runtime| runtime.reentrant_effects.borrow().get(&effect_id).copied());
     effect_id.dispose();
+    #[cfg(feature = "track-panic")]
+    if let Some(origin) = origin {
+        RUNTIME.with(|runtime| runtime.origins.borrow_mut().insert(effect_id, origin));
+    }
+    if let Some(reentered) = reentered {
+        RUNTIME.with(|runtime| {
+            runtime
+                .reentrant_effects
+                .borrow_mut()
+                .insert(effect_id, reentered)
+        });
+    }
+    RUNTIME.with(|runtime| runtime.enter_effect(effect_id));
 
     observer_clean_up(&effect);
 
@@ -177,6 +312,7 @@ pub(crate) fn run_effect(effect: Rc<dyn EffectTrait>) {
         });
 
         *runtime.current_effect.borrow_mut() = None;
+        runtime.exit_effect();
     });
 }
 
@@ -231,6 +367,10 @@ where
         self.id
     }
 
+    fn priority(&self) -> EffectPriority {
+        self.priority
+    }
+
     fn run(&self) -> bool {
         let curr_value = self.value.borrow_mut().take();
 
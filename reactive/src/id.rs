@@ -3,7 +3,7 @@ use std::sync::atomic::AtomicU64;
 use crate::{effect::observer_clean_up, runtime::RUNTIME, signal::Signal};
 
 /// An internal id which can reference a Signal/Effect/Scope.
-#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Hash)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Id(u64);
 
 impl Id {
@@ -18,6 +18,25 @@ impl Id {
         RUNTIME.with(|runtime| runtime.signals.borrow().get(self).cloned())
     }
 
+    /// Get the Signal that links with this Id, and panic if it was disposed
+    #[cfg_attr(feature = "track-panic", track_caller)]
+    pub(crate) fn signal_or_disposed(&self) -> Signal {
+        match self.signal() {
+            Some(signal) => signal,
+            None => self.disposed(),
+        }
+    }
+
+    #[cfg_attr(feature = "track-panic", track_caller)]
+    pub(crate) fn disposed(&self) -> ! {
+        match crate::debug::origin(*self) {
+            Some(origin) => {
+                panic!("{self:?} was used after it was disposed, it was created as {origin}")
+            }
+            None => panic!("{self:?} was used after it was disposed"),
+        }
+    }
+
     /// Try to set the Signal to be linking with this Id
     pub(crate) fn add_signal(&self, signal: Signal) {
         RUNTIME.with(|runtime| runtime.signals.borrow_mut().insert(*self, signal));
@@ -37,6 +56,10 @@ impl Id {
     /// and grandchildren.
     pub(crate) fn dispose(&self) {
         if let Ok((children, signal)) = RUNTIME.try_with(|runtime| {
+            runtime.remove_idle_effect(*self);
+            runtime.reentrant_effects.borrow_mut().remove(self);
+            #[cfg(feature = "track-panic")]
+            runtime.origins.borrow_mut().remove(self);
             (
                 runtime.children.borrow_mut().remove(self),
                 runtime.signals.borrow_mut().remove(self),
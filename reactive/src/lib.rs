@@ -5,7 +5,9 @@
 //! and [`RwSignal::write_only`](RwSignal::write_only) where necessary, but the reverse is not possible.
 
 mod base;
+mod collections;
 mod context;
+pub mod debug;
 mod derived;
 mod effect;
 mod id;
@@ -15,16 +17,29 @@ mod read;
 mod runtime;
 mod scope;
 mod signal;
+mod store;
+mod transaction;
 mod trigger;
+mod undo;
 mod write;
 
 pub use base::{create_base_signal, BaseSignal};
+pub use collections::{
+    create_signal_map, create_signal_vec, MapDelta, RwSignalMap, RwSignalVec, VecDelta,
+};
 pub use context::{provide_context, use_context};
 pub use derived::{create_derived_rw_signal, DerivedRwSignal};
-pub use effect::{batch, create_effect, create_stateful_updater, create_updater, untrack};
-pub use memo::{create_memo, Memo};
+pub use effect::{
+    batch, create_effect, create_effect_with_priority, create_idle_effect, create_stateful_updater,
+    create_updater, create_watch, has_idle_effects, run_idle_effects, untrack, EffectPriority,
+};
+pub use id::Id;
+pub use memo::{create_memo, create_memo_with_eq, Memo};
 pub use read::{ReadSignalValue, SignalGet, SignalRead, SignalTrack, SignalWith};
 pub use scope::{as_child_of_current_scope, with_scope, Scope};
 pub use signal::{create_rw_signal, create_signal, ReadSignal, RwSignal, WriteSignal};
+pub use store::{create_store, Store};
+pub use transaction::{transaction, Transaction};
 pub use trigger::{create_trigger, Trigger};
+pub use undo::{create_undoable_signal, UndoableSignal};
 pub use write::{SignalUpdate, SignalWrite, WriteSignalValue};
@@ -46,9 +46,26 @@ impl<T> SignalTrack<T> for Memo<T> {
 
 /// Create a Memo which takes the computed value of the given function, and triggers
 /// the reactive system when the computed value is different with the last computed value.
+#[cfg_attr(feature = "track-panic", track_caller)]
 pub fn create_memo<T>(f: impl Fn(Option<&T>) -> T + 'static) -> Memo<T>
 where
     T: PartialEq + 'static,
+{
+    create_memo_with_eq(f, T::eq)
+}
+
+/// Create a Memo which triggers the reactive system only when `eq` returns `false` for
+/// the last and the newly computed value.
+///
+/// Use it for values without a [`PartialEq`] implementation, or to ignore changes which
+/// don't matter downstream, e.g. comparing only the ids of the items of a filtered list.
+#[cfg_attr(feature = "track-panic", track_caller)]
+pub fn create_memo_with_eq<T>(
+    f: impl Fn(Option<&T>) -> T + 'static,
+    eq: impl Fn(&T, &T) -> bool + 'static,
+) -> Memo<T>
+where
+    T: 'static,
 {
     let cx = Scope::current();
     let initial = f(None);
@@ -60,7 +77,7 @@ where
         let (is_different, new_value) = {
             let last_value = reader.borrow();
             let new_value = f(Some(&last_value));
-            (new_value != *last_value, new_value)
+            (!eq(&last_value, &new_value), new_value)
         };
         if is_different {
             setter.set(new_value);
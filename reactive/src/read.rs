@@ -25,20 +25,22 @@ pub trait SignalGet<T: Clone> {
 
     /// Clones and returns the current value stored in the Signal, but it doesn't subscribe
     /// to the current running effect.
+    #[cfg_attr(feature = "track-panic", track_caller)]
     fn get_untracked(&self) -> T
     where
         T: 'static,
     {
-        self.try_get_untracked().unwrap()
+        self.id().signal_or_disposed().get_untracked()
     }
 
     /// Clones and returns the current value stored in the Signal, and subscribes
     /// to the current running effect to this Signal.
+    #[cfg_attr(feature = "track-panic", track_caller)]
     fn get(&self) -> T
     where
         T: 'static,
     {
-        self.try_get().unwrap()
+        self.id().signal_or_disposed().get()
     }
 
     /// Try to clone and return the current value stored in the Signal, and returns None
@@ -64,8 +66,9 @@ pub trait SignalTrack<T> {
     fn id(&self) -> Id;
     /// Only subscribes to the current running effect to this Signal.
     ///
+    #[cfg_attr(feature = "track-panic", track_caller)]
     fn track(&self) {
-        self.id().signal().unwrap().subscribe();
+        self.id().signal_or_disposed().subscribe();
     }
 
     /// If the signal isn't disposed,
@@ -83,20 +86,22 @@ pub trait SignalWith<T> {
 
     /// Applies a closure to the current value stored in the Signal, and subscribes
     /// to the current running effect to this Memo.
+    #[cfg_attr(feature = "track-panic", track_caller)]
     fn with<O>(&self, f: impl FnOnce(&T) -> O) -> O
     where
         T: 'static,
     {
-        self.id().signal().unwrap().with(f)
+        self.id().signal_or_disposed().with(f)
     }
 
     /// Applies a closure to the current value stored in the Signal, but it doesn't subscribe
     /// to the current running effect.
+    #[cfg_attr(feature = "track-panic", track_caller)]
     fn with_untracked<O>(&self, f: impl FnOnce(&T) -> O) -> O
     where
         T: 'static,
     {
-        self.id().signal().unwrap().with_untracked(f)
+        self.id().signal_or_disposed().with_untracked(f)
     }
 
     /// If the signal isn't disposed, applies a closure to the current value stored in the Signal.
@@ -133,21 +138,29 @@ pub trait SignalRead<T> {
     /// Reads the data stored in the Signal to a RefCell, so that you can `borrow()`
     /// and access the data.
     /// It subscribes to the current running effect.
+    #[cfg_attr(feature = "track-panic", track_caller)]
     fn read(&self) -> ReadSignalValue<T>
     where
         T: 'static,
     {
-        self.try_read().unwrap()
+        match self.try_read() {
+            Some(value) => value,
+            None => self.id().disposed(),
+        }
     }
 
     /// Reads the data stored in the Signal to a RefCell, so that you can `borrow()`
     /// and access the data.
     /// It doesn't subscribe to the current running effect.
+    #[cfg_attr(feature = "track-panic", track_caller)]
     fn read_untracked(&self) -> ReadSignalValue<T>
     where
         T: 'static,
     {
-        self.try_read_untracked().unwrap()
+        match self.try_read_untracked() {
+            Some(value) => value,
+            None => self.id().disposed(),
+        }
     }
 
     /// If the signal isn't disposed,
@@ -1,16 +1,17 @@
 use std::{
     any::{Any, TypeId},
     cell::{Cell, RefCell},
-    collections::{HashMap, HashSet},
+    collections::{HashMap, HashSet, VecDeque},
     rc::Rc,
 };
 
 use smallvec::SmallVec;
 
 use crate::{
-    effect::{run_effect, EffectTrait},
+    effect::{run_effect, EffectPriority, EffectTrait},
     id::Id,
     signal::Signal,
+    transaction::TransactionLog,
 };
 
 thread_local! {
@@ -27,6 +28,14 @@ pub(crate) struct Runtime {
     pub(crate) contexts: RefCell<HashMap<TypeId, Box<dyn Any>>>,
     pub(crate) batching: Cell<bool>,
     pub(crate) pending_effects: RefCell<SmallVec<[Rc<dyn EffectTrait>; 10]>>,
+    pub(crate) idle_effects: RefCell<VecDeque<Rc<dyn EffectTrait>>>,
+    /// The effects which are running, the innermost last
+    pub(crate) running_effects: RefCell<Vec<Id>>,
+    pub(crate) reentrant_effects: RefCell<HashMap<Id, usize>>,
+    /// The running transactions, the innermost last
+    pub(crate) transactions: RefCell<Vec<TransactionLog>>,
+    #[cfg(feature = "track-panic")]
+    pub(crate) origins: RefCell<HashMap<Id, crate::debug::Origin>>,
 }
 
 impl Default for Runtime {
@@ -45,6 +54,12 @@ impl Runtime {
             contexts: Default::default(),
             batching: Cell::new(false),
             pending_effects: RefCell::new(SmallVec::new()),
+            idle_effects: RefCell::new(VecDeque::new()),
+            running_effects: RefCell::new(Vec::new()),
+            reentrant_effects: Default::default(),
+            transactions: Default::default(),
+            #[cfg(feature = "track-panic")]
+            origins: Default::default(),
         }
     }
 
@@ -61,8 +76,66 @@ impl Runtime {
 
     pub(crate) fn run_pending_effects(&self) {
         let pending_effects = self.pending_effects.take();
-        for effect in pending_effects {
-            run_effect(effect);
+        self.run_effects(pending_effects);
+    }
+
+    /// Runs the effects in the order of their priority, and queues the idle ones.
+    pub(crate) fn run_effects(&self, effects: impl IntoIterator<Item = Rc<dyn EffectTrait>>) {
+        let mut effects: SmallVec<[_; 10]> = effects.into_iter().collect();
+        effects.sort_by_key(|effect| effect.priority());
+        for effect in effects {
+            if effect.priority() == EffectPriority::Idle {
+                self.add_idle_effect(effect);
+            } else {
+                run_effect(effect);
+            }
+        }
+    }
+
+    pub(crate) fn enter_effect(&self, id: Id) {
+        /// An effect running this often inside itself never settles
+        #[cfg(feature = "track-panic")]
+        const MAX_REENTRANCY: usize = 100;
+
+        let mut running_effects = self.running_effects.borrow_mut();
+        let reentrancy = running_effects.iter().filter(|e| **e == id).count();
+        running_effects.push(id);
+        drop(running_effects);
+        if reentrancy > 0 {
+            *self.reentrant_effects.borrow_mut().entry(id).or_default() += 1;
+            #[cfg(feature = "track-panic")]
+            if reentrancy >= MAX_REENTRANCY {
+                let origin = crate::debug::origin(id)
+                    .map(|origin| format!(" created as {origin}"))
+                    .unwrap_or_default();
+                panic!("effect {id:?}{origin} keeps triggering itself");
+            }
         }
     }
+
+    pub(crate) fn exit_effect(&self) {
+        self.running_effects.borrow_mut().pop();
+    }
+
+    pub(crate) fn add_idle_effect(&self, effect: Rc<dyn EffectTrait>) {
+        let mut idle_effects = self.idle_effects.borrow_mut();
+        if !idle_effects.iter().any(|e| e.id() == effect.id()) {
+            idle_effects.push_back(effect);
+        }
+    }
+
+    pub(crate) fn pop_idle_effect(&self) -> Option<Rc<dyn EffectTrait>> {
+        self.idle_effects.borrow_mut().pop_front()
+    }
+
+    /// Forgets a queued idle effect whose scope was disposed.
+    pub(crate) fn remove_idle_effect(&self, id: Id) {
+        let removed = {
+            let mut idle_effects = self.idle_effects.borrow_mut();
+            let index = idle_effects.iter().position(|effect| effect.id() == id);
+            index.and_then(|index| idle_effects.remove(index))
+        };
+        // Dropping the effect disposes it again, which needs the queue
+        drop(removed);
+    }
 }
@@ -1,9 +1,12 @@
-use std::{any::Any, cell::RefCell, collections::HashMap, fmt, marker::PhantomData, rc::Rc};
+use std::{
+    any::Any, cell::RefCell, collections::HashMap, fmt, hash::Hash, marker::PhantomData, rc::Rc,
+};
 
 use crate::{
-    create_effect, create_updater,
+    collections::{create_signal_map, create_signal_vec, RwSignalMap, RwSignalVec},
+    create_effect, create_idle_effect, create_updater, create_watch,
     id::Id,
-    memo::{create_memo, Memo},
+    memo::{create_memo, create_memo_with_eq, Memo},
     runtime::RUNTIME,
     signal::{
         create_rw_signal, create_signal, NotThreadSafe, ReadSignal, RwSignal, Signal, WriteSignal,
@@ -39,6 +42,11 @@ impl Scope {
         Self(Id::next(), PhantomData)
     }
 
+    /// The id of the scope in [`debug::reactive_graph`](crate::debug::reactive_graph)
+    pub fn id(&self) -> Id {
+        self.0
+    }
+
     /// The current Scope in the Runtime. Any Signal/Effect/Memo created with
     /// implicitly Scope will be under this Scope
     pub fn current() -> Scope {
@@ -46,8 +54,11 @@ impl Scope {
     }
 
     /// Create a child Scope of this Scope
+    #[cfg_attr(feature = "track-panic", track_caller)]
     pub fn create_child(&self) -> Scope {
         let child = Id::next();
+        #[cfg(feature = "track-panic")]
+        crate::debug::record_origin(child, crate::debug::NodeKind::Scope, None);
         RUNTIME.with(|runtime| {
             let mut children = runtime.children.borrow_mut();
             let children = children.entry(self.0).or_default();
@@ -80,6 +91,38 @@ impl Scope {
         with_scope(self, || create_memo(f))
     }
 
+    /// Create a Memo with a custom equality under this Scope, see [`create_memo_with_eq`]
+    pub fn create_memo_with_eq<T>(
+        self,
+        f: impl Fn(Option<&T>) -> T + 'static,
+        eq: impl Fn(&T, &T) -> bool + 'static,
+    ) -> Memo<T>
+    where
+        T: 'static,
+    {
+        with_scope(self, || create_memo_with_eq(f, eq))
+    }
+
+    /// Create a [`RwSignalVec`] under this Scope
+    pub fn create_signal_vec<T>(self, values: Vec<T>) -> RwSignalVec<T>
+    where
+        T: Clone + 'static,
+    {
+        with_scope(self, || create_signal_vec(values))
+    }
+
+    /// Create a [`RwSignalMap`] under this Scope
+    pub fn create_signal_map<K, V>(
+        self,
+        entries: impl IntoIterator<Item = (K, V)>,
+    ) -> RwSignalMap<K, V>
+    where
+        K: Eq + Hash + Clone + 'static,
+        V: Clone + 'static,
+    {
+        with_scope(self, || create_signal_map(entries))
+    }
+
     /// Create a Trigger under this Scope
     pub fn create_trigger(self) -> Trigger {
         with_scope(self, create_trigger)
@@ -93,6 +136,26 @@ impl Scope {
         with_scope(self, || create_effect(f))
     }
 
+    /// Create an idle effect under this Scope, see [`create_idle_effect`]
+    pub fn create_idle_effect<T>(self, f: impl Fn(Option<T>) -> T + 'static)
+    where
+        T: Any + 'static,
+    {
+        with_scope(self, || create_idle_effect(f))
+    }
+
+    /// Create a watch under this Scope, see [`create_watch`]
+    pub fn create_watch<T>(
+        self,
+        source: impl Fn() -> T + 'static,
+        callback: impl Fn(Option<&T>, &T) + 'static,
+        immediate: bool,
+    ) where
+        T: Any + 'static,
+    {
+        with_scope(self, || create_watch(source, callback, immediate))
+    }
+
     /// Create updater under this Scope
     pub fn create_updater<R>(
         self,
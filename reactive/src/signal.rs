@@ -8,10 +8,11 @@ use std::{
 };
 
 use crate::{
-    effect::{run_effect, EffectTrait},
+    effect::EffectTrait,
     id::Id,
     read::{SignalRead, SignalTrack, SignalWith},
     runtime::RUNTIME,
+    transaction::record_previous_value,
     write::SignalWrite,
     SignalGet, SignalUpdate,
 };
@@ -74,9 +75,11 @@ impl<T> RwSignal<T> {
 }
 
 impl<T: 'static> RwSignal<T> {
+    #[cfg_attr(feature = "track-panic", track_caller)]
     pub fn new(value: T) -> Self {
         create_rw_signal(value)
     }
+    #[cfg_attr(feature = "track-panic", track_caller)]
     pub fn new_split(value: T) -> (ReadSignal<T>, WriteSignal<T>) {
         let sig = Self::new(value);
         (sig.read_only(), sig.write_only())
@@ -88,6 +91,7 @@ impl<T: 'static> RwSignal<T> {
 /// Accessing the signal value in an Effect will make the Effect subscribe
 /// to the value change of the Signal. And whenever the signal value changes,
 /// it will trigger an effect run.
+#[cfg_attr(feature = "track-panic", track_caller)]
 pub fn create_rw_signal<T>(value: T) -> RwSignal<T>
 where
     T: Any + 'static,
@@ -152,6 +156,7 @@ impl<T> PartialEq for WriteSignal<T> {
 /// Accessing the signal value in an Effect will make the Effect subscribe
 /// to the value change of the Signal. And whenever the signal value changes,
 /// it will trigger an effect run.
+#[cfg_attr(feature = "track-panic", track_caller)]
 pub fn create_signal<T>(value: T) -> (ReadSignal<T>, WriteSignal<T>)
 where
     T: Any + 'static,
@@ -170,11 +175,18 @@ pub(crate) struct Signal {
 }
 
 impl Signal {
+    #[cfg_attr(feature = "track-panic", track_caller)]
     pub fn create<T>(value: T) -> Id
     where
         T: Any + 'static,
     {
         let id = Id::next();
+        #[cfg(feature = "track-panic")]
+        crate::debug::record_origin(
+            id,
+            crate::debug::NodeKind::Signal,
+            Some(std::any::type_name::<T>()),
+        );
         let value = RefCell::new(value);
         let signal = Signal {
             id,
@@ -224,6 +236,19 @@ impl Signal {
         result
     }
 
+    /// Replaces the value, remembering the previous one if a transaction is running.
+    pub(crate) fn set_value<T: 'static>(&self, new_value: T) {
+        let previous = {
+            let value = self
+                .value
+                .downcast_ref::<RefCell<T>>()
+                .expect("to downcast signal type");
+            std::mem::replace(&mut *value.borrow_mut(), new_value)
+        };
+        record_previous_value(self, previous);
+        self.run_effects();
+    }
+
     pub(crate) fn subscribers(&self) -> HashMap<Id, Rc<dyn EffectTrait>> {
         self.subscribers.borrow().clone()
     }
@@ -239,9 +264,7 @@ impl Signal {
             return;
         }
 
-        for (_, subscriber) in self.subscribers() {
-            run_effect(subscriber);
-        }
+        RUNTIME.with(|r| r.run_effects(self.subscribers().into_values()));
     }
 
     pub(crate) fn subscribe(&self) {
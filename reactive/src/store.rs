@@ -0,0 +1,190 @@
+use crate::{scope::Scope, with_scope};
+
+/// A plain struct which can be turned into a struct of signals, one per field, so that
+/// readers only subscribe to the fields they use.
+///
+/// Implement it with [`store!`](crate::store!), which defines the struct together with
+/// its store, the struct of signals.
+pub trait Store: Sized + 'static {
+    /// The signals of the fields, with the store of nested stores.
+    type Fields: Copy + 'static;
+
+    /// Creates the signals with the values of the fields.
+    fn into_fields(self) -> Self::Fields;
+
+    /// Reads every field, subscribing to all of them.
+    fn get_fields(fields: &Self::Fields) -> Self;
+
+    /// Reads every field without subscribing to them.
+    fn get_fields_untracked(fields: &Self::Fields) -> Self;
+
+    /// Sets every field in one batch.
+    fn set_fields(self, fields: &Self::Fields);
+}
+
+/// Create the signals of the store `T` with the given value.
+pub fn create_store<T: Store>(value: T) -> T::Fields {
+    value.into_fields()
+}
+
+impl Scope {
+    /// Create the signals of a [`Store`] under this Scope
+    pub fn create_store<T: Store>(self, value: T) -> T::Fields {
+        with_scope(self, || create_store(value))
+    }
+}
+
+/// Defines a struct together with its store, a struct with a signal for every field,
+/// and implements [`Store`] for it.
+///
+/// The store is named after the `=>`. Fields whose type is prefixed with `store` are
+/// nested stores, their field in the store is the store of that type instead of a
+/// signal, so `settings.editor.font_size` is a signal of its own. The other field types
+/// have to implement `Clone`.
+///
+/// The store is `Copy` and has `get`, `get_untracked` and `set` methods to read or write
+/// all fields at once, e.g. to load or save the settings.
+///
+/// ## Example
+/// ```
+/// use floem_reactive::*;
+///
+/// store! {
+///     #[derive(Clone, Debug, PartialEq)]
+///     pub struct EditorSettings => EditorSettingsStore {
+///         pub font_size: u32,
+///         pub line_numbers: bool,
+///     }
+/// }
+///
+/// store! {
+///     #[derive(Clone, Debug, PartialEq)]
+///     pub struct Settings => SettingsStore {
+///         pub theme: String,
+///         pub editor: store EditorSettings,
+///     }
+/// }
+///
+/// let settings = create_store(Settings {
+///     theme: "dark".to_string(),
+///     editor: EditorSettings {
+///         font_size: 12,
+///         line_numbers: true,
+///     },
+/// });
+///
+/// settings.editor.font_size.set(14);
+/// assert_eq!(settings.get_untracked().editor.font_size, 14);
+/// ```
+#[macro_export]
+macro_rules! store {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident => $store:ident {
+            $($body:tt)*
+        }
+    ) => {
+        $crate::store!(@fields [$(#[$meta])* $vis $name $store] [] $($body)*);
+    };
+
+    // Collect the fields as `(kind [attributes] vis name type)`
+    (@fields $head:tt [$($fields:tt)*]) => {
+        $crate::store!(@define $head $($fields)*);
+    };
+    (@fields $head:tt [$($fields:tt)*]
+        $(#[$field_meta:meta])* $field_vis:vis $field:ident: store $ty:ty $(, $($rest:tt)*)?
+    ) => {
+        $crate::store!(@fields $head
+            [$($fields)* (nested [$(#[$field_meta])*] $field_vis $field $ty)]
+            $($($rest)*)?
+        );
+    };
+    (@fields $head:tt [$($fields:tt)*]
+        $(#[$field_meta:meta])* $field_vis:vis $field:ident: $ty:ty $(, $($rest:tt)*)?
+    ) => {
+        $crate::store!(@fields $head
+            [$($fields)* (signal [$(#[$field_meta])*] $field_vis $field $ty)]
+            $($($rest)*)?
+        );
+    };
+
+    (@define [$(#[$meta:meta])* $vis:vis $name:ident $store:ident]
+        $(($kind:ident [$($field_meta:tt)*] $field_vis:vis $field:ident $ty:ty))*
+    ) => {
+        $(#[$meta])*
+        $vis struct $name {
+            $($($field_meta)* $field_vis $field: $ty,)*
+        }
+
+        #[derive(Clone, Copy)]
+        #[allow(missing_docs)]
+        $vis struct $store {
+            $($field_vis $field: $crate::store!(@type $kind $ty),)*
+        }
+
+        impl $crate::Store for $name {
+            type Fields = $store;
+
+            fn into_fields(self) -> $store {
+                $store {
+                    $($field: $crate::store!(@create $kind self.$field),)*
+                }
+            }
+
+            fn get_fields(fields: &$store) -> Self {
+                Self {
+                    $($field: $crate::store!(@get $kind $ty, fields.$field),)*
+                }
+            }
+
+            fn get_fields_untracked(fields: &$store) -> Self {
+                Self {
+                    $($field: $crate::store!(@get_untracked $kind $ty, fields.$field),)*
+                }
+            }
+
+            fn set_fields(self, fields: &$store) {
+                $crate::batch(|| {
+                    $($crate::store!(@set $kind self.$field, fields.$field);)*
+                });
+            }
+        }
+
+        #[allow(dead_code)]
+        impl $store {
+            /// Reads every field, subscribing to all of them.
+            $vis fn get(&self) -> $name {
+                <$name as $crate::Store>::get_fields(self)
+            }
+
+            /// Reads every field without subscribing to them.
+            $vis fn get_untracked(&self) -> $name {
+                <$name as $crate::Store>::get_fields_untracked(self)
+            }
+
+            /// Sets every field in one batch.
+            $vis fn set(&self, value: $name) {
+                <$name as $crate::Store>::set_fields(value, self)
+            }
+        }
+    };
+
+    (@type signal $ty:ty) => { $crate::RwSignal<$ty> };
+    (@type nested $ty:ty) => { <$ty as $crate::Store>::Fields };
+
+    (@create signal $value:expr) => { $crate::create_rw_signal($value) };
+    (@create nested $value:expr) => { $crate::Store::into_fields($value) };
+
+    (@get signal $ty:ty, $fields:expr) => { $crate::SignalGet::get(&$fields) };
+    (@get nested $ty:ty, $fields:expr) => { <$ty as $crate::Store>::get_fields(&$fields) };
+
+    (@get_untracked signal $ty:ty, $fields:expr) => {
+        $crate::SignalGet::get_untracked(&$fields)
+    };
+    (@get_untracked nested $ty:ty, $fields:expr) => {
+        <$ty as $crate::Store>::get_fields_untracked(&$fields)
+    };
+
+    (@set signal $value:expr, $fields:expr) => { $crate::SignalUpdate::set(&$fields, $value) };
+    (@set nested $value:expr, $fields:expr) => { $crate::Store::set_fields($value, &$fields) };
+}
@@ -0,0 +1,172 @@
+use std::{any::Any, cell::RefCell, collections::HashSet, marker::PhantomData, rc::Rc};
+
+use crate::{
+    effect::batch,
+    id::Id,
+    runtime::RUNTIME,
+    signal::{NotThreadSafe, Signal},
+    write::SignalUpdate,
+};
+
+/// The previous values of the signals changed in a running transaction.
+#[derive(Default)]
+pub(crate) struct TransactionLog {
+    ids: HashSet<Id>,
+    /// Restores the value of a signal, applied in reverse order on rollback
+    restore: Vec<(Id, Box<dyn FnOnce()>)>,
+}
+
+impl TransactionLog {
+    /// Returns the restore function back if the signal already was recorded, since only
+    /// the value from before the first change needs to be restored.
+    fn record(&mut self, id: Id, restore: Box<dyn FnOnce()>) -> Option<Box<dyn FnOnce()>> {
+        if self.ids.insert(id) {
+            self.restore.push((id, restore));
+            None
+        } else {
+            Some(restore)
+        }
+    }
+}
+
+/// Pushes the log of a transaction and pops it when dropped, so that a transaction which
+/// panics doesn't keep recording the changes made after it.
+struct LogGuard;
+
+impl LogGuard {
+    fn push() -> Self {
+        RUNTIME.with(|runtime| {
+            runtime
+                .transactions
+                .borrow_mut()
+                .push(TransactionLog::default())
+        });
+        LogGuard
+    }
+
+    fn pop(self) -> TransactionLog {
+        std::mem::forget(self);
+        RUNTIME
+            .with(|runtime| runtime.transactions.borrow_mut().pop())
+            .unwrap_or_default()
+    }
+}
+
+impl Drop for LogGuard {
+    fn drop(&mut self) {
+        // Dropped outside of the borrow, in case the values use the runtime when dropped
+        let log = RUNTIME.try_with(|runtime| runtime.transactions.borrow_mut().pop());
+        drop(log);
+    }
+}
+
+/// A running [`transaction`], used to snapshot signals which are changed in place.
+pub struct Transaction {
+    ts: PhantomData<NotThreadSafe>,
+}
+
+impl Transaction {
+    /// Remembers the current value of the signal, to restore it if the transaction is
+    /// rolled back.
+    ///
+    /// Signals changed with `set` are remembered automatically, but the ones changed with
+    /// `update`, `try_update` or `write` have to be snapshotted before.
+    pub fn snapshot<T: Clone + 'static>(&self, signal: &impl SignalUpdate<T>) {
+        if let Some(signal) = signal.id().signal() {
+            let value = signal.borrow::<T>().clone();
+            record_previous_value(&signal, value);
+        }
+    }
+
+    /// Snapshots the signal and updates it.
+    pub fn update<T: Clone + 'static>(
+        &self,
+        signal: &impl SignalUpdate<T>,
+        f: impl FnOnce(&mut T),
+    ) {
+        self.snapshot(signal);
+        signal.update(f);
+    }
+}
+
+/// Runs the closure in a [`batch`], and restores the previous values of the signals it
+/// changed if it returns an error.
+///
+/// The closure can validate the changes as it goes and bail out in the middle of an
+/// operation spanning many signals, without leaving them half updated. Effects only run
+/// once the outermost batch ends, so they never see the values which are rolled back.
+///
+/// Transactions can be nested: rolling back an inner transaction only restores the
+/// signals it changed, and the changes of a committed inner transaction are rolled back
+/// with the outer one.
+///
+/// ```
+/// # use floem_reactive::{create_rw_signal, transaction, SignalGet, SignalUpdate};
+/// let start = create_rw_signal(2);
+/// let end = create_rw_signal(5);
+///
+/// let result = transaction(|_| {
+///     start.set(7);
+///     if start.get_untracked() > end.get_untracked() {
+///         return Err("the range is empty");
+///     }
+///     end.set(10);
+///     Ok(())
+/// });
+/// assert!(result.is_err());
+/// assert_eq!(start.get_untracked(), 2);
+/// ```
+pub fn transaction<T, E>(f: impl FnOnce(&Transaction) -> Result<T, E>) -> Result<T, E> {
+    batch(|| {
+        let guard = LogGuard::push();
+        let result = f(&Transaction { ts: PhantomData });
+        let log = guard.pop();
+        match &result {
+            Ok(_) => {
+                let unused: Vec<_> =
+                    RUNTIME.with(
+                        |runtime| match runtime.transactions.borrow_mut().last_mut() {
+                            Some(outer) => log
+                                .restore
+                                .into_iter()
+                                .filter_map(|(id, restore)| outer.record(id, restore))
+                                .collect(),
+                            None => Vec::new(),
+                        },
+                    );
+                drop(unused);
+            }
+            Err(_) => {
+                for (id, restore) in log.restore.into_iter().rev() {
+                    restore();
+                    if let Some(signal) = id.signal() {
+                        signal.run_effects();
+                    }
+                }
+            }
+        }
+        result
+    })
+}
+
+/// Remembers the value a signal had before it was changed, if a transaction is running.
+pub(crate) fn record_previous_value<T: 'static>(signal: &Signal, previous: T) {
+    let unused = RUNTIME.with(|runtime| {
+        let mut transactions = runtime.transactions.borrow_mut();
+        match transactions.last_mut() {
+            Some(log) if !log.ids.contains(&signal.id) => {
+                let value: Rc<dyn Any> = signal.value.clone();
+                let restore = Box::new(move || {
+                    if let Some(value) = value.downcast_ref::<RefCell<T>>() {
+                        *value.borrow_mut() = previous;
+                    }
+                });
+                log.record(signal.id, restore);
+                None
+            }
+            _ => Some(previous),
+        }
+    });
+    // Dropped outside of the borrow, in case the value uses the runtime when dropped
+    drop(unused);
+}
@@ -26,11 +26,13 @@ impl Trigger {
     }
 
     #[allow(clippy::new_without_default)]
+    #[cfg_attr(feature = "track-panic", track_caller)]
     pub fn new() -> Self {
         create_trigger()
     }
 }
 
+#[cfg_attr(feature = "track-panic", track_caller)]
 pub fn create_trigger() -> Trigger {
     Trigger {
         signal: create_rw_signal(()),
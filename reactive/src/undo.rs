@@ -0,0 +1,191 @@
+use crate::{
+    batch,
+    id::Id,
+    memo::{create_memo, Memo},
+    read::{SignalGet, SignalRead, SignalTrack, SignalWith},
+    signal::{create_rw_signal, RwSignal},
+    write::SignalUpdate,
+};
+
+struct History<T> {
+    /// The values before each change, the last one is restored first
+    undo: Vec<T>,
+    redo: Vec<T>,
+    /// The coalescing key of the last change, if it can still be merged with the next one
+    coalesce: Option<&'static str>,
+    limit: usize,
+}
+
+/// A signal which records its previous values, so that changes can be undone and redone.
+///
+/// Reading works like with any other signal. Writing goes through the methods of the
+/// signal, which record the value before the change as one undo step:
+/// - [`set`](Self::set) and [`update`](Self::update) add a step for every change.
+/// - [`set_coalesced`](Self::set_coalesced) and [`update_coalesced`](Self::update_coalesced)
+///   merge consecutive changes with the same key into one step, e.g. the moves of a drag
+///   or the steps of a slider. [`end_coalescing`](Self::end_coalescing) ends a group early,
+///   e.g. when the drag ends.
+///
+/// Making a new change after undoing discards the redo steps.
+pub struct UndoableSignal<T: 'static> {
+    value: RwSignal<T>,
+    history: RwSignal<History<T>>,
+    can_undo: Memo<bool>,
+    can_redo: Memo<bool>,
+}
+
+impl<T> Copy for UndoableSignal<T> {}
+
+impl<T> Clone for UndoableSignal<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: Clone + 'static> UndoableSignal<T> {
+    /// Sets the value as a new undo step.
+    pub fn set(&self, value: T) {
+        self.update(|v| *v = value);
+    }
+
+    /// Updates the value as a new undo step.
+    pub fn update(&self, f: impl FnOnce(&mut T)) {
+        self.record(None, f);
+    }
+
+    /// Sets the value, merging the change into the last undo step if that was made with
+    /// the same `key`.
+    pub fn set_coalesced(&self, key: &'static str, value: T) {
+        self.update_coalesced(key, |v| *v = value);
+    }
+
+    /// Updates the value, merging the change into the last undo step if that was made
+    /// with the same `key`.
+    pub fn update_coalesced(&self, key: &'static str, f: impl FnOnce(&mut T)) {
+        self.record(Some(key), f);
+    }
+
+    /// Makes the next change a new undo step, even if it has the same coalescing key.
+    pub fn end_coalescing(&self) {
+        self.history.update(|history| history.coalesce = None);
+    }
+
+    fn record(&self, key: Option<&'static str>, f: impl FnOnce(&mut T)) {
+        let prev = self.value.get_untracked();
+        batch(|| {
+            self.history.update(|history| {
+                history.redo.clear();
+                if key.is_none() || history.coalesce != key || history.undo.is_empty() {
+                    history.undo.push(prev);
+                    if history.undo.len() > history.limit {
+                        history.undo.remove(0);
+                    }
+                }
+                history.coalesce = key;
+            });
+            self.value.update(f);
+        });
+    }
+
+    /// Restores the value before the last change. Returns `false` if there is nothing to
+    /// undo.
+    pub fn undo(&self) -> bool {
+        self.step(|history| (&mut history.undo, &mut history.redo))
+    }
+
+    /// Restores the value of the last undone change. Returns `false` if there is nothing
+    /// to redo.
+    pub fn redo(&self) -> bool {
+        self.step(|history| (&mut history.redo, &mut history.undo))
+    }
+
+    /// Moves the current value to one stack and restores the last value of the other.
+    fn step(&self, stacks: impl Fn(&mut History<T>) -> (&mut Vec<T>, &mut Vec<T>)) -> bool {
+        let mut restored = None;
+        batch(|| {
+            let current = self.value.get_untracked();
+            self.history.update(|history| {
+                history.coalesce = None;
+                let (from, to) = stacks(history);
+                restored = from.pop();
+                if restored.is_some() {
+                    to.push(current);
+                }
+            });
+            if let Some(value) = restored.clone() {
+                self.value.set(value);
+            }
+        });
+        restored.is_some()
+    }
+
+    /// A signal which is `true` when there is a change to undo, e.g. to enable an undo
+    /// button.
+    pub fn can_undo(&self) -> Memo<bool> {
+        self.can_undo
+    }
+
+    /// A signal which is `true` when there is an undone change to redo.
+    pub fn can_redo(&self) -> Memo<bool> {
+        self.can_redo
+    }
+
+    /// Forgets all undo and redo steps, e.g. after saving or loading a document.
+    pub fn clear_history(&self) {
+        self.history.update(|history| {
+            history.undo.clear();
+            history.redo.clear();
+            history.coalesce = None;
+        });
+    }
+
+    /// Sets the maximum number of undo steps, the oldest steps are dropped first. Defaults
+    /// to 100.
+    pub fn set_history_limit(&self, limit: usize) {
+        self.history.update(|history| {
+            history.limit = limit;
+            let excess = history.undo.len().saturating_sub(limit);
+            history.undo.drain(..excess);
+        });
+    }
+}
+
+impl<T: Clone> SignalGet<T> for UndoableSignal<T> {
+    fn id(&self) -> Id {
+        self.value.id
+    }
+}
+
+impl<T> SignalWith<T> for UndoableSignal<T> {
+    fn id(&self) -> Id {
+        self.value.id
+    }
+}
+
+impl<T> SignalTrack<T> for UndoableSignal<T> {
+    fn id(&self) -> Id {
+        self.value.id
+    }
+}
+
+impl<T> SignalRead<T> for UndoableSignal<T> {
+    fn id(&self) -> Id {
+        self.value.id
+    }
+}
+
+/// Create an [`UndoableSignal`] with the given initial value and no history.
+pub fn create_undoable_signal<T: Clone + 'static>(value: T) -> UndoableSignal<T> {
+    let history = create_rw_signal(History {
+        undo: Vec::new(),
+        redo: Vec::new(),
+        coalesce: None,
+        limit: 100,
+    });
+    UndoableSignal {
+        value: create_rw_signal(value),
+        history,
+        can_undo: create_memo(move |_| history.with(|history| !history.undo.is_empty())),
+        can_redo: create_memo(move |_| history.with(|history| !history.redo.is_empty())),
+    }
+}
@@ -38,7 +38,7 @@ pub trait SignalUpdate<T> {
         T: 'static,
     {
         if let Some(signal) = self.id().signal() {
-            signal.update_value(|v| *v = new_value);
+            signal.set_value(new_value);
         }
     }
 
@@ -69,11 +69,15 @@ pub trait SignalWrite<T> {
     /// original data of the signal, so that you can `borrow_mut()` to update the data.
     ///
     /// When `WriteSignalValue` drops, it triggers effect run
+    #[cfg_attr(feature = "track-panic", track_caller)]
     fn write(&self) -> WriteSignalValue<T>
     where
         T: 'static,
     {
-        self.try_write().unwrap()
+        match self.try_write() {
+            Some(value) => value,
+            None => self.id().disposed(),
+        }
     }
 
     /// If the Signal isn't disposed,
@@ -0,0 +1,80 @@
+use floem_reactive::{
+    create_effect, create_rw_signal,
+    debug::{reactive_graph, NodeKind, ScopeLeakDetector},
+    with_scope, Scope, SignalGet, SignalUpdate,
+};
+
+#[test]
+fn graph_snapshot() {
+    let scope = Scope::new();
+    let (signal, _) = with_scope(scope, || {
+        let signal = create_rw_signal(0);
+        let doubled = create_rw_signal(0);
+        create_effect(move |_| doubled.set(signal.get() * 2));
+        (signal, doubled)
+    });
+
+    let graph = reactive_graph();
+    let node = &graph.nodes[&scope_id(scope)];
+    assert_eq!(node.kind, NodeKind::Scope);
+    assert_eq!(node.children.len(), 3);
+    let signal_node = &graph.nodes[&SignalGet::id(&signal)];
+    assert_eq!(signal_node.kind, NodeKind::Signal);
+    assert_eq!(signal_node.scope, Some(scope_id(scope)));
+    assert_eq!(signal_node.subscribers.len(), 1);
+    assert_eq!(
+        graph.nodes[&signal_node.subscribers[0]].kind,
+        NodeKind::Effect
+    );
+    assert!(graph.to_string().contains("Signal"));
+
+    scope.dispose();
+    let graph = reactive_graph();
+    assert!(!graph.nodes.contains_key(&SignalGet::id(&signal)));
+}
+
+#[test]
+fn reentrant_effect() {
+    let scope = Scope::new();
+    let count = create_rw_signal(0);
+    with_scope(scope, || {
+        create_effect(move |_| {
+            let value = count.get();
+            if value < 3 {
+                count.set(value + 1);
+            }
+        })
+    });
+    assert_eq!(count.get_untracked(), 3);
+    let graph = reactive_graph();
+    assert_eq!(graph.reentrant_effects.values().sum::<usize>(), 3);
+
+    // Disposed effects are forgotten
+    scope.dispose();
+    assert!(reactive_graph().reentrant_effects.is_empty());
+}
+
+#[test]
+fn scope_leak_detector() {
+    let leaking = Scope::new();
+    let disposed = Scope::new();
+    let mut detector = ScopeLeakDetector::new(3);
+    for _ in 0..5 {
+        assert!(detector
+            .check()
+            .iter()
+            .all(|growth| growth.scope != scope_id(disposed)));
+        with_scope(leaking, || create_rw_signal(0));
+        let child = disposed.create_child();
+        with_scope(child, || create_rw_signal(0));
+        child.dispose();
+    }
+    let growing = detector.check();
+    assert!(growing
+        .iter()
+        .any(|growth| growth.scope == scope_id(leaking) && growth.children == 5));
+}
+
+fn scope_id(scope: Scope) -> floem_reactive::Id {
+    scope.id()
+}
@@ -1,6 +1,13 @@
-use std::{cell::Cell, rc::Rc};
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
 
-use floem_reactive::{batch, create_effect, create_rw_signal, SignalTrack, SignalUpdate};
+use floem_reactive::{
+    batch, create_effect, create_effect_with_priority, create_idle_effect, create_rw_signal,
+    create_watch, has_idle_effects, run_idle_effects, with_scope, EffectPriority, Scope, SignalGet,
+    SignalTrack, SignalUpdate,
+};
 
 #[test]
 fn batch_simple() {
@@ -68,3 +75,123 @@ fn batch_batch() {
 
     assert_eq!(count.get(), 2);
 }
+
+#[test]
+fn effect_priority_order() {
+    let value = create_rw_signal(0);
+    let order = Rc::new(RefCell::new(Vec::new()));
+
+    for priority in [
+        EffectPriority::Layout,
+        EffectPriority::Input,
+        EffectPriority::Animation,
+    ] {
+        create_effect_with_priority(priority, {
+            let order = order.clone();
+            move |_| {
+                value.track();
+                order.borrow_mut().push(priority);
+            }
+        });
+    }
+
+    order.borrow_mut().clear();
+    value.set(1);
+    assert_eq!(
+        *order.borrow(),
+        vec![
+            EffectPriority::Input,
+            EffectPriority::Animation,
+            EffectPriority::Layout
+        ]
+    );
+}
+
+#[test]
+fn idle_effect() {
+    let value = create_rw_signal(0);
+    let seen = Rc::new(RefCell::new(Vec::new()));
+
+    create_idle_effect({
+        let seen = seen.clone();
+        move |_| seen.borrow_mut().push(value.get())
+    });
+    // Doesn't run before the application is idle
+    assert!(seen.borrow().is_empty());
+    assert!(has_idle_effects());
+
+    assert!(!run_idle_effects(|| false));
+    assert_eq!(*seen.borrow(), vec![0]);
+
+    // Changes are coalesced into one run
+    value.set(1);
+    value.set(2);
+    assert_eq!(*seen.borrow(), vec![0]);
+    run_idle_effects(|| false);
+    assert_eq!(*seen.borrow(), vec![0, 2]);
+    assert!(!has_idle_effects());
+}
+
+#[test]
+fn idle_effect_disposed() {
+    let value = create_rw_signal(0);
+    let count = Rc::new(Cell::new(0));
+
+    let scope = Scope::new();
+    with_scope(scope, || {
+        create_idle_effect({
+            let count = count.clone();
+            move |_| {
+                value.track();
+                count.set(count.get() + 1);
+            }
+        });
+    });
+    scope.dispose();
+
+    run_idle_effects(|| false);
+    assert_eq!(count.get(), 0);
+}
+
+#[test]
+fn watch_previous_value() {
+    let value = create_rw_signal(1);
+    let other = create_rw_signal(0);
+    let seen = Rc::new(RefCell::new(Vec::new()));
+
+    create_watch(
+        move || value.get(),
+        {
+            let seen = seen.clone();
+            move |prev, next| {
+                // Reading in the callback doesn't subscribe
+                other.get();
+                seen.borrow_mut().push((prev.copied(), *next));
+            }
+        },
+        false,
+    );
+    assert!(seen.borrow().is_empty());
+
+    value.set(2);
+    other.set(1);
+    value.set(3);
+    assert_eq!(*seen.borrow(), vec![(Some(1), 2), (Some(2), 3)]);
+}
+
+#[test]
+fn watch_immediate() {
+    let value = create_rw_signal(1);
+    let seen = Rc::new(RefCell::new(Vec::new()));
+
+    create_watch(
+        move || value.get(),
+        {
+            let seen = seen.clone();
+            move |prev, next| seen.borrow_mut().push((prev.copied(), *next))
+        },
+        true,
+    );
+    value.set(2);
+    assert_eq!(*seen.borrow(), vec![(None, 1), (Some(1), 2)]);
+}
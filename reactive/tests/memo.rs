@@ -0,0 +1,58 @@
+use std::{cell::Cell, rc::Rc};
+
+use floem_reactive::{
+    create_effect, create_memo, create_memo_with_eq, create_rw_signal, SignalGet, SignalUpdate,
+    SignalWith,
+};
+
+#[test]
+fn memo_only_notifies_on_change() {
+    let value = create_rw_signal(1);
+    let is_even = create_memo(move |_| value.get() % 2 == 0);
+
+    let count = Rc::new(Cell::new(0));
+    create_effect({
+        let count = count.clone();
+        move |_| {
+            is_even.get();
+            count.set(count.get() + 1);
+        }
+    });
+    assert_eq!(count.get(), 1);
+
+    // Still odd
+    value.set(3);
+    assert_eq!(count.get(), 1);
+
+    value.set(4);
+    assert_eq!(count.get(), 2);
+    assert!(is_even.get_untracked());
+}
+
+#[test]
+fn memo_with_custom_eq() {
+    let items = create_rw_signal(vec![(1, "one"), (2, "two")]);
+    // Only the ids matter downstream
+    let filtered = create_memo_with_eq(
+        move |_| items.get(),
+        |a, b| a.iter().map(|i| i.0).eq(b.iter().map(|i| i.0)),
+    );
+
+    let count = Rc::new(Cell::new(0));
+    create_effect({
+        let count = count.clone();
+        move |_| {
+            filtered.with(|_| {});
+            count.set(count.get() + 1);
+        }
+    });
+    assert_eq!(count.get(), 1);
+
+    items.set(vec![(1, "uno"), (2, "dos")]);
+    assert_eq!(count.get(), 1);
+    // The memo keeps the value it considers equal
+    assert_eq!(filtered.get_untracked()[0].1, "one");
+
+    items.set(vec![(1, "uno")]);
+    assert_eq!(count.get(), 2);
+}
@@ -0,0 +1,89 @@
+use std::{cell::RefCell, rc::Rc};
+
+use floem_reactive::{
+    batch, create_effect, create_signal_map, create_signal_vec, MapDelta, VecDelta,
+};
+
+#[test]
+fn signal_vec_records_deltas() {
+    let list = create_signal_vec(vec![1, 2, 3]);
+    let seen = Rc::new(RefCell::new(Vec::new()));
+    create_effect({
+        let seen = seen.clone();
+        move |version: Option<u64>| {
+            if let Some(deltas) = version.and_then(|version| list.deltas_since(version)) {
+                seen.borrow_mut().extend(deltas);
+            } else {
+                list.track();
+            }
+            list.version()
+        }
+    });
+
+    list.push(4);
+    list.remove(0);
+    list.move_item(0, 2);
+    list.set(1, 10);
+    assert_eq!(list.get_untracked(), vec![3, 10, 2]);
+    assert_eq!(
+        *seen.borrow(),
+        vec![
+            VecDelta::Insert { index: 3, value: 4 },
+            VecDelta::Remove { index: 0 },
+            VecDelta::Move { from: 0, to: 2 },
+            VecDelta::Set {
+                index: 1,
+                value: 10
+            },
+        ]
+    );
+
+    seen.borrow_mut().clear();
+    batch(|| {
+        list.pop();
+        list.clear();
+    });
+    assert_eq!(
+        *seen.borrow(),
+        vec![VecDelta::Remove { index: 2 }, VecDelta::Clear]
+    );
+}
+
+#[test]
+fn signal_vec_resets_when_behind() {
+    let list = create_signal_vec(Vec::new());
+    let version = list.version();
+    for i in 0..1000 {
+        list.push(i);
+    }
+    assert_eq!(list.deltas_since(version), None);
+    assert_eq!(list.deltas_since(list.version()), Some(Vec::new()));
+}
+
+#[test]
+fn signal_map_keeps_insertion_order() {
+    let map = create_signal_map([("a", 1), ("b", 2)]);
+    let version = map.version();
+    map.insert("c", 3);
+    assert_eq!(map.insert("a", 10), Some(1));
+    assert_eq!(map.remove(&"b"), Some(2));
+    assert_eq!(map.remove(&"b"), None);
+    assert_eq!(map.get(&"c"), Some(3));
+    map.with_untracked(|entries| assert_eq!(entries, &[("a", 10), ("c", 3)]));
+    assert_eq!(
+        map.deltas_since(version).unwrap(),
+        vec![
+            MapDelta::Insert {
+                index: 2,
+                key: "c",
+                value: 3
+            },
+            MapDelta::Set {
+                index: 0,
+                key: "a",
+                value: 10
+            },
+            MapDelta::Remove { index: 1, key: "b" },
+        ]
+    );
+}
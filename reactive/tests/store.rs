@@ -0,0 +1,84 @@
+use std::{cell::Cell, rc::Rc};
+
+use floem_reactive::{create_effect, create_store, store, SignalGet, SignalUpdate};
+
+store! {
+    #[derive(Clone, Debug, PartialEq)]
+    struct EditorSettings => EditorSettingsStore {
+        font_size: u32,
+        /// Doc comments stay on the field
+        line_numbers: bool,
+    }
+}
+
+store! {
+    #[derive(Clone, Debug, PartialEq)]
+    struct Settings => SettingsStore {
+        theme: String,
+        editor: store EditorSettings,
+    }
+}
+
+fn settings() -> Settings {
+    Settings {
+        theme: "dark".to_string(),
+        editor: EditorSettings {
+            font_size: 12,
+            line_numbers: true,
+        },
+    }
+}
+
+#[test]
+fn store_field_subscriptions() {
+    let settings = create_store(settings());
+
+    let font_size_runs = Rc::new(Cell::new(0));
+    create_effect({
+        let font_size_runs = font_size_runs.clone();
+        move |_| {
+            settings.editor.font_size.get();
+            font_size_runs.set(font_size_runs.get() + 1);
+        }
+    });
+    let all_runs = Rc::new(Cell::new(0));
+    create_effect({
+        let all_runs = all_runs.clone();
+        move |_| {
+            settings.get();
+            all_runs.set(all_runs.get() + 1);
+        }
+    });
+
+    settings.theme.set("light".to_string());
+    assert_eq!(font_size_runs.get(), 1);
+    assert_eq!(all_runs.get(), 2);
+
+    settings.editor.font_size.set(14);
+    assert_eq!(font_size_runs.get(), 2);
+    assert_eq!(all_runs.get(), 3);
+    assert_eq!(settings.editor.font_size.get_untracked(), 14);
+}
+
+#[test]
+fn store_set_all_fields() {
+    let settings = create_store(settings());
+
+    let runs = Rc::new(Cell::new(0));
+    create_effect({
+        let runs = runs.clone();
+        move |_| {
+            settings.get();
+            runs.set(runs.get() + 1);
+        }
+    });
+
+    let mut value = settings.get_untracked();
+    value.editor.line_numbers = false;
+    value.theme = "light".to_string();
+    settings.set(value.clone());
+    // Batched into one run
+    assert_eq!(runs.get(), 2);
+    assert_eq!(settings.get_untracked(), value);
+    assert!(!settings.editor.line_numbers.get_untracked());
+}
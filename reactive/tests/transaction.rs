@@ -0,0 +1,79 @@
+use std::{cell::Cell, rc::Rc};
+
+use floem_reactive::{
+    create_effect, create_rw_signal, transaction, SignalGet, SignalTrack, SignalUpdate,
+};
+
+#[test]
+fn rollback_restores_values() {
+    let name = create_rw_signal(String::from("a"));
+    let items = create_rw_signal(vec![1, 2]);
+    let runs = Rc::new(Cell::new(0));
+    create_effect({
+        let runs = runs.clone();
+        move |_| {
+            name.track();
+            items.track();
+            runs.set(runs.get() + 1);
+        }
+    });
+
+    let result: Result<(), &str> = transaction(|tx| {
+        name.set(String::from("b"));
+        name.set(String::from("c"));
+        tx.update(&items, |items| items.push(3));
+        Err("invalid")
+    });
+    assert_eq!(result, Err("invalid"));
+    assert_eq!(name.get_untracked(), "a");
+    assert_eq!(items.get_untracked(), vec![1, 2]);
+    // The effects ran once after the batch, with the restored values
+    assert_eq!(runs.get(), 2);
+
+    let result: Result<u32, ()> = transaction(|_| {
+        name.set(String::from("d"));
+        Ok(1)
+    });
+    assert_eq!(result, Ok(1));
+    assert_eq!(name.get_untracked(), "d");
+}
+
+#[test]
+fn nested_transactions() {
+    let value = create_rw_signal(0);
+    let other = create_rw_signal(0);
+
+    let result: Result<(), ()> = transaction(|_| {
+        value.set(1);
+        let inner: Result<(), ()> = transaction(|_| {
+            value.set(2);
+            other.set(2);
+            Err(())
+        });
+        assert!(inner.is_err());
+        assert_eq!(value.get_untracked(), 1);
+        assert_eq!(other.get_untracked(), 0);
+
+        let inner: Result<(), ()> = transaction(|_| {
+            other.set(3);
+            Ok(())
+        });
+        assert!(inner.is_ok());
+        Err(())
+    });
+    assert!(result.is_err());
+    assert_eq!(value.get_untracked(), 0);
+    assert_eq!(other.get_untracked(), 0);
+}
+
+#[test]
+fn panicking_transaction() {
+    let value = create_rw_signal(Rc::new(()));
+    let first = value.get_untracked();
+    let result = std::panic::catch_unwind(|| transaction::<(), ()>(|_| panic!("failed")));
+    assert!(result.is_err());
+
+    // The changes made after the transaction aren't recorded by it
+    value.set(Rc::new(()));
+    assert_eq!(Rc::strong_count(&first), 1);
+}
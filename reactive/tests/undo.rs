@@ -0,0 +1,60 @@
+use floem_reactive::{create_undoable_signal, SignalGet};
+
+#[test]
+fn undo_redo() {
+    let value = create_undoable_signal(0);
+    assert!(!value.can_undo().get_untracked());
+
+    value.set(1);
+    value.set(2);
+    assert!(value.can_undo().get_untracked());
+
+    assert!(value.undo());
+    assert_eq!(value.get_untracked(), 1);
+    assert!(value.can_redo().get_untracked());
+
+    assert!(value.undo());
+    assert_eq!(value.get_untracked(), 0);
+    assert!(!value.undo());
+
+    assert!(value.redo());
+    assert_eq!(value.get_untracked(), 1);
+
+    // A new change discards the redo steps
+    value.set(5);
+    assert!(!value.can_redo().get_untracked());
+    assert!(!value.redo());
+    assert!(value.undo());
+    assert_eq!(value.get_untracked(), 1);
+}
+
+#[test]
+fn undo_coalesced() {
+    let value = create_undoable_signal(0);
+    for i in 1..=10 {
+        value.set_coalesced("drag", i);
+    }
+    value.end_coalescing();
+    value.set_coalesced("drag", 20);
+    value.update(|v| *v += 1);
+
+    assert_eq!(value.get_untracked(), 21);
+    value.undo();
+    assert_eq!(value.get_untracked(), 20);
+    value.undo();
+    assert_eq!(value.get_untracked(), 10);
+    value.undo();
+    assert_eq!(value.get_untracked(), 0);
+    assert!(!value.can_undo().get_untracked());
+}
+
+#[test]
+fn undo_history_limit() {
+    let value = create_undoable_signal(0);
+    for i in 1..=10 {
+        value.set(i);
+    }
+    value.set_history_limit(3);
+    while value.undo() {}
+    assert_eq!(value.get_untracked(), 7);
+}
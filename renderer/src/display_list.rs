@@ -0,0 +1,245 @@
+//! Recording of draw commands, to implement renderers outside of floem.
+//!
+//! A [`DisplayList`] is a [`Renderer`] which keeps the draw commands instead of drawing
+//! them, so they can be turned into e.g. a PDF or SVG document, or sent to be rendered
+//! remotely. It can be replayed onto any other renderer.
+
+use std::sync::Arc;
+
+use cosmic_text::LayoutGlyph;
+use peniko::{
+    kurbo::{Affine, BezPath, Point, Rect, Shape, Size, Stroke},
+    Brush, BrushRef,
+};
+
+use crate::{text::LayoutRun, usvg, Img, Renderer, Svg};
+
+/// The tolerance used to turn shapes into paths.
+const PATH_TOLERANCE: f64 = 0.1;
+
+/// A draw command recorded by a [`DisplayList`].
+#[derive(Clone, Debug)]
+pub enum DrawCommand {
+    /// Sets the transform of the following commands.
+    Transform(Affine),
+    SetZIndex(i32),
+    /// Clips the following commands to a path, in the current transform.
+    Clip(BezPath),
+    ClearClip,
+    Stroke {
+        path: BezPath,
+        brush: Brush,
+        stroke: Stroke,
+    },
+    /// Fills a path using the non-zero fill rule.
+    Fill {
+        path: BezPath,
+        brush: Brush,
+        blur_radius: f64,
+    },
+    /// Draws text, with `pos` as the upper-left corner of the layout.
+    Text {
+        runs: Vec<TextRun>,
+        pos: Point,
+    },
+    Svg {
+        tree: Arc<usvg::Tree>,
+        hash: Vec<u8>,
+        rect: Rect,
+        brush: Option<Brush>,
+    },
+    Image {
+        image: peniko::Image,
+        hash: Vec<u8>,
+        rect: Rect,
+    },
+}
+
+/// An owned [`LayoutRun`], a line of shaped text.
+#[derive(Clone, Debug)]
+pub struct TextRun {
+    /// The index of the original text line
+    pub line_i: usize,
+    /// The original text line
+    pub text: String,
+    /// True if the original paragraph direction is RTL
+    pub rtl: bool,
+    /// The glyphs to draw, with their fonts and colors
+    pub glyphs: Vec<LayoutGlyph>,
+    pub max_ascent: f32,
+    pub max_descent: f32,
+    /// Y offset to baseline of line
+    pub line_y: f32,
+    /// Y offset to top of line
+    pub line_top: f32,
+    pub line_height: f32,
+    pub line_w: f32,
+}
+
+impl TextRun {
+    pub fn new(run: &LayoutRun) -> Self {
+        Self {
+            line_i: run.line_i,
+            text: run.text.to_string(),
+            rtl: run.rtl,
+            glyphs: run.glyphs.to_vec(),
+            max_ascent: run.max_ascent,
+            max_descent: run.max_descent,
+            line_y: run.line_y,
+            line_top: run.line_top,
+            line_height: run.line_height,
+            line_w: run.line_w,
+        }
+    }
+
+    pub fn layout_run(&self) -> LayoutRun<'_> {
+        LayoutRun {
+            line_i: self.line_i,
+            text: &self.text,
+            rtl: self.rtl,
+            glyphs: &self.glyphs,
+            max_ascent: self.max_ascent,
+            max_descent: self.max_descent,
+            line_y: self.line_y,
+            line_top: self.line_top,
+            line_height: self.line_height,
+            line_w: self.line_w,
+        }
+    }
+}
+
+/// A renderer recording the draw commands of a frame, in logical coordinates.
+#[derive(Clone, Debug, Default)]
+pub struct DisplayList {
+    /// The size of the recorded area
+    pub size: Size,
+    pub commands: Vec<DrawCommand>,
+}
+
+impl DisplayList {
+    pub fn new(size: Size) -> Self {
+        Self {
+            size,
+            commands: Vec::new(),
+        }
+    }
+
+    /// Draws the recorded commands with another renderer, between its `begin` and `finish`.
+    pub fn replay(&self, renderer: &mut impl Renderer) {
+        for command in &self.commands {
+            match command {
+                DrawCommand::Transform(transform) => renderer.transform(*transform),
+                DrawCommand::SetZIndex(z_index) => renderer.set_z_index(*z_index),
+                DrawCommand::Clip(path) => renderer.clip(path),
+                DrawCommand::ClearClip => renderer.clear_clip(),
+                DrawCommand::Stroke {
+                    path,
+                    brush,
+                    stroke,
+                } => renderer.stroke(path, brush, stroke),
+                DrawCommand::Fill {
+                    path,
+                    brush,
+                    blur_radius,
+                } => renderer.fill(path, brush, *blur_radius),
+                DrawCommand::Text { runs, pos } => {
+                    renderer.draw_text_with_layout(runs.iter().map(TextRun::layout_run), *pos)
+                }
+                DrawCommand::Svg {
+                    tree,
+                    hash,
+                    rect,
+                    brush,
+                } => renderer.draw_svg(Svg { tree, hash }, *rect, brush.as_ref()),
+                DrawCommand::Image { image, hash, rect } => renderer.draw_img(
+                    Img {
+                        img: image.clone(),
+                        hash,
+                    },
+                    *rect,
+                ),
+            }
+        }
+    }
+}
+
+impl Renderer for DisplayList {
+    fn begin(&mut self, _capture: bool) {
+        self.commands.clear();
+    }
+
+    fn transform(&mut self, transform: Affine) {
+        self.commands.push(DrawCommand::Transform(transform));
+    }
+
+    fn set_z_index(&mut self, z_index: i32) {
+        self.commands.push(DrawCommand::SetZIndex(z_index));
+    }
+
+    fn clip(&mut self, shape: &impl Shape) {
+        self.commands
+            .push(DrawCommand::Clip(shape.to_path(PATH_TOLERANCE)));
+    }
+
+    fn clear_clip(&mut self) {
+        self.commands.push(DrawCommand::ClearClip);
+    }
+
+    fn stroke<'b, 's>(
+        &mut self,
+        shape: &impl Shape,
+        brush: impl Into<BrushRef<'b>>,
+        stroke: &'s Stroke,
+    ) {
+        let brush: BrushRef<'b> = brush.into();
+        self.commands.push(DrawCommand::Stroke {
+            path: shape.to_path(PATH_TOLERANCE),
+            brush: brush.to_owned(),
+            stroke: stroke.clone(),
+        });
+    }
+
+    fn fill<'b>(&mut self, path: &impl Shape, brush: impl Into<BrushRef<'b>>, blur_radius: f64) {
+        let brush: BrushRef<'b> = brush.into();
+        self.commands.push(DrawCommand::Fill {
+            path: path.to_path(PATH_TOLERANCE),
+            brush: brush.to_owned(),
+            blur_radius,
+        });
+    }
+
+    fn draw_text_with_layout<'b>(
+        &mut self,
+        layout: impl Iterator<Item = LayoutRun<'b>>,
+        pos: impl Into<Point>,
+    ) {
+        self.commands.push(DrawCommand::Text {
+            runs: layout.map(|run| TextRun::new(&run)).collect(),
+            pos: pos.into(),
+        });
+    }
+
+    fn draw_svg<'b>(&mut self, svg: Svg<'b>, rect: Rect, brush: Option<impl Into<BrushRef<'b>>>) {
+        self.commands.push(DrawCommand::Svg {
+            tree: Arc::new(svg.tree.clone()),
+            hash: svg.hash.to_vec(),
+            rect,
+            brush: brush.map(|brush| {
+                let brush: BrushRef<'b> = brush.into();
+                brush.to_owned()
+            }),
+        });
+    }
+
+    fn draw_img(&mut self, img: Img<'_>, rect: Rect) {
+        self.commands.push(DrawCommand::Image {
+            image: img.img,
+            hash: img.hash.to_vec(),
+            rect,
+        });
+    }
+
+    fn finish(&mut self) -> Option<peniko::Image> {
+        None
+    }
+}
@@ -1,3 +1,4 @@
+pub mod display_list;
 pub mod swash;
 pub mod text;
 
@@ -25,6 +26,15 @@ pub struct Img<'a> {
 pub trait Renderer {
     fn begin(&mut self, capture: bool);
 
+    /// Begins a frame which only repaints `damage`, keeping the rest of the previous frame.
+    /// Painting outside of `damage` has to be clipped by the caller.
+    ///
+    /// Returns `false` without beginning a frame if the renderer can't keep the previous
+    /// frame, in which case the whole frame is painted after [`Renderer::begin`].
+    fn begin_partial(&mut self, _damage: Rect) -> bool {
+        false
+    }
+
     fn transform(&mut self, transform: Affine);
 
     fn set_z_index(&mut self, z_index: i32);
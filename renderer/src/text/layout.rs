@@ -1,4 +1,9 @@
-use std::{ops::Range, sync::LazyLock};
+use std::{cell::Cell, ops::Range, sync::LazyLock};
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::{Duration, Instant};
+#[cfg(target_arch = "wasm32")]
+use web_time::{Duration, Instant};
 
 use crate::text::AttrsList;
 use cosmic_text::{
@@ -19,6 +24,22 @@ pub static FONT_SYSTEM: LazyLock<Mutex<FontSystem>> = LazyLock::new(|| {
     Mutex::new(font_system)
 });
 
+thread_local! {
+    static SHAPING_TIME: Cell<Duration> = const { Cell::new(Duration::ZERO) };
+}
+
+/// Returns the time spent shaping and laying out text on this thread since the last call.
+pub fn take_shaping_time() -> Duration {
+    SHAPING_TIME.with(|time| time.take())
+}
+
+fn shaping<R>(f: impl FnOnce(&mut FontSystem) -> R) -> R {
+    let start = Instant::now();
+    let result = f(&mut FONT_SYSTEM.lock());
+    SHAPING_TIME.with(|time| time.set(time.get() + start.elapsed()));
+    result
+}
+
 /// A line of visible text for rendering
 #[derive(Debug)]
 pub struct LayoutRun<'a> {
@@ -255,13 +276,11 @@ impl TextLayout {
             self.lines_range.push(0..0)
         }
         self.buffer.set_scroll(Scroll::default());
-        let mut font_system = FONT_SYSTEM.lock();
-        self.buffer.shape_until_scroll(&mut font_system, false);
+        shaping(|font_system| self.buffer.shape_until_scroll(font_system, false));
     }
 
     pub fn set_wrap(&mut self, wrap: Wrap) {
-        let mut font_system = FONT_SYSTEM.lock();
-        self.buffer.set_wrap(&mut font_system, wrap);
+        shaping(|font_system| self.buffer.set_wrap(font_system, wrap));
     }
 
     pub fn set_tab_width(&mut self, tab_width: usize) {
@@ -271,11 +290,9 @@ impl TextLayout {
     }
 
     pub fn set_size(&mut self, width: f32, height: f32) {
-        let mut font_system = FONT_SYSTEM.lock();
         self.width_opt = Some(width);
         self.height_opt = Some(height);
-        self.buffer
-            .set_size(&mut font_system, Some(width), Some(height));
+        shaping(|font_system| self.buffer.set_size(font_system, Some(width), Some(height)));
     }
 
     pub fn metrics(&self) -> Metrics {
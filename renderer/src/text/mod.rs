@@ -3,7 +3,7 @@ mod layout;
 
 pub use attrs::{Attrs, AttrsList, AttrsOwned, FamilyOwned, LineHeightValue};
 pub use cosmic_text::{
-    fontdb, CacheKey, Cursor, Family, LayoutGlyph, LayoutLine, LineEnding, Stretch, Style,
-    SubpixelBin, SwashCache, SwashContent, Weight, Wrap,
+    fontdb, CacheKey, CacheKeyFlags, Cursor, Family, LayoutGlyph, LayoutLine, LineEnding, Stretch,
+    Style, SubpixelBin, SwashCache, SwashContent, Weight, Wrap,
 };
-pub use layout::{HitPoint, HitPosition, LayoutRun, TextLayout, FONT_SYSTEM};
+pub use layout::{take_shaping_time, HitPoint, HitPosition, LayoutRun, TextLayout, FONT_SYSTEM};
@@ -0,0 +1,304 @@
+//! Exposes the view tree to assistive technology through [AccessKit](accesskit).
+//!
+//! Every view becomes a node of the accessibility tree. Views describe themselves with
+//! [`View::accessibility`](crate::View::accessibility), and the description can be
+//! overridden from the outside with the `accessibility_*` methods of
+//! [`Decorators`](crate::views::Decorators), whose values follow signals.
+//!
+//! Actions requested by assistive technology are delivered to the target view as
+//! [`Event::AccessibilityAction`](crate::event::Event::AccessibilityAction). Focus
+//! requests focus the view, and unhandled click requests run its click handlers.
+//!
+//! The tree is only sent to the platform with the `accesskit` feature enabled, and only
+//! while an assistive technology is listening.
+
+use accesskit::{Live, Node, NodeId, Role, Toggled, Tree, TreeUpdate};
+use peniko::kurbo::Rect;
+use slotmap::{Key, KeyData};
+
+use crate::{app_state::AppState, id::ViewId};
+
+/// The accessibility properties of a view set through
+/// [`Decorators`](crate::views::Decorators), which take precedence over the ones the view
+/// sets itself.
+#[derive(Default, Clone)]
+pub(crate) struct AccessibilityProps {
+    pub(crate) role: Option<Role>,
+    pub(crate) label: Option<String>,
+    pub(crate) value: Option<String>,
+    pub(crate) toggled: Option<Toggled>,
+    pub(crate) live: Option<Live>,
+}
+
+impl AccessibilityProps {
+    fn apply(&self, node: &mut Node) {
+        if let Some(role) = self.role {
+            node.set_role(role);
+        }
+        if let Some(label) = &self.label {
+            node.set_label(label.clone());
+        }
+        if let Some(value) = &self.value {
+            node.set_value(value.clone());
+        }
+        if let Some(toggled) = self.toggled {
+            node.set_toggled(toggled);
+        }
+        if let Some(live) = self.live {
+            node.set_live(live);
+        }
+    }
+}
+
+/// Roles whose label is taken from the text they contain if they don't set one.
+const LABEL_FROM_CONTENTS: &[Role] = &[
+    Role::Button,
+    Role::CheckBox,
+    Role::RadioButton,
+    Role::Switch,
+    Role::Tab,
+    Role::ListItem,
+    Role::Link,
+    Role::MenuItem,
+];
+
+pub(crate) fn node_id(id: ViewId) -> NodeId {
+    NodeId(id.data().as_ffi())
+}
+
+pub(crate) fn view_id(id: NodeId) -> ViewId {
+    ViewId::from(KeyData::from_ffi(id.0))
+}
+
+/// Builds the full accessibility tree of the window with the given root view.
+///
+/// `scale` converts from view coordinates to the physical pixels of the window.
+pub(crate) fn tree_update(root: ViewId, app_state: &AppState, scale: f64) -> TreeUpdate {
+    let mut nodes = Vec::new();
+    build_node(root, app_state, scale, &mut nodes);
+    let focus = app_state
+        .focus
+        .filter(|id| !id.is_hidden_recursive())
+        .unwrap_or(root);
+    TreeUpdate {
+        nodes,
+        tree: Some(Tree::new(node_id(root))),
+        focus: node_id(focus),
+    }
+}
+
+fn build_node(
+    id: ViewId,
+    app_state: &AppState,
+    scale: f64,
+    nodes: &mut Vec<(NodeId, Node)>,
+) -> NodeId {
+    let mut node = Node::new(Role::GenericContainer);
+    id.view().borrow().accessibility(&mut node);
+    id.state().borrow().accessibility.apply(&mut node);
+
+    let window_origin = id.state().borrow().window_origin;
+    let size = id.get_size().unwrap_or_default();
+    let bounds = Rect::from_origin_size(window_origin, size).scale_from_origin(scale);
+    node.set_bounds(accesskit::Rect {
+        x0: bounds.x0,
+        y0: bounds.y0,
+        x1: bounds.x1,
+        y1: bounds.y1,
+    });
+
+    if app_state.keyboard_navigable.contains(&id) {
+        node.add_action(accesskit::Action::Focus);
+    }
+    if app_state.is_disabled(&id) {
+        node.set_disabled();
+    }
+
+    let subtree_start = nodes.len();
+    let children: Vec<NodeId> = id
+        .children()
+        .into_iter()
+        .filter(|child| !child.style_has_hidden())
+        .map(|child| build_node(child, app_state, scale, nodes))
+        .collect();
+    node.set_children(children);
+
+    if node.label().is_none() && LABEL_FROM_CONTENTS.contains(&node.role()) {
+        let label = nodes[subtree_start..]
+            .iter()
+            .filter(|(_, node)| node.role() == Role::Label)
+            .filter_map(|(_, node)| node.label())
+            .collect::<Vec<_>>()
+            .join(" ");
+        if !label.is_empty() {
+            node.set_label(label);
+        }
+    }
+
+    let node_id = node_id(id);
+    nodes.push((node_id, node));
+    node_id
+}
+
+/// The text shown by the view and its descendants, as read by assistive technology.
+pub(crate) fn view_text(id: ViewId) -> String {
+    fn collect(id: ViewId, text: &mut Vec<String>) {
+        if let Some(label) = &id.state().borrow().accessibility.label {
+            text.push(label.clone());
+            return;
+        }
+        let mut node = Node::new(Role::GenericContainer);
+        id.view().borrow().accessibility(&mut node);
+        if let (Role::Label, Some(label)) = (node.role(), node.label()) {
+            text.push(label.to_string());
+        }
+        for child in id.children() {
+            if !child.style_has_hidden() {
+                collect(child, text);
+            }
+        }
+    }
+    let mut text = Vec::new();
+    collect(id, &mut text);
+    text.join(" ")
+}
+
+#[cfg(feature = "accesskit")]
+pub(crate) use adapter::AccessibilityAdapter;
+
+#[cfg(feature = "accesskit")]
+mod adapter {
+    use accesskit::{ActionHandler, ActionRequest, ActivationHandler, TreeUpdate};
+    use winit::window::{Window, WindowId};
+
+    use crate::{app::UserEvent, Application};
+
+    /// Asks the application to send the initial tree, since it's only accessible on
+    /// the UI thread.
+    struct Activation {
+        window_id: WindowId,
+    }
+
+    impl ActivationHandler for Activation {
+        fn request_initial_tree(&mut self) -> Option<TreeUpdate> {
+            Application::send_proxy_event(UserEvent::AccessibilityActivated {
+                window_id: self.window_id,
+            });
+            None
+        }
+    }
+
+    struct Actions {
+        window_id: WindowId,
+    }
+
+    impl ActionHandler for Actions {
+        fn do_action(&mut self, request: ActionRequest) {
+            Application::send_proxy_event(UserEvent::AccessibilityAction {
+                window_id: self.window_id,
+                request,
+            });
+        }
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "freebsd"))]
+    struct Deactivation;
+
+    #[cfg(any(target_os = "linux", target_os = "freebsd"))]
+    impl accesskit::DeactivationHandler for Deactivation {
+        fn deactivate_accessibility(&mut self) {}
+    }
+
+    /// The platform adapter of a window.
+    pub(crate) struct AccessibilityAdapter {
+        #[cfg(target_os = "windows")]
+        adapter: accesskit_windows::SubclassingAdapter,
+        #[cfg(target_os = "macos")]
+        adapter: accesskit_macos::SubclassingAdapter,
+        #[cfg(any(target_os = "linux", target_os = "freebsd"))]
+        adapter: accesskit_unix::Adapter,
+    }
+
+    impl AccessibilityAdapter {
+        #[allow(unused_variables)]
+        pub(crate) fn new(window_id: WindowId, window: &dyn Window) -> Option<Self> {
+            #[cfg(any(target_os = "windows", target_os = "macos"))]
+            use raw_window_handle::{HasWindowHandle, RawWindowHandle};
+
+            let activation = Activation { window_id };
+            let actions = Actions { window_id };
+
+            #[cfg(target_os = "windows")]
+            {
+                let RawWindowHandle::Win32(handle) = window.window_handle().ok()?.as_raw() else {
+                    return None;
+                };
+                let hwnd = accesskit_windows::HWND(isize::from(handle.hwnd) as _);
+                Some(Self {
+                    adapter: accesskit_windows::SubclassingAdapter::new(hwnd, activation, actions),
+                })
+            }
+
+            #[cfg(target_os = "macos")]
+            {
+                let RawWindowHandle::AppKit(handle) = window.window_handle().ok()?.as_raw() else {
+                    return None;
+                };
+                let adapter = unsafe {
+                    accesskit_macos::SubclassingAdapter::new(
+                        handle.ns_view.as_ptr(),
+                        activation,
+                        actions,
+                    )
+                };
+                Some(Self { adapter })
+            }
+
+            #[cfg(any(target_os = "linux", target_os = "freebsd"))]
+            {
+                Some(Self {
+                    adapter: accesskit_unix::Adapter::new(activation, actions, Deactivation),
+                })
+            }
+
+            #[cfg(not(any(
+                target_os = "windows",
+                target_os = "macos",
+                target_os = "linux",
+                target_os = "freebsd"
+            )))]
+            None
+        }
+
+        /// Sends a new tree, `tree` is only called if assistive technology is listening.
+        pub(crate) fn update_if_active(&mut self, tree: impl FnOnce() -> TreeUpdate) {
+            #[cfg(any(target_os = "windows", target_os = "macos"))]
+            if let Some(events) = self.adapter.update_if_active(tree) {
+                events.raise();
+            }
+            #[cfg(any(target_os = "linux", target_os = "freebsd"))]
+            self.adapter.update_if_active(tree);
+        }
+
+        pub(crate) fn focus_changed(&mut self, focused: bool) {
+            #[cfg(target_os = "windows")]
+            if let Some(events) = self.adapter.update_window_focus_state(focused) {
+                events.raise();
+            }
+            #[cfg(target_os = "macos")]
+            if let Some(events) = self.adapter.update_view_focus_state(focused) {
+                events.raise();
+            }
+            #[cfg(any(target_os = "linux", target_os = "freebsd"))]
+            self.adapter.update_window_focus_state(focused);
+        }
+
+        /// Tells the adapter where the window is, which only the unix adapter can't
+        /// find out by itself.
+        #[allow(unused_variables)]
+        pub(crate) fn set_window_bounds(&mut self, outer: accesskit::Rect, inner: accesskit::Rect) {
+            #[cfg(any(target_os = "linux", target_os = "freebsd"))]
+            self.adapter.set_root_window_bounds(outer, inner);
+        }
+    }
+}
@@ -6,10 +6,16 @@
 //!
 //! This includes, moving the window, resizing the window, adding context menus and overlays, and running a callback after a specified duration.
 
-use std::sync::atomic::AtomicU64;
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+    sync::atomic::AtomicU64,
+};
 
-use floem_reactive::SignalWith;
-use peniko::kurbo::{Point, Size, Vec2};
+use floem_reactive::{
+    create_effect, create_signal, ReadSignal, SignalGet, SignalUpdate, SignalWith, WriteSignal,
+};
+use peniko::kurbo::{Point, Rect, Size, Vec2};
 use winit::window::ResizeDirection;
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -51,6 +57,29 @@ pub fn minimize_window() {
     add_update_message(UpdateMessage::MinimizeWindow);
 }
 
+/// Request the window to be closed, as if the user clicked its close button
+///
+/// If the window was created with [`WindowConfig::hide_on_close`](crate::window::WindowConfig::hide_on_close),
+/// it is hidden instead.
+pub fn request_close_window() {
+    add_update_message(UpdateMessage::RequestCloseWindow);
+}
+
+/// Set the area of the custom maximize button of the window, in window coordinates, or remove it
+/// with `None`.
+///
+/// [`window_maximize_button`](crate::views::window_maximize_button) does this by itself, custom
+/// maximize buttons can use it to get the same platform integration.
+///
+/// ## Platform-specific
+///
+/// - **Windows:** The area is reported as the maximize button of the window, so that hovering it
+///   shows the snap layout flyout of Windows 11.
+/// - **Other platforms:** Unsupported.
+pub fn set_window_maximize_button_area(area: Option<Rect>) {
+    add_update_message(UpdateMessage::SetMaximizeButtonArea(area));
+}
+
 /// If and while the mouse is pressed, allow the window to be dragged
 pub fn drag_window() {
     add_update_message(UpdateMessage::DragWindow);
@@ -68,7 +97,8 @@ pub fn set_window_delta(delta: Vec2) {
 
 /// Set the window scale
 ///
-/// This will scale all view elements in the renderer
+/// This will scale all view elements in the renderer. The scale is multiplied by the
+/// [UI scale](crate::ui_scale) of the application, which applies to all windows.
 pub fn set_window_scale(window_scale: f64) {
     add_update_message(UpdateMessage::WindowScale(window_scale));
 }
@@ -78,6 +108,18 @@ pub fn inspect() {
     add_update_message(UpdateMessage::Inspect);
 }
 
+/// Show or hide the inspector overlay of this Window, which outlines the view under the
+/// cursor and shows its id, layout and style.
+pub fn toggle_inspector_overlay() {
+    add_update_message(UpdateMessage::ToggleInspectorOverlay);
+}
+
+/// Show or hide the frame profiler overlay of this Window, which shows the frame rate and
+/// the time spent on events, updates, layout, text shaping and painting.
+pub fn toggle_frame_profiler() {
+    add_update_message(UpdateMessage::ToggleFrameProfiler);
+}
+
 pub(crate) struct Timer {
     pub(crate) token: TimerToken,
     pub(crate) action: Box<dyn FnOnce(TimerToken)>,
@@ -95,7 +137,8 @@ impl TimerToken {
 
     /// Create a new token.
     pub fn next() -> TimerToken {
-        static TIMER_COUNTER: AtomicU64 = AtomicU64::new(0);
+        // Starts at 1 as 0 is `INVALID`
+        static TIMER_COUNTER: AtomicU64 = AtomicU64::new(1);
         TimerToken(TIMER_COUNTER.fetch_add(1, std::sync::atomic::Ordering::Relaxed))
     }
 
@@ -113,6 +156,12 @@ impl TimerToken {
     pub fn cancel(self) {
         add_app_update_event(AppUpdateEvent::CancelTimer { timer: self });
     }
+
+    fn cancel_if_valid(self) {
+        if self != TimerToken::INVALID {
+            self.cancel();
+        }
+    }
 }
 
 /// Execute a callback after a specified duration
@@ -174,6 +223,157 @@ where
     );
 }
 
+/// Create a signal which follows `signal`, but only once it hasn't changed for `duration`.
+///
+/// Useful for expensive work which should only run once the user stopped typing, e.g. a
+/// search as you type or an autosave.
+pub fn debounce<T: Clone + 'static>(
+    signal: impl SignalGet<T> + 'static,
+    duration: Duration,
+) -> ReadSignal<T> {
+    let (read, write) = create_signal(signal.get_untracked());
+    create_effect(move |prev_timer: Option<Option<TimerToken>>| {
+        let value = signal.get();
+        match prev_timer {
+            // The initial value is already set
+            None => None,
+            Some(prev_timer) => {
+                if let Some(timer) = prev_timer {
+                    timer.cancel();
+                }
+                Some(exec_after(duration, move |_| write.set(value)))
+            }
+        }
+    });
+    read
+}
+
+/// Create a signal which follows `signal`, but changes at most once per `duration`.
+///
+/// The first change is applied right away, and the latest of the changes made during the
+/// following `duration` is applied once it has passed, so the final value is never lost.
+pub fn throttle<T: Clone + 'static>(
+    signal: impl SignalGet<T> + 'static,
+    duration: Duration,
+) -> ReadSignal<T> {
+    let (read, write) = create_signal(signal.get_untracked());
+    let last_update: Rc<Cell<Option<Instant>>> = Rc::new(Cell::new(None));
+    let pending: Rc<RefCell<Option<T>>> = Rc::new(RefCell::new(None));
+    create_effect(move |first_run: Option<()>| {
+        let value = signal.get();
+        if first_run.is_none() {
+            // The initial value is already set
+            return;
+        }
+        let now = Instant::now();
+        match last_update.get() {
+            Some(last) if now < last + duration => {
+                // If a value was already pending, its timer applies the new value instead
+                let timer_scheduled = pending.borrow_mut().replace(value).is_some();
+                if !timer_scheduled {
+                    let last_update = last_update.clone();
+                    let pending = pending.clone();
+                    exec_after(last + duration - now, move |_| {
+                        let value = pending.borrow_mut().take();
+                        if let Some(value) = value {
+                            last_update.set(Some(Instant::now()));
+                            write.set(value);
+                        }
+                    });
+                }
+            }
+            _ => {
+                last_update.set(Some(now));
+                write.set(value);
+            }
+        }
+    });
+    read
+}
+
+/// Create a signal which follows `signal`, applying every change after `duration`.
+pub fn delay<T: Clone + 'static>(
+    signal: impl SignalGet<T> + 'static,
+    duration: Duration,
+) -> ReadSignal<T> {
+    let (read, write) = create_signal(signal.get_untracked());
+    create_effect(move |first_run: Option<()>| {
+        let value = signal.get();
+        // The initial value is already set
+        if first_run.is_some() {
+            exec_after(duration, move |_| write.set(value));
+        }
+    });
+    read
+}
+
+/// Create a signal which is set to the current time every `interval`, e.g. for a clock,
+/// polling or a blinking cursor.
+///
+/// The timer stops when the scope the signal was created in is disposed.
+pub fn create_interval(interval: Duration) -> ReadSignal<Instant> {
+    fn schedule(
+        deadline: Instant,
+        interval: Duration,
+        write: WriteSignal<Instant>,
+        timer: Rc<Cell<TimerToken>>,
+    ) {
+        let token = exec_after(
+            deadline.saturating_duration_since(Instant::now()),
+            move |_| {
+                let now = Instant::now();
+                if write.try_update(|tick| *tick = now).is_some() {
+                    // Skip the ticks which were missed, e.g. while the system was suspended
+                    let next = (deadline + interval).max(now);
+                    schedule(next, interval, write, timer);
+                }
+            },
+        );
+        timer.set(token);
+    }
+
+    let now = Instant::now();
+    let (read, write) = create_signal(now);
+    let timer = Rc::new(Cell::new(TimerToken::INVALID));
+    schedule(now + interval, interval, write, timer.clone());
+    cancel_on_dispose(timer);
+    read
+}
+
+/// Create a signal which turns `true` once `duration` has passed.
+///
+/// The timer is cancelled when the scope the signal was created in is disposed before.
+pub fn create_timeout(duration: Duration) -> ReadSignal<bool> {
+    let (read, write) = create_signal(false);
+    let timer = Rc::new(Cell::new(TimerToken::INVALID));
+    timer.set(exec_after(duration, {
+        let timer = timer.clone();
+        move |_| {
+            timer.set(TimerToken::INVALID);
+            write.try_update(|elapsed| *elapsed = true);
+        }
+    }));
+    cancel_on_dispose(timer);
+    read
+}
+
+/// Cancels the timer when the current scope is disposed.
+fn cancel_on_dispose(timer: Rc<Cell<TimerToken>>) {
+    struct CancelTimer(Rc<Cell<TimerToken>>);
+
+    impl Drop for CancelTimer {
+        fn drop(&mut self) {
+            self.0.get().cancel_if_valid();
+        }
+    }
+
+    // The effect is dropped with its scope, and the guard with it
+    let guard = CancelTimer(timer);
+    create_effect(move |_| {
+        let _ = &guard;
+    });
+}
+
 /// Show a system context menu at the specified position
 ///
 /// Platform support:
@@ -186,10 +386,13 @@ pub fn show_context_menu(menu: Menu, pos: Option<Point>) {
 
 /// Set the system window menu
 ///
+/// The actions of the menu items are run on the window which set the menu, and the
+/// [accelerators](crate::menu::MenuItem::accelerator) of the items are active while the window is focused.
+///
 /// Platform support:
-/// - Windows: No
-/// - macOS: Yes (not currently implemented)
-/// - Linux: No
+/// - Windows: Yes
+/// - macOS: Yes, the menu of the focused window is shown in the application menu bar
+/// - Linux: Uses a custom Floem View at the top of the window
 pub fn set_window_menu(menu: Menu) {
     add_update_message(UpdateMessage::WindowMenu { menu });
 }
@@ -210,10 +413,31 @@ pub fn clear_app_focus() {
 }
 
 /// Set whether ime input is shown
+///
+/// This applies to the window until the focus changes, after which the IME follows the settings
+/// of the focused view, see [`ViewId::set_ime_allowed`].
 pub fn set_ime_allowed(allowed: bool) {
     add_update_message(UpdateMessage::SetImeAllowed { allowed });
 }
 
+/// The kind of text which is typed into an input, for the IME and the virtual keyboards.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ImePurpose {
+    #[default]
+    Normal,
+    /// Hidden text, for which composition is disabled so that nothing is shown by the IME
+    Password,
+    /// Numbers, for which a numeric virtual keyboard is shown where the platform supports it
+    Digits,
+}
+
+/// Set the kind of text which is typed, see [`ImePurpose`]
+///
+/// Like [`set_ime_allowed`], this applies to the window until the focus changes.
+pub fn set_ime_purpose(purpose: ImePurpose) {
+    add_update_message(UpdateMessage::SetImePurpose { purpose });
+}
+
 /// Set the ime cursor area
 pub fn set_ime_cursor_area(position: Point, size: Size) {
     add_update_message(UpdateMessage::SetImeCursorArea { position, size });
@@ -4,6 +4,7 @@
 
 use crate::{
     easing::*,
+    preferences::prefers_reduced_motion,
     style::{Style, StylePropRef},
     unit::UnitExt,
     view_state::StackOffset,
@@ -829,7 +830,8 @@ impl Animation {
                     elapsed - self.delay
                 };
 
-                if temp_elapsed >= self.duration {
+                // Animations jump to their end when the user prefers reduced motion
+                if temp_elapsed >= self.duration || prefers_reduced_motion() {
                     if self.props_in_ext_progress.is_empty() {
                         self.state = AnimState::PassFinished {
                             elapsed,
@@ -863,7 +865,7 @@ impl Animation {
                 elapsed,
                 was_in_ext,
             } => match self.repeat_mode {
-                RepeatMode::LoopForever => {
+                RepeatMode::LoopForever if !prefers_reduced_motion() => {
                     if self.reverse_once.is_rev() {
                         self.reverse_once.set(false);
                     } else if self.auto_reverse {
@@ -874,7 +876,12 @@ impl Animation {
                         elapsed: Duration::ZERO,
                     }
                 }
-                RepeatMode::Times(times) => {
+                _ => {
+                    // Animations looping forever stop after a pass with reduced motion
+                    let times = match self.repeat_mode {
+                        RepeatMode::Times(times) => times,
+                        RepeatMode::LoopForever => 0,
+                    };
                     self.repeat_count += 1;
                     if self.repeat_count >= times {
                         let was_reversing = self.reverse_once.is_rev();
@@ -956,6 +963,9 @@ impl Animation {
         if self.duration == Duration::ZERO {
             return 0.;
         }
+        if prefers_reduced_motion() {
+            return if self.reverse_once.is_rev() { 0. } else { 1. };
+        }
         let mut elapsed = self.elapsed().unwrap_or(Duration::ZERO);
         // don't account for delay when reversing
         if !self.reverse_once.is_rev() && elapsed < self.delay {
@@ -10,7 +10,7 @@ use parking_lot::Mutex;
 use raw_window_handle::HasDisplayHandle;
 use winit::{
     application::ApplicationHandler,
-    event::WindowEvent,
+    event::{DeviceEvent, DeviceId, WindowEvent},
     event_loop::{ActiveEventLoop, EventLoop, EventLoopProxy},
     window::WindowId,
 };
@@ -57,6 +57,9 @@ pub enum AppEvent {
 pub(crate) enum UserEvent {
     AppUpdate,
     Idle,
+    /// The browser is about to paint a frame, which fires the timers due by then
+    #[allow(dead_code)]
+    AnimationFrame,
     QuitApp,
     #[allow(dead_code)]
     Reopen {
@@ -65,6 +68,20 @@ pub(crate) enum UserEvent {
     GpuResourcesUpdate {
         window_id: WindowId,
     },
+    #[allow(dead_code)]
+    AccessibilityActivated {
+        window_id: WindowId,
+    },
+    #[allow(dead_code)]
+    AccessibilityAction {
+        window_id: WindowId,
+        request: accesskit::ActionRequest,
+    },
+    #[cfg(all(
+        feature = "gamepad",
+        not(any(target_arch = "wasm32", target_os = "android", target_os = "ios"))
+    ))]
+    Gamepad(crate::gamepad::GamepadEvent),
 }
 
 pub(crate) enum AppUpdateEvent {
@@ -91,6 +108,21 @@ pub(crate) enum AppUpdateEvent {
     MenuAction {
         action_id: String,
     },
+    UiScaleChanged,
+    HighContrastChanged,
+    CreateCustomCursor {
+        cursor: crate::cursor::CustomCursor,
+        source: winit::window::CustomCursorSource,
+    },
+    #[cfg(feature = "tray")]
+    Tray {
+        update: crate::tray::TrayUpdate,
+    },
+    #[cfg(feature = "tray")]
+    #[allow(dead_code)]
+    TrayEvent {
+        event: crate::tray::TrayEvent,
+    },
 }
 
 pub(crate) fn add_app_update_event(event: AppUpdateEvent) {
@@ -137,6 +169,15 @@ impl ApplicationHandler for Application {
             .handle_window_event(window_id, event, event_loop);
     }
 
+    fn device_event(
+        &mut self,
+        _event_loop: &dyn ActiveEventLoop,
+        _device_id: Option<DeviceId>,
+        event: DeviceEvent,
+    ) {
+        self.handle.handle_device_event(event);
+    }
+
     fn proxy_wake_up(&mut self, event_loop: &dyn ActiveEventLoop) {
         self.handle.handle_timer(event_loop);
         for event in self.receiver.try_iter() {
@@ -149,17 +190,44 @@ impl ApplicationHandler for Application {
         if let Some(action) = self.handle.event_listener.as_ref() {
             action(AppEvent::WillTerminate);
         }
+        #[cfg(feature = "serde")]
+        crate::persist::flush();
     }
 
     fn about_to_wait(&mut self, event_loop: &dyn ActiveEventLoop) {
         self.handle.handle_timer(event_loop);
+        self.handle.handle_idle(event_loop);
     }
 }
 
 impl Application {
     pub fn new() -> Self {
-        let event_loop = EventLoop::new().expect("can't start the event loop");
+        Self::with_event_loop(EventLoop::new().expect("can't start the event loop"))
+    }
 
+    /// Creates the application of an Android activity.
+    ///
+    /// This requires either the `android-native-activity` or the `android-game-activity`
+    /// feature, and is called from the `android_main` entry point of the app library:
+    /// ```ignore
+    /// #[no_mangle]
+    /// fn android_main(app: AndroidApp) {
+    ///     floem::Application::new_android(app)
+    ///         .window(|_| app_view(), None)
+    ///         .run();
+    /// }
+    /// ```
+    #[cfg(target_os = "android")]
+    pub fn new_android(app: winit::platform::android::activity::AndroidApp) -> Self {
+        use winit::platform::android::EventLoopBuilderExtAndroid;
+        let event_loop = EventLoop::builder()
+            .with_android_app(app)
+            .build()
+            .expect("can't start the event loop");
+        Self::with_event_loop(event_loop)
+    }
+
+    fn with_event_loop(event_loop: EventLoop) -> Self {
         #[cfg(target_os = "macos")]
         crate::app_delegate::set_app_delegate();
 
@@ -179,6 +247,15 @@ impl Application {
             });
         }));
 
+        #[cfg(all(feature = "tray", any(target_os = "windows", target_os = "macos")))]
+        crate::tray::set_tray_event_handler();
+
+        #[cfg(all(
+            feature = "gamepad",
+            not(any(target_arch = "wasm32", target_os = "android", target_os = "ios"))
+        ))]
+        crate::gamepad::spawn_event_thread();
+
         Self {
             receiver,
             handle,
@@ -213,7 +290,16 @@ impl Application {
 
     pub fn run(mut self) {
         let event_loop = self.event_loop.take().unwrap();
-        let _ = event_loop.run_app(self);
+        // The browser runs the event loop, so it can't block
+        #[cfg(target_arch = "wasm32")]
+        {
+            use winit::platform::web::EventLoopExtWeb;
+            event_loop.spawn_app(self);
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let _ = event_loop.run_app(self);
+        }
     }
 
     pub(crate) fn send_proxy_event(event: UserEvent) {
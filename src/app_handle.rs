@@ -7,11 +7,11 @@ use web_time::Instant;
 use wgpu::web_sys;
 
 use floem_reactive::SignalUpdate;
-use peniko::kurbo::{Point, Size};
-use std::{collections::HashMap, rc::Rc};
+use peniko::kurbo::{Point, Size, Vec2};
+use std::{collections::HashMap, rc::Rc, time::Duration};
 use winit::{
     dpi::{LogicalPosition, LogicalSize},
-    event::WindowEvent,
+    event::{ButtonSource, DeviceEvent, PointerKind, PointerSource, TouchPhase, WindowEvent},
     event_loop::{ActiveEventLoop, ControlFlow},
     window::WindowId,
 };
@@ -19,9 +19,10 @@ use winit::{
 use crate::{
     action::{Timer, TimerToken},
     app::{AppEventCallback, AppUpdateEvent, UserEvent, APP_UPDATE_EVENTS},
+    cursor::create_platform_cursor,
     ext_event::EXT_EVENT_HANDLER,
     inspector::Capture,
-    profiler::{Profile, ProfileEvent},
+    profiler::{FrameProfiler, Profile, ProfileEvent},
     view::View,
     window::WindowConfig,
     window_handle::WindowHandle,
@@ -32,7 +33,14 @@ use crate::{
 pub(crate) struct ApplicationHandle {
     window_handles: HashMap<winit::window::WindowId, WindowHandle>,
     timers: HashMap<TimerToken, Timer>,
+    /// The event loop polls because idle effects were left in the last run
+    idle_polling: bool,
+    /// An animation frame of the browser is requested to fire the next timer
+    #[cfg(target_arch = "wasm32")]
+    animation_frame_requested: bool,
     pub(crate) event_listener: Option<Box<AppEventCallback>>,
+    #[cfg(feature = "tray")]
+    tray: crate::tray::TrayState,
 }
 
 impl ApplicationHandle {
@@ -40,7 +48,12 @@ impl ApplicationHandle {
         Self {
             window_handles: HashMap::new(),
             timers: HashMap::new(),
+            idle_polling: false,
+            #[cfg(target_arch = "wasm32")]
+            animation_frame_requested: false,
             event_listener: None,
+            #[cfg(feature = "tray")]
+            tray: Default::default(),
         }
     }
 
@@ -52,6 +65,14 @@ impl ApplicationHandle {
             UserEvent::Idle => {
                 self.idle();
             }
+            UserEvent::AnimationFrame => {
+                // The timers were fired when the event loop woke up
+                #[cfg(target_arch = "wasm32")]
+                {
+                    self.animation_frame_requested = false;
+                    self.fire_timer(event_loop);
+                }
+            }
             UserEvent::QuitApp => {
                 event_loop.exit();
             }
@@ -70,6 +91,25 @@ impl ApplicationHandle {
                     .unwrap()
                     .init_renderer();
             }
+            UserEvent::AccessibilityActivated { window_id } => {
+                if let Some(window_handle) = self.window_handles.get_mut(&window_id) {
+                    window_handle.update_accessibility();
+                }
+            }
+            UserEvent::AccessibilityAction { window_id, request } => {
+                if let Some(window_handle) = self.window_handles.get_mut(&window_id) {
+                    window_handle.accessibility_action(request);
+                }
+            }
+            #[cfg(all(
+                feature = "gamepad",
+                not(any(target_arch = "wasm32", target_os = "android", target_os = "ios"))
+            ))]
+            UserEvent::Gamepad(event) => {
+                for window_handle in self.window_handles.values_mut() {
+                    window_handle.gamepad(event);
+                }
+            }
         }
     }
 
@@ -115,6 +155,10 @@ impl ApplicationHandle {
                     }
                 }
                 AppUpdateEvent::MenuAction { action_id } => {
+                    #[cfg(feature = "tray")]
+                    if self.tray.menu_action(&action_id) {
+                        continue;
+                    }
                     for (_, handle) in self.window_handles.iter_mut() {
                         if handle.app_state.context_menu.contains_key(&action_id)
                             || handle.app_state.window_menu.contains_key(&action_id)
@@ -124,6 +168,29 @@ impl ApplicationHandle {
                         }
                     }
                 }
+                AppUpdateEvent::UiScaleChanged => {
+                    for handle in self.window_handles.values_mut() {
+                        handle.apply_zoom();
+                        handle.process_update();
+                    }
+                }
+                AppUpdateEvent::HighContrastChanged => {
+                    for handle in self.window_handles.values_mut() {
+                        handle.high_contrast_changed();
+                        handle.process_update();
+                    }
+                }
+                AppUpdateEvent::CreateCustomCursor { cursor, source } => {
+                    create_platform_cursor(event_loop, cursor, source);
+                }
+                #[cfg(feature = "tray")]
+                AppUpdateEvent::Tray { update } => {
+                    self.tray.update(update);
+                }
+                #[cfg(feature = "tray")]
+                AppUpdateEvent::TrayEvent { event } => {
+                    self.tray.event(event);
+                }
             }
         }
     }
@@ -189,7 +256,13 @@ impl ApplicationHandle {
                 window_handle.position(point);
             }
             WindowEvent::CloseRequested => {
-                self.close_window(window_id, event_loop);
+                if window_handle.hide_on_close {
+                    if let Some(window) = window_handle.window.as_ref() {
+                        window.set_visible(false);
+                    }
+                } else {
+                    self.close_window(window_id, event_loop);
+                }
             }
             WindowEvent::Destroyed => {
                 self.close_window(window_id, event_loop);
@@ -200,6 +273,10 @@ impl ApplicationHandle {
             WindowEvent::HoveredFile(_) => {}
             WindowEvent::HoveredFileCancelled => {}
             WindowEvent::Focused(focused) => {
+                if focused {
+                    // The preferences may have been changed while another app was focused
+                    crate::preferences::refresh();
+                }
                 window_handle.focused(focused);
             }
             WindowEvent::KeyboardInput {
@@ -217,20 +294,51 @@ impl ApplicationHandle {
             WindowEvent::Ime(ime) => {
                 window_handle.ime(ime);
             }
-            WindowEvent::PointerMoved { position, .. } => {
+            WindowEvent::PointerMoved {
+                position, source, ..
+            } => {
                 let position: LogicalPosition<f64> = position.to_logical(window_handle.scale);
                 let point = Point::new(position.x, position.y);
                 window_handle.pointer_move(point);
+                if let PointerSource::Touch { finger_id, force } = source {
+                    window_handle.touch(TouchPhase::Moved, finger_id, point, force);
+                }
             }
             WindowEvent::PointerEntered { .. } => {}
-            WindowEvent::PointerLeft { .. } => {
+            WindowEvent::PointerLeft { position, kind, .. } => {
                 window_handle.pointer_leave();
+                // A touch which leaves without being released was canceled
+                if let PointerKind::Touch(finger_id) = kind {
+                    let point = position
+                        .map(|position| {
+                            let position: LogicalPosition<f64> =
+                                position.to_logical(window_handle.scale);
+                            Point::new(position.x, position.y)
+                        })
+                        .unwrap_or(window_handle.cursor_position);
+                    window_handle.touch(TouchPhase::Cancelled, finger_id, point, None);
+                }
             }
             WindowEvent::MouseWheel { delta, .. } => {
                 window_handle.mouse_wheel(delta);
             }
-            WindowEvent::PointerButton { state, button, .. } => {
+            WindowEvent::PointerButton {
+                state,
+                position,
+                button,
+                ..
+            } => {
                 window_handle.pointer_button(button, state);
+                if let ButtonSource::Touch { finger_id, force } = button {
+                    let position: LogicalPosition<f64> = position.to_logical(window_handle.scale);
+                    let phase = if state.is_pressed() {
+                        TouchPhase::Started
+                    } else {
+                        TouchPhase::Ended
+                    };
+                    let point = Point::new(position.x, position.y);
+                    window_handle.touch(phase, finger_id, point, force);
+                }
             }
             WindowEvent::PinchGesture { delta, phase, .. } => {
                 window_handle.pinch_gesture(delta, phase);
@@ -240,6 +348,7 @@ impl ApplicationHandle {
                 window_handle.scale(scale_factor);
             }
             WindowEvent::ThemeChanged(theme) => {
+                crate::preferences::refresh();
                 window_handle.os_theme_changed(theme);
             }
             WindowEvent::Occluded(_) => {}
@@ -295,6 +404,12 @@ impl ApplicationHandle {
             mac_os_config,
             web_config,
             font_embolden,
+            hide_on_close,
+            inspector_overlay_key,
+            frame_profiler,
+            renderer,
+            safe_area_padding,
+            direction,
         }: WindowConfig,
     ) {
         let logical_size = size.map(|size| LogicalSize::new(size.width, size.height));
@@ -438,14 +553,22 @@ impl ApplicationHandle {
             return;
         };
         let window_id = window.id();
-        let window_handle = WindowHandle::new(
+        // The default theme of the window follows the high contrast preference
+        crate::preferences::refresh();
+        let mut window_handle = WindowHandle::new(
             window,
             view_fn,
             transparent,
             apply_default_theme,
             logical_size,
             font_embolden,
+            renderer,
+            safe_area_padding,
+            direction,
         );
+        window_handle.hide_on_close = hide_on_close;
+        window_handle.inspector_overlay_key = inspector_overlay_key;
+        window_handle.frame_profiler = frame_profiler.then(FrameProfiler::new);
         self.window_handles.insert(window_id, window_handle);
     }
 
@@ -482,6 +605,15 @@ impl ApplicationHandle {
         self.handle_updates_for_all_windows();
     }
 
+    /// Sends the relative motion of the pointer to the window which locked the pointer.
+    pub(crate) fn handle_device_event(&mut self, event: DeviceEvent) {
+        if let DeviceEvent::PointerMotion { delta: (x, y) } = event {
+            for handle in self.window_handles.values_mut() {
+                handle.pointer_delta(Vec2::new(x, y));
+            }
+        }
+    }
+
     pub(crate) fn handle_updates_for_all_windows(&mut self) {
         for (window_id, handle) in self.window_handles.iter_mut() {
             handle.process_update();
@@ -505,10 +637,54 @@ impl ApplicationHandle {
 
         let deadline = self.timers.values().map(|timer| timer.deadline).min();
         if let Some(deadline) = deadline {
+            // Timers due by the next frame, like the ones of animations, fire when the
+            // browser is about to paint instead of at a time which may be between frames
+            #[cfg(target_arch = "wasm32")]
+            if deadline <= Instant::now() + Duration::from_millis(16) {
+                self.request_animation_frame();
+                return;
+            }
             event_loop.set_control_flow(ControlFlow::WaitUntil(deadline));
         }
     }
 
+    #[cfg(target_arch = "wasm32")]
+    fn request_animation_frame(&mut self) {
+        use wasm_bindgen::{closure::Closure, JsCast};
+
+        if std::mem::replace(&mut self.animation_frame_requested, true) {
+            return;
+        }
+        let callback = Closure::once_into_js(|| {
+            crate::Application::send_proxy_event(UserEvent::AnimationFrame);
+        });
+        if let Some(window) = web_sys::window() {
+            let _ = window.request_animation_frame(callback.unchecked_ref());
+        }
+    }
+
+    /// Runs idle effects for a short time once all events are handled, and keeps the
+    /// event loop polling until none are left.
+    pub(crate) fn handle_idle(&mut self, event_loop: &dyn ActiveEventLoop) {
+        const IDLE_BUDGET: Duration = Duration::from_millis(4);
+
+        let remaining = if floem_reactive::has_idle_effects() {
+            let start = Instant::now();
+            let remaining = floem_reactive::run_idle_effects(|| start.elapsed() >= IDLE_BUDGET);
+            self.handle_updates_for_all_windows();
+            remaining
+        } else {
+            false
+        };
+        if remaining {
+            event_loop.set_control_flow(ControlFlow::Poll);
+        } else if self.idle_polling {
+            event_loop.set_control_flow(ControlFlow::Wait);
+            self.fire_timer(event_loop);
+        }
+        self.idle_polling = remaining;
+    }
+
     pub(crate) fn handle_timer(&mut self, event_loop: &dyn ActiveEventLoop) {
         let now = Instant::now();
         let tokens: Vec<TimerToken> = self
@@ -1,8 +1,7 @@
 use std::collections::{HashMap, HashSet};
 
-use peniko::kurbo::{Point, Size};
+use peniko::kurbo::{Point, Rect, Size};
 use taffy::{AvailableSpace, NodeId};
-use winit::window::CursorIcon;
 
 use crate::{
     context::{DragState, FrameUpdate, InteractionState},
@@ -10,8 +9,11 @@ use crate::{
     id::ViewId,
     inspector::CaptureState,
     menu::Menu,
+    nav::{focus_group_focused, Typeahead},
+    pointer::FingerId,
     responsive::{GridBreakpoints, ScreenSizeBp},
     style::{CursorStyle, Style, StyleClassRef, StyleSelector},
+    update::UpdateMessage,
     view_storage::VIEW_STORAGE,
 };
 
@@ -23,13 +25,20 @@ pub struct AppState {
     /// when a view is active, it gets mouse event even when the mouse is
     /// not on it
     pub(crate) active: Option<ViewId>,
+    /// The view the pointer is locked to, which receives its relative motion
+    pub(crate) pointer_lock: Option<ViewId>,
+    /// The fingers touching the window, with the view which handled the start of their touch
+    pub(crate) touches: HashMap<FingerId, Option<ViewId>>,
     pub(crate) root_view_id: ViewId,
     pub(crate) root: Option<NodeId>,
     pub(crate) root_size: Size,
     pub(crate) scale: f64,
     pub(crate) scheduled_updates: Vec<FrameUpdate>,
     pub(crate) request_compute_layout: bool,
+    /// Requests the whole window to be repainted
     pub(crate) request_paint: bool,
+    /// The parts of the window which views requested to be repainted, in window coordinates
+    pub(crate) damage: Option<Rect>,
     // the bool idicates if this item is the root of the disabled item
     pub(crate) disabled: HashSet<(ViewId, bool)>,
     pub(crate) keyboard_navigable: HashSet<ViewId>,
@@ -44,11 +53,14 @@ pub struct AppState {
     /// This keeps track of all views that have an animation,
     /// regardless of the status of the animation
     pub(crate) cursor: Option<CursorStyle>,
-    pub(crate) last_cursor: CursorIcon,
+    pub(crate) last_cursor: CursorStyle,
     pub(crate) last_cursor_location: Point,
     pub(crate) keyboard_navigation: bool,
     pub(crate) window_menu: HashMap<String, Box<dyn Fn()>>,
     pub(crate) context_menu: HashMap<String, Box<dyn Fn()>>,
+    /// The view focused last in each focus group
+    pub(crate) focus_group_entries: HashMap<ViewId, ViewId>,
+    pub(crate) typeahead: Typeahead,
 
     /// This is set if we're currently capturing the window for the inspector.
     pub(crate) capture: Option<CaptureState>,
@@ -62,11 +74,14 @@ impl AppState {
             focus: None,
             prev_focus: None,
             active: None,
+            pointer_lock: None,
+            touches: HashMap::new(),
             scale: 1.0,
             root_size: Size::ZERO,
             screen_size_bp: ScreenSizeBp::Xs,
             scheduled_updates: Vec::new(),
             request_paint: false,
+            damage: None,
             request_compute_layout: false,
             disabled: HashSet::new(),
             keyboard_navigable: HashSet::new(),
@@ -77,12 +92,14 @@ impl AppState {
             clicking: HashSet::new(),
             hovered: HashSet::new(),
             cursor: None,
-            last_cursor: CursorIcon::Default,
+            last_cursor: CursorStyle::Default,
             last_cursor_location: Default::default(),
             keyboard_navigation: false,
             grid_bps: GridBreakpoints::default(),
             window_menu: HashMap::new(),
             context_menu: HashMap::new(),
+            focus_group_entries: HashMap::new(),
+            typeahead: Typeahead::default(),
             capture: None,
         }
     }
@@ -125,6 +142,7 @@ impl AppState {
         self.clicking.remove(&id);
         self.hovered.remove(&id);
         self.clicking.remove(&id);
+        self.focus_group_entries.remove(&id);
         if self.focus == Some(id) {
             self.focus = None;
         }
@@ -135,6 +153,15 @@ impl AppState {
         if self.active == Some(id) {
             self.active = None;
         }
+        for target in self.touches.values_mut() {
+            if *target == Some(id) {
+                *target = None;
+            }
+        }
+        if self.pointer_lock == Some(id) {
+            self.root_view_id
+                .add_update_message(UpdateMessage::ReleasePointerLock(id));
+        }
     }
 
     pub(crate) fn can_focus(&self, id: ViewId) -> bool {
@@ -229,9 +256,28 @@ impl AppState {
         self.request_compute_layout = true;
     }
 
-    // `Id` is unused currently, but could be used to calculate damage regions.
-    pub fn request_paint(&mut self, _id: ViewId) {
-        self.request_paint = true;
+    /// Requests the part of the window covered by the view to be repainted.
+    pub fn request_paint(&mut self, id: ViewId) {
+        match id.paint_rect() {
+            Some(rect) => self.add_damage(rect),
+            None => self.request_paint = true,
+        }
+    }
+
+    /// Requests `rect` of the view, in its own coordinates, to be repainted.
+    pub fn request_paint_rect(&mut self, id: ViewId, rect: Rect) {
+        if id.is_transformed() {
+            self.request_paint = true;
+        } else {
+            self.add_damage(rect + id.layout_rect().origin().to_vec2());
+        }
+    }
+
+    fn add_damage(&mut self, rect: Rect) {
+        self.damage = Some(match self.damage {
+            Some(damage) => damage.union(rect),
+            None => rect,
+        });
     }
 
     pub(crate) fn update_active(&mut self, id: ViewId) {
@@ -276,6 +322,7 @@ impl AppState {
 
         self.focus = Some(id);
         self.keyboard_navigation = keyboard_navigation;
+        focus_group_focused(self, id);
 
         if self.has_style_for_sel(id, StyleSelector::Focus)
             || self.has_style_for_sel(id, StyleSelector::FocusVisible)
@@ -284,6 +331,12 @@ impl AppState {
         }
     }
 
+    /// The view which handled the start of the touch of the finger, which receives its
+    /// following events.
+    pub(crate) fn touch_target(&self, finger_id: FingerId) -> Option<ViewId> {
+        self.touches.get(&finger_id).copied().flatten()
+    }
+
     pub(crate) fn has_style_for_sel(&mut self, id: ViewId, selector_kind: StyleSelector) -> bool {
         let view_state = id.state();
         let view_state = view_state.borrow();
@@ -293,22 +346,12 @@ impl AppState {
     }
 
     pub(crate) fn update_context_menu(&mut self, menu: &mut Menu) {
-        if let Some(action) = menu.item.action.take() {
-            self.context_menu.insert(menu.item.id.clone(), action);
-        }
-        for child in menu.children.iter_mut() {
-            match child {
-                crate::menu::MenuEntry::Separator => {}
-                crate::menu::MenuEntry::Item(item) => {
-                    if let Some(action) = item.action.take() {
-                        self.context_menu.insert(item.id.clone(), action);
-                    }
-                }
-                crate::menu::MenuEntry::SubMenu(m) => {
-                    self.update_context_menu(m);
-                }
-            }
-        }
+        collect_menu_actions(menu, &mut self.context_menu);
+    }
+
+    pub(crate) fn update_window_menu(&mut self, menu: &mut Menu) {
+        self.window_menu.clear();
+        collect_menu_actions(menu, &mut self.window_menu);
     }
 
     pub(crate) fn focus_changed(&mut self, old: Option<ViewId>, new: Option<ViewId>) {
@@ -319,6 +362,8 @@ impl AppState {
             {
                 id.request_style_recursive();
             }
+            // To paint the focus ring
+            self.request_paint(id);
             id.apply_event(&EventListener::FocusGained, &Event::FocusGained);
             id.scroll_to(None);
         }
@@ -330,7 +375,31 @@ impl AppState {
             {
                 old_id.request_style_recursive();
             }
+            self.request_paint(old_id);
             old_id.apply_event(&EventListener::FocusLost, &Event::FocusLost);
         }
+
+        // After the views reacted to the focus change, so that the IME follows the new focus
+        self.root_view_id
+            .add_update_message(UpdateMessage::FocusedIme);
+    }
+}
+
+pub(crate) fn collect_menu_actions(menu: &mut Menu, actions: &mut HashMap<String, Box<dyn Fn()>>) {
+    if let Some(action) = menu.item.action.take() {
+        actions.insert(menu.item.id.clone(), action);
+    }
+    for child in menu.children.iter_mut() {
+        match child {
+            crate::menu::MenuEntry::Separator => {}
+            crate::menu::MenuEntry::Item(item) => {
+                if let Some(action) = item.action.take() {
+                    actions.insert(item.id.clone(), action);
+                }
+            }
+            crate::menu::MenuEntry::SubMenu(m) => {
+                collect_menu_actions(m, actions);
+            }
+        }
     }
 }
@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use parking_lot::Mutex;
 use raw_window_handle::RawDisplayHandle;
 
@@ -5,10 +7,21 @@ use copypasta::{ClipboardContext, ClipboardProvider};
 
 static CLIPBOARD: Mutex<Option<Clipboard>> = Mutex::new(None);
 
+/// Access to the system clipboard.
+///
+/// Plain text goes through [`get_contents`](Self::get_contents) and
+/// [`set_contents`](Self::set_contents), other formats through [`get`](Self::get) and
+/// [`set`](Self::set) or the typed helpers. On Linux the primary selection, which is
+/// pasted with a middle click, is available through
+/// [`get_primary_contents`](Self::get_primary_contents) and
+/// [`set_primary_contents`](Self::set_primary_contents).
 pub struct Clipboard {
     clipboard: Box<dyn ClipboardProvider>,
     #[allow(dead_code)]
     selection: Option<Box<dyn ClipboardProvider>>,
+    /// Used for everything but plain text
+    #[cfg(not(any(target_arch = "wasm32", target_os = "android", target_os = "ios")))]
+    rich: Option<arboard::Clipboard>,
 }
 
 #[derive(Clone, Debug)]
@@ -17,6 +30,49 @@ pub enum ClipboardError {
     ProviderError(String),
 }
 
+/// A format of data on the clipboard.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ClipboardFormat {
+    Text,
+    Html,
+    Image,
+    Files,
+}
+
+/// Typed data read from or written to the clipboard.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ClipboardContent {
+    Text(String),
+    /// HTML markup, with an optional plain text version for applications which
+    /// don't accept HTML.
+    Html {
+        html: String,
+        alt_text: Option<String>,
+    },
+    Image(ClipboardImage),
+    /// A list of file paths, as copied by file managers.
+    Files(Vec<PathBuf>),
+}
+
+impl ClipboardContent {
+    pub fn format(&self) -> ClipboardFormat {
+        match self {
+            ClipboardContent::Text(_) => ClipboardFormat::Text,
+            ClipboardContent::Html { .. } => ClipboardFormat::Html,
+            ClipboardContent::Image(_) => ClipboardFormat::Image,
+            ClipboardContent::Files(_) => ClipboardFormat::Files,
+        }
+    }
+}
+
+/// An image on the clipboard, as RGBA8 pixels.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ClipboardImage {
+    pub width: usize,
+    pub height: usize,
+    pub rgba: Vec<u8>,
+}
+
 impl Clipboard {
     pub fn get_contents() -> Result<String, ClipboardError> {
         CLIPBOARD
@@ -43,13 +99,161 @@ impl Clipboard {
             .map_err(|e| ClipboardError::ProviderError(e.to_string()))
     }
 
+    /// Read the primary selection. Only available on Linux and the BSDs.
+    pub fn get_primary_contents() -> Result<String, ClipboardError> {
+        CLIPBOARD
+            .lock()
+            .as_mut()
+            .ok_or(ClipboardError::NotAvailable)?
+            .selection
+            .as_mut()
+            .ok_or(ClipboardError::NotAvailable)?
+            .get_contents()
+            .map_err(|e| ClipboardError::ProviderError(e.to_string()))
+    }
+
+    /// Replace the primary selection. Only available on Linux and the BSDs.
+    pub fn set_primary_contents(s: String) -> Result<(), ClipboardError> {
+        if s.is_empty() {
+            return Err(ClipboardError::ProviderError(
+                "content is empty".to_string(),
+            ));
+        }
+        CLIPBOARD
+            .lock()
+            .as_mut()
+            .ok_or(ClipboardError::NotAvailable)?
+            .selection
+            .as_mut()
+            .ok_or(ClipboardError::NotAvailable)?
+            .set_contents(s)
+            .map_err(|e| ClipboardError::ProviderError(e.to_string()))
+    }
+
+    /// Read the clipboard in the given format.
+    pub fn get(format: ClipboardFormat) -> Result<ClipboardContent, ClipboardError> {
+        match format {
+            ClipboardFormat::Text => Self::get_contents().map(ClipboardContent::Text),
+            ClipboardFormat::Html => Self::get_html().map(|html| ClipboardContent::Html {
+                html,
+                alt_text: None,
+            }),
+            ClipboardFormat::Image => Self::get_image().map(ClipboardContent::Image),
+            ClipboardFormat::Files => Self::get_file_list().map(ClipboardContent::Files),
+        }
+    }
+
+    /// Replace the clipboard with the given content.
+    pub fn set(content: ClipboardContent) -> Result<(), ClipboardError> {
+        match content {
+            ClipboardContent::Text(text) => Self::set_contents(text),
+            ClipboardContent::Html { html, alt_text } => Self::set_html(html, alt_text),
+            ClipboardContent::Image(image) => Self::set_image(image),
+            ClipboardContent::Files(files) => Self::set_file_list(&files),
+        }
+    }
+
+    #[cfg(any(target_arch = "wasm32", target_os = "android", target_os = "ios"))]
+    pub fn get_html() -> Result<String, ClipboardError> {
+        Err(ClipboardError::NotAvailable)
+    }
+
+    #[cfg(any(target_arch = "wasm32", target_os = "android", target_os = "ios"))]
+    pub fn set_html(_html: String, _alt_text: Option<String>) -> Result<(), ClipboardError> {
+        Err(ClipboardError::NotAvailable)
+    }
+
+    #[cfg(any(target_arch = "wasm32", target_os = "android", target_os = "ios"))]
+    pub fn get_image() -> Result<ClipboardImage, ClipboardError> {
+        Err(ClipboardError::NotAvailable)
+    }
+
+    #[cfg(any(target_arch = "wasm32", target_os = "android", target_os = "ios"))]
+    pub fn set_image(_image: ClipboardImage) -> Result<(), ClipboardError> {
+        Err(ClipboardError::NotAvailable)
+    }
+
+    #[cfg(any(target_arch = "wasm32", target_os = "android", target_os = "ios"))]
+    pub fn get_file_list() -> Result<Vec<PathBuf>, ClipboardError> {
+        Err(ClipboardError::NotAvailable)
+    }
+
+    #[cfg(any(target_arch = "wasm32", target_os = "android", target_os = "ios"))]
+    pub fn set_file_list(_files: &[PathBuf]) -> Result<(), ClipboardError> {
+        Err(ClipboardError::NotAvailable)
+    }
+
+    #[cfg(not(any(target_arch = "wasm32", target_os = "android", target_os = "ios")))]
+    pub fn get_html() -> Result<String, ClipboardError> {
+        Self::with_rich(|clipboard| clipboard.get().html())
+    }
+
+    #[cfg(not(any(target_arch = "wasm32", target_os = "android", target_os = "ios")))]
+    pub fn set_html(html: String, alt_text: Option<String>) -> Result<(), ClipboardError> {
+        Self::with_rich(|clipboard| clipboard.set_html(html, alt_text))
+    }
+
+    #[cfg(not(any(target_arch = "wasm32", target_os = "android", target_os = "ios")))]
+    pub fn get_image() -> Result<ClipboardImage, ClipboardError> {
+        Self::with_rich(|clipboard| clipboard.get_image()).map(|image| ClipboardImage {
+            width: image.width,
+            height: image.height,
+            rgba: image.bytes.into_owned(),
+        })
+    }
+
+    #[cfg(not(any(target_arch = "wasm32", target_os = "android", target_os = "ios")))]
+    pub fn set_image(image: ClipboardImage) -> Result<(), ClipboardError> {
+        Self::with_rich(|clipboard| {
+            clipboard.set_image(arboard::ImageData {
+                width: image.width,
+                height: image.height,
+                bytes: image.rgba.into(),
+            })
+        })
+    }
+
     #[cfg(windows)]
-    pub fn get_file_list() -> Result<Vec<std::path::PathBuf>, ClipboardError> {
+    pub fn get_file_list() -> Result<Vec<PathBuf>, ClipboardError> {
         clipboard_win::Clipboard::new_attempts(10)
             .and_then(|x| x.get_file_list())
             .map_err(|e| ClipboardError::ProviderError(e.to_string()))
     }
 
+    #[cfg(not(any(
+        windows,
+        target_arch = "wasm32",
+        target_os = "android",
+        target_os = "ios"
+    )))]
+    pub fn get_file_list() -> Result<Vec<PathBuf>, ClipboardError> {
+        Self::with_rich(|clipboard| clipboard.get().file_list())
+    }
+
+    #[cfg(not(any(target_arch = "wasm32", target_os = "android", target_os = "ios")))]
+    pub fn set_file_list(files: &[PathBuf]) -> Result<(), ClipboardError> {
+        if files.is_empty() {
+            return Err(ClipboardError::ProviderError(
+                "content is empty".to_string(),
+            ));
+        }
+        Self::with_rich(|clipboard| clipboard.set().file_list(files))
+    }
+
+    #[cfg(not(any(target_arch = "wasm32", target_os = "android", target_os = "ios")))]
+    fn with_rich<T>(
+        f: impl FnOnce(&mut arboard::Clipboard) -> Result<T, arboard::Error>,
+    ) -> Result<T, ClipboardError> {
+        let mut clipboard = CLIPBOARD.lock();
+        let rich = clipboard
+            .as_mut()
+            .ok_or(ClipboardError::NotAvailable)?
+            .rich
+            .as_mut()
+            .ok_or(ClipboardError::NotAvailable)?;
+        f(rich).map_err(|e| ClipboardError::ProviderError(e.to_string()))
+    }
+
     pub(crate) unsafe fn init(display: RawDisplayHandle) {
         *CLIPBOARD.lock() = Some(Self::new(display));
     }
@@ -74,6 +278,7 @@ impl Clipboard {
                 return Self {
                     clipboard: Box::new(clipboard),
                     selection: Some(Box::new(selection)),
+                    rich: arboard::Clipboard::new().ok(),
                 };
             }
 
@@ -81,20 +286,91 @@ impl Clipboard {
             Self {
                 clipboard: Box::new(ClipboardContext::new().unwrap()),
                 selection: Some(Box::new(X11ClipboardContext::<Primary>::new().unwrap())),
+                rich: arboard::Clipboard::new().ok(),
             }
         }
 
-        // TODO: Implement clipboard support for the web, ios, and android
+        #[cfg(target_arch = "wasm32")]
+        {
+            web::watch_browser_clipboard();
+            Self {
+                clipboard: Box::new(web::WebClipboard),
+                selection: None,
+            }
+        }
+
+        // TODO: Implement clipboard support for ios and android
         #[cfg(any(
             target_os = "macos",
             target_os = "windows",
             target_os = "ios",
-            target_os = "android",
-            target_arch = "wasm32"
+            target_os = "android"
         ))]
         return Self {
             clipboard: Box::new(ClipboardContext::new().unwrap()),
             selection: None,
+            #[cfg(not(any(target_os = "ios", target_os = "android")))]
+            rich: arboard::Clipboard::new().ok(),
         };
     }
 }
+
+/// The clipboard of the browser, which can only be read asynchronously.
+///
+/// Text copied in the app is kept to be pasted right away and written to the browser
+/// clipboard. Text copied in other pages and applications is read when the page gets the
+/// focus back, if the user allows it.
+#[cfg(target_arch = "wasm32")]
+mod web {
+    use std::error::Error;
+
+    use copypasta::ClipboardProvider;
+    use parking_lot::Mutex;
+    use wasm_bindgen::{closure::Closure, JsCast};
+
+    static CONTENTS: Mutex<String> = Mutex::new(String::new());
+
+    pub(super) struct WebClipboard;
+
+    impl ClipboardProvider for WebClipboard {
+        fn get_contents(&mut self) -> Result<String, Box<dyn Error + Send + Sync + 'static>> {
+            Ok(CONTENTS.lock().clone())
+        }
+
+        fn set_contents(
+            &mut self,
+            contents: String,
+        ) -> Result<(), Box<dyn Error + Send + Sync + 'static>> {
+            if let Some(window) = web_sys::window() {
+                let _ = window.navigator().clipboard().write_text(&contents);
+            }
+            *CONTENTS.lock() = contents;
+            Ok(())
+        }
+    }
+
+    pub(super) fn watch_browser_clipboard() {
+        let Some(window) = web_sys::window() else {
+            return;
+        };
+        read_browser_clipboard();
+        let on_focus = Closure::<dyn Fn()>::new(read_browser_clipboard);
+        let _ = window.add_event_listener_with_callback("focus", on_focus.as_ref().unchecked_ref());
+        on_focus.forget();
+    }
+
+    fn read_browser_clipboard() {
+        let Some(window) = web_sys::window() else {
+            return;
+        };
+        let read = window.navigator().clipboard().read_text();
+        wasm_bindgen_futures::spawn_local(async move {
+            // Reading is denied without the permission of the user
+            if let Ok(text) = wasm_bindgen_futures::JsFuture::from(read).await {
+                if let Some(text) = text.as_string() {
+                    *CONTENTS.lock() = text;
+                }
+            }
+        });
+    }
+}
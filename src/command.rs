@@ -0,0 +1,310 @@
+//! A registry of the commands of an application, with their keyboard shortcuts.
+//!
+//! Registered commands run when their shortcut is pressed in a window, unless a focused view
+//! handled the key first, and are listed by the
+//! [command palette](crate::views::show_command_palette).
+//! ```rust
+//! use floem::command::{register_command, Command};
+//! use floem::views::show_command_palette;
+//!
+//! register_command(
+//!     Command::new("palette.show", "Show All Commands")
+//!         .shortcut("CmdOrCtrl+Shift+P")
+//!         .action(show_command_palette),
+//! );
+//! register_command(
+//!     Command::new("file.save", "Save")
+//!         .category("File")
+//!         .shortcut("CmdOrCtrl+S")
+//!         .action(|| println!("saving")),
+//! );
+//! ```
+use std::{cell::RefCell, rc::Rc};
+
+use floem_reactive::{RwSignal, Scope, SignalTrack, SignalUpdate};
+
+use crate::{keyboard::KeyEvent, menu::MenuAccelerator};
+
+/// The number of recently run commands which are remembered.
+const RECENT_COMMANDS: usize = 32;
+
+thread_local! {
+    static COMMANDS: RefCell<Vec<Rc<Command>>> = const { RefCell::new(Vec::new()) };
+    /// Bumped when commands are registered or removed
+    static REVISION: RwSignal<u64> = Scope::new().create_rw_signal(0);
+    /// The ids of recently run commands, most recent first
+    static RECENT: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+/// A command which can be run from the command palette or with a keyboard shortcut.
+pub struct Command {
+    id: String,
+    title: String,
+    category: Option<String>,
+    shortcut: Option<MenuAccelerator>,
+    enabled: Option<Box<dyn Fn() -> bool>>,
+    action: Option<Box<dyn Fn()>>,
+}
+
+impl Command {
+    /// Creates a command with a unique id and a title shown to the user.
+    pub fn new(id: impl Into<String>, title: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            title: title.into(),
+            category: None,
+            shortcut: None,
+            enabled: None,
+            action: None,
+        }
+    }
+
+    /// Sets the category shown before the title, like `File` or `View`.
+    pub fn category(mut self, category: impl Into<String>) -> Self {
+        self.category = Some(category.into());
+        self
+    }
+
+    /// Sets the keyboard shortcut of the command, like `"CmdOrCtrl+Shift+P"`, in the format
+    /// of [menu accelerators](crate::menu::MenuItem::accelerator). Invalid shortcuts are
+    /// ignored.
+    pub fn shortcut(mut self, shortcut: impl AsRef<str>) -> Self {
+        self.shortcut = MenuAccelerator::parse(shortcut.as_ref());
+        self
+    }
+
+    /// Sets a function deciding whether the command can run. Disabled commands aren't
+    /// listed by the command palette, and their shortcut does nothing.
+    pub fn enabled(mut self, enabled: impl Fn() -> bool + 'static) -> Self {
+        self.enabled = Some(Box::new(enabled));
+        self
+    }
+
+    pub fn action(mut self, action: impl Fn() + 'static) -> Self {
+        self.action = Some(Box::new(action));
+        self
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn category_name(&self) -> Option<&str> {
+        self.category.as_deref()
+    }
+
+    /// The text of the shortcut, like `Ctrl+Shift+P`.
+    pub fn shortcut_label(&self) -> Option<String> {
+        self.shortcut.as_ref().map(MenuAccelerator::display)
+    }
+
+    /// The title with the category before it, like `File: Save`, which the command palette
+    /// matches against.
+    pub fn label(&self) -> String {
+        match &self.category {
+            Some(category) => format!("{category}: {}", self.title),
+            None => self.title.clone(),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.as_ref().is_none_or(|enabled| enabled())
+    }
+
+    /// Runs the command if it's enabled, and remembers it as recently run.
+    pub fn run(&self) {
+        if !self.is_enabled() {
+            return;
+        }
+        RECENT.with_borrow_mut(|recent| {
+            recent.retain(|id| *id != self.id);
+            recent.insert(0, self.id.clone());
+            recent.truncate(RECENT_COMMANDS);
+        });
+        if let Some(action) = &self.action {
+            action();
+        }
+    }
+}
+
+/// Adds a command to the registry, replacing the command with the same id.
+pub fn register_command(command: Command) {
+    COMMANDS.with_borrow_mut(|commands| {
+        let command = Rc::new(command);
+        match commands.iter_mut().find(|c| c.id == command.id) {
+            Some(existing) => *existing = command,
+            None => commands.push(command),
+        }
+    });
+    REVISION.with(|revision| revision.update(|revision| *revision += 1));
+}
+
+/// Removes a command from the registry.
+pub fn unregister_command(id: &str) {
+    COMMANDS.with_borrow_mut(|commands| commands.retain(|command| command.id != id));
+    REVISION.with(|revision| revision.update(|revision| *revision += 1));
+}
+
+/// The registered commands, in the order they were registered.
+///
+/// Changes of the registry are tracked.
+pub fn commands() -> Vec<Rc<Command>> {
+    REVISION.with(|revision| revision.track());
+    COMMANDS.with_borrow(|commands| commands.clone())
+}
+
+/// Runs the command with an id. Returns `false` if there's no such command.
+pub fn run_command(id: &str) -> bool {
+    let command = COMMANDS.with_borrow(|commands| commands.iter().find(|c| c.id == id).cloned());
+    match command {
+        Some(command) => {
+            command.run();
+            true
+        }
+        None => false,
+    }
+}
+
+/// The ids of recently run commands, most recent first.
+pub fn recent_commands() -> Vec<String> {
+    RECENT.with_borrow(|recent| recent.clone())
+}
+
+/// The enabled command whose shortcut is triggered by a key press.
+pub(crate) fn command_for_key(event: &KeyEvent) -> Option<Rc<Command>> {
+    COMMANDS
+        .with_borrow(|commands| {
+            commands
+                .iter()
+                .find(|command| {
+                    command
+                        .shortcut
+                        .as_ref()
+                        .is_some_and(|shortcut| shortcut.matches(event))
+                })
+                .cloned()
+        })
+        .filter(|command| command.is_enabled())
+}
+
+/// How well a query matches a text, see [`fuzzy_match`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FuzzyMatch {
+    /// Higher scores are better matches
+    pub score: i64,
+    /// The indices of the matched characters in the text
+    pub indices: Vec<usize>,
+}
+
+/// Matches the characters of a query, in order but not necessarily adjacent, against a text,
+/// ignoring case. Returns `None` if the text doesn't contain the query.
+///
+/// Matches of adjacent characters and at the start of words score higher, so `fs` matches
+/// `File: Save` better than `Offsets`.
+pub fn fuzzy_match(query: &str, text: &str) -> Option<FuzzyMatch> {
+    let text: Vec<char> = text.chars().collect();
+    let mut indices = Vec::new();
+    let mut score = 0;
+    let mut start = 0;
+    for q in query.chars().filter(|c| !c.is_whitespace()) {
+        let matches = |i: &usize| chars_match(q, text[*i]);
+        let next = (start..text.len()).find(matches)?;
+        // Prefer the start of a later word to a match in the middle of a word
+        let adjacent = indices.last().is_some_and(|last| last + 1 == next);
+        let index = if adjacent || is_word_start(&text, next) {
+            next
+        } else {
+            (next..text.len())
+                .filter(matches)
+                .find(|i| is_word_start(&text, *i))
+                .unwrap_or(next)
+        };
+
+        score += 16;
+        if is_word_start(&text, index) {
+            score += 10;
+        }
+        match indices.last() {
+            Some(last) if *last + 1 == index => score += 12,
+            Some(last) => score -= (index - last - 1).min(8) as i64,
+            None => score -= index.min(8) as i64,
+        }
+        indices.push(index);
+        start = index + 1;
+    }
+    // Shorter texts match a query better
+    score -= (text.len() / 8) as i64;
+    Some(FuzzyMatch { score, indices })
+}
+
+fn chars_match(query: char, c: char) -> bool {
+    query == c || query.to_lowercase().eq(c.to_lowercase())
+}
+
+fn is_word_start(text: &[char], index: usize) -> bool {
+    if index == 0 {
+        return true;
+    }
+    let (prev, c) = (text[index - 1], text[index]);
+    !prev.is_alphanumeric() || (prev.is_lowercase() && c.is_uppercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fuzzy_matching() {
+        assert!(fuzzy_match("fsx", "File: Save").is_none());
+        let m = fuzzy_match("fs", "File: Save").unwrap();
+        assert_eq!(m.indices, vec![0, 6]);
+        let m = fuzzy_match("sa", "File: Save").unwrap();
+        assert_eq!(m.indices, vec![6, 7]);
+        // Word starts are preferred over earlier matches inside words
+        let m = fuzzy_match("t", "Set Theme").unwrap();
+        assert_eq!(m.indices, vec![4]);
+
+        let adjacent = fuzzy_match("save", "File: Save").unwrap();
+        let scattered = fuzzy_match("save", "Show All Views Everywhere").unwrap();
+        assert!(adjacent.score > scattered.score);
+        assert!(fuzzy_match("", "Anything").is_some());
+        assert_eq!(
+            fuzzy_match("TOGGLE", "toggleWrap").unwrap().indices.len(),
+            6
+        );
+    }
+
+    #[test]
+    fn registry() {
+        let runs = Rc::new(std::cell::Cell::new(0));
+        let count = runs.clone();
+        register_command(
+            Command::new("test.count", "Count")
+                .category("Test")
+                .shortcut("Ctrl+K")
+                .action(move || count.set(count.get() + 1)),
+        );
+        register_command(Command::new("test.disabled", "Disabled").enabled(|| false));
+
+        assert!(run_command("test.count"));
+        assert!(run_command("test.disabled"));
+        assert!(!run_command("test.missing"));
+        assert_eq!(runs.get(), 1);
+        // Disabled commands aren't remembered
+        assert_eq!(recent_commands(), vec!["test.count".to_string()]);
+
+        let command = commands()
+            .into_iter()
+            .find(|c| c.id() == "test.count")
+            .unwrap();
+        assert_eq!(command.label(), "Test: Count");
+        assert_eq!(command.shortcut_label().as_deref(), Some("Ctrl+K"));
+
+        unregister_command("test.count");
+        assert!(!run_command("test.count"));
+    }
+}
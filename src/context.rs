@@ -24,23 +24,25 @@ use taffy::prelude::NodeId;
 use crate::animate::{AnimStateKind, RepeatMode};
 use crate::easing::{Easing, Linear};
 use crate::renderer::Renderer;
-use crate::style::{DisplayProp, PointerEvents, PointerEventsProp};
+use crate::style::{Direction, DisplayProp, MirrorInRtl, PointerEvents, PointerEventsProp};
 use crate::view_state::IsHiddenState;
 use crate::{
     action::{exec_after, show_context_menu},
     app_state::AppState,
     event::{Event, EventListener, EventPropagation},
+    gamepad::{GamepadButton, GamepadEvent},
     id::ViewId,
     inspector::CaptureState,
     menu::Menu,
     style::{Style, StyleProp, ZIndex},
-    view::{paint_bg, paint_border, paint_outline, View},
+    view::{paint_bg, paint_border, paint_focus_ring, paint_outline, View},
     view_state::ChangeFlags,
 };
 
 pub type EventCallback = dyn FnMut(&Event) -> EventPropagation;
 pub type ResizeCallback = dyn Fn(Rect);
 pub type MenuCallback = dyn Fn() -> Menu;
+pub type PaintCallback = dyn Fn(&mut PaintCx, Rect);
 
 pub(crate) struct ResizeListener {
     pub(crate) rect: Rect,
@@ -53,6 +55,39 @@ pub(crate) struct MoveListener {
     pub(crate) callback: Box<dyn Fn(Point)>,
 }
 
+/// The listener of whether a view is visible in the viewports of its ancestors
+pub(crate) struct VisibilityListener {
+    /// The fraction of the view which has to be visible for it to count as visible
+    pub(crate) threshold: f64,
+    pub(crate) visible: bool,
+    pub(crate) callback: Box<dyn Fn(bool)>,
+}
+
+impl VisibilityListener {
+    /// Calls the callback if the view became visible or stopped being visible, given the
+    /// fraction of the view which is visible.
+    pub(crate) fn update(&mut self, ratio: f64) {
+        let visible = ratio > 0.0 && ratio >= self.threshold;
+        if visible != self.visible {
+            self.visible = visible;
+            (*self.callback)(visible);
+        }
+    }
+}
+
+/// The fraction of a view of `size` inside `viewport`, in the coordinates of the view.
+fn visible_ratio(size: Size, viewport: Rect) -> f64 {
+    let area = size.area();
+    if area > 0.0 {
+        viewport.intersect(size.to_rect()).area() / area
+    } else if viewport.contains(Point::ZERO) {
+        // An empty view is visible when its origin is in the viewport
+        1.0
+    } else {
+        0.0
+    }
+}
+
 pub struct DragState {
     pub(crate) id: ViewId,
     pub(crate) offset: Vec2,
@@ -145,6 +180,7 @@ impl EventCx<'_> {
                 .event_before_children(self, &event)
                 .is_processed()
         {
+            self.capture_touch(view_id, &event);
             if let Event::PointerDown(event) = &event {
                 if self.app_state.keyboard_navigable.contains(&view_id) {
                     let rect = view_id.get_size().unwrap_or_default().to_rect();
@@ -188,6 +224,7 @@ impl EventCx<'_> {
                 .event_after_children(self, &event)
                 .is_processed()
         {
+            self.capture_touch(view_id, &event);
             return (EventPropagation::Stop, PointerEventConsumed::Yes);
         }
 
@@ -422,6 +459,14 @@ impl EventCx<'_> {
                         view_id.apply_event(&EventListener::Click, &event);
                     }
                 }
+                Event::Gamepad(GamepadEvent::ButtonDown {
+                    button: GamepadButton::South,
+                    ..
+                }) => {
+                    if self.app_state.is_focused(&view_id) {
+                        view_id.apply_event(&EventListener::Click, &event);
+                    }
+                }
                 Event::WindowResized(_) => {
                     if view_state.borrow().has_style_selectors.has_responsive() {
                         view_id.request_style();
@@ -435,17 +480,21 @@ impl EventCx<'_> {
             if let Some(listener) = event.listener() {
                 let event_listeners = view_state.borrow().event_listeners.clone();
                 if let Some(handlers) = event_listeners.get(&listener).cloned() {
-                    let should_run = if let Some(pos) = event.point() {
-                        let rect = view_id.get_size().unwrap_or_default().to_rect();
-                        rect.contains(pos)
-                    } else {
-                        true
+                    // The touches captured by the view are sent outside of it too
+                    let captured = directed && event.touch().is_some();
+                    let should_run = match event.point() {
+                        Some(pos) if !captured => {
+                            let rect = view_id.get_size().unwrap_or_default().to_rect();
+                            rect.contains(pos)
+                        }
+                        _ => true,
                     };
                     if should_run
                         && handlers.iter().fold(false, |handled, handler| {
                             handled | (handler.borrow_mut())(&event).is_processed()
                         })
                     {
+                        self.capture_touch(view_id, &event);
                         return (EventPropagation::Stop, PointerEventConsumed::Yes);
                     }
                 }
@@ -455,6 +504,15 @@ impl EventCx<'_> {
         (EventPropagation::Continue, PointerEventConsumed::Yes)
     }
 
+    /// Directs the following events of a touch to the view which handled its start.
+    fn capture_touch(&mut self, view_id: ViewId, event: &Event) {
+        if let Event::TouchStart(touch) = event {
+            if let Some(target) = self.app_state.touches.get_mut(&touch.finger_id) {
+                target.get_or_insert(view_id);
+            }
+        }
+    }
+
     /// translate a window-positioned event to the local coordinate system of a view
     pub(crate) fn offset_event(&self, id: ViewId, event: Event) -> Event {
         let state = id.state();
@@ -688,7 +746,13 @@ impl<'a> StyleCx<'a> {
             y: transform_y,
         });
 
-        let scale_x = view_state.borrow().layout_props.scale_x().0 / 100.;
+        let mirrored = {
+            let view_state = view_state.borrow();
+            view_state.computed_style.get(MirrorInRtl)
+                && view_state.layout_props.direction() == Direction::Rtl
+        };
+        let scale_x =
+            view_state.borrow().layout_props.scale_x().0 / 100. * if mirrored { -1. } else { 1. };
         let scale_y = view_state.borrow().layout_props.scale_y().0 / 100.;
         let size = view_id.layout_rect();
         let center_x = size.width() / 2.;
@@ -808,7 +872,7 @@ impl<'a> ComputeLayoutCx<'a> {
     /// It's responsible for:
     /// - calculating and setting the view's origin (local coordinates and window coordinates)
     /// - calculating and setting the view's viewport
-    /// - invoking any attached `context::ResizeListener`s
+    /// - invoking any attached `context::ResizeListener`s and `context::VisibilityListener`s
     ///
     /// Returns the bounding rect that encompasses this view and its children
     pub fn compute_view_layout(&mut self, id: ViewId) -> Option<Rect> {
@@ -816,6 +880,10 @@ impl<'a> ComputeLayoutCx<'a> {
 
         if view_state.borrow().is_hidden_state == IsHiddenState::Hidden {
             view_state.borrow_mut().layout_rect = Rect::ZERO;
+            let visibility_listeners = view_state.borrow().visibility_listeners.clone();
+            for listener in visibility_listeners {
+                listener.borrow_mut().update(0.0);
+            }
             return None;
         }
 
@@ -843,18 +911,26 @@ impl<'a> ComputeLayoutCx<'a> {
             view_state.borrow_mut().window_origin = window_origin;
         }
 
-        let resize_listener = view_state.borrow().resize_listener.clone();
-        if let Some(resize) = resize_listener.as_ref() {
+        let resize_listeners = view_state.borrow().resize_listeners.clone();
+        let new_rect = size.to_rect().with_origin(origin);
+        for resize in resize_listeners {
             let mut resize = resize.borrow_mut();
-            let new_rect = size.to_rect().with_origin(origin);
             if new_rect != resize.rect {
                 resize.rect = new_rect;
                 (*resize.callback)(new_rect);
             }
         }
 
-        let move_listener = view_state.borrow().move_listener.clone();
-        if let Some(listener) = move_listener {
+        let visibility_listeners = view_state.borrow().visibility_listeners.clone();
+        if !visibility_listeners.is_empty() {
+            let ratio = visible_ratio(size, parent_viewport);
+            for listener in visibility_listeners {
+                listener.borrow_mut().update(ratio);
+            }
+        }
+
+        let move_listeners = view_state.borrow().move_listeners.clone();
+        for listener in move_listeners {
             let mut listener = listener.borrow_mut();
             if window_origin != listener.window_origin {
                 listener.window_origin = window_origin;
@@ -1045,10 +1121,22 @@ impl PaintCx<'_> {
             }
 
             paint_bg(self, &view_style_props, size);
+            let (paint_under, paint_over) = {
+                let view_state = view_state.borrow();
+                (
+                    view_state.paint_under_listeners.clone(),
+                    view_state.paint_over_listeners.clone(),
+                )
+            };
+            self.paint_hooks(&paint_under, size);
 
             view.borrow_mut().paint(self);
             paint_border(self, &layout_props, &view_style_props, size);
-            paint_outline(self, &view_style_props, size)
+            paint_outline(self, &view_style_props, size);
+            if self.app_state.focus == Some(id) && self.app_state.keyboard_navigation {
+                paint_focus_ring(self, &view_style_props, size);
+            }
+            self.paint_hooks(&paint_over, size);
         }
         let mut drag_set_to_none = false;
 
@@ -1114,10 +1202,19 @@ impl PaintCx<'_> {
 
                     CURRENT_DRAG_PAINTING_ID.set(Some(id));
 
+                    let (paint_under, paint_over) = {
+                        let view_state = view_state.borrow();
+                        (
+                            view_state.paint_under_listeners.clone(),
+                            view_state.paint_over_listeners.clone(),
+                        )
+                    };
                     paint_bg(self, &view_style_props, size);
+                    self.paint_hooks(&paint_under, size);
                     view.borrow_mut().paint(self);
                     paint_border(self, &layout_props, &view_style_props, size);
                     paint_outline(self, &view_style_props, size);
+                    self.paint_hooks(&paint_over, size);
 
                     self.restore();
 
@@ -1132,6 +1229,17 @@ impl PaintCx<'_> {
         self.restore();
     }
 
+    /// Calls the paint hooks of a view, added with
+    /// [`on_paint_under`](crate::views::Decorators::on_paint_under) or
+    /// [`on_paint_over`](crate::views::Decorators::on_paint_over).
+    fn paint_hooks(&mut self, hooks: &[Rc<PaintCallback>], size: Size) {
+        for hook in hooks {
+            self.save();
+            hook(self, size.to_rect());
+            self.restore();
+        }
+    }
+
     /// Clip the drawing area to the given shape.
     pub fn clip(&mut self, shape: &impl Shape) {
         let rect = if let Some(rect) = shape.as_rect() {
@@ -1215,7 +1323,8 @@ pub enum PaintState {
     /// The renderer is not yet initialized. This state is used to wait for the GPU resources to be acquired.
     PendingGpuResources {
         window: Arc<dyn Window>,
-        rx: Receiver<Result<GpuResources, GpuResourceError>>,
+        /// `None` when the software renderer is used
+        rx: Option<Receiver<Result<GpuResources, GpuResourceError>>>,
         font_embolden: f32,
         /// This field holds an instance of `Renderer::Uninitialized` until the GPU resources are acquired,
         /// which will be returned in `PaintState::renderer` and `PaintState::renderer_mut`.
@@ -1232,7 +1341,7 @@ pub enum PaintState {
 impl PaintState {
     pub fn new(
         window: Arc<dyn Window>,
-        rx: Receiver<Result<GpuResources, GpuResourceError>>,
+        rx: Option<Receiver<Result<GpuResources, GpuResourceError>>>,
         scale: f64,
         size: Size,
         font_embolden: f32,
@@ -1253,7 +1362,7 @@ impl PaintState {
             renderer,
         } = self
         {
-            let gpu_resources = rx.recv().unwrap().unwrap();
+            let gpu_resources = rx.as_ref().map(|rx| rx.recv().unwrap());
             let renderer = crate::renderer::Renderer::new(
                 window.clone(),
                 gpu_resources,
@@ -1372,7 +1481,7 @@ fn stop_reset_remove_animations(id: ViewId) {
         .for_each(stop_reset_remove_animations)
 }
 
-fn animations_on_create(id: ViewId) {
+pub(crate) fn animations_on_create(id: ViewId) {
     let state = id.state();
     let mut state = state.borrow_mut();
     state.num_waiting_animations = 0;
@@ -1391,3 +1500,79 @@ fn animations_on_create(id: ViewId) {
 
     id.children().into_iter().for_each(animations_on_create);
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use floem_reactive::{RwSignal, SignalGet, SignalUpdate};
+    use peniko::kurbo::{Point, Rect, Size};
+
+    use crate::{
+        testing::TestHarness,
+        views::{empty, scroll, v_stack, Decorators},
+    };
+
+    use super::visible_ratio;
+
+    #[test]
+    fn ratios() {
+        let size = Size::new(100.0, 100.0);
+        assert_eq!(visible_ratio(size, Rect::new(0.0, 0.0, 200.0, 200.0)), 1.0);
+        assert_eq!(visible_ratio(size, Rect::new(0.0, 50.0, 200.0, 200.0)), 0.5);
+        assert_eq!(
+            visible_ratio(size, Rect::new(0.0, 150.0, 200.0, 200.0)),
+            0.0
+        );
+        assert_eq!(
+            visible_ratio(Size::ZERO, Rect::new(-5.0, -5.0, 5.0, 5.0)),
+            1.0
+        );
+        assert_eq!(
+            visible_ratio(Size::ZERO, Rect::new(5.0, 5.0, 10.0, 10.0)),
+            0.0
+        );
+    }
+
+    #[test]
+    fn visibility_in_scroll() {
+        let changes = Rc::new(RefCell::new(Vec::new()));
+        let offset = RwSignal::new(0.0);
+        let mut harness = TestHarness::new_with_size(
+            {
+                let changes = changes.clone();
+                move || {
+                    scroll(
+                        v_stack((
+                            empty().style(|s| s.height(200.0)),
+                            empty()
+                                .style(|s| s.height(100.0).width_full())
+                                .on_visibility_change(0.5, move |visible| {
+                                    changes.borrow_mut().push(visible)
+                                }),
+                            empty().style(|s| s.height(200.0)),
+                        ))
+                        .style(|s| s.width_full()),
+                    )
+                    .scroll_to(move || Some(Point::new(0.0, offset.get())))
+                    .style(|s| s.size(100.0, 100.0))
+                }
+            },
+            Size::new(100.0, 100.0),
+        );
+        assert!(changes.borrow().is_empty());
+
+        // A quarter of the view is visible, which is below the threshold
+        offset.set(125.0);
+        harness.update();
+        assert!(changes.borrow().is_empty());
+
+        offset.set(150.0);
+        harness.update();
+        assert_eq!(*changes.borrow(), [true]);
+
+        offset.set(300.0);
+        harness.update();
+        assert_eq!(*changes.borrow(), [true, false]);
+    }
+}
@@ -0,0 +1,127 @@
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use winit::{event_loop::ActiveEventLoop, window::CursorIcon};
+
+use crate::{
+    app::{add_app_update_event, AppUpdateEvent},
+    style::CursorStyle,
+};
+
+thread_local! {
+    static PLATFORM_CURSORS: RefCell<HashMap<CustomCursor, winit::window::CustomCursor>> =
+        Default::default();
+}
+
+/// A cursor made from an image, shown with [`CursorStyle::Custom`].
+///
+/// Creating a cursor uploads the image to the platform, so create it once and reuse the
+/// returned handle in styles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CustomCursor(u64);
+
+/// The error returned when a [`CustomCursor`] can't be created.
+#[derive(Debug)]
+pub enum CustomCursorError {
+    /// The image couldn't be decoded
+    Decode(image::ImageError),
+    /// The image is too large or the hotspot is outside of the image
+    BadImage(winit::window::BadImage),
+}
+
+impl CustomCursor {
+    /// Creates a cursor from raw RGBA pixels. The hotspot is the pixel of the image which
+    /// is at the pointer position, e.g. the tip of an arrow.
+    pub fn from_rgba(
+        rgba: Vec<u8>,
+        width: u16,
+        height: u16,
+        hotspot_x: u16,
+        hotspot_y: u16,
+    ) -> Result<Self, CustomCursorError> {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+        let source =
+            winit::window::CustomCursor::from_rgba(rgba, width, height, hotspot_x, hotspot_y)
+                .map_err(CustomCursorError::BadImage)?;
+        let cursor = CustomCursor(NEXT_ID.fetch_add(1, Ordering::Relaxed));
+        add_app_update_event(AppUpdateEvent::CreateCustomCursor { cursor, source });
+        Ok(cursor)
+    }
+
+    /// Creates a cursor by decoding an image file (e.g. a PNG) from its bytes.
+    ///
+    /// The supported formats depend on the enabled `image-*` features.
+    pub fn from_bytes(
+        bytes: &[u8],
+        hotspot_x: u16,
+        hotspot_y: u16,
+    ) -> Result<Self, CustomCursorError> {
+        let image = image::load_from_memory(bytes)
+            .map_err(CustomCursorError::Decode)?
+            .into_rgba8();
+        let (width, height) = image.dimensions();
+        let width = u16::try_from(width).unwrap_or(u16::MAX);
+        let height = u16::try_from(height).unwrap_or(u16::MAX);
+        Self::from_rgba(image.into_raw(), width, height, hotspot_x, hotspot_y)
+    }
+}
+
+pub(crate) fn create_platform_cursor(
+    event_loop: &dyn ActiveEventLoop,
+    cursor: CustomCursor,
+    source: winit::window::CustomCursorSource,
+) {
+    if let Ok(platform_cursor) = event_loop.create_custom_cursor(source) {
+        PLATFORM_CURSORS.with_borrow_mut(|cursors| cursors.insert(cursor, platform_cursor));
+    }
+}
+
+/// Returns the cursor to show on the window, or `None` if it's a custom cursor which
+/// isn't created yet.
+pub(crate) fn platform_cursor(cursor: CursorStyle) -> Option<winit::window::Cursor> {
+    let icon = match cursor {
+        CursorStyle::Custom(cursor) => {
+            return PLATFORM_CURSORS
+                .with_borrow(|cursors| cursors.get(&cursor).cloned().map(Into::into))
+        }
+        CursorStyle::Default => CursorIcon::Default,
+        CursorStyle::ContextMenu => CursorIcon::ContextMenu,
+        CursorStyle::Help => CursorIcon::Help,
+        CursorStyle::Pointer => CursorIcon::Pointer,
+        CursorStyle::Progress => CursorIcon::Progress,
+        CursorStyle::Wait => CursorIcon::Wait,
+        CursorStyle::Cell => CursorIcon::Cell,
+        CursorStyle::Crosshair => CursorIcon::Crosshair,
+        CursorStyle::Text => CursorIcon::Text,
+        CursorStyle::VerticalText => CursorIcon::VerticalText,
+        CursorStyle::Alias => CursorIcon::Alias,
+        CursorStyle::Copy => CursorIcon::Copy,
+        CursorStyle::Move => CursorIcon::Move,
+        CursorStyle::NoDrop => CursorIcon::NoDrop,
+        CursorStyle::NotAllowed => CursorIcon::NotAllowed,
+        CursorStyle::Grab => CursorIcon::Grab,
+        CursorStyle::Grabbing => CursorIcon::Grabbing,
+        CursorStyle::AllScroll => CursorIcon::AllScroll,
+        CursorStyle::ZoomIn => CursorIcon::ZoomIn,
+        CursorStyle::ZoomOut => CursorIcon::ZoomOut,
+        CursorStyle::ColResize => CursorIcon::ColResize,
+        CursorStyle::RowResize => CursorIcon::RowResize,
+        CursorStyle::WResize => CursorIcon::WResize,
+        CursorStyle::EResize => CursorIcon::EResize,
+        CursorStyle::SResize => CursorIcon::SResize,
+        CursorStyle::NResize => CursorIcon::NResize,
+        CursorStyle::NwResize => CursorIcon::NwResize,
+        CursorStyle::NeResize => CursorIcon::NeResize,
+        CursorStyle::SwResize => CursorIcon::SwResize,
+        CursorStyle::SeResize => CursorIcon::SeResize,
+        CursorStyle::EwResize => CursorIcon::EwResize,
+        CursorStyle::NsResize => CursorIcon::NsResize,
+        CursorStyle::NeswResize => CursorIcon::NeswResize,
+        CursorStyle::NwseResize => CursorIcon::NwseResize,
+    };
+    Some(icon.into())
+}
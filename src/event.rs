@@ -1,4 +1,4 @@
-use peniko::kurbo::{Affine, Point, Size};
+use peniko::kurbo::{Affine, Point, Size, Vec2};
 use winit::{
     keyboard::{KeyCode, PhysicalKey},
     window::Theme,
@@ -6,8 +6,9 @@ use winit::{
 
 use crate::{
     dropped_file::DroppedFileEvent,
+    gamepad::GamepadEvent,
     keyboard::KeyEvent,
-    pointer::{PointerInputEvent, PointerMoveEvent, PointerWheelEvent},
+    pointer::{PointerInputEvent, PointerMoveEvent, PointerWheelEvent, TouchEvent},
     touchpad::PinchGestureEvent,
 };
 
@@ -98,8 +99,26 @@ pub enum EventListener {
     WindowLostFocus,
     /// Receives [`Event::WindowMaximizeChanged`]
     WindowMaximizeChanged,
+    /// Receives [`Event::WindowScaleChanged`]
+    WindowScaleChanged,
+    /// Receives [`Event::AccessibilityAction`]
+    AccessibilityAction,
     /// Receives [`Event::DroppedFile`]
     DroppedFile,
+    /// Receives [`Event::PointerDelta`]
+    PointerDelta,
+    /// Receives [`Event::PointerLockReleased`]
+    PointerLockReleased,
+    /// Receives [`Event::TouchStart`]
+    TouchStart,
+    /// Receives [`Event::TouchMove`]
+    TouchMove,
+    /// Receives [`Event::TouchEnd`]
+    TouchEnd,
+    /// Receives [`Event::TouchCancel`]
+    TouchCancel,
+    /// Receives [`Event::Gamepad`]
+    Gamepad,
 }
 
 #[derive(Debug, Clone)]
@@ -126,9 +145,34 @@ pub enum Event {
     WindowResized(Size),
     WindowMoved(Point),
     WindowMaximizeChanged(bool),
+    /// The scale factor of the window changed, e.g. because it was moved to a
+    /// monitor with a different DPI
+    WindowScaleChanged(f64),
     ThemeChanged(Theme),
+    /// An assistive technology requested an action on the view, e.g. to increment a
+    /// slider. Only sent to the target view.
+    AccessibilityAction(accesskit::ActionRequest),
     FocusGained,
     FocusLost,
+    /// The relative motion of the pointer while it's locked to the view, see
+    /// [`ViewId::request_pointer_lock`](crate::ViewId::request_pointer_lock). Only sent to the
+    /// view holding the lock.
+    PointerDelta(Vec2),
+    /// The pointer lock of the view was released. Only sent to the view which held the lock.
+    PointerLockReleased,
+    /// A finger touched the screen. This is sent in addition to the pointer events, which are
+    /// only synthesized for one finger, to track multiple fingers for gestures or drawing.
+    ///
+    /// The following touch events of the finger are sent to the view which handled the start
+    /// of the touch, even when the finger moves out of it.
+    TouchStart(TouchEvent),
+    TouchMove(TouchEvent),
+    TouchEnd(TouchEvent),
+    /// The touch was interrupted by the platform, e.g. because it turned into a system gesture.
+    TouchCancel(TouchEvent),
+    /// A button or an axis of a game controller changed, or a controller was connected. Sent
+    /// to the focused view like key events, see [`gamepad`](crate::gamepad).
+    Gamepad(GamepadEvent),
 }
 
 impl Event {
@@ -151,10 +195,18 @@ impl Event {
             | Event::WindowResized(_)
             | Event::WindowMoved(_)
             | Event::WindowMaximizeChanged(_)
+            | Event::WindowScaleChanged(_)
+            | Event::AccessibilityAction(_)
             | Event::WindowGotFocus
             | Event::WindowLostFocus
-            | Event::DroppedFile(_) => false,
-            Event::KeyDown(_) | Event::KeyUp(_) => true,
+            | Event::DroppedFile(_)
+            | Event::PointerDelta(_)
+            | Event::PointerLockReleased
+            | Event::TouchStart(_)
+            | Event::TouchMove(_)
+            | Event::TouchEnd(_)
+            | Event::TouchCancel(_) => false,
+            Event::KeyDown(_) | Event::KeyUp(_) | Event::Gamepad(_) => true,
         }
     }
 
@@ -179,9 +231,29 @@ impl Event {
             | Event::WindowResized(_)
             | Event::WindowMoved(_)
             | Event::WindowMaximizeChanged(_)
+            | Event::WindowScaleChanged(_)
+            | Event::AccessibilityAction(_)
             | Event::WindowGotFocus
             | Event::WindowLostFocus
-            | Event::DroppedFile(_) => false,
+            | Event::DroppedFile(_)
+            | Event::PointerDelta(_)
+            | Event::PointerLockReleased
+            | Event::TouchStart(_)
+            | Event::TouchMove(_)
+            | Event::TouchEnd(_)
+            | Event::TouchCancel(_)
+            | Event::Gamepad(_) => false,
+        }
+    }
+
+    /// The touch of the event, for the touch events.
+    pub fn touch(&self) -> Option<&TouchEvent> {
+        match self {
+            Event::TouchStart(touch)
+            | Event::TouchMove(touch)
+            | Event::TouchEnd(touch)
+            | Event::TouchCancel(touch) => Some(touch),
+            _ => None,
         }
     }
 
@@ -212,7 +284,14 @@ impl Event {
             | Event::ImePreedit { .. }
             | Event::ImeCommit(_)
             | Event::KeyDown(_)
-            | Event::KeyUp(_) => false,
+            | Event::KeyUp(_)
+            | Event::AccessibilityAction(_)
+            | Event::PointerDelta(_)
+            | Event::TouchStart(_)
+            | Event::TouchMove(_)
+            | Event::TouchEnd(_)
+            | Event::TouchCancel(_)
+            | Event::Gamepad(_) => false,
             Event::PinchGesture(_)
             | Event::PointerLeave
             | Event::PointerMove(_)
@@ -222,8 +301,10 @@ impl Event {
             | Event::WindowMoved(_)
             | Event::WindowGotFocus
             | Event::WindowMaximizeChanged(_)
+            | Event::WindowScaleChanged(_)
             | Event::WindowLostFocus
-            | Event::DroppedFile(_) => true,
+            | Event::DroppedFile(_)
+            | Event::PointerLockReleased => true,
         }
     }
 
@@ -235,6 +316,10 @@ impl Event {
             Event::PointerMove(pointer_event) => Some(pointer_event.pos),
             Event::PointerWheel(pointer_event) => Some(pointer_event.pos),
             Event::DroppedFile(event) => Some(event.pos),
+            Event::TouchStart(touch)
+            | Event::TouchMove(touch)
+            | Event::TouchEnd(touch)
+            | Event::TouchCancel(touch) => Some(touch.pos),
             Event::PinchGesture(_)
             | Event::PointerLeave
             | Event::KeyDown(_)
@@ -250,8 +335,13 @@ impl Event {
             | Event::WindowResized(_)
             | Event::WindowMoved(_)
             | Event::WindowMaximizeChanged(_)
+            | Event::WindowScaleChanged(_)
+            | Event::AccessibilityAction(_)
             | Event::WindowGotFocus
-            | Event::WindowLostFocus => None,
+            | Event::WindowLostFocus
+            | Event::PointerDelta(_)
+            | Event::PointerLockReleased
+            | Event::Gamepad(_) => None,
         }
     }
 
@@ -273,6 +363,12 @@ impl Event {
             Event::DroppedFile(event) => {
                 event.pos = transform.inverse() * event.pos;
             }
+            Event::TouchStart(touch)
+            | Event::TouchMove(touch)
+            | Event::TouchEnd(touch)
+            | Event::TouchCancel(touch) => {
+                touch.pos = transform.inverse() * touch.pos;
+            }
             Event::PinchGesture(_)
             | Event::PointerLeave
             | Event::KeyDown(_)
@@ -288,8 +384,13 @@ impl Event {
             | Event::WindowResized(_)
             | Event::WindowMoved(_)
             | Event::WindowMaximizeChanged(_)
+            | Event::WindowScaleChanged(_)
+            | Event::AccessibilityAction(_)
             | Event::WindowGotFocus
-            | Event::WindowLostFocus => {}
+            | Event::WindowLostFocus
+            | Event::PointerDelta(_)
+            | Event::PointerLockReleased
+            | Event::Gamepad(_) => {}
         }
         self
     }
@@ -312,12 +413,21 @@ impl Event {
             Event::WindowResized(_) => Some(EventListener::WindowResized),
             Event::WindowMoved(_) => Some(EventListener::WindowMoved),
             Event::WindowMaximizeChanged(_) => Some(EventListener::WindowMaximizeChanged),
+            Event::WindowScaleChanged(_) => Some(EventListener::WindowScaleChanged),
+            Event::AccessibilityAction(_) => Some(EventListener::AccessibilityAction),
             Event::WindowGotFocus => Some(EventListener::WindowGotFocus),
             Event::WindowLostFocus => Some(EventListener::WindowLostFocus),
             Event::FocusLost => Some(EventListener::FocusLost),
             Event::FocusGained => Some(EventListener::FocusGained),
             Event::ThemeChanged(_) => Some(EventListener::ThemeChanged),
             Event::DroppedFile(_) => Some(EventListener::DroppedFile),
+            Event::PointerDelta(_) => Some(EventListener::PointerDelta),
+            Event::PointerLockReleased => Some(EventListener::PointerLockReleased),
+            Event::TouchStart(_) => Some(EventListener::TouchStart),
+            Event::TouchMove(_) => Some(EventListener::TouchMove),
+            Event::TouchEnd(_) => Some(EventListener::TouchEnd),
+            Event::TouchCancel(_) => Some(EventListener::TouchCancel),
+            Event::Gamepad(_) => Some(EventListener::Gamepad),
         }
     }
 }
@@ -1,10 +1,20 @@
-use std::{cell::Cell, collections::VecDeque, sync::Arc};
+use std::{
+    cell::{Cell, RefCell},
+    collections::VecDeque,
+    future::Future,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    task::{Context, Wake, Waker},
+    thread::{self, ThreadId},
+};
 
 use floem_reactive::{
-    create_effect, create_rw_signal, untrack, with_scope, ReadSignal, RwSignal, Scope, SignalGet,
-    SignalUpdate, SignalWith, WriteSignal,
+    create_effect, create_effect_with_priority, create_rw_signal, untrack, with_scope,
+    EffectPriority, ReadSignal, RwSignal, Scope, SignalGet, SignalUpdate, SignalWith, WriteSignal,
 };
-use parking_lot::Mutex;
+use parking_lot::{Condvar, Mutex, RwLock};
 
 use crate::{
     app::UserEvent,
@@ -126,6 +136,191 @@ pub fn create_ext_action<T: Send + 'static>(
     }
 }
 
+/// Waker that wakes by registering a trigger
+struct TriggerWaker(ExtSendTrigger);
+
+impl Wake for TriggerWaker {
+    fn wake(self: Arc<Self>) {
+        EXT_EVENT_HANDLER.add_trigger(self.0);
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        EXT_EVENT_HANDLER.add_trigger(self.0);
+    }
+}
+
+/// Runs futures on the UI thread, tied to the lifetime of a [`Scope`].
+pub trait SpawnLocalExt {
+    /// Runs the future on the UI thread, without blocking it.
+    ///
+    /// The future is polled when the event loop is idle, so it can read and write signals.
+    /// It's dropped, and so cancelled, when the scope is disposed, e.g. when the view which
+    /// spawned it is removed.
+    ///
+    /// The future runs on the UI thread, so it shouldn't do blocking work. Wait for
+    /// blocking work running on another thread instead, e.g. with a channel.
+    fn spawn_local(self, future: impl Future<Output = ()> + 'static);
+}
+
+impl SpawnLocalExt for Scope {
+    fn spawn_local(self, future: impl Future<Output = ()> + 'static) {
+        let view = get_current_view();
+        let cx = self.create_child();
+        let trigger = with_scope(cx, ExtSendTrigger::new);
+        let waker = Waker::from(Arc::new(TriggerWaker(trigger)));
+
+        // We need a refcell because effects are `Fn` and not `FnMut`
+        let future = RefCell::new(Some(Box::pin(future)));
+        cx.create_effect(move |_| {
+            // Poll the future when the waker is called
+            trigger.track();
+            let Ok(mut future) = future.try_borrow_mut() else {
+                unreachable!("The waker registers events effects to be run only at idle")
+            };
+            let Some(pinned) = future.as_mut() else {
+                return;
+            };
+            let current_view = get_current_view();
+            set_current_view(view);
+            let poll = untrack(|| pinned.as_mut().poll(&mut Context::from_waker(&waker)));
+            set_current_view(current_view);
+            if poll.is_ready() {
+                *future = None;
+                cx.dispose();
+            }
+        });
+    }
+}
+
+/// Creates a signal with the result of an async function, which runs on the UI thread.
+///
+/// The function is called with the value returned by `source`, and called again when the
+/// signals read by `source` change. The signal is `None` until the first result is ready,
+/// and keeps the previous result while a new one is loading. A result which is still
+/// loading when the source changes, or when the current scope is disposed, is cancelled.
+///
+/// ```rust,ignore
+/// let user_id = RwSignal::new(1);
+/// let user = create_resource(move || user_id.get(), |id| async move { fetch_user(id).await });
+/// ```
+pub fn create_resource<S, T, Fut>(
+    source: impl Fn() -> S + 'static,
+    fetcher: impl Fn(S) -> Fut + 'static,
+) -> ReadSignal<Option<T>>
+where
+    S: 'static,
+    T: 'static,
+    Fut: Future<Output = T> + 'static,
+{
+    let cx = Scope::current().create_child();
+    let (read, write) = cx.create_signal(None);
+
+    cx.create_effect(move |previous_task: Option<Scope>| {
+        let source = source();
+        if let Some(previous_task) = previous_task {
+            // Cancel the result which is still loading
+            previous_task.dispose();
+        }
+        let task = cx.create_child();
+        let future = untrack(|| fetcher(source));
+        task.spawn_local(async move {
+            let value = future.await;
+            write.set(Some(value));
+        });
+        task
+    });
+
+    read
+}
+
+/// Limits how many values received from a channel wait for the UI thread, see
+/// [`on_channel`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Backpressure {
+    /// Every value is delivered, in order, however many are waiting.
+    #[default]
+    Unbounded,
+    /// Only the newest value is delivered, the values it replaced are dropped. Useful for
+    /// values which supersede each other, like progress updates.
+    Latest,
+    /// At most this many values wait for the UI thread. Until it catches up, the channel
+    /// isn't read, so senders on a bounded channel wait as well.
+    Bounded(usize),
+}
+
+/// Calls `on_value` on the UI thread with each value received from the channel, until
+/// the channel is closed.
+///
+/// The values are delivered when the event loop wakes up, so worker threads can send
+/// values at any rate without blocking the UI. `backpressure` limits how many values
+/// can wait for the UI thread.
+pub fn on_channel<T: Send + 'static>(
+    rx: Receiver<T>,
+    backpressure: Backpressure,
+    on_value: impl Fn(T) + 'static,
+) {
+    let cx = Scope::new();
+    let trigger = with_scope(cx, ExtSendTrigger::new);
+
+    let channel_closed = cx.create_rw_signal(false);
+    let pending = Arc::new((Mutex::new(VecDeque::new()), Condvar::new()));
+
+    {
+        let pending = pending.clone();
+        cx.create_effect(move |_| {
+            trigger.track();
+            let (values, space) = &*pending;
+            let values = std::mem::take(&mut *values.lock());
+            space.notify_one();
+            untrack(|| {
+                for value in values {
+                    on_value(value);
+                }
+            });
+
+            if channel_closed.get() {
+                cx.dispose();
+            }
+        });
+    }
+
+    let send = create_ext_action(cx, move |_| {
+        channel_closed.set(true);
+    });
+
+    std::thread::spawn(move || {
+        while let Ok(value) = rx.recv() {
+            let (values, space) = &*pending;
+            let mut values = values.lock();
+            match backpressure {
+                Backpressure::Unbounded => {}
+                Backpressure::Latest => values.clear(),
+                Backpressure::Bounded(max) => {
+                    while values.len() >= max.max(1) {
+                        space.wait(&mut values);
+                    }
+                }
+            }
+            values.push_back(value);
+            drop(values);
+            EXT_EVENT_HANDLER.add_trigger(trigger);
+        }
+        send(());
+    });
+}
+
+/// Creates a signal which is set to each value received from the channel, until the
+/// channel is closed. See [`on_channel`] for how the values are delivered.
+pub fn signal_from_channel<T: Send + 'static>(
+    rx: Receiver<T>,
+    initial_value: T,
+    backpressure: Backpressure,
+) -> RwSignal<T> {
+    let signal = create_rw_signal(initial_value);
+    on_channel(rx, backpressure, move |value| signal.set(value));
+    signal
+}
+
 pub fn update_signal_from_channel<T: Send + 'static>(
     writer: WriteSignal<Option<T>>,
     rx: Receiver<T>,
@@ -304,3 +499,185 @@ pub fn create_signal_from_stream<T: 'static>(
 
     read
 }
+
+struct ArcSignalInner<T> {
+    value: RwLock<T>,
+    trigger: ExtSendTrigger,
+    ui_thread: ThreadId,
+    /// A notification from another thread is waiting for the UI thread
+    notify_pending: AtomicBool,
+}
+
+impl<T> ArcSignalInner<T> {
+    fn track(&self) {
+        if thread::current().id() == self.ui_thread {
+            self.trigger.track();
+        }
+    }
+
+    fn notify(&self) {
+        if thread::current().id() == self.ui_thread {
+            self.trigger.notify();
+        } else if !self.notify_pending.swap(true, Ordering::AcqRel) {
+            EXT_EVENT_HANDLER.add_trigger(self.trigger);
+        }
+    }
+}
+
+/// A signal which can be read and written from any thread, e.g. to report the progress of
+/// work running on a worker thread.
+///
+/// Reading it on the UI thread subscribes to it like any other signal. Writes from other
+/// threads notify the subscribers once the UI thread gets to it, and writes made in the
+/// meantime are coalesced into one notification.
+///
+/// It has to be created on the UI thread, and stops notifying once the scope it was
+/// created in is disposed.
+///
+/// ```rust,ignore
+/// let progress = create_arc_rw_signal(0.0);
+/// std::thread::spawn({
+///     let progress = progress.clone();
+///     move || {
+///         for i in 0..=100 {
+///             do_work(i);
+///             progress.set(i as f64 / 100.0);
+///         }
+///     }
+/// });
+/// label(move || format!("{:.0}%", progress.get() * 100.0))
+/// ```
+pub struct ArcRwSignal<T> {
+    inner: Arc<ArcSignalInner<T>>,
+}
+
+impl<T> Clone for ArcRwSignal<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+/// The read half of an [`ArcRwSignal`].
+pub struct ArcReadSignal<T> {
+    inner: Arc<ArcSignalInner<T>>,
+}
+
+impl<T> Clone for ArcReadSignal<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+/// The write half of an [`ArcRwSignal`].
+pub struct ArcWriteSignal<T> {
+    inner: Arc<ArcSignalInner<T>>,
+}
+
+impl<T> Clone for ArcWriteSignal<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+macro_rules! arc_signal_read {
+    ($name:ident) => {
+        impl<T> $name<T> {
+            /// Clones the value, and subscribes to it on the UI thread.
+            pub fn get(&self) -> T
+            where
+                T: Clone,
+            {
+                self.with(T::clone)
+            }
+
+            pub fn get_untracked(&self) -> T
+            where
+                T: Clone,
+            {
+                self.with_untracked(T::clone)
+            }
+
+            /// Reads the value, and subscribes to it on the UI thread.
+            pub fn with<O>(&self, f: impl FnOnce(&T) -> O) -> O {
+                self.inner.track();
+                self.with_untracked(f)
+            }
+
+            pub fn with_untracked<O>(&self, f: impl FnOnce(&T) -> O) -> O {
+                f(&self.inner.value.read())
+            }
+        }
+    };
+}
+
+macro_rules! arc_signal_write {
+    ($name:ident) => {
+        impl<T> $name<T> {
+            pub fn set(&self, value: T) {
+                self.update(|v| *v = value);
+            }
+
+            pub fn update(&self, f: impl FnOnce(&mut T)) {
+                f(&mut self.inner.value.write());
+                self.inner.notify();
+            }
+        }
+    };
+}
+
+arc_signal_read!(ArcRwSignal);
+arc_signal_read!(ArcReadSignal);
+arc_signal_write!(ArcRwSignal);
+arc_signal_write!(ArcWriteSignal);
+
+impl<T> ArcRwSignal<T> {
+    pub fn read_only(&self) -> ArcReadSignal<T> {
+        ArcReadSignal {
+            inner: self.inner.clone(),
+        }
+    }
+
+    pub fn write_only(&self) -> ArcWriteSignal<T> {
+        ArcWriteSignal {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+/// Create an [`ArcRwSignal`], which can be used from any thread. This has to be called on
+/// the UI thread.
+pub fn create_arc_rw_signal<T: Send + Sync + 'static>(value: T) -> ArcRwSignal<T> {
+    let trigger = ExtSendTrigger::new();
+    let inner = Arc::new(ArcSignalInner {
+        value: RwLock::new(value),
+        trigger,
+        ui_thread: thread::current().id(),
+        notify_pending: AtomicBool::new(false),
+    });
+    // Runs before the effects which read the value, so that a write which happens after
+    // they read it sends a new notification
+    create_effect_with_priority(EffectPriority::Input, {
+        let inner = Arc::downgrade(&inner);
+        move |_| {
+            trigger.track();
+            if let Some(inner) = inner.upgrade() {
+                inner.notify_pending.store(false, Ordering::Release);
+            }
+        }
+    });
+    ArcRwSignal { inner }
+}
+
+/// Create the read and write halves of an [`ArcRwSignal`], see [`create_arc_rw_signal`].
+pub fn create_arc_signal<T: Send + Sync + 'static>(
+    value: T,
+) -> (ArcReadSignal<T>, ArcWriteSignal<T>) {
+    let signal = create_arc_rw_signal(value);
+    (signal.read_only(), signal.write_only())
+}
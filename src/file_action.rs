@@ -1,12 +1,38 @@
-use std::path::PathBuf;
+use std::{
+    future::Future,
+    path::PathBuf,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll, Waker},
+};
 
-use floem_reactive::Scope;
+use floem_reactive::{ReadSignal, Scope, SignalUpdate};
+use parking_lot::Mutex;
 
 use crate::{
     ext_event::create_ext_action,
     file::{FileDialogOptions, FileInfo},
 };
 
+fn dialog(options: &FileDialogOptions) -> rfd::FileDialog {
+    let mut dialog = rfd::FileDialog::new();
+    if let Some(path) = options.starting_directory.as_ref() {
+        dialog = dialog.set_directory(path);
+    }
+    if let Some(name) = options.default_name.as_ref() {
+        dialog = dialog.set_file_name(name);
+    }
+    if let Some(title) = options.title.as_ref() {
+        dialog = dialog.set_title(title);
+    }
+    if let Some(allowed_types) = options.allowed_types.as_ref() {
+        dialog = allowed_types.iter().fold(dialog, |dialog, filter| {
+            dialog.add_filter(filter.name, filter.extensions)
+        });
+    }
+    dialog
+}
+
 /// Open a file using the system file dialog
 pub fn open_file(
     options: FileDialogOptions,
@@ -29,18 +55,7 @@ pub fn open_file(
         },
     );
     std::thread::spawn(move || {
-        let mut dialog = rfd::FileDialog::new();
-        if let Some(path) = options.starting_directory.as_ref() {
-            dialog = dialog.set_directory(path);
-        }
-        if let Some(title) = options.title.as_ref() {
-            dialog = dialog.set_title(title);
-        }
-        if let Some(allowed_types) = options.allowed_types.as_ref() {
-            dialog = allowed_types.iter().fold(dialog, |dialog, filter| {
-                dialog.add_filter(filter.name, filter.extensions)
-            });
-        }
+        let dialog = dialog(&options);
 
         if options.select_directories && options.multi_selection {
             send((None, dialog.pick_folders()));
@@ -63,17 +78,112 @@ pub fn save_as(options: FileDialogOptions, file_info_action: impl Fn(Option<File
         }))
     });
     std::thread::spawn(move || {
-        let mut dialog = rfd::FileDialog::new();
-        if let Some(path) = options.starting_directory.as_ref() {
-            dialog = dialog.set_directory(path);
+        let path = dialog(&options).save_file();
+        send(path);
+    });
+}
+
+/// Show a system dialog to pick a single file.
+///
+/// The returned [`FileDialogFuture`] resolves to `None` if the dialog was cancelled.
+pub fn pick_file(options: FileDialogOptions) -> FileDialogFuture<Option<PathBuf>> {
+    FileDialogFuture::spawn(move || dialog(&options).pick_file())
+}
+
+/// Show a system dialog to pick one or more files.
+///
+/// The returned [`FileDialogFuture`] resolves to `None` if the dialog was cancelled.
+pub fn pick_files(options: FileDialogOptions) -> FileDialogFuture<Option<Vec<PathBuf>>> {
+    FileDialogFuture::spawn(move || dialog(&options).pick_files())
+}
+
+/// Show a system dialog to pick a folder.
+///
+/// The returned [`FileDialogFuture`] resolves to `None` if the dialog was cancelled.
+pub fn pick_folder(options: FileDialogOptions) -> FileDialogFuture<Option<PathBuf>> {
+    FileDialogFuture::spawn(move || dialog(&options).pick_folder())
+}
+
+/// Show a system dialog to choose where to save a file.
+///
+/// The returned [`FileDialogFuture`] resolves to `None` if the dialog was cancelled.
+pub fn save_file(options: FileDialogOptions) -> FileDialogFuture<Option<PathBuf>> {
+    FileDialogFuture::spawn(move || dialog(&options).save_file())
+}
+
+struct DialogState<T> {
+    result: Option<T>,
+    waker: Option<Waker>,
+    on_result: Option<Box<dyn FnOnce(T) + Send>>,
+}
+
+/// The result of a file dialog which is shown in the background.
+///
+/// The result can be awaited from any executor, or be delivered on the UI thread with
+/// [`FileDialogFuture::on_result`] or [`FileDialogFuture::into_signal`].
+pub struct FileDialogFuture<T> {
+    state: Arc<Mutex<DialogState<T>>>,
+}
+
+impl<T: Send + 'static> FileDialogFuture<T> {
+    fn spawn(dialog: impl FnOnce() -> T + Send + 'static) -> Self {
+        let state = Arc::new(Mutex::new(DialogState {
+            result: None,
+            waker: None,
+            on_result: None,
+        }));
+        {
+            let state = state.clone();
+            std::thread::spawn(move || {
+                let result = dialog();
+                let mut state = state.lock();
+                if let Some(on_result) = state.on_result.take() {
+                    drop(state);
+                    on_result(result);
+                } else {
+                    state.result = Some(result);
+                    if let Some(waker) = state.waker.take() {
+                        waker.wake();
+                    }
+                }
+            });
         }
-        if let Some(name) = options.default_name.as_ref() {
-            dialog = dialog.set_file_name(name);
+        Self { state }
+    }
+
+    /// Run `action` on the UI thread once the dialog is closed.
+    pub fn on_result(self, action: impl FnOnce(T) + 'static) {
+        let send = create_ext_action(Scope::new(), action);
+        let mut state = self.state.lock();
+        if let Some(result) = state.result.take() {
+            drop(state);
+            send(result);
+        } else {
+            state.on_result = Some(Box::new(send));
         }
-        if let Some(title) = options.title.as_ref() {
-            dialog = dialog.set_title(title);
+    }
+
+    /// Convert the dialog into a signal which is `None` while the dialog is open, and is
+    /// set to the result once it's closed.
+    ///
+    /// The signal is created in the current [`Scope`].
+    pub fn into_signal(self) -> ReadSignal<Option<T>> {
+        let (read, write) = Scope::current().create_signal(None);
+        self.on_result(move |result| write.set(Some(result)));
+        read
+    }
+}
+
+impl<T> Future for FileDialogFuture<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        let mut state = self.state.lock();
+        if let Some(result) = state.result.take() {
+            Poll::Ready(result)
+        } else {
+            state.waker = Some(cx.waker().clone());
+            Poll::Pending
         }
-        let path = dialog.save_file();
-        send(path);
-    });
+    }
 }
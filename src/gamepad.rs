@@ -0,0 +1,222 @@
+#![deny(missing_docs)]
+
+//! Game controller input.
+//!
+//! With the `gamepad` feature, the buttons and axes of the connected game controllers are
+//! sent as [`Event::Gamepad`](crate::event::Event::Gamepad) to the focused view of the
+//! focused window, like key events. When the focused view doesn't handle them, the d-pad
+//! moves the focus like the arrow keys and the south button (A on Xbox controllers) clicks
+//! the focused view, so that apps built for the keyboard can be used from a sofa, like
+//! media centers and kiosks.
+//!
+//! Platform support:
+//! - Windows, macOS, Linux: Yes
+//! - Web, Android, iOS: No, the events are never sent
+
+/// Identifies a connected game controller, until it's disconnected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GamepadId(pub(crate) usize);
+
+/// A button of a game controller, named after its position on an Xbox controller.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GamepadButton {
+    /// The bottom action button, A on Xbox controllers
+    South,
+    /// The right action button, B on Xbox controllers
+    East,
+    /// The top action button, Y on Xbox controllers
+    North,
+    /// The left action button, X on Xbox controllers
+    West,
+    /// The left bumper
+    LeftBumper,
+    /// The left trigger
+    LeftTrigger,
+    /// The right bumper
+    RightBumper,
+    /// The right trigger
+    RightTrigger,
+    /// The left menu button, View on Xbox controllers
+    Select,
+    /// The right menu button, Menu on Xbox controllers
+    Start,
+    /// The button of the vendor, like the Xbox button
+    Mode,
+    /// Pressing the left stick
+    LeftThumb,
+    /// Pressing the right stick
+    RightThumb,
+    /// Up on the d-pad
+    DPadUp,
+    /// Down on the d-pad
+    DPadDown,
+    /// Left on the d-pad
+    DPadLeft,
+    /// Right on the d-pad
+    DPadRight,
+    /// A button which isn't known
+    Unknown,
+}
+
+/// An axis of a game controller.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GamepadAxis {
+    /// The horizontal axis of the left stick, from -1 on the left to 1 on the right
+    LeftStickX,
+    /// The vertical axis of the left stick, from -1 at the bottom to 1 at the top
+    LeftStickY,
+    /// The horizontal axis of the right stick
+    RightStickX,
+    /// The vertical axis of the right stick
+    RightStickY,
+    /// An axis which isn't known
+    Unknown,
+}
+
+/// An event of a game controller.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GamepadEvent {
+    /// A controller was connected
+    Connected(GamepadId),
+    /// A controller was disconnected
+    Disconnected(GamepadId),
+    /// A button was pressed
+    ButtonDown {
+        /// The controller of the button
+        gamepad: GamepadId,
+        /// The pressed button
+        button: GamepadButton,
+    },
+    /// A button was released
+    ButtonUp {
+        /// The controller of the button
+        gamepad: GamepadId,
+        /// The released button
+        button: GamepadButton,
+    },
+    /// An axis moved
+    Axis {
+        /// The controller of the axis
+        gamepad: GamepadId,
+        /// The axis which moved
+        axis: GamepadAxis,
+        /// The position of the axis, from -1 to 1
+        value: f32,
+    },
+}
+
+impl GamepadButton {
+    /// The arrow key the button moves the focus like, for the d-pad.
+    pub(crate) fn arrow_key(self) -> Option<winit::keyboard::NamedKey> {
+        use winit::keyboard::NamedKey;
+
+        match self {
+            GamepadButton::DPadUp => Some(NamedKey::ArrowUp),
+            GamepadButton::DPadDown => Some(NamedKey::ArrowDown),
+            GamepadButton::DPadLeft => Some(NamedKey::ArrowLeft),
+            GamepadButton::DPadRight => Some(NamedKey::ArrowRight),
+            _ => None,
+        }
+    }
+}
+
+/// Reads the events of the game controllers on a thread, and sends them to the event loop.
+#[cfg(all(
+    feature = "gamepad",
+    not(any(target_arch = "wasm32", target_os = "android", target_os = "ios"))
+))]
+pub(crate) fn spawn_event_thread() {
+    use crate::app::{Application, UserEvent};
+
+    std::thread::Builder::new()
+        .name("gamepad".to_string())
+        .spawn(|| {
+            let mut gilrs = match gilrs::Gilrs::new() {
+                Ok(gilrs) => gilrs,
+                Err(gilrs::Error::NotImplemented(gilrs)) => gilrs,
+                Err(_) => return,
+            };
+            loop {
+                let Some(event) = gilrs.next_event_blocking(None) else {
+                    continue;
+                };
+                if let Some(event) = convert_event(event) {
+                    Application::send_proxy_event(UserEvent::Gamepad(event));
+                }
+            }
+        })
+        .expect("can't start the gamepad thread");
+}
+
+#[cfg(all(
+    feature = "gamepad",
+    not(any(target_arch = "wasm32", target_os = "android", target_os = "ios"))
+))]
+fn convert_event(event: gilrs::Event) -> Option<GamepadEvent> {
+    use gilrs::EventType;
+
+    let gamepad = GamepadId(event.id.into());
+    Some(match event.event {
+        EventType::Connected => GamepadEvent::Connected(gamepad),
+        EventType::Disconnected => GamepadEvent::Disconnected(gamepad),
+        EventType::ButtonPressed(button, _) => GamepadEvent::ButtonDown {
+            gamepad,
+            button: convert_button(button),
+        },
+        EventType::ButtonReleased(button, _) => GamepadEvent::ButtonUp {
+            gamepad,
+            button: convert_button(button),
+        },
+        EventType::AxisChanged(axis, value, _) => GamepadEvent::Axis {
+            gamepad,
+            axis: convert_axis(axis),
+            value,
+        },
+        _ => return None,
+    })
+}
+
+#[cfg(all(
+    feature = "gamepad",
+    not(any(target_arch = "wasm32", target_os = "android", target_os = "ios"))
+))]
+fn convert_button(button: gilrs::Button) -> GamepadButton {
+    use gilrs::Button;
+
+    match button {
+        Button::South => GamepadButton::South,
+        Button::East => GamepadButton::East,
+        Button::North => GamepadButton::North,
+        Button::West => GamepadButton::West,
+        Button::LeftTrigger => GamepadButton::LeftBumper,
+        Button::LeftTrigger2 => GamepadButton::LeftTrigger,
+        Button::RightTrigger => GamepadButton::RightBumper,
+        Button::RightTrigger2 => GamepadButton::RightTrigger,
+        Button::Select => GamepadButton::Select,
+        Button::Start => GamepadButton::Start,
+        Button::Mode => GamepadButton::Mode,
+        Button::LeftThumb => GamepadButton::LeftThumb,
+        Button::RightThumb => GamepadButton::RightThumb,
+        Button::DPadUp => GamepadButton::DPadUp,
+        Button::DPadDown => GamepadButton::DPadDown,
+        Button::DPadLeft => GamepadButton::DPadLeft,
+        Button::DPadRight => GamepadButton::DPadRight,
+        _ => GamepadButton::Unknown,
+    }
+}
+
+#[cfg(all(
+    feature = "gamepad",
+    not(any(target_arch = "wasm32", target_os = "android", target_os = "ios"))
+))]
+fn convert_axis(axis: gilrs::Axis) -> GamepadAxis {
+    use gilrs::Axis;
+
+    match axis {
+        Axis::LeftStickX => GamepadAxis::LeftStickX,
+        Axis::LeftStickY => GamepadAxis::LeftStickY,
+        Axis::RightStickX => GamepadAxis::RightStickX,
+        Axis::RightStickY => GamepadAxis::RightStickY,
+        _ => GamepadAxis::Unknown,
+    }
+}
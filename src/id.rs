@@ -6,12 +6,15 @@
 
 use std::{any::Any, cell::RefCell, rc::Rc};
 
-use peniko::kurbo::{Insets, Point, Rect, Size};
+use floem_renderer::display_list::DisplayList;
+use peniko::kurbo::{Affine, Insets, Point, Rect, Size};
 use slotmap::new_key_type;
 use taffy::{Display, Layout, NodeId, TaffyTree};
 use winit::window::WindowId;
 
 use crate::{
+    accessibility::AccessibilityProps,
+    action::ImePurpose,
     animate::{AnimStateCommand, Animation},
     context::{EventCallback, ResizeCallback},
     event::{EventListener, EventPropagation},
@@ -295,7 +298,13 @@ impl ViewId {
 
     /// Request that this view have it's paint pass run
     pub fn request_paint(&self) {
-        self.add_update_message(UpdateMessage::RequestPaint);
+        self.add_update_message(UpdateMessage::RequestPaint(*self));
+    }
+
+    /// Request that only `rect` of this view, in its own coordinates, is painted again, e.g.
+    /// for a blinking caret.
+    pub fn request_paint_rect(&self, rect: Rect) {
+        self.add_update_message(UpdateMessage::RequestPaintRect { id: *self, rect });
     }
 
     /// request that this node be styled again
@@ -332,6 +341,36 @@ impl ViewId {
         self.add_update_message(UpdateMessage::ClearFocus(*self));
     }
 
+    /// Sets whether the IME is enabled while this view has the focus, like for text inputs.
+    pub fn set_ime_allowed(&self, allowed: bool) {
+        self.state().borrow_mut().ime_allowed = allowed;
+        self.add_update_message(UpdateMessage::FocusedIme);
+    }
+
+    /// Sets the kind of text typed into this view while it has the focus, like passwords which
+    /// disable composition.
+    pub fn set_ime_purpose(&self, purpose: ImePurpose) {
+        self.state().borrow_mut().ime_purpose = purpose;
+        self.add_update_message(UpdateMessage::FocusedIme);
+    }
+
+    /// Locks the pointer to this view, for views which follow the motion of the pointer rather
+    /// than its position, like 3D viewports, color wheels or infinite sliders.
+    ///
+    /// The cursor is hidden while the pointer is locked, and the view receives the relative
+    /// motion of the pointer as [`Event::PointerDelta`](crate::event::Event::PointerDelta).
+    /// The lock is released with [`release_pointer_lock`](Self::release_pointer_lock), when the
+    /// user presses Escape or when the window loses the focus, after which the view receives
+    /// [`Event::PointerLockReleased`](crate::event::Event::PointerLockReleased).
+    pub fn request_pointer_lock(&self) {
+        self.add_update_message(UpdateMessage::PointerLock(*self));
+    }
+
+    /// Releases the pointer lock of this view, if it holds it.
+    pub fn release_pointer_lock(&self) {
+        self.add_update_message(UpdateMessage::ReleasePointerLock(*self));
+    }
+
     /// Set the system context menu that should be shown when this view is right-clicked
     pub fn update_context_menu(&self, menu: impl Fn() -> Menu + 'static) {
         self.state().borrow_mut().context_menu = Some(Rc::new(menu));
@@ -363,6 +402,45 @@ impl ViewId {
         self.add_update_message(UpdateMessage::Inspect);
     }
 
+    /// Show or hide the inspector overlay of the window, which outlines the view under the
+    /// cursor and shows its id, layout and style.
+    pub fn toggle_inspector_overlay(&self) {
+        self.add_update_message(UpdateMessage::ToggleInspectorOverlay);
+    }
+
+    /// Show or hide the frame profiler overlay of the window, which shows the frame rate and
+    /// the time spent on events, updates, layout, text shaping and painting.
+    pub fn toggle_frame_profiler(&self) {
+        self.add_update_message(UpdateMessage::ToggleFrameProfiler);
+    }
+
+    /// Paints this view and its children into an image of the view size times `scale`, e.g.
+    /// to export the view as a PNG file or to attach it to a bug report.
+    ///
+    /// The image is painted with the software renderer on a white background once the
+    /// pending style and layout updates are done, and passed to `on_capture`. It isn't
+    /// called if the view is removed before.
+    pub fn capture_image(&self, scale: f64, on_capture: impl FnOnce(image::RgbaImage) + 'static) {
+        self.add_update_message(UpdateMessage::CaptureImage {
+            id: *self,
+            scale,
+            on_capture: Box::new(on_capture),
+        });
+    }
+
+    /// Records the draw commands of this view and its children into a [`DisplayList`], in the
+    /// coordinates of the view, and passes it to `on_record`.
+    ///
+    /// The display list can be turned into other formats such as PDF or SVG, or replayed onto
+    /// another renderer. Like [`ViewId::capture_image`], it's recorded once the pending style
+    /// and layout updates are done.
+    pub fn record_display_list(&self, on_record: impl FnOnce(DisplayList) + 'static) {
+        self.add_update_message(UpdateMessage::RecordDisplayList {
+            id: *self,
+            on_record: Box::new(on_record),
+        });
+    }
+
     /// Scrolls the view and all direct and indirect children to bring the view to be
     /// visible. The optional rectangle can be used to add an additional offset and intersection.
     pub fn scroll_to(&self, rect: Option<Rect>) {
@@ -412,16 +490,16 @@ impl ViewId {
         state.borrow_mut().add_event_listener(listener, action);
     }
 
-    /// Set a callback that should be run when the size of the view changes
-    pub fn update_resize_listener(&self, action: Box<ResizeCallback>) {
+    /// Add a callback that should be run when the size of the view changes
+    pub fn add_resize_listener(&self, action: Box<ResizeCallback>) {
         let state = self.state();
-        state.borrow_mut().update_resize_listener(action);
+        state.borrow_mut().add_resize_listener(action);
     }
 
-    /// Set a callback that should be run when the position of the view changes
-    pub fn update_move_listener(&self, action: Box<dyn Fn(Point)>) {
+    /// Add a callback that should be run when the position of the view changes
+    pub fn add_move_listener(&self, action: Box<dyn Fn(Point)>) {
         let state = self.state();
-        state.borrow_mut().update_move_listener(action);
+        state.borrow_mut().add_move_listener(action);
     }
 
     /// Set a callback that should be run when the view is removed from the view tree
@@ -464,6 +542,67 @@ impl ViewId {
         self.request_style();
     }
 
+    /// Changes the style values edited in the inspector, which override the style of the view.
+    pub(crate) fn update_inspector_style(&self, f: impl FnOnce(&mut Style)) {
+        f(&mut self.state().borrow_mut().inspector_style);
+        self.request_style_recursive();
+        self.request_layout();
+    }
+
+    /// Whether this view or one of its parents is transformed, so its layout rect doesn't
+    /// match where it's painted.
+    pub(crate) fn is_transformed(&self) -> bool {
+        let mut id = Some(*self);
+        while let Some(current) = id {
+            if current.state().borrow().transform != Affine::IDENTITY {
+                return true;
+            }
+            id = current.parent();
+        }
+        false
+    }
+
+    /// The part of the window this view paints to including its outline and shadow, or
+    /// `None` if it's transformed.
+    pub(crate) fn paint_rect(&self) -> Option<Rect> {
+        if self.is_transformed() {
+            return None;
+        }
+        let state = self.state();
+        let state = state.borrow();
+        let rect = state.layout_rect;
+        let length = |value: PxPct| match value {
+            PxPct::Px(px) => px.abs(),
+            PxPct::Pct(pct) => (rect.size().min_side() * pct / 100.0).abs(),
+        };
+        let props = &state.view_style_props;
+        let mut extent = props
+            .outline()
+            .0
+            .width
+            .max(props.focus_ring_offset().0 + props.focus_ring_width().0);
+        if let Some(shadow) = props.shadow() {
+            extent = extent.max(
+                length(shadow.h_offset).max(length(shadow.v_offset))
+                    + length(shadow.spread)
+                    + length(shadow.blur_radius) * 2.0,
+            );
+        }
+        Some(rect.inflate(extent, extent))
+    }
+
+    /// The topmost visible view under `pos` in this view or its children, in window coordinates.
+    pub(crate) fn view_at(&self, pos: Point) -> Option<ViewId> {
+        if self.is_hidden_recursive() || !self.layout_rect().contains(pos) {
+            return None;
+        }
+        self.children()
+            .into_iter()
+            .rev()
+            .find_map(|child| child.view_at(pos))
+            .or(Some(*self))
+    }
+
     pub(crate) fn update_style(&self, offset: StackOffset<Style>, style: Style) {
         let state = self.state();
         let old_any_inherited = state.borrow().style().any_inherited();
@@ -506,6 +645,13 @@ impl ViewId {
         });
     }
 
+    /// Update the accessibility properties set on this view from the outside.
+    pub(crate) fn update_accessibility(&self, f: impl FnOnce(&mut AccessibilityProps)) {
+        f(&mut self.state().borrow_mut().accessibility);
+        // The accessibility tree is updated after painting
+        self.request_paint();
+    }
+
     /// Mark this view as a view that can be navigated to using the keyboard
     pub fn keyboard_navigable(&self) {
         self.add_update_message(UpdateMessage::KeyboardNavigable { id: *self });
@@ -547,7 +693,7 @@ impl ViewId {
         self.add_update_message(UpdateMessage::WindowVisible(visible));
     }
 
-    fn add_update_message(&self, msg: UpdateMessage) {
+    pub(crate) fn add_update_message(&self, msg: UpdateMessage) {
         CENTRAL_UPDATE_MESSAGES.with_borrow_mut(|msgs| {
             msgs.push((*self, msg));
         });
@@ -2,19 +2,21 @@ mod data;
 mod view;
 
 use crate::app_state::AppState;
-use crate::context::StyleCx;
+use crate::context::{PaintCx, StyleCx};
 use crate::event::{Event, EventListener, EventPropagation};
 use crate::id::ViewId;
 use crate::style::{Style, StyleClassRef, StylePropRef, Transition};
+use crate::text::{Attrs, AttrsList, TextLayout};
 use crate::view::{IntoView, View};
 use crate::view_state::ChangeFlags;
 use crate::views::{
-    button, dyn_container, stack, static_label, text, v_stack, v_stack_from_iter, Decorators, Label,
+    button, dyn_container, stack, static_label, text, text_input, v_stack, v_stack_from_iter,
+    Decorators, Label,
 };
-use crate::{keyboard, style, Clipboard};
-use floem_reactive::{batch, RwSignal, Scope, SignalGet, SignalUpdate};
+use crate::{keyboard, style, Clipboard, Renderer};
+use floem_reactive::{batch, create_effect, RwSignal, Scope, SignalGet, SignalUpdate};
 use peniko::color::palette;
-use peniko::kurbo::{Point, Rect, Size};
+use peniko::kurbo::{Point, Rect, Size, Stroke};
 use peniko::Color;
 use slotmap::Key;
 use std::cell::Cell;
@@ -300,6 +302,97 @@ fn stats(capture: &Capture) -> impl IntoView {
     ))
 }
 
+/// An input overriding a style property of a view with the value typed in, which is
+/// removed again when the input is cleared.
+fn style_editor(id: ViewId, prop: StylePropRef) -> impl IntoView {
+    let buffer = RwSignal::new(String::new());
+    let invalid = RwSignal::new(false);
+    create_effect(move |first_run: Option<()>| {
+        let value = buffer.get();
+        // Keep edits made before the view was selected again
+        if first_run.is_none() {
+            return;
+        }
+        if value.trim().is_empty() {
+            invalid.set(false);
+            id.update_inspector_style(|style| {
+                style.map.remove(&prop.key);
+            });
+        } else if let Some(value) = (prop.info().parse)(&value) {
+            invalid.set(false);
+            id.update_inspector_style(|style| {
+                style.map.insert(prop.key, value);
+            });
+        } else {
+            invalid.set(true);
+        }
+    });
+    text_input(buffer).placeholder("Edit").style(move |s| {
+        s.margin_left(10.0)
+            .width(120.0)
+            .font_size(11.0)
+            .apply_if(invalid.get(), |s| s.border_color(palette::css::RED))
+    })
+}
+
+/// Paints the inspector overlay, outlining the view under the cursor and showing its id,
+/// layout and resolved style next to it.
+pub(crate) fn paint_overlay(cx: &mut PaintCx, root: ViewId) {
+    let Some(id) = root.view_at(cx.app_state.last_cursor_location) else {
+        return;
+    };
+    let rect = id.layout_rect();
+    cx.fill(&rect, palette::css::DODGER_BLUE.with_alpha(0.15), 0.0);
+    cx.stroke(&rect, palette::css::DODGER_BLUE, &Stroke::new(1.0));
+
+    let style = id.state().borrow().computed_style.clone();
+    let mut props = style
+        .style_props()
+        .filter_map(|prop| {
+            let value = style.map.get(&prop.key)?;
+            let name = format!("{:?}", prop.key);
+            let name = name.strip_prefix("floem::style::").unwrap_or(&name);
+            Some(format!("{name}: {}", (prop.info().debug_any)(&**value)))
+        })
+        .collect::<Vec<_>>();
+    props.sort_unstable();
+    let lines = [
+        format!("{} {:?}", id.view().borrow().debug_name(), id),
+        format!(
+            "x: {} y: {} width: {} height: {}",
+            rect.x0,
+            rect.y0,
+            rect.width(),
+            rect.height()
+        ),
+    ]
+    .into_iter()
+    .chain(props)
+    .collect::<Vec<_>>();
+
+    let mut layout = TextLayout::new();
+    layout.set_text(
+        &lines.join("\n"),
+        AttrsList::new(Attrs::new().font_size(11.0).color(palette::css::WHITE)),
+    );
+    let padding = 4.0;
+    let size = layout.size() + Size::new(padding * 2.0, padding * 2.0);
+    let window = root.layout_rect();
+    // Below the view if it fits, otherwise above it, and kept inside the window
+    let y = if rect.y1 + size.height <= window.y1 {
+        rect.y1
+    } else {
+        (rect.y0 - size.height).max(window.y0)
+    };
+    let x = rect.x0.min(window.x1 - size.width).max(window.x0);
+    cx.fill(
+        &Rect::from_origin_size((x, y), size).to_rounded_rect(3.0),
+        Color::from_rgba8(30, 30, 30, 220),
+        0.0,
+    );
+    cx.draw_text(&layout, (x + padding, y + padding));
+}
+
 fn selected_view(capture: &Rc<Capture>, selected: RwSignal<Option<ViewId>>) -> impl IntoView {
     let capture = capture.clone();
     dyn_container(
@@ -397,7 +490,13 @@ fn selected_view(capture: &Rc<Capture>, selected: RwSignal<Option<ViewId>>) -> i
                 let clear = button("Clear selection")
                     .style(|s| s.margin(5.0))
                     .on_click_stop(move |_| selected.set(None));
-                let clear = stack((clear,));
+                let view_id = view.id;
+                let reset = button("Reset style edits")
+                    .style(|s| s.margin(5.0))
+                    .on_click_stop(move |_| {
+                        view_id.update_inspector_style(|style| *style = Style::new())
+                    });
+                let clear = stack((clear, reset));
 
                 let style_header = header("View Style");
                 let class_header = header("Class Header");
@@ -480,6 +579,7 @@ fn selected_view(capture: &Rc<Capture>, selected: RwSignal<Option<ViewId>>) -> i
                             .style(|s| s.items_center());
                             v = v_stack((v, transition)).into_any();
                         }
+                        let v = stack((v, style_editor(view_id, prop))).style(|s| s.items_center());
                         stack((
                             stack((name.style(|s| {
                                 s.margin_right(5.0)
@@ -0,0 +1,902 @@
+//! Translation of the text of an application, and formatting for the current locale.
+//!
+//! The current locale is a signal, see [`locale`] and [`set_locale`]. Text created with
+//! [`tr!`](crate::tr) reads it, so views showing translated text update when the language is
+//! switched:
+//! ```rust
+//! use floem::{l10n, reactive::RwSignal, tr, views::label};
+//!
+//! l10n::add_translations(
+//!     "en",
+//!     r#"
+//! hello = Hello, { $name }!
+//! unread = { $count ->
+//!     [one] One unread message
+//!    *[other] { $count } unread messages
+//! }
+//! "#,
+//! )
+//! .unwrap();
+//!
+//! let count = RwSignal::new(3);
+//! label(tr!("hello", name = "Floem"));
+//! label(tr!("unread", count = count.get()));
+//! ```
+//!
+//! Translations are written in the [Fluent](https://projectfluent.org) syntax. Messages, terms,
+//! attributes, variables, message references, string literals and select expressions are
+//! supported, but functions like `NUMBER()` are not. Numbers passed as arguments are formatted
+//! for the locale, and select plural variants like `[one]` by the plural rules of the locale.
+use std::{cell::RefCell, collections::HashMap, fmt, io, path::Path};
+
+use floem_reactive::{
+    create_signal, create_updater, ReadSignal, RwSignal, Scope, SignalGet, SignalTrack,
+    SignalUpdate, SignalWith,
+};
+
+use crate::{style::Direction, views::NumberFormat};
+
+thread_local! {
+    static LOCALE: RwSignal<String> = Scope::new().create_rw_signal(system_locale());
+    /// Bumped when translations are added, so text using them is translated again
+    static REVISION: RwSignal<u64> = Scope::new().create_rw_signal(0);
+    static FALLBACK_LOCALE: RefCell<String> = RefCell::new("en".to_string());
+    static BUNDLES: RefCell<HashMap<String, Bundle>> = Default::default();
+}
+
+/// The signal of the current locale, as a language tag like `"en-US"`.
+pub fn locale() -> ReadSignal<String> {
+    LOCALE.with(|locale| locale.read_only())
+}
+
+/// Switches the language of the application. Text translated with [`tr!`](crate::tr) and
+/// formatted for the locale is updated.
+pub fn set_locale(locale: impl AsRef<str>) {
+    let locale = normalize_locale(locale.as_ref());
+    LOCALE.with(|signal| signal.set(locale));
+}
+
+/// Sets the locale whose translations are used for messages missing in the current locale.
+///
+/// The default is `"en"`.
+pub fn set_fallback_locale(locale: impl AsRef<str>) {
+    FALLBACK_LOCALE.with_borrow_mut(|fallback| *fallback = normalize_locale(locale.as_ref()));
+    REVISION.with(|revision| revision.update(|revision| *revision += 1));
+}
+
+/// The locale of the user, from the environment or the browser.
+pub fn system_locale() -> String {
+    #[cfg(target_arch = "wasm32")]
+    let locale = web_sys::window().and_then(|window| window.navigator().language());
+    #[cfg(not(target_arch = "wasm32"))]
+    let locale = ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .into_iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|locale| !locale.is_empty() && locale != "C" && locale != "POSIX");
+    normalize_locale(locale.as_deref().unwrap_or("en-US"))
+}
+
+/// The layout direction of the current locale, right-to-left for languages like Arabic and
+/// Hebrew.
+///
+/// Reading the locale is tracked.
+pub fn direction() -> Direction {
+    LOCALE.with(|locale| locale.with(|locale| locale_direction(locale)))
+}
+
+/// The layout direction of a locale, given as a language tag.
+pub fn locale_direction(locale: &str) -> Direction {
+    match language(locale) {
+        "ar" | "he" | "iw" | "fa" | "ur" | "ps" | "yi" | "dv" | "ckb" | "sd" | "ug" => {
+            Direction::Rtl
+        }
+        _ => Direction::Ltr,
+    }
+}
+
+/// Turns locales like `de_DE.UTF-8` into language tags like `de-DE`.
+fn normalize_locale(locale: &str) -> String {
+    let locale = locale.split(['.', '@']).next().unwrap_or_default();
+    let mut parts = locale.split(['-', '_']).filter(|part| !part.is_empty());
+    let mut tag = parts.next().unwrap_or("en").to_ascii_lowercase();
+    for part in parts {
+        tag.push('-');
+        if part.len() == 2 {
+            tag.push_str(&part.to_ascii_uppercase());
+        } else {
+            tag.push_str(part);
+        }
+    }
+    tag
+}
+
+fn language(locale: &str) -> &str {
+    locale.split('-').next().unwrap_or(locale)
+}
+
+/// Adds translations for a locale, given as a language tag like `"fr"` or `"pt-BR"`.
+///
+/// Messages which are already translated for the locale are replaced. A message missing in
+/// e.g. `pt-BR` is looked up in `pt`, and then in the fallback locale.
+pub fn add_translations(locale: &str, source: &str) -> Result<(), ParseError> {
+    let messages = parse_resource(source)?;
+    let locale = normalize_locale(locale);
+    BUNDLES.with_borrow_mut(|bundles| {
+        bundles
+            .entry(locale.clone())
+            .or_insert_with(|| Bundle {
+                locale,
+                messages: HashMap::new(),
+            })
+            .messages
+            .extend(messages);
+    });
+    REVISION.with(|revision| revision.update(|revision| *revision += 1));
+    Ok(())
+}
+
+/// Adds the translations of a Fluent (`.ftl`) file for a locale.
+pub fn load_translations(locale: &str, path: impl AsRef<Path>) -> io::Result<()> {
+    let source = std::fs::read_to_string(path)?;
+    add_translations(locale, &source).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+/// An argument of a translated message.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    String(String),
+    Number(f64),
+}
+
+impl From<&str> for Value {
+    fn from(value: &str) -> Self {
+        Value::String(value.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(value: String) -> Self {
+        Value::String(value)
+    }
+}
+
+impl From<&String> for Value {
+    fn from(value: &String) -> Self {
+        Value::String(value.clone())
+    }
+}
+
+macro_rules! number_value {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for Value {
+                fn from(value: $ty) -> Self {
+                    Value::Number(value as f64)
+                }
+            }
+        )*
+    };
+}
+
+number_value!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, f32, f64);
+
+/// Translates the message `key` into the current locale.
+///
+/// If no translation has the message, the key itself is returned. Reading the locale is
+/// tracked, so this is usually called through [`tr!`](crate::tr) in a reactive closure.
+pub fn translate(key: &str, args: &[(&str, Value)]) -> String {
+    let locale = LOCALE.with(|locale| locale.get());
+    REVISION.with(|revision| revision.track());
+    let fallback = FALLBACK_LOCALE.with_borrow(|fallback| fallback.clone());
+
+    let mut candidates = vec![
+        locale.as_str(),
+        language(&locale),
+        fallback.as_str(),
+        language(&fallback),
+    ];
+    candidates.dedup();
+
+    BUNDLES.with_borrow(|bundles| {
+        candidates
+            .iter()
+            .filter_map(|locale| bundles.get(*locale))
+            .find_map(|bundle| {
+                let pattern = bundle.messages.get(key)?;
+                let mut text = String::new();
+                bundle.format(pattern, args, &mut text, 0);
+                Some(text)
+            })
+            .unwrap_or_else(|| key.to_string())
+    })
+}
+
+/// Creates a closure translating a message into the current locale, with named arguments.
+///
+/// The arguments are evaluated when the text is translated, so signals read by them are
+/// tracked like the locale.
+/// ```rust
+/// use floem::{reactive::{RwSignal, SignalGet}, tr, views::label};
+///
+/// let name = RwSignal::new("world".to_string());
+/// label(tr!("greeting", name = name.get()));
+/// ```
+#[macro_export]
+macro_rules! tr {
+    ($key:expr $(,)?) => {
+        move || $crate::l10n::translate($key, &[])
+    };
+    ($key:expr, $($name:ident = $value:expr),+ $(,)?) => {
+        move || {
+            $crate::l10n::translate(
+                $key,
+                &[$((stringify!($name), $crate::l10n::Value::from($value))),+],
+            )
+        }
+    };
+}
+
+/// The plural category of a number, which decides the plural form of words counting it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PluralCategory {
+    Zero,
+    One,
+    Two,
+    Few,
+    Many,
+    Other,
+}
+
+impl PluralCategory {
+    /// The name of the category, like `one`, used as the key of select variants.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PluralCategory::Zero => "zero",
+            PluralCategory::One => "one",
+            PluralCategory::Two => "two",
+            PluralCategory::Few => "few",
+            PluralCategory::Many => "many",
+            PluralCategory::Other => "other",
+        }
+    }
+}
+
+/// The plural category of a number in a locale, following the CLDR rules of common
+/// languages. Other languages use the English rules.
+pub fn plural_category(locale: &str, n: f64) -> PluralCategory {
+    use PluralCategory::*;
+
+    let integer = n.fract() == 0.0;
+    let i = n.abs().trunc() as u64;
+    let (i10, i100) = (i % 10, i % 100);
+    match language(locale) {
+        "ja" | "zh" | "ko" | "vi" | "th" | "id" | "ms" | "lo" | "my" => Other,
+        "fr" | "hy" | "kab" => {
+            if i <= 1 {
+                One
+            } else {
+                Other
+            }
+        }
+        "pt" if !locale.ends_with("-PT") => {
+            if i <= 1 {
+                One
+            } else {
+                Other
+            }
+        }
+        "ru" | "uk" | "be" => match () {
+            _ if !integer => Other,
+            _ if i10 == 1 && i100 != 11 => One,
+            _ if (2..=4).contains(&i10) && !(12..=14).contains(&i100) => Few,
+            _ => Many,
+        },
+        "pl" => match () {
+            _ if !integer => Other,
+            _ if i == 1 => One,
+            _ if (2..=4).contains(&i10) && !(12..=14).contains(&i100) => Few,
+            _ => Many,
+        },
+        "cs" | "sk" => match () {
+            _ if !integer => Many,
+            _ if i == 1 => One,
+            _ if (2..=4).contains(&i) => Few,
+            _ => Other,
+        },
+        "ar" => match () {
+            _ if !integer => Other,
+            _ if i == 0 => Zero,
+            _ if i == 1 => One,
+            _ if i == 2 => Two,
+            _ if (3..=10).contains(&i100) => Few,
+            _ if (11..=99).contains(&i100) => Many,
+            _ => Other,
+        },
+        "he" => match () {
+            _ if integer && i == 1 => One,
+            _ if integer && i == 2 => Two,
+            _ => Other,
+        },
+        _ => {
+            if integer && i == 1 {
+                One
+            } else {
+                Other
+            }
+        }
+    }
+}
+
+/// Picks the form of a word for a count, by the plural category of the count in the current
+/// locale.
+///
+/// Forms are given by the name of their [category](PluralCategory::as_str). If there's no
+/// form for the category, the `other` form is used, or else the last form. This is usually
+/// called through [`plural!`](crate::plural).
+///
+/// Reading the locale is tracked.
+pub fn plural_form<T, const N: usize>(count: f64, forms: [(&str, T); N]) -> T {
+    let category = LOCALE.with(|locale| locale.with(|locale| plural_category(locale, count)));
+    let mut other = None;
+    let mut last = None;
+    for (name, form) in forms {
+        if name == category.as_str() {
+            return form;
+        }
+        if name == PluralCategory::Other.as_str() {
+            other = Some(form);
+        } else {
+            last = Some(form);
+        }
+    }
+    other
+        .or(last)
+        .expect("`plural_form` needs at least one form")
+}
+
+/// Picks the form of a word for a count, by the plural rules of the current locale.
+///
+/// The count can be any number, and the forms are named by their plural category.
+/// ```rust
+/// use floem::plural;
+///
+/// let files = 3;
+/// assert_eq!(plural!(files, one = "file", other = "files"), "files");
+/// ```
+#[macro_export]
+macro_rules! plural {
+    ($count:expr, $($category:ident = $form:expr),+ $(,)?) => {
+        $crate::l10n::plural_form(($count) as f64, [$((stringify!($category), $form)),+])
+    };
+}
+
+/// Creates a signal of text computed by `text`, which is computed again when the signals it
+/// reads change, like the locale read by [`tr!`](crate::tr).
+///
+/// ```rust
+/// use floem::{l10n::text_signal, reactive::{RwSignal, SignalGet}, tr};
+///
+/// let count = RwSignal::new(2);
+/// let unread = text_signal(tr!("unread", count = count.get()));
+/// ```
+pub fn text_signal(text: impl Fn() -> String + 'static) -> ReadSignal<String> {
+    let (signal, set_signal) = create_signal(String::new());
+    let initial = create_updater(text, move |text| {
+        if signal.with_untracked(|current| *current != text) {
+            set_signal.set(text);
+        }
+    });
+    set_signal.set(initial);
+    signal
+}
+
+/// Creates a signal of text formatted like [`format!`], which is formatted again when the
+/// signals read by the arguments change.
+///
+/// This replaces computing text with `create_memo(move |_| format!(..))`, and
+/// [`plural!`](crate::plural) can be used in the arguments to pick the form of words.
+/// ```rust
+/// use floem::{format_signal, plural, reactive::{RwSignal, SignalGet, SignalUpdate}};
+///
+/// let count = RwSignal::new(1);
+/// let text = format_signal!(
+///     "{} {}",
+///     count.get(),
+///     plural!(count.get(), one = "item", other = "items")
+/// );
+/// assert_eq!(text.get(), "1 item");
+///
+/// count.set(3);
+/// assert_eq!(text.get(), "3 items");
+/// ```
+#[macro_export]
+macro_rules! format_signal {
+    ($($args:tt)+) => {
+        $crate::l10n::text_signal(move || format!($($args)+))
+    };
+}
+
+/// Formats a number with the separators of the current locale.
+///
+/// Reading the locale is tracked.
+pub fn format_number(value: f64) -> String {
+    NumberFormat::localized().format(value)
+}
+
+/// Formats a date in the short numeric form of the current locale, like `3/14/2025` in
+/// `en-US` or `14.03.2025` in `de`.
+///
+/// Reading the locale is tracked.
+pub fn format_date(year: i32, month: u32, day: u32) -> String {
+    let locale = LOCALE.with(|locale| locale.get());
+    let region = locale.split('-').nth(1).unwrap_or_default();
+    match (language(&locale), region) {
+        ("en", "US" | "") | ("en", "PH") => format!("{month}/{day}/{year}"),
+        ("en" | "fr" | "es" | "it" | "pt" | "el" | "vi" | "id" | "ms", _) => {
+            format!("{day:02}/{month:02}/{year}")
+        }
+        ("de" | "ru" | "pl" | "fi" | "cs" | "sk" | "nb" | "no" | "da" | "uk" | "tr" | "ro", _) => {
+            format!("{day:02}.{month:02}.{year}")
+        }
+        ("nl", _) => format!("{day}-{month}-{year}"),
+        ("ja" | "zh", _) => format!("{year}/{month}/{day}"),
+        ("ko", _) => format!("{year}. {month}. {day}."),
+        ("hu", _) => format!("{year}. {month:02}. {day:02}."),
+        _ => format!("{year}-{month:02}-{day:02}"),
+    }
+}
+
+/// An error in the Fluent syntax of translations.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// The line of the error, starting at 1
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// The messages translated to a locale.
+struct Bundle {
+    locale: String,
+    messages: HashMap<String, Pattern>,
+}
+
+type Pattern = Vec<Element>;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Element {
+    Text(String),
+    Variable(String),
+    /// A reference to a message or term
+    Message(String),
+    Select {
+        selector: String,
+        variants: Vec<Variant>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Variant {
+    key: String,
+    default: bool,
+    value: Pattern,
+}
+
+/// The depth of message references followed, to stop at cycles.
+const MAX_REFERENCE_DEPTH: usize = 16;
+
+impl Bundle {
+    fn format(&self, pattern: &Pattern, args: &[(&str, Value)], text: &mut String, depth: usize) {
+        let arg = |name: &str| {
+            args.iter()
+                .find(|(arg, _)| *arg == name)
+                .map(|(_, value)| value)
+        };
+        for element in pattern {
+            match element {
+                Element::Text(t) => text.push_str(t),
+                Element::Variable(name) => match arg(name) {
+                    Some(Value::String(value)) => text.push_str(value),
+                    Some(Value::Number(value)) => {
+                        text.push_str(&NumberFormat::for_locale(&self.locale).format(*value))
+                    }
+                    None => {
+                        text.push_str("{$");
+                        text.push_str(name);
+                        text.push('}');
+                    }
+                },
+                Element::Message(id) => match self.messages.get(id) {
+                    Some(pattern) if depth < MAX_REFERENCE_DEPTH => {
+                        self.format(pattern, args, text, depth + 1)
+                    }
+                    _ => {
+                        text.push('{');
+                        text.push_str(id);
+                        text.push('}');
+                    }
+                },
+                Element::Select { selector, variants } => {
+                    let matches = |key: &str| match arg(selector) {
+                        Some(Value::Number(n)) => {
+                            key.parse::<f64>().is_ok_and(|key| key == *n)
+                                || key == plural_category(&self.locale, *n).as_str()
+                        }
+                        Some(Value::String(value)) => key == value,
+                        None => false,
+                    };
+                    // Exact numbers take precedence over plural categories
+                    let variant = variants
+                        .iter()
+                        .find(|variant| variant.key.parse::<f64>().is_ok() && matches(&variant.key))
+                        .or_else(|| variants.iter().find(|variant| matches(&variant.key)))
+                        .or_else(|| variants.iter().find(|variant| variant.default));
+                    if let Some(variant) = variant {
+                        self.format(&variant.value, args, text, depth);
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn is_identifier(id: &str) -> bool {
+    let mut chars = id.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+fn parse_resource(source: &str) -> Result<HashMap<String, Pattern>, ParseError> {
+    let mut messages = HashMap::new();
+    // The id, first line and lines of the entry being read
+    let mut entry: Option<(String, usize, Vec<&str>)> = None;
+    let mut message_id: Option<String> = None;
+
+    let mut finish = |entry: &mut Option<(String, usize, Vec<&str>)>| -> Result<(), ParseError> {
+        if let Some((id, line, lines)) = entry.take() {
+            let value = lines.join("\n");
+            let pattern = parse_pattern(value.trim()).map_err(|message| ParseError {
+                line: line + 1,
+                message,
+            })?;
+            messages.insert(id, pattern);
+        }
+        Ok(())
+    };
+
+    for (i, line) in source.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            if let Some((_, _, lines)) = entry.as_mut() {
+                lines.push("");
+            }
+            continue;
+        }
+        if line.starts_with('#') {
+            finish(&mut entry)?;
+            message_id = None;
+            continue;
+        }
+        let error = |message: &str| ParseError {
+            line: i + 1,
+            message: message.to_string(),
+        };
+        // The brace closing a select expression may be unindented
+        if line.starts_with('}') {
+            if let Some((_, _, lines)) = entry.as_mut() {
+                lines.push(trimmed);
+                continue;
+            }
+        }
+        if line.starts_with(char::is_whitespace) {
+            if let Some(attribute) = trimmed.strip_prefix('.') {
+                let Some(message_id) = message_id.as_ref() else {
+                    return Err(error("attribute without a message"));
+                };
+                let (name, value) = attribute
+                    .split_once('=')
+                    .ok_or_else(|| error("expected `=` after the attribute name"))?;
+                if !is_identifier(name.trim()) {
+                    return Err(error("invalid attribute name"));
+                }
+                finish(&mut entry)?;
+                entry = Some((
+                    format!("{message_id}.{}", name.trim()),
+                    i,
+                    vec![value.trim_start()],
+                ));
+            } else if let Some((_, _, lines)) = entry.as_mut() {
+                lines.push(trimmed);
+            } else {
+                return Err(error("indented line outside of a message"));
+            }
+            continue;
+        }
+
+        finish(&mut entry)?;
+        let (id, value) = line
+            .split_once('=')
+            .ok_or_else(|| error("expected `=` after the message id"))?;
+        let id = id.trim();
+        let name = id.strip_prefix('-').unwrap_or(id);
+        if !is_identifier(name) {
+            return Err(error("invalid message id"));
+        }
+        message_id = Some(id.to_string());
+        entry = Some((id.to_string(), i, vec![value.trim_start()]));
+    }
+    finish(&mut entry)?;
+
+    Ok(messages)
+}
+
+fn parse_pattern(source: &str) -> Result<Pattern, String> {
+    let mut pattern = Vec::new();
+    let mut text = String::new();
+    let mut rest = source;
+    while let Some(start) = rest.find(['{', '}']) {
+        if rest[start..].starts_with('}') {
+            return Err("unexpected `}`".to_string());
+        }
+        text.push_str(&rest[..start]);
+        let end = start + closing_brace(&rest[start..])?;
+        if !text.is_empty() {
+            pattern.push(Element::Text(std::mem::take(&mut text)));
+        }
+        pattern.push(parse_placeable(&rest[start + 1..end])?);
+        rest = &rest[end + 1..];
+    }
+    text.push_str(rest);
+    if !text.is_empty() {
+        pattern.push(Element::Text(text));
+    }
+    Ok(pattern)
+}
+
+/// The offset of the brace closing the one `source` starts with.
+fn closing_brace(source: &str) -> Result<usize, String> {
+    let mut depth = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+    for (i, c) in source.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            '{' if !in_string => depth += 1,
+            '}' if !in_string => {
+                depth -= 1;
+                if depth == 0 {
+                    return Ok(i);
+                }
+            }
+            _ => {}
+        }
+    }
+    Err("unclosed `{`".to_string())
+}
+
+fn parse_placeable(source: &str) -> Result<Element, String> {
+    let source = source.trim();
+    if let Some((selector, variants)) = source.split_once("->") {
+        let selector = selector.trim();
+        let selector = selector
+            .strip_prefix('$')
+            .filter(|name| is_identifier(name))
+            .ok_or_else(|| format!("invalid selector `{selector}`"))?;
+        let variants = parse_variants(variants)?;
+        if variants.iter().filter(|variant| variant.default).count() != 1 {
+            return Err("a select expression needs exactly one default variant".to_string());
+        }
+        return Ok(Element::Select {
+            selector: selector.to_string(),
+            variants,
+        });
+    }
+
+    if let Some(name) = source.strip_prefix('$') {
+        if is_identifier(name) {
+            return Ok(Element::Variable(name.to_string()));
+        }
+    } else if let Some(literal) = source
+        .strip_prefix('"')
+        .and_then(|literal| literal.strip_suffix('"'))
+    {
+        return Ok(Element::Text(
+            literal.replace("\\\"", "\"").replace("\\\\", "\\"),
+        ));
+    } else if is_identifier(source.strip_prefix('-').unwrap_or(source)) {
+        return Ok(Element::Message(source.to_string()));
+    }
+    Err(format!("invalid placeable `{{ {source} }}`"))
+}
+
+fn parse_variants(source: &str) -> Result<Vec<Variant>, String> {
+    let mut variants: Vec<Variant> = Vec::new();
+    // The key, default flag and lines of the variant being read
+    let mut current: Option<(String, bool, Vec<&str>)> = None;
+    let mut finish = |current: &mut Option<(String, bool, Vec<&str>)>| -> Result<(), String> {
+        if let Some((key, default, lines)) = current.take() {
+            variants.push(Variant {
+                key,
+                default,
+                value: parse_pattern(lines.join("\n").trim())?,
+            });
+        }
+        Ok(())
+    };
+
+    let mut depth = 0;
+    for line in source.lines() {
+        let trimmed = line.trim();
+        // Lines of placeables nested in a variant value can't start a variant
+        let starts_variant = depth == 0 && (trimmed.starts_with('[') || trimmed.starts_with("*["));
+        depth += trimmed.matches('{').count() as i32 - trimmed.matches('}').count() as i32;
+        if !starts_variant {
+            match current.as_mut() {
+                Some((_, _, lines)) => lines.push(trimmed),
+                None if trimmed.is_empty() => {}
+                None => return Err("expected a variant".to_string()),
+            }
+            continue;
+        }
+        finish(&mut current)?;
+        let default = trimmed.starts_with('*');
+        let variant = trimmed.trim_start_matches('*');
+        let (key, value) = variant[1..]
+            .split_once(']')
+            .ok_or_else(|| "unclosed variant key".to_string())?;
+        current = Some((key.trim().to_string(), default, vec![value.trim_start()]));
+    }
+    finish(&mut current)?;
+
+    Ok(variants)
+}
+
+#[cfg(test)]
+mod tests {
+    use floem_reactive::{create_effect, RwSignal, SignalGet, SignalUpdate};
+
+    use super::*;
+
+    const EN: &str = r#"
+# Comments are ignored
+-brand = Floem
+welcome = Welcome to { -brand }, { $name }!
+files = { $count ->
+    [0] No files
+    [one] One file
+   *[other] { $count } files
+}
+multiline =
+    First line
+    second line
+button = Save
+    .tooltip = Save the { "document" }
+"#;
+
+    const RU: &str = r#"
+files = { $count ->
+    [one] { $count } файл
+    [few] { $count } файла
+   *[many] { $count } файлов
+}
+"#;
+
+    #[test]
+    fn translate_messages() {
+        add_translations("en", EN).unwrap();
+        set_locale("en-US");
+
+        assert_eq!(
+            translate("welcome", &[("name", "Ada".into())]),
+            "Welcome to Floem, Ada!"
+        );
+        assert_eq!(translate("files", &[("count", 0.into())]), "No files");
+        assert_eq!(translate("files", &[("count", 1.into())]), "One file");
+        assert_eq!(translate("files", &[("count", 1200.into())]), "1,200 files");
+        assert_eq!(translate("multiline", &[]), "First line\nsecond line");
+        assert_eq!(translate("button.tooltip", &[]), "Save the document");
+        assert_eq!(translate("missing", &[]), "missing");
+        assert_eq!(translate("welcome", &[]), "Welcome to Floem, {$name}!");
+    }
+
+    #[test]
+    fn locale_fallback_and_plurals() {
+        add_translations("en", EN).unwrap();
+        add_translations("ru", RU).unwrap();
+        set_locale("ru_RU.UTF-8");
+        assert_eq!(locale().get_untracked(), "ru-RU");
+
+        assert_eq!(translate("files", &[("count", 21.into())]), "21 файл");
+        assert_eq!(translate("files", &[("count", 3.into())]), "3 файла");
+        assert_eq!(translate("files", &[("count", 11.into())]), "11 файлов");
+        // Missing in Russian
+        assert_eq!(translate("button", &[]), "Save");
+    }
+
+    #[test]
+    fn switching_locale_updates_text() {
+        add_translations("en", "hello = Hello").unwrap();
+        add_translations("de", "hello = Hallo").unwrap();
+        set_locale("en");
+
+        let text = RwSignal::new(String::new());
+        let hello = crate::tr!("hello");
+        create_effect(move |_| text.set(hello()));
+        assert_eq!(text.get_untracked(), "Hello");
+
+        set_locale("de-AT");
+        assert_eq!(text.get_untracked(), "Hallo");
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(parse_resource("hello").unwrap_err().line, 1);
+        assert!(parse_resource("a = { $x ->\n [one] x\n}").is_err());
+        assert!(parse_resource("a = { oops").is_err());
+        assert!(parse_resource("\n  .attr = x").is_err());
+    }
+
+    #[test]
+    fn plural_categories() {
+        assert_eq!(plural_category("en", 1.0), PluralCategory::One);
+        assert_eq!(plural_category("en", 1.5), PluralCategory::Other);
+        assert_eq!(plural_category("fr", 0.0), PluralCategory::One);
+        assert_eq!(plural_category("pl", 22.0), PluralCategory::Few);
+        assert_eq!(plural_category("pl", 25.0), PluralCategory::Many);
+        assert_eq!(plural_category("ar", 102.0), PluralCategory::Other);
+        assert_eq!(plural_category("ar", 105.0), PluralCategory::Few);
+        assert_eq!(plural_category("ja", 1.0), PluralCategory::Other);
+    }
+
+    #[test]
+    fn dates_and_numbers() {
+        set_locale("en-US");
+        assert_eq!(format_date(2025, 3, 14), "3/14/2025");
+        assert_eq!(format_number(1234.5), "1,234.5");
+        set_locale("de");
+        assert_eq!(format_date(2025, 3, 14), "14.03.2025");
+        assert_eq!(format_number(1234.5), "1.234,5");
+        set_locale("sv-SE");
+        assert_eq!(format_date(2025, 3, 14), "2025-03-14");
+    }
+
+    #[test]
+    fn format_signal_and_plurals() {
+        set_locale("en");
+        let count = RwSignal::new(1usize);
+        let text = crate::format_signal!(
+            "{} {}",
+            count.get(),
+            crate::plural!(count.get(), one = "file", other = "files")
+        );
+        assert_eq!(text.get_untracked(), "1 file");
+        count.set(4);
+        assert_eq!(text.get_untracked(), "4 files");
+
+        set_locale("ru");
+        let form = || crate::plural!(count.get(), one = "файл", few = "файла", many = "файлов");
+        assert_eq!(form(), "файла");
+        count.set(5);
+        assert_eq!(form(), "файлов");
+        // No `few` form, so the last one is used
+        assert_eq!(crate::plural!(2, one = "a", many = "b"), "b");
+    }
+
+    #[test]
+    fn locale_directions() {
+        assert_eq!(locale_direction("ar-EG"), Direction::Rtl);
+        assert_eq!(locale_direction("he"), Direction::Rtl);
+        assert_eq!(locale_direction("en-US"), Direction::Ltr);
+        set_locale("fa_IR.UTF-8");
+        assert_eq!(direction(), Direction::Rtl);
+    }
+}
@@ -176,6 +176,7 @@
 //!
 //! For additional information about animation, [see here](crate::animate::Animation).
 
+pub mod accessibility;
 pub mod action;
 pub mod animate;
 mod app;
@@ -186,7 +187,9 @@ pub(crate) mod app_state;
 #[cfg(feature = "vello")]
 mod border_path_iter;
 mod clipboard;
+pub mod command;
 pub mod context;
+mod cursor;
 pub mod dropped_file;
 pub mod easing;
 pub mod event;
@@ -194,19 +197,37 @@ pub mod ext_event;
 pub mod file;
 #[cfg(any(feature = "rfd-async-std", feature = "rfd-tokio"))]
 pub mod file_action;
+pub mod gamepad;
 pub(crate) mod id;
 mod inspector;
 pub mod keyboard;
+pub mod l10n;
+pub mod measure;
 pub mod menu;
+pub mod monitor;
 mod nav;
+mod offscreen;
+#[cfg(feature = "serde")]
+pub mod persist;
 pub mod pointer;
+pub mod preferences;
+pub mod print;
 mod profiler;
 mod renderer;
 pub mod responsive;
 mod screen_layout;
+#[cfg(feature = "serde")]
+pub mod session;
+#[cfg(target_os = "windows")]
+mod snap_layout;
+pub mod sound;
 pub mod style;
+pub mod testing;
 pub(crate) mod theme;
 pub mod touchpad;
+#[cfg(feature = "tray")]
+pub mod tray;
+pub mod ui_scale;
 pub mod unit;
 mod update;
 pub(crate) mod view;
@@ -219,13 +240,16 @@ mod window_handle;
 mod window_id;
 mod window_tracking;
 
+pub use accesskit;
 pub use app::{launch, quit_app, AppEvent, Application};
 pub use app_state::AppState;
-pub use clipboard::{Clipboard, ClipboardError};
+pub use clipboard::{Clipboard, ClipboardContent, ClipboardError, ClipboardFormat, ClipboardImage};
 pub use floem_reactive as reactive;
+pub use floem_renderer::display_list;
 pub use floem_renderer::text;
 pub use floem_renderer::Renderer;
 pub use id::ViewId;
+pub use nav::FocusGroup;
 pub use peniko;
 pub use peniko::kurbo;
 pub use screen_layout::ScreenLayout;
@@ -235,6 +259,7 @@ pub use window::{close_window, new_window};
 pub use window_id::{Urgency, WindowIdExt};
 
 pub mod prelude {
+    pub use crate::ext_event::SpawnLocalExt;
     pub use crate::unit::{DurationUnitExt, UnitExt};
     pub use crate::view_tuple::ViewTuple;
     pub use crate::views::*;
@@ -0,0 +1,99 @@
+//! Hooks exposing the layout of views as signals, for views which need to know their size,
+//! like charts or virtual lists, without implementing [`View`](crate::View) themselves.
+//!
+//! ```rust
+//! use floem::{measure::use_measure, views::{label, Decorators}};
+//!
+//! let measure = use_measure();
+//! label(move || format!("{:.0} px wide", measure.size().width))
+//!     .measure(measure)
+//!     .style(|s| s.width_full());
+//! ```
+use floem_reactive::{ReadSignal, RwSignal, SignalGet, SignalUpdate, SignalWith};
+use peniko::kurbo::{Point, Rect, Size};
+
+/// The layout of a view, kept up to date once it's attached to the view with
+/// [`Decorators::measure`](crate::views::Decorators::measure).
+///
+/// All the getters are tracked, so effects and styles reading them rerun when the view is
+/// resized or moved.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Measure {
+    rect: RwSignal<Rect>,
+    window_origin: RwSignal<Point>,
+}
+
+/// Creates a [`Measure`] in the current scope, whose values are zero until the view it's
+/// attached to is laid out.
+pub fn use_measure() -> Measure {
+    Measure {
+        rect: RwSignal::new(Rect::ZERO),
+        window_origin: RwSignal::new(Point::ZERO),
+    }
+}
+
+impl Measure {
+    /// The layout rect of the view, relative to its parent.
+    pub fn rect(&self) -> ReadSignal<Rect> {
+        self.rect.read_only()
+    }
+
+    /// The size of the view.
+    pub fn size(&self) -> Size {
+        self.rect.with(|rect| rect.size())
+    }
+
+    /// The layout rect of the view, relative to the window.
+    pub fn window_rect(&self) -> Rect {
+        let size = self.size();
+        size.to_rect().with_origin(self.window_origin.get())
+    }
+
+    pub(crate) fn set_rect(&self, rect: Rect) {
+        if self.rect.get_untracked() != rect {
+            self.rect.set(rect);
+        }
+    }
+
+    pub(crate) fn set_window_origin(&self, origin: Point) {
+        if self.window_origin.get_untracked() != origin {
+            self.window_origin.set(origin);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use peniko::kurbo::{Point, Rect, Size};
+
+    use crate::{
+        testing::TestHarness,
+        views::{container, empty, Decorators},
+    };
+
+    use super::use_measure;
+
+    #[test]
+    fn follows_layout() {
+        let measure = use_measure();
+        let mut harness = TestHarness::new_with_size(
+            move || {
+                container(
+                    empty()
+                        .measure(measure)
+                        .style(|s| s.width_pct(50.0).height(20.0)),
+                )
+                .style(|s| s.padding(10.0).size_full())
+            },
+            Size::new(200.0, 100.0),
+        );
+        assert_eq!(measure.size(), Size::new(90.0, 20.0));
+        assert_eq!(
+            measure.window_rect(),
+            Rect::from_origin_size(Point::new(10.0, 10.0), (90.0, 20.0))
+        );
+
+        harness.resize(Size::new(400.0, 100.0));
+        assert_eq!(measure.size(), Size::new(190.0, 20.0));
+    }
+}
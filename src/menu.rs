@@ -1,5 +1,7 @@
 use std::sync::atomic::AtomicU64;
 
+use crate::keyboard::{Key, KeyEvent, Modifiers, NamedKey};
+
 /// An entry in a menu.
 ///
 /// An entry is either a [`MenuItem`], a submenu (i.e. [`Menu`]).
@@ -55,12 +57,7 @@ impl Menu {
                     let _ = menu.append(&muda::PredefinedMenuItem::separator());
                 }
                 MenuEntry::Item(item) => {
-                    let _ = menu.append(&muda::MenuItem::with_id(
-                        item.id.clone(),
-                        item.title.clone(),
-                        item.enabled,
-                        None,
-                    ));
+                    let _ = menu.append(item.platform_item().as_ref());
                 }
                 MenuEntry::SubMenu(floem_menu) => {
                     let _ = menu.append(&floem_menu.platform_submenu());
@@ -79,12 +76,7 @@ impl Menu {
                     let _ = menu.append(&muda::PredefinedMenuItem::separator());
                 }
                 MenuEntry::Item(item) => {
-                    let _ = menu.append(&muda::MenuItem::with_id(
-                        item.id.clone(),
-                        item.title.clone(),
-                        item.enabled,
-                        None,
-                    ));
+                    let _ = menu.append(item.platform_item().as_ref());
                 }
                 MenuEntry::SubMenu(floem_menu) => {
                     let _ = menu.append(&floem_menu.platform_submenu());
@@ -93,12 +85,62 @@ impl Menu {
         }
         menu
     }
+
+    /// Copies the structure of this menu without the actions.
+    ///
+    /// This is used by the in-window menu bar, which only needs the ids to dispatch the
+    /// actions that have already been registered for the window.
+    #[cfg(any(target_os = "linux", target_os = "freebsd"))]
+    pub(crate) fn display_copy(&self) -> Menu {
+        Menu {
+            popup: self.popup,
+            item: self.item.display_copy(),
+            children: self
+                .children
+                .iter()
+                .map(|entry| match entry {
+                    MenuEntry::Separator => MenuEntry::Separator,
+                    MenuEntry::Item(item) => MenuEntry::Item(item.display_copy()),
+                    MenuEntry::SubMenu(menu) => MenuEntry::SubMenu(menu.display_copy()),
+                })
+                .collect(),
+        }
+    }
+
+    /// Collects the parsed accelerators of all items in this menu together with the item ids.
+    #[cfg_attr(target_os = "macos", allow(dead_code))]
+    pub(crate) fn accelerators(&self) -> Vec<(MenuAccelerator, String)> {
+        let mut accelerators = Vec::new();
+        for entry in &self.children {
+            match entry {
+                MenuEntry::Separator => {}
+                MenuEntry::Item(item) => {
+                    if !item.enabled {
+                        continue;
+                    }
+                    if let Some(accelerator) =
+                        item.accelerator.as_deref().and_then(MenuAccelerator::parse)
+                    {
+                        accelerators.push((accelerator, item.id.clone()));
+                    }
+                }
+                MenuEntry::SubMenu(menu) => {
+                    if menu.item.enabled {
+                        accelerators.extend(menu.accelerators());
+                    }
+                }
+            }
+        }
+        accelerators
+    }
 }
 
 pub struct MenuItem {
     pub(crate) id: String,
     pub(crate) title: String,
     pub(crate) enabled: bool,
+    pub(crate) checked: Option<bool>,
+    pub(crate) accelerator: Option<String>,
     pub(crate) action: Option<Box<dyn Fn()>>,
 }
 
@@ -116,6 +158,8 @@ impl MenuItem {
             id: id.to_string(),
             title: title.into(),
             enabled: true,
+            checked: None,
+            accelerator: None,
             action: None,
         }
     }
@@ -129,4 +173,189 @@ impl MenuItem {
         self.enabled = enabled;
         self
     }
+
+    /// Makes this item checkable and sets whether it is currently checked.
+    pub fn checked(mut self, checked: bool) -> Self {
+        self.checked = Some(checked);
+        self
+    }
+
+    /// Sets the keyboard shortcut of this item, e.g. `"CmdOrCtrl+Shift+S"`.
+    ///
+    /// The accelerator is a `+` separated list of modifiers followed by a key. `CmdOrCtrl`
+    /// maps to the command key on macOS and to the control key on other platforms.
+    ///
+    /// The shortcut is shown next to the item and triggers its action when the key
+    /// combination is pressed in the window, unless a focused view handled the key first.
+    pub fn accelerator(mut self, accelerator: impl Into<String>) -> Self {
+        self.accelerator = Some(accelerator.into());
+        self
+    }
+
+    #[cfg(any(target_os = "windows", target_os = "macos"))]
+    fn platform_item(&self) -> Box<dyn muda::IsMenuItem> {
+        let accelerator = self
+            .accelerator
+            .as_deref()
+            .and_then(|accelerator| accelerator.parse::<muda::accelerator::Accelerator>().ok());
+        match self.checked {
+            Some(checked) => Box::new(muda::CheckMenuItem::with_id(
+                self.id.clone(),
+                self.title.clone(),
+                self.enabled,
+                checked,
+                accelerator,
+            )),
+            None => Box::new(muda::MenuItem::with_id(
+                self.id.clone(),
+                self.title.clone(),
+                self.enabled,
+                accelerator,
+            )),
+        }
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "freebsd"))]
+    fn display_copy(&self) -> MenuItem {
+        MenuItem {
+            id: self.id.clone(),
+            title: self.title.clone(),
+            enabled: self.enabled,
+            checked: self.checked,
+            accelerator: self.accelerator.clone(),
+            action: None,
+        }
+    }
+}
+
+/// A parsed menu accelerator, used to trigger window menu items from key presses on
+/// platforms where the native menu doesn't handle them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct MenuAccelerator {
+    pub(crate) modifiers: Modifiers,
+    pub(crate) key: Key,
+}
+
+impl MenuAccelerator {
+    pub(crate) fn parse(accelerator: &str) -> Option<Self> {
+        let mut modifiers = Modifiers::empty();
+        let mut key = None;
+        for token in accelerator.split('+').map(str::trim) {
+            if key.is_some() {
+                // The key has to be the last token
+                return None;
+            }
+            match token.to_ascii_lowercase().as_str() {
+                "shift" => modifiers.insert(Modifiers::SHIFT),
+                "ctrl" | "control" => modifiers.insert(Modifiers::CONTROL),
+                "alt" | "option" => modifiers.insert(Modifiers::ALT),
+                "super" | "cmd" | "command" | "meta" => modifiers.insert(Modifiers::META),
+                "cmdorctrl" | "commandorcontrol" => {
+                    if cfg!(target_os = "macos") {
+                        modifiers.insert(Modifiers::META);
+                    } else {
+                        modifiers.insert(Modifiers::CONTROL);
+                    }
+                }
+                other => key = Some(Self::parse_key(other)?),
+            }
+        }
+        Some(Self {
+            modifiers,
+            key: key?,
+        })
+    }
+
+    fn parse_key(key: &str) -> Option<Key> {
+        let named = match key {
+            "enter" | "return" => NamedKey::Enter,
+            "esc" | "escape" => NamedKey::Escape,
+            "tab" => NamedKey::Tab,
+            "space" => NamedKey::Space,
+            "backspace" => NamedKey::Backspace,
+            "del" | "delete" => NamedKey::Delete,
+            "insert" => NamedKey::Insert,
+            "home" => NamedKey::Home,
+            "end" => NamedKey::End,
+            "pageup" => NamedKey::PageUp,
+            "pagedown" => NamedKey::PageDown,
+            "up" | "arrowup" => NamedKey::ArrowUp,
+            "down" | "arrowdown" => NamedKey::ArrowDown,
+            "left" | "arrowleft" => NamedKey::ArrowLeft,
+            "right" | "arrowright" => NamedKey::ArrowRight,
+            "f1" => NamedKey::F1,
+            "f2" => NamedKey::F2,
+            "f3" => NamedKey::F3,
+            "f4" => NamedKey::F4,
+            "f5" => NamedKey::F5,
+            "f6" => NamedKey::F6,
+            "f7" => NamedKey::F7,
+            "f8" => NamedKey::F8,
+            "f9" => NamedKey::F9,
+            "f10" => NamedKey::F10,
+            "f11" => NamedKey::F11,
+            "f12" => NamedKey::F12,
+            "plus" => return Some(Key::Character("+".into())),
+            key if key.chars().count() == 1 => return Some(Key::Character(key.into())),
+            _ => return None,
+        };
+        Some(Key::Named(named))
+    }
+
+    /// Returns `true` if the key event triggers this accelerator.
+    pub(crate) fn matches(&self, event: &KeyEvent) -> bool {
+        if event.modifiers.difference(Modifiers::ALTGR) != self.modifiers {
+            return false;
+        }
+        match (&self.key, &event.key.logical_key) {
+            (Key::Character(expected), Key::Character(c)) => {
+                expected.eq_ignore_ascii_case(c.as_str())
+            }
+            (expected, key) => expected == key,
+        }
+    }
+
+    /// The text shown next to a menu item, e.g. `Ctrl+Shift+S`.
+    pub(crate) fn display(&self) -> String {
+        let mut parts = Vec::new();
+        if self.modifiers.control() {
+            parts.push("Ctrl".to_string());
+        }
+        if self.modifiers.alt() {
+            parts.push("Alt".to_string());
+        }
+        if self.modifiers.shift() {
+            parts.push("Shift".to_string());
+        }
+        if self.modifiers.meta() {
+            parts.push("Super".to_string());
+        }
+        parts.push(match &self.key {
+            Key::Character(c) => c.to_uppercase(),
+            Key::Named(named) => format!("{named:?}"),
+            key => format!("{key:?}"),
+        });
+        parts.join("+")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_accelerator() {
+        let accelerator = MenuAccelerator::parse("Ctrl+Shift+S").unwrap();
+        assert_eq!(accelerator.modifiers, Modifiers::CONTROL | Modifiers::SHIFT);
+        assert_eq!(accelerator.key, Key::Character("s".into()));
+        assert_eq!(accelerator.display(), "Ctrl+Shift+S");
+
+        let accelerator = MenuAccelerator::parse("alt+F4").unwrap();
+        assert_eq!(accelerator.modifiers, Modifiers::ALT);
+        assert_eq!(accelerator.key, Key::Named(NamedKey::F4));
+
+        assert!(MenuAccelerator::parse("Ctrl+Shift").is_none());
+        assert!(MenuAccelerator::parse("Ctrl+S+Shift").is_none());
+        assert!(MenuAccelerator::parse("Ctrl+Unknown").is_none());
+    }
 }
@@ -0,0 +1,107 @@
+//! Information about the monitors connected to the system.
+//!
+//! Use [`WindowIdExt::current_monitor`](crate::WindowIdExt::current_monitor) and
+//! [`WindowIdExt::available_monitors`](crate::WindowIdExt::available_monitors) to query
+//! monitors once, or [`window_monitor`] to get a signal which follows the monitor a
+//! window is on, e.g. when the user drags it to another screen.
+//!
+//! [`window_safe_area`] follows the parts of a window covered by the notches, rounded
+//! corners and system bars of mobile screens.
+use std::{cell::RefCell, collections::HashMap};
+
+use floem_reactive::{ReadSignal, RwSignal};
+use peniko::kurbo::{Insets, Rect};
+use winit::{monitor::MonitorHandle, window::WindowId};
+
+thread_local! {
+    static WINDOW_MONITORS: RefCell<HashMap<WindowId, RwSignal<Option<MonitorInfo>>>> =
+        Default::default();
+    static WINDOW_SAFE_AREAS: RefCell<HashMap<WindowId, RwSignal<Insets>>> =
+        Default::default();
+}
+
+/// A snapshot of the properties of a monitor.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonitorInfo {
+    /// The human readable name of the monitor, if the platform provides one.
+    pub name: Option<String>,
+    /// The bounds of the monitor in physical pixels, in the desktop coordinate space.
+    pub physical_bounds: Rect,
+    /// The scale factor of the monitor, e.g. `2.0` for a HiDPI display.
+    pub scale_factor: f64,
+    /// The refresh rate of the current video mode, in millihertz.
+    pub refresh_rate_millihertz: Option<u32>,
+}
+
+impl MonitorInfo {
+    pub(crate) fn from_handle(monitor: &MonitorHandle) -> Self {
+        let position = monitor.position().unwrap_or_default();
+        let video_mode = monitor.current_video_mode();
+        let size = video_mode
+            .as_ref()
+            .map(|mode| mode.size())
+            .unwrap_or_default();
+        let x = position.x as f64;
+        let y = position.y as f64;
+        Self {
+            name: monitor.name().map(|name| name.to_string()),
+            physical_bounds: Rect::new(x, y, x + size.width as f64, y + size.height as f64),
+            scale_factor: monitor.scale_factor(),
+            refresh_rate_millihertz: video_mode
+                .and_then(|mode| mode.refresh_rate_millihertz())
+                .map(u32::from),
+        }
+    }
+
+    /// The bounds of the monitor in logical pixels of the monitor's own scale factor.
+    pub fn logical_bounds(&self) -> Rect {
+        let scale = 1.0 / self.scale_factor;
+        Rect::new(
+            self.physical_bounds.x0 * scale,
+            self.physical_bounds.y0 * scale,
+            self.physical_bounds.x1 * scale,
+            self.physical_bounds.y1 * scale,
+        )
+    }
+
+    /// The refresh rate of the current video mode, in hertz.
+    pub fn refresh_rate(&self) -> Option<f64> {
+        self.refresh_rate_millihertz
+            .map(|millihertz| millihertz as f64 / 1000.0)
+    }
+}
+
+/// Get a signal with the monitor the window is currently on.
+///
+/// The signal is updated when the window moves to another monitor or the scale factor
+/// of its monitor changes. Returns `None` if the window doesn't exist (anymore).
+pub fn window_monitor(window_id: WindowId) -> Option<ReadSignal<Option<MonitorInfo>>> {
+    WINDOW_MONITORS.with_borrow(|monitors| monitors.get(&window_id).map(|m| m.read_only()))
+}
+
+pub(crate) fn store_window_monitor(window_id: WindowId, monitor: RwSignal<Option<MonitorInfo>>) {
+    WINDOW_MONITORS.with_borrow_mut(|monitors| monitors.insert(window_id, monitor));
+}
+
+pub(crate) fn remove_window_monitor(window_id: &WindowId) {
+    WINDOW_MONITORS.with_borrow_mut(|monitors| monitors.remove(window_id));
+}
+
+/// Get a signal with the insets of the window which are obscured by the screen or the system,
+/// in logical pixels.
+///
+/// The window pads its content by these insets unless
+/// [`WindowConfig::safe_area_padding`](crate::window::WindowConfig::safe_area_padding) is
+/// disabled, in which case views can use this signal to only keep their content in the safe
+/// area. The insets are zero on desktop platforms.
+pub fn window_safe_area(window_id: WindowId) -> Option<ReadSignal<Insets>> {
+    WINDOW_SAFE_AREAS.with_borrow(|areas| areas.get(&window_id).map(|a| a.read_only()))
+}
+
+pub(crate) fn store_window_safe_area(window_id: WindowId, safe_area: RwSignal<Insets>) {
+    WINDOW_SAFE_AREAS.with_borrow_mut(|areas| areas.insert(window_id, safe_area));
+}
+
+pub(crate) fn remove_window_safe_area(window_id: &WindowId) {
+    WINDOW_SAFE_AREAS.with_borrow_mut(|areas| areas.remove(window_id));
+}
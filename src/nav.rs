@@ -1,7 +1,15 @@
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::{Duration, Instant};
+#[cfg(target_arch = "wasm32")]
+use web_time::{Duration, Instant};
+
 use peniko::kurbo::{Point, Rect};
-use winit::keyboard::NamedKey;
+use winit::keyboard::{Key, NamedKey};
 
-use crate::{app_state::AppState, id::ViewId, view::view_tab_navigation};
+use crate::{
+    accessibility::view_text, app_state::AppState, id::ViewId, keyboard::Modifiers,
+    view::view_tab_navigation,
+};
 
 pub(crate) fn view_arrow_navigation(key: NamedKey, app_state: &mut AppState, view: ViewId) {
     let focused = match app_state.focus {
@@ -72,3 +80,290 @@ pub(crate) fn view_arrow_navigation(key: NamedKey, app_state: &mut AppState, vie
         app_state.update_focus(id, true);
     }
 }
+
+/// How long typeahead waits for the next character before starting a new search.
+const TYPEAHEAD_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Describes how the keyboard moves the focus between the keyboard navigable views inside a
+/// focus group, see [`Decorators::focus_group`](crate::views::Decorators::focus_group).
+///
+/// Inside a focus group the arrow keys move the focus to the previous or next view,
+/// <kbd>Home</kbd> and <kbd>End</kbd> to the first or last view, and typing focuses the
+/// next view whose text starts with the typed characters. <kbd>Tab</kbd> doesn't stop at
+/// every view of the group, but only at the one which was focused last.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FocusGroup {
+    horizontal: bool,
+    vertical: bool,
+    wrap: bool,
+    typeahead: bool,
+}
+
+impl FocusGroup {
+    fn new(horizontal: bool, vertical: bool) -> Self {
+        Self {
+            horizontal,
+            vertical,
+            wrap: false,
+            typeahead: true,
+        }
+    }
+
+    /// The up and down arrows move the focus, e.g. in a list or a menu.
+    pub fn vertical() -> Self {
+        Self::new(false, true)
+    }
+
+    /// The left and right arrows move the focus, e.g. in a tab bar or a toolbar.
+    pub fn horizontal() -> Self {
+        Self::new(true, false)
+    }
+
+    /// All arrows move the focus, e.g. in a group of radio buttons.
+    pub fn both() -> Self {
+        Self::new(true, true)
+    }
+
+    /// Whether moving past the last view focuses the first one and the other way around.
+    /// Defaults to `false`.
+    pub fn wrap(mut self, wrap: bool) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    /// Whether typing focuses the view whose text starts with the typed characters.
+    /// Defaults to `true`.
+    pub fn typeahead(mut self, typeahead: bool) -> Self {
+        self.typeahead = typeahead;
+        self
+    }
+}
+
+/// The search query of typeahead, which is reset when nothing is typed for a while.
+#[derive(Default)]
+pub(crate) struct Typeahead {
+    query: String,
+    last_input: Option<Instant>,
+}
+
+impl Typeahead {
+    /// Adds the typed text to the query, and returns the index of the item the query
+    /// matches, starting from the `current` item.
+    pub(crate) fn search(
+        &mut self,
+        text: &str,
+        current: Option<usize>,
+        items: &[String],
+    ) -> Option<usize> {
+        let now = Instant::now();
+        if self
+            .last_input
+            .is_some_and(|last| now.saturating_duration_since(last) > TYPEAHEAD_TIMEOUT)
+        {
+            self.query.clear();
+        }
+        self.last_input = Some(now);
+        self.query.push_str(&text.to_lowercase());
+        typeahead_match(&self.query, current, items)
+    }
+}
+
+fn typeahead_match(query: &str, current: Option<usize>, items: &[String]) -> Option<usize> {
+    let mut chars = query.chars();
+    let first = chars.next()?;
+    // Typing the same character repeatedly cycles through the items starting with it
+    let (query, start) = if chars.all(|c| c == first) {
+        (
+            &query[..first.len_utf8()],
+            current.map(|i| i + 1).unwrap_or(0),
+        )
+    } else {
+        (query, current.unwrap_or(0))
+    };
+    (0..items.len())
+        .map(|i| (start + i) % items.len())
+        .find(|&i| items[i].trim_start().to_lowercase().starts_with(query))
+}
+
+/// The closest focus group containing `id` as a member, which excludes the view of the
+/// group itself.
+fn member_focus_group(id: ViewId) -> Option<ViewId> {
+    let mut ancestor = id.parent()?;
+    loop {
+        if ancestor.state().borrow().focus_group.is_some() {
+            return Some(ancestor);
+        }
+        ancestor = ancestor.parent()?;
+    }
+}
+
+/// The focusable views of the group, in tree order. The views of nested focus groups
+/// belong to those groups instead.
+fn focus_group_members(app_state: &AppState, group: ViewId) -> Vec<ViewId> {
+    fn collect(app_state: &AppState, id: ViewId, members: &mut Vec<ViewId>) {
+        for child in id.children() {
+            if child.style_has_hidden() {
+                continue;
+            }
+            if app_state.can_focus(child) {
+                members.push(child);
+            }
+            if child.state().borrow().focus_group.is_none() {
+                collect(app_state, child, members);
+            }
+        }
+    }
+    let mut members = Vec::new();
+    collect(app_state, group, &mut members);
+    members
+}
+
+/// Remembers the focused view as the view <kbd>Tab</kbd> stops at in its focus group.
+pub(crate) fn focus_group_focused(app_state: &mut AppState, id: ViewId) {
+    if let Some(group) = member_focus_group(id) {
+        app_state.focus_group_entries.insert(group, id);
+    }
+}
+
+/// Whether <kbd>Tab</kbd> navigation starting from `from` should stop at `id`. Only the
+/// view of a focus group which was focused last is a tab stop, or the first one if none
+/// was focused yet, and tabbing from inside a group leaves the group.
+pub(crate) fn is_tab_stop(app_state: &AppState, id: ViewId, from: ViewId) -> bool {
+    let Some(group) = member_focus_group(id) else {
+        return true;
+    };
+    if member_focus_group(from) == Some(group) {
+        return false;
+    }
+    let entry = app_state
+        .focus_group_entries
+        .get(&group)
+        .copied()
+        .filter(|entry| member_focus_group(*entry) == Some(group) && app_state.can_focus(*entry));
+    match entry {
+        Some(entry) => entry == id,
+        None => focus_group_members(app_state, group).first() == Some(&id),
+    }
+}
+
+/// Moves the focus inside the focus groups containing the focused view, returns `true`
+/// if the key was handled.
+pub(crate) fn focus_group_navigation(
+    app_state: &mut AppState,
+    key: &Key,
+    modifiers: Modifiers,
+) -> bool {
+    let Some(focused) = app_state.focus else {
+        return false;
+    };
+    // The focused view can itself be a focus group, whose members are reached with the
+    // arrow keys
+    let mut group = if focused.state().borrow().focus_group.is_some() {
+        Some(focused)
+    } else {
+        member_focus_group(focused)
+    };
+    while let Some(group_id) = group {
+        let Some(focus_group) = group_id.state().borrow().focus_group else {
+            break;
+        };
+        if focus_group_move(app_state, group_id, focus_group, focused, key, modifiers) {
+            return true;
+        }
+        // Keys which the group doesn't handle move the focus in the enclosing group
+        group = member_focus_group(group_id);
+    }
+    false
+}
+
+fn focus_group_move(
+    app_state: &mut AppState,
+    group_id: ViewId,
+    group: FocusGroup,
+    focused: ViewId,
+    key: &Key,
+    modifiers: Modifiers,
+) -> bool {
+    let members = focus_group_members(app_state, group_id);
+    if members.is_empty() {
+        return false;
+    }
+    let last = members.len() - 1;
+    let current = members.iter().position(|id| *id == focused);
+    let next = |current: Option<usize>| match current {
+        Some(i) if i < last => i + 1,
+        Some(_) if group.wrap => 0,
+        Some(i) => i,
+        None => 0,
+    };
+    let previous = |current: Option<usize>| match current {
+        Some(i) if i > 0 => i - 1,
+        Some(_) if group.wrap => last,
+        Some(i) => i,
+        None => last,
+    };
+
+    let new_focus = match key {
+        _ if !modifiers.is_empty() && !matches!(key, Key::Character(_)) => return false,
+        Key::Named(NamedKey::ArrowDown) if group.vertical => next(current),
+        Key::Named(NamedKey::ArrowUp) if group.vertical => previous(current),
+        Key::Named(NamedKey::ArrowRight) if group.horizontal => next(current),
+        Key::Named(NamedKey::ArrowLeft) if group.horizontal => previous(current),
+        Key::Named(NamedKey::Home) => 0,
+        Key::Named(NamedKey::End) => last,
+        Key::Character(text)
+            if group.typeahead
+                && modifiers
+                    .difference(Modifiers::SHIFT | Modifiers::ALTGR)
+                    .is_empty()
+                && !text.trim().is_empty() =>
+        {
+            let items: Vec<String> = members.iter().map(|id| view_text(*id)).collect();
+            let Some(found) = app_state.typeahead.search(text, current, &items) else {
+                return false;
+            };
+            found
+        }
+        _ => return false,
+    };
+
+    let new_focus = members[new_focus];
+    if new_focus != focused {
+        app_state.clear_focus();
+        app_state.update_focus(new_focus, true);
+        new_focus.scroll_to(None);
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::typeahead_match;
+
+    fn items() -> Vec<String> {
+        ["Apple", "Banana", "blueberry", "Cherry", "Apricot"]
+            .into_iter()
+            .map(String::from)
+            .collect()
+    }
+
+    #[test]
+    fn typeahead_matches_prefix_case_insensitively() {
+        assert_eq!(typeahead_match("bl", None, &items()), Some(2));
+        assert_eq!(typeahead_match("ch", Some(1), &items()), Some(3));
+        assert_eq!(typeahead_match("x", None, &items()), None);
+    }
+
+    #[test]
+    fn typeahead_repeated_character_cycles() {
+        assert_eq!(typeahead_match("a", Some(0), &items()), Some(4));
+        assert_eq!(typeahead_match("aa", Some(4), &items()), Some(0));
+        assert_eq!(typeahead_match("b", None, &items()), Some(1));
+        assert_eq!(typeahead_match("b", Some(1), &items()), Some(2));
+    }
+
+    #[test]
+    fn typeahead_longer_query_keeps_current() {
+        assert_eq!(typeahead_match("ban", Some(1), &items()), Some(1));
+    }
+}
@@ -0,0 +1,80 @@
+//! Views laid out and painted without a window, for printing and [`crate::testing`].
+
+use floem_renderer::display_list::DisplayList;
+use peniko::kurbo::{Rect, Size};
+
+use crate::{id::ViewId, view::IntoView, window_handle::WindowHandle};
+
+/// A view tree mounted in a headless window, which runs style, layout and paint like a
+/// window would and paints with the software renderer.
+pub(crate) struct OffscreenView {
+    pub(crate) handle: WindowHandle,
+}
+
+impl OffscreenView {
+    /// Mounts the view in a headless window of `size`, with the scale factor of a monitor.
+    pub(crate) fn new<V: IntoView + 'static>(
+        view_fn: impl FnOnce() -> V + 'static,
+        size: Size,
+        scale: f64,
+    ) -> Self {
+        let mut view = Self {
+            handle: WindowHandle::new_headless(move |_| view_fn().into_any(), size, scale),
+        };
+        view.update();
+        view
+    }
+
+    /// The id of the mounted view.
+    pub(crate) fn root(&self) -> ViewId {
+        self.handle.main_view()
+    }
+
+    /// Runs the pending idle effects, and then style, layout and paint like a frame would.
+    pub(crate) fn update(&mut self) {
+        loop {
+            let ran_idle_effects = floem_reactive::has_idle_effects();
+            floem_reactive::run_idle_effects(|| false);
+            self.handle.render_frame();
+            if !ran_idle_effects {
+                break;
+            }
+        }
+    }
+
+    /// Paints the views into an image of the window size times the scale.
+    pub(crate) fn render(&mut self) -> image::RgbaImage {
+        let frame = self
+            .handle
+            .render_offscreen()
+            .expect("the offscreen renderer returns every frame");
+        image::RgbaImage::from_raw(frame.width, frame.height, frame.data.data().to_vec())
+            .expect("the frame is RGBA8")
+    }
+
+    /// Paints the part `rect` of a view and its children, in the coordinates of the view,
+    /// into an image of the size of `rect` times `scale`.
+    pub(crate) fn capture_image_rect(
+        &mut self,
+        id: ViewId,
+        rect: Rect,
+        scale: f64,
+    ) -> image::RgbaImage {
+        self.handle
+            .capture_image_rect(id, rect, scale)
+            .expect("the view is laid out")
+    }
+
+    /// Records the draw commands of a view and its children, in the coordinates of the view.
+    pub(crate) fn record_display_list(&mut self, id: ViewId) -> DisplayList {
+        self.handle
+            .record_display_list(id)
+            .expect("the view is laid out")
+    }
+}
+
+impl Drop for OffscreenView {
+    fn drop(&mut self) {
+        self.handle.destroy();
+    }
+}
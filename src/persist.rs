@@ -0,0 +1,283 @@
+//! Signals whose value survives restarts of the application.
+//!
+//! [`create_persistent_signal`] loads the value stored under a key and saves it again
+//! whenever it changes, after the changes settled for a moment. The values are stored by
+//! a [`StorageBackend`], which is a [`JsonFileStorage`] in the configuration directory of
+//! the user unless another one is set with [`set_storage_backend`].
+//!
+//! Stored values carry the version of their schema, so that values stored by an older
+//! version of the application can be migrated with [`PersistOptions::migrate`] instead of
+//! being replaced by the default.
+//!
+//! ## Example
+//! ```no_run
+//! use floem::persist::*;
+//! use floem::reactive::*;
+//!
+//! let theme = create_persistent_signal("theme", "dark".to_string());
+//! theme.set("light".to_string());
+//! ```
+
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    rc::Rc,
+    time::Duration,
+};
+
+use floem_reactive::{create_effect, create_rw_signal, RwSignal, SignalGet};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::action::{exec_after, TimerToken};
+
+thread_local! {
+    static STORAGE: RefCell<Option<Rc<dyn StorageBackend>>> = const { RefCell::new(None) };
+    /// Saves which are waiting for their debounce timer
+    static PENDING: RefCell<HashMap<String, (TimerToken, StoredValue)>> =
+        RefCell::new(HashMap::new());
+}
+
+/// A value as it is stored, together with the version of its schema.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StoredValue {
+    pub version: u32,
+    pub value: Value,
+}
+
+/// Where persistent signals are loaded from and saved to.
+pub trait StorageBackend {
+    fn load(&self, key: &str) -> Option<StoredValue>;
+
+    fn save(&self, key: &str, value: StoredValue) -> io::Result<()>;
+}
+
+/// Stores all values as one JSON object in a file.
+///
+/// The file is read once, and rewritten on every save by writing a temporary file and
+/// moving it over the old one, so a crash never leaves a half written file behind.
+pub struct JsonFileStorage {
+    path: PathBuf,
+    values: RefCell<Option<HashMap<String, StoredValue>>>,
+}
+
+impl JsonFileStorage {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            values: RefCell::new(None),
+        }
+    }
+
+    /// Stores the values in `<config dir>/<app_name>/state.json`, where the config dir is
+    /// `%APPDATA%` on Windows, `~/Library/Application Support` on macOS and
+    /// `$XDG_CONFIG_HOME` or `~/.config` elsewhere.
+    pub fn in_config_dir(app_name: &str) -> Option<Self> {
+        Some(Self::new(config_dir()?.join(app_name).join("state.json")))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn with_values<O>(&self, f: impl FnOnce(&mut HashMap<String, StoredValue>) -> O) -> O {
+        let mut values = self.values.borrow_mut();
+        let values = values.get_or_insert_with(|| {
+            fs::read(&self.path)
+                .ok()
+                .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+                .unwrap_or_default()
+        });
+        f(values)
+    }
+}
+
+impl StorageBackend for JsonFileStorage {
+    fn load(&self, key: &str) -> Option<StoredValue> {
+        self.with_values(|values| values.get(key).cloned())
+    }
+
+    fn save(&self, key: &str, value: StoredValue) -> io::Result<()> {
+        let json = self.with_values(|values| {
+            values.insert(key.to_string(), value);
+            serde_json::to_vec_pretty(values)
+        })?;
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let tmp_path = self.path.with_extension("json.tmp");
+        let mut file = fs::File::create(&tmp_path)?;
+        file.write_all(&json)?;
+        file.sync_all()?;
+        fs::rename(&tmp_path, &self.path)
+    }
+}
+
+fn config_dir() -> Option<PathBuf> {
+    let var = |name| std::env::var_os(name).filter(|value| !value.is_empty());
+    if cfg!(target_os = "windows") {
+        var("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        var("HOME").map(|home| PathBuf::from(home).join("Library/Application Support"))
+    } else {
+        var("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| var("HOME").map(|home| PathBuf::from(home).join(".config")))
+    }
+}
+
+/// Sets where persistent signals are stored. This has to be called before the first
+/// persistent signal is created.
+pub fn set_storage_backend(storage: impl StorageBackend + 'static) {
+    STORAGE.with_borrow_mut(|backend| *backend = Some(Rc::new(storage)));
+}
+
+/// The backend set with [`set_storage_backend`], or a [`JsonFileStorage`] named after the
+/// executable.
+fn storage_backend() -> Option<Rc<dyn StorageBackend>> {
+    STORAGE.with_borrow_mut(|backend| {
+        if backend.is_none() {
+            let app_name = std::env::current_exe()
+                .ok()?
+                .file_stem()?
+                .to_string_lossy()
+                .into_owned();
+            *backend = Some(Rc::new(JsonFileStorage::in_config_dir(&app_name)?));
+        }
+        backend.clone()
+    })
+}
+
+type Migration = Rc<dyn Fn(u32, Value) -> Option<Value>>;
+
+/// Options for [`create_persistent_signal_with_options`].
+#[derive(Clone)]
+pub struct PersistOptions {
+    version: u32,
+    migrate: Option<Migration>,
+    debounce: Duration,
+}
+
+impl Default for PersistOptions {
+    fn default() -> Self {
+        Self {
+            version: 0,
+            migrate: None,
+            debounce: Duration::from_millis(500),
+        }
+    }
+}
+
+impl PersistOptions {
+    /// The version of the schema of the value, which is stored with it. Increase it when
+    /// the type changes in a way that old values can't be read anymore.
+    pub fn version(mut self, version: u32) -> Self {
+        self.version = version;
+        self
+    }
+
+    /// Converts values stored with an older version, `migrate` gets the version of the
+    /// stored value. Returning `None` uses the default instead.
+    pub fn migrate(mut self, migrate: impl Fn(u32, Value) -> Option<Value> + 'static) -> Self {
+        self.migrate = Some(Rc::new(migrate));
+        self
+    }
+
+    /// How long the value has to stay unchanged before it is saved, to not write the
+    /// file on every step of e.g. resizing a window. Defaults to 500ms.
+    pub fn debounce(mut self, debounce: Duration) -> Self {
+        self.debounce = debounce;
+        self
+    }
+}
+
+/// Create a signal with the value stored under `key`, or `default` if there is none, which
+/// saves its value whenever it changes.
+pub fn create_persistent_signal<T>(key: impl Into<String>, default: T) -> RwSignal<T>
+where
+    T: Serialize + DeserializeOwned + Clone + 'static,
+{
+    create_persistent_signal_with_options(key, default, PersistOptions::default())
+}
+
+/// Like [`create_persistent_signal`], with a schema version, a migration and the debounce
+/// duration set in `options`.
+pub fn create_persistent_signal_with_options<T>(
+    key: impl Into<String>,
+    default: T,
+    options: PersistOptions,
+) -> RwSignal<T>
+where
+    T: Serialize + DeserializeOwned + Clone + 'static,
+{
+    let key = key.into();
+    let storage = storage_backend();
+    let initial = storage
+        .as_ref()
+        .and_then(|storage| storage.load(&key))
+        .and_then(|stored| load_value(stored, &options))
+        .unwrap_or(default);
+    let signal = create_rw_signal(initial);
+
+    create_effect(move |first_run: Option<()>| {
+        let value = signal.get();
+        if first_run.is_none() {
+            return;
+        }
+        let Some(storage) = storage.clone() else {
+            return;
+        };
+        let Ok(value) = serde_json::to_value(value) else {
+            return;
+        };
+        let stored = StoredValue {
+            version: options.version,
+            value,
+        };
+        let timer = exec_after(options.debounce, {
+            let key = key.clone();
+            move |_| {
+                if let Some((_, stored)) = PENDING.with_borrow_mut(|pending| pending.remove(&key)) {
+                    save(&*storage, &key, stored);
+                }
+            }
+        });
+        let prev = PENDING.with_borrow_mut(|pending| pending.insert(key.clone(), (timer, stored)));
+        if let Some((prev_timer, _)) = prev {
+            prev_timer.cancel();
+        }
+    });
+
+    signal
+}
+
+fn load_value<T: DeserializeOwned>(stored: StoredValue, options: &PersistOptions) -> Option<T> {
+    let value = if stored.version == options.version {
+        stored.value
+    } else {
+        (options.migrate.as_ref()?)(stored.version, stored.value)?
+    };
+    serde_json::from_value(value).ok()
+}
+
+fn save(storage: &dyn StorageBackend, key: &str, stored: StoredValue) {
+    if let Err(err) = storage.save(key, stored) {
+        eprintln!("failed to save persistent signal {key}: {err}");
+    }
+}
+
+/// Saves the values whose debounce timer didn't fire yet. This is done when the
+/// application exits.
+pub fn flush() {
+    let pending = PENDING.with_borrow_mut(std::mem::take);
+    let Some(storage) = storage_backend() else {
+        return;
+    };
+    for (key, (timer, stored)) in pending {
+        timer.cancel();
+        save(&*storage, &key, stored);
+    }
+}
@@ -120,16 +120,45 @@ impl MouseButton {
     }
 }
 
+/// The state of a pen or stylus, for drawing and annotation views.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PenState {
+    /// The pressure of the tip, from 0 to 1
+    pub pressure: f64,
+    /// The tilt of the pen along each axis in degrees, from -90 to 90, which is 0 when the pen
+    /// is perpendicular to the surface
+    pub tilt: Vec2,
+    /// Whether the eraser end of the pen is used
+    pub eraser: bool,
+    /// Whether the button on the barrel of the pen is pressed
+    pub barrel_button: bool,
+}
+
 #[derive(Debug, Clone)]
 pub struct PointerInputEvent {
     pub pos: Point,
     pub button: PointerButton,
     pub modifiers: Modifiers,
     pub count: u8,
+    /// The state of the pen, for the events of a pen rather than a mouse or a finger
+    pub pen: Option<PenState>,
 }
 
 #[derive(Debug, Clone)]
 pub struct PointerMoveEvent {
     pub pos: Point,
     pub modifiers: Modifiers,
+    /// The state of the pen, for the events of a pen rather than a mouse or a finger
+    pub pen: Option<PenState>,
+}
+
+/// A touch of a finger on a touch screen, see [`Event::TouchStart`](crate::event::Event::TouchStart).
+#[derive(Debug, Clone)]
+pub struct TouchEvent {
+    /// Identifies the finger from the start to the end of the touch
+    pub finger_id: FingerId,
+    pub pos: Point,
+    /// The pressure of the finger, on the touch screens which measure it
+    pub force: Option<Force>,
+    pub modifiers: Modifiers,
 }
@@ -0,0 +1,233 @@
+//! Accessibility preferences of the user, like reduced motion and high contrast.
+//!
+//! The preferences are read from the operating system when a window is created or focused,
+//! and can be overridden by the application, e.g. from its own settings. While reduced
+//! motion is preferred, [animations](crate::animate) jump to their end and style transitions
+//! are skipped. While high contrast is preferred, the built-in widgets are styled with the
+//! high-contrast colors of the default theme.
+//! ```rust
+//! use floem::{preferences, reactive::SignalGet, views::*};
+//!
+//! let reduced_motion = preferences::reduced_motion();
+//! label(move || if reduced_motion.get() { "Animations off" } else { "Animations on" });
+//! ```
+use std::cell::Cell;
+
+use floem_reactive::{ReadSignal, RwSignal, Scope, SignalGet, SignalUpdate};
+
+use crate::app::{add_app_update_event, AppUpdateEvent};
+
+/// A preference of the operating system, which can be overridden by the application.
+struct Preference {
+    value: RwSignal<bool>,
+    os: Cell<bool>,
+    overridden: Cell<Option<bool>>,
+}
+
+impl Preference {
+    fn new() -> Self {
+        Self {
+            value: Scope::new().create_rw_signal(false),
+            os: Cell::new(false),
+            overridden: Cell::new(None),
+        }
+    }
+
+    /// Updates the value from the preference of the operating system and the override.
+    /// Returns `true` if it changed.
+    fn update(&self) -> bool {
+        let value = self.overridden.get().unwrap_or(self.os.get());
+        if self.value.get_untracked() == value {
+            return false;
+        }
+        self.value.set(value);
+        true
+    }
+}
+
+thread_local! {
+    static REDUCED_MOTION: Preference = Preference::new();
+    static HIGH_CONTRAST: Preference = Preference::new();
+}
+
+/// Whether the user prefers less motion, like animations and smooth transitions.
+pub fn reduced_motion() -> ReadSignal<bool> {
+    REDUCED_MOTION.with(|preference| preference.value.read_only())
+}
+
+/// Whether the user prefers colors with a higher contrast.
+pub fn high_contrast() -> ReadSignal<bool> {
+    HIGH_CONTRAST.with(|preference| preference.value.read_only())
+}
+
+/// Overrides the reduced motion preference of the operating system, or follows it again
+/// with `None`.
+pub fn set_reduced_motion(reduced_motion: Option<bool>) {
+    REDUCED_MOTION.with(|preference| {
+        preference.overridden.set(reduced_motion);
+        preference.update();
+    });
+}
+
+/// Overrides the high contrast preference of the operating system, or follows it again
+/// with `None`.
+pub fn set_high_contrast(high_contrast: Option<bool>) {
+    let changed = HIGH_CONTRAST.with(|preference| {
+        preference.overridden.set(high_contrast);
+        preference.update()
+    });
+    if changed {
+        add_app_update_event(AppUpdateEvent::HighContrastChanged);
+    }
+}
+
+pub(crate) fn prefers_reduced_motion() -> bool {
+    REDUCED_MOTION.with(|preference| preference.value.get_untracked())
+}
+
+pub(crate) fn prefers_high_contrast() -> bool {
+    HIGH_CONTRAST.with(|preference| preference.value.get_untracked())
+}
+
+/// Reads the preferences of the operating system again.
+pub(crate) fn refresh() {
+    let os = OsPreferences::read();
+    REDUCED_MOTION.with(|preference| {
+        preference.os.set(os.reduced_motion);
+        preference.update();
+    });
+    let high_contrast_changed = HIGH_CONTRAST.with(|preference| {
+        preference.os.set(os.high_contrast);
+        preference.update()
+    });
+    if high_contrast_changed {
+        add_app_update_event(AppUpdateEvent::HighContrastChanged);
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct OsPreferences {
+    reduced_motion: bool,
+    high_contrast: bool,
+}
+
+impl OsPreferences {
+    #[cfg(target_os = "windows")]
+    fn read() -> Self {
+        use windows_sys::Win32::UI::{
+            Accessibility::{HCF_HIGHCONTRASTON, HIGHCONTRASTW},
+            WindowsAndMessaging::{
+                SystemParametersInfoW, SPI_GETCLIENTAREAANIMATION, SPI_GETHIGHCONTRAST,
+            },
+        };
+
+        let mut animations = 1;
+        let mut high_contrast = HIGHCONTRASTW {
+            cbSize: std::mem::size_of::<HIGHCONTRASTW>() as u32,
+            dwFlags: 0,
+            lpszDefaultScheme: std::ptr::null_mut(),
+        };
+        unsafe {
+            if SystemParametersInfoW(
+                SPI_GETCLIENTAREAANIMATION,
+                0,
+                &mut animations as *mut i32 as *mut _,
+                0,
+            ) == 0
+            {
+                animations = 1;
+            }
+            if SystemParametersInfoW(
+                SPI_GETHIGHCONTRAST,
+                high_contrast.cbSize,
+                &mut high_contrast as *mut HIGHCONTRASTW as *mut _,
+                0,
+            ) == 0
+            {
+                high_contrast.dwFlags = 0;
+            }
+        }
+        Self {
+            reduced_motion: animations == 0,
+            high_contrast: high_contrast.dwFlags & HCF_HIGHCONTRASTON != 0,
+        }
+    }
+
+    #[cfg(target_os = "macos")]
+    #[allow(unused_unsafe)]
+    fn read() -> Self {
+        use objc2_app_kit::NSWorkspace;
+
+        let workspace = unsafe { NSWorkspace::sharedWorkspace() };
+        unsafe {
+            Self {
+                reduced_motion: workspace.accessibilityDisplayShouldReduceMotion(),
+                high_contrast: workspace.accessibilityDisplayShouldIncreaseContrast(),
+            }
+        }
+    }
+
+    /// The GNOME settings, which most other desktops follow.
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "freebsd",
+        target_os = "dragonfly",
+        target_os = "openbsd",
+        target_os = "netbsd"
+    ))]
+    fn read() -> Self {
+        fn gsetting(schema: &str, key: &str) -> Option<bool> {
+            let output = std::process::Command::new("gsettings")
+                .args(["get", schema, key])
+                .output()
+                .ok()?;
+            match String::from_utf8_lossy(&output.stdout).trim() {
+                "true" => Some(true),
+                "false" => Some(false),
+                _ => None,
+            }
+        }
+        Self {
+            reduced_motion: gsetting("org.gnome.desktop.interface", "enable-animations")
+                == Some(false),
+            high_contrast: gsetting("org.gnome.desktop.a11y.interface", "high-contrast")
+                == Some(true),
+        }
+    }
+
+    #[cfg(not(any(
+        target_os = "windows",
+        target_os = "macos",
+        target_os = "linux",
+        target_os = "freebsd",
+        target_os = "dragonfly",
+        target_os = "openbsd",
+        target_os = "netbsd"
+    )))]
+    fn read() -> Self {
+        Self::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use floem_reactive::SignalGet;
+
+    use super::{prefers_reduced_motion, reduced_motion, set_reduced_motion, REDUCED_MOTION};
+
+    #[test]
+    fn overrides() {
+        let signal = reduced_motion();
+        set_reduced_motion(Some(true));
+        assert!(signal.get_untracked());
+
+        // The override takes precedence over the operating system until it's removed
+        REDUCED_MOTION.with(|preference| {
+            preference.os.set(false);
+            preference.update();
+        });
+        assert!(prefers_reduced_motion());
+        set_reduced_motion(None);
+        assert!(!signal.get_untracked());
+    }
+}
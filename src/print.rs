@@ -0,0 +1,488 @@
+//! Printing views and exporting them to PDF.
+//!
+//! A [`PrintDocument`] lays out a view at the width of a page, splits it into pages
+//! without cutting through lines of text, and writes the pages into a PDF document or
+//! prints them with the print dialog of the platform, with an optional header and footer
+//! in the margins.
+//!
+//! Page sizes and margins are in points, 1/72 of an inch, and views are laid out with one
+//! logical pixel per point.
+//!
+//! ```no_run
+//! use floem::{
+//!     print::{PageSetup, PrintDocument},
+//!     views::{label, Decorators},
+//! };
+//!
+//! PrintDocument::new(|| label(|| "Hello, paper"))
+//!     .page_setup(PageSetup::new(PageSetup::LETTER).margins(72.0))
+//!     .footer(|page| label(move || format!("Page {} of {}", page.number, page.count)))
+//!     .write_pdf("hello.pdf")
+//!     .unwrap();
+//! ```
+
+mod dialog;
+mod pdf;
+
+use std::{
+    cell::RefCell,
+    io,
+    ops::Range,
+    path::{Path, PathBuf},
+    rc::Rc,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use floem_renderer::display_list::DisplayList;
+use image::RgbaImage;
+use peniko::kurbo::{Insets, Rect, Size};
+use taffy::AlignItems;
+
+use crate::{
+    id::ViewId,
+    offscreen::OffscreenView,
+    view::{AnyView, IntoView},
+    views::{container, Decorators},
+};
+
+use self::pdf::{Layer, PdfWriter};
+
+/// The size and margins of the pages, and the resolution they are painted at.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PageSetup {
+    /// The size of a page in points
+    pub size: Size,
+    /// The margins around the content in points, which hold the header and footer
+    pub margins: Insets,
+    /// The number of pixels per inch of the painted pages
+    pub dpi: f64,
+}
+
+impl Default for PageSetup {
+    fn default() -> Self {
+        Self::new(Self::A4)
+    }
+}
+
+impl PageSetup {
+    /// The size of an A4 page, 210 x 297 mm.
+    pub const A4: Size = Size::new(595.0, 842.0);
+    /// The size of a US letter page, 8.5 x 11 inches.
+    pub const LETTER: Size = Size::new(612.0, 792.0);
+
+    /// Creates a setup for portrait pages of `size`, with margins of half an inch, painted
+    /// at 144 dpi.
+    pub fn new(size: Size) -> Self {
+        Self {
+            size,
+            margins: Insets::uniform(36.0),
+            dpi: 144.0,
+        }
+    }
+
+    pub fn margins(mut self, margins: impl Into<Insets>) -> Self {
+        self.margins = margins.into();
+        self
+    }
+
+    /// Turns the pages so that they are wider than they are tall.
+    pub fn landscape(mut self) -> Self {
+        self.size = Size::new(
+            self.size.width.max(self.size.height),
+            self.size.width.min(self.size.height),
+        );
+        self
+    }
+
+    pub fn dpi(mut self, dpi: f64) -> Self {
+        self.dpi = dpi;
+        self
+    }
+
+    /// The area of a page inside of the margins.
+    ///
+    /// It's at least one point wide and tall, even if the margins are larger than the page.
+    pub fn content_rect(&self) -> Rect {
+        let rect = self.size.to_rect() - self.margins;
+        let x0 = rect.x0.clamp(0.0, (self.size.width - 1.0).max(0.0));
+        let y0 = rect.y0.clamp(0.0, (self.size.height - 1.0).max(0.0));
+        Rect::new(x0, y0, rect.x1.max(x0 + 1.0), rect.y1.max(y0 + 1.0))
+    }
+
+    fn scale(&self) -> f64 {
+        self.dpi / 72.0
+    }
+}
+
+/// The page a header or footer is painted on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PageInfo {
+    /// The number of the page, starting at 1
+    pub number: usize,
+    /// The number of pages of the document
+    pub count: usize,
+}
+
+type PageView = Rc<dyn Fn(PageInfo) -> AnyView>;
+
+/// A view to print or export to PDF, see the [module documentation](self).
+pub struct PrintDocument {
+    view_fn: Box<dyn FnOnce() -> AnyView>,
+    setup: PageSetup,
+    header: Option<PageView>,
+    footer: Option<PageView>,
+    title: String,
+}
+
+impl PrintDocument {
+    pub fn new<V: IntoView + 'static>(view_fn: impl FnOnce() -> V + 'static) -> Self {
+        Self {
+            view_fn: Box::new(move || view_fn().into_any()),
+            setup: PageSetup::default(),
+            header: None,
+            footer: None,
+            title: "Untitled".to_string(),
+        }
+    }
+
+    /// Creates a document with the text of the editor, in its font and syntax colors.
+    ///
+    /// Long lines are wrapped at the width of the page.
+    #[cfg(feature = "editor")]
+    pub fn editor(editor: &crate::views::editor::Editor) -> Self {
+        use crate::{
+            text::Wrap,
+            views::{rich_text, v_stack_from_iter},
+        };
+
+        let layouts: Vec<_> = (0..=editor.last_line())
+            .map(|line| {
+                let mut layout = editor.text_layout(line).text.clone();
+                layout.set_wrap(Wrap::Word);
+                layout
+            })
+            .collect();
+        Self::new(move || {
+            v_stack_from_iter(
+                layouts
+                    .into_iter()
+                    .map(|layout| rich_text(move || layout.clone())),
+            )
+        })
+    }
+
+    pub fn page_setup(mut self, setup: PageSetup) -> Self {
+        self.setup = setup;
+        self
+    }
+
+    /// Sets the name of the print job, shown in the print dialog and the printer queue.
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    /// Sets the view painted in the top margin of every page.
+    pub fn header<V: IntoView + 'static>(
+        mut self,
+        header: impl Fn(PageInfo) -> V + 'static,
+    ) -> Self {
+        self.header = Some(Rc::new(move |page| header(page).into_any()));
+        self
+    }
+
+    /// Sets the view painted in the bottom margin of every page.
+    pub fn footer<V: IntoView + 'static>(
+        mut self,
+        footer: impl Fn(PageInfo) -> V + 'static,
+    ) -> Self {
+        self.footer = Some(Rc::new(move |page| footer(page).into_any()));
+        self
+    }
+
+    /// Paints the pages into images of the page size at the resolution of the page setup.
+    pub fn render_pages(self) -> Vec<RgbaImage> {
+        let scale = self.setup.scale();
+        let mut pages = Pages::new(self);
+        (0..pages.count()).map(|i| pages.image(i, scale)).collect()
+    }
+
+    /// Writes the pages into a PDF document.
+    ///
+    /// Text is written with the outlines of its glyphs, so that it can be selected and
+    /// searched, and SVGs are painted at the resolution of the page setup.
+    pub fn to_pdf(self) -> Vec<u8> {
+        let mut pages = Pages::new(self);
+        pages.pdf(0..pages.count())
+    }
+
+    /// Writes the pages to a PDF file.
+    pub fn write_pdf(self, path: impl AsRef<Path>) -> io::Result<()> {
+        std::fs::write(path, self.to_pdf())
+    }
+
+    /// Shows the print dialog of the platform and prints the pages, or passes them to the
+    /// print handler as a PDF file if one is set, see [`set_print_handler`].
+    ///
+    /// Returns `Ok` without printing if the dialog is cancelled.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Linux / BSD:** Uses the print portal of the desktop, which needs the
+    ///   `print-dialog` feature. Without it the pages are opened in the PDF viewer of the
+    ///   system.
+    /// - **Windows:** Pages are painted at the resolution of the printer, up to 300 dpi.
+    /// - **Web / Android / iOS:** Unsupported.
+    pub fn print(self) -> io::Result<()> {
+        match PRINT_HANDLER.with_borrow(|handler| handler.clone()) {
+            Some(handler) => {
+                let path = temp_pdf_path();
+                self.write_pdf(&path)?;
+                handler(&path)
+            }
+            None => dialog::print(self),
+        }
+    }
+}
+
+thread_local! {
+    static PRINT_HANDLER: RefCell<Option<Rc<dyn Fn(&Path) -> io::Result<()>>>> =
+        const { RefCell::new(None) };
+}
+
+/// Sets the function used by [`PrintDocument::print`] to print a PDF file instead of the
+/// print dialog of the platform, e.g. to preview the pages first.
+pub fn set_print_handler(handler: impl Fn(&Path) -> io::Result<()> + 'static) {
+    PRINT_HANDLER.set(Some(Rc::new(handler)));
+}
+
+/// A new path in the temporary directory for a PDF file to print.
+fn temp_pdf_path() -> PathBuf {
+    static PRINTS: AtomicUsize = AtomicUsize::new(0);
+
+    std::env::temp_dir().join(format!(
+        "floem-print-{}-{}.pdf",
+        std::process::id(),
+        PRINTS.fetch_add(1, Ordering::Relaxed)
+    ))
+}
+
+/// The view of a document laid out and split into pages.
+struct Pages {
+    setup: PageSetup,
+    body: OffscreenView,
+    /// The top and bottom of each page in the view
+    breaks: Vec<(f64, f64)>,
+    header: Option<PageView>,
+    footer: Option<PageView>,
+    /// The draw commands of the view, recorded for the first PDF page
+    list: Option<DisplayList>,
+    title: String,
+}
+
+impl Pages {
+    fn new(document: PrintDocument) -> Self {
+        let PrintDocument {
+            view_fn,
+            setup,
+            header,
+            footer,
+            title,
+        } = document;
+        let content = setup.content_rect();
+        // The view isn't stretched to the height of the page, so it has its natural height
+        let body = OffscreenView::new(
+            move || {
+                container(view_fn())
+                    .style(|s| s.width_full().align_self(Some(AlignItems::FlexStart)))
+            },
+            content.size(),
+            1.0,
+        );
+        let breaks = page_breaks(body.root(), content.height());
+        Self {
+            setup,
+            body,
+            breaks,
+            header,
+            footer,
+            list: None,
+            title,
+        }
+    }
+
+    fn count(&self) -> usize {
+        self.breaks.len()
+    }
+
+    fn info(&self, i: usize) -> PageInfo {
+        PageInfo {
+            number: i + 1,
+            count: self.count(),
+        }
+    }
+
+    /// The views in the margins of a page, with their area on the page.
+    fn margins(&self, i: usize) -> impl Iterator<Item = (impl FnOnce() -> AnyView, Rect)> {
+        let page = self.info(i);
+        let content = self.setup.content_rect();
+        [
+            (self.header.clone(), 0.0, content.y0),
+            (self.footer.clone(), content.y1, self.setup.size.height),
+        ]
+        .into_iter()
+        .filter(|(_, y0, y1)| y1 - y0 >= 1.0)
+        .filter_map(move |(view, y0, y1)| {
+            let view = view?;
+            Some((
+                move || view(page),
+                Rect::new(content.x0, y0, content.x1, y1),
+            ))
+        })
+    }
+
+    /// Paints a page into an image of the page size times `scale`.
+    fn image(&mut self, i: usize, scale: f64) -> RgbaImage {
+        let content = self.setup.content_rect();
+        let pixels = |value: f64| (value * scale).round() as i64;
+        let (top, bottom) = self.breaks[i];
+        let mut image = RgbaImage::from_pixel(
+            pixels(self.setup.size.width) as u32,
+            pixels(self.setup.size.height) as u32,
+            image::Rgba([255, 255, 255, 255]),
+        );
+        let root = self.body.root();
+        let body =
+            self.body
+                .capture_image_rect(root, Rect::new(0.0, top, content.width(), bottom), scale);
+        image::imageops::overlay(&mut image, &body, pixels(content.x0), pixels(content.y0));
+
+        for (view, rect) in self.margins(i) {
+            let margin = OffscreenView::new(view, rect.size(), scale).render();
+            image::imageops::overlay(&mut image, &margin, pixels(rect.x0), pixels(rect.y0));
+        }
+        image
+    }
+
+    /// Writes the pages in `range` into a PDF document.
+    fn pdf(&mut self, range: Range<usize>) -> Vec<u8> {
+        let content = self.setup.content_rect();
+        if self.list.is_none() {
+            let root = self.body.root();
+            self.list = Some(self.body.record_display_list(root));
+        }
+        let mut writer = PdfWriter::new(self.setup.scale());
+        for i in range {
+            let margins: Vec<_> = self
+                .margins(i)
+                .map(|(view, rect)| {
+                    let mut view = OffscreenView::new(view, rect.size(), 1.0);
+                    let root = view.root();
+                    (view.record_display_list(root), rect)
+                })
+                .collect();
+            let (top, bottom) = self.breaks[i];
+            let mut layers = vec![Layer {
+                list: self.list.as_ref().unwrap(),
+                area: Rect::new(0.0, top, content.width(), bottom),
+                origin: content.origin(),
+            }];
+            layers.extend(margins.iter().map(|(list, rect)| Layer {
+                list,
+                area: rect.size().to_rect(),
+                origin: rect.origin(),
+            }));
+            writer.page(self.setup.size, &layers);
+        }
+        writer.finish()
+    }
+}
+
+/// Splits the view into pages of `page_height`, breaking before the views without children
+/// which would be cut by the end of a page, unless they start at the top of the page.
+fn page_breaks(root: ViewId, page_height: f64) -> Vec<(f64, f64)> {
+    fn leaves(id: ViewId, origin: f64, ranges: &mut Vec<(f64, f64)>) {
+        let rect = id.layout_rect();
+        let children = id.children();
+        if children.is_empty() {
+            ranges.push((rect.y0 - origin, rect.y1 - origin));
+        }
+        for child in children {
+            leaves(child, origin, ranges);
+        }
+    }
+
+    let rect = root.layout_rect();
+    let mut ranges = Vec::new();
+    leaves(root, rect.y0, &mut ranges);
+
+    let mut pages = Vec::new();
+    let mut top = 0.0;
+    loop {
+        let end = top + page_height;
+        if end >= rect.height() {
+            pages.push((top, rect.height().max(top)));
+            return pages;
+        }
+        let bottom = ranges
+            .iter()
+            .filter(|(y0, y1)| *y0 > top && *y0 < end && *y1 > end)
+            .map(|(y0, _)| *y0)
+            .fold(end, f64::min);
+        pages.push((top, bottom));
+        top = bottom;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::views::{empty, label, v_stack_from_iter};
+
+    #[test]
+    fn page_breaks_keep_views_whole() {
+        let document = PrintDocument::new(|| {
+            v_stack_from_iter((0..5).map(|_| empty().style(|s| s.height(40.0).width_full())))
+        })
+        .page_setup(
+            PageSetup::new(Size::new(100.0, 120.0))
+                .margins(10.0)
+                .dpi(72.0),
+        );
+        let pages = document.render_pages();
+        // 100 points of content fit two rows per page
+        assert_eq!(pages.len(), 3);
+        assert_eq!(pages[0].dimensions(), (100, 120));
+    }
+
+    #[test]
+    fn margins_larger_than_the_page() {
+        let document = PrintDocument::new(|| empty().style(|s| s.height(10.0).width_full()))
+            .page_setup(PageSetup::new(PageSetup::LETTER).margins(400.0).dpi(72.0));
+        let content = document.setup.content_rect();
+        assert!(content.width() >= 1.0 && content.height() >= 1.0);
+        assert!(PageSetup::LETTER.to_rect().contains_rect(content));
+        // One point of content per page
+        assert_eq!(document.render_pages().len(), 10);
+    }
+
+    #[test]
+    fn pdf_text_is_selectable() {
+        let document = PrintDocument::new(|| label(|| "Hello"))
+            .page_setup(PageSetup::new(Size::new(100.0, 100.0)).dpi(72.0));
+        let pdf = String::from_utf8_lossy(&document.to_pdf()).into_owned();
+        assert!(pdf.contains("/Subtype /Type3"));
+        assert!(pdf.contains("/ToUnicode"));
+        assert!(!pdf.contains("/Subtype /Image"));
+    }
+
+    #[test]
+    fn pdf_structure() {
+        let document =
+            PrintDocument::new(empty).page_setup(PageSetup::new(Size::new(100.0, 100.0)).dpi(72.0));
+        let pdf = String::from_utf8_lossy(&document.to_pdf()).into_owned();
+        assert!(pdf.starts_with("%PDF-1.4"));
+        assert!(pdf.contains("/Count 1"));
+        assert!(pdf.trim_end().ends_with("%%EOF"));
+    }
+}
@@ -0,0 +1,271 @@
+//! The print dialogs of the platforms, see [`PrintDocument::print`].
+
+use std::io;
+
+use super::{Pages, PrintDocument};
+
+#[cfg(target_os = "windows")]
+pub(super) fn print(document: PrintDocument) -> io::Result<()> {
+    use std::ptr::null;
+
+    use windows_sys::Win32::{
+        Foundation::GlobalFree,
+        Graphics::Gdi::{
+            DeleteDC, GetDeviceCaps, StretchDIBits, BITMAPINFO, BITMAPINFOHEADER, BI_RGB,
+            DIB_RGB_COLORS, LOGPIXELSX, PHYSICALOFFSETX, PHYSICALOFFSETY, SRCCOPY,
+        },
+        Storage::Xps::{EndDoc, EndPage, StartDocW, StartPage, DOCINFOW},
+        UI::Controls::Dialogs::{
+            CommDlgExtendedError, PrintDlgW, PD_NOPAGENUMS, PD_NOSELECTION, PD_RETURNDC, PRINTDLGW,
+        },
+    };
+
+    /// The highest resolution pages are painted at, as they are sent to the printer as images.
+    const MAX_DPI: f64 = 300.0;
+
+    let mut dialog: PRINTDLGW = unsafe { std::mem::zeroed() };
+    dialog.lStructSize = std::mem::size_of::<PRINTDLGW>() as u32;
+    dialog.Flags = PD_RETURNDC | PD_NOSELECTION | PD_NOPAGENUMS;
+    if unsafe { PrintDlgW(&mut dialog) } == 0 {
+        return match unsafe { CommDlgExtendedError() } {
+            0 => Ok(()),
+            code => Err(io::Error::other(format!(
+                "the print dialog failed with {code:#x}"
+            ))),
+        };
+    }
+    let dc = dialog.hDC;
+    unsafe {
+        GlobalFree(dialog.hDevMode);
+        GlobalFree(dialog.hDevNames);
+    }
+
+    let result = (|| {
+        let caps = |index: u32| unsafe { GetDeviceCaps(dc, index as i32) } as f64;
+        let dpi = caps(LOGPIXELSX);
+        let scale = dpi.min(MAX_DPI) / 72.0;
+        // The page is printed at its size, from the corner of the paper rather than the
+        // corner of the printable area
+        let (offset_x, offset_y) = (caps(PHYSICALOFFSETX), caps(PHYSICALOFFSETY));
+
+        let mut pages = Pages::new(document);
+        let title: Vec<u16> = pages.title.encode_utf16().chain([0]).collect();
+        let info = DOCINFOW {
+            cbSize: std::mem::size_of::<DOCINFOW>() as i32,
+            lpszDocName: title.as_ptr(),
+            lpszOutput: null(),
+            lpszDatatype: null(),
+            fwType: 0,
+        };
+        if unsafe { StartDocW(dc, &info) } <= 0 {
+            return Err(io::Error::last_os_error());
+        }
+        for i in 0..pages.count() {
+            let image = pages.image(i, scale);
+            let bgra: Vec<u8> = image
+                .pixels()
+                .flat_map(|pixel| [pixel[2], pixel[1], pixel[0], pixel[3]])
+                .collect();
+            let mut bitmap: BITMAPINFO = unsafe { std::mem::zeroed() };
+            bitmap.bmiHeader = BITMAPINFOHEADER {
+                biSize: std::mem::size_of::<BITMAPINFOHEADER>() as u32,
+                biWidth: image.width() as i32,
+                // The rows are from the top
+                biHeight: -(image.height() as i32),
+                biPlanes: 1,
+                biBitCount: 32,
+                biCompression: BI_RGB,
+                ..bitmap.bmiHeader
+            };
+            let size = pages.setup.size;
+            let ok = unsafe {
+                StartPage(dc) > 0
+                    && StretchDIBits(
+                        dc,
+                        -offset_x as i32,
+                        -offset_y as i32,
+                        (size.width / 72.0 * dpi).round() as i32,
+                        (size.height / 72.0 * dpi).round() as i32,
+                        0,
+                        0,
+                        image.width() as i32,
+                        image.height() as i32,
+                        bgra.as_ptr().cast(),
+                        &bitmap,
+                        DIB_RGB_COLORS,
+                        SRCCOPY,
+                    ) != 0
+                    && EndPage(dc) > 0
+            };
+            if !ok {
+                return Err(io::Error::last_os_error());
+            }
+        }
+        if unsafe { EndDoc(dc) } <= 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(())
+    })();
+    unsafe { DeleteDC(dc) };
+    result
+}
+
+#[cfg(target_os = "macos")]
+pub(super) fn print(document: PrintDocument) -> io::Result<()> {
+    use objc2::{AllocAnyThread, MainThreadMarker, MainThreadOnly};
+    use objc2_app_kit::{
+        NSImage, NSImageScaling, NSImageView, NSPrintInfo, NSPrintOperation,
+        NSPrintingPaginationMode, NSView,
+    };
+    use objc2_foundation::{NSData, NSPoint, NSRect, NSSize, NSString};
+
+    let mtm = MainThreadMarker::new()
+        .ok_or_else(|| io::Error::other("printing has to happen on the main thread"))?;
+    let mut pages = Pages::new(document);
+    let size = pages.setup.size;
+    let count = pages.count();
+    unsafe {
+        // The pages are stacked from the top of the view, one page of paper each
+        let view = NSView::initWithFrame(
+            NSView::alloc(mtm),
+            NSRect::new(
+                NSPoint::new(0.0, 0.0),
+                NSSize::new(size.width, size.height * count as f64),
+            ),
+        );
+        for i in 0..count {
+            let data = NSData::with_bytes(&pages.pdf(i..i + 1));
+            let image = NSImage::initWithData(NSImage::alloc(), &data)
+                .ok_or_else(|| io::Error::other("the page isn't a valid PDF document"))?;
+            let page = NSImageView::imageViewWithImage(&image, mtm);
+            page.setImageScaling(NSImageScaling::ScaleAxesIndependently);
+            page.setFrame(NSRect::new(
+                NSPoint::new(0.0, size.height * (count - 1 - i) as f64),
+                NSSize::new(size.width, size.height),
+            ));
+            view.addSubview(&page);
+        }
+
+        let info = NSPrintInfo::new();
+        info.setPaperSize(NSSize::new(size.width, size.height));
+        info.setTopMargin(0.0);
+        info.setBottomMargin(0.0);
+        info.setLeftMargin(0.0);
+        info.setRightMargin(0.0);
+        info.setHorizontallyCentered(false);
+        info.setVerticallyCentered(false);
+        info.setHorizontalPagination(NSPrintingPaginationMode::Fit);
+        info.setVerticalPagination(NSPrintingPaginationMode::Automatic);
+
+        let operation = NSPrintOperation::printOperationWithView_printInfo(&view, &info);
+        operation.setJobTitle(Some(&NSString::from_str(&pages.title)));
+        // Returns false when the dialog is cancelled
+        operation.runOperation();
+    }
+    Ok(())
+}
+
+#[cfg(all(
+    feature = "print-dialog",
+    any(
+        target_os = "linux",
+        target_os = "freebsd",
+        target_os = "dragonfly",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    )
+))]
+pub(super) fn print(document: PrintDocument) -> io::Result<()> {
+    use std::os::fd::AsFd;
+
+    use ashpd::{
+        desktop::{
+            print::{PageSetup, PrintProxy, Settings},
+            ResponseError,
+        },
+        Error, WindowIdentifier,
+    };
+
+    fn millimeters(points: f64) -> f64 {
+        points / 72.0 * 25.4
+    }
+
+    let mut pages = Pages::new(document);
+    let path = super::temp_pdf_path();
+    std::fs::write(&path, pages.pdf(0..pages.count()))?;
+    let file = std::fs::File::open(&path)?;
+    let setup = pages.setup;
+    let title = pages.title.clone();
+
+    let result = pollster::block_on(async {
+        let proxy = PrintProxy::new().await?;
+        let identifier = WindowIdentifier::default();
+        let page_setup = PageSetup::default()
+            .width(millimeters(setup.size.width))
+            .height(millimeters(setup.size.height))
+            .margin_top(0.0)
+            .margin_bottom(0.0)
+            .margin_left(0.0)
+            .margin_right(0.0);
+        let prepared = proxy
+            .prepare_print(
+                &identifier,
+                &title,
+                Settings::default(),
+                page_setup,
+                None,
+                true,
+            )
+            .await?
+            .response()?;
+        proxy
+            .print(
+                &identifier,
+                &title,
+                &file.as_fd(),
+                Some(prepared.token),
+                true,
+            )
+            .await?
+            .response()
+    });
+    let _ = std::fs::remove_file(&path);
+    match result {
+        Ok(()) | Err(Error::Response(ResponseError::Cancelled)) => Ok(()),
+        Err(err) => Err(io::Error::other(err)),
+    }
+}
+
+/// Opens the pages in the PDF viewer of the system, which can print them.
+#[cfg(all(
+    not(feature = "print-dialog"),
+    any(
+        target_os = "linux",
+        target_os = "freebsd",
+        target_os = "dragonfly",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    )
+))]
+pub(super) fn print(document: PrintDocument) -> io::Result<()> {
+    let mut pages = Pages::new(document);
+    let path = super::temp_pdf_path();
+    std::fs::write(&path, pages.pdf(0..pages.count()))?;
+    std::process::Command::new("xdg-open")
+        .arg(path)
+        .spawn()
+        .map(|_| ())
+}
+
+#[cfg(not(any(
+    target_os = "windows",
+    target_os = "macos",
+    target_os = "linux",
+    target_os = "freebsd",
+    target_os = "dragonfly",
+    target_os = "netbsd",
+    target_os = "openbsd"
+)))]
+pub(super) fn print(_document: PrintDocument) -> io::Result<()> {
+    Err(io::ErrorKind::Unsupported.into())
+}
@@ -0,0 +1,903 @@
+//! Writing display lists into PDF documents.
+//!
+//! Paths, strokes and gradients are written as vector graphics, and text as Type 3 fonts
+//! made of the outlines of the glyphs, with a mapping back to the text so that it can be
+//! selected, searched and copied. Images and SVGs are embedded as images, the SVGs being
+//! painted at the resolution of the page setup. Blurred fills, which are used for shadows,
+//! are left out.
+
+use std::{collections::HashMap, fmt, fmt::Write};
+
+use floem_renderer::{
+    display_list::{DisplayList, DrawCommand, TextRun},
+    text::{fontdb, CacheKeyFlags, FONT_SYSTEM},
+    usvg,
+};
+use peniko::{
+    color::palette,
+    kurbo::{Affine, BezPath, Cap, Join, PathEl, Point, Rect, Size, Stroke},
+    Brush, Color, Gradient, GradientKind,
+};
+use swash::scale::ScaleContext;
+
+/// The number of units per em of the glyphs of the fonts.
+const UNITS_PER_EM: f32 = 1000.0;
+
+/// The skew of the fake italic of fonts without an italic style, like the renderers.
+const FAKE_ITALIC_SKEW: f64 = 0.2493;
+
+/// A part of a display list placed on a page.
+pub(super) struct Layer<'a> {
+    pub list: &'a DisplayList,
+    /// The part of the display list shown, in its coordinates
+    pub area: Rect,
+    /// Where the top left corner of `area` is on the page, in points from the top left corner
+    pub origin: Point,
+}
+
+/// A number formatted for a content stream.
+struct Num(f64);
+
+impl fmt::Display for Num {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = if self.0.is_finite() { self.0 } else { 0.0 };
+        let rounded = (value * 1000.0).round() / 1000.0;
+        if rounded == rounded.trunc() {
+            write!(f, "{}", rounded as i64)
+        } else {
+            let text = format!("{rounded:.3}");
+            f.write_str(text.trim_end_matches('0'))
+        }
+    }
+}
+
+/// A Type 3 font, holding up to 256 glyphs of any fonts.
+#[derive(Default)]
+struct Type3Font {
+    /// The outline of each glyph, as a glyph procedure
+    procs: Vec<String>,
+    widths: Vec<f32>,
+    /// The text of each glyph, for the `ToUnicode` map
+    text: Vec<String>,
+}
+
+/// Writes pages into a PDF document, see the [module documentation](self).
+pub(super) struct PdfWriter {
+    /// The objects, numbered from 1, which are written when the document is finished
+    objects: Vec<Vec<u8>>,
+    pages: Vec<usize>,
+    fonts: Vec<Type3Font>,
+    /// The font and code of each glyph of a font
+    glyphs: HashMap<(fontdb::ID, u16), (usize, u8)>,
+    scale_context: ScaleContext,
+    /// The image objects, by the hash of their content
+    images: HashMap<Vec<u8>, usize>,
+    image_objects: Vec<usize>,
+    pattern_objects: Vec<usize>,
+    /// The opacities used, which have a graphics state each
+    alphas: Vec<u8>,
+    /// The scale at which SVGs are painted into images
+    scale: f64,
+}
+
+const CATALOG: usize = 1;
+const PAGES: usize = 2;
+const RESOURCES: usize = 3;
+
+impl PdfWriter {
+    /// Creates a writer which paints SVGs at `scale` pixels per point.
+    pub(super) fn new(scale: f64) -> Self {
+        Self {
+            // The catalog, page tree and resources are written when the document is finished
+            objects: vec![Vec::new(); 3],
+            pages: Vec::new(),
+            fonts: Vec::new(),
+            glyphs: HashMap::new(),
+            scale_context: ScaleContext::new(),
+            images: HashMap::new(),
+            image_objects: Vec::new(),
+            pattern_objects: Vec::new(),
+            alphas: Vec::new(),
+            scale,
+        }
+    }
+
+    fn add(&mut self, object: Vec<u8>) -> usize {
+        self.objects.push(object);
+        self.objects.len()
+    }
+
+    fn add_dict(&mut self, dict: String) -> usize {
+        self.add(dict.into_bytes())
+    }
+
+    /// Adds a stream compressed with deflate.
+    fn add_stream(&mut self, dict: &str, data: &[u8]) -> usize {
+        let data = miniz_oxide::deflate::compress_to_vec_zlib(data, 6);
+        let mut object = format!(
+            "<< {dict} /Filter /FlateDecode /Length {} >>\nstream\n",
+            data.len()
+        )
+        .into_bytes();
+        object.extend_from_slice(&data);
+        object.extend_from_slice(b"\nendstream");
+        self.add(object)
+    }
+
+    /// Adds a page of `size` in points, with the layers painted in order.
+    pub(super) fn page(&mut self, size: Size, layers: &[Layer<'_>]) {
+        let mut content = Content {
+            ops: String::new(),
+            base: Affine::new([1.0, 0.0, 0.0, -1.0, 0.0, size.height]),
+        };
+        // PDF has the origin at the bottom left, floem at the top left
+        writeln!(content.ops, "1 0 0 -1 0 {} cm", Num(size.height)).unwrap();
+        for layer in layers {
+            self.layer(&mut content, layer);
+        }
+        let contents = self.add_stream("", content.ops.as_bytes());
+        let page = self.add_dict(format!(
+            "<< /Type /Page /Parent {PAGES} 0 R /MediaBox [0 0 {} {}] \
+             /Resources {RESOURCES} 0 R /Contents {contents} 0 R >>",
+            Num(size.width),
+            Num(size.height),
+        ));
+        self.pages.push(page);
+    }
+
+    fn layer(&mut self, content: &mut Content, layer: &Layer<'_>) {
+        let offset = layer.origin - layer.area.origin();
+        let area = layer.area;
+        let saved_base = content.base;
+        content.base = saved_base * Affine::translate(offset);
+        writeln!(
+            content.ops,
+            "q 1 0 0 1 {} {} cm {} {} {} {} re W n",
+            Num(offset.x),
+            Num(offset.y),
+            Num(area.x0),
+            Num(area.y0),
+            Num(area.width()),
+            Num(area.height()),
+        )
+        .unwrap();
+
+        let mut transform = Affine::IDENTITY;
+        let mut clip: Option<BezPath> = None;
+        // The state of the commands changes with the transform and the clip, and the
+        // commands between changes share a saved graphics state
+        let mut state = 0;
+        let mut open_state = None;
+        for command in &layer.list.commands {
+            match command {
+                DrawCommand::Transform(new) => {
+                    transform = *new;
+                    state += 1;
+                    continue;
+                }
+                DrawCommand::Clip(path) => {
+                    clip = Some(transform * path.clone());
+                    state += 1;
+                    continue;
+                }
+                DrawCommand::ClearClip => {
+                    clip = None;
+                    state += 1;
+                    continue;
+                }
+                DrawCommand::SetZIndex(_) => continue,
+                _ => {}
+            }
+            let Some(bounds) = command_bounds(command) else {
+                continue;
+            };
+            let bounds = transform.transform_rect_bbox(bounds);
+            if bounds.x1 < area.x0
+                || bounds.x0 > area.x1
+                || bounds.y1 < area.y0
+                || bounds.y0 > area.y1
+            {
+                continue;
+            }
+            if open_state != Some(state) {
+                if open_state.is_some() {
+                    content.ops.push_str("Q\n");
+                }
+                content.ops.push_str("q\n");
+                if let Some(clip) = &clip {
+                    path(&mut content.ops, clip);
+                    content.ops.push_str("W n\n");
+                }
+                write_matrix(&mut content.ops, transform);
+                content.ops.push_str(" cm\n");
+                open_state = Some(state);
+            }
+            let ctm = content.base * transform;
+            self.command(&mut content.ops, command, ctm);
+        }
+        if open_state.is_some() {
+            content.ops.push_str("Q\n");
+        }
+        content.ops.push_str("Q\n");
+        content.base = saved_base;
+    }
+
+    /// Writes a drawing command, with `ctm` the transform from its coordinates to the page.
+    fn command(&mut self, ops: &mut String, command: &DrawCommand, ctm: Affine) {
+        match command {
+            DrawCommand::Fill {
+                path: shape,
+                brush,
+                blur_radius,
+            } => {
+                if *blur_radius > 0.0 || !self.set_brush(ops, brush, ctm, false) {
+                    return;
+                }
+                path(ops, shape);
+                ops.push_str("f\n");
+            }
+            DrawCommand::Stroke {
+                path: shape,
+                brush,
+                stroke,
+            } => {
+                if !self.set_brush(ops, brush, ctm, true) {
+                    return;
+                }
+                set_stroke(ops, stroke);
+                path(ops, shape);
+                ops.push_str("S\n");
+            }
+            DrawCommand::Text { runs, pos } => {
+                for run in runs {
+                    self.text_run(ops, run, *pos);
+                }
+            }
+            DrawCommand::Image { image, hash, rect } => {
+                if image.width == 0 || image.height == 0 {
+                    return;
+                }
+                let index = self.image(hash.clone(), || {
+                    (image.width, image.height, image.data.data().to_vec())
+                });
+                draw_image(ops, index, *rect);
+            }
+            DrawCommand::Svg {
+                tree,
+                hash,
+                rect,
+                brush,
+            } => {
+                let width = (rect.width() * self.scale).round() as u32;
+                let height = (rect.height() * self.scale).round() as u32;
+                let color = match brush {
+                    Some(Brush::Solid(color)) => Some(color.to_rgba8()),
+                    _ => None,
+                };
+                let mut key = hash.clone();
+                key.extend_from_slice(&width.to_le_bytes());
+                key.extend_from_slice(&height.to_le_bytes());
+                if let Some(color) = color {
+                    key.extend_from_slice(&[color.r, color.g, color.b, color.a]);
+                }
+                let Some(pixels) = svg_pixels(tree, width, height, color) else {
+                    return;
+                };
+                let index = self.image(key, || (width, height, pixels));
+                draw_image(ops, index, *rect);
+            }
+            DrawCommand::Transform(_)
+            | DrawCommand::SetZIndex(_)
+            | DrawCommand::Clip(_)
+            | DrawCommand::ClearClip => {}
+        }
+    }
+
+    /// Sets the fill or stroke color to the brush, and returns whether anything is painted.
+    fn set_brush(&mut self, ops: &mut String, brush: &Brush, ctm: Affine, stroke: bool) -> bool {
+        match brush {
+            Brush::Solid(color) => {
+                let rgba = color.to_rgba8();
+                if rgba.a == 0 {
+                    return false;
+                }
+                self.set_color(ops, *color, stroke);
+                true
+            }
+            Brush::Gradient(gradient) => {
+                let Some(pattern) = self.pattern(gradient, ctm) else {
+                    return false;
+                };
+                let (space, color) = if stroke { ("CS", "SCN") } else { ("cs", "scn") };
+                writeln!(ops, "/Pattern {space} /P{pattern} {color}").unwrap();
+                true
+            }
+            Brush::Image(_) => false,
+        }
+    }
+
+    fn set_color(&mut self, ops: &mut String, color: Color, stroke: bool) {
+        let rgba = color.to_rgba8();
+        writeln!(
+            ops,
+            "{} {} {} {}",
+            Num(rgba.r as f64 / 255.0),
+            Num(rgba.g as f64 / 255.0),
+            Num(rgba.b as f64 / 255.0),
+            if stroke { "RG" } else { "rg" },
+        )
+        .unwrap();
+        if !self.alphas.contains(&rgba.a) {
+            self.alphas.push(rgba.a);
+        }
+        writeln!(ops, "/A{} gs", rgba.a).unwrap();
+    }
+
+    /// Adds a shading pattern for a linear or radial gradient, in the coordinates of `ctm`.
+    fn pattern(&mut self, gradient: &Gradient, ctm: Affine) -> Option<usize> {
+        let (shading_type, coords) = match gradient.kind {
+            GradientKind::Linear { start, end } => (
+                2,
+                format!(
+                    "{} {} {} {}",
+                    Num(start.x),
+                    Num(start.y),
+                    Num(end.x),
+                    Num(end.y)
+                ),
+            ),
+            GradientKind::Radial {
+                start_center,
+                start_radius,
+                end_center,
+                end_radius,
+            } => (
+                3,
+                format!(
+                    "{} {} {} {} {} {}",
+                    Num(start_center.x),
+                    Num(start_center.y),
+                    Num(start_radius as f64),
+                    Num(end_center.x),
+                    Num(end_center.y),
+                    Num(end_radius as f64),
+                ),
+            ),
+            GradientKind::Sweep { .. } => return None,
+        };
+        let mut stops: Vec<(f32, [f64; 3])> = gradient
+            .stops
+            .iter()
+            .map(|stop| {
+                let color: Color = stop.color.to_alpha_color();
+                let rgba = color.to_rgba8();
+                (
+                    stop.offset,
+                    [rgba.r, rgba.g, rgba.b].map(|c| c as f64 / 255.0),
+                )
+            })
+            .collect();
+        let first = *stops.first()?;
+        let last = *stops.last()?;
+        if first.0 > 0.0 {
+            stops.insert(0, (0.0, first.1));
+        }
+        if last.0 < 1.0 {
+            stops.push((1.0, last.1));
+        }
+        let rgb = |c: [f64; 3]| format!("{} {} {}", Num(c[0]), Num(c[1]), Num(c[2]));
+        let segment = |from: [f64; 3], to: [f64; 3]| {
+            format!(
+                "<< /FunctionType 2 /Domain [0 1] /C0 [{}] /C1 [{}] /N 1 >>",
+                rgb(from),
+                rgb(to)
+            )
+        };
+        let function = if stops.len() == 1 {
+            segment(first.1, first.1)
+        } else {
+            let segments: Vec<_> = stops
+                .windows(2)
+                .map(|pair| segment(pair[0].1, pair[1].1))
+                .collect();
+            let bounds: Vec<_> = stops[1..stops.len() - 1]
+                .iter()
+                .map(|(offset, _)| Num(*offset as f64).to_string())
+                .collect();
+            let encode = vec!["0 1"; segments.len()];
+            format!(
+                "<< /FunctionType 3 /Domain [0 1] /Functions [{}] /Bounds [{}] /Encode [{}] >>",
+                segments.join(" "),
+                bounds.join(" "),
+                encode.join(" ")
+            )
+        };
+        let mut matrix = String::new();
+        write_matrix(&mut matrix, ctm);
+        let object = self.add_dict(format!(
+            "<< /PatternType 2 /Matrix [{matrix}] /Shading << /ShadingType {shading_type} \
+             /ColorSpace /DeviceRGB /Coords [{coords}] /Function {function} \
+             /Extend [true true] >> >>"
+        ));
+        self.pattern_objects.push(object);
+        Some(self.pattern_objects.len() - 1)
+    }
+
+    /// Writes a line of text, grouping the glyphs which are shown with the same font, size
+    /// and color.
+    fn text_run(&mut self, ops: &mut String, run: &TextRun, pos: Point) {
+        let y = pos.y + run.line_y as f64;
+        let mut group: Option<(usize, f32, Color, bool)> = None;
+        // The position of the pen after the last glyph, in the text space of the group
+        let mut pen = 0.0;
+        for glyph in &run.glyphs {
+            let text = run.text.get(glyph.start..glyph.end).unwrap_or_default();
+            let Some((font, code)) = self.glyph(glyph.font_id, glyph.glyph_id, text) else {
+                continue;
+            };
+            let color = glyph.color_opt.map_or(palette::css::BLACK, |c| {
+                Color::from_rgba8(c.r(), c.g(), c.b(), c.a())
+            });
+            if color.to_rgba8().a == 0 {
+                continue;
+            }
+            let italic = glyph.cache_key_flags.contains(CacheKeyFlags::FAKE_ITALIC);
+            let x = pos.x + glyph.x as f64;
+            let key = (font, glyph.font_size, color, italic);
+            if group != Some(key) {
+                if group.is_some() {
+                    ops.push_str("] TJ ET\n");
+                }
+                ops.push_str("BT\n");
+                self.set_color(ops, color, false);
+                writeln!(
+                    ops,
+                    "/F{font} {} Tf 1 0 {} -1 {} {} Tm [",
+                    Num(glyph.font_size as f64),
+                    Num(if italic { FAKE_ITALIC_SKEW } else { 0.0 }),
+                    Num(x),
+                    Num(y)
+                )
+                .unwrap();
+                group = Some(key);
+                pen = x;
+            }
+            // The glyphs are placed where the layout put them, rather than after the
+            // advance of the previous glyph in the font
+            let adjust = (pen - x) * UNITS_PER_EM as f64 / glyph.font_size as f64;
+            if adjust.abs() >= 0.01 {
+                write!(ops, "{}", Num(adjust)).unwrap();
+            }
+            write!(ops, "<{code:02x}>").unwrap();
+            let width = self.fonts[font].widths[code as usize] as f64;
+            pen = x + width * glyph.font_size as f64 / UNITS_PER_EM as f64;
+        }
+        if group.is_some() {
+            ops.push_str("] TJ ET\n");
+        }
+    }
+
+    /// The Type 3 font and code of a glyph, adding it to a font the first time.
+    fn glyph(&mut self, font_id: fontdb::ID, glyph_id: u16, text: &str) -> Option<(usize, u8)> {
+        if let Some(glyph) = self.glyphs.get(&(font_id, glyph_id)) {
+            return Some(*glyph);
+        }
+        let font = FONT_SYSTEM.lock().get_font(font_id)?;
+        let font_ref = font.as_swash();
+        let width = font_ref
+            .glyph_metrics(&[])
+            .scale(UNITS_PER_EM)
+            .advance_width(glyph_id);
+        let mut scaler = self
+            .scale_context
+            .builder(font_ref)
+            .size(UNITS_PER_EM)
+            .build();
+        let outline = scaler
+            .scale_outline(glyph_id)
+            .or_else(|| scaler.scale_color_outline(glyph_id));
+
+        let mut proc = String::new();
+        match outline {
+            Some(outline) if !outline.is_empty() => {
+                let bounds = outline.bounds();
+                writeln!(
+                    proc,
+                    "{} 0 {} {} {} {} d1",
+                    Num(width as f64),
+                    Num(bounds.min.x as f64),
+                    Num(bounds.min.y as f64),
+                    Num(bounds.max.x as f64),
+                    Num(bounds.max.y as f64),
+                )
+                .unwrap();
+                let points = outline.points();
+                let mut i = 0;
+                let mut current = Point::ZERO;
+                let point = |i: usize| Point::new(points[i].x as f64, points[i].y as f64);
+                for verb in outline.verbs() {
+                    use swash::zeno::Verb;
+                    match verb {
+                        Verb::MoveTo => {
+                            current = point(i);
+                            writeln!(proc, "{} {} m", Num(current.x), Num(current.y)).unwrap();
+                            i += 1;
+                        }
+                        Verb::LineTo => {
+                            current = point(i);
+                            writeln!(proc, "{} {} l", Num(current.x), Num(current.y)).unwrap();
+                            i += 1;
+                        }
+                        Verb::QuadTo => {
+                            let (control, end) = (point(i), point(i + 1));
+                            curve_from_quad(&mut proc, current, control, end);
+                            current = end;
+                            i += 2;
+                        }
+                        Verb::CurveTo => {
+                            let (a, b, end) = (point(i), point(i + 1), point(i + 2));
+                            curve(&mut proc, a, b, end);
+                            current = end;
+                            i += 3;
+                        }
+                        Verb::Close => proc.push_str("h\n"),
+                    }
+                }
+                proc.push_str("f\n");
+            }
+            _ => writeln!(proc, "{} 0 0 0 0 0 d1", Num(width as f64)).unwrap(),
+        }
+
+        if self.fonts.last().is_none_or(|font| font.procs.len() == 256) {
+            self.fonts.push(Type3Font::default());
+        }
+        let index = self.fonts.len() - 1;
+        let type3 = &mut self.fonts[index];
+        let code = type3.procs.len() as u8;
+        type3.procs.push(proc);
+        type3.widths.push(width);
+        type3.text.push(text.to_string());
+        self.glyphs.insert((font_id, glyph_id), (index, code));
+        Some((index, code))
+    }
+
+    /// The index of an image, adding it from its RGBA pixels the first time.
+    fn image(&mut self, key: Vec<u8>, pixels: impl FnOnce() -> (u32, u32, Vec<u8>)) -> usize {
+        if let Some(index) = self.images.get(&key) {
+            return *index;
+        }
+        let (width, height, rgba) = pixels();
+        let rgb: Vec<u8> = rgba
+            .chunks_exact(4)
+            .flat_map(|pixel| [pixel[0], pixel[1], pixel[2]])
+            .collect();
+        let alpha: Vec<u8> = rgba.chunks_exact(4).map(|pixel| pixel[3]).collect();
+        let size = format!("/Width {width} /Height {height} /BitsPerComponent 8");
+        let mask = if alpha.iter().all(|a| *a == 255) {
+            String::new()
+        } else {
+            let mask = self.add_stream(
+                &format!("/Type /XObject /Subtype /Image {size} /ColorSpace /DeviceGray"),
+                &alpha,
+            );
+            format!(" /SMask {mask} 0 R")
+        };
+        let object = self.add_stream(
+            &format!("/Type /XObject /Subtype /Image {size} /ColorSpace /DeviceRGB{mask}"),
+            &rgb,
+        );
+        self.image_objects.push(object);
+        let index = self.image_objects.len() - 1;
+        self.images.insert(key, index);
+        index
+    }
+
+    /// Writes the fonts, resources and page tree, and returns the document.
+    pub(super) fn finish(mut self) -> Vec<u8> {
+        let mut fonts = Vec::new();
+        for (index, font) in std::mem::take(&mut self.fonts).into_iter().enumerate() {
+            let procs: Vec<_> = font
+                .procs
+                .iter()
+                .enumerate()
+                .map(|(code, proc)| {
+                    format!("/g{code} {} 0 R", self.add_stream("", proc.as_bytes()))
+                })
+                .collect();
+            let to_unicode = self.add_stream("", to_unicode(&font.text).as_bytes());
+            let names: Vec<_> = (0..font.procs.len())
+                .map(|code| format!("/g{code}"))
+                .collect();
+            let widths: Vec<_> = font
+                .widths
+                .iter()
+                .map(|width| Num(*width as f64).to_string())
+                .collect();
+            let object = self.add_dict(format!(
+                "<< /Type /Font /Subtype /Type3 /FontBBox [0 0 0 0] \
+                 /FontMatrix [0.001 0 0 0.001 0 0] /CharProcs << {} >> \
+                 /Encoding << /Type /Encoding /Differences [0 {}] >> \
+                 /FirstChar 0 /LastChar {} /Widths [{}] /Resources << >> \
+                 /ToUnicode {to_unicode} 0 R >>",
+                procs.join(" "),
+                names.join(" "),
+                font.procs.len() - 1,
+                widths.join(" "),
+            ));
+            fonts.push(format!("/F{index} {object} 0 R"));
+        }
+
+        let images: Vec<_> = self
+            .image_objects
+            .iter()
+            .enumerate()
+            .map(|(index, object)| format!("/Im{index} {object} 0 R"))
+            .collect();
+        let patterns: Vec<_> = self
+            .pattern_objects
+            .iter()
+            .enumerate()
+            .map(|(index, object)| format!("/P{index} {object} 0 R"))
+            .collect();
+        let alphas: Vec<_> = self
+            .alphas
+            .iter()
+            .map(|alpha| {
+                let value = Num(*alpha as f64 / 255.0);
+                format!("/A{alpha} << /ca {value} /CA {value} >>")
+            })
+            .collect();
+        self.objects[RESOURCES - 1] = format!(
+            "<< /Font << {} >> /XObject << {} >> /Pattern << {} >> /ExtGState << {} >> >>",
+            fonts.join(" "),
+            images.join(" "),
+            patterns.join(" "),
+            alphas.join(" "),
+        )
+        .into_bytes();
+        let kids: Vec<_> = self
+            .pages
+            .iter()
+            .map(|page| format!("{page} 0 R"))
+            .collect();
+        self.objects[PAGES - 1] = format!(
+            "<< /Type /Pages /Kids [{}] /Count {} >>",
+            kids.join(" "),
+            self.pages.len()
+        )
+        .into_bytes();
+        self.objects[CATALOG - 1] = format!("<< /Type /Catalog /Pages {PAGES} 0 R >>").into_bytes();
+
+        let mut out = b"%PDF-1.4\n%\xe2\xe3\xcf\xd3\n".to_vec();
+        let mut offsets = Vec::with_capacity(self.objects.len());
+        for (index, object) in self.objects.iter().enumerate() {
+            offsets.push(out.len());
+            out.extend_from_slice(format!("{} 0 obj\n", index + 1).as_bytes());
+            out.extend_from_slice(object);
+            out.extend_from_slice(b"\nendobj\n");
+        }
+        let xref = out.len();
+        let mut trailer = format!("xref\n0 {}\n0000000000 65535 f \n", offsets.len() + 1);
+        for offset in &offsets {
+            writeln!(trailer, "{offset:010} 00000 n ").unwrap();
+        }
+        write!(
+            trailer,
+            "trailer\n<< /Size {} /Root {CATALOG} 0 R >>\nstartxref\n{xref}\n%%EOF\n",
+            offsets.len() + 1
+        )
+        .unwrap();
+        out.extend_from_slice(trailer.as_bytes());
+        out
+    }
+}
+
+/// The content stream of a page.
+struct Content {
+    ops: String,
+    /// The transform from the coordinates of the current layer to the default coordinates of
+    /// the page, which patterns are relative to
+    base: Affine,
+}
+
+/// The area a drawing command paints, in its coordinates.
+fn command_bounds(command: &DrawCommand) -> Option<Rect> {
+    use peniko::kurbo::Shape;
+
+    match command {
+        DrawCommand::Fill { path, .. } => Some(path.bounding_box()),
+        DrawCommand::Stroke { path, stroke, .. } => {
+            let half = stroke.width / 2.0 * stroke.miter_limit.max(1.0);
+            Some(path.bounding_box().inflate(half, half))
+        }
+        DrawCommand::Text { runs, pos } => runs
+            .iter()
+            .map(|run| {
+                Rect::new(
+                    pos.x,
+                    pos.y + run.line_top as f64,
+                    pos.x + run.line_w as f64,
+                    pos.y + (run.line_top + run.line_height) as f64,
+                )
+            })
+            .reduce(|a, b| a.union(b)),
+        DrawCommand::Svg { rect, .. } | DrawCommand::Image { rect, .. } => Some(*rect),
+        DrawCommand::Transform(_)
+        | DrawCommand::SetZIndex(_)
+        | DrawCommand::Clip(_)
+        | DrawCommand::ClearClip => None,
+    }
+}
+
+fn write_matrix(ops: &mut String, transform: Affine) {
+    let [a, b, c, d, e, f] = transform.as_coeffs();
+    write!(
+        ops,
+        "{} {} {} {} {} {}",
+        Num(a),
+        Num(b),
+        Num(c),
+        Num(d),
+        Num(e),
+        Num(f)
+    )
+    .unwrap();
+}
+
+fn curve(ops: &mut String, a: Point, b: Point, end: Point) {
+    writeln!(
+        ops,
+        "{} {} {} {} {} {} c",
+        Num(a.x),
+        Num(a.y),
+        Num(b.x),
+        Num(b.y),
+        Num(end.x),
+        Num(end.y)
+    )
+    .unwrap();
+}
+
+/// Writes a quadratic curve as the cubic curve PDF supports.
+fn curve_from_quad(ops: &mut String, start: Point, control: Point, end: Point) {
+    curve(
+        ops,
+        start + (control - start) * (2.0 / 3.0),
+        end + (control - end) * (2.0 / 3.0),
+        end,
+    );
+}
+
+fn path(ops: &mut String, path: &BezPath) {
+    let mut start = Point::ZERO;
+    let mut current = Point::ZERO;
+    for element in path.elements() {
+        match *element {
+            PathEl::MoveTo(point) => {
+                writeln!(ops, "{} {} m", Num(point.x), Num(point.y)).unwrap();
+                start = point;
+                current = point;
+            }
+            PathEl::LineTo(point) => {
+                writeln!(ops, "{} {} l", Num(point.x), Num(point.y)).unwrap();
+                current = point;
+            }
+            PathEl::QuadTo(control, end) => {
+                curve_from_quad(ops, current, control, end);
+                current = end;
+            }
+            PathEl::CurveTo(a, b, end) => {
+                curve(ops, a, b, end);
+                current = end;
+            }
+            PathEl::ClosePath => {
+                ops.push_str("h\n");
+                current = start;
+            }
+        }
+    }
+}
+
+fn set_stroke(ops: &mut String, stroke: &Stroke) {
+    let cap = match stroke.start_cap {
+        Cap::Butt => 0,
+        Cap::Round => 1,
+        Cap::Square => 2,
+    };
+    let join = match stroke.join {
+        Join::Miter => 0,
+        Join::Round => 1,
+        Join::Bevel => 2,
+    };
+    let dashes: Vec<_> = stroke
+        .dash_pattern
+        .iter()
+        .map(|dash| Num(*dash).to_string())
+        .collect();
+    writeln!(
+        ops,
+        "{} w {cap} J {join} j {} M [{}] {} d",
+        Num(stroke.width),
+        Num(stroke.miter_limit.max(1.0)),
+        dashes.join(" "),
+        Num(stroke.dash_offset)
+    )
+    .unwrap();
+}
+
+/// Draws an image, whose unit square has the first row at the top.
+fn draw_image(ops: &mut String, index: usize, rect: Rect) {
+    writeln!(
+        ops,
+        "q {} 0 0 {} {} {} cm /Im{index} Do Q",
+        Num(rect.width()),
+        Num(-rect.height()),
+        Num(rect.x0),
+        Num(rect.y1)
+    )
+    .unwrap();
+}
+
+/// Paints an SVG into RGBA pixels, in the color of `color` if set like the renderers do.
+fn svg_pixels(
+    tree: &usvg::Tree,
+    width: u32,
+    height: u32,
+    color: Option<peniko::color::Rgba8>,
+) -> Option<Vec<u8>> {
+    use floem_renderer::tiny_skia::{Pixmap, Transform};
+
+    let mut pixmap = Pixmap::new(width, height)?;
+    let transform = Transform::from_scale(
+        width as f32 / tree.size().width(),
+        height as f32 / tree.size().height(),
+    );
+    resvg::render(tree, transform, &mut pixmap.as_mut());
+    Some(
+        pixmap
+            .pixels()
+            .iter()
+            .flat_map(|pixel| {
+                let pixel = pixel.demultiply();
+                match color {
+                    Some(color) => [
+                        color.r,
+                        color.g,
+                        color.b,
+                        (pixel.alpha() as u16 * color.a as u16 / 255) as u8,
+                    ],
+                    None => [pixel.red(), pixel.green(), pixel.blue(), pixel.alpha()],
+                }
+            })
+            .collect(),
+    )
+}
+
+/// The `ToUnicode` map of a Type 3 font, from the codes of the glyphs to their text.
+fn to_unicode(text: &[String]) -> String {
+    let mut map = String::from(
+        "/CIDInit /ProcSet findresource begin\n12 dict begin\nbegincmap\n\
+         /CIDSystemInfo << /Registry (Adobe) /Ordering (UCS) /Supplement 0 >> def\n\
+         /CMapName /Adobe-Identity-UCS def\n/CMapType 2 def\n\
+         1 begincodespacerange\n<00> <ff>\nendcodespacerange\n",
+    );
+    let chars: Vec<_> = text
+        .iter()
+        .enumerate()
+        .filter(|(_, text)| !text.is_empty())
+        .collect();
+    // A block can have at most 100 entries
+    for block in chars.chunks(100) {
+        writeln!(map, "{} beginbfchar", block.len()).unwrap();
+        for (code, text) in block {
+            let utf16: String = text
+                .encode_utf16()
+                .map(|unit| format!("{unit:04x}"))
+                .collect();
+            writeln!(map, "<{code:02x}> <{utf16}>").unwrap();
+        }
+        map.push_str("endbfchar\n");
+    }
+    map.push_str("endcmap\nCMapName currentdict /CMap defineresource pop\nend\nend\n");
+    map
+}
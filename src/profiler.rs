@@ -1,14 +1,19 @@
 use crate::app::{add_app_update_event, AppUpdateEvent};
+use crate::context::PaintCx;
 use crate::event::{Event, EventListener, EventPropagation};
 use crate::inspector::header;
+use crate::text::{take_shaping_time, Attrs, AttrsList, TextLayout};
 use crate::view::IntoView;
 use crate::views::{
     button, clip, container, dyn_container, empty, h_stack, label, scroll, stack, static_label,
     text, v_stack, v_stack_from_iter, Decorators,
 };
+use crate::Renderer;
 use floem_reactive::{create_rw_signal, RwSignal, Scope, SignalGet, SignalUpdate};
 use peniko::color::palette;
+use peniko::kurbo::{Rect, Size};
 use peniko::Color;
+use std::collections::VecDeque;
 use std::fmt::Display;
 use std::mem;
 use std::rc::Rc;
@@ -44,6 +49,192 @@ impl Profile {
     }
 }
 
+/// The time spent in each phase of a frame.
+#[derive(Clone, Copy, Default)]
+pub(crate) struct FrameTimings {
+    /// Dispatching events to the views
+    pub(crate) event: Duration,
+    /// Processing the updates sent by effects and views
+    pub(crate) update: Duration,
+    /// Styling and layout
+    pub(crate) layout: Duration,
+    pub(crate) paint: Duration,
+    /// Shaping text, which happens as part of the other phases
+    pub(crate) shaping: Duration,
+}
+
+impl FrameTimings {
+    fn total(&self) -> Duration {
+        self.event + self.update + self.layout + self.paint
+    }
+}
+
+/// Collects the timings of the recent frames of a window and paints them as an overlay.
+#[derive(Default)]
+pub(crate) struct FrameProfiler {
+    pub(crate) current: FrameTimings,
+    frames: VecDeque<(Instant, FrameTimings)>,
+}
+
+impl FrameProfiler {
+    const FRAMES: usize = 120;
+    const BAR_WIDTH: f64 = 2.0;
+    const GRAPH_HEIGHT: f64 = 60.0;
+    /// The frame time at the top of the graph
+    const GRAPH_MAX: Duration = Duration::from_micros(33_333);
+
+    pub(crate) fn new() -> Self {
+        // Shaping done before profiling started shouldn't count towards the first frame
+        take_shaping_time();
+        Self::default()
+    }
+
+    pub(crate) fn end_frame(&mut self, paint: Duration) {
+        let mut timings = mem::take(&mut self.current);
+        timings.paint += paint;
+        timings.shaping += take_shaping_time();
+        if self.frames.len() == Self::FRAMES {
+            self.frames.pop_front();
+        }
+        self.frames.push_back((Instant::now(), timings));
+    }
+
+    /// Frames per second over the last second of painted frames.
+    fn fps(&self) -> f64 {
+        let Some((last, _)) = self.frames.back() else {
+            return 0.0;
+        };
+        let window = Duration::from_secs(1);
+        let frames = self
+            .frames
+            .iter()
+            .filter(|(end, _)| last.saturating_duration_since(*end) < window)
+            .count();
+        let first = self.frames[self.frames.len() - frames].0;
+        let elapsed = last.saturating_duration_since(first).as_secs_f64();
+        if elapsed > 0.0 {
+            (frames - 1) as f64 / elapsed
+        } else {
+            0.0
+        }
+    }
+
+    fn average(&self) -> FrameTimings {
+        let mut average = FrameTimings::default();
+        let count = self.frames.len().max(1) as u32;
+        for (_, timings) in &self.frames {
+            average.event += timings.event / count;
+            average.update += timings.update / count;
+            average.layout += timings.layout / count;
+            average.paint += timings.paint / count;
+            average.shaping += timings.shaping / count;
+        }
+        average
+    }
+
+    /// Paints the average timings and a graph of the recent frames in the top right corner
+    /// of `window`.
+    pub(crate) fn paint(&self, cx: &mut PaintCx, window: Rect) {
+        let padding = 6.0;
+        let width = Self::FRAMES as f64 * Self::BAR_WIDTH;
+        let average = self.average();
+        let ms = |duration: Duration| duration.as_secs_f64() * 1000.0;
+        let lines = [
+            (
+                format!("{:.0} FPS  {:.2} ms/frame", self.fps(), ms(average.total())),
+                palette::css::WHITE,
+            ),
+            (
+                format!("event {:.2} ms", ms(average.event)),
+                palette::css::ORANGE,
+            ),
+            (
+                format!("update {:.2} ms", ms(average.update)),
+                palette::css::MEDIUM_PURPLE,
+            ),
+            (
+                format!("layout {:.2} ms", ms(average.layout)),
+                palette::css::DODGER_BLUE,
+            ),
+            (
+                format!("paint {:.2} ms", ms(average.paint)),
+                palette::css::LIME_GREEN,
+            ),
+            (
+                format!("text shaping {:.2} ms", ms(average.shaping)),
+                palette::css::WHITE.with_alpha(0.6),
+            ),
+        ]
+        .map(|(line, color)| {
+            let mut layout = TextLayout::new();
+            layout.set_text(
+                &line,
+                AttrsList::new(Attrs::new().font_size(11.0).color(color)),
+            );
+            layout
+        });
+        let text_height: f64 = lines.iter().map(|layout| layout.size().height).sum();
+
+        let size = Size::new(
+            width + padding * 2.0,
+            text_height + Self::GRAPH_HEIGHT + padding * 3.0,
+        );
+        let origin = (window.x1 - size.width - padding, window.y0 + padding);
+        let panel = Rect::from_origin_size(origin, size);
+        cx.fill(
+            &panel.to_rounded_rect(3.0),
+            Color::from_rgba8(30, 30, 30, 220),
+            0.0,
+        );
+
+        let mut y = panel.y0 + padding;
+        for layout in &lines {
+            cx.draw_text(layout, (panel.x0 + padding, y));
+            y += layout.size().height;
+        }
+
+        let graph = Rect::new(
+            panel.x0 + padding,
+            panel.y1 - padding - Self::GRAPH_HEIGHT,
+            panel.x1 - padding,
+            panel.y1 - padding,
+        );
+        let height = |duration: Duration| {
+            (duration.as_secs_f64() / Self::GRAPH_MAX.as_secs_f64() * Self::GRAPH_HEIGHT)
+                .min(Self::GRAPH_HEIGHT)
+        };
+        let x0 = graph.x1 - self.frames.len() as f64 * Self::BAR_WIDTH;
+        for (i, (_, timings)) in self.frames.iter().enumerate() {
+            let x = x0 + i as f64 * Self::BAR_WIDTH;
+            let mut bottom = graph.y1;
+            for (duration, color) in [
+                (timings.event, palette::css::ORANGE),
+                (timings.update, palette::css::MEDIUM_PURPLE),
+                (timings.layout, palette::css::DODGER_BLUE),
+                (timings.paint, palette::css::LIME_GREEN),
+            ] {
+                let top = (bottom - height(duration)).max(graph.y0);
+                cx.fill(&Rect::new(x, top, x + Self::BAR_WIDTH, bottom), color, 0.0);
+                bottom = top;
+            }
+            let shaping = graph.y1 - height(timings.shaping);
+            cx.fill(
+                &Rect::new(x, shaping, x + Self::BAR_WIDTH, graph.y1),
+                palette::css::WHITE.with_alpha(0.6),
+                0.0,
+            );
+        }
+
+        // The budget of a frame at 60 FPS
+        let budget = graph.y1 - height(Duration::from_micros(16_667));
+        cx.fill(
+            &Rect::new(graph.x0, budget, graph.x1, budget + 1.0),
+            palette::css::RED.with_alpha(0.6),
+            0.0,
+        );
+    }
+}
+
 struct ProfileFrameData {
     start: Option<Instant>,
     duration: Duration,
@@ -50,7 +50,8 @@
 use std::sync::Arc;
 
 use crate::kurbo::Point;
-use floem_renderer::gpu_resources::GpuResources;
+use floem_renderer::display_list::DisplayList;
+use floem_renderer::gpu_resources::{GpuResourceError, GpuResources};
 use floem_renderer::text::LayoutRun;
 use floem_renderer::Img;
 use floem_tiny_skia_renderer::TinySkiaRenderer;
@@ -69,6 +70,8 @@ pub enum Renderer {
     #[cfg(not(feature = "vello"))]
     Vger(VgerRenderer),
     TinySkia(TinySkiaRenderer<Arc<dyn Window>>),
+    /// Records the draw commands instead of drawing them
+    Recording(DisplayList),
     /// Uninitialized renderer, used to allow the renderer to be created lazily
     /// All operations on this renderer are no-ops
     Uninitialized {
@@ -78,39 +81,33 @@ pub enum Renderer {
 }
 
 impl Renderer {
+    /// Creates the GPU renderer, or the software renderer if `gpu_resources` is `None` or the
+    /// GPU can't be used.
     pub fn new(
         window: Arc<dyn Window>,
-        gpu_resources: GpuResources,
+        gpu_resources: Option<Result<GpuResources, GpuResourceError>>,
         scale: f64,
         size: Size,
         font_embolden: f32,
     ) -> Self {
         let size = Size::new(size.width.max(1.0), size.height.max(1.0));
 
-        let force_tiny_skia = std::env::var("FLOEM_FORCE_TINY_SKIA")
-            .ok()
-            .map(|val| val.as_str() == "1")
-            .unwrap_or(false);
-
-        #[cfg(feature = "vello")]
-        let vger_err = if !force_tiny_skia {
-            match VelloRenderer::new(
+        let gpu_err = match gpu_resources {
+            None => None,
+            Some(Err(err)) => Some(format!("Failed to acquire GPU resources: {err}")),
+            #[cfg(feature = "vello")]
+            Some(Ok(gpu_resources)) => match VelloRenderer::new(
                 gpu_resources,
                 size.width as u32,
                 size.height as u32,
                 scale,
                 font_embolden,
             ) {
-                Ok(vger) => return Self::Vello(vger),
-                Err(err) => Some(err),
-            }
-        } else {
-            None
-        };
-
-        #[cfg(not(feature = "vello"))]
-        let vger_err = if !force_tiny_skia {
-            match VgerRenderer::new(
+                Ok(vello) => return Self::Vello(vello),
+                Err(err) => Some(format!("Failed to create VelloRenderer: {err}")),
+            },
+            #[cfg(not(feature = "vello"))]
+            Some(Ok(gpu_resources)) => match VgerRenderer::new(
                 gpu_resources,
                 size.width as u32,
                 size.height as u32,
@@ -118,27 +115,42 @@ impl Renderer {
                 font_embolden,
             ) {
                 Ok(vger) => return Self::Vger(vger),
-                Err(err) => Some(err),
-            }
-        } else {
-            None
+                Err(err) => Some(format!("Failed to create VgerRenderer: {err}")),
+            },
         };
+        if let Some(gpu_err) = &gpu_err {
+            eprintln!("{gpu_err}\nFalling back to the software renderer");
+        }
 
-        let tiny_skia_err = match TinySkiaRenderer::new(
+        match TinySkiaRenderer::new(
             window,
             size.width as u32,
             size.height as u32,
             scale,
             font_embolden,
         ) {
-            Ok(tiny_skia) => return Self::TinySkia(tiny_skia),
-            Err(err) => err,
-        };
+            Ok(tiny_skia) => Self::TinySkia(tiny_skia),
+            Err(err) => match gpu_err {
+                Some(gpu_err) => {
+                    panic!("{gpu_err}\nFailed to create TinySkiaRenderer: {err}")
+                }
+                None => panic!("Failed to create TinySkiaRenderer: {err}"),
+            },
+        }
+    }
 
-        if !force_tiny_skia {
-            panic!("Failed to create VgerRenderer: {}\nFailed to create TinySkiaRenderer: {tiny_skia_err}", vger_err.unwrap());
-        } else {
-            panic!("Failed to create TinySkiaRenderer: {tiny_skia_err}");
+    /// Creates a software renderer which returns every frame as an image instead of
+    /// presenting it, used to render views without a window.
+    pub fn new_offscreen(scale: f64, size: Size, font_embolden: f32) -> Self {
+        let size = Size::new(size.width.max(1.0), size.height.max(1.0));
+        match TinySkiaRenderer::new_offscreen(
+            size.width as u32,
+            size.height as u32,
+            scale,
+            font_embolden,
+        ) {
+            Ok(tiny_skia) => Self::TinySkia(tiny_skia),
+            Err(err) => panic!("Failed to create offscreen TinySkiaRenderer: {err}"),
         }
     }
 
@@ -150,7 +162,7 @@ impl Renderer {
             #[cfg(not(feature = "vello"))]
             Renderer::Vger(r) => r.resize(size.width as u32, size.height as u32, scale),
             Renderer::TinySkia(r) => r.resize(size.width as u32, size.height as u32, scale),
-            Renderer::Uninitialized { .. } => {}
+            Renderer::Recording(_) | Renderer::Uninitialized { .. } => {}
         }
     }
 
@@ -161,6 +173,7 @@ impl Renderer {
             #[cfg(not(feature = "vello"))]
             Renderer::Vger(r) => r.set_scale(scale),
             Renderer::TinySkia(r) => r.set_scale(scale),
+            Renderer::Recording(_) => {}
             Renderer::Uninitialized {
                 scale: old_scale, ..
             } => {
@@ -176,6 +189,8 @@ impl Renderer {
             #[cfg(not(feature = "vello"))]
             Renderer::Vger(r) => r.scale(),
             Renderer::TinySkia(r) => r.scale(),
+            // Commands are recorded in logical coordinates
+            Renderer::Recording(_) => 1.0,
             Renderer::Uninitialized { scale, .. } => *scale,
         }
     }
@@ -187,6 +202,7 @@ impl Renderer {
             #[cfg(not(feature = "vello"))]
             Renderer::Vger(r) => r.size(),
             Renderer::TinySkia(r) => r.size(),
+            Renderer::Recording(r) => r.size,
             Renderer::Uninitialized { size, .. } => *size,
         }
     }
@@ -206,10 +222,18 @@ impl floem_renderer::Renderer for Renderer {
             Renderer::TinySkia(r) => {
                 r.begin(capture);
             }
+            Renderer::Recording(r) => r.begin(capture),
             Renderer::Uninitialized { .. } => {}
         }
     }
 
+    fn begin_partial(&mut self, damage: Rect) -> bool {
+        match self {
+            Renderer::TinySkia(r) => r.begin_partial(damage),
+            _ => false,
+        }
+    }
+
     fn clip(&mut self, shape: &impl Shape) {
         match self {
             #[cfg(feature = "vello")]
@@ -223,6 +247,7 @@ impl floem_renderer::Renderer for Renderer {
             Renderer::TinySkia(v) => {
                 v.clip(shape);
             }
+            Renderer::Recording(r) => r.clip(shape),
             Renderer::Uninitialized { .. } => {}
         }
     }
@@ -240,6 +265,7 @@ impl floem_renderer::Renderer for Renderer {
             Renderer::TinySkia(v) => {
                 v.clear_clip();
             }
+            Renderer::Recording(r) => r.clear_clip(),
             Renderer::Uninitialized { .. } => {}
         }
     }
@@ -262,6 +288,7 @@ impl floem_renderer::Renderer for Renderer {
             Renderer::TinySkia(v) => {
                 v.stroke(shape, brush, stroke);
             }
+            Renderer::Recording(r) => r.stroke(shape, brush, stroke),
             Renderer::Uninitialized { .. } => {}
         }
     }
@@ -284,6 +311,7 @@ impl floem_renderer::Renderer for Renderer {
             Renderer::TinySkia(v) => {
                 v.fill(path, brush, blur_radius);
             }
+            Renderer::Recording(r) => r.fill(path, brush, blur_radius),
             Renderer::Uninitialized { .. } => {}
         }
     }
@@ -305,6 +333,7 @@ impl floem_renderer::Renderer for Renderer {
             Renderer::TinySkia(v) => {
                 v.draw_text_with_layout(layout, pos);
             }
+            Renderer::Recording(r) => r.draw_text_with_layout(layout, pos),
             Renderer::Uninitialized { .. } => {}
         }
     }
@@ -322,6 +351,7 @@ impl floem_renderer::Renderer for Renderer {
             Renderer::TinySkia(v) => {
                 v.draw_img(img, rect);
             }
+            Renderer::Recording(r) => r.draw_img(img, rect),
             Renderer::Uninitialized { .. } => {}
         }
     }
@@ -344,6 +374,7 @@ impl floem_renderer::Renderer for Renderer {
             Renderer::TinySkia(v) => {
                 v.draw_svg(svg, rect, brush);
             }
+            Renderer::Recording(r) => r.draw_svg(svg, rect, brush),
             Renderer::Uninitialized { .. } => {}
         }
     }
@@ -361,6 +392,7 @@ impl floem_renderer::Renderer for Renderer {
             Renderer::TinySkia(v) => {
                 v.transform(transform);
             }
+            Renderer::Recording(r) => r.transform(transform),
             Renderer::Uninitialized { .. } => {}
         }
     }
@@ -378,6 +410,7 @@ impl floem_renderer::Renderer for Renderer {
             Renderer::TinySkia(v) => {
                 v.set_z_index(z_index);
             }
+            Renderer::Recording(r) => r.set_z_index(z_index),
             Renderer::Uninitialized { .. } => {}
         }
     }
@@ -389,6 +422,7 @@ impl floem_renderer::Renderer for Renderer {
             #[cfg(not(feature = "vello"))]
             Renderer::Vger(r) => r.finish(),
             Renderer::TinySkia(r) => r.finish(),
+            Renderer::Recording(r) => r.finish(),
             Renderer::Uninitialized { .. } => None,
         }
     }
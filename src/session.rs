@@ -0,0 +1,203 @@
+//! Restoring the windows and the open documents of an application as they were when it was
+//! last closed.
+//!
+//! The state is stored with [persistent signals](crate::persist), so it's saved by the
+//! [`StorageBackend`](crate::persist::StorageBackend) of the application. Nothing is stored
+//! unless these helpers are used.
+//!
+//! ## Example
+//! ```no_run
+//! use floem::{session::*, views::label, window::WindowConfig, Application};
+//!
+//! let documents = persistent_open_documents("documents");
+//! Application::new()
+//!     .window(
+//!         move |window_id| persistent_window("main", window_id, label(|| "Hello")),
+//!         Some(restore_window_config("main", WindowConfig::default())),
+//!     )
+//!     .run();
+//! ```
+//!
+//! Other parts of the layout of an application, like the sizes of its panels, can be stored
+//! with [`create_persistent_signal`](crate::persist::create_persistent_signal).
+use std::path::PathBuf;
+
+use floem_reactive::{RwSignal, SignalGet, SignalUpdate, SignalWith};
+use peniko::kurbo::{Point, Rect, Size};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    event::EventListener, persist::create_persistent_signal, view::IntoView, views::Decorators,
+    window::WindowConfig, WindowIdExt,
+};
+use winit::window::WindowId;
+
+/// How much of the top of a window must be on a monitor for its stored position to be
+/// restored, so that it can still be dragged by its title bar.
+const MIN_VISIBLE: Size = Size::new(64.0, 32.0);
+
+/// The geometry of a window, in logical pixels of the desktop.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct WindowState {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+    /// The geometry is the one the window has when it isn't maximized
+    pub maximized: bool,
+}
+
+impl WindowState {
+    pub fn bounds(&self) -> Rect {
+        Rect::new(self.x, self.y, self.x + self.width, self.y + self.height)
+    }
+
+    fn is_valid(&self) -> bool {
+        self.width > 0.0 && self.height > 0.0
+    }
+}
+
+/// The key under which the state of the window `key` is stored.
+fn window_key(key: &str) -> String {
+    format!("{key}.window")
+}
+
+/// `bounds` moved and shrunk so that the window is on one of `monitors`, or `bounds` if its
+/// title bar is already on one. Returns `None` when there is no monitor.
+///
+/// A window which isn't on any monitor, like a window which was on a monitor which was
+/// disconnected, is centered on the first monitor.
+pub fn fit_to_monitors(bounds: Rect, monitors: &[Rect]) -> Option<Rect> {
+    let title_bar = Rect::new(
+        bounds.x0,
+        bounds.y0,
+        bounds.x1,
+        bounds.y0 + MIN_VISIBLE.height,
+    );
+    let visible = monitors.iter().any(|monitor| {
+        let visible = title_bar.intersect(*monitor);
+        visible.width() >= MIN_VISIBLE.width.min(bounds.width())
+            && visible.height() >= MIN_VISIBLE.height.min(bounds.height())
+    });
+    if visible {
+        return Some(bounds);
+    }
+    let monitor = monitors.first()?;
+    let size = Size::new(
+        bounds.width().min(monitor.width()),
+        bounds.height().min(monitor.height()),
+    );
+    let origin = Point::new(
+        monitor.x0 + (monitor.width() - size.width) / 2.0,
+        monitor.y0 + (monitor.height() - size.height) / 2.0,
+    );
+    Some(Rect::from_origin_size(origin, size))
+}
+
+/// Sets the size and the position of `config` to the ones the window `key` had when it was
+/// last closed, when it was stored by [`persistent_window`].
+///
+/// The position is checked against the monitors once the window is created, which
+/// [`persistent_window`] does.
+pub fn restore_window_config(key: &str, config: WindowConfig) -> WindowConfig {
+    let state = create_persistent_signal(window_key(key), None::<WindowState>);
+    match state.get_untracked().filter(WindowState::is_valid) {
+        Some(state) => config
+            .size(Size::new(state.width, state.height))
+            .position(Point::new(state.x, state.y)),
+        None => config,
+    }
+}
+
+/// Stores the geometry and the maximized state of the window `window_id` under `key` while
+/// it's open, wrapping the root `view` of the window.
+///
+/// When the window opens, it's maximized if it was when it was closed, and moved back onto a
+/// monitor if its stored position isn't on any of the current monitors.
+pub fn persistent_window<V: IntoView + 'static>(key: &str, window_id: WindowId, view: V) -> V::V {
+    let state = create_persistent_signal(window_key(key), None::<WindowState>);
+
+    if let Some(stored) = state.get_untracked().filter(WindowState::is_valid) {
+        let monitors: Vec<Rect> = window_id
+            .available_monitors()
+            .iter()
+            .map(|monitor| monitor.logical_bounds())
+            .collect();
+        if let Some(bounds) = fit_to_monitors(stored.bounds(), &monitors) {
+            if bounds != stored.bounds() {
+                window_id.set_window_outer_bounds(bounds);
+            }
+        }
+        if stored.maximized {
+            window_id.maximized(true);
+        }
+    }
+
+    let save = move || {
+        let maximized = window_id.is_maximized();
+        let bounds = window_id.bounds_on_screen_including_frame();
+        state.update(|state| {
+            let mut new_state = state.unwrap_or_default();
+            new_state.maximized = maximized;
+            // A maximized or minimized window keeps the geometry it's restored to
+            if let Some(bounds) = bounds.filter(|_| !maximized && !window_id.is_minimized()) {
+                new_state.x = bounds.x0;
+                new_state.y = bounds.y0;
+                new_state.width = bounds.width();
+                new_state.height = bounds.height();
+            }
+            *state = Some(new_state);
+        });
+    };
+
+    view.into_view()
+        .on_event_cont(EventListener::WindowResized, move |_| save())
+        .on_event_cont(EventListener::WindowMoved, move |_| save())
+        .on_event_cont(EventListener::WindowMaximizeChanged, move |_| save())
+}
+
+/// A signal with the documents which were open when the application was last closed, stored
+/// under `key`, without the documents which don't exist anymore.
+///
+/// The application updates the signal as documents are opened and closed.
+pub fn persistent_open_documents(key: &str) -> RwSignal<Vec<PathBuf>> {
+    let documents = create_persistent_signal(format!("{key}.documents"), Vec::<PathBuf>::new());
+    if documents.with_untracked(|documents| documents.iter().any(|path| !path.exists())) {
+        documents.update(|documents| documents.retain(|path| path.exists()));
+    }
+    documents
+}
+
+#[cfg(test)]
+mod tests {
+    use peniko::kurbo::Rect;
+
+    use super::fit_to_monitors;
+
+    #[test]
+    fn monitors() {
+        let monitors = [
+            Rect::new(0.0, 0.0, 1920.0, 1080.0),
+            Rect::new(1920.0, 0.0, 3200.0, 1024.0),
+        ];
+        // On the second monitor
+        let bounds = Rect::new(2000.0, 100.0, 2800.0, 700.0);
+        assert_eq!(fit_to_monitors(bounds, &monitors), Some(bounds));
+        // Mostly off screen, but the title bar can still be dragged
+        let bounds = Rect::new(1800.0, 1000.0, 2600.0, 1600.0);
+        assert_eq!(fit_to_monitors(bounds, &monitors), Some(bounds));
+        // On a monitor which was disconnected
+        let bounds = Rect::new(-1500.0, 100.0, -700.0, 700.0);
+        assert_eq!(
+            fit_to_monitors(bounds, &monitors),
+            Some(Rect::new(560.0, 240.0, 1360.0, 840.0))
+        );
+        // Larger than the monitor
+        let bounds = Rect::new(5000.0, 0.0, 8000.0, 2000.0);
+        assert_eq!(
+            fit_to_monitors(bounds, &monitors),
+            Some(Rect::new(0.0, 0.0, 1920.0, 1080.0))
+        );
+        assert_eq!(fit_to_monitors(bounds, &[]), None);
+    }
+}
@@ -0,0 +1,121 @@
+//! Snap layouts of Windows 11 for custom maximize buttons.
+//!
+//! Windows only shows the snap layout flyout when hovering a point which the window reports as
+//! its maximize button in `WM_NCHITTEST`. The window is subclassed to report the area of the
+//! custom maximize button as `HTMAXBUTTON`, and the non-client mouse messages which Windows then
+//! sends for that area are forwarded as client messages, so the button keeps its hover and
+//! active styles and receives its clicks as usual.
+use std::{cell::RefCell, collections::HashMap};
+
+use peniko::kurbo::{Point, Rect};
+use raw_window_handle::{HasWindowHandle, RawWindowHandle};
+use windows_sys::Win32::{
+    Foundation::{HWND, LPARAM, LRESULT, POINT, WPARAM},
+    Graphics::Gdi::ScreenToClient,
+    UI::{
+        Shell::{DefSubclassProc, RemoveWindowSubclass, SetWindowSubclass},
+        WindowsAndMessaging::{
+            PostMessageW, HTMAXBUTTON, MK_LBUTTON, WM_LBUTTONDOWN, WM_LBUTTONUP, WM_MOUSEMOVE,
+            WM_NCDESTROY, WM_NCHITTEST, WM_NCLBUTTONDBLCLK, WM_NCLBUTTONDOWN, WM_NCLBUTTONUP,
+            WM_NCMOUSEMOVE,
+        },
+    },
+};
+use winit::window::Window;
+
+const SUBCLASS_ID: usize = 0x666c_6f65;
+
+thread_local! {
+    /// The area of the maximize button of each subclassed window, in physical client coordinates.
+    static MAXIMIZE_BUTTON_AREAS: RefCell<HashMap<isize, Rect>> = RefCell::new(HashMap::new());
+}
+
+/// Sets the area of the maximize button of the window, in physical pixels relative to the
+/// client area, or removes it with `None`.
+pub(crate) fn set_maximize_button_area(window: &dyn Window, area: Option<Rect>) {
+    let Ok(handle) = window.window_handle() else {
+        return;
+    };
+    let RawWindowHandle::Win32(handle) = handle.as_raw() else {
+        return;
+    };
+    let hwnd = isize::from(handle.hwnd);
+    MAXIMIZE_BUTTON_AREAS.with_borrow_mut(|areas| match area {
+        Some(area) => {
+            if areas.insert(hwnd, area).is_none() {
+                unsafe {
+                    SetWindowSubclass(hwnd as HWND, Some(subclass_proc), SUBCLASS_ID, 0);
+                }
+            }
+        }
+        None => {
+            if areas.remove(&hwnd).is_some() {
+                unsafe {
+                    RemoveWindowSubclass(hwnd as HWND, Some(subclass_proc), SUBCLASS_ID);
+                }
+            }
+        }
+    });
+}
+
+/// The screen coordinates packed in the `lparam` of the non-client mouse messages.
+fn screen_point(lparam: LPARAM) -> POINT {
+    POINT {
+        x: (lparam & 0xffff) as u16 as i16 as i32,
+        y: ((lparam >> 16) & 0xffff) as u16 as i16 as i32,
+    }
+}
+
+/// Converts a point in screen coordinates to client coordinates, and returns it if it's in the
+/// maximize button.
+fn client_point_in_button(hwnd: HWND, lparam: LPARAM) -> Option<POINT> {
+    let mut point = screen_point(lparam);
+    if unsafe { ScreenToClient(hwnd, &mut point) } == 0 {
+        return None;
+    }
+    let area = MAXIMIZE_BUTTON_AREAS.with_borrow(|areas| areas.get(&(hwnd as isize)).copied())?;
+    area.contains(Point::new(point.x as f64, point.y as f64))
+        .then_some(point)
+}
+
+fn client_lparam(point: POINT) -> LPARAM {
+    ((point.y as u16 as isize) << 16) | point.x as u16 as isize
+}
+
+unsafe extern "system" fn subclass_proc(
+    hwnd: HWND,
+    msg: u32,
+    wparam: WPARAM,
+    lparam: LPARAM,
+    _id: usize,
+    _data: usize,
+) -> LRESULT {
+    match msg {
+        WM_NCHITTEST => {
+            if client_point_in_button(hwnd, lparam).is_some() {
+                return HTMAXBUTTON as LRESULT;
+            }
+        }
+        WM_NCMOUSEMOVE | WM_NCLBUTTONDOWN | WM_NCLBUTTONDBLCLK | WM_NCLBUTTONUP
+            if wparam == HTMAXBUTTON as WPARAM =>
+        {
+            // The default handling would draw and click the classic caption button, so the
+            // messages go to the view of the button instead.
+            if let Some(point) = client_point_in_button(hwnd, lparam) {
+                let (msg, keys) = match msg {
+                    WM_NCMOUSEMOVE => (WM_MOUSEMOVE, 0),
+                    WM_NCLBUTTONUP => (WM_LBUTTONUP, 0),
+                    _ => (WM_LBUTTONDOWN, MK_LBUTTON),
+                };
+                PostMessageW(hwnd, msg, keys as WPARAM, client_lparam(point));
+            }
+            return 0;
+        }
+        WM_NCDESTROY => {
+            MAXIMIZE_BUTTON_AREAS.with_borrow_mut(|areas| areas.remove(&(hwnd as isize)));
+            RemoveWindowSubclass(hwnd, Some(subclass_proc), SUBCLASS_ID);
+        }
+        _ => {}
+    }
+    DefSubclassProc(hwnd, msg, wparam, lparam)
+}
@@ -0,0 +1,160 @@
+//! Sounds played as feedback for events of the user interface, like the beep of a text input
+//! when nothing is left to delete, for users who rely on audible feedback.
+//!
+//! The [system sounds](SystemSound) are the sounds of the operating system, so they follow the
+//! sound theme and the volume set by the user. With the `audio` feature, applications can also
+//! play their own samples with [`play_sample`]. All sounds are silenced while [`muted`].
+//! ```rust
+//! use floem::sound::{play_sound, set_muted, SystemSound};
+//!
+//! set_muted(false);
+//! play_sound(SystemSound::Notification);
+//! ```
+use floem_reactive::{ReadSignal, RwSignal, Scope, SignalGet, SignalUpdate};
+
+/// A sound of the operating system.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SystemSound {
+    /// The default beep, for an action which had no effect
+    Beep,
+    /// An action failed, like an invalid input
+    Error,
+    /// Something needs the attention of the user
+    Warning,
+    /// Something happened which the user may want to know about, like a message
+    Notification,
+}
+
+thread_local! {
+    static MUTED: RwSignal<bool> = Scope::new().create_rw_signal(false);
+}
+
+/// Whether the sounds are muted, `false` by default.
+pub fn muted() -> ReadSignal<bool> {
+    MUTED.with(|muted| muted.read_only())
+}
+
+/// Mutes or unmutes all the sounds played by the application.
+pub fn set_muted(muted: bool) {
+    MUTED.with(|signal| signal.set(muted));
+}
+
+fn is_muted() -> bool {
+    MUTED.with(|muted| muted.get_untracked())
+}
+
+/// Plays a sound of the operating system, unless the sounds are [`muted`].
+///
+/// Platform support:
+/// - Windows, macOS: Yes
+/// - Linux: When `canberra-gtk-play` is installed, which is the case on most desktops
+/// - Web, Android, iOS: No
+pub fn play_sound(sound: SystemSound) {
+    if is_muted() {
+        return;
+    }
+    platform_play_sound(sound);
+}
+
+#[cfg(target_os = "windows")]
+fn platform_play_sound(sound: SystemSound) {
+    use windows_sys::Win32::UI::WindowsAndMessaging::{
+        MessageBeep, MB_ICONASTERISK, MB_ICONEXCLAMATION, MB_ICONHAND, MB_OK,
+    };
+
+    let style = match sound {
+        SystemSound::Beep => MB_OK,
+        SystemSound::Error => MB_ICONHAND,
+        SystemSound::Warning => MB_ICONEXCLAMATION,
+        SystemSound::Notification => MB_ICONASTERISK,
+    };
+    unsafe {
+        MessageBeep(style);
+    }
+}
+
+#[cfg(target_os = "macos")]
+#[allow(unused_unsafe)]
+fn platform_play_sound(sound: SystemSound) {
+    use objc2_app_kit::NSSound;
+    use objc2_foundation::NSString;
+
+    let name = match sound {
+        SystemSound::Beep => "Tink",
+        SystemSound::Error => "Basso",
+        SystemSound::Warning => "Funk",
+        SystemSound::Notification => "Glass",
+    };
+    unsafe {
+        if let Some(sound) = NSSound::soundNamed(&NSString::from_str(name)) {
+            sound.play();
+        }
+    }
+}
+
+/// The sounds of the freedesktop sound theme, played by libcanberra.
+#[cfg(any(
+    target_os = "linux",
+    target_os = "freebsd",
+    target_os = "dragonfly",
+    target_os = "openbsd",
+    target_os = "netbsd"
+))]
+fn platform_play_sound(sound: SystemSound) {
+    let id = match sound {
+        SystemSound::Beep => "bell",
+        SystemSound::Error => "dialog-error",
+        SystemSound::Warning => "dialog-warning",
+        SystemSound::Notification => "message-new-instant",
+    };
+    // The process is waited for on a thread so that it isn't left as a zombie
+    std::thread::spawn(move || {
+        let _ = std::process::Command::new("canberra-gtk-play")
+            .args(["--id", id])
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .status();
+    });
+}
+
+#[cfg(not(any(
+    target_os = "windows",
+    target_os = "macos",
+    target_os = "linux",
+    target_os = "freebsd",
+    target_os = "dragonfly",
+    target_os = "openbsd",
+    target_os = "netbsd"
+)))]
+fn platform_play_sound(_sound: SystemSound) {}
+
+/// Plays an audio sample, like the content of a WAV, MP3, FLAC or Vorbis file, unless the sounds
+/// are [`muted`]. The sample is decoded and played on a thread, samples which can't be decoded
+/// are ignored.
+///
+/// Not supported on the web, Android and iOS.
+#[cfg(feature = "audio")]
+pub fn play_sample(sample: impl Into<std::sync::Arc<[u8]>>) {
+    if is_muted() {
+        return;
+    }
+    #[cfg(not(any(target_arch = "wasm32", target_os = "android", target_os = "ios")))]
+    {
+        let sample = sample.into();
+        std::thread::spawn(move || {
+            let Ok((_stream, handle)) = rodio::OutputStream::try_default() else {
+                return;
+            };
+            let Ok(sink) = rodio::Sink::try_new(&handle) else {
+                return;
+            };
+            let Ok(source) = rodio::Decoder::new(std::io::Cursor::new(sample)) else {
+                return;
+            };
+            sink.append(source);
+            sink.sleep_until_end();
+        });
+    }
+    #[cfg(any(target_arch = "wasm32", target_os = "android", target_os = "ios"))]
+    let _ = sample;
+}
@@ -34,6 +34,7 @@ use taffy::{
 };
 
 use crate::context::InteractionState;
+pub use crate::cursor::{CustomCursor, CustomCursorError};
 use crate::easing::*;
 use crate::responsive::{ScreenSize, ScreenSizeBp};
 use crate::unit::{Pct, Px, PxPct, PxPctAuto, UnitExt};
@@ -48,33 +49,57 @@ pub trait StylePropValue: Clone + PartialEq + Debug {
     fn interpolate(&self, _other: &Self, _value: f64) -> Option<Self> {
         None
     }
+
+    /// Parses a value from text, used by the inspector to edit styles at runtime.
+    fn parse(_text: &str) -> Option<Self> {
+        None
+    }
 }
 
 impl StylePropValue for i32 {
     fn interpolate(&self, other: &Self, value: f64) -> Option<Self> {
         Some((*self as f64 + (*other as f64 - *self as f64) * value).round() as i32)
     }
+    fn parse(text: &str) -> Option<Self> {
+        text.trim().parse().ok()
+    }
+}
+impl StylePropValue for bool {
+    fn parse(text: &str) -> Option<Self> {
+        text.trim().parse().ok()
+    }
 }
-impl StylePropValue for bool {}
 impl StylePropValue for f32 {
     fn interpolate(&self, other: &Self, value: f64) -> Option<Self> {
         Some(*self * (1.0 - value as f32) + *other * value as f32)
     }
+    fn parse(text: &str) -> Option<Self> {
+        text.trim().parse().ok()
+    }
 }
 impl StylePropValue for u16 {
     fn interpolate(&self, other: &Self, value: f64) -> Option<Self> {
         Some((*self as f64 + (*other as f64 - *self as f64) * value).round() as u16)
     }
+    fn parse(text: &str) -> Option<Self> {
+        text.trim().parse().ok()
+    }
 }
 impl StylePropValue for usize {
     fn interpolate(&self, other: &Self, value: f64) -> Option<Self> {
         Some((*self as f64 + (*other as f64 - *self as f64) * value).round() as usize)
     }
+    fn parse(text: &str) -> Option<Self> {
+        text.trim().parse().ok()
+    }
 }
 impl StylePropValue for f64 {
     fn interpolate(&self, other: &Self, value: f64) -> Option<Self> {
         Some(*self * (1.0 - value) + *other * value)
     }
+    fn parse(text: &str) -> Option<Self> {
+        text.trim().parse().ok()
+    }
 }
 impl StylePropValue for Overflow {}
 impl StylePropValue for Display {}
@@ -105,7 +130,11 @@ impl StylePropValue for BoxShadow {
         })
     }
 }
-impl StylePropValue for String {}
+impl StylePropValue for String {
+    fn parse(text: &str) -> Option<Self> {
+        Some(text.to_owned())
+    }
+}
 impl StylePropValue for Weight {
     fn interpolate(&self, other: &Self, value: f64) -> Option<Self> {
         self.0.interpolate(&other.0, value).map(Weight)
@@ -113,6 +142,15 @@ impl StylePropValue for Weight {
 }
 impl StylePropValue for crate::text::Style {}
 impl StylePropValue for TextOverflow {}
+impl StylePropValue for FontSizeRange {
+    fn interpolate(&self, other: &Self, value: f64) -> Option<Self> {
+        Some(FontSizeRange {
+            min: self.min.interpolate(&other.min, value)?,
+            max: self.max.interpolate(&other.max, value)?,
+        })
+    }
+}
+impl StylePropValue for Direction {}
 impl StylePropValue for PointerEvents {}
 impl StylePropValue for LineHeightValue {
     fn interpolate(&self, other: &Self, value: f64) -> Option<Self> {
@@ -134,6 +172,14 @@ impl<T: StylePropValue> StylePropValue for Option<T> {
         self.as_ref().and_then(|v| v.debug_view())
     }
 
+    fn parse(text: &str) -> Option<Self> {
+        if text.trim() == "none" {
+            Some(None)
+        } else {
+            T::parse(text).map(Some)
+        }
+    }
+
     fn interpolate(&self, other: &Self, value: f64) -> Option<Self> {
         self.as_ref().and_then(|this| {
             other
@@ -168,6 +214,9 @@ impl StylePropValue for Px {
     fn interpolate(&self, other: &Self, value: f64) -> Option<Self> {
         self.0.interpolate(&other.0, value).map(Px)
     }
+    fn parse(text: &str) -> Option<Self> {
+        parse_px(text).map(Px)
+    }
 }
 impl StylePropValue for Pct {
     fn debug_view(&self) -> Option<Box<dyn View>> {
@@ -176,6 +225,9 @@ impl StylePropValue for Pct {
     fn interpolate(&self, other: &Self, value: f64) -> Option<Self> {
         self.0.interpolate(&other.0, value).map(Pct)
     }
+    fn parse(text: &str) -> Option<Self> {
+        parse_pct(text).map(Pct)
+    }
 }
 impl StylePropValue for PxPctAuto {
     fn debug_view(&self) -> Option<Box<dyn View>> {
@@ -195,6 +247,16 @@ impl StylePropValue for PxPctAuto {
             _ => None,
         }
     }
+    fn parse(text: &str) -> Option<Self> {
+        if text.trim() == "auto" {
+            Some(Self::Auto)
+        } else {
+            PxPct::parse(text).map(|v| match v {
+                PxPct::Px(v) => Self::Px(v),
+                PxPct::Pct(v) => Self::Pct(v),
+            })
+        }
+    }
 }
 impl StylePropValue for PxPct {
     fn debug_view(&self) -> Option<Box<dyn View>> {
@@ -213,7 +275,29 @@ impl StylePropValue for PxPct {
             _ => None,
         }
     }
+
+    fn parse(text: &str) -> Option<Self> {
+        parse_pct(text)
+            .map(Self::Pct)
+            .or_else(|| parse_px(text).map(Self::Px))
+    }
+}
+
+/// Parses `10` or `10px` as pixels.
+fn parse_px(text: &str) -> Option<f64> {
+    let text = text.trim();
+    text.strip_suffix("px")
+        .unwrap_or(text)
+        .trim_end()
+        .parse()
+        .ok()
+}
+
+/// Parses `50%` as a percentage.
+fn parse_pct(text: &str) -> Option<f64> {
+    text.trim().strip_suffix('%')?.trim_end().parse().ok()
 }
+
 impl StylePropValue for Color {
     fn debug_view(&self) -> Option<Box<dyn View>> {
         let color = *self;
@@ -241,6 +325,12 @@ impl StylePropValue for Color {
     fn interpolate(&self, other: &Self, value: f64) -> Option<Self> {
         Some(self.lerp(*other, value as f32, HueDirection::default()))
     }
+
+    fn parse(text: &str) -> Option<Self> {
+        peniko::color::parse_color(text.trim())
+            .ok()
+            .map(|color| color.to_alpha_color())
+    }
 }
 
 impl StylePropValue for Gradient {
@@ -487,6 +577,10 @@ impl StylePropValue for Brush {
             _ => None,
         }
     }
+
+    fn parse(text: &str) -> Option<Self> {
+        Color::parse(text).map(Brush::Solid)
+    }
 }
 impl StylePropValue for Duration {
     fn debug_view(&self) -> Option<Box<dyn View>> {
@@ -573,6 +667,7 @@ pub struct StylePropInfo {
     pub(crate) interpolate: InterpolateFn,
     pub(crate) debug_any: fn(val: &dyn Any) -> String,
     pub(crate) debug_view: fn(val: &dyn Any) -> Option<Box<dyn View>>,
+    pub(crate) parse: fn(text: &str) -> Option<Rc<dyn Any>>,
     pub(crate) transition_key: StyleKey,
 }
 
@@ -640,6 +735,7 @@ impl StylePropInfo {
                     )
                 }
             },
+            parse: |text| T::parse(text).map(|v| Rc::new(StyleMapValue::Val(v)) as Rc<dyn Any>),
             transition_key,
         }
     }
@@ -948,7 +1044,8 @@ impl<T: StylePropValue> TransitionState<T> {
         if !self.initial {
             return;
         }
-        if self.transition.is_some() {
+        // Transitions are skipped when the user prefers reduced motion
+        if self.transition.is_some() && !crate::preferences::prefers_reduced_motion() {
             self.active = Some(ActiveTransition {
                 start: Instant::now(),
                 before: before.clone(),
@@ -1472,11 +1569,53 @@ pub enum TextOverflow {
     Ellipsis,
 }
 
+/// The font sizes a label shrinks its text between to fit its width, see
+/// [`Style::text_auto_shrink`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FontSizeRange {
+    pub min: f32,
+    pub max: f32,
+}
+
+/// The direction views are laid out in horizontally.
+///
+/// In right-to-left layout rows start at the right, the left and right paddings, margins,
+/// borders and insets of views are swapped, and scroll bars are on the left. It's inherited
+/// by child views, and the window follows the direction of the
+/// [current locale](crate::l10n::direction) unless configured otherwise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Direction {
+    #[default]
+    Ltr,
+    Rtl,
+}
+
+/// The cursor shown while the pointer is over a view.
+///
+/// The cursor of the innermost hovered view with a cursor style is shown, so the cursor
+/// of a parent view is restored when the pointer leaves a child view.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum CursorStyle {
     Default,
+    ContextMenu,
+    Help,
     Pointer,
+    Progress,
+    Wait,
+    Cell,
+    Crosshair,
     Text,
+    VerticalText,
+    Alias,
+    Copy,
+    Move,
+    NoDrop,
+    NotAllowed,
+    Grab,
+    Grabbing,
+    AllScroll,
+    ZoomIn,
+    ZoomOut,
     ColResize,
     RowResize,
     WResize,
@@ -1487,8 +1626,18 @@ pub enum CursorStyle {
     NeResize,
     SwResize,
     SeResize,
+    EwResize,
+    NsResize,
     NeswResize,
     NwseResize,
+    /// A cursor made from an image
+    Custom(CustomCursor),
+}
+
+impl From<CustomCursor> for CursorStyle {
+    fn from(cursor: CustomCursor) -> Self {
+        CursorStyle::Custom(cursor)
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -1646,6 +1795,9 @@ define_builtin_props!(
     OutlineColor outline_color: Brush {} = Brush::Solid(palette::css::TRANSPARENT),
     Outline outline nocb: StrokeWrap {} = StrokeWrap::new(0.),
     OutlineProgress outline_progress: Pct {} = Pct(100.),
+    FocusRingColor focus_ring_color: Brush { inherited } = Brush::Solid(Color::from_rgb8(0, 95, 204)),
+    FocusRingWidth focus_ring_width: Px { inherited } = Px(2.),
+    FocusRingOffset focus_ring_offset: Px { inherited } = Px(2.),
     BorderLeftColor border_left_color: Brush {} = Brush::Solid(palette::css::BLACK),
     BorderTopColor border_top_color: Brush {} = Brush::Solid(palette::css::BLACK),
     BorderRightColor border_right_color: Brush {} = Brush::Solid(palette::css::BLACK),
@@ -1687,6 +1839,19 @@ define_builtin_props!(
     TranslateX translate_x: PxPct {} = PxPct::Px(0.),
     TranslateY translate_y: PxPct {} = PxPct::Px(0.),
     Rotation rotate: Px {} = Px(0.),
+    DirectionProp direction: Direction { inherited } = Direction::Ltr,
+);
+
+prop!(
+    /// Mirrors the view horizontally in right-to-left layout, for icons pointing in the
+    /// reading direction like arrows
+    pub MirrorInRtl: bool {} = false
+);
+
+prop!(
+    /// Shrinks the font size of the text of a label within the range to fit its width, before
+    /// the text overflows
+    pub TextAutoShrink: Option<FontSizeRange> {} = None
 );
 
 prop!(
@@ -1755,6 +1920,7 @@ prop_extractor! {
 
         pub rotation: Rotation,
 
+        pub direction: DirectionProp,
     }
 }
 
@@ -1788,6 +1954,7 @@ impl LayoutProps {
             .margin_bottom(self.margin_bottom())
             .col_gap(self.col_gap())
             .row_gap(self.row_gap())
+            .direction(self.direction())
     }
 }
 
@@ -2255,6 +2422,22 @@ impl Style {
         self.pointer_events(PointerEvents::None)
     }
 
+    /// Lays out the view and its children right-to-left.
+    pub fn rtl(self) -> Self {
+        self.direction(Direction::Rtl)
+    }
+
+    /// Lays out the view and its children left-to-right, like views in a right-to-left
+    /// window which show code or numbers.
+    pub fn ltr(self) -> Self {
+        self.direction(Direction::Ltr)
+    }
+
+    /// Mirrors the view in right-to-left layout, see [`MirrorInRtl`].
+    pub fn mirror_in_rtl(self) -> Self {
+        self.set(MirrorInRtl, true)
+    }
+
     pub fn text_ellipsis(self) -> Self {
         self.text_overflow(TextOverflow::Ellipsis)
     }
@@ -2263,6 +2446,19 @@ impl Style {
         self.text_overflow(TextOverflow::Clip)
     }
 
+    /// Shows the text of a label at the font size `max`, shrinking it down to `min` when the
+    /// text is wider than the label, like for badges and stat cards. Text which is still too
+    /// wide at `min` overflows as set by [`text_overflow`](Self::text_overflow).
+    pub fn text_auto_shrink(self, min: f32, max: f32) -> Self {
+        self.set(
+            TextAutoShrink,
+            Some(FontSizeRange {
+                min: min.min(max),
+                max,
+            }),
+        )
+    }
+
     pub fn absolute(self) -> Self {
         self.position(taffy::style::Position::Absolute)
     }
@@ -2387,6 +2583,53 @@ impl Style {
 }
 
 impl Style {
+    fn swap_props<A: StyleProp, B: StyleProp<Type = A::Type>>(&mut self) {
+        let a = self.map.remove(&A::key());
+        let b = self.map.remove(&B::key());
+        if let Some(a) = a {
+            self.map.insert(B::key(), a);
+        }
+        if let Some(b) = b {
+            self.map.insert(A::key(), b);
+        }
+    }
+
+    /// Mirrors the horizontal properties of the style for right-to-left layout.
+    pub(crate) fn mirror_horizontally(&mut self) {
+        self.swap_props::<PaddingLeft, PaddingRight>();
+        self.swap_props::<MarginLeft, MarginRight>();
+        self.swap_props::<InsetLeft, InsetRight>();
+        self.swap_props::<BorderLeft, BorderRight>();
+        self.swap_props::<BorderLeftColor, BorderRightColor>();
+
+        let flex_direction = self.get(FlexDirectionProp);
+        let mirrored = match flex_direction {
+            FlexDirection::Row => FlexDirection::RowReverse,
+            FlexDirection::RowReverse => FlexDirection::Row,
+            FlexDirection::Column | FlexDirection::ColumnReverse => {
+                // The cross axis of columns is horizontal
+                let mirror_align = |align| match align {
+                    AlignItems::Start => AlignItems::End,
+                    AlignItems::End => AlignItems::Start,
+                    AlignItems::FlexStart => AlignItems::FlexEnd,
+                    AlignItems::FlexEnd => AlignItems::FlexStart,
+                    align => align,
+                };
+                if let Some(align) = self.get(AlignItemsProp) {
+                    self.map.insert(
+                        AlignItemsProp::key(),
+                        Rc::new(StyleMapValue::Val(Some(mirror_align(align)))),
+                    );
+                }
+                flex_direction
+            }
+        };
+        self.map.insert(
+            FlexDirectionProp::key(),
+            Rc::new(StyleMapValue::Val(mirrored)),
+        );
+    }
+
     pub fn to_taffy_style(&self) -> TaffyStyle {
         let style = self.builtin();
         TaffyStyle {
@@ -2485,11 +2728,15 @@ pub trait CustomStylable<S: Default + Into<Style> + 'static>:
 
 #[cfg(test)]
 mod tests {
-    use super::{Style, StyleValue};
+    use super::{Style, StylePropValue, StyleValue};
     use crate::{
-        style::{PaddingBottom, PaddingLeft},
-        unit::PxPct,
+        style::{
+            AlignItemsProp, FlexDirectionProp, MarginLeft, PaddingBottom, PaddingLeft, PaddingRight,
+        },
+        unit::{PxPct, PxPctAuto},
     };
+    use peniko::{color::palette, Color};
+    use taffy::style::{AlignItems, FlexDirection};
 
     #[test]
     fn style_override() {
@@ -2564,4 +2811,38 @@ mod tests {
             StyleValue::Val(PxPct::Px(100.0))
         );
     }
+
+    #[test]
+    fn parse_values() {
+        assert_eq!(PxPct::parse("12"), Some(PxPct::Px(12.0)));
+        assert_eq!(PxPct::parse(" 12px "), Some(PxPct::Px(12.0)));
+        assert_eq!(PxPct::parse("50%"), Some(PxPct::Pct(50.0)));
+        assert_eq!(PxPctAuto::parse("auto"), Some(PxPctAuto::Auto));
+        assert_eq!(
+            <Color as StylePropValue>::parse("red"),
+            Some(palette::css::RED)
+        );
+        assert_eq!(Option::<f64>::parse("none"), Some(None));
+        assert_eq!(f64::parse("wide"), None);
+    }
+
+    #[test]
+    fn mirror_horizontally() {
+        let mut style = Style::new()
+            .padding_left(4.0)
+            .margin_right(8.0)
+            .align_items(AlignItems::FlexStart);
+        style.mirror_horizontally();
+        assert_eq!(style.get(PaddingLeft), PxPct::Px(0.0));
+        assert_eq!(style.get(PaddingRight), PxPct::Px(4.0));
+        assert_eq!(style.get(MarginLeft), PxPctAuto::Px(8.0));
+        assert_eq!(style.get(FlexDirectionProp), FlexDirection::RowReverse);
+        // Rows keep their vertical alignment
+        assert_eq!(style.get(AlignItemsProp), Some(AlignItems::FlexStart));
+
+        let mut style = Style::new().flex_col().align_items(AlignItems::FlexStart);
+        style.mirror_horizontally();
+        assert_eq!(style.get(FlexDirectionProp), FlexDirection::Column);
+        assert_eq!(style.get(AlignItemsProp), Some(AlignItems::FlexEnd));
+    }
 }
@@ -0,0 +1,622 @@
+//! Mounting views without a window, to test them.
+//!
+//! A [`TestHarness`] builds a view tree like a window would, and runs style, layout and
+//! paint for it without a real window or renderer. Tests drive it with synthetic pointer
+//! and IME events, advance the clock of timers, and inspect the resulting layout.
+//!
+//! ```no_run
+//! use floem::{
+//!     kurbo::Size, reactive::{create_rw_signal, SignalGet, SignalUpdate},
+//!     testing::TestHarness, views::{empty, Decorators},
+//! };
+//!
+//! let clicks = create_rw_signal(0);
+//! let mut harness = TestHarness::new(move || {
+//!     empty()
+//!         .style(|s| s.size(100.0, 50.0))
+//!         .on_click_stop(move |_| clicks.update(|clicks| *clicks += 1))
+//! });
+//! harness.click_view(harness.root());
+//! assert_eq!(clicks.get_untracked(), 1);
+//! assert_eq!(harness.layout_rect(harness.root()).size(), Size::new(100.0, 50.0));
+//! ```
+//!
+//! winit doesn't allow creating key events, so text is typed as IME commits with
+//! [`TestHarness::type_text`], which text inputs and the editor handle like typed text.
+//!
+//! # Snapshot tests
+//!
+//! [`TestHarness::render`] and [`render_to_image`] paint the views with the software
+//! renderer into an image, which is the same on every machine as long as the same fonts
+//! are installed. [`assert_snapshot`] compares it with a golden image stored next to the
+//! tests, to catch visual regressions in styles, text and the paint code of views.
+
+use std::collections::HashMap;
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::{Duration, Instant};
+#[cfg(target_arch = "wasm32")]
+use web_time::{Duration, Instant};
+
+use floem_renderer::display_list::DisplayList;
+use peniko::kurbo::{Point, Rect, Size, Vec2};
+use winit::event::Ime;
+
+use crate::{
+    action::{Timer, TimerToken},
+    app::{AppUpdateEvent, APP_UPDATE_EVENTS},
+    event::Event,
+    id::ViewId,
+    keyboard::Modifiers,
+    offscreen::OffscreenView,
+    pointer::{MouseButton, PenState, PointerButton, PointerInputEvent, PointerWheelEvent},
+    view::IntoView,
+};
+
+/// A view tree mounted without a window, see the [module documentation](self).
+pub struct TestHarness {
+    view: OffscreenView,
+    timers: HashMap<TimerToken, Timer>,
+    /// How far the clock of the timers was advanced
+    elapsed: Duration,
+}
+
+impl TestHarness {
+    /// Mounts the view in a window of 800x600.
+    pub fn new<V: IntoView + 'static>(view_fn: impl FnOnce() -> V + 'static) -> Self {
+        Self::new_with_size(view_fn, Size::new(800.0, 600.0))
+    }
+
+    /// Mounts the view in a window of the given size.
+    pub fn new_with_size<V: IntoView + 'static>(
+        view_fn: impl FnOnce() -> V + 'static,
+        size: Size,
+    ) -> Self {
+        Self::new_with_scale(view_fn, size, 1.0)
+    }
+
+    /// Mounts the view in a window of the given size, with the scale factor of a monitor,
+    /// which changes the size of rendered images.
+    pub fn new_with_scale<V: IntoView + 'static>(
+        view_fn: impl FnOnce() -> V + 'static,
+        size: Size,
+        scale: f64,
+    ) -> Self {
+        let mut harness = Self {
+            view: OffscreenView::new(view_fn, size, scale),
+            timers: HashMap::new(),
+            elapsed: Duration::ZERO,
+        };
+        harness.update();
+        harness
+    }
+
+    /// The id of the mounted view.
+    pub fn root(&self) -> ViewId {
+        self.view.root()
+    }
+
+    /// Resizes the window.
+    pub fn resize(&mut self, size: Size) {
+        self.view.handle.size(size);
+        self.update();
+    }
+
+    /// Runs the pending effects, timers which are due and idle effects, and then style,
+    /// layout and paint like a frame would.
+    pub fn update(&mut self) {
+        loop {
+            self.handle_app_update_events();
+            let ran_idle_effects = floem_reactive::has_idle_effects();
+            floem_reactive::run_idle_effects(|| false);
+            self.view.handle.render_frame();
+            if !ran_idle_effects && APP_UPDATE_EVENTS.with(|events| events.borrow().is_empty()) {
+                break;
+            }
+        }
+    }
+
+    /// Advances the clock of the timers, running the ones which become due in the order
+    /// of their deadline, e.g. for [`exec_after`](crate::action::exec_after), debouncing
+    /// or animations driven by timers.
+    pub fn advance_time(&mut self, duration: Duration) {
+        self.elapsed += duration;
+        loop {
+            self.handle_app_update_events();
+            let now = Instant::now() + self.elapsed;
+            let due = self
+                .timers
+                .values()
+                .filter(|timer| timer.deadline <= now)
+                .min_by_key(|timer| timer.deadline)
+                .map(|timer| timer.token);
+            let Some(timer) = due.and_then(|token| self.timers.remove(&token)) else {
+                break;
+            };
+            (timer.action)(timer.token);
+            self.view.handle.process_update();
+        }
+        self.update();
+    }
+
+    /// Dispatches an event to the views, like the window would.
+    pub fn event(&mut self, event: Event) {
+        self.view.handle.event(event);
+        self.update();
+    }
+
+    /// Sets the modifiers of the following pointer events.
+    pub fn set_modifiers(&mut self, modifiers: Modifiers) {
+        self.view.handle.modifiers = modifiers;
+    }
+
+    /// Sets the pen state of the following pointer events, to test drawing views with a pen
+    /// rather than a mouse.
+    pub fn set_pen(&mut self, pen: Option<PenState>) {
+        self.view.handle.pen = pen;
+    }
+
+    /// Moves the pointer to `pos`, in window coordinates.
+    pub fn pointer_move(&mut self, pos: Point) {
+        self.view.handle.pointer_move(pos);
+        self.update();
+    }
+
+    /// Moves the pointer to `pos` and presses the button.
+    pub fn pointer_down(&mut self, pos: Point, button: MouseButton) {
+        self.pointer_move(pos);
+        let event = self.pointer_input_event(button, 1);
+        self.event(Event::PointerDown(event));
+    }
+
+    /// Moves the pointer to `pos` and releases the button.
+    pub fn pointer_up(&mut self, pos: Point, button: MouseButton) {
+        self.pointer_move(pos);
+        let event = self.pointer_input_event(button, 0);
+        self.event(Event::PointerUp(event));
+    }
+
+    /// Clicks the primary button at `pos`.
+    pub fn click(&mut self, pos: Point) {
+        self.pointer_down(pos, MouseButton::Primary);
+        self.pointer_up(pos, MouseButton::Primary);
+    }
+
+    /// Clicks the primary button at the center of the view.
+    pub fn click_view(&mut self, id: ViewId) {
+        self.click(self.layout_rect(id).center());
+    }
+
+    /// Scrolls by `delta` at `pos`.
+    pub fn scroll(&mut self, pos: Point, delta: Vec2) {
+        self.pointer_move(pos);
+        let event = PointerWheelEvent {
+            pos,
+            delta,
+            modifiers: self.view.handle.modifiers,
+        };
+        self.event(Event::PointerWheel(event));
+    }
+
+    /// Types the text into the focused view, as an IME commit.
+    pub fn type_text(&mut self, text: &str) {
+        self.view.handle.ime(Ime::Commit(text.to_string()));
+        self.update();
+    }
+
+    /// Shows the text being composed by the IME in the focused view.
+    pub fn ime_preedit(&mut self, text: &str, cursor: Option<(usize, usize)>) {
+        self.view.handle.ime(Ime::Preedit(text.to_string(), cursor));
+        self.update();
+    }
+
+    /// Focuses the view, like clicking it would.
+    pub fn focus(&mut self, id: ViewId) {
+        id.request_focus();
+        self.update();
+    }
+
+    /// The focused view.
+    pub fn focused(&self) -> Option<ViewId> {
+        self.view.handle.app_state.focus
+    }
+
+    /// Whether the IME is enabled on the window, by the focused view or by
+    /// [`set_ime_allowed`](crate::action::set_ime_allowed).
+    pub fn ime_allowed(&self) -> bool {
+        self.view.handle.ime_allowed
+    }
+
+    /// Whether the pointer is over the view.
+    pub fn is_hovered(&self, id: ViewId) -> bool {
+        self.view.handle.app_state.is_hovered(&id)
+    }
+
+    /// Whether the view is pressed.
+    pub fn is_active(&self, id: ViewId) -> bool {
+        self.view.handle.app_state.is_active(&id)
+    }
+
+    /// The rectangle of the view in window coordinates.
+    pub fn layout_rect(&self, id: ViewId) -> Rect {
+        id.layout_rect()
+    }
+
+    /// The debug name of the view, which includes the text of labels and text inputs.
+    pub fn debug_name(&self, id: ViewId) -> String {
+        id.view().borrow().debug_name().into_owned()
+    }
+
+    /// The first view below `root` in depth-first order whose debug name contains
+    /// `text`, e.g. to find the label showing a text.
+    pub fn find(&self, root: ViewId, text: &str) -> Option<ViewId> {
+        if self.debug_name(root).contains(text) {
+            return Some(root);
+        }
+        root.children()
+            .into_iter()
+            .find_map(|child| self.find(child, text))
+    }
+
+    /// The topmost visible view under `pos`, in window coordinates.
+    pub fn view_at(&self, pos: Point) -> Option<ViewId> {
+        self.root().view_at(pos)
+    }
+
+    /// Paints the views into an image of the window size times the scale.
+    pub fn render(&mut self) -> image::RgbaImage {
+        self.update();
+        self.view.render()
+    }
+
+    /// Paints a view and its children into an image of the view size times `scale`, like
+    /// [`ViewId::capture_image`].
+    pub fn capture_image(&mut self, id: ViewId, scale: f64) -> image::RgbaImage {
+        self.update();
+        self.view
+            .handle
+            .capture_image(id, scale)
+            .expect("the view is laid out")
+    }
+
+    /// Paints the part `rect` of a view and its children, in the coordinates of the view,
+    /// into an image of the size of `rect` times `scale`.
+    pub fn capture_image_rect(&mut self, id: ViewId, rect: Rect, scale: f64) -> image::RgbaImage {
+        self.update();
+        self.view.capture_image_rect(id, rect, scale)
+    }
+
+    /// Records the draw commands of a view and its children, like
+    /// [`ViewId::record_display_list`].
+    pub fn record_display_list(&mut self, id: ViewId) -> DisplayList {
+        self.update();
+        self.view.record_display_list(id)
+    }
+
+    fn pointer_input_event(&self, button: MouseButton, count: u8) -> PointerInputEvent {
+        PointerInputEvent {
+            pos: self.view.handle.cursor_position,
+            button: PointerButton::Mouse(button),
+            modifiers: self.view.handle.modifiers,
+            count,
+            pen: self.view.handle.pen,
+        }
+    }
+
+    /// Keeps the timers, as there is no event loop to run them.
+    fn handle_app_update_events(&mut self) {
+        let events = APP_UPDATE_EVENTS.with(|events| std::mem::take(&mut *events.borrow_mut()));
+        for event in events {
+            match event {
+                AppUpdateEvent::RequestTimer { timer } => {
+                    self.timers.insert(timer.token, timer);
+                }
+                AppUpdateEvent::CancelTimer { timer } => {
+                    self.timers.remove(&timer);
+                }
+                // Events for the application and other windows have no effect in tests
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Paints the view into an image of `size` times `scale`, without a window.
+pub fn render_to_image<V: IntoView + 'static>(
+    view_fn: impl FnOnce() -> V + 'static,
+    size: Size,
+    scale: f64,
+) -> image::RgbaImage {
+    TestHarness::new_with_scale(view_fn, size, scale).render()
+}
+
+/// Compares the image with the golden image at `path`, which is a PNG file.
+///
+/// The golden image is written if it doesn't exist yet, or if the `FLOEM_UPDATE_SNAPSHOTS`
+/// environment variable is set, to accept intended changes. Channels may differ by a
+/// little for anti-aliasing. On a mismatch the image is written next to the golden image
+/// with an `.actual.png` extension, to look at the difference.
+#[cfg(feature = "image-png")]
+#[track_caller]
+pub fn assert_snapshot(image: &image::RgbaImage, path: impl AsRef<std::path::Path>) {
+    const TOLERANCE: u8 = 2;
+
+    let path = path.as_ref();
+    if !path.exists() || std::env::var_os("FLOEM_UPDATE_SNAPSHOTS").is_some() {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).expect("failed to create the snapshot directory");
+        }
+        image.save(path).expect("failed to write the snapshot");
+        return;
+    }
+
+    let golden = image::open(path)
+        .unwrap_or_else(|err| panic!("failed to read the snapshot {}: {err}", path.display()))
+        .to_rgba8();
+    let mismatch = if golden.dimensions() != image.dimensions() {
+        Some(format!(
+            "the size is {:?} instead of {:?}",
+            image.dimensions(),
+            golden.dimensions()
+        ))
+    } else {
+        let different = golden
+            .pixels()
+            .zip(image.pixels())
+            .filter(|(golden, actual)| {
+                golden
+                    .0
+                    .iter()
+                    .zip(actual.0.iter())
+                    .any(|(golden, actual)| golden.abs_diff(*actual) > TOLERANCE)
+            })
+            .count();
+        (different > 0).then(|| format!("{different} pixels are different"))
+    };
+    if let Some(mismatch) = mismatch {
+        let actual = path.with_extension("actual.png");
+        let _ = image.save(&actual);
+        panic!(
+            "the rendering doesn't match the snapshot {}: {mismatch}, see {}",
+            path.display(),
+            actual.display()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use floem_reactive::{create_rw_signal, SignalGet, SignalUpdate};
+
+    use floem_renderer::{display_list::DrawCommand, Renderer};
+    use peniko::kurbo::Shape;
+
+    use super::*;
+    use crate::{
+        action::exec_after,
+        view::View,
+        views::{empty, stack, Decorators},
+    };
+
+    #[test]
+    fn click_and_layout() {
+        let clicks = create_rw_signal(0);
+        let mut harness = TestHarness::new(move || {
+            empty()
+                .style(|s| s.size(100.0, 50.0))
+                .on_click_stop(move |_| clicks.update(|clicks| *clicks += 1))
+        });
+        let root = harness.root();
+        assert_eq!(harness.layout_rect(root).size(), Size::new(100.0, 50.0));
+        assert_eq!(harness.view_at(Point::new(10.0, 10.0)), Some(root));
+
+        harness.click_view(root);
+        assert_eq!(clicks.get_untracked(), 1);
+        harness.click(Point::new(300.0, 300.0));
+        assert_eq!(clicks.get_untracked(), 1);
+    }
+
+    #[test]
+    fn pen() {
+        let pressure = create_rw_signal(None);
+        let mut harness = TestHarness::new(move || {
+            empty().style(|s| s.size(100.0, 50.0)).on_event_stop(
+                crate::event::EventListener::PointerDown,
+                move |event| {
+                    if let Event::PointerDown(event) = event {
+                        pressure.set(event.pen.map(|pen| pen.pressure));
+                    }
+                },
+            )
+        });
+        harness.click(Point::new(10.0, 10.0));
+        assert_eq!(pressure.get_untracked(), None);
+
+        harness.set_pen(Some(PenState {
+            pressure: 0.25,
+            ..Default::default()
+        }));
+        harness.click(Point::new(10.0, 10.0));
+        assert_eq!(pressure.get_untracked(), Some(0.25));
+    }
+
+    #[test]
+    fn gamepad() {
+        use crate::gamepad::{GamepadButton, GamepadEvent, GamepadId};
+
+        let clicks = create_rw_signal(0);
+        let first = empty().style(|s| s.size(50.0, 50.0)).keyboard_navigable();
+        let second = empty()
+            .style(|s| s.size(50.0, 50.0))
+            .keyboard_navigable()
+            .on_click_stop(move |_| clicks.update(|clicks| *clicks += 1));
+        let (first_id, second_id) = (first.id(), second.id());
+        let mut harness = TestHarness::new(move || stack((first, second)));
+        harness.focus(first_id);
+
+        let mut press = |button| {
+            harness.event(Event::Gamepad(GamepadEvent::ButtonDown {
+                gamepad: GamepadId(0),
+                button,
+            }))
+        };
+        press(GamepadButton::DPadRight);
+        press(GamepadButton::South);
+        assert_eq!(harness.focused(), Some(second_id));
+        assert_eq!(clicks.get_untracked(), 1);
+    }
+
+    #[test]
+    fn ime_allowed_on_focus() {
+        let input = empty()
+            .style(|s| s.size(50.0, 50.0))
+            .keyboard_navigable()
+            .on_event_cont(crate::event::EventListener::FocusGained, |_| {
+                crate::action::set_ime_allowed(true)
+            });
+        let other = empty().style(|s| s.size(50.0, 50.0)).keyboard_navigable();
+        let (input_id, other_id) = (input.id(), other.id());
+        let mut harness = TestHarness::new(move || stack((input, other)));
+        assert!(!harness.ime_allowed());
+
+        // The IME set by the focused view isn't overwritten by the settings of the view
+        harness.focus(input_id);
+        assert!(harness.ime_allowed());
+        // Until the focus changes
+        harness.focus(other_id);
+        assert!(!harness.ime_allowed());
+    }
+
+    #[test]
+    fn render() {
+        let image = render_to_image(
+            || {
+                empty().style(|s| {
+                    s.size(10.0, 10.0)
+                        .background(peniko::color::palette::css::RED)
+                })
+            },
+            Size::new(20.0, 20.0),
+            2.0,
+        );
+        assert_eq!(image.dimensions(), (40, 40));
+        assert_eq!(image.get_pixel(5, 5).0, [255, 0, 0, 255]);
+        assert_eq!(image.get_pixel(30, 30).0, [255, 255, 255, 255]);
+    }
+
+    #[test]
+    fn paint_hooks() {
+        let image = render_to_image(
+            || {
+                empty()
+                    .style(|s| s.size(20.0, 20.0))
+                    .on_paint_under(|cx, rect| {
+                        cx.fill(&rect, peniko::color::palette::css::BLUE, 0.0);
+                    })
+                    .on_paint_over(|cx, rect| {
+                        cx.fill(
+                            &rect.with_size((10.0, 10.0)),
+                            peniko::color::palette::css::RED,
+                            0.0,
+                        );
+                    })
+            },
+            Size::new(20.0, 20.0),
+            1.0,
+        );
+        assert_eq!(image.get_pixel(5, 5).0, [255, 0, 0, 255]);
+        assert_eq!(image.get_pixel(15, 15).0, [0, 0, 255, 255]);
+    }
+
+    #[test]
+    fn capture_image() {
+        let mut harness = TestHarness::new(|| {
+            stack((
+                empty().style(|s| s.size(10.0, 10.0)),
+                empty().style(|s| {
+                    s.size(20.0, 10.0)
+                        .background(peniko::color::palette::css::RED)
+                }),
+            ))
+        });
+        let child = harness.root().children()[1];
+        let image = harness.capture_image(child, 2.0);
+        assert_eq!(image.dimensions(), (40, 20));
+        assert_eq!(image.get_pixel(1, 1).0, [255, 0, 0, 255]);
+        assert_eq!(image.get_pixel(39, 19).0, [255, 0, 0, 255]);
+
+        let captured = create_rw_signal(None);
+        child.capture_image(1.0, move |image| captured.set(Some(image.dimensions())));
+        harness.update();
+        assert_eq!(captured.get_untracked(), Some((20, 10)));
+    }
+
+    #[test]
+    fn record_display_list() {
+        let mut harness = TestHarness::new(|| {
+            stack((
+                empty().style(|s| s.size(10.0, 10.0)),
+                empty().style(|s| {
+                    s.size(20.0, 10.0)
+                        .background(peniko::color::palette::css::RED)
+                }),
+            ))
+        });
+        let child = harness.root().children()[1];
+        let display_list = harness.record_display_list(child);
+        assert_eq!(display_list.size, Size::new(20.0, 10.0));
+        let fill = display_list
+            .commands
+            .iter()
+            .find_map(|command| match command {
+                DrawCommand::Fill { path, brush, .. } => Some((path.bounding_box(), brush.clone())),
+                _ => None,
+            });
+        assert_eq!(
+            fill,
+            Some((
+                Rect::new(0.0, 0.0, 20.0, 10.0),
+                peniko::Brush::Solid(peniko::color::palette::css::RED)
+            ))
+        );
+
+        let mut replayed = DisplayList::default();
+        display_list.replay(&mut replayed);
+        assert_eq!(replayed.commands.len(), display_list.commands.len());
+    }
+
+    #[test]
+    fn paint_damage() {
+        let mut harness = TestHarness::new(|| {
+            stack((
+                empty().style(|s| {
+                    s.size(10.0, 10.0)
+                        .background(peniko::color::palette::css::RED)
+                }),
+                empty().style(|s| s.size(10.0, 10.0)),
+            ))
+        });
+        let full = harness.render();
+        let child = harness.root().children()[1];
+        child.request_paint();
+        assert!(harness.view.handle.process_update_no_paint());
+        assert_eq!(
+            harness.view.handle.app_state.damage,
+            Some(harness.layout_rect(child))
+        );
+        assert_eq!(harness.render(), full);
+    }
+
+    #[test]
+    fn advance_time() {
+        let fired = create_rw_signal(false);
+        let mut harness = TestHarness::new(move || {
+            exec_after(Duration::from_secs(1), move |_| fired.set(true));
+            empty()
+        });
+        harness.advance_time(Duration::from_millis(500));
+        assert!(!fired.get_untracked());
+        harness.advance_time(Duration::from_millis(600));
+        assert!(fired.get_untracked());
+    }
+}
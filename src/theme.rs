@@ -4,10 +4,15 @@ use crate::{
     views::{
         dropdown, scroll,
         slider::{self, SliderClass},
-        ButtonClass, CheckboxClass, LabelClass, LabelCustomStyle, LabeledCheckboxClass,
-        LabeledRadioButtonClass, ListClass, ListItemClass, PlaceholderTextClass, RadioButtonClass,
-        RadioButtonDotClass, TextInputClass, ToggleButtonCircleRad, ToggleButtonClass,
-        ToggleButtonInset, TooltipClass,
+        ButtonClass, ButtonGroupClass, CheckboxClass, CommandPaletteClass, CommandPaletteItemClass,
+        CommandPaletteSelectedClass, LabelClass, LabelCustomStyle, LabeledCheckboxClass,
+        LabeledRadioButtonClass, ListClass, ListItemClass, PillSwitchActiveClass, PillSwitchClass,
+        PillSwitchOptionClass, PlaceholderTextClass, RadioButtonClass, RadioButtonDotClass,
+        RatingClass, RatingStarClass, RatingStarFilledClass, SegmentClass, SegmentSelectedClass,
+        SkeletonClass, StatusBarClass, StatusBarItemClass, StepperButtonClass, StepperClass,
+        TextInputClass, ToggleButtonCircleRad, ToggleButtonClass, ToggleButtonInset,
+        ToolbarButtonClass, ToolbarCheckedClass, ToolbarClass, ToolbarGroupClass,
+        ToolbarSeparatorClass, TooltipClass, WindowCloseButtonClass, WindowControlButtonClass,
     },
 };
 use peniko::color::palette;
@@ -20,37 +25,109 @@ pub(crate) struct Theme {
     pub(crate) style: Rc<Style>,
 }
 
+/// The colors and widths shared by the built-in widgets in the default theme.
+struct ThemeTokens {
+    background: Color,
+    text: Color,
+    control_background: Color,
+    border: Color,
+    border_width: f64,
+    focus: Color,
+    hover: Color,
+    focus_hover: Color,
+    active: Color,
+    selected: Color,
+    selected_hover: Color,
+    selected_unfocused: Color,
+    selected_unfocused_hover: Color,
+    light_hover: Color,
+    light_focus_hover: Color,
+    placeholder: Color,
+}
+
+impl ThemeTokens {
+    fn light() -> Self {
+        Self {
+            background: Color::from_rgb8(248, 248, 248),
+            text: palette::css::BLACK,
+            control_background: Color::from_rgb8(240, 240, 240),
+            border: Color::from_rgb8(140, 140, 140),
+            border_width: 1.0,
+            focus: Color::from_rgb8(114, 74, 140),
+            hover: Color::from_rgba8(228, 237, 216, 160),
+            focus_hover: Color::from_rgb8(234, 230, 236),
+            active: Color::from_rgb8(160, 160, 160),
+            selected: Color::from_rgb8(213, 208, 216),
+            selected_hover: Color::from_rgb8(186, 180, 216),
+            selected_unfocused: Color::from_rgb8(212, 212, 212),
+            selected_unfocused_hover: Color::from_rgb8(197, 197, 197),
+            light_hover: Color::from_rgb8(250, 252, 248),
+            light_focus_hover: Color::from_rgb8(250, 249, 251),
+            placeholder: Color::from_rgba8(158, 158, 158, 30),
+        }
+    }
+
+    /// Black on white, with thicker borders, and opaque hover, selection and focus colors
+    /// which stand out from the background.
+    fn high_contrast() -> Self {
+        Self {
+            background: palette::css::WHITE,
+            text: palette::css::BLACK,
+            control_background: palette::css::WHITE,
+            border: palette::css::BLACK,
+            border_width: 2.0,
+            focus: Color::from_rgb8(0, 0, 200),
+            hover: Color::from_rgb8(220, 220, 220),
+            focus_hover: Color::from_rgb8(200, 200, 200),
+            active: palette::css::BLACK,
+            selected: Color::from_rgb8(255, 214, 0),
+            selected_hover: Color::from_rgb8(255, 190, 0),
+            selected_unfocused: Color::from_rgb8(200, 200, 200),
+            selected_unfocused_hover: Color::from_rgb8(180, 180, 180),
+            light_hover: Color::from_rgb8(240, 240, 240),
+            light_focus_hover: Color::from_rgb8(235, 235, 235),
+            placeholder: Color::from_rgb8(90, 90, 90),
+        }
+    }
+}
+
+/// The default theme, with high-contrast colors while the user
+/// [prefers them](crate::preferences::high_contrast).
 pub(crate) fn default_theme() -> Theme {
-    let border = Color::from_rgb8(140, 140, 140);
+    let tokens = if crate::preferences::prefers_high_contrast() {
+        ThemeTokens::high_contrast()
+    } else {
+        ThemeTokens::light()
+    };
+    theme_with_tokens(&tokens)
+}
+
+fn theme_with_tokens(tokens: &ThemeTokens) -> Theme {
+    let border = tokens.border;
 
     let padding = 5.0;
     let border_radius = 5.0;
 
-    let hover_bg_color = Color::from_rgba8(228, 237, 216, 160);
-    let focus_hover_bg_color = Color::from_rgb8(234, 230, 236);
-    let active_bg_color = Color::from_rgb8(160, 160, 160);
-
-    let selected_bg_color = Color::from_rgb8(213, 208, 216);
-    let selected_hover_bg_color = Color::from_rgb8(186, 180, 216);
+    let hover_bg_color = tokens.hover;
+    let focus_hover_bg_color = tokens.focus_hover;
+    let active_bg_color = tokens.active;
 
-    let selected_unfocused_bg_color = Color::from_rgb8(212, 212, 212);
-    let selected_unfocused_hover_bg_color = Color::from_rgb8(197, 197, 197);
+    let selected_bg_color = tokens.selected;
+    let selected_hover_bg_color = tokens.selected_hover;
 
-    let light_hover_bg_color = Color::from_rgb8(250, 252, 248);
-    let light_focus_hover_bg_color = Color::from_rgb8(250, 249, 251);
+    let selected_unfocused_bg_color = tokens.selected_unfocused;
+    let selected_unfocused_hover_bg_color = tokens.selected_unfocused_hover;
 
-    let focus_applied_style = Style::new().border_color(Color::from_rgb8(114, 74, 140));
+    let light_hover_bg_color = tokens.light_hover;
+    let light_focus_hover_bg_color = tokens.light_focus_hover;
 
-    let focus_visible_applied_style = Style::new().outline(3.0);
+    let focus_applied_style = Style::new().border_color(tokens.focus);
 
-    let focus_style = Style::new()
-        .outline_color(Color::from_rgba8(213, 208, 216, 150))
-        .focus(|_| focus_applied_style.clone())
-        .focus_visible(|_| focus_visible_applied_style.clone());
+    let focus_style = Style::new().focus(|_| focus_applied_style.clone());
 
     let border_style = Style::new()
         .disabled(|s| s.border_color(Color::from_rgb8(131, 145, 123).with_alpha(0.3)))
-        .border(1.0)
+        .border(tokens.border_width)
         .border_color(border)
         .padding(padding)
         .border_radius(border_radius)
@@ -61,7 +138,7 @@ pub(crate) fn default_theme() -> Theme {
         .class(LabelClass, |s| {
             s.apply(LabelCustomStyle::new().selectable(false).style())
         })
-        .background(Color::from_rgb8(240, 240, 240))
+        .background(tokens.control_background)
         .disabled(|s| {
             s.background(Color::from_rgb8(180, 188, 175).with_alpha(0.3))
                 .border_color(Color::from_rgb8(131, 145, 123).with_alpha(0.3))
@@ -175,7 +252,7 @@ pub(crate) fn default_theme() -> Theme {
                 )
         })
         .aspect_ratio(2.)
-        .background(Color::from_rgb8(240, 240, 240))
+        .background(tokens.control_background)
         .border_radius(50.pct())
         .border(1.)
         .focus(|s| s.hover(|s| s.background(focus_hover_bg_color)))
@@ -258,8 +335,7 @@ pub(crate) fn default_theme() -> Theme {
             .width(100)
         })
         .class(PlaceholderTextClass, |s| {
-            s.color(Color::from_rgba8(158, 158, 158, 30))
-                .font_size(FONT_SIZE)
+            s.color(tokens.placeholder).font_size(FONT_SIZE)
         })
         .class(TooltipClass, |s| {
             s.border(0.5)
@@ -274,6 +350,133 @@ pub(crate) fn default_theme() -> Theme {
                 .box_shadow_v_offset(2.0)
                 .box_shadow_color(palette::css::BLACK.with_alpha(0.2))
         })
+        .class(CommandPaletteClass, |s| {
+            s.padding(padding)
+                .row_gap(padding)
+                .apply(border_style.clone())
+                .background(palette::css::WHITE_SMOKE)
+                .box_shadow_blur(4.0)
+                .box_shadow_v_offset(2.0)
+                .box_shadow_color(palette::css::BLACK.with_alpha(0.3))
+                .class(CommandPaletteItemClass, |s| {
+                    s.padding_horiz(padding)
+                        .padding_vert(3.0)
+                        .col_gap(20.0)
+                        .border_radius(border_radius)
+                        .hover(|s| s.background(hover_bg_color))
+                })
+                .class(CommandPaletteSelectedClass, |s| {
+                    s.background(selected_bg_color)
+                        .hover(|s| s.background(selected_hover_bg_color))
+                })
+        })
+        .class(StatusBarClass, |s| {
+            s.padding_horiz(padding)
+                .border_top(1.0)
+                .border_color(border)
+                .background(palette::css::WHITE_SMOKE)
+                .class(StatusBarItemClass, |s| {
+                    s.padding_horiz(padding)
+                        .padding_vert(2.0)
+                        .border_radius(2.0)
+                        .hover(|s| s.background(hover_bg_color))
+                })
+        })
+        .class(ButtonGroupClass, |s| {
+            s.border(1.0)
+                .border_color(border)
+                .border_radius(border_radius)
+                .class(SegmentClass, |s| {
+                    s.padding_horiz(10.0)
+                        .padding_vert(padding)
+                        .border_color(border)
+                        .hover(|s| s.background(hover_bg_color))
+                        .focus_visible(|s| s.background(focus_hover_bg_color))
+                })
+                .class(SegmentSelectedClass, |s| {
+                    s.background(selected_bg_color)
+                        .hover(|s| s.background(selected_hover_bg_color))
+                })
+        })
+        .class(RatingClass, |s| {
+            s.col_gap(2.0)
+                .apply(focus_style.clone())
+                .border_radius(border_radius)
+                .class(RatingStarClass, |s| {
+                    s.font_size(18.0)
+                        .color(Color::from_rgb8(200, 200, 200))
+                        .cursor(CursorStyle::Pointer)
+                })
+                .class(RatingStarFilledClass, |s| {
+                    s.color(Color::from_rgb8(240, 180, 20))
+                })
+        })
+        .class(StepperClass, |s| {
+            s.class(StepperButtonClass, |s| s.width(28.0).padding_horiz(0.0))
+        })
+        .class(PillSwitchClass, |s| {
+            s.padding(2.0)
+                .border(1.0)
+                .border_color(border)
+                .border_radius(100.pct())
+                .background(tokens.control_background)
+                .cursor(CursorStyle::Pointer)
+                .apply(focus_style.clone())
+                .class(PillSwitchOptionClass, |s| {
+                    s.padding_horiz(10.0)
+                        .padding_vert(3.0)
+                        .border_radius(100.pct())
+                        .color(palette::css::GRAY)
+                        .apply(LabelCustomStyle::new().selectable(false).style())
+                })
+                .class(PillSwitchActiveClass, |s| {
+                    s.background(palette::css::WHITE)
+                        .color(Color::from_rgb8(40, 40, 40))
+                        .box_shadow_blur(2.0)
+                        .box_shadow_color(palette::css::BLACK.with_alpha(0.2))
+                })
+        })
+        .class(SkeletonClass, |s| {
+            s.background(Color::from_rgb8(224, 224, 224))
+        })
+        .class(ToolbarClass, |s| {
+            s.padding(3.0)
+                .col_gap(2.0)
+                .class(ToolbarButtonClass, |s| {
+                    s.padding(padding)
+                        .border_radius(border_radius)
+                        .items_center()
+                        .justify_center()
+                        .hover(|s| s.background(hover_bg_color))
+                        .active(|s| s.background(active_bg_color))
+                        .apply(focus_style.clone())
+                })
+                .class(ToolbarCheckedClass, |s| {
+                    s.background(selected_bg_color)
+                        .hover(|s| s.background(selected_hover_bg_color))
+                })
+                .class(ToolbarGroupClass, |s| s.col_gap(2.0))
+                .class(ToolbarSeparatorClass, |s| {
+                    s.width(1.0)
+                        .height(16.0)
+                        .margin_horiz(3.0)
+                        .background(border)
+                })
+        })
+        .class(WindowControlButtonClass, |s| {
+            s.hover(|s| s.background(Color::from_rgba8(0, 0, 0, 25)))
+                .active(|s| s.background(Color::from_rgba8(0, 0, 0, 50)))
+        })
+        .class(WindowCloseButtonClass, |s| {
+            s.hover(|s| {
+                s.background(Color::from_rgb8(196, 43, 28))
+                    .color(palette::css::WHITE)
+            })
+            .active(|s| {
+                s.background(Color::from_rgb8(200, 80, 70))
+                    .color(palette::css::WHITE)
+            })
+        })
         .class(dropdown::DropdownClass, |s| {
             s.width(75)
                 .padding(3)
@@ -295,10 +498,11 @@ pub(crate) fn default_theme() -> Theme {
                 })
         })
         .font_size(FONT_SIZE)
-        .color(palette::css::BLACK);
+        .color(tokens.text)
+        .focus_ring_color(tokens.focus);
 
     Theme {
-        background: Color::from_rgb8(248, 248, 248),
+        background: tokens.background,
         style: Rc::new(theme),
     }
 }
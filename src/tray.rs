@@ -0,0 +1,310 @@
+#![deny(missing_docs)]
+
+//! System tray icon support.
+//!
+//! An application can show a single icon in the system tray (the notification area on
+//! Windows, the status bar on macOS), with a tooltip and a [`Menu`] whose item actions
+//! run on the UI thread like any other menu action.
+//!
+//! Combined with [`WindowConfig::hide_on_close`](crate::window::WindowConfig::hide_on_close),
+//! this can be used to build long-running utilities which keep running in the tray
+//! after their window is closed.
+//!
+//! Platform support:
+//! - Windows: Yes
+//! - macOS: Yes
+//! - Linux: No, the functions in this module have no effect
+
+use crate::{
+    app::{add_app_update_event, AppUpdateEvent},
+    app_state::collect_menu_actions,
+    menu::Menu,
+};
+
+/// The image of a tray icon.
+#[cfg_attr(not(any(target_os = "windows", target_os = "macos")), allow(dead_code))]
+#[derive(Debug, Clone)]
+pub struct TrayImage {
+    pub(crate) rgba: Vec<u8>,
+    pub(crate) width: u32,
+    pub(crate) height: u32,
+}
+
+impl TrayImage {
+    /// Creates an image from raw RGBA pixels.
+    pub fn from_rgba(rgba: Vec<u8>, width: u32, height: u32) -> Self {
+        Self {
+            rgba,
+            width,
+            height,
+        }
+    }
+
+    /// Decodes an image file (e.g. a PNG) from its bytes.
+    ///
+    /// The supported formats depend on the enabled `image-*` features.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, image::ImageError> {
+        let image = image::load_from_memory(bytes)?.into_rgba8();
+        let (width, height) = image.dimensions();
+        Ok(Self::from_rgba(image.into_raw(), width, height))
+    }
+}
+
+/// An event on the tray icon itself, rather than on its menu.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrayEvent {
+    /// The icon was clicked with the primary button.
+    Click,
+    /// The icon was clicked with the secondary button.
+    ///
+    /// If the tray has a menu, the menu is shown as well.
+    SecondaryClick,
+    /// The icon was double clicked. Only emitted on Windows.
+    DoubleClick,
+}
+
+/// Describes the tray icon of the application.
+///
+/// Use [`set_tray`] to show it.
+#[cfg_attr(not(any(target_os = "windows", target_os = "macos")), allow(dead_code))]
+pub struct Tray {
+    pub(crate) icon: Option<TrayImage>,
+    pub(crate) tooltip: Option<String>,
+    pub(crate) title: Option<String>,
+    pub(crate) menu: Option<Menu>,
+    pub(crate) on_event: Option<Box<dyn Fn(TrayEvent)>>,
+}
+
+impl Default for Tray {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Tray {
+    /// Creates an empty tray icon description.
+    pub fn new() -> Self {
+        Self {
+            icon: None,
+            tooltip: None,
+            title: None,
+            menu: None,
+            on_event: None,
+        }
+    }
+
+    /// Sets the icon image.
+    pub fn icon(mut self, icon: TrayImage) -> Self {
+        self.icon = Some(icon);
+        self
+    }
+
+    /// Sets the tooltip shown when hovering the icon.
+    pub fn tooltip(mut self, tooltip: impl Into<String>) -> Self {
+        self.tooltip = Some(tooltip.into());
+        self
+    }
+
+    /// Sets the text shown next to the icon. Only has an effect on macOS.
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Sets the menu shown when the icon is clicked with the secondary button.
+    pub fn menu(mut self, menu: Menu) -> Self {
+        self.menu = Some(menu);
+        self
+    }
+
+    /// Sets the handler of clicks on the icon.
+    pub fn on_event(mut self, action: impl Fn(TrayEvent) + 'static) -> Self {
+        self.on_event = Some(Box::new(action));
+        self
+    }
+}
+
+#[cfg_attr(not(any(target_os = "windows", target_os = "macos")), allow(dead_code))]
+pub(crate) enum TrayUpdate {
+    Set(Tray),
+    Remove,
+    Icon(Option<TrayImage>),
+    Tooltip(Option<String>),
+    Title(Option<String>),
+    Menu(Option<Menu>),
+    Visible(bool),
+}
+
+fn update_tray(update: TrayUpdate) {
+    add_app_update_event(AppUpdateEvent::Tray { update });
+}
+
+/// Shows the tray icon of the application, replacing the current one.
+pub fn set_tray(tray: Tray) {
+    update_tray(TrayUpdate::Set(tray));
+}
+
+/// Removes the tray icon of the application.
+pub fn remove_tray() {
+    update_tray(TrayUpdate::Remove);
+}
+
+/// Updates the icon image of the tray icon.
+pub fn set_tray_icon(icon: Option<TrayImage>) {
+    update_tray(TrayUpdate::Icon(icon));
+}
+
+/// Updates the tooltip of the tray icon, e.g. to report the status of a background task.
+pub fn set_tray_tooltip(tooltip: Option<String>) {
+    update_tray(TrayUpdate::Tooltip(tooltip));
+}
+
+/// Updates the text shown next to the tray icon. Only has an effect on macOS.
+pub fn set_tray_title(title: Option<String>) {
+    update_tray(TrayUpdate::Title(title));
+}
+
+/// Replaces the menu of the tray icon.
+pub fn set_tray_menu(menu: Option<Menu>) {
+    update_tray(TrayUpdate::Menu(menu));
+}
+
+/// Shows or hides the tray icon without removing it.
+pub fn set_tray_visible(visible: bool) {
+    update_tray(TrayUpdate::Visible(visible));
+}
+
+/// The tray icon owned by the application handle.
+#[derive(Default)]
+pub(crate) struct TrayState {
+    #[cfg(any(target_os = "windows", target_os = "macos"))]
+    icon: Option<tray_icon::TrayIcon>,
+    actions: std::collections::HashMap<String, Box<dyn Fn()>>,
+    on_event: Option<Box<dyn Fn(TrayEvent)>>,
+}
+
+impl TrayState {
+    pub(crate) fn update(&mut self, update: TrayUpdate) {
+        match update {
+            TrayUpdate::Set(tray) => {
+                self.actions.clear();
+                self.on_event = tray.on_event;
+                let mut menu = tray.menu;
+                if let Some(menu) = menu.as_mut() {
+                    collect_menu_actions(menu, &mut self.actions);
+                }
+                #[cfg(any(target_os = "windows", target_os = "macos"))]
+                {
+                    let mut builder = tray_icon::TrayIconBuilder::new();
+                    if let Some(icon) = tray.icon.and_then(platform_icon) {
+                        builder = builder.with_icon(icon);
+                    }
+                    if let Some(tooltip) = tray.tooltip {
+                        builder = builder.with_tooltip(tooltip);
+                    }
+                    if let Some(title) = tray.title {
+                        builder = builder.with_title(title);
+                    }
+                    if let Some(menu) = menu {
+                        builder = builder.with_menu(Box::new(menu.platform_menu()));
+                    }
+                    // Drop the old icon first so that it's removed from the tray.
+                    self.icon = None;
+                    self.icon = builder.build().ok();
+                }
+            }
+            TrayUpdate::Remove => {
+                self.actions.clear();
+                self.on_event = None;
+                #[cfg(any(target_os = "windows", target_os = "macos"))]
+                {
+                    self.icon = None;
+                }
+            }
+            TrayUpdate::Menu(mut menu) => {
+                self.actions.clear();
+                if let Some(menu) = menu.as_mut() {
+                    collect_menu_actions(menu, &mut self.actions);
+                }
+                #[cfg(any(target_os = "windows", target_os = "macos"))]
+                if let Some(icon) = self.icon.as_ref() {
+                    icon.set_menu(menu.map(|menu| {
+                        Box::new(menu.platform_menu()) as Box<dyn tray_icon::menu::ContextMenu>
+                    }));
+                }
+            }
+            #[cfg(any(target_os = "windows", target_os = "macos"))]
+            update => {
+                let Some(tray_icon) = self.icon.as_ref() else {
+                    return;
+                };
+                match update {
+                    TrayUpdate::Icon(icon) => {
+                        let _ = tray_icon.set_icon(icon.and_then(platform_icon));
+                    }
+                    TrayUpdate::Tooltip(tooltip) => {
+                        let _ = tray_icon.set_tooltip(tooltip);
+                    }
+                    TrayUpdate::Title(title) => {
+                        tray_icon.set_title(title);
+                    }
+                    TrayUpdate::Visible(visible) => {
+                        let _ = tray_icon.set_visible(visible);
+                    }
+                    TrayUpdate::Set(_) | TrayUpdate::Remove | TrayUpdate::Menu(_) => {}
+                }
+            }
+            #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+            _ => {}
+        }
+    }
+
+    /// Runs the action of a tray menu item, returns `false` if the item doesn't belong to
+    /// the tray menu.
+    pub(crate) fn menu_action(&self, id: &str) -> bool {
+        if let Some(action) = self.actions.get(id) {
+            action();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub(crate) fn event(&self, event: TrayEvent) {
+        if let Some(on_event) = self.on_event.as_ref() {
+            on_event(event);
+        }
+    }
+}
+
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+fn platform_icon(image: TrayImage) -> Option<tray_icon::Icon> {
+    tray_icon::Icon::from_rgba(image.rgba, image.width, image.height).ok()
+}
+
+/// Forwards the events of the platform tray icon to the application.
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+pub(crate) fn set_tray_event_handler() {
+    use tray_icon::{MouseButton, MouseButtonState, TrayIconEvent};
+
+    TrayIconEvent::set_event_handler(Some(|event: TrayIconEvent| {
+        let event = match event {
+            TrayIconEvent::Click {
+                button: MouseButton::Left,
+                button_state: MouseButtonState::Up,
+                ..
+            } => TrayEvent::Click,
+            TrayIconEvent::Click {
+                button: MouseButton::Right,
+                button_state: MouseButtonState::Up,
+                ..
+            } => TrayEvent::SecondaryClick,
+            TrayIconEvent::DoubleClick {
+                button: MouseButton::Left,
+                ..
+            } => TrayEvent::DoubleClick,
+            _ => return,
+        };
+        add_app_update_event(AppUpdateEvent::TrayEvent { event });
+    }));
+}
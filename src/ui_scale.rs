@@ -0,0 +1,122 @@
+//! A zoom of the whole user interface of the application, independent of the scale factor of
+//! the monitors, for users who need larger text and controls.
+//!
+//! The UI scale multiplies the [window scale](crate::action::set_window_scale) of every
+//! window, so views are laid out and text is shaped at the zoomed size rather than stretched.
+//! ```rust
+//! use floem::{command::register_command, ui_scale};
+//!
+//! for command in ui_scale::commands() {
+//!     register_command(command);
+//! }
+//! ```
+use floem_reactive::{RwSignal, Scope, SignalGet, SignalUpdate};
+
+use crate::{
+    app::{add_app_update_event, AppUpdateEvent},
+    command::Command,
+};
+
+/// The UI scales stepped through by [`zoom_in`] and [`zoom_out`], which are also the limits
+/// of [`set_ui_scale`].
+pub const ZOOM_LEVELS: [f64; 13] = [
+    0.5, 0.67, 0.75, 0.8, 0.9, 1.0, 1.1, 1.25, 1.5, 1.75, 2.0, 2.5, 3.0,
+];
+
+thread_local! {
+    static UI_SCALE: RwSignal<f64> = Scope::new().create_rw_signal(1.0);
+}
+
+/// The UI scale of the application, 1 by default.
+///
+/// The scale is tracked.
+pub fn ui_scale() -> f64 {
+    UI_SCALE.with(|scale| scale.get())
+}
+
+pub(crate) fn ui_scale_untracked() -> f64 {
+    UI_SCALE.with(|scale| scale.get_untracked())
+}
+
+/// Sets the UI scale of the application, which applies to all its windows. The scale is
+/// limited to the range of [`ZOOM_LEVELS`].
+pub fn set_ui_scale(scale: f64) {
+    if !scale.is_finite() {
+        return;
+    }
+    let scale = scale.clamp(ZOOM_LEVELS[0], ZOOM_LEVELS[ZOOM_LEVELS.len() - 1]);
+    if scale == ui_scale_untracked() {
+        return;
+    }
+    UI_SCALE.with(|signal| signal.set(scale));
+    add_app_update_event(AppUpdateEvent::UiScaleChanged);
+}
+
+/// The zoom level after `scale` when zooming in, or before it when zooming out.
+fn next_level(scale: f64, zoom_in: bool) -> f64 {
+    // Scales set with `set_ui_scale` can be slightly off a level
+    const EPSILON: f64 = 0.001;
+    if zoom_in {
+        ZOOM_LEVELS
+            .into_iter()
+            .find(|level| *level > scale + EPSILON)
+            .unwrap_or(ZOOM_LEVELS[ZOOM_LEVELS.len() - 1])
+    } else {
+        ZOOM_LEVELS
+            .into_iter()
+            .rev()
+            .find(|level| *level < scale - EPSILON)
+            .unwrap_or(ZOOM_LEVELS[0])
+    }
+}
+
+/// Sets the UI scale to the next of the [`ZOOM_LEVELS`].
+pub fn zoom_in() {
+    set_ui_scale(next_level(ui_scale_untracked(), true));
+}
+
+/// Sets the UI scale to the previous of the [`ZOOM_LEVELS`].
+pub fn zoom_out() {
+    set_ui_scale(next_level(ui_scale_untracked(), false));
+}
+
+/// Sets the UI scale back to 1.
+pub fn reset_zoom() {
+    set_ui_scale(1.0);
+}
+
+/// The commands zooming in with `CmdOrCtrl+=`, zooming out with `CmdOrCtrl+-` and resetting
+/// the zoom with `CmdOrCtrl+0`, to add to the [command registry](crate::command).
+pub fn commands() -> Vec<Command> {
+    vec![
+        Command::new("view.zoom_in", "Zoom In")
+            .category("View")
+            .shortcut("CmdOrCtrl+=")
+            .enabled(|| ui_scale() < ZOOM_LEVELS[ZOOM_LEVELS.len() - 1])
+            .action(zoom_in),
+        Command::new("view.zoom_out", "Zoom Out")
+            .category("View")
+            .shortcut("CmdOrCtrl+-")
+            .enabled(|| ui_scale() > ZOOM_LEVELS[0])
+            .action(zoom_out),
+        Command::new("view.reset_zoom", "Reset Zoom")
+            .category("View")
+            .shortcut("CmdOrCtrl+0")
+            .action(reset_zoom),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{next_level, ZOOM_LEVELS};
+
+    #[test]
+    fn zoom_levels() {
+        assert_eq!(next_level(1.0, true), 1.1);
+        assert_eq!(next_level(1.0, false), 0.9);
+        assert_eq!(next_level(0.6705, true), 0.75);
+        assert_eq!(next_level(1.3, false), 1.25);
+        assert_eq!(next_level(3.0, true), 3.0);
+        assert_eq!(next_level(ZOOM_LEVELS[0], false), 0.5);
+    }
+}
@@ -1,9 +1,10 @@
 use std::{any::Any, cell::RefCell, collections::HashMap};
 
+use floem_renderer::display_list::DisplayList;
 use peniko::kurbo::{Point, Rect, Size, Vec2};
 use winit::window::ResizeDirection;
 
-use crate::{id::ViewId, menu::Menu, view::View};
+use crate::{action::ImePurpose, id::ViewId, menu::Menu, view::View};
 
 thread_local! {
     /// Stores all the update message with their original `ViewId`
@@ -29,6 +30,9 @@ type DeferredUpdateMessages = HashMap<ViewId, Vec<(ViewId, Box<dyn Any>)>>;
 pub(crate) enum UpdateMessage {
     Focus(ViewId),
     ClearFocus(ViewId),
+    PointerLock(ViewId),
+    /// Releases the pointer lock if it's held by the view.
+    ReleasePointerLock(ViewId),
     ClearAppFocus,
     Active(ViewId),
     ClearActive(ViewId),
@@ -37,7 +41,11 @@ pub(crate) enum UpdateMessage {
         id: ViewId,
         is_disabled: bool,
     },
-    RequestPaint,
+    RequestPaint(ViewId),
+    RequestPaintRect {
+        id: ViewId,
+        rect: Rect,
+    },
     State {
         id: ViewId,
         state: Box<dyn Any>,
@@ -54,6 +62,8 @@ pub(crate) enum UpdateMessage {
     ToggleWindowMaximized,
     SetWindowMaximized(bool),
     MinimizeWindow,
+    RequestCloseWindow,
+    SetMaximizeButtonArea(Option<Rect>),
     DragWindow,
     DragResizeWindow(ResizeDirection),
     SetWindowDelta(Vec2),
@@ -76,6 +86,17 @@ pub(crate) enum UpdateMessage {
         id: ViewId,
     },
     Inspect,
+    ToggleInspectorOverlay,
+    ToggleFrameProfiler,
+    CaptureImage {
+        id: ViewId,
+        scale: f64,
+        on_capture: Box<dyn FnOnce(image::RgbaImage)>,
+    },
+    RecordDisplayList {
+        id: ViewId,
+        on_record: Box<dyn FnOnce(DisplayList)>,
+    },
     ScrollTo {
         id: ViewId,
         rect: Option<Rect>,
@@ -84,6 +105,11 @@ pub(crate) enum UpdateMessage {
     SetImeAllowed {
         allowed: bool,
     },
+    SetImePurpose {
+        purpose: ImePurpose,
+    },
+    /// Applies the IME settings of the focused view to the window.
+    FocusedIme,
     SetImeCursorArea {
         position: Point,
         size: Size,
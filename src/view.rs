@@ -53,6 +53,7 @@ use crate::{
     context::{ComputeLayoutCx, EventCx, LayoutCx, PaintCx, StyleCx, UpdateCx},
     event::{Event, EventPropagation},
     id::ViewId,
+    nav::is_tab_stop,
     style::{LayoutProps, Style, StyleClassRef},
     view_state::ViewStyleProps,
     views::{dyn_view, DynamicView},
@@ -348,6 +349,15 @@ pub trait View {
         }
         found
     }
+
+    /// Describes this view to assistive technology, see [`crate::accessibility`].
+    ///
+    /// `node` starts out as a [`Role::GenericContainer`](accesskit::Role::GenericContainer)
+    /// with the bounds and children of the view already set. Views with a meaning of
+    /// their own set a role, and the label, value or state users need to know about.
+    fn accessibility(&self, node: &mut accesskit::Node) {
+        let _ = node;
+    }
 }
 
 impl View for Box<dyn View> {
@@ -398,6 +408,10 @@ impl View for Box<dyn View> {
     fn scroll_to(&mut self, cx: &mut AppState, target: ViewId, rect: Option<Rect>) -> bool {
         (**self).scroll_to(cx, target, rect)
     }
+
+    fn accessibility(&self, node: &mut accesskit::Node) {
+        (**self).accessibility(node)
+    }
 }
 
 /// Computes the layout of the view's children, if any.
@@ -568,6 +582,31 @@ pub(crate) fn paint_outline(cx: &mut PaintCx, style: &ViewStyleProps, size: Size
     );
 }
 
+/// Paints the focus ring around a view focused with the keyboard, outside of its border.
+pub(crate) fn paint_focus_ring(cx: &mut PaintCx, style: &ViewStyleProps, size: Size) {
+    let width = style.focus_ring_width().0;
+    if width <= 0. {
+        return;
+    }
+    let inflate = style.focus_ring_offset().0 + width / 2.0;
+    let rect = size.to_rect().inflate(inflate, inflate);
+    let border_radius = match style.border_radius() {
+        crate::unit::PxPct::Px(px) => px,
+        crate::unit::PxPct::Pct(pct) => size.min_side() * (pct / 100.),
+    };
+    // Square corners stay square, rounded ones follow the border around the offset
+    let radius = if border_radius > 0. {
+        border_radius + inflate
+    } else {
+        0.
+    };
+    cx.stroke(
+        &rect.to_rounded_rect(radius),
+        &style.focus_ring_color(),
+        &Stroke::new(width),
+    );
+}
+
 #[cfg(not(feature = "vello"))]
 pub(crate) fn paint_border(
     cx: &mut PaintCx,
@@ -730,7 +769,9 @@ pub(crate) fn view_tab_navigation(root_view: ViewId, app_state: &mut AppState, b
     };
 
     let mut new_focus = tree_iter(start);
-    while new_focus != start && !app_state.can_focus(new_focus) {
+    while new_focus != start
+        && !(app_state.can_focus(new_focus) && is_tab_stop(app_state, new_focus, start))
+    {
         new_focus = tree_iter(new_focus);
     }
 
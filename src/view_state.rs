@@ -1,16 +1,20 @@
 use crate::{
+    accessibility::AccessibilityProps,
+    action::ImePurpose,
     animate::Animation,
     context::{
-        EventCallback, InteractionState, MenuCallback, MoveListener, ResizeCallback, ResizeListener,
+        EventCallback, InteractionState, MenuCallback, MoveListener, PaintCallback, ResizeCallback,
+        ResizeListener, VisibilityListener,
     },
     event::EventListener,
+    nav::FocusGroup,
     pointer::PointerInputEvent,
     prop_extractor,
     responsive::ScreenSizeBp,
     style::{
         Background, BorderBottomColor, BorderLeftColor, BorderRadius, BorderRightColor,
-        BorderTopColor, BoxShadowProp, LayoutProps, Outline, OutlineColor, Style, StyleClassRef,
-        StyleSelectors,
+        BorderTopColor, BoxShadowProp, Direction, DirectionProp, LayoutProps, Outline,
+        OutlineColor, Style, StyleClassRef, StyleSelectors,
     },
 };
 use bitflags::bitflags;
@@ -74,6 +78,9 @@ prop_extractor! {
 
         pub outline: Outline,
         pub outline_color: OutlineColor,
+        pub focus_ring_color: crate::style::FocusRingColor,
+        pub focus_ring_width: crate::style::FocusRingWidth,
+        pub focus_ring_offset: crate::style::FocusRingOffset,
         pub outline_progress: crate::style::OutlineProgress,
         pub border_left_color: BorderLeftColor,
         pub border_top_color: BorderTopColor,
@@ -91,6 +98,9 @@ prop_extractor! {
 
         pub outline: Outline,
         pub outline_color: OutlineColor,
+        pub focus_ring_color: crate::style::FocusRingColor,
+        pub focus_ring_width: crate::style::FocusRingWidth,
+        pub focus_ring_offset: crate::style::FocusRingOffset,
         pub border_left_color: BorderLeftColor,
         pub border_top_color: BorderTopColor,
         pub border_right_color: BorderRightColor,
@@ -184,6 +194,8 @@ pub struct ViewState {
     pub(crate) animations: Stack<Animation>,
     pub(crate) classes: Vec<StyleClassRef>,
     pub(crate) dragging_style: Option<Style>,
+    /// Style values edited in the inspector, applied on top of everything else
+    pub(crate) inspector_style: Style,
     /// Combine the stacked style into one style, and apply the interact state
     pub(crate) combined_style: Style,
     /// The final style including inherited style from parent
@@ -192,9 +204,12 @@ pub struct ViewState {
     pub(crate) event_listeners: HashMap<EventListener, Vec<Rc<RefCell<EventCallback>>>>,
     pub(crate) context_menu: Option<Rc<MenuCallback>>,
     pub(crate) popout_menu: Option<Rc<MenuCallback>>,
-    pub(crate) resize_listener: Option<Rc<RefCell<ResizeListener>>>,
+    pub(crate) resize_listeners: Vec<Rc<RefCell<ResizeListener>>>,
     pub(crate) window_origin: Point,
-    pub(crate) move_listener: Option<Rc<RefCell<MoveListener>>>,
+    pub(crate) move_listeners: Vec<Rc<RefCell<MoveListener>>>,
+    pub(crate) visibility_listeners: Vec<Rc<RefCell<VisibilityListener>>>,
+    pub(crate) paint_under_listeners: Vec<Rc<PaintCallback>>,
+    pub(crate) paint_over_listeners: Vec<Rc<PaintCallback>>,
     pub(crate) cleanup_listener: Option<Rc<dyn Fn()>>,
     pub(crate) last_pointer_down: Option<PointerInputEvent>,
     pub(crate) is_hidden_state: IsHiddenState,
@@ -202,6 +217,11 @@ pub struct ViewState {
     pub(crate) disable_default_events: HashSet<EventListener>,
     pub(crate) transform: Affine,
     pub(crate) debug_name: SmallVec<[String; 1]>,
+    pub(crate) accessibility: AccessibilityProps,
+    pub(crate) focus_group: Option<FocusGroup>,
+    /// The IME settings of the window while the view has the focus
+    pub(crate) ime_allowed: bool,
+    pub(crate) ime_purpose: ImePurpose,
 }
 
 impl ViewState {
@@ -222,11 +242,15 @@ impl ViewState {
             computed_style: Style::new(),
             taffy_style: taffy::style::Style::DEFAULT,
             dragging_style: None,
+            inspector_style: Style::new(),
             event_listeners: HashMap::new(),
             context_menu: None,
             popout_menu: None,
-            resize_listener: None,
-            move_listener: None,
+            resize_listeners: Vec::new(),
+            move_listeners: Vec::new(),
+            visibility_listeners: Vec::new(),
+            paint_under_listeners: Vec::new(),
+            paint_over_listeners: Vec::new(),
             cleanup_listener: None,
             last_pointer_down: None,
             window_origin: Point::ZERO,
@@ -235,6 +259,10 @@ impl ViewState {
             disable_default_events: HashSet::new(),
             transform: Affine::IDENTITY,
             debug_name: Default::default(),
+            accessibility: Default::default(),
+            focus_group: None,
+            ime_allowed: false,
+            ime_purpose: ImePurpose::Normal,
         }
     }
 
@@ -282,6 +310,15 @@ impl ViewState {
             debug_assert!(!animation.is_idle());
         }
 
+        computed_style.apply_mut(self.inspector_style.clone());
+
+        let direction = computed_style
+            .get_prop::<DirectionProp>()
+            .or_else(|| context.get_prop::<DirectionProp>());
+        if direction == Some(Direction::Rtl) {
+            computed_style.mirror_horizontally();
+        }
+
         self.combined_style = computed_style;
 
         new_frame
@@ -315,20 +352,38 @@ impl ViewState {
             .push(Rc::new(RefCell::new(action)));
     }
 
-    pub(crate) fn update_resize_listener(&mut self, action: Box<ResizeCallback>) {
-        self.resize_listener = Some(Rc::new(RefCell::new(ResizeListener {
-            rect: Rect::ZERO,
-            callback: action,
-        })));
+    pub(crate) fn add_resize_listener(&mut self, action: Box<ResizeCallback>) {
+        self.resize_listeners
+            .push(Rc::new(RefCell::new(ResizeListener {
+                rect: Rect::ZERO,
+                callback: action,
+            })));
     }
 
-    pub(crate) fn update_move_listener(&mut self, action: Box<dyn Fn(Point)>) {
-        self.move_listener = Some(Rc::new(RefCell::new(MoveListener {
+    pub(crate) fn add_move_listener(&mut self, action: Box<dyn Fn(Point)>) {
+        self.move_listeners.push(Rc::new(RefCell::new(MoveListener {
             window_origin: Point::ZERO,
             callback: action,
         })));
     }
 
+    pub(crate) fn add_visibility_listener(&mut self, threshold: f64, action: Box<dyn Fn(bool)>) {
+        self.visibility_listeners
+            .push(Rc::new(RefCell::new(VisibilityListener {
+                threshold,
+                visible: false,
+                callback: action,
+            })));
+    }
+
+    pub(crate) fn add_paint_under_listener(&mut self, action: Box<PaintCallback>) {
+        self.paint_under_listeners.push(action.into());
+    }
+
+    pub(crate) fn add_paint_over_listener(&mut self, action: Box<PaintCallback>) {
+        self.paint_over_listeners.push(action.into());
+    }
+
     pub(crate) fn update_cleanup_listener(&mut self, action: impl Fn() + 'static) {
         self.cleanup_listener = Some(Rc::new(action));
     }
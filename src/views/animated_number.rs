@@ -0,0 +1,121 @@
+//! A label which counts up or down to the new values of a number, for dashboards and counters.
+use floem_reactive::{untrack, RwSignal, SignalGet, SignalUpdate};
+
+use crate::{
+    context::StyleCx,
+    id::ViewId,
+    prop, prop_extractor,
+    style::Transition,
+    unit::DurationUnitExt,
+    view::View,
+    views::{label, Decorators},
+};
+
+prop!(
+    /// The number shown by an [`AnimatedNumber`], which is tweened by the transition of the
+    /// prop.
+    pub AnimatedNumberValue: f64 {} = 0.0
+);
+
+prop_extractor! {
+    AnimatedNumberStyle {
+        value: AnimatedNumberValue,
+    }
+}
+
+/// A label showing a number, tweened between its values. See [`animated_number`].
+pub struct AnimatedNumber {
+    id: ViewId,
+    style: AnimatedNumberStyle,
+    text: RwSignal<String>,
+    format: Box<dyn Fn(f64) -> String>,
+}
+
+/// A label showing the number returned by `value`, formatted by `format`, which counts up or
+/// down to the new values of `value` rather than jumping to them.
+///
+/// The number is tweened by the transition of [`AnimatedNumberValue`], which eases in and out
+/// over 600ms by default, and can be styled like other transitions. Like the other transitions,
+/// the number jumps to the new values when the user prefers
+/// [reduced motion](crate::preferences::reduced_motion).
+/// ```rust
+/// use std::time::Duration;
+///
+/// use floem::{
+///     reactive::{RwSignal, SignalGet},
+///     style::Transition,
+///     views::{animated_number, AnimatedNumberValue, Decorators},
+/// };
+///
+/// let visitors = RwSignal::new(1200.0);
+/// animated_number(move || visitors.get(), |n| format!("{n:.0} visitors")).style(|s| {
+///     s.transition(AnimatedNumberValue, Transition::linear(Duration::from_secs(2)))
+/// });
+/// ```
+pub fn animated_number(
+    value: impl Fn() -> f64 + 'static,
+    format: impl Fn(f64) -> String + 'static,
+) -> AnimatedNumber {
+    let id = ViewId::new();
+    let text = RwSignal::new(format(untrack(&value)));
+    id.set_children(vec![label(move || text.get())]);
+
+    AnimatedNumber {
+        id,
+        style: Default::default(),
+        text,
+        format: Box::new(format),
+    }
+    .style(move |s| {
+        s.set(AnimatedNumberValue, value())
+            .transition(AnimatedNumberValue, Transition::ease_in_out(600.millis()))
+    })
+}
+
+impl View for AnimatedNumber {
+    fn id(&self) -> ViewId {
+        self.id
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "Animated Number".into()
+    }
+
+    fn style_pass(&mut self, cx: &mut StyleCx<'_>) {
+        // The prop changes at every frame of the transition
+        if self.style.read(cx) {
+            let text = (self.format)(self.style.value());
+            if self.text.get_untracked() != text {
+                self.text.set(text);
+            }
+        }
+        for child in self.id.children() {
+            cx.style_view(child);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use floem_reactive::{RwSignal, SignalGet, SignalUpdate};
+
+    use crate::{preferences::set_reduced_motion, testing::TestHarness};
+
+    use super::animated_number;
+
+    #[test]
+    fn reduced_motion() {
+        set_reduced_motion(Some(true));
+        let value = RwSignal::new(1.0);
+        let mut harness = TestHarness::new(move || {
+            animated_number(move || value.get(), |n| format!("{n:.0} visitors"))
+        });
+        let root = harness.root();
+        assert!(harness.find(root, "\"1 visitors\"").is_some());
+
+        // The number jumps to the new value without the transition
+        value.set(42.0);
+        harness.update();
+        assert!(harness.find(root, "\"42 visitors\"").is_some());
+    }
+}
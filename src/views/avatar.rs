@@ -0,0 +1,420 @@
+//! Avatars standing for a user, showing their picture or, until it's loaded or when they
+//! don't have one, their initials or an identicon generated from their name.
+use std::rc::Rc;
+
+use accesskit::Role;
+use floem_reactive::{create_effect, RwSignal, SignalGet, SignalUpdate};
+use peniko::{
+    color::palette,
+    kurbo::{BezPath, Rect, Shape},
+    Color,
+};
+
+use crate::{
+    context::PaintCx,
+    id::ViewId,
+    style_class,
+    unit::UnitExt,
+    view::{AnyView, IntoView, View},
+    views::{clip, dyn_container, empty, label, Decorators},
+};
+
+use super::img::{decode_image, img_dynamic};
+
+style_class!(
+    /// Style class of avatars
+    pub AvatarClass
+);
+
+style_class!(
+    /// Style class of the status dot of avatars
+    pub AvatarStatusClass
+);
+
+/// The background colors of the initials and the colors of the identicons, picked from the
+/// name so that a user keeps the same color.
+const COLORS: [Color; 8] = [
+    Color::from_rgb8(0xE5, 0x48, 0x4D),
+    Color::from_rgb8(0xF7, 0x6B, 0x15),
+    Color::from_rgb8(0xD6, 0x40, 0x9F),
+    Color::from_rgb8(0x8E, 0x4E, 0xC6),
+    Color::from_rgb8(0x3E, 0x63, 0xDD),
+    Color::from_rgb8(0x00, 0x90, 0xFF),
+    Color::from_rgb8(0x12, 0xA5, 0x94),
+    Color::from_rgb8(0x30, 0xA4, 0x6C),
+];
+
+/// The number of rows and columns of the cells of an identicon.
+const IDENTICON_CELLS: usize = 5;
+
+/// The shape of an [`Avatar`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AvatarShape {
+    #[default]
+    Circle,
+    /// A square with rounded corners
+    Rounded,
+    Square,
+}
+
+/// The status of the user of an [`Avatar`], shown as a dot at its bottom right corner.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AvatarStatus {
+    Online,
+    Away,
+    Busy,
+    Offline,
+}
+
+impl AvatarStatus {
+    /// The color of the status dot.
+    pub fn color(&self) -> Color {
+        match self {
+            AvatarStatus::Online => Color::from_rgb8(0x30, 0xA4, 0x6C),
+            AvatarStatus::Away => Color::from_rgb8(0xFF, 0xB2, 0x24),
+            AvatarStatus::Busy => Color::from_rgb8(0xE5, 0x48, 0x4D),
+            AvatarStatus::Offline => Color::from_rgb8(0x8B, 0x8D, 0x98),
+        }
+    }
+}
+
+/// An FNV-1a hash of `text`, which, unlike the hasher of the standard library, is the same
+/// on every platform and release, so that users keep their color and identicon.
+fn hash(text: &str) -> u64 {
+    text.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// The color picked for `name`.
+fn name_color(name: &str) -> Color {
+    COLORS[(hash(name) % COLORS.len() as u64) as usize]
+}
+
+/// The initials of `name`, the first letters of its first and last words, uppercased.
+fn initials(name: &str) -> String {
+    let mut words = name
+        .split_whitespace()
+        .filter_map(|word| word.chars().find(|c| c.is_alphanumeric()));
+    let first = words.next();
+    let last = words.last();
+    first
+        .into_iter()
+        .chain(last)
+        .flat_map(char::to_uppercase)
+        .collect()
+}
+
+/// The cells of the identicon of `seed`, row by row, `true` for the filled ones. The cells are
+/// mirrored around the middle column.
+fn identicon_cells(seed: &str) -> [bool; IDENTICON_CELLS * IDENTICON_CELLS] {
+    let hash = hash(seed);
+    let half = IDENTICON_CELLS.div_ceil(2);
+    let mut cells = [false; IDENTICON_CELLS * IDENTICON_CELLS];
+    for row in 0..IDENTICON_CELLS {
+        for column in 0..half {
+            // The low bits pick the color, so the cells use the high ones
+            let filled = (hash >> (63 - row * half - column)) & 1 == 1;
+            cells[row * IDENTICON_CELLS + column] = filled;
+            cells[row * IDENTICON_CELLS + IDENTICON_CELLS - 1 - column] = filled;
+        }
+    }
+    cells
+}
+
+/// An identicon, a symmetric pattern generated from a seed. See [`identicon`].
+pub struct Identicon {
+    id: ViewId,
+    cells: [bool; IDENTICON_CELLS * IDENTICON_CELLS],
+    color: Color,
+}
+
+/// A symmetric pattern of cells generated from the text returned by `seed`, like the name or
+/// the email of a user, to tell users apart at a glance without a picture.
+///
+/// The same seed always gives the same pattern and color. The pattern is square and centered
+/// in the view, over the background of the view.
+/// ```rust
+/// use floem::views::{identicon, Decorators};
+///
+/// identicon(|| "ada@example.com".to_string()).style(|s| s.size(32.0, 32.0));
+/// ```
+pub fn identicon(seed: impl Fn() -> String + 'static) -> Identicon {
+    let id = ViewId::new();
+    create_effect(move |_| {
+        id.update_state(seed());
+    });
+
+    Identicon {
+        id,
+        cells: [false; IDENTICON_CELLS * IDENTICON_CELLS],
+        color: palette::css::TRANSPARENT,
+    }
+    .accessibility_role(Role::Image)
+    .style(|s| s.size(40.0, 40.0))
+}
+
+impl View for Identicon {
+    fn id(&self) -> ViewId {
+        self.id
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "Identicon".into()
+    }
+
+    fn update(&mut self, _cx: &mut crate::context::UpdateCx, state: Box<dyn std::any::Any>) {
+        if let Ok(seed) = state.downcast::<String>() {
+            self.cells = identicon_cells(&seed);
+            self.color = name_color(&seed);
+            self.id.request_paint();
+        }
+    }
+
+    fn paint(&mut self, cx: &mut PaintCx) {
+        let Some(layout) = self.id.get_layout() else {
+            return;
+        };
+        let (width, height) = (layout.size.width as f64, layout.size.height as f64);
+        let side = width.min(height);
+        // Half a cell is left around the cells
+        let cell = side / (IDENTICON_CELLS + 1) as f64;
+        let origin = (
+            (width - side) / 2.0 + cell / 2.0,
+            (height - side) / 2.0 + cell / 2.0,
+        );
+        let mut path = BezPath::new();
+        for (i, _) in self.cells.iter().enumerate().filter(|(_, filled)| **filled) {
+            let (x, y) = ((i % IDENTICON_CELLS) as f64, (i / IDENTICON_CELLS) as f64);
+            let rect = Rect::new(
+                origin.0 + x * cell,
+                origin.1 + y * cell,
+                origin.0 + (x + 1.0) * cell,
+                origin.1 + (y + 1.0) * cell,
+            );
+            path.extend(rect.path_elements(0.1));
+        }
+        cx.fill(&path, self.color, 0.0);
+    }
+}
+
+/// What an avatar shows, in the order of the fallbacks.
+#[derive(Clone)]
+enum AvatarContent {
+    Image(peniko::Image),
+    Initials(String, Color),
+    Identicon,
+}
+
+/// The content of an avatar, the `image` when there's one, then the initials of `name` unless
+/// `prefer_identicon`, then the identicon of `name`.
+fn avatar_content(
+    image: Option<peniko::Image>,
+    name: &str,
+    prefer_identicon: bool,
+) -> AvatarContent {
+    if let Some(image) = image {
+        return AvatarContent::Image(image);
+    }
+    let initials = initials(name);
+    if prefer_identicon || initials.is_empty() {
+        AvatarContent::Identicon
+    } else {
+        AvatarContent::Initials(initials, name_color(name))
+    }
+}
+
+/// An avatar standing for a user. See [`avatar`].
+pub struct Avatar {
+    id: ViewId,
+    image: RwSignal<Option<peniko::Image>>,
+    prefer_identicon: RwSignal<bool>,
+    shape: RwSignal<AvatarShape>,
+    status: RwSignal<Option<AvatarStatus>>,
+}
+
+/// An avatar standing for the user named by `name`.
+///
+/// The avatar shows the picture set by [`Avatar::image`] once it's loaded, and falls back to
+/// the initials of the name on a background colored from the name, or to an [`identicon`] of
+/// the name when the name has no letters or digits, or with [`Avatar::identicon`].
+/// ```rust,ignore
+/// let picture = create_resource(move || user.get().id, |id| async move { fetch_picture(id).await });
+/// avatar(move || user.get().name)
+///     .image(move || picture.get().flatten())
+///     .shape(AvatarShape::Rounded)
+///     .status(move || Some(AvatarStatus::Online));
+/// ```
+pub fn avatar(name: impl Fn() -> String + 'static) -> Avatar {
+    let id = ViewId::new();
+    let image = RwSignal::new(None);
+    let prefer_identicon = RwSignal::new(false);
+    let shape = RwSignal::new(AvatarShape::default());
+    let status = RwSignal::new(None);
+    let name = Rc::new(name);
+
+    let content = {
+        let name = name.clone();
+        dyn_container(
+            move || avatar_content(image.get(), &name(), prefer_identicon.get()),
+            {
+                let name = name.clone();
+                move |content| -> AnyView {
+                    match content {
+                        AvatarContent::Image(image) => img_dynamic(move || image.clone())
+                            .style(|s| s.size_full())
+                            .into_any(),
+                        AvatarContent::Initials(initials, color) => label(move || initials.clone())
+                            .style(move |s| {
+                                s.size_full()
+                                    .items_center()
+                                    .justify_center()
+                                    .background(color)
+                                    .color(palette::css::WHITE)
+                                    .font_bold()
+                            })
+                            .into_any(),
+                        AvatarContent::Identicon => {
+                            let name = name.clone();
+                            identicon(move || name())
+                                .style(|s| {
+                                    s.size_full().background(Color::from_rgb8(0xF0, 0xF0, 0xF3))
+                                })
+                                .into_any()
+                        }
+                    }
+                }
+            },
+        )
+        .style(|s| s.size_full())
+    };
+    let content = clip(content).style(move |s| {
+        let radius = match shape.get() {
+            AvatarShape::Circle => 50.pct(),
+            AvatarShape::Rounded => 20.pct(),
+            AvatarShape::Square => 0.pct(),
+        };
+        s.size_full().border_radius(radius)
+    });
+    let status_dot = empty().class(AvatarStatusClass).style(move |s| {
+        let s = s
+            .absolute()
+            .inset_right(0.0)
+            .inset_bottom(0.0)
+            .size_pct(28.0, 28.0)
+            .border_radius(50.pct())
+            .border(2.0)
+            .border_color(palette::css::WHITE);
+        match status.get() {
+            Some(status) => s.background(status.color()),
+            None => s.hide(),
+        }
+    });
+    id.set_children(vec![content.into_any(), status_dot.into_any()]);
+
+    Avatar {
+        id,
+        image,
+        prefer_identicon,
+        shape,
+        status,
+    }
+    .class(AvatarClass)
+    .accessibility_role(Role::Image)
+    .accessibility_label(move || name())
+    .style(|s| s.size(40.0, 40.0).font_size(16.0))
+}
+
+impl Avatar {
+    /// Sets the picture of the avatar from the encoded image returned by `image`, like the
+    /// content of a PNG or JPEG file. The avatar falls back to the initials or the identicon
+    /// while `image` returns `None`, like while the picture is loading, and when the image
+    /// can't be decoded.
+    pub fn image(self, image: impl Fn() -> Option<Vec<u8>> + 'static) -> Self {
+        let signal = self.image;
+        create_effect(move |_| {
+            let decoded = image().and_then(|data| decode_image(&data));
+            signal.set(decoded);
+        });
+        self
+    }
+
+    /// Falls back to the identicon of the name rather than to its initials.
+    pub fn identicon(self) -> Self {
+        self.prefer_identicon.set(true);
+        self
+    }
+
+    /// Sets the shape of the avatar, a circle by default.
+    pub fn shape(self, shape: AvatarShape) -> Self {
+        self.shape.set(shape);
+        self
+    }
+
+    /// Shows the status returned by `status` as a dot at the bottom right corner of the avatar,
+    /// or no dot for `None`.
+    pub fn status(self, status: impl Fn() -> Option<AvatarStatus> + 'static) -> Self {
+        let signal = self.status;
+        create_effect(move |_| {
+            signal.set(status());
+        });
+        self
+    }
+}
+
+impl View for Avatar {
+    fn id(&self) -> ViewId {
+        self.id
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "Avatar".into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use floem_reactive::{RwSignal, SignalGet, SignalUpdate};
+
+    use crate::testing::TestHarness;
+
+    use super::{avatar, identicon_cells, initials, IDENTICON_CELLS};
+
+    #[test]
+    fn initials_of_names() {
+        assert_eq!(initials("Ada Lovelace"), "AL");
+        assert_eq!(initials("grace brewster murray hopper"), "GH");
+        assert_eq!(initials("  linus  "), "L");
+        assert_eq!(initials("élodie (away)"), "ÉA");
+        assert_eq!(initials("-- !!"), "");
+    }
+
+    #[test]
+    fn identicon_is_symmetric() {
+        let cells = identicon_cells("ada@example.com");
+        assert_eq!(cells, identicon_cells("ada@example.com"));
+        assert_ne!(cells, identicon_cells("grace@example.com"));
+        for row in cells.chunks(IDENTICON_CELLS) {
+            assert!(row.iter().eq(row.iter().rev()));
+        }
+    }
+
+    #[test]
+    fn fallbacks() {
+        let name = RwSignal::new("Ada Lovelace".to_string());
+        let picture = RwSignal::new(None);
+        let mut harness =
+            TestHarness::new(move || avatar(move || name.get()).image(move || picture.get()));
+        let root = harness.root();
+        assert!(harness.find(root, "\"AL\"").is_some());
+
+        // An image which can't be decoded keeps the initials
+        picture.set(Some(b"not an image".to_vec()));
+        harness.update();
+        assert!(harness.find(root, "\"AL\"").is_some());
+
+        name.set("?".to_string());
+        harness.update();
+        assert!(harness.find(root, "Identicon").is_some());
+    }
+}
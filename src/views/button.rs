@@ -14,6 +14,11 @@ impl View for Button {
     fn id(&self) -> ViewId {
         self.id
     }
+
+    fn accessibility(&self, node: &mut accesskit::Node) {
+        node.set_role(accesskit::Role::Button);
+        node.add_action(accesskit::Action::Click);
+    }
 }
 impl Button {
     pub fn new(child: impl IntoView) -> Self {
@@ -0,0 +1,373 @@
+//! A list of chat messages which stays scrolled to the latest message as messages arrive,
+//! with separators between the days and the consecutive messages of an author grouped.
+use std::{hash::Hash, rc::Rc};
+
+use floem_reactive::{create_effect, RwSignal, SignalGet, SignalUpdate};
+use peniko::{color::palette, kurbo::Point};
+
+use crate::{
+    event::{Event, EventListener},
+    style_class,
+    unit::UnitExt,
+    view::{AnyView, IntoView},
+    views::{button, label, scroll, stack, virtual_stack, Decorators, Stack},
+};
+
+style_class!(
+    /// Style class of a [`chat_list`]
+    pub ChatListClass
+);
+
+style_class!(
+    /// Style class of the separators shown before the first message of each day
+    pub ChatDaySeparatorClass
+);
+
+style_class!(
+    /// Style class of the button jumping to the latest message, shown while the list is
+    /// scrolled up
+    pub ChatJumpToLatestClass
+);
+
+/// The height of the separators between the days.
+const DAY_SEPARATOR_HEIGHT: f64 = 32.0;
+
+/// A message of a [`chat_list`].
+pub trait ChatMessage: Clone + 'static {
+    /// Identifies the message among the messages of the list
+    type Key: Eq + Hash + 'static;
+
+    fn key(&self) -> Self::Key;
+
+    /// The author of the message. The consecutive messages of an author on a day are grouped,
+    /// so that only the first one of a group needs to show the name and the avatar.
+    fn sender(&self) -> &str;
+
+    /// The label of the day of the message, like `"Today"` or `"March 3"`, shown in a
+    /// separator before the first message of each day.
+    fn day(&self) -> String;
+
+    /// The height of the view of the message, which is created differently for the first
+    /// message of a group.
+    fn height(&self, first_of_group: bool) -> f64;
+}
+
+/// A row of a [`chat_list`].
+#[derive(Clone)]
+enum ChatRow<M> {
+    /// The separator of a day, before its first message
+    Day(String, M),
+    Message {
+        message: M,
+        first_of_group: bool,
+    },
+}
+
+#[derive(PartialEq, Eq, Hash)]
+enum ChatRowKey<K> {
+    // Days can have the same label, like weekdays, so they're keyed by their first message
+    Day(K),
+    // A message moving to or from the start of a group gets a new view
+    Message(K, bool),
+}
+
+impl<M: ChatMessage> ChatRow<M> {
+    fn key(&self) -> ChatRowKey<M::Key> {
+        match self {
+            ChatRow::Day(_, message) => ChatRowKey::Day(message.key()),
+            ChatRow::Message {
+                message,
+                first_of_group,
+            } => ChatRowKey::Message(message.key(), *first_of_group),
+        }
+    }
+
+    fn height(&self) -> f64 {
+        match self {
+            ChatRow::Day(..) => DAY_SEPARATOR_HEIGHT,
+            ChatRow::Message {
+                message,
+                first_of_group,
+            } => message.height(*first_of_group),
+        }
+    }
+}
+
+/// The rows of `messages`, with a separator before the first message of each day.
+fn chat_rows<M: ChatMessage>(messages: &im::Vector<M>) -> im::Vector<ChatRow<M>> {
+    let mut rows = im::Vector::new();
+    let mut previous: Option<(&M, String)> = None;
+    for message in messages {
+        let day = message.day();
+        let new_day = previous
+            .as_ref()
+            .is_none_or(|(_, previous_day)| *previous_day != day);
+        if new_day {
+            rows.push_back(ChatRow::Day(day.clone(), message.clone()));
+        }
+        let first_of_group = new_day
+            || previous
+                .as_ref()
+                .is_some_and(|(previous, _)| previous.sender() != message.sender());
+        rows.push_back(ChatRow::Message {
+            message: message.clone(),
+            first_of_group,
+        });
+        previous = Some((message, day));
+    }
+    rows
+}
+
+/// The scrolling of a [`chat_list`].
+#[derive(Clone, Copy)]
+pub struct ChatListState {
+    /// Whether the list stays scrolled to the latest message as messages arrive. It's turned
+    /// off when the list is scrolled up, and on again when it's scrolled to the end.
+    pub follow_latest: RwSignal<bool>,
+    unseen: RwSignal<usize>,
+}
+
+impl Default for ChatListState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ChatListState {
+    pub fn new() -> Self {
+        Self {
+            follow_latest: RwSignal::new(true),
+            unseen: RwSignal::new(0),
+        }
+    }
+
+    /// The number of messages which arrived while the list was scrolled up.
+    pub fn unseen(&self) -> usize {
+        self.unseen.get()
+    }
+
+    /// Scrolls the list to the latest message, and follows the messages arriving again, like
+    /// after the user sent a message.
+    pub fn jump_to_latest(&self) {
+        self.follow_latest.set(true);
+        self.unseen.set(0);
+    }
+}
+
+/// A list of the messages returned by `messages`, oldest first, with the views created by
+/// `view_fn` from a message and whether it's the first message of a group.
+///
+/// The list stays scrolled to the latest message as messages arrive, unless the user scrolled
+/// up to read older messages. A button then jumps back to the latest message, counting the
+/// messages which arrived since. Only the views of the visible messages are created, from
+/// the heights given by [`ChatMessage::height`].
+/// ```rust
+/// use floem::{
+///     reactive::{RwSignal, SignalGet},
+///     views::{chat_list, label, ChatListState, ChatMessage, Decorators},
+/// };
+///
+/// #[derive(Clone)]
+/// struct Message {
+///     id: u64,
+///     sender: String,
+///     day: String,
+///     text: String,
+/// }
+///
+/// impl ChatMessage for Message {
+///     type Key = u64;
+///
+///     fn key(&self) -> u64 {
+///         self.id
+///     }
+///
+///     fn sender(&self) -> &str {
+///         &self.sender
+///     }
+///
+///     fn day(&self) -> String {
+///         self.day.clone()
+///     }
+///
+///     fn height(&self, first_of_group: bool) -> f64 {
+///         if first_of_group { 44.0 } else { 24.0 }
+///     }
+/// }
+///
+/// let messages = RwSignal::new(im::Vector::<Message>::new());
+/// chat_list(ChatListState::new(), move || messages.get(), |message, first_of_group| {
+///     let height = message.height(first_of_group);
+///     let text = if first_of_group {
+///         format!("{}\n{}", message.sender, message.text)
+///     } else {
+///         message.text
+///     };
+///     label(move || text.clone()).style(move |s| s.height(height))
+/// });
+/// ```
+pub fn chat_list<M, V>(
+    state: ChatListState,
+    messages: impl Fn() -> im::Vector<M> + 'static,
+    view_fn: impl Fn(M, bool) -> V + 'static,
+) -> Stack
+where
+    M: ChatMessage,
+    V: IntoView + 'static,
+{
+    let messages = Rc::new(messages);
+    // Count the messages arriving while the list is scrolled up
+    {
+        let messages = messages.clone();
+        create_effect(move |previous: Option<usize>| {
+            let count = messages().len();
+            if let Some(previous) = previous {
+                if count > previous && !state.follow_latest.get_untracked() {
+                    state.unseen.update(|unseen| *unseen += count - previous);
+                }
+            }
+            count
+        });
+    }
+
+    let content_height = RwSignal::new(0.0);
+    let list = scroll(
+        virtual_stack(
+            move || chat_rows(&messages()),
+            ChatRow::key,
+            move |row| -> AnyView {
+                match row {
+                    ChatRow::Day(day, _) => label(move || day.clone())
+                        .class(ChatDaySeparatorClass)
+                        .style(|s| {
+                            s.height(DAY_SEPARATOR_HEIGHT)
+                                .width_full()
+                                .items_center()
+                                .justify_center()
+                                .font_size(12.0)
+                                .color(palette::css::GRAY)
+                        })
+                        .into_any(),
+                    ChatRow::Message {
+                        message,
+                        first_of_group,
+                    } => view_fn(message, first_of_group).into_any(),
+                }
+            },
+        )
+        .item_size_fn(ChatRow::height)
+        .on_resize(move |rect| {
+            if content_height.get_untracked() != rect.height() {
+                content_height.set(rect.height());
+            }
+        })
+        .style(|s| s.flex_col().min_width_full()),
+    )
+    .scroll_to(move || {
+        state
+            .follow_latest
+            .get()
+            .then(|| Point::new(0.0, content_height.get()))
+    })
+    .on_scroll(move |viewport| {
+        // Scrolling up stops following the latest message, scrolling to the end follows it again
+        let at_end = viewport.y1 >= content_height.get_untracked() - 1.0;
+        if state.follow_latest.get_untracked() != at_end {
+            state.follow_latest.set(at_end);
+        }
+        if at_end && state.unseen.get_untracked() > 0 {
+            state.unseen.set(0);
+        }
+    })
+    .on_event_cont(EventListener::PointerWheel, move |event| {
+        if let Event::PointerWheel(wheel) = event {
+            if wheel.delta.y < 0.0 && state.follow_latest.get_untracked() {
+                state.follow_latest.set(false);
+            }
+        }
+    })
+    .style(|s| s.size_full());
+
+    let jump = button(label(move || match state.unseen() {
+        0 => "Jump to latest".to_string(),
+        1 => "1 new message".to_string(),
+        unseen => format!("{unseen} new messages"),
+    }))
+    .class(ChatJumpToLatestClass)
+    .action(move || state.jump_to_latest())
+    .style(move |s| {
+        s.absolute()
+            .inset_right(16.0)
+            .inset_bottom(16.0)
+            .border_radius(50.pct())
+            .apply_if(state.follow_latest.get(), |s| s.hide())
+    });
+
+    stack((list, jump))
+        .class(ChatListClass)
+        .style(|s| s.size_full())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{chat_rows, ChatMessage, ChatRow};
+
+    #[derive(Clone)]
+    struct Message {
+        id: u64,
+        sender: &'static str,
+        day: &'static str,
+    }
+
+    impl ChatMessage for Message {
+        type Key = u64;
+
+        fn key(&self) -> u64 {
+            self.id
+        }
+
+        fn sender(&self) -> &str {
+            self.sender
+        }
+
+        fn day(&self) -> String {
+            self.day.to_string()
+        }
+
+        fn height(&self, first_of_group: bool) -> f64 {
+            if first_of_group {
+                40.0
+            } else {
+                20.0
+            }
+        }
+    }
+
+    #[test]
+    fn rows() {
+        let messages = [
+            (1, "ada", "Monday"),
+            (2, "ada", "Monday"),
+            (3, "grace", "Monday"),
+            (4, "grace", "Tuesday"),
+            (5, "grace", "Tuesday"),
+        ]
+        .into_iter()
+        .map(|(id, sender, day)| Message { id, sender, day })
+        .collect();
+        let rows = chat_rows(&messages)
+            .iter()
+            .map(|row| match row {
+                ChatRow::Day(day, _) => day.clone(),
+                ChatRow::Message {
+                    message,
+                    first_of_group,
+                } => format!("{} {first_of_group}", message.id),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            rows,
+            ["Monday", "1 true", "2 false", "3 true", "Tuesday", "4 true", "5 false"]
+        );
+    }
+}
@@ -2,14 +2,16 @@
 //! A checkbox view for boolean selection.
 
 use crate::{
+    nav::FocusGroup,
     style_class,
-    view::IntoView,
+    view::{IntoView, View},
     views::{
-        self, create_value_container_signals, h_stack, svg, value_container, Decorators,
-        ValueContainer,
+        self, create_value_container_signals, h_stack, svg, v_stack_from_iter, value_container,
+        Decorators, Stack, ValueContainer,
     },
 };
-use floem_reactive::{SignalGet, SignalUpdate};
+use accesskit::{Role, Toggled};
+use floem_reactive::{create_effect, RwSignal, SignalGet, SignalUpdate, SignalWith};
 use std::fmt::Display;
 
 style_class!(
@@ -25,6 +27,76 @@ style_class!(
 /// The default checkbox SVG
 pub const DEFAULT_CHECKBOX_SVG: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="-2 -2 16 16"><polygon points="5.19,11.83 0.18,7.44 1.82,5.56 4.81,8.17 10,1.25 12,2.75" /></svg>"#;
 
+/// The SVG shown by a tri-state checkbox which is [indeterminate](CheckState::Indeterminate)
+pub const DEFAULT_INDETERMINATE_SVG: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="-2 -2 16 16"><rect x="1" y="5" width="10" height="2" /></svg>"#;
+
+/// The state of a [tri-state checkbox](tristate_checkbox).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CheckState {
+    /// Not checked.
+    #[default]
+    Unchecked,
+    /// Checked.
+    Checked,
+    /// Partly checked, like a checkbox selecting all the options of a group when only some
+    /// of them are selected.
+    Indeterminate,
+}
+
+impl CheckState {
+    /// The state after the checkbox is clicked: an indeterminate checkbox becomes checked.
+    pub fn toggled(self) -> Self {
+        match self {
+            CheckState::Checked => CheckState::Unchecked,
+            CheckState::Unchecked | CheckState::Indeterminate => CheckState::Checked,
+        }
+    }
+
+    /// The state of a checkbox standing for a group of options, depending on how many of
+    /// the `total` options are checked.
+    pub fn of_group(checked: usize, total: usize) -> Self {
+        if checked == 0 {
+            CheckState::Unchecked
+        } else if checked >= total {
+            CheckState::Checked
+        } else {
+            CheckState::Indeterminate
+        }
+    }
+}
+
+impl From<bool> for CheckState {
+    fn from(checked: bool) -> Self {
+        if checked {
+            CheckState::Checked
+        } else {
+            CheckState::Unchecked
+        }
+    }
+}
+
+fn tristate_svg(state: impl SignalGet<CheckState> + Copy + 'static) -> impl IntoView {
+    let view = svg(DEFAULT_CHECKBOX_SVG)
+        .update_value(move || match state.get() {
+            CheckState::Checked => DEFAULT_CHECKBOX_SVG,
+            CheckState::Indeterminate => DEFAULT_INDETERMINATE_SVG,
+            CheckState::Unchecked => "",
+        })
+        .class(CheckboxClass)
+        .keyboard_navigable()
+        .accessibility_role(Role::CheckBox);
+    let id = view.id();
+    create_effect(move |_| {
+        let toggled = match state.get() {
+            CheckState::Checked => Toggled::True,
+            CheckState::Unchecked => Toggled::False,
+            CheckState::Indeterminate => Toggled::Mixed,
+        };
+        id.update_accessibility(|props| props.toggled = Some(toggled));
+    });
+    view
+}
+
 fn checkbox_svg(
     checked: impl SignalGet<bool> + 'static,
     check_svg: impl Into<String> + 'static,
@@ -44,6 +116,8 @@ fn checkbox_svg(
         .update_value(update_svg)
         .class(CheckboxClass)
         .keyboard_navigable()
+        .accessibility_role(Role::CheckBox)
+        .accessibility_toggled(move || checked.get())
 }
 
 /// # A customizable checkbox view for boolean selection.
@@ -185,6 +259,118 @@ impl Checkbox {
     }
 }
 
+/// Renders a checkbox which can also be [indeterminate](CheckState::Indeterminate), with a
+/// closure that determines its state.
+///
+/// Clicking the checkbox checks it, or unchecks it if it's checked. You can add an
+/// `on_update` handler to the returned [`ValueContainer`] to handle changes.
+pub fn tristate_checkbox(state: impl Fn() -> CheckState + 'static) -> ValueContainer<CheckState> {
+    let (inbound_signal, outbound_signal) = create_value_container_signals(state);
+
+    value_container(
+        tristate_svg(inbound_signal.read_only()).on_click_stop(move |_| {
+            outbound_signal.set(inbound_signal.get_untracked().toggled());
+        }),
+        move || outbound_signal.get(),
+    )
+}
+
+/// Renders a labeled [tri-state checkbox](tristate_checkbox).
+pub fn labeled_tristate_checkbox<S: Display + 'static>(
+    state: impl Fn() -> CheckState + 'static,
+    label: impl Fn() -> S + 'static,
+) -> ValueContainer<CheckState> {
+    let (inbound_signal, outbound_signal) = create_value_container_signals(state);
+
+    value_container(
+        h_stack((
+            tristate_svg(inbound_signal.read_only()),
+            views::label(label),
+        ))
+        .class(LabeledCheckboxClass)
+        .on_click_stop(move |_| {
+            outbound_signal.set(inbound_signal.get_untracked().toggled());
+        })
+        .style(|s| s.items_center()),
+        move || outbound_signal.get(),
+    )
+}
+
+/// Renders a column of labeled checkboxes, one for each of the `options`, keeping the values
+/// of the checked options in `selected`, in the order of the options.
+///
+/// The group is a [focus group](FocusGroup): the arrow keys move the focus between the
+/// checkboxes, and <kbd>Space</kbd> toggles the focused one.
+/// ```rust
+/// use floem::{reactive::RwSignal, views::{checkbox_group, select_all_checkbox}};
+///
+/// let toppings = ["Cheese", "Ham", "Olives"];
+/// let selected = RwSignal::new(vec!["Cheese"]);
+/// select_all_checkbox(selected, toppings.to_vec(), || "All toppings");
+/// checkbox_group(selected, toppings.map(|topping| (topping, topping)));
+/// ```
+pub fn checkbox_group<T, S>(
+    selected: RwSignal<Vec<T>>,
+    options: impl IntoIterator<Item = (T, S)>,
+) -> Stack
+where
+    T: PartialEq + Clone + 'static,
+    S: Display + Clone + 'static,
+{
+    let options: Vec<(T, S)> = options.into_iter().collect();
+    let order: Vec<T> = options.iter().map(|(value, _)| value.clone()).collect();
+    v_stack_from_iter(options.into_iter().map(move |(value, label)| {
+        let order = order.clone();
+        let checked_value = value.clone();
+        labeled_checkbox(
+            move || selected.with(|selected| selected.contains(&checked_value)),
+            move || label.clone(),
+        )
+        .on_update(move |checked| {
+            selected.update(|selected| {
+                selected.retain(|v| *v != value);
+                if checked {
+                    selected.push(value.clone());
+                    selected.sort_by_key(|v| order.iter().position(|o| o == v));
+                }
+            });
+        })
+    }))
+    .focus_group(FocusGroup::vertical())
+}
+
+/// Renders a labeled [tri-state checkbox](tristate_checkbox) checking or unchecking all the
+/// options of a [`checkbox_group`] at once.
+///
+/// The checkbox is indeterminate while only some of the options are selected.
+pub fn select_all_checkbox<T, S>(
+    selected: RwSignal<Vec<T>>,
+    all: Vec<T>,
+    label: impl Fn() -> S + 'static,
+) -> ValueContainer<CheckState>
+where
+    T: PartialEq + Clone + 'static,
+    S: Display + 'static,
+{
+    let options = all.clone();
+    labeled_tristate_checkbox(
+        move || {
+            selected.with(|selected| {
+                let checked = options.iter().filter(|v| selected.contains(v)).count();
+                CheckState::of_group(checked, options.len())
+            })
+        },
+        label,
+    )
+    .on_update(move |state| {
+        if state == CheckState::Checked {
+            selected.set(all.clone());
+        } else {
+            selected.set(Vec::new());
+        }
+    })
+}
+
 /// Renders a checkbox the provided checked signal. See also [`Checkbox::new`] and [`Checkbox::new_rw`].
 pub fn checkbox(checked: impl Fn() -> bool + 'static) -> ValueContainer<bool> {
     Checkbox::new(checked)
@@ -214,3 +400,27 @@ pub fn custom_labeled_checkbox<S: Display + 'static>(
 ) -> ValueContainer<bool> {
     Checkbox::custom_labeled(checked, label, custom_check)
 }
+
+#[cfg(test)]
+mod tests {
+    use floem_reactive::{RwSignal, SignalGet};
+
+    use super::{checkbox_group, CheckState};
+
+    #[test]
+    fn check_states() {
+        assert_eq!(CheckState::of_group(0, 3), CheckState::Unchecked);
+        assert_eq!(CheckState::of_group(2, 3), CheckState::Indeterminate);
+        assert_eq!(CheckState::of_group(3, 3), CheckState::Checked);
+        assert_eq!(CheckState::Indeterminate.toggled(), CheckState::Checked);
+        assert_eq!(CheckState::Checked.toggled(), CheckState::Unchecked);
+        assert_eq!(CheckState::from(true), CheckState::Checked);
+    }
+
+    #[test]
+    fn group_keeps_selection() {
+        let selected = RwSignal::new(vec![2]);
+        let _group = checkbox_group(selected, [(1, "One"), (2, "Two")]);
+        assert_eq!(selected.get(), vec![2]);
+    }
+}
@@ -0,0 +1,280 @@
+//! A command palette listing the registered [commands](crate::command), filtered by a fuzzy
+//! search and run from the keyboard.
+use std::{cell::Cell, rc::Rc};
+
+use floem_reactive::{create_effect, RwSignal, SignalGet, SignalUpdate, SignalWith};
+use peniko::{
+    color::palette,
+    kurbo::{Point, Size},
+};
+
+use crate::{
+    action::{add_overlay, remove_overlay},
+    command::{commands, fuzzy_match, recent_commands, Command},
+    event::{Event, EventListener, EventPropagation},
+    id::ViewId,
+    keyboard::{Key, NamedKey},
+    style_class,
+    view::IntoView,
+    views::{dyn_stack, label, text_input, ContainerExt, Decorators, StackExt},
+    window_handle::get_current_view,
+};
+
+style_class!(
+    /// Style class of the panel of the command palette
+    pub CommandPaletteClass
+);
+
+style_class!(
+    /// Style class of a command listed by the command palette
+    pub CommandPaletteItemClass
+);
+
+style_class!(
+    /// Style class of the command selected in the command palette, which runs on `Enter`
+    pub CommandPaletteSelectedClass
+);
+
+/// The number of recently run commands ranked higher than others.
+const RECENT_BONUS: i64 = 32;
+
+/// The number of commands listed at once.
+const MAX_LISTED: usize = 12;
+
+thread_local! {
+    static PALETTE: Cell<Option<ViewId>> = const { Cell::new(None) };
+}
+
+/// Opens the command palette in an overlay of the current window.
+///
+/// The palette lists the enabled commands matching what's typed, recently run commands
+/// first. The arrow keys select a command, `Enter` runs it, and `Escape` or clicking outside
+/// of the palette closes it.
+pub fn show_command_palette() {
+    if PALETTE.get().is_some() {
+        return;
+    }
+    let window_size = get_current_view().get_size().unwrap_or_default();
+    let id = add_overlay(Point::ZERO, move |id| palette_view(id, window_size));
+    PALETTE.set(Some(id));
+}
+
+/// Closes the command palette if it's open.
+pub fn hide_command_palette() {
+    if let Some(id) = PALETTE.take() {
+        remove_overlay(id);
+    }
+}
+
+/// The enabled commands matching a query, best matches first.
+fn rank_commands(query: &str) -> Vec<Rc<Command>> {
+    let recent = recent_commands();
+    let mut ranked: Vec<_> = commands()
+        .into_iter()
+        .filter(|command| command.is_enabled())
+        .filter_map(|command| {
+            let score = if query.trim().is_empty() {
+                0
+            } else {
+                fuzzy_match(query, &command.label())?.score
+            };
+            let bonus = recent
+                .iter()
+                .position(|id| id == command.id())
+                .map_or(0, |i| RECENT_BONUS - i as i64);
+            Some((score + bonus, command))
+        })
+        .collect();
+    ranked.sort_by(|(a_score, a), (b_score, b)| {
+        b_score.cmp(a_score).then_with(|| a.label().cmp(&b.label()))
+    });
+    ranked.into_iter().map(|(_, command)| command).collect()
+}
+
+fn run(command: &Command) {
+    hide_command_palette();
+    command.run();
+}
+
+fn palette_view(overlay: ViewId, window_size: Size) -> impl IntoView {
+    let query = RwSignal::new(String::new());
+    let results = RwSignal::new(Vec::new());
+    let selected = RwSignal::new(0);
+    // The index of the first listed command
+    let offset = RwSignal::new(0);
+    create_effect(move |_| {
+        let ranked = query.with(|query| rank_commands(query));
+        results.set(ranked);
+        selected.set(0);
+    });
+    create_effect(move |_| {
+        let selected = selected.get();
+        let first = offset.get_untracked();
+        if selected < first {
+            offset.set(selected);
+        } else if selected >= first + MAX_LISTED {
+            offset.set(selected + 1 - MAX_LISTED);
+        }
+    });
+    let is_selected = move |id: &str| {
+        let selected = selected.get();
+        results.with(|results| results.get(selected).is_some_and(|c| c.id() == id))
+    };
+    // Whether the pointer went down in the panel, which takes the focus from the input
+    let clicked_panel = Rc::new(Cell::new(false));
+
+    let input = text_input(query)
+        .placeholder("Type a command")
+        .style(|s| s.width_full())
+        .on_event(EventListener::KeyDown, move |event| {
+            let Event::KeyDown(event) = event else {
+                return EventPropagation::Continue;
+            };
+            let len = results.with_untracked(|results| results.len());
+            match event.key.logical_key {
+                Key::Named(NamedKey::ArrowDown) if len > 0 => {
+                    selected.update(|selected| *selected = (*selected + 1) % len);
+                }
+                Key::Named(NamedKey::ArrowUp) if len > 0 => {
+                    selected.update(|selected| *selected = (*selected + len - 1) % len);
+                }
+                Key::Named(NamedKey::PageDown) if len > 0 => {
+                    selected.update(|selected| *selected = (*selected + MAX_LISTED).min(len - 1));
+                }
+                Key::Named(NamedKey::PageUp) => {
+                    selected.update(|selected| *selected = selected.saturating_sub(MAX_LISTED));
+                }
+                Key::Named(NamedKey::Enter) => {
+                    let command = results
+                        .with_untracked(|results| results.get(selected.get_untracked()).cloned());
+                    match command {
+                        Some(command) => run(&command),
+                        None => hide_command_palette(),
+                    }
+                }
+                _ => return EventPropagation::Continue,
+            }
+            EventPropagation::Stop
+        });
+    let input_id = input.id();
+    input_id.request_focus();
+    // Escape and clicks outside of the panel take the focus from the input
+    let input = input.on_event_cont(EventListener::FocusLost, {
+        let clicked_panel = clicked_panel.clone();
+        move |_| {
+            if clicked_panel.replace(false) {
+                input_id.request_focus();
+            } else {
+                hide_command_palette();
+            }
+        }
+    });
+
+    let list = dyn_stack(
+        move || {
+            results.with(|results| {
+                results
+                    .iter()
+                    .skip(offset.get())
+                    .take(MAX_LISTED)
+                    .cloned()
+                    .collect::<Vec<_>>()
+            })
+        },
+        |command| command.id().to_string(),
+        move |command| {
+            let id = command.id().to_string();
+            let shortcut = command.shortcut_label().unwrap_or_default();
+            (
+                label(move || command.label()).style(|s| s.flex_grow(1.0)),
+                label(move || shortcut.clone()).style(|s| s.color(palette::css::GRAY)),
+            )
+                .h_stack()
+                .class(CommandPaletteItemClass)
+                .class_if(
+                    {
+                        let id = id.clone();
+                        move || is_selected(&id)
+                    },
+                    CommandPaletteSelectedClass,
+                )
+                .on_event_stop(EventListener::PointerDown, move |_| {
+                    // Runs on pointer down, before the input loses the focus
+                    let command = results.with_untracked(|results| {
+                        results.iter().find(|command| command.id() == id).cloned()
+                    });
+                    if let Some(command) = command {
+                        run(&command);
+                    }
+                })
+        },
+    )
+    .style(|s| s.flex_col().width_full())
+    .on_event_stop(EventListener::PointerWheel, move |event| {
+        if let Event::PointerWheel(event) = event {
+            let len = results.with_untracked(|results| results.len());
+            let max_offset = len.saturating_sub(MAX_LISTED);
+            if event.delta.y > 0.0 {
+                offset.update(|offset| *offset = (*offset + 1).min(max_offset));
+            } else if event.delta.y < 0.0 {
+                offset.update(|offset| *offset = offset.saturating_sub(1));
+            }
+        }
+    });
+
+    let panel = (input, list)
+        .v_stack()
+        .class(CommandPaletteClass)
+        .style(move |s| {
+            s.width((window_size.width - 40.0).clamp(0.0, 600.0))
+                .margin_top(window_size.height * 0.1)
+        })
+        .on_event_cont(EventListener::PointerDown, move |_| {
+            clicked_panel.set(true);
+        });
+
+    panel
+        .container()
+        .style(move |s| {
+            s.size(window_size.width, window_size.height)
+                .flex_col()
+                .items_center()
+        })
+        .on_cleanup(move || {
+            // The overlay is also removed with its window
+            if PALETTE.get() == Some(overlay) {
+                PALETTE.set(None);
+            }
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::command::{register_command, run_command, Command};
+
+    use super::rank_commands;
+
+    #[test]
+    fn ranking() {
+        register_command(Command::new("palette.save", "Save").category("File"));
+        register_command(Command::new("palette.save_all", "Save All").category("File"));
+        register_command(Command::new("palette.theme", "Select Theme").category("View"));
+        register_command(Command::new("palette.hidden", "Save Hidden").enabled(|| false));
+
+        let ids = |query| {
+            rank_commands(query)
+                .iter()
+                .map(|command| command.id().to_string())
+                .filter(|id| id.starts_with("palette."))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(ids("save"), ["palette.save", "palette.save_all"]);
+        assert_eq!(ids("thm"), ["palette.theme"]);
+
+        // Recently run commands come first
+        run_command("palette.theme");
+        assert_eq!(ids("")[0], "palette.theme");
+        run_command("palette.save_all");
+        assert_eq!(ids("sa")[0], "palette.save_all");
+    }
+}
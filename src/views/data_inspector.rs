@@ -0,0 +1,321 @@
+//! A view showing JSON values as an expandable tree, like the inspectors of devtools.
+use std::{collections::HashSet, rc::Rc};
+
+use floem_reactive::{create_memo, RwSignal, SignalGet, SignalUpdate, SignalWith};
+use peniko::color::palette;
+use serde_json::Value;
+
+use crate::{
+    menu::{Menu, MenuItem},
+    style::CursorStyle,
+    style_class,
+    views::{label, scroll, text_input, v_stack, virtual_stack, Decorators, Stack, StackExt},
+    Clipboard,
+};
+
+style_class!(
+    /// Style class of a [`data_inspector`]
+    pub DataInspectorClass
+);
+
+style_class!(
+    /// Style class of the rows of a [`data_inspector`]
+    pub DataInspectorRowClass
+);
+
+/// The height of a row of a [`data_inspector`].
+const ROW_HEIGHT: f64 = 20.0;
+/// The indentation of each level of the tree.
+const INDENT: f64 = 14.0;
+
+/// A key of an object or an index of an array, leading to a value inside another.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+enum PathSegment {
+    Key(String),
+    Index(usize),
+}
+
+/// The [JSON pointer](https://www.rfc-editor.org/rfc/rfc6901) of `path`, as used by
+/// [`Value::pointer`].
+fn json_pointer(path: &[PathSegment]) -> String {
+    path.iter()
+        .map(|segment| match segment {
+            PathSegment::Key(key) => format!("/{}", key.replace('~', "~0").replace('/', "~1")),
+            PathSegment::Index(index) => format!("/{index}"),
+        })
+        .collect()
+}
+
+/// The path of a value as a JavaScript-like expression, like `$.items[0]["first name"]`.
+fn path_expression(path: &[PathSegment]) -> String {
+    let mut expression = String::from("$");
+    for segment in path {
+        match segment {
+            PathSegment::Key(key)
+                if !key.is_empty()
+                    && !key.starts_with(|c: char| c.is_ascii_digit())
+                    && key.chars().all(|c| c.is_alphanumeric() || c == '_') =>
+            {
+                expression.push('.');
+                expression.push_str(key);
+            }
+            PathSegment::Key(key) => {
+                expression.push('[');
+                expression.push_str(&Value::String(key.clone()).to_string());
+                expression.push(']');
+            }
+            PathSegment::Index(index) => expression.push_str(&format!("[{index}]")),
+        }
+    }
+    expression
+}
+
+/// The text shown for a value, which for objects and arrays is their size.
+fn summary(value: &Value) -> String {
+    match value {
+        Value::Object(map) if map.len() == 1 => "{1 key}".to_string(),
+        Value::Object(map) => format!("{{{} keys}}", map.len()),
+        Value::Array(items) if items.len() == 1 => "[1 item]".to_string(),
+        Value::Array(items) => format!("[{} items]", items.len()),
+        value => value.to_string(),
+    }
+}
+
+fn children(value: &Value) -> Vec<(PathSegment, &Value)> {
+    match value {
+        Value::Object(map) => map
+            .iter()
+            .map(|(key, value)| (PathSegment::Key(key.clone()), value))
+            .collect(),
+        Value::Array(items) => items
+            .iter()
+            .enumerate()
+            .map(|(index, value)| (PathSegment::Index(index), value))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Whether the key or the text of `value`, or of a value inside it, contains `search`, which
+/// is lowercase.
+fn contains_match(segment: Option<&PathSegment>, value: &Value, search: &str) -> bool {
+    let key_matches =
+        matches!(segment, Some(PathSegment::Key(key)) if key.to_lowercase().contains(search));
+    key_matches
+        || match value {
+            Value::Object(_) | Value::Array(_) => children(value)
+                .iter()
+                .any(|(segment, value)| contains_match(Some(segment), value, search)),
+            Value::String(s) => s.to_lowercase().contains(search),
+            value => value.to_string().contains(search),
+        }
+}
+
+/// A row of the tree shown by a [`data_inspector`].
+#[derive(Clone, Debug, PartialEq)]
+struct Row {
+    path: Vec<PathSegment>,
+    pointer: String,
+    summary: String,
+    expandable: bool,
+    expanded: bool,
+}
+
+impl Row {
+    fn depth(&self) -> usize {
+        self.path.len()
+    }
+
+    fn name(&self) -> Option<String> {
+        self.path.last().map(|segment| match segment {
+            PathSegment::Key(key) => key.clone(),
+            PathSegment::Index(index) => index.to_string(),
+        })
+    }
+}
+
+/// The rows shown for `root`, with the values at the pointers of `expanded` expanded.
+///
+/// When `search` isn't empty, only the values matching it and the values containing them are
+/// shown, and the values containing matches are expanded.
+fn rows(root: &Value, expanded: &HashSet<String>, search: &str) -> Vec<Row> {
+    fn visit(
+        value: &Value,
+        path: &mut Vec<PathSegment>,
+        expanded: &HashSet<String>,
+        search: &str,
+        rows: &mut Vec<Row>,
+    ) {
+        let pointer = json_pointer(path);
+        let expandable = matches!(value, Value::Object(map) if !map.is_empty())
+            || matches!(value, Value::Array(items) if !items.is_empty());
+        let is_expanded = expandable && (!search.is_empty() || expanded.contains(&pointer));
+        rows.push(Row {
+            path: path.clone(),
+            pointer,
+            summary: summary(value),
+            expandable,
+            expanded: is_expanded,
+        });
+        if is_expanded {
+            for (segment, child) in children(value) {
+                if search.is_empty() || contains_match(Some(&segment), child, search) {
+                    path.push(segment);
+                    visit(child, path, expanded, search, rows);
+                    path.pop();
+                }
+            }
+        }
+    }
+
+    let mut rows = Vec::new();
+    if search.is_empty() || contains_match(None, root, search) {
+        visit(root, &mut Vec::new(), expanded, search, &mut rows);
+    }
+    rows
+}
+
+/// A view showing `value` as a tree, whose objects and arrays can be expanded, with a search
+/// field filtering the tree.
+///
+/// The context menu of a row copies the path of its value, like `$.items[0].name`, or the
+/// value as JSON.
+/// ```rust
+/// use floem::{reactive::{RwSignal, SignalGet}, views::data_inspector};
+/// use serde_json::json;
+///
+/// let response = RwSignal::new(json!({ "items": [{ "name": "floem", "stars": 3000 }] }));
+/// data_inspector(move || response.get());
+/// ```
+pub fn data_inspector(value: impl Fn() -> Value + 'static) -> Stack {
+    let root = create_memo(move |_| Rc::new(value()));
+    // The root is expanded at first
+    let expanded = RwSignal::new(HashSet::from([String::new()]));
+    let search = RwSignal::new(String::new());
+    let visible_rows = create_memo(move |_| {
+        let search = search.with(|search| search.trim().to_lowercase());
+        root.with(|root| expanded.with(|expanded| rows(root, expanded, &search)))
+    });
+
+    let row_view = move |row: Row| {
+        let Row {
+            path,
+            pointer,
+            summary,
+            expandable,
+            expanded: is_expanded,
+        } = row.clone();
+        let toggle_pointer = pointer.clone();
+        let toggle = move || {
+            if expandable {
+                expanded.update(|expanded| {
+                    if !expanded.remove(&toggle_pointer) {
+                        expanded.insert(toggle_pointer.clone());
+                    }
+                });
+            }
+        };
+        let arrow = match (expandable, is_expanded) {
+            (false, _) => " ",
+            (true, false) => "▸",
+            (true, true) => "▾",
+        };
+        let is_string = !expandable && summary.starts_with('"');
+        let name = row.name();
+        (
+            label(move || arrow).style(|s| s.width(INDENT)),
+            label(move || {
+                name.clone()
+                    .map(|name| format!("{name}: "))
+                    .unwrap_or_default()
+            })
+            .style(|s| s.color(palette::css::PURPLE)),
+            label(move || summary.clone()).style(move |s| {
+                s.apply_if(expandable, |s| s.color(palette::css::GRAY))
+                    .apply_if(is_string, |s| s.color(palette::css::DARK_GREEN))
+            }),
+        )
+            .h_stack()
+            .class(DataInspectorRowClass)
+            .on_click_stop(move |_| toggle())
+            .context_menu(move || {
+                let copy_path = path_expression(&path);
+                let copy_pointer = pointer.clone();
+                Menu::new("")
+                    .entry(MenuItem::new("Copy Path").action(move || {
+                        let _ = Clipboard::set_contents(copy_path.clone());
+                    }))
+                    .entry(MenuItem::new("Copy Value").action(move || {
+                        let json = root.with_untracked(|root| {
+                            root.pointer(&copy_pointer)
+                                .and_then(|value| serde_json::to_string_pretty(value).ok())
+                        });
+                        if let Some(json) = json {
+                            let _ = Clipboard::set_contents(json);
+                        }
+                    }))
+            })
+            .style(move |s| {
+                s.height(ROW_HEIGHT)
+                    .items_center()
+                    .padding_left(row.depth() as f64 * INDENT)
+                    .apply_if(expandable, |s| s.cursor(CursorStyle::Pointer))
+                    .hover(|s| s.background(palette::css::LIGHT_GRAY.with_alpha(0.3)))
+            })
+    };
+
+    v_stack((
+        text_input(search)
+            .placeholder("Search")
+            .style(|s| s.width_full()),
+        scroll(
+            virtual_stack(
+                move || visible_rows.get().into_iter().collect::<im::Vector<_>>(),
+                |row| (row.pointer.clone(), row.expanded),
+                row_view,
+            )
+            .item_size_fixed(|| ROW_HEIGHT)
+            .style(|s| s.flex_col().min_width_full()),
+        )
+        .style(|s| s.flex_grow(1.0).min_height(0.0)),
+    ))
+    .class(DataInspectorClass)
+    .style(|s| s.gap(4.0).font_family("monospace".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use serde_json::json;
+
+    use super::{path_expression, rows, PathSegment};
+
+    #[test]
+    fn paths() {
+        let path = [
+            PathSegment::Key("items".to_string()),
+            PathSegment::Index(0),
+            PathSegment::Key("first name".to_string()),
+        ];
+        assert_eq!(path_expression(&path), r#"$.items[0]["first name"]"#);
+        assert_eq!(super::json_pointer(&path), "/items/0/first name");
+    }
+
+    #[test]
+    fn tree() {
+        let value = json!({ "a": { "b": 1, "c": "needle" }, "d": [true] });
+        let pointers = |expanded: &[&str], search: &str| {
+            let expanded: HashSet<String> = expanded.iter().map(|p| p.to_string()).collect();
+            rows(&value, &expanded, search)
+                .into_iter()
+                .map(|row| row.pointer)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(pointers(&[], ""), [""]);
+        assert_eq!(pointers(&["", "/d"], ""), ["", "/a", "/d", "/d/0"]);
+        // Searching expands the values containing matches
+        assert_eq!(pointers(&[], "needle"), ["", "/a", "/a/c"]);
+        assert!(pointers(&[], "nothing").is_empty());
+    }
+}
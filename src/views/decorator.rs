@@ -4,16 +4,22 @@
 //!
 //! The decorator trait is the primary interface for extending the appearance and functionality of ['View']s.
 
+use accesskit::{Live, Role, Toggled};
 use floem_reactive::{create_effect, create_updater, SignalUpdate};
 use peniko::kurbo::{Point, Rect};
 use winit::keyboard::Key;
 
 use crate::{
-    action::{set_window_menu, set_window_scale, set_window_title},
+    action::{
+        drag_window, set_window_menu, set_window_scale, set_window_title, toggle_window_maximized,
+    },
     animate::Animation,
+    context::PaintCx,
     event::{Event, EventListener, EventPropagation},
     keyboard::Modifiers,
+    measure::Measure,
     menu::Menu,
+    nav::FocusGroup,
     style::{Style, StyleClass, StyleSelector},
     view::{IntoView, View},
 };
@@ -175,6 +181,22 @@ pub trait Decorators: IntoView<V = Self::DV> + Sized {
         view
     }
 
+    /// Make the view move the window when it's dragged with the primary mouse button, and toggle
+    /// the maximized state of the window when it's double clicked.
+    ///
+    /// This is meant for custom title bars of windows with the title bar turned off. Children
+    /// which handle pointer events themselves, such as buttons, keep working as usual.
+    fn drag_window_area(self) -> Self::DV {
+        self.on_event_stop(EventListener::PointerDown, |e| {
+            if let Event::PointerDown(input_event) = e {
+                if input_event.button.is_primary() {
+                    drag_window();
+                }
+            }
+        })
+        .on_double_click_stop(|_| toggle_window_maximized())
+    }
+
     /// Mark the view as disabled
     ///
     /// # Reactivity
@@ -191,6 +213,84 @@ pub trait Decorators: IntoView<V = Self::DV> + Sized {
         view
     }
 
+    /// Make this view a focus group: the arrow keys move the focus between the keyboard
+    /// navigable views inside it, while <kbd>Tab</kbd> moves the focus out of it.
+    ///
+    /// See [`FocusGroup`] for the keys handled by the group.
+    fn focus_group(self, group: FocusGroup) -> Self::DV {
+        let view = self.into_view();
+        view.id().state().borrow_mut().focus_group = Some(group);
+        view
+    }
+
+    /// Set the accessibility role of the view, overriding the role set by the view itself.
+    ///
+    /// See [`crate::accessibility`].
+    fn accessibility_role(self, role: Role) -> Self::DV {
+        let view = self.into_view();
+        view.id()
+            .update_accessibility(|props| props.role = Some(role));
+        view
+    }
+
+    /// Set the label read by assistive technology for the view.
+    ///
+    /// # Reactivity
+    /// The `label` function is reactive.
+    fn accessibility_label(self, label: impl Fn() -> String + 'static) -> Self::DV {
+        let view = self.into_view();
+        let id = view.id();
+        create_effect(move |_| {
+            let label = label();
+            id.update_accessibility(|props| props.label = Some(label));
+        });
+        view
+    }
+
+    /// Set the value read by assistive technology for the view, e.g. the current
+    /// selection of a dropdown.
+    ///
+    /// # Reactivity
+    /// The `value` function is reactive.
+    fn accessibility_value(self, value: impl Fn() -> String + 'static) -> Self::DV {
+        let view = self.into_view();
+        let id = view.id();
+        create_effect(move |_| {
+            let value = value();
+            id.update_accessibility(|props| props.value = Some(value));
+        });
+        view
+    }
+
+    /// Expose the view as checked or unchecked, for views which act like a checkbox.
+    ///
+    /// # Reactivity
+    /// The `toggled` function is reactive.
+    fn accessibility_toggled(self, toggled: impl Fn() -> bool + 'static) -> Self::DV {
+        let view = self.into_view();
+        let id = view.id();
+        create_effect(move |_| {
+            let toggled = toggled();
+            id.update_accessibility(|props| {
+                props.toggled = Some(if toggled {
+                    Toggled::True
+                } else {
+                    Toggled::False
+                })
+            });
+        });
+        view
+    }
+
+    /// Mark the view as a live region, whose changes are announced by assistive
+    /// technology even if the view isn't focused, e.g. a status message.
+    fn accessibility_live(self, live: Live) -> Self::DV {
+        let view = self.into_view();
+        view.id()
+            .update_accessibility(|props| props.live = Some(live));
+        view
+    }
+
     /// Add an event handler for the given [`EventListener`].
     fn on_event(
         self,
@@ -329,9 +429,10 @@ pub trait Decorators: IntoView<V = Self::DV> + Sized {
         })
     }
 
-    /// Set the event handler for resize events for this view.
+    /// Add an event handler for resize events for this view.
     ///
-    /// There can only be one resize event handler for a view.
+    /// The action is called with the layout rect of the view, relative to its parent, when
+    /// it's first laid out and whenever it changes. A view can have several resize handlers.
     ///
     /// # Reactivity
     /// The action will be called whenever the view is resized but will not rerun automatically in response to signal changes
@@ -339,13 +440,14 @@ pub trait Decorators: IntoView<V = Self::DV> + Sized {
         let view = self.into_view();
         let id = view.id();
         let state = id.state();
-        state.borrow_mut().update_resize_listener(Box::new(action));
+        state.borrow_mut().add_resize_listener(Box::new(action));
         view
     }
 
-    /// Set the event handler for move events for this view.
+    /// Add an event handler for move events for this view.
     ///
-    /// There can only be one move event handler for a view.
+    /// The action is called with the origin of the view in the window whenever it changes.
+    /// A view can have several move handlers.
     ///
     /// # Reactivity
     /// The action will be called whenever the view is moved but will not rerun automatically in response to signal changes
@@ -353,7 +455,83 @@ pub trait Decorators: IntoView<V = Self::DV> + Sized {
         let view = self.into_view();
         let id = view.id();
         let state = id.state();
-        state.borrow_mut().update_move_listener(Box::new(action));
+        state.borrow_mut().add_move_listener(Box::new(action));
+        view
+    }
+
+    /// Add an event handler called with `true` when this view enters the visible area of its
+    /// [`scroll`](crate::views::scroll) ancestors and the window, and with `false` when it
+    /// leaves it.
+    ///
+    /// The view counts as visible once the fraction of its area which is visible reaches
+    /// `threshold`, between `0.0`, for any part of the view, and `1.0`, for the whole view.
+    /// Hidden views aren't visible.
+    ///
+    /// This can be used to load images only once they're scrolled to, or to pause animations
+    /// which are offscreen.
+    ///
+    /// # Reactivity
+    /// The action will be called whenever the visibility changes but will not rerun automatically in response to signal changes
+    fn on_visibility_change(self, threshold: f64, action: impl Fn(bool) + 'static) -> Self::DV {
+        let view = self.into_view();
+        let id = view.id();
+        let state = id.state();
+        state
+            .borrow_mut()
+            .add_visibility_listener(threshold, Box::new(action));
+        view
+    }
+
+    /// Add a function painting below the content of this view, after its background.
+    ///
+    /// The function is called with the paint context, transformed to the coordinates of the
+    /// view, and the rect of the view. Several functions are called in the order they were
+    /// added.
+    ///
+    /// # Reactivity
+    /// The function is called whenever the view is painted, but signal changes don't repaint
+    /// the view; use [`ViewId::request_paint`](crate::ViewId::request_paint) for that.
+    fn on_paint_under(self, paint: impl Fn(&mut PaintCx, Rect) + 'static) -> Self::DV {
+        let view = self.into_view();
+        let id = view.id();
+        let state = id.state();
+        state.borrow_mut().add_paint_under_listener(Box::new(paint));
+        view
+    }
+
+    /// Add a function painting above this view and its children, after its border and
+    /// outline, like a badge or a selection outline.
+    ///
+    /// The function is called like the ones of [`on_paint_under`](Self::on_paint_under).
+    ///
+    /// ```rust
+    /// use floem::{peniko::{color::palette, kurbo::Circle}, views::{label, Decorators}, Renderer};
+    ///
+    /// label(|| "Inbox").on_paint_over(|cx, rect| {
+    ///     // An unread badge at the top right corner
+    ///     cx.fill(&Circle::new((rect.x1, rect.y0), 3.0), palette::css::RED, 0.0);
+    /// });
+    /// ```
+    ///
+    /// # Reactivity
+    /// The function is called whenever the view is painted, but signal changes don't repaint
+    /// the view; use [`ViewId::request_paint`](crate::ViewId::request_paint) for that.
+    fn on_paint_over(self, paint: impl Fn(&mut PaintCx, Rect) + 'static) -> Self::DV {
+        let view = self.into_view();
+        let id = view.id();
+        let state = id.state();
+        state.borrow_mut().add_paint_over_listener(Box::new(paint));
+        view
+    }
+
+    /// Keeps `measure` up to date with the layout of this view, see [`use_measure`].
+    ///
+    /// [`use_measure`]: crate::measure::use_measure
+    fn measure(self, measure: Measure) -> Self::DV {
+        let view = self.into_view();
+        let id = view.id();
+        id.add_resize_listener(Box::new(move |rect| measure.set_rect(rect)));
+        id.add_move_listener(Box::new(move |origin| measure.set_window_origin(origin)));
         view
     }
 
@@ -463,12 +641,13 @@ pub trait Decorators: IntoView<V = Self::DV> + Sized {
     /// This internally calls the [`crate::action::set_window_menu`] function.
     ///
     /// Platform support:
-    /// - Windows: No
-    /// - macOS: Yes (not currently implemented)
-    /// - Linux: No
+    /// - Windows: Yes
+    /// - macOS: Yes, the menu of the focused window is shown in the application menu bar
+    /// - Linux: Uses a custom Floem View at the top of the window
     ///
     /// # Reactivity
     /// The menu function is reactive and will rereun in response to any signal changes in the function.
+    /// This can be used to bind the enabled or checked state of the items to signals.
     fn window_menu(self, menu_fn: impl Fn() -> Menu + 'static) -> Self {
         create_effect(move |_| {
             let menu = menu_fn();
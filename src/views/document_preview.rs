@@ -0,0 +1,500 @@
+//! A preview of the document of an editor, like rendered markdown next to its source.
+use std::{cell::RefCell, rc::Rc, time::Duration};
+
+use floem_reactive::{create_effect, RwSignal, SignalGet, SignalTrack, SignalUpdate, SignalWith};
+use peniko::{kurbo::Point, Color};
+
+use crate::{
+    action::{exec_after, TimerToken},
+    style::{CursorStyle, FlexWrap, Style},
+    style_class,
+    text::{Style as FontStyle, Weight},
+    view::IntoView,
+    views::{
+        dyn_container, empty, h_stack_from_iter, label, scroll, v_stack_from_iter, Decorators,
+        Scroll, Stack,
+    },
+};
+
+use super::editor::Editor;
+
+style_class!(
+    /// Style class of a [`document_preview`]
+    pub DocumentPreviewClass
+);
+
+style_class!(
+    /// Style class of the links of a [`document_preview`]
+    pub PreviewLinkClass
+);
+
+style_class!(
+    /// Style class of the code blocks and inline code of a [`document_preview`]
+    pub PreviewCodeClass
+);
+
+style_class!(
+    /// Style class of the quotes of a [`document_preview`]
+    pub PreviewQuoteClass
+);
+
+/// Text inside a [`PreviewBlock`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Inline {
+    Text(String),
+    Strong(String),
+    Emphasis(String),
+    Code(String),
+    Link { text: String, url: String },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BlockKind {
+    /// A heading and its level, from 1 to 6
+    Heading(u8, Vec<Inline>),
+    Paragraph(Vec<Inline>),
+    ListItem(Vec<Inline>),
+    Quote(Vec<Inline>),
+    Code(String),
+    Rule,
+}
+
+/// A block of a preview, with the line of the document it starts at, which is used to scroll
+/// the preview along with the editor.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PreviewBlock {
+    pub line: usize,
+    pub kind: BlockKind,
+}
+
+type TransformFn = dyn Fn(&str) -> Vec<PreviewBlock>;
+type LinkFn = dyn Fn(&str);
+
+/// Parses the inline markup of markdown: code, strong and emphasized text, and links.
+fn parse_inlines(text: &str) -> Vec<Inline> {
+    let mut inlines = Vec::new();
+    let mut plain = String::new();
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        let parsed = match c {
+            '`' => rest[1..]
+                .find('`')
+                .map(|end| (Inline::Code(rest[1..end + 1].to_string()), end + 2)),
+            '*' | '_' if rest[1..].starts_with(c) => {
+                let delimiter = &rest[..2];
+                rest[2..]
+                    .find(delimiter)
+                    .map(|end| (Inline::Strong(rest[2..end + 2].to_string()), end + 4))
+            }
+            '*' | '_' => rest[1..]
+                .find(c)
+                .map(|end| (Inline::Emphasis(rest[1..end + 1].to_string()), end + 2)),
+            '[' => rest.find("](").and_then(|middle| {
+                let end = middle + rest[middle..].find(')')?;
+                let link = Inline::Link {
+                    text: rest[1..middle].to_string(),
+                    url: rest[middle + 2..end].to_string(),
+                };
+                Some((link, end + 1))
+            }),
+            _ => None,
+        };
+        match parsed {
+            Some((inline, len)) => {
+                if !plain.is_empty() {
+                    inlines.push(Inline::Text(std::mem::take(&mut plain)));
+                }
+                inlines.push(inline);
+                rest = &rest[len..];
+            }
+            None => {
+                plain.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+    if !plain.is_empty() {
+        inlines.push(Inline::Text(plain));
+    }
+    inlines
+}
+
+/// Parses the common subset of markdown: headings, paragraphs, lists, quotes, fenced code
+/// blocks, rules, and the inline markup of [`Inline`].
+pub fn parse_markdown(text: &str) -> Vec<PreviewBlock> {
+    let mut blocks = Vec::new();
+    // The paragraph being read, with its first line
+    let mut paragraph: Option<(usize, String)> = None;
+    let mut code: Option<(usize, String)> = None;
+
+    let end_paragraph = |paragraph: &mut Option<(usize, String)>, blocks: &mut Vec<_>| {
+        if let Some((line, text)) = paragraph.take() {
+            blocks.push(PreviewBlock {
+                line,
+                kind: BlockKind::Paragraph(parse_inlines(&text)),
+            });
+        }
+    };
+
+    for (line, content) in text.lines().enumerate() {
+        let trimmed = content.trim();
+        if let Some((start, mut code_text)) = code.take() {
+            if trimmed.starts_with("```") {
+                code_text.pop();
+                blocks.push(PreviewBlock {
+                    line: start,
+                    kind: BlockKind::Code(code_text),
+                });
+            } else {
+                code_text.push_str(content);
+                code_text.push('\n');
+                code = Some((start, code_text));
+            }
+            continue;
+        }
+
+        let heading_level = trimmed.chars().take_while(|c| *c == '#').count();
+        let kind = if trimmed.starts_with("```") {
+            end_paragraph(&mut paragraph, &mut blocks);
+            code = Some((line, String::new()));
+            continue;
+        } else if trimmed.is_empty() {
+            end_paragraph(&mut paragraph, &mut blocks);
+            continue;
+        } else if (1..=6).contains(&heading_level) && trimmed[heading_level..].starts_with(' ') {
+            BlockKind::Heading(
+                heading_level as u8,
+                parse_inlines(trimmed[heading_level..].trim()),
+            )
+        } else if trimmed.len() >= 3
+            && (trimmed.chars().all(|c| c == '-') || trimmed.chars().all(|c| c == '*'))
+        {
+            BlockKind::Rule
+        } else if let Some(item) = trimmed
+            .strip_prefix("- ")
+            .or_else(|| trimmed.strip_prefix("* "))
+            .or_else(|| trimmed.strip_prefix("+ "))
+        {
+            BlockKind::ListItem(parse_inlines(item))
+        } else if let Some(quote) = trimmed.strip_prefix('>') {
+            BlockKind::Quote(parse_inlines(quote.trim()))
+        } else {
+            match &mut paragraph {
+                Some((_, text)) => {
+                    text.push(' ');
+                    text.push_str(trimmed);
+                }
+                None => paragraph = Some((line, trimmed.to_string())),
+            }
+            continue;
+        };
+        end_paragraph(&mut paragraph, &mut blocks);
+        blocks.push(PreviewBlock { line, kind });
+    }
+    end_paragraph(&mut paragraph, &mut blocks);
+    // An unclosed code block runs until the end of the document
+    if let Some((line, text)) = code {
+        blocks.push(PreviewBlock {
+            line,
+            kind: BlockKind::Code(text),
+        });
+    }
+    blocks
+}
+
+/// The font size of a heading of `level`.
+fn heading_size(level: u8) -> f32 {
+    match level {
+        1 => 26.0,
+        2 => 22.0,
+        3 => 18.0,
+        _ => 15.0,
+    }
+}
+
+fn inline_views(inlines: Vec<Inline>, on_link: Rc<RefCell<Option<Rc<LinkFn>>>>) -> Stack {
+    // Every word is a label, so that the text wraps between the words
+    let words = inlines.into_iter().flat_map(move |inline| {
+        let (text, url) = match &inline {
+            Inline::Text(text)
+            | Inline::Strong(text)
+            | Inline::Emphasis(text)
+            | Inline::Code(text) => (text.clone(), None),
+            Inline::Link { text, url } => (text.clone(), Some(url.clone())),
+        };
+        let on_link = on_link.clone();
+        text.split_inclusive(' ')
+            .map(move |word| {
+                let word = word.to_string();
+                let view = label(move || word.clone());
+                let view = match &inline {
+                    Inline::Strong(_) => view.style(|s| s.font_bold()),
+                    Inline::Emphasis(_) => view.style(|s| s.font_style(FontStyle::Italic)),
+                    Inline::Code(_) => view.class(PreviewCodeClass),
+                    _ => view,
+                };
+                match url.clone() {
+                    Some(url) => {
+                        let on_link = on_link.clone();
+                        view.class(PreviewLinkClass)
+                            .on_click_stop(move |_| {
+                                let on_link = on_link.borrow().clone();
+                                if let Some(on_link) = on_link {
+                                    on_link(&url);
+                                }
+                            })
+                            .style(|s| s.cursor(CursorStyle::Pointer))
+                            .into_any()
+                    }
+                    None => view.into_any(),
+                }
+            })
+            .collect::<Vec<_>>()
+    });
+    h_stack_from_iter(words).style(|s| s.flex_wrap(FlexWrap::Wrap))
+}
+
+fn block_view(block: PreviewBlock, on_link: Rc<RefCell<Option<Rc<LinkFn>>>>) -> impl IntoView {
+    match block.kind {
+        BlockKind::Heading(level, inlines) => inline_views(inlines, on_link)
+            .style(move |s| s.font_size(heading_size(level)).font_weight(Weight::BOLD))
+            .into_any(),
+        BlockKind::Paragraph(inlines) => inline_views(inlines, on_link).into_any(),
+        BlockKind::ListItem(inlines) => h_stack_from_iter([
+            label(|| "\u{2022}")
+                .style(|s| s.width(16.0).flex_shrink(0.0))
+                .into_any(),
+            inline_views(inlines, on_link).into_any(),
+        ])
+        .into_any(),
+        BlockKind::Quote(inlines) => inline_views(inlines, on_link)
+            .class(PreviewQuoteClass)
+            .into_any(),
+        BlockKind::Code(code) => label(move || code.clone())
+            .class(PreviewCodeClass)
+            .style(|s| s.width_full())
+            .into_any(),
+        BlockKind::Rule => empty()
+            .style(|s| {
+                s.width_full()
+                    .height(1.0)
+                    .background(Color::from_rgb8(200, 200, 200))
+            })
+            .into_any(),
+    }
+}
+
+/// The scroll offset of the preview which shows `line` at its top, interpolated between the
+/// `(line, top)` of the blocks.
+fn preview_offset(blocks: &[(usize, f64)], line: f64) -> f64 {
+    let next = blocks.partition_point(|(block_line, _)| (*block_line as f64) <= line);
+    match (next.checked_sub(1).map(|i| blocks[i]), blocks.get(next)) {
+        (Some((start_line, start)), Some((end_line, end))) => {
+            let fraction = (line - start_line as f64) / (end_line - start_line) as f64;
+            start + (end - start) * fraction
+        }
+        (Some((_, top)), None) => top,
+        (None, _) => 0.0,
+    }
+}
+
+/// A preview of the document of an editor, see [`document_preview`].
+pub struct DocumentPreview {
+    scroll: Scroll,
+    on_link: Rc<RefCell<Option<Rc<LinkFn>>>>,
+    debounce: RwSignal<Duration>,
+    scroll_sync: RwSignal<bool>,
+}
+
+/// A rendered preview of the markdown in the document of `editor`, see
+/// [`document_preview_with`].
+pub fn document_preview(editor: &Editor) -> DocumentPreview {
+    document_preview_with(editor, parse_markdown)
+}
+
+/// A preview of the document of `editor`, made of the blocks returned by `transform` for its
+/// text.
+///
+/// The preview is updated once the document wasn't edited for a while, and scrolls along with
+/// the editor so that it shows the blocks of the lines at the top of the editor.
+/// ```rust
+/// use floem::views::{document_preview, h_stack, text_editor};
+///
+/// let editor = text_editor("# Title\n\nSee [the docs](https://docs.rs/floem).");
+/// let preview = document_preview(editor.editor())
+///     .on_link_click(|url| println!("open {url}"))
+///     .style(|s| s.width(300.0));
+/// h_stack((editor, preview));
+/// ```
+pub fn document_preview_with(
+    editor: &Editor,
+    transform: impl Fn(&str) -> Vec<PreviewBlock> + 'static,
+) -> DocumentPreview {
+    let transform: Rc<TransformFn> = Rc::new(transform);
+    let on_link: Rc<RefCell<Option<Rc<LinkFn>>>> = Rc::new(RefCell::new(None));
+    let debounce = RwSignal::new(Duration::from_millis(200));
+    let scroll_sync = RwSignal::new(true);
+
+    let doc = editor.doc;
+    let blocks = RwSignal::new(transform(&doc.get_untracked().text().to_string()));
+    create_effect(move |timer: Option<TimerToken>| {
+        let doc = doc.get();
+        doc.cache_rev().track();
+        if let Some(timer) = timer {
+            if timer != TimerToken::INVALID {
+                timer.cancel();
+            }
+            let transform = transform.clone();
+            exec_after(debounce.get_untracked(), move |_| {
+                blocks.set(transform(&doc.text().to_string()));
+            })
+        } else {
+            // The first preview is made right away
+            TimerToken::INVALID
+        }
+    });
+
+    // The layout of the blocks, as the line each block starts at and its top
+    let tops: RwSignal<Vec<(usize, f64)>> = RwSignal::new(Vec::new());
+    let scroll_to = RwSignal::new(None);
+    let editor = editor.clone();
+    create_effect(move |_| {
+        if !scroll_sync.get() {
+            return;
+        }
+        let y = editor.viewport.with(|viewport| viewport.y0);
+        let line = y / f64::from(editor.line_height(0));
+        let offset = tops.with(|tops| preview_offset(tops, line));
+        scroll_to.set(Some(Point::new(0.0, offset)));
+    });
+
+    let link_handler = on_link.clone();
+    let content = dyn_container(
+        move || blocks.get(),
+        move |blocks| {
+            tops.set(blocks.iter().map(|block| (block.line, 0.0)).collect());
+            let on_link = link_handler.clone();
+            v_stack_from_iter(blocks.into_iter().enumerate().map(move |(i, block)| {
+                block_view(block, on_link.clone()).on_resize(move |rect| {
+                    tops.update(|tops| {
+                        if let Some((_, top)) = tops.get_mut(i) {
+                            *top = rect.y0;
+                        }
+                    })
+                })
+            }))
+            .style(|s| s.width_full().row_gap(8.0))
+        },
+    )
+    .style(|s| s.width_full());
+
+    DocumentPreview {
+        scroll: scroll(content)
+            .scroll_to(move || scroll_to.get())
+            .class(DocumentPreviewClass)
+            .style(|s| {
+                s.padding(8.0)
+                    .class(PreviewCodeClass, |s| {
+                        s.font_family("monospace".to_string())
+                            .padding_horiz(3.0)
+                            .border_radius(3.0)
+                            .background(Color::from_rgb8(238, 238, 238))
+                    })
+                    .class(PreviewLinkClass, |s| s.color(Color::from_rgb8(9, 105, 218)))
+                    .class(PreviewQuoteClass, |s| {
+                        s.padding_left(8.0)
+                            .border_left(3.0)
+                            .border_color(Color::from_rgb8(208, 208, 208))
+                            .color(Color::from_rgb8(100, 100, 100))
+                    })
+            }),
+        on_link,
+        debounce,
+        scroll_sync,
+    }
+}
+
+impl DocumentPreview {
+    /// Sets how long the document has to stay unchanged before the preview is updated,
+    /// 200ms by default.
+    pub fn debounce(self, debounce: Duration) -> Self {
+        self.debounce.set(debounce);
+        self
+    }
+
+    /// Sets whether the preview scrolls along with the editor, `true` by default.
+    pub fn scroll_sync(self, scroll_sync: bool) -> Self {
+        self.scroll_sync.set(scroll_sync);
+        self
+    }
+
+    /// Sets the handler called with the url of a link when it's clicked.
+    pub fn on_link_click(self, on_link: impl Fn(&str) + 'static) -> Self {
+        *self.on_link.borrow_mut() = Some(Rc::new(on_link));
+        self
+    }
+
+    /// Styles the scroll view of the preview.
+    pub fn style(mut self, style: impl Fn(Style) -> Style + 'static) -> Self {
+        self.scroll = self.scroll.style(style);
+        self
+    }
+}
+
+impl IntoView for DocumentPreview {
+    type V = Scroll;
+
+    fn into_view(self) -> Self::V {
+        self.scroll
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_markdown, preview_offset, BlockKind, Inline};
+
+    #[test]
+    fn markdown() {
+        let blocks = parse_markdown(
+            "# Title\n\nSome **bold** text,\nand a [link](url).\n\n- item\n```\ncode\n```\n---",
+        );
+        let kinds: Vec<_> = blocks
+            .iter()
+            .map(|block| (block.line, &block.kind))
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                (
+                    0,
+                    &BlockKind::Heading(1, vec![Inline::Text("Title".into())])
+                ),
+                (
+                    2,
+                    &BlockKind::Paragraph(vec![
+                        Inline::Text("Some ".into()),
+                        Inline::Strong("bold".into()),
+                        Inline::Text(" text, and a ".into()),
+                        Inline::Link {
+                            text: "link".into(),
+                            url: "url".into()
+                        },
+                        Inline::Text(".".into()),
+                    ])
+                ),
+                (5, &BlockKind::ListItem(vec![Inline::Text("item".into())])),
+                (6, &BlockKind::Code("code".into())),
+                (9, &BlockKind::Rule),
+            ]
+        );
+    }
+
+    #[test]
+    fn offsets() {
+        let blocks = [(0, 0.0), (4, 100.0), (10, 160.0)];
+        assert_eq!(preview_offset(&blocks, 2.0), 50.0);
+        assert_eq!(preview_offset(&blocks, 7.0), 130.0);
+        assert_eq!(preview_offset(&blocks, 20.0), 160.0);
+        assert_eq!(preview_offset(&[], 3.0), 0.0);
+    }
+}
@@ -1,11 +1,14 @@
+use peniko::kurbo::Point;
 use winit::window::ResizeDirection;
 
 use crate::{
     action::drag_resize_window,
-    event::EventListener,
+    context::EventCx,
+    event::{Event, EventListener, EventPropagation},
     id::ViewId,
     style::CursorStyle,
     view::{IntoView, View},
+    WindowIdExt,
 };
 
 use super::Decorators;
@@ -36,19 +39,7 @@ pub fn drag_resize_window_area<V: IntoView + 'static>(
         .on_event_stop(EventListener::PointerDown, move |_| {
             drag_resize_window(direction)
         })
-        .style(move |s| {
-            let cursor = match direction {
-                ResizeDirection::East => CursorStyle::ColResize,
-                ResizeDirection::West => CursorStyle::ColResize,
-                ResizeDirection::North => CursorStyle::RowResize,
-                ResizeDirection::South => CursorStyle::RowResize,
-                ResizeDirection::NorthEast => CursorStyle::NeswResize,
-                ResizeDirection::SouthWest => CursorStyle::NeswResize,
-                ResizeDirection::SouthEast => CursorStyle::NwseResize,
-                ResizeDirection::NorthWest => CursorStyle::NwseResize,
-            };
-            s.cursor(cursor)
-        })
+        .style(move |s| s.cursor(resize_cursor(direction)))
 }
 
 impl View for DragResizeWindowArea {
@@ -60,3 +51,106 @@ impl View for DragResizeWindowArea {
         "Drag-Resize Window Area".into()
     }
 }
+
+/// A view that resizes the window when the mouse is dragged from within `border_width` of its edges.
+/// See [`window_resize_border`].
+///
+/// ## Platform-specific
+///
+/// - **macOS:** Not supported.
+/// - **iOS / Android / Web / Orbital:** Not supported.
+pub struct WindowResizeBorder {
+    id: ViewId,
+    border_width: f64,
+}
+
+/// A view that resizes the window when the mouse is dragged from within `border_width` of its edges.
+///
+/// This is meant to wrap the whole content of an undecorated window, so that the window can still
+/// be resized from its borders. The borders take precedence over the children, and are disabled while
+/// the window is maximized.
+///
+/// ## Platform-specific
+///
+/// - **macOS:** Not supported.
+/// - **iOS / Android / Web / Orbital:** Not supported.
+pub fn window_resize_border<V: IntoView + 'static>(
+    border_width: f64,
+    child: V,
+) -> WindowResizeBorder {
+    let id = ViewId::new();
+    id.set_children(vec![child.into_view()]);
+    WindowResizeBorder { id, border_width }
+}
+
+impl WindowResizeBorder {
+    /// Returns the resize direction for a position relative to this view.
+    fn hit_test(&self, pos: Point) -> Option<ResizeDirection> {
+        let is_maximized = self
+            .id
+            .window_id()
+            .map(|window_id| window_id.is_maximized())
+            .unwrap_or(false);
+        if is_maximized {
+            return None;
+        }
+        let size = self.id.get_size()?;
+        let left = pos.x <= self.border_width;
+        let right = pos.x >= size.width - self.border_width;
+        let top = pos.y <= self.border_width;
+        let bottom = pos.y >= size.height - self.border_width;
+        match (left, right, top, bottom) {
+            (true, _, true, _) => Some(ResizeDirection::NorthWest),
+            (_, true, true, _) => Some(ResizeDirection::NorthEast),
+            (true, _, _, true) => Some(ResizeDirection::SouthWest),
+            (_, true, _, true) => Some(ResizeDirection::SouthEast),
+            (true, _, _, _) => Some(ResizeDirection::West),
+            (_, true, _, _) => Some(ResizeDirection::East),
+            (_, _, true, _) => Some(ResizeDirection::North),
+            (_, _, _, true) => Some(ResizeDirection::South),
+            _ => None,
+        }
+    }
+}
+
+impl View for WindowResizeBorder {
+    fn id(&self) -> ViewId {
+        self.id
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "Window Resize Border".into()
+    }
+
+    fn event_before_children(&mut self, cx: &mut EventCx, event: &Event) -> EventPropagation {
+        match event {
+            Event::PointerMove(pointer_event) => {
+                if let Some(direction) = self.hit_test(pointer_event.pos) {
+                    cx.app_state.cursor = Some(resize_cursor(direction));
+                    return EventPropagation::Stop;
+                }
+            }
+            Event::PointerDown(pointer_event) if pointer_event.button.is_primary() => {
+                if let Some(direction) = self.hit_test(pointer_event.pos) {
+                    drag_resize_window(direction);
+                    return EventPropagation::Stop;
+                }
+            }
+            _ => {}
+        }
+        EventPropagation::Continue
+    }
+}
+
+fn resize_cursor(direction: ResizeDirection) -> CursorStyle {
+    match direction {
+        ResizeDirection::East => CursorStyle::ColResize,
+        ResizeDirection::West => CursorStyle::ColResize,
+        ResizeDirection::North => CursorStyle::RowResize,
+        ResizeDirection::South => CursorStyle::RowResize,
+        ResizeDirection::NorthEast => CursorStyle::NeswResize,
+        ResizeDirection::SouthWest => CursorStyle::NeswResize,
+        ResizeDirection::SouthEast => CursorStyle::NwseResize,
+        ResizeDirection::NorthWest => CursorStyle::NwseResize,
+    }
+}
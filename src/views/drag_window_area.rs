@@ -1,6 +1,4 @@
 use crate::{
-    action::{drag_window, toggle_window_maximized},
-    event::{Event, EventListener},
     id::ViewId,
     view::{IntoView, View},
 };
@@ -18,15 +16,7 @@ pub struct DragWindowArea {
 pub fn drag_window_area<V: IntoView + 'static>(child: V) -> DragWindowArea {
     let id = ViewId::new();
     id.set_children(vec![child]);
-    DragWindowArea { id }
-        .on_event_stop(EventListener::PointerDown, |e| {
-            if let Event::PointerDown(input_event) = e {
-                if input_event.button.is_primary() {
-                    drag_window();
-                }
-            }
-        })
-        .on_double_click_stop(|_| toggle_window_maximized())
+    DragWindowArea { id }.drag_window_area()
 }
 impl View for DragWindowArea {
     fn id(&self) -> ViewId {
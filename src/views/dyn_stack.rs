@@ -3,7 +3,9 @@ use std::{
     marker::PhantomData,
 };
 
-use floem_reactive::{as_child_of_current_scope, create_effect, Scope};
+use floem_reactive::{
+    as_child_of_current_scope, create_effect, MapDelta, RwSignalMap, RwSignalVec, Scope, VecDelta,
+};
 use rustc_hash::FxHasher;
 use smallvec::SmallVec;
 
@@ -125,6 +127,183 @@ where
     }
 }
 
+/// A stack which shows the items of a [`RwSignalVec`].
+///
+/// Unlike [`dyn_stack`], which compares all keys whenever the items change, this applies
+/// the recorded changes of the `RwSignalVec` directly, so pushing one item creates one view
+/// and leaves the others alone. Replacing an item with [`RwSignalVec::set`] recreates its
+/// view.
+///
+/// ## Example
+/// ```
+/// use floem::reactive::*;
+/// use floem::views::*;
+///
+/// let items = create_signal_vec(vec![1, 2, 3]);
+///
+/// dyn_stack_signal_vec(items, move |item| label(move || item));
+///
+/// items.push(4);
+/// ```
+pub fn dyn_stack_signal_vec<T, VF, V>(items: RwSignalVec<T>, view_fn: VF) -> DynStack<T>
+where
+    T: Clone + 'static,
+    VF: Fn(T) -> V + 'static,
+    V: IntoView + 'static,
+{
+    let id = ViewId::new();
+    // The version of the items shown and their number
+    create_effect(move |prev: Option<(u64, usize)>| {
+        match prev.and_then(|(version, len)| Some((items.deltas_since(version)?, len))) {
+            Some((deltas, mut len)) => {
+                for delta in deltas {
+                    let diff = vec_delta_diff(delta, &mut len);
+                    if !diff.is_empty() {
+                        id.update_state(diff);
+                    }
+                }
+                (items.version(), len)
+            }
+            None => {
+                let mut diff = Diff {
+                    clear: prev.is_some(),
+                    ..Default::default()
+                };
+                items.with(|items| {
+                    for (at, item) in items.iter().enumerate() {
+                        diff.added.push(DiffOpAdd {
+                            at,
+                            view: Some(item.clone()),
+                        });
+                    }
+                });
+                let len = diff.added.len();
+                id.update_state(diff);
+                (items.version(), len)
+            }
+        }
+    });
+    let view_fn = Box::new(as_child_of_current_scope(move |e| view_fn(e).into_any()));
+    DynStack {
+        id,
+        children: Vec::new(),
+        view_fn,
+        phantom: PhantomData,
+    }
+}
+
+/// A stack which shows the entries of a [`RwSignalMap`] in insertion order, see
+/// [`dyn_stack_signal_vec`].
+pub fn dyn_stack_signal_map<K, T, VF, V>(
+    entries: RwSignalMap<K, T>,
+    view_fn: VF,
+) -> DynStack<(K, T)>
+where
+    K: Eq + Hash + Clone + 'static,
+    T: Clone + 'static,
+    VF: Fn(K, T) -> V + 'static,
+    V: IntoView + 'static,
+{
+    let id = ViewId::new();
+    create_effect(move |prev: Option<(u64, usize)>| {
+        match prev.and_then(|(version, len)| Some((entries.deltas_since(version)?, len))) {
+            Some((deltas, mut len)) => {
+                for delta in deltas {
+                    let delta = match delta {
+                        MapDelta::Insert { index, key, value } => VecDelta::Insert {
+                            index,
+                            value: (key, value),
+                        },
+                        MapDelta::Remove { index, .. } => VecDelta::Remove { index },
+                        MapDelta::Set { index, key, value } => VecDelta::Set {
+                            index,
+                            value: (key, value),
+                        },
+                        MapDelta::Clear => VecDelta::Clear,
+                    };
+                    id.update_state(vec_delta_diff(delta, &mut len));
+                }
+                (entries.version(), len)
+            }
+            None => {
+                let mut diff = Diff {
+                    clear: prev.is_some(),
+                    ..Default::default()
+                };
+                entries.with(|entries| {
+                    for (at, entry) in entries.iter().enumerate() {
+                        diff.added.push(DiffOpAdd {
+                            at,
+                            view: Some(entry.clone()),
+                        });
+                    }
+                });
+                let len = diff.added.len();
+                id.update_state(diff);
+                (entries.version(), len)
+            }
+        }
+    });
+    let view_fn = Box::new(as_child_of_current_scope(move |(key, value)| {
+        view_fn(key, value).into_any()
+    }));
+    DynStack {
+        id,
+        children: Vec::new(),
+        view_fn,
+        phantom: PhantomData,
+    }
+}
+
+/// Converts a change of a [`RwSignalVec`] with `len` items into the diff of the children,
+/// and updates `len` to the length after the change.
+fn vec_delta_diff<T>(delta: VecDelta<T>, len: &mut usize) -> Diff<T> {
+    let mut diff = Diff::default();
+    match delta {
+        VecDelta::Insert { index, value } => {
+            // Make room by shifting the following children
+            for from in index..*len {
+                diff.moved.push(DiffOpMove { from, to: from + 1 });
+            }
+            diff.added.push(DiffOpAdd {
+                at: index,
+                view: Some(value),
+            });
+            *len += 1;
+        }
+        VecDelta::Remove { index } => {
+            diff.removed.push(DiffOpRemove { at: index });
+            *len -= 1;
+        }
+        VecDelta::Set { index, value } => {
+            diff.removed.push(DiffOpRemove { at: index });
+            diff.added.push(DiffOpAdd {
+                at: index,
+                view: Some(value),
+            });
+        }
+        VecDelta::Move { from, to } => {
+            if from != to {
+                diff.moved.push(DiffOpMove { from, to });
+                if from < to {
+                    for i in from + 1..=to {
+                        diff.moved.push(DiffOpMove { from: i, to: i - 1 });
+                    }
+                } else {
+                    for i in to..from {
+                        diff.moved.push(DiffOpMove { from: i, to: i + 1 });
+                    }
+                }
+            }
+        }
+        VecDelta::Clear => {
+            diff.clear = true;
+            *len = 0;
+        }
+    }
+    diff
+}
+
 impl<T> View for DynStack<T> {
     fn id(&self) -> ViewId {
         self.id
@@ -6,13 +6,16 @@ use floem_editor_core::{
     cursor::Cursor,
     mode::MotionMode,
     movement::Movement,
-    register::Register,
+    register::{Clipboard, Register},
 };
 use floem_reactive::{SignalGet, SignalUpdate, SignalWith};
 
 use super::{
     command::{Command, CommandExecuted},
-    movement, Editor,
+    movement,
+    paste::{paste_in_chunks, LARGE_PASTE},
+    text::SystemClipboard,
+    Editor,
 };
 
 pub fn handle_command_default(
@@ -39,6 +42,15 @@ fn handle_edit_command_default(
     cmd: &EditCommand,
 ) -> CommandExecuted {
     let modal = ed.es.with_untracked(|es| es.modal());
+    // Large pastes are inserted over several frames, rather than blocking the UI
+    if *cmd == EditCommand::ClipboardPaste && !modal && !ed.read_only.get_untracked() {
+        if let Some(text) = SystemClipboard::new().get_string() {
+            if text.len() >= LARGE_PASTE {
+                paste_in_chunks(ed, text);
+                return CommandExecuted::Yes;
+            }
+        }
+    }
     let smart_tab = ed.es.with_untracked(|es| es.smart_tab());
     let mut cursor = ed.cursor.get_untracked();
     let mut register = ed.register.get_untracked();
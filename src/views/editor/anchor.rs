@@ -0,0 +1,151 @@
+//! Positions in a document which follow its edits.
+use std::{cell::RefCell, rc::Rc};
+
+use floem_reactive::{RwSignal, SignalGet, SignalUpdate};
+use lapce_xi_rope::{RopeDelta, Transformer};
+
+/// The side of text inserted at the offset of an [`Anchor`] which the anchor ends up on.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Bias {
+    /// The anchor stays before the inserted text, like the end of a range
+    #[default]
+    Left,
+    /// The anchor moves after the inserted text, like the start of a range
+    Right,
+}
+
+/// An offset in a document which is moved by the edits of the document, so that it stays next
+/// to the same text. When the text around it is deleted, it moves to the start of the
+/// deletion.
+///
+/// Anchors are created by [`Document::create_anchor`](super::text::Document::create_anchor),
+/// and live as long as the reactive scope they're created in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Anchor {
+    offset: RwSignal<usize>,
+    bias: Bias,
+}
+
+impl Anchor {
+    /// The current offset of the anchor.
+    ///
+    /// This subscribes to the anchor, so that effects reading it rerun when an edit moves it.
+    pub fn offset(&self) -> usize {
+        self.offset.get()
+    }
+
+    /// The current offset of the anchor, without subscribing to it.
+    pub fn offset_untracked(&self) -> usize {
+        self.offset.get_untracked()
+    }
+
+    pub fn bias(&self) -> Bias {
+        self.bias
+    }
+
+    /// Moves the anchor to `offset`.
+    pub fn set_offset(&self, offset: usize) {
+        self.offset.set(offset);
+    }
+}
+
+/// The anchors of a document, for implementations of
+/// [`Document`](super::text::Document) which support anchors.
+#[derive(Clone, Default)]
+pub struct Anchors {
+    anchors: Rc<RefCell<Vec<Anchor>>>,
+}
+
+impl Anchors {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an anchor at `offset` in the current scope.
+    pub fn create(&self, offset: usize, bias: Bias) -> Anchor {
+        let anchor = Anchor {
+            offset: RwSignal::new(offset),
+            bias,
+        };
+        self.anchors.borrow_mut().push(anchor);
+        anchor
+    }
+
+    /// Moves the anchors through an edit of the document, and forgets the anchors whose scope
+    /// was disposed.
+    pub fn apply_delta(&self, delta: &RopeDelta) {
+        let mut transformer = Transformer::new(delta);
+        let mut moved = Vec::new();
+        self.anchors.borrow_mut().retain(|anchor| {
+            let Some(offset) = anchor.offset.try_get_untracked() else {
+                return false;
+            };
+            let new_offset = transformer.transform(offset, anchor.bias == Bias::Right);
+            if new_offset != offset {
+                moved.push((*anchor, new_offset));
+            }
+            true
+        });
+        // Effects reading the anchors may create new anchors
+        for (anchor, offset) in moved {
+            anchor.set_offset(offset);
+        }
+    }
+
+    /// The number of anchors which are alive.
+    pub fn len(&self) -> usize {
+        self.anchors
+            .borrow()
+            .iter()
+            .filter(|anchor| anchor.offset.try_get_untracked().is_some())
+            .count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use floem_reactive::{with_scope, Scope};
+    use lapce_xi_rope::{Delta, Interval, Rope};
+
+    use super::{Anchors, Bias};
+
+    #[test]
+    fn bias() {
+        let anchors = Anchors::new();
+        let cx = Scope::new();
+        let (left, right, after) = with_scope(cx, || {
+            (
+                anchors.create(5, Bias::Left),
+                anchors.create(5, Bias::Right),
+                anchors.create(8, Bias::Left),
+            )
+        });
+
+        // Insert "abc" at 5
+        anchors.apply_delta(&Delta::simple_edit(
+            Interval::new(5, 5),
+            Rope::from("abc"),
+            10,
+        ));
+        assert_eq!(left.offset_untracked(), 5);
+        assert_eq!(right.offset_untracked(), 8);
+        assert_eq!(after.offset_untracked(), 11);
+
+        // Delete 2..10, which contains all the anchors but `after`
+        anchors.apply_delta(&Delta::simple_edit(
+            Interval::new(2, 10),
+            Rope::from(""),
+            13,
+        ));
+        assert_eq!(left.offset_untracked(), 2);
+        assert_eq!(right.offset_untracked(), 2);
+        assert_eq!(after.offset_untracked(), 3);
+
+        cx.dispose();
+        assert!(anchors.is_empty());
+    }
+}
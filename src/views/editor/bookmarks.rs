@@ -0,0 +1,266 @@
+//! Bookmarked lines of an editor, shown in its gutter, with commands to move between them.
+//!
+//! The bookmarks are [anchors](super::anchor) at the start of their lines, so they stay on the
+//! same text when lines are inserted or removed above them.
+use floem_reactive::{with_scope, RwSignal, Scope, SignalUpdate, SignalWith};
+use peniko::Color;
+
+use crate::command::Command;
+
+use super::{
+    anchor::{Anchor, Anchors, Bias},
+    gutter::GutterMark,
+    Editor,
+};
+
+/// The bookmarks of an [`Editor`].
+///
+/// [`Bookmarks::lines`] is tracked, so it can be used to show the bookmarks in a side panel.
+/// ```rust,ignore
+/// let bookmarks = Bookmarks::new(&editor);
+/// bookmarks.show_in_gutter(palette::css::DODGER_BLUE);
+/// for command in bookmarks.commands() {
+///     register_command(command);
+/// }
+/// dyn_stack(move || bookmarks.lines(), |line| *line, |line| label(move || line + 1));
+/// ```
+#[derive(Clone)]
+pub struct Bookmarks {
+    editor: Editor,
+    /// The anchors of the bookmarks, each in its own scope which is disposed with the bookmark
+    anchors: RwSignal<Vec<(Anchor, Scope)>>,
+    /// Anchors for documents which don't support anchors, which don't follow the edits
+    fallback: Anchors,
+}
+
+impl Bookmarks {
+    pub fn new(editor: &Editor) -> Self {
+        Self {
+            editor: editor.clone(),
+            anchors: RwSignal::new(Vec::new()),
+            fallback: Anchors::new(),
+        }
+    }
+
+    fn line_of_anchor(&self, anchor: &Anchor) -> usize {
+        let offset = anchor.offset().min(self.editor.text().len());
+        self.editor.line_of_offset(offset)
+    }
+
+    /// The bookmarked lines, sorted.
+    pub fn lines(&self) -> Vec<usize> {
+        let mut lines: Vec<usize> = self.anchors.with(|anchors| {
+            anchors
+                .iter()
+                .map(|(anchor, _)| self.line_of_anchor(anchor))
+                .collect()
+        });
+        // Deleting the text between two bookmarks moves them to the same line
+        lines.sort_unstable();
+        lines.dedup();
+        lines
+    }
+
+    pub fn contains(&self, line: usize) -> bool {
+        self.lines().contains(&line)
+    }
+
+    fn add(&self, line: usize) {
+        let offset = self.editor.offset_of_line(line);
+        let scope = Scope::new();
+        let anchor = with_scope(scope, || {
+            self.editor
+                .doc()
+                .create_anchor(offset, Bias::Right)
+                .unwrap_or_else(|| self.fallback.create(offset, Bias::Right))
+        });
+        self.anchors.update(|anchors| anchors.push((anchor, scope)));
+    }
+
+    /// Adds a bookmark on `line`, or removes the bookmarks on it.
+    pub fn toggle(&self, line: usize) {
+        let removed: Vec<Scope> = self
+            .anchors
+            .try_update(|anchors| {
+                let mut removed = Vec::new();
+                anchors.retain(|(anchor, scope)| {
+                    let keep = self.line_of_anchor(anchor) != line;
+                    if !keep {
+                        removed.push(*scope);
+                    }
+                    keep
+                });
+                removed
+            })
+            .unwrap_or_default();
+        if removed.is_empty() {
+            self.add(line);
+        }
+        for scope in removed {
+            scope.dispose();
+        }
+    }
+
+    /// Replaces the bookmarks with bookmarks on `lines`.
+    pub fn set_lines(&self, lines: impl IntoIterator<Item = usize>) {
+        self.clear();
+        let last_line = self.editor.last_line();
+        for line in lines {
+            if line <= last_line && !self.contains(line) {
+                self.add(line);
+            }
+        }
+    }
+
+    pub fn clear(&self) {
+        let anchors = self.anchors.try_update(std::mem::take).unwrap_or_default();
+        for (_, scope) in anchors {
+            scope.dispose();
+        }
+    }
+
+    /// The first bookmarked line after `line`, wrapping around to the first bookmark.
+    pub fn next(&self, line: usize) -> Option<usize> {
+        let lines = self.lines();
+        lines
+            .iter()
+            .find(|l| **l > line)
+            .or_else(|| lines.first())
+            .copied()
+    }
+
+    /// The last bookmarked line before `line`, wrapping around to the last bookmark.
+    pub fn previous(&self, line: usize) -> Option<usize> {
+        let lines = self.lines();
+        lines
+            .iter()
+            .rev()
+            .find(|l| **l < line)
+            .or_else(|| lines.last())
+            .copied()
+    }
+
+    fn cursor_line(&self) -> usize {
+        let offset = self.editor.cursor.with_untracked(|cursor| cursor.offset());
+        self.editor.line_of_offset(offset)
+    }
+
+    fn go_to_line(&self, line: usize) {
+        let offset = self.editor.offset_of_line(line);
+        self.editor
+            .cursor
+            .update(|cursor| cursor.set_offset(offset, false, false));
+    }
+
+    /// Adds a bookmark on the line of the cursor, or removes it.
+    pub fn toggle_at_cursor(&self) {
+        self.toggle(self.cursor_line());
+    }
+
+    /// Moves the cursor to the next bookmark.
+    pub fn go_to_next(&self) {
+        if let Some(line) = self.next(self.cursor_line()) {
+            self.go_to_line(line);
+        }
+    }
+
+    /// Moves the cursor to the previous bookmark.
+    pub fn go_to_previous(&self) {
+        if let Some(line) = self.previous(self.cursor_line()) {
+            self.go_to_line(line);
+        }
+    }
+
+    /// Paints a dot of `color` in the gutter of the editor next to the bookmarked lines.
+    pub fn show_in_gutter(&self, color: Color) {
+        let bookmarks = self.clone();
+        self.editor.add_gutter_marks(move || {
+            bookmarks
+                .lines()
+                .into_iter()
+                .map(|line| GutterMark { line, color })
+                .collect()
+        });
+    }
+
+    /// The commands toggling a bookmark and moving between bookmarks, for
+    /// [`register_command`](crate::command::register_command) and the command palette.
+    pub fn commands(&self) -> Vec<Command> {
+        let toggle = self.clone();
+        let next = self.clone();
+        let previous = self.clone();
+        let has_bookmarks = self.anchors;
+        vec![
+            Command::new("bookmarks.toggle", "Toggle Bookmark")
+                .category("Bookmarks")
+                .shortcut("CmdOrCtrl+Alt+K")
+                .action(move || toggle.toggle_at_cursor()),
+            Command::new("bookmarks.next", "Go to Next Bookmark")
+                .category("Bookmarks")
+                .shortcut("F2")
+                .enabled(move || has_bookmarks.with(|anchors| !anchors.is_empty()))
+                .action(move || next.go_to_next()),
+            Command::new("bookmarks.previous", "Go to Previous Bookmark")
+                .category("Bookmarks")
+                .shortcut("Shift+F2")
+                .enabled(move || has_bookmarks.with(|anchors| !anchors.is_empty()))
+                .action(move || previous.go_to_previous()),
+        ]
+    }
+
+    /// Restores the bookmarks stored under `key` with the
+    /// [persistence](crate::persist) of the application, and stores them whenever they change.
+    #[cfg(feature = "serde")]
+    pub fn persist(&self, key: impl Into<String>) {
+        use floem_reactive::{create_effect, SignalGet};
+
+        let stored = crate::persist::create_persistent_signal(key, Vec::<usize>::new());
+        self.set_lines(stored.get_untracked());
+        let bookmarks = self.clone();
+        create_effect(move |_| {
+            let lines = bookmarks.lines();
+            if stored.get_untracked() != lines {
+                stored.set(lines);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use floem_editor_core::{editor::EditType, selection::Selection};
+    use floem_reactive::Scope;
+
+    use crate::views::editor::{text::SimpleStyling, text_document::TextDocument, Editor};
+
+    use super::Bookmarks;
+
+    #[test]
+    fn navigation() {
+        let cx = Scope::new();
+        let doc = Rc::new(TextDocument::new(cx, "a\nb\nc\nd\ne"));
+        let editor = Editor::new(cx, doc, Rc::new(SimpleStyling::new()), false);
+        let bookmarks = Bookmarks::new(&editor);
+
+        assert_eq!(bookmarks.next(0), None);
+        bookmarks.toggle(1);
+        bookmarks.toggle(3);
+        assert_eq!(bookmarks.lines(), [1, 3]);
+        assert_eq!(bookmarks.next(1), Some(3));
+        assert_eq!(bookmarks.next(3), Some(1));
+        assert_eq!(bookmarks.previous(2), Some(1));
+        assert_eq!(bookmarks.previous(0), Some(3));
+
+        // Inserting a line above moves the bookmarks down
+        editor
+            .doc()
+            .edit_single(Selection::caret(0), "z\n", EditType::InsertChars);
+        assert_eq!(bookmarks.lines(), [2, 4]);
+
+        bookmarks.toggle(2);
+        assert_eq!(bookmarks.lines(), [4]);
+        cx.dispose();
+    }
+}
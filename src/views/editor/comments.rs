@@ -0,0 +1,233 @@
+//! Comment threads anchored at lines of a document, shown in a margin next to the editor.
+//!
+//! The threads are arbitrary views, positioned at the height of the line they're anchored to
+//! and pushed down when they would overlap the thread above. The anchors follow the edits of
+//! the document, so a thread stays next to its line when lines are inserted or removed above
+//! it.
+use std::rc::Rc;
+
+use floem_reactive::{create_memo, RwSignal, SignalGet, SignalTrack, SignalUpdate, SignalWith};
+use lapce_xi_rope::{RopeDelta, Transformer};
+
+use crate::{
+    style_class,
+    view::{AnyView, IntoView},
+    views::{clip, dyn_stack, Decorators},
+};
+
+use super::Editor;
+
+style_class!(
+    /// Style class of the margin showing the comment threads of an editor
+    pub CommentMarginClass
+);
+
+/// The vertical space between two threads pushed against each other.
+const THREAD_GAP: f64 = 4.0;
+
+/// Identifies a comment thread of a [`CommentThreads`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CommentThreadId(u64);
+
+/// A comment thread anchored at an offset of a document.
+#[derive(Clone, Copy, Debug)]
+pub struct CommentThread {
+    id: CommentThreadId,
+    offset: RwSignal<usize>,
+    /// Whether the thread shows its comments or only a summary
+    pub expanded: RwSignal<bool>,
+    height: RwSignal<f64>,
+}
+
+impl CommentThread {
+    pub fn id(&self) -> CommentThreadId {
+        self.id
+    }
+
+    /// The offset the thread is anchored at, which follows the edits of the document.
+    pub fn offset(&self) -> usize {
+        self.offset.get()
+    }
+}
+
+type ThreadViewFn = Rc<dyn Fn(CommentThread) -> AnyView>;
+
+/// The comment threads of a document, shown by [`editor_comment_margin`].
+///
+/// The anchors of the threads are updated by [`CommentThreads::apply_delta`], which
+/// [`TextEditor::comment_threads`](crate::views::TextEditor::comment_threads) calls for every
+/// edit of the editor.
+#[derive(Clone, Copy)]
+pub struct CommentThreads {
+    threads: RwSignal<Vec<(CommentThread, ThreadViewFn)>>,
+    next_id: RwSignal<u64>,
+}
+
+impl Default for CommentThreads {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CommentThreads {
+    pub fn new() -> Self {
+        Self {
+            threads: RwSignal::new(Vec::new()),
+            next_id: RwSignal::new(0),
+        }
+    }
+
+    /// Adds a thread anchored at the line containing `offset`, shown by the view returned by
+    /// `view_fn`. The thread starts collapsed.
+    pub fn add<V: IntoView + 'static>(
+        &self,
+        offset: usize,
+        view_fn: impl Fn(CommentThread) -> V + 'static,
+    ) -> CommentThread {
+        let id = self.next_id.get_untracked();
+        self.next_id.set(id + 1);
+        let thread = CommentThread {
+            id: CommentThreadId(id),
+            offset: RwSignal::new(offset),
+            expanded: RwSignal::new(false),
+            height: RwSignal::new(0.0),
+        };
+        let view_fn: ThreadViewFn = Rc::new(move |thread| view_fn(thread).into_any());
+        self.threads
+            .update(|threads| threads.push((thread, view_fn)));
+        thread
+    }
+
+    pub fn remove(&self, id: CommentThreadId) {
+        self.threads
+            .update(|threads| threads.retain(|(thread, _)| thread.id != id));
+    }
+
+    /// The threads, in the order they were added.
+    pub fn threads(&self) -> Vec<CommentThread> {
+        self.threads
+            .with(|threads| threads.iter().map(|(thread, _)| *thread).collect())
+    }
+
+    /// Moves the anchors of the threads through an edit of the document.
+    pub fn apply_delta(&self, delta: &RopeDelta) {
+        let mut transformer = Transformer::new(delta);
+        self.threads.with_untracked(|threads| {
+            for (thread, _) in threads {
+                let offset = thread.offset.get_untracked();
+                let new_offset = transformer.transform(offset, true);
+                if new_offset != offset {
+                    thread.offset.set(new_offset);
+                }
+            }
+        });
+    }
+}
+
+/// The tops of threads sorted by the height of their anchor, given as `(anchor, height)`,
+/// moving threads down so that they don't overlap the previous one.
+fn stack_threads(threads: &[(f64, f64)], gap: f64) -> Vec<f64> {
+    let mut bottom = f64::NEG_INFINITY;
+    threads
+        .iter()
+        .map(|(anchor, height)| {
+            let top = anchor.max(bottom + gap);
+            bottom = top + height;
+            top
+        })
+        .collect()
+}
+
+/// A margin showing the comment `threads` next to the lines they're anchored to, scrolled
+/// along with the editor.
+///
+/// The margin is meant to be put beside the editor, with the same top. It doesn't reserve
+/// space between the lines of the editor, so threads taller than their line are shown over the
+/// following lines of the margin, pushing the following threads down.
+pub fn editor_comment_margin(editor: &Editor, threads: CommentThreads) -> impl IntoView {
+    let editor = editor.clone();
+    let tops = create_memo(move |_| {
+        editor.doc().cache_rev().track();
+        let viewport = editor.viewport.get();
+        let len = editor.text().len();
+        let mut anchors: Vec<_> = threads.threads.with(|threads| {
+            threads
+                .iter()
+                .map(|(thread, _)| {
+                    let line = editor.line_of_offset(thread.offset.get().min(len));
+                    let y = editor.y_of_line(line);
+                    (thread.id, y, thread.height.get())
+                })
+                .collect()
+        });
+        anchors.sort_by(|a, b| a.1.total_cmp(&b.1));
+        let heights: Vec<_> = anchors.iter().map(|(_, y, h)| (*y, *h)).collect();
+        anchors
+            .iter()
+            .zip(stack_threads(&heights, THREAD_GAP))
+            .map(|((id, _, _), top)| (*id, top - viewport.y0))
+            .collect::<Vec<_>>()
+    });
+
+    clip(
+        dyn_stack(
+            move || threads.threads.get(),
+            |(thread, _)| thread.id,
+            move |(thread, view_fn)| {
+                view_fn(thread)
+                    .on_resize(move |rect| {
+                        if thread.height.get_untracked() != rect.height() {
+                            thread.height.set(rect.height());
+                        }
+                    })
+                    .style(move |s| {
+                        let top = tops.with(|tops| {
+                            tops.iter()
+                                .find(|(id, _)| *id == thread.id)
+                                .map(|(_, top)| *top)
+                                .unwrap_or_default()
+                        });
+                        s.absolute().inset_top(top).width_full()
+                    })
+            },
+        )
+        .style(|s| s.size_full()),
+    )
+    .class(CommentMarginClass)
+    .style(|s| s.height_full())
+}
+
+#[cfg(test)]
+mod tests {
+    use floem_reactive::{with_scope, Scope};
+    use lapce_xi_rope::{Delta, Interval, Rope};
+
+    use super::{stack_threads, CommentThreads};
+
+    #[test]
+    fn stacking() {
+        assert_eq!(
+            stack_threads(&[(0.0, 30.0), (20.0, 10.0), (100.0, 10.0)], 4.0),
+            [0.0, 34.0, 100.0]
+        );
+    }
+
+    #[test]
+    fn anchors_follow_edits() {
+        let cx = Scope::new();
+        with_scope(cx, || {
+            let threads = CommentThreads::new();
+            let first = threads.add(0, |_| "first");
+            let second = threads.add(10, |_| "second");
+
+            let delta = Delta::simple_edit(Interval::new(2, 2), Rope::from("abc\n"), 20);
+            threads.apply_delta(&delta);
+            assert_eq!(first.offset.get_untracked(), 0);
+            assert_eq!(second.offset.get_untracked(), 14);
+
+            threads.remove(first.id());
+            assert_eq!(threads.threads().len(), 1);
+        });
+        cx.dispose();
+    }
+}
@@ -0,0 +1,169 @@
+//! A read-only excerpt of code with the lines added and removed by a change, for review panels
+//! and tooltips.
+//!
+//! The lines are laid out with the same [`Styling`] as an editor, so they get the same fonts and
+//! syntax highlighting, but without the document, the cursor and the caches of an [`Editor`],
+//! which makes it cheap to show many hunks in a list.
+//!
+//! [`Editor`]: super::Editor
+use std::rc::Rc;
+
+use peniko::{color::palette, Color};
+
+use crate::{
+    style_class,
+    text::{Attrs, AttrsList, LineHeightValue, TextLayout},
+    views::{h_stack, label, rich_text, v_stack_from_iter, Decorators, Stack},
+};
+
+use super::{id::EditorId, text::Styling, EditorStyle};
+
+style_class!(
+    /// Style class of a [`diff_hunk`]
+    pub DiffHunkClass
+);
+style_class!(
+    /// Style class of the lines of a [`diff_hunk`] added by the change
+    pub DiffAddedLineClass
+);
+style_class!(
+    /// Style class of the lines of a [`diff_hunk`] removed by the change
+    pub DiffRemovedLineClass
+);
+
+const ADDED_BACKGROUND: Color = Color::from_rgba8(0x2E, 0xA0, 0x43, 0x30);
+const REMOVED_BACKGROUND: Color = Color::from_rgba8(0xF8, 0x51, 0x49, 0x30);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HunkLineKind {
+    /// A line which is the same before and after the change
+    Context,
+    Added,
+    Removed,
+}
+
+/// A line of a [`diff_hunk`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HunkLine {
+    pub kind: HunkLineKind,
+    pub text: String,
+}
+
+impl HunkLine {
+    pub fn new(kind: HunkLineKind, text: impl Into<String>) -> Self {
+        HunkLine {
+            kind,
+            text: text.into(),
+        }
+    }
+
+    /// The lines of a hunk of a unified diff, like the output of `git diff`. The `@@` header of
+    /// the hunk and the `\ No newline at end of file` markers are skipped.
+    pub fn parse_unified(hunk: &str) -> Vec<HunkLine> {
+        hunk.lines()
+            .filter_map(|line| {
+                let kind = match line.as_bytes().first() {
+                    Some(b'+') => HunkLineKind::Added,
+                    Some(b'-') => HunkLineKind::Removed,
+                    Some(b' ') => HunkLineKind::Context,
+                    // Blank context lines sometimes lose their leading space
+                    None => return Some(HunkLine::new(HunkLineKind::Context, "")),
+                    _ => return None,
+                };
+                Some(HunkLine::new(kind, &line[1..]))
+            })
+            .collect()
+    }
+
+    fn sign(&self) -> &'static str {
+        match self.kind {
+            HunkLineKind::Context => " ",
+            HunkLineKind::Added => "+",
+            HunkLineKind::Removed => "-",
+        }
+    }
+}
+
+/// The layout of the line `line` of the excerpt, styled like the lines of an editor but without
+/// phantom text nor wrapping.
+fn hunk_text_layout(
+    styling: &dyn Styling,
+    edid: EditorId,
+    es: &EditorStyle,
+    line: usize,
+    text: &str,
+) -> TextLayout {
+    let family = styling.font_family(edid, line);
+    let attrs = Attrs::new()
+        .color(es.ed_text_color())
+        .family(&family)
+        .font_size(styling.font_size(edid, line) as f32)
+        .line_height(LineHeightValue::Px(styling.line_height(edid, line)));
+    let mut attrs_list = AttrsList::new(attrs);
+    styling.apply_attr_styles(edid, es, line, attrs, &mut attrs_list);
+
+    let mut text_layout = TextLayout::new();
+    text_layout.set_tab_width(styling.tab_width(edid, line));
+    text_layout.set_text(text, attrs_list);
+    text_layout
+}
+
+/// A read-only code excerpt showing `lines`, with the added and removed lines highlighted.
+///
+/// The lines are numbered from 0 for `styling`, so syntax highlighting is computed over the
+/// excerpt. The backgrounds of the lines are set with [`DiffAddedLineClass`] and
+/// [`DiffRemovedLineClass`].
+///
+/// ## Example
+/// ```rust
+/// use std::rc::Rc;
+///
+/// use floem::views::editor::{diff_hunk::*, text::SimpleStyling};
+///
+/// let hunk = "@@ -1,2 +1,2 @@\n fn main() {\n-    old();\n+    new();\n";
+/// diff_hunk(
+///     HunkLine::parse_unified(hunk),
+///     Rc::new(SimpleStyling::new()),
+/// );
+/// ```
+pub fn diff_hunk(lines: impl IntoIterator<Item = HunkLine>, styling: Rc<dyn Styling>) -> Stack {
+    let edid = EditorId::next();
+    let es = EditorStyle::default();
+    v_stack_from_iter(lines.into_iter().enumerate().map(|(i, line)| {
+        let layout = hunk_text_layout(&*styling, edid, &es, i, &line.text);
+        let sign = line.sign();
+        let kind = line.kind;
+        h_stack((
+            label(move || sign).style(|s| s.width(16.0).flex_shrink(0.0).color(palette::css::GRAY)),
+            rich_text(move || layout.clone()),
+        ))
+        .class_if(move || kind == HunkLineKind::Added, DiffAddedLineClass)
+        .class_if(move || kind == HunkLineKind::Removed, DiffRemovedLineClass)
+        .style(|s| s.width_full())
+    }))
+    .class(DiffHunkClass)
+    .style(|s| {
+        s.class(DiffAddedLineClass, |s| s.background(ADDED_BACKGROUND))
+            .class(DiffRemovedLineClass, |s| s.background(REMOVED_BACKGROUND))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HunkLine, HunkLineKind};
+
+    #[test]
+    fn parse_unified() {
+        let hunk = "@@ -1,4 +1,4 @@ fn main\n fn main() {\n-    old();\n+    new();\n\n }\n\\ No newline at end of file\n";
+        assert_eq!(
+            HunkLine::parse_unified(hunk),
+            vec![
+                HunkLine::new(HunkLineKind::Context, "fn main() {"),
+                HunkLine::new(HunkLineKind::Removed, "    old();"),
+                HunkLine::new(HunkLineKind::Added, "    new();"),
+                HunkLine::new(HunkLineKind::Context, ""),
+                HunkLine::new(HunkLineKind::Context, "}"),
+            ]
+        );
+    }
+}
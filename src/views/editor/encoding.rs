@@ -0,0 +1,185 @@
+//! Decoding the bytes of files into text and encoding text back into the encoding of the file.
+//!
+//! Text files are mostly UTF-8, but files written by older programs can be in UTF-16 or in the
+//! legacy code page of their system, like Windows-1252 or Shift_JIS. The encoding of a file is
+//! found when it's loaded, so that it can be saved back in the same encoding.
+use encoding_rs::{Encoding, UTF_16BE, UTF_16LE, UTF_8, WINDOWS_1252};
+
+/// The encoding of a file, and whether it starts with a byte order mark.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FileEncoding {
+    pub encoding: &'static Encoding,
+    pub bom: bool,
+}
+
+/// UTF-8 without a byte order mark.
+impl Default for FileEncoding {
+    fn default() -> Self {
+        FileEncoding {
+            encoding: UTF_8,
+            bom: false,
+        }
+    }
+}
+
+impl FileEncoding {
+    /// The encoding with the WHATWG `label`, like `utf-16le`, `latin1` or `shift_jis`.
+    pub fn for_label(label: &str) -> Option<FileEncoding> {
+        Encoding::for_label(label.as_bytes()).map(|encoding| FileEncoding {
+            encoding,
+            bom: false,
+        })
+    }
+
+    /// The name of the encoding, like `UTF-8` or `windows-1252`.
+    pub fn name(&self) -> &'static str {
+        self.encoding.name()
+    }
+
+    /// Finds the encoding of `bytes` and decodes them.
+    ///
+    /// The encoding is given by a byte order mark, or is UTF-16 if the bytes look like UTF-16
+    /// text without a byte order mark, or UTF-8 if they are valid UTF-8. Otherwise the bytes
+    /// are decoded with `fallback`, usually the legacy code page of the user, or Windows-1252
+    /// if it's `None`.
+    pub fn detect(bytes: &[u8], fallback: Option<&'static Encoding>) -> (String, FileEncoding) {
+        let encoding = if let Some((encoding, _)) = Encoding::for_bom(bytes) {
+            FileEncoding {
+                encoding,
+                bom: true,
+            }
+        } else if let Some(encoding) = guess_utf16(bytes) {
+            // NUL bytes are valid UTF-8, so UTF-16 is checked first
+            FileEncoding {
+                encoding,
+                bom: false,
+            }
+        } else if std::str::from_utf8(bytes).is_ok() {
+            FileEncoding::default()
+        } else {
+            FileEncoding {
+                encoding: fallback.unwrap_or(WINDOWS_1252),
+                bom: false,
+            }
+        };
+        (encoding.decode(bytes), encoding)
+    }
+
+    /// Decodes `bytes`, skipping the byte order mark. Bytes which aren't valid in the encoding
+    /// are replaced by `�`.
+    pub fn decode(&self, bytes: &[u8]) -> String {
+        let (text, _) = self.encoding.decode_with_bom_removal(bytes);
+        text.into_owned()
+    }
+
+    /// Encodes `text`, with a byte order mark if the file had one. Characters which can't be
+    /// written in the encoding are written as HTML numeric character references, like
+    /// `&#8364;`.
+    pub fn encode(&self, text: &str) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(text.len() + 3);
+        // encoding_rs only decodes UTF-16, encoding to it gives UTF-8
+        if self.encoding == UTF_16LE || self.encoding == UTF_16BE {
+            let big_endian = self.encoding == UTF_16BE;
+            let units = self
+                .bom
+                .then_some(0xFEFF)
+                .into_iter()
+                .chain(text.encode_utf16());
+            for unit in units {
+                if big_endian {
+                    bytes.extend_from_slice(&unit.to_be_bytes());
+                } else {
+                    bytes.extend_from_slice(&unit.to_le_bytes());
+                }
+            }
+            return bytes;
+        }
+        if self.bom && self.encoding == UTF_8 {
+            bytes.extend_from_slice(b"\xEF\xBB\xBF");
+        }
+        let (encoded, _, _) = self.encoding.encode(text);
+        bytes.extend_from_slice(&encoded);
+        bytes
+    }
+}
+
+/// UTF-16 without a byte order mark, guessed from the zero bytes of ASCII characters.
+fn guess_utf16(bytes: &[u8]) -> Option<&'static Encoding> {
+    if bytes.len() < 4 || bytes.len() % 2 != 0 {
+        return None;
+    }
+    let pairs = bytes.len() / 2;
+    let even_zeros = bytes.iter().step_by(2).filter(|b| **b == 0).count();
+    let odd_zeros = bytes.iter().skip(1).step_by(2).filter(|b| **b == 0).count();
+    // Mostly ASCII text has a zero in most of its pairs, always on the same side
+    if odd_zeros * 10 >= pairs * 7 && even_zeros * 10 < pairs {
+        Some(UTF_16LE)
+    } else if even_zeros * 10 >= pairs * 7 && odd_zeros * 10 < pairs {
+        Some(UTF_16BE)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use encoding_rs::{SHIFT_JIS, UTF_16BE, UTF_16LE, UTF_8, WINDOWS_1252};
+
+    use super::FileEncoding;
+
+    #[test]
+    fn detect() {
+        let (text, encoding) = FileEncoding::detect("héllo".as_bytes(), None);
+        assert_eq!(
+            (text.as_str(), encoding),
+            ("héllo", FileEncoding::default())
+        );
+
+        let (text, encoding) = FileEncoding::detect(b"\xEF\xBB\xBFhi", None);
+        assert_eq!(text, "hi");
+        assert_eq!(encoding.encoding, UTF_8);
+        assert!(encoding.bom);
+
+        let (text, encoding) = FileEncoding::detect(b"h\0i\0!\0\n\0", None);
+        assert_eq!((text.as_str(), encoding.encoding), ("hi!\n", UTF_16LE));
+
+        let (text, encoding) = FileEncoding::detect(b"caf\xE9", None);
+        assert_eq!((text.as_str(), encoding.encoding), ("café", WINDOWS_1252));
+    }
+
+    #[test]
+    fn round_trip() {
+        let encodings = [
+            FileEncoding::default(),
+            FileEncoding {
+                encoding: UTF_8,
+                bom: true,
+            },
+            FileEncoding {
+                encoding: UTF_16LE,
+                bom: true,
+            },
+            FileEncoding {
+                encoding: UTF_16BE,
+                bom: false,
+            },
+            FileEncoding {
+                encoding: SHIFT_JIS,
+                bom: false,
+            },
+        ];
+        for encoding in encodings {
+            let bytes = encoding.encode("テキスト\r\n");
+            assert_eq!(
+                encoding.decode(&bytes),
+                "テキスト\r\n",
+                "{}",
+                encoding.name()
+            );
+        }
+        assert_eq!(
+            FileEncoding::for_label("latin1").unwrap().encode("€ and ✓"),
+            b"\x80 and &#10003;"
+        );
+    }
+}
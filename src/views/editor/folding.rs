@@ -0,0 +1,206 @@
+//! Folding of ranges of lines, like the body of a function, toggled by the chevrons painted in
+//! the gutter.
+//!
+//! The ranges which can be folded are supplied by
+//! [`Document::folding_ranges`](super::text::Document::folding_ranges), while the folds are kept
+//! by each editor, see [`Editor::toggle_fold`](super::Editor::toggle_fold). The first line of a
+//! folded range stays shown with a placeholder after its text, the other lines are skipped by
+//! the screen lines.
+use std::{ops::Range, rc::Rc};
+
+use super::{
+    visual_line::{RVLine, VLineInfo},
+    Editor,
+};
+
+/// The placeholder shown after the text of the first line of a folded range.
+pub const FOLD_PLACEHOLDER: &str = " ⋯ ";
+
+/// A range of lines which can be folded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct FoldingRange {
+    /// The line which stays shown when the range is folded, with the chevron in the gutter
+    pub start_line: usize,
+    /// The last line hidden when the range is folded
+    pub end_line: usize,
+}
+
+impl FoldingRange {
+    pub fn new(start_line: usize, end_line: usize) -> Self {
+        FoldingRange {
+            start_line,
+            end_line,
+        }
+    }
+
+    /// The lines hidden when the range is folded.
+    pub fn hidden_lines(&self) -> Range<usize> {
+        self.start_line + 1..self.end_line + 1
+    }
+}
+
+/// The lines hidden by `folds`, sorted and merged, so that the folds nested in other folds are
+/// hidden with them.
+pub(crate) fn hidden_lines(folds: &[FoldingRange]) -> Vec<Range<usize>> {
+    let mut lines: Vec<Range<usize>> = folds
+        .iter()
+        .map(FoldingRange::hidden_lines)
+        .filter(|lines| !lines.is_empty())
+        .collect();
+    lines.sort_by_key(|lines| lines.start);
+
+    let mut merged: Vec<Range<usize>> = Vec::with_capacity(lines.len());
+    for lines in lines {
+        match merged.last_mut() {
+            Some(last) if lines.start <= last.end => last.end = last.end.max(lines.end),
+            _ => merged.push(lines),
+        }
+    }
+    merged
+}
+
+/// The lines hidden by the folded ranges of an editor, for a cache revision of its document.
+#[derive(Debug, Clone)]
+pub(crate) struct HiddenLines {
+    pub cache_rev: u64,
+    pub lines: Rc<[Range<usize>]>,
+    /// Whether the lines were moved by the edits of the document since they were computed, so
+    /// that they may not be the lines hidden for `cache_rev`
+    pub moved: bool,
+}
+
+impl HiddenLines {
+    /// Moves the hidden lines for the edit which replaced the `old_count` lines from
+    /// `start_line` by `new_count` lines.
+    pub fn splice(&mut self, start_line: usize, old_count: usize, new_count: usize) {
+        self.lines = splice_lines(&self.lines, start_line, old_count, new_count).into();
+        self.moved = true;
+    }
+}
+
+/// Moves the `hidden` lines for the edit which replaced the `old_count` lines from `start_line`
+/// by `new_count` lines. The replaced lines which were hidden stay hidden, as far as there are
+/// new lines.
+pub(crate) fn splice_lines(
+    hidden: &[Range<usize>],
+    start_line: usize,
+    old_count: usize,
+    new_count: usize,
+) -> Vec<Range<usize>> {
+    let old_end = start_line + old_count;
+    let move_line = |line: usize| {
+        if line < start_line {
+            line
+        } else if line >= old_end {
+            line - old_count + new_count
+        } else {
+            start_line + (line - start_line).min(new_count)
+        }
+    };
+    hidden
+        .iter()
+        .map(|lines| move_line(lines.start)..move_line(lines.end))
+        .filter(|lines| !lines.is_empty())
+        .collect()
+}
+
+/// The ranges of the lines `0..line_count` which aren't `hidden`.
+pub(crate) fn shown_lines(hidden: &[Range<usize>], line_count: usize) -> Vec<Range<usize>> {
+    let mut shown = Vec::with_capacity(hidden.len() + 1);
+    let mut start = 0;
+    for lines in hidden {
+        if lines.start >= line_count {
+            break;
+        }
+        if lines.start > start {
+            shown.push(start..lines.start);
+        }
+        start = lines.end;
+    }
+    if start < line_count {
+        shown.push(start..line_count);
+    }
+    shown
+}
+
+/// The visual lines of `lines`, as the visual line after the last line for the end.
+fn vlines_of(editor: &Editor, lines: &Range<usize>) -> Range<usize> {
+    let last_line = editor.last_line();
+    let vline_of = |line: usize| {
+        if line > last_line {
+            editor.last_vline().get() + 1
+        } else {
+            editor.vline_of_line(line).get()
+        }
+    };
+    vline_of(lines.start)..vline_of(lines.end)
+}
+
+/// The number of visual lines of the `hidden` lines.
+pub(crate) fn hidden_vline_count(editor: &Editor, hidden: &[Range<usize>]) -> usize {
+    hidden
+        .iter()
+        .map(|lines| vlines_of(editor, lines).len())
+        .sum()
+}
+
+/// The shown visual lines, from the visual line `start`, skipping the `hidden` lines.
+pub(crate) fn iter_shown_rvlines<'a>(
+    editor: &'a Editor,
+    hidden: &[Range<usize>],
+    start: RVLine,
+    cache_rev: u64,
+) -> impl Iterator<Item = VLineInfo<()>> + 'a {
+    let shown: Vec<(RVLine, usize)> = shown_lines(hidden, editor.last_line() + 1)
+        .into_iter()
+        .filter(|lines| lines.end > start.line)
+        .map(|lines| {
+            let first = if lines.contains(&start.line) {
+                start
+            } else {
+                RVLine::new(lines.start, 0)
+            };
+            (first, lines.end)
+        })
+        .collect();
+
+    let config_id = editor.config_id();
+    shown.into_iter().flat_map(move |(start, end)| {
+        editor
+            .lines
+            .iter_rvlines_init(editor.text_prov(), cache_rev, config_id, start, false)
+            .take_while(move |info| info.rvline.line < end)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{hidden_lines, shown_lines, splice_lines, FoldingRange};
+
+    #[test]
+    fn lines() {
+        let folds = [
+            FoldingRange::new(10, 14),
+            FoldingRange::new(2, 8),
+            FoldingRange::new(4, 6),
+            FoldingRange::new(8, 9),
+            FoldingRange::new(20, 20),
+        ];
+        let hidden = hidden_lines(&folds);
+        assert_eq!(hidden, [3..10, 11..15]);
+        assert_eq!(shown_lines(&hidden, 30), [0..3, 10..11, 15..30]);
+        assert_eq!(shown_lines(&hidden, 12), [0..3, 10..11]);
+        assert_eq!(shown_lines(&[], 3), [0..3]);
+    }
+
+    #[test]
+    fn splice() {
+        let hidden = [3..10, 11..15];
+        // Two lines are inserted in line 1
+        assert_eq!(splice_lines(&hidden, 1, 1, 3), [5..12, 13..17]);
+        // Lines 9 to 11 are replaced by one line
+        assert_eq!(splice_lines(&hidden, 9, 3, 1), [3..10, 10..13]);
+        // Lines 2 to 10 are replaced by one line, which hides the first range
+        assert_eq!(splice_lines(&hidden, 2, 9, 1), [3..7]);
+    }
+}
@@ -1,5 +1,6 @@
 use crate::{
-    context::PaintCx,
+    context::{EventCx, PaintCx},
+    event::{Event, EventPropagation},
     id::ViewId,
     peniko::kurbo::Point,
     prop, prop_extractor,
@@ -11,10 +12,14 @@ use crate::{
     Renderer,
 };
 use floem_editor_core::{cursor::CursorMode, mode::Mode};
-use floem_reactive::{RwSignal, SignalGet, SignalWith};
+use floem_reactive::{create_effect, RwSignal, SignalGet, SignalTrack, SignalWith};
 use peniko::color::palette;
-use peniko::kurbo::Rect;
+use peniko::kurbo::{BezPath, Circle, Rect, Stroke};
 use peniko::Color;
+use std::{
+    collections::{HashMap, HashSet},
+    rc::Rc,
+};
 
 use super::{CurrentLineColor, Editor};
 
@@ -41,6 +46,20 @@ impl GutterStyle {
     }
 }
 
+/// A mark painted in the gutter before the number of a line, like a bookmark or a breakpoint.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GutterMark {
+    pub line: usize,
+    pub color: Color,
+}
+
+pub(crate) type GutterMarksFn = Rc<dyn Fn() -> Vec<GutterMark>>;
+
+/// The radius of the dot painted for a [`GutterMark`]
+const MARK_RADIUS: f64 = 3.5;
+/// The half width of the chevron painted for a [`FoldingRange`](super::folding::FoldingRange)
+const CHEVRON_SIZE: f64 = 3.5;
+
 pub struct EditorGutterView {
     id: ViewId,
     editor: RwSignal<Editor>,
@@ -54,6 +73,24 @@ style_class!(pub GutterClass);
 pub fn editor_gutter_view(editor: RwSignal<Editor>) -> EditorGutterView {
     let id = ViewId::new();
 
+    // Repaint when the marks change
+    let gutter_marks = editor.get_untracked().gutter_marks;
+    create_effect(move |_| {
+        gutter_marks.with(|marks| {
+            for marks in marks {
+                marks();
+            }
+        });
+        id.request_paint();
+    });
+
+    // Repaint when ranges are folded or unfolded
+    let folded_lines = editor.get_untracked().folded_lines;
+    create_effect(move |_| {
+        folded_lines.track();
+        id.request_paint();
+    });
+
     EditorGutterView {
         id,
         editor,
@@ -73,6 +110,42 @@ impl View for EditorGutterView {
         "Editor Gutter View".into()
     }
 
+    fn event_before_children(&mut self, _cx: &mut EventCx, event: &Event) -> EventPropagation {
+        let Event::PointerDown(pointer_event) = event else {
+            return EventPropagation::Continue;
+        };
+        // The chevrons are in the padding after the line numbers
+        if !pointer_event.button.is_primary()
+            || pointer_event.pos.x < self.full_width - self.gutter_style.right_padding()
+        {
+            return EventPropagation::Continue;
+        }
+
+        let editor = self.editor.get_untracked();
+        let y = pointer_event.pos.y + editor.viewport.get_untracked().y0;
+        let line = editor.screen_lines.with_untracked(|screen_lines| {
+            screen_lines.iter_lines_y().find_map(|(line, line_y)| {
+                let line_height = f64::from(editor.line_height(line));
+                (line_y <= y && y < line_y + line_height).then_some(line)
+            })
+        });
+        let Some(line) = line else {
+            return EventPropagation::Continue;
+        };
+        let foldable = editor
+            .doc()
+            .folding_ranges()
+            .iter()
+            .any(|range| range.start_line == line && range.start_line < range.end_line);
+        if !foldable {
+            return EventPropagation::Continue;
+        }
+
+        editor.toggle_fold(line);
+        self.id.request_paint();
+        EventPropagation::Stop
+    }
+
     fn style_pass(&mut self, cx: &mut crate::context::StyleCx<'_>) {
         if self.gutter_style.read(cx) {
             cx.app_state_mut().request_paint(self.id());
@@ -156,6 +229,22 @@ impl View for EditorGutterView {
 
         self.text_width = self.compute_widest_text_width(&attrs_list);
 
+        let marks: HashMap<usize, Color> = editor.gutter_marks.with_untracked(|marks| {
+            marks
+                .iter()
+                .flat_map(|marks| marks())
+                .map(|mark| (mark.line, mark.color))
+                .collect()
+        });
+
+        let fold_starts: HashSet<usize> = editor
+            .doc()
+            .folding_ranges()
+            .iter()
+            .filter(|range| range.start_line < range.end_line)
+            .map(|range| range.start_line)
+            .collect();
+
         editor.screen_lines.with_untracked(|screen_lines| {
             for (line, y) in screen_lines.iter_lines_y() {
                 // If it ends up outside the bounds of the file, stop trying to display line numbers
@@ -221,11 +310,45 @@ impl View for EditorGutterView {
                 );
 
                 cx.draw_text(&text_layout, pos);
+
+                if let Some(color) = marks.get(&line) {
+                    let center = Point::new(
+                        self.gutter_style.left_padding() / 2.0,
+                        y + line_height / 2.0 - viewport.y0,
+                    );
+                    cx.fill(&Circle::new(center, MARK_RADIUS), *color, 0.0);
+                }
+
+                if fold_starts.contains(&line) {
+                    let center = Point::new(
+                        self.full_width - self.gutter_style.right_padding() / 2.0,
+                        y + line_height / 2.0 - viewport.y0,
+                    );
+                    let chevron = chevron(center, editor.is_folded(line));
+                    cx.stroke(&chevron, dim_color, &Stroke::new(1.5));
+                }
             }
         });
     }
 }
 
+/// A chevron pointing right for a folded range, or down for an unfolded one.
+fn chevron(center: Point, folded: bool) -> BezPath {
+    let (x, y) = (center.x, center.y);
+    let s = CHEVRON_SIZE;
+    let mut path = BezPath::new();
+    if folded {
+        path.move_to((x - s / 2.0, y - s));
+        path.line_to((x + s / 2.0, y));
+        path.line_to((x - s / 2.0, y + s));
+    } else {
+        path.move_to((x - s, y - s / 2.0));
+        path.line_to((x, y + s / 2.0));
+        path.line_to((x + s, y - s / 2.0));
+    }
+    path
+}
+
 impl EditorGutterView {
     fn compute_widest_text_width(&mut self, attrs_list: &AttrsList) -> f64 {
         let last_line = self.editor.get_untracked().last_line() + 1;
@@ -0,0 +1,126 @@
+//! Named layers of highlighted ranges painted behind the text of an editor, like the
+//! occurrences of the word under the cursor, the current line of a debugger or the ranges of
+//! lint warnings.
+//!
+//! A layer is replaced as a whole by adding a layer with the same name, so the ranges are
+//! cheap to swap. The ranges aren't moved by edits, so layers which depend on the text are
+//! updated by their owner after the edits.
+use std::{ops::Range, rc::Rc};
+
+use peniko::Color;
+
+/// How the ranges of a [`HighlightLayer`] are painted.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct HighlightStyle {
+    pub background: Option<Color>,
+    pub border: Option<Color>,
+    pub corner_radius: f64,
+    /// A wavy line under the ranges, like for lint warnings
+    pub wave_line: Option<Color>,
+    /// Whether the whole width of the lines of the ranges is highlighted, like the current line
+    /// of a debugger
+    pub full_line: bool,
+    /// The layers with a higher z index are painted above the others. The layers with a
+    /// negative z index are painted below the selection, the other ones between the selection
+    /// and the text.
+    pub z_index: i32,
+}
+
+impl HighlightStyle {
+    pub fn background(color: Color) -> Self {
+        HighlightStyle {
+            background: Some(color),
+            ..Default::default()
+        }
+    }
+
+    pub fn border(mut self, color: Color) -> Self {
+        self.border = Some(color);
+        self
+    }
+
+    pub fn corner_radius(mut self, radius: f64) -> Self {
+        self.corner_radius = radius;
+        self
+    }
+
+    pub fn wave_line(mut self, color: Color) -> Self {
+        self.wave_line = Some(color);
+        self
+    }
+
+    pub fn full_line(mut self) -> Self {
+        self.full_line = true;
+        self
+    }
+
+    pub fn z_index(mut self, z_index: i32) -> Self {
+        self.z_index = z_index;
+        self
+    }
+}
+
+/// A named set of ranges of the document highlighted with the same style, see
+/// [`Editor::add_highlight_layer`](super::Editor::add_highlight_layer).
+#[derive(Clone, Debug, PartialEq)]
+pub struct HighlightLayer {
+    pub name: String,
+    pub ranges: Rc<[Range<usize>]>,
+    pub style: HighlightStyle,
+}
+
+impl HighlightLayer {
+    /// The ranges which intersect `visible`, with empty ranges widened to the next character
+    /// so that they stay visible.
+    pub(crate) fn visible_ranges(
+        &self,
+        visible: Range<usize>,
+    ) -> impl Iterator<Item = Range<usize>> + '_ {
+        self.ranges.iter().filter_map(move |range| {
+            let start = range.start.min(range.end);
+            let end = range.start.max(range.end).max(start + 1);
+            (start < visible.end && end > visible.start)
+                .then(|| start.max(visible.start)..end.min(visible.end))
+        })
+    }
+}
+
+/// Inserts `layer` among `layers`, replacing the layer with the same name, and keeping the
+/// layers sorted by z index, in the order they were added for the same z index.
+pub(crate) fn insert_layer(layers: &mut Vec<HighlightLayer>, layer: HighlightLayer) {
+    layers.retain(|l| l.name != layer.name);
+    let index = layers.partition_point(|l| l.style.z_index <= layer.style.z_index);
+    layers.insert(index, layer);
+}
+
+#[cfg(test)]
+mod tests {
+    use peniko::color::palette;
+
+    use super::{insert_layer, HighlightLayer, HighlightStyle};
+
+    fn layer(name: &str, ranges: &[std::ops::Range<usize>], z_index: i32) -> HighlightLayer {
+        HighlightLayer {
+            name: name.to_string(),
+            ranges: ranges.into(),
+            style: HighlightStyle::background(palette::css::YELLOW).z_index(z_index),
+        }
+    }
+
+    #[test]
+    fn layers() {
+        let mut layers = Vec::new();
+        insert_layer(&mut layers, layer("occurrences", &[2..5], 0));
+        insert_layer(&mut layers, layer("debugger", &[], -1));
+        insert_layer(&mut layers, layer("lints", &[], 0));
+        insert_layer(
+            &mut layers,
+            layer("occurrences", &[8..9, 20..20, 40..60], 0),
+        );
+        let names: Vec<&str> = layers.iter().map(|l| l.name.as_str()).collect();
+        assert_eq!(names, ["debugger", "lints", "occurrences"]);
+
+        let visible: Vec<_> = layers[2].visible_ranges(10..50).collect();
+        assert_eq!(visible, [20..21, 40..50]);
+    }
+}
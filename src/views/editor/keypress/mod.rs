@@ -131,6 +131,8 @@ fn add_default_common(c: &mut HashMap<KeyPress, Command>) {
         key("down", Modifiers::ALT | Modifiers::SHIFT),
         Command::Edit(EditCommand::DuplicateLineDown),
     );
+
+    c.insert(key_d("f9"), Command::Edit(EditCommand::SortLines));
 }
 
 fn add_default_windows(c: &mut HashMap<KeyPress, Command>) {
@@ -244,12 +246,21 @@ fn add_default_macos(c: &mut HashMap<KeyPress, Command>) {
         key("l", Modifiers::META | Modifiers::SHIFT),
         Command::MultiSelection(MultiSelectionCommand::SelectAllCurrent),
     );
+    c.insert(
+        key("d", Modifiers::META),
+        Command::MultiSelection(MultiSelectionCommand::SelectNextCurrent),
+    );
 
     c.insert(
         key("u", Modifiers::META),
         Command::MultiSelection(MultiSelectionCommand::SelectUndo),
     );
 
+    c.insert(
+        key("j", Modifiers::META),
+        Command::Edit(EditCommand::JoinLines),
+    );
+
     // --- ---- ---
     c.insert(
         key("up", Modifiers::META),
@@ -357,12 +368,21 @@ fn add_default_nonmacos(c: &mut HashMap<KeyPress, Command>) {
         key("l", Modifiers::CONTROL | Modifiers::SHIFT),
         Command::MultiSelection(MultiSelectionCommand::SelectAllCurrent),
     );
+    c.insert(
+        key("d", Modifiers::CONTROL),
+        Command::MultiSelection(MultiSelectionCommand::SelectNextCurrent),
+    );
 
     c.insert(
         key("u", Modifiers::CONTROL),
         Command::MultiSelection(MultiSelectionCommand::SelectUndo),
     );
 
+    c.insert(
+        key("j", Modifiers::CONTROL),
+        Command::Edit(EditCommand::JoinLines),
+    );
+
     // --- Navigation ---
     c.insert(
         key("home", Modifiers::CONTROL),
@@ -0,0 +1,318 @@
+//! The heights of the lines of an editor, summed so that y positions are converted to lines and
+//! back in `O(log n)`, whatever the height of each line.
+//!
+//! The heights are kept by [`Lines`](super::visual_line::Lines) with the text layouts, see
+//! [`Editor::with_line_heights`](super::Editor::with_line_heights). A line which wasn't laid out
+//! yet has an estimated number of visual lines, until its text layout is created.
+
+use std::{collections::BTreeSet, ops::Range};
+
+/// The heights of the buffer lines of a document, each the height of a visual line of the line
+/// times its number of visual lines.
+///
+/// The heights are summed in a Fenwick tree, so that changing the number of visual lines of a
+/// line, and the conversions between y positions and lines, don't go over every line. Edits
+/// splice the lines and mark the edited ones as stale, so that only those are measured again.
+#[derive(Debug, Clone, Default)]
+pub struct LineHeights {
+    /// The height of a visual line of each buffer line, `0` for hidden lines
+    line_heights: Vec<f64>,
+    /// The number of visual lines of each buffer line
+    vline_counts: Vec<usize>,
+    /// The Fenwick tree of the heights of the lines, from index 1
+    tree: Vec<f64>,
+    /// The lines whose height or number of visual lines may have changed since they were set
+    stale: BTreeSet<usize>,
+    /// Whether the number of visual lines of every line may have changed, like when the
+    /// wrapping width changes
+    stale_vline_counts: bool,
+}
+
+impl LineHeights {
+    /// The heights of lines with a visual line of `line_heights[line]`, and `vline_counts[line]`
+    /// visual lines.
+    pub fn new(line_heights: Vec<f64>, vline_counts: Vec<usize>) -> Self {
+        debug_assert_eq!(line_heights.len(), vline_counts.len());
+        let mut heights = LineHeights {
+            line_heights,
+            vline_counts,
+            tree: Vec::new(),
+            stale: BTreeSet::new(),
+            stale_vline_counts: false,
+        };
+        heights.build_tree();
+        heights
+    }
+
+    /// Builds the tree from the heights of the lines, in `O(n)`.
+    fn build_tree(&mut self) {
+        self.tree.clear();
+        self.tree.resize(self.line_heights.len() + 1, 0.0);
+        for i in 0..self.line_heights.len() {
+            self.tree[i + 1] += self.height(i);
+            let parent = i + 1 + lowest_bit(i + 1);
+            if parent < self.tree.len() {
+                self.tree[parent] += self.tree[i + 1];
+            }
+        }
+    }
+
+    /// Adds `delta` to the height of `line` in the tree.
+    fn add_height(&mut self, line: usize, delta: f64) {
+        let mut i = line + 1;
+        while i < self.tree.len() {
+            self.tree[i] += delta;
+            i += lowest_bit(i);
+        }
+    }
+
+    /// The number of lines.
+    pub fn len(&self) -> usize {
+        self.line_heights.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.line_heights.is_empty()
+    }
+
+    /// The height of a visual line of `line`.
+    pub fn line_height(&self, line: usize) -> f64 {
+        self.line_heights.get(line).copied().unwrap_or(0.0)
+    }
+
+    /// The number of visual lines of `line`.
+    pub fn vline_count(&self, line: usize) -> usize {
+        self.vline_counts.get(line).copied().unwrap_or(1)
+    }
+
+    /// The height of all the visual lines of `line`.
+    pub fn height(&self, line: usize) -> f64 {
+        self.line_height(line) * self.vline_count(line) as f64
+    }
+
+    /// Sets the number of visual lines of `line`, once it's laid out.
+    pub fn set_vline_count(&mut self, line: usize, count: usize) {
+        let Some(previous) = self.vline_counts.get_mut(line) else {
+            return;
+        };
+        let delta = self.line_heights[line] * (count as f64 - *previous as f64);
+        *previous = count;
+        self.add_height(line, delta);
+    }
+
+    /// Sets the height of a visual line and the number of visual lines of `line`, which is no
+    /// longer stale.
+    pub fn set_line(&mut self, line: usize, line_height: f64, count: usize) {
+        if line >= self.len() {
+            return;
+        }
+        let delta = line_height * count as f64 - self.height(line);
+        self.line_heights[line] = line_height;
+        self.vline_counts[line] = count;
+        self.stale.remove(&line);
+        self.add_height(line, delta);
+    }
+
+    /// Marks `lines` as stale, so that their heights are set again before they're used.
+    pub fn invalidate(&mut self, lines: Range<usize>) {
+        self.stale.extend(lines.start..lines.end.min(self.len()));
+    }
+
+    /// Replaces the `old_count` lines from `start_line` by `new_count` stale lines, moving the
+    /// lines after them.
+    pub fn splice(&mut self, start_line: usize, old_count: usize, new_count: usize) {
+        let start_line = start_line.min(self.len());
+        let old_end = (start_line + old_count).min(self.len());
+        if old_end - start_line == new_count {
+            self.invalidate(start_line..old_end);
+            return;
+        }
+        self.line_heights
+            .splice(start_line..old_end, std::iter::repeat_n(0.0, new_count));
+        self.vline_counts
+            .splice(start_line..old_end, std::iter::repeat_n(1, new_count));
+        let moved = self.stale.split_off(&old_end);
+        self.stale.retain(|line| *line < start_line);
+        self.stale.extend(start_line..start_line + new_count);
+        self.stale.extend(
+            moved
+                .into_iter()
+                .map(|line| line - old_end + start_line + new_count),
+        );
+        self.build_tree();
+    }
+
+    /// Adds or removes lines at the end so that there are `len` lines, the added lines being
+    /// stale.
+    pub fn resize(&mut self, len: usize) {
+        let old_len = self.len();
+        if len < old_len {
+            self.splice(len, old_len - len, 0);
+        } else if len > old_len {
+            self.splice(old_len, 0, len - old_len);
+        }
+    }
+
+    /// Marks the number of visual lines of every line as stale, the heights of their visual
+    /// lines are kept.
+    pub fn invalidate_vline_counts(&mut self) {
+        self.stale_vline_counts = true;
+    }
+
+    /// Whether the number of visual lines of every line has to be set again with
+    /// [`LineHeights::set_vline_counts`].
+    pub fn has_stale_vline_counts(&self) -> bool {
+        self.stale_vline_counts
+    }
+
+    /// Sets the number of visual lines of every line to `vline_count(line)`, in `O(n)`.
+    pub fn set_vline_counts(&mut self, vline_count: impl Fn(usize) -> usize) {
+        for (line, count) in self.vline_counts.iter_mut().enumerate() {
+            *count = vline_count(line);
+        }
+        self.stale_vline_counts = false;
+        self.build_tree();
+    }
+
+    /// Removes the stale lines, which have to be set again with [`LineHeights::set_line`].
+    pub fn take_stale(&mut self) -> BTreeSet<usize> {
+        std::mem::take(&mut self.stale)
+    }
+
+    /// The y position of the top of `line`, which is the sum of the heights of the lines before
+    /// it. The lines past the last line are at the bottom of the last line.
+    pub fn y_of_line(&self, line: usize) -> f64 {
+        let mut i = line.min(self.len());
+        let mut y = 0.0;
+        while i > 0 {
+            y += self.tree[i];
+            i -= lowest_bit(i);
+        }
+        y
+    }
+
+    /// The height of all the lines.
+    pub fn total_height(&self) -> f64 {
+        self.y_of_line(self.len())
+    }
+
+    /// The number of lines which end at or above `y`, or before `y` when not `inclusive`.
+    fn lines_above(&self, y: f64, inclusive: bool) -> usize {
+        let mut line = 0;
+        let mut rest = y;
+        let mut step = self.tree.len().next_power_of_two() / 2;
+        while step > 0 {
+            let next = line + step;
+            if next < self.tree.len() {
+                let height = self.tree[next];
+                if height < rest || (inclusive && height == rest) {
+                    line = next;
+                    rest -= height;
+                }
+            }
+            step /= 2;
+        }
+        line
+    }
+
+    /// The line at the height `y`, which is the first line for the positions above the lines and
+    /// the last line with a height for the positions below them. The lines without a height,
+    /// like hidden lines, are never at a height.
+    pub fn line_at_y(&self, y: f64) -> usize {
+        if self.is_empty() {
+            return 0;
+        }
+        let line = self.lines_above(y, true);
+        if line < self.len() {
+            line
+        } else {
+            self.lines_above(self.total_height(), false)
+                .min(self.len() - 1)
+        }
+    }
+
+    /// The `(line, line_index)` of the visual line at the height `y`, like
+    /// [`LineHeights::line_at_y`].
+    pub fn vline_at_y(&self, y: f64) -> (usize, usize) {
+        let line = self.line_at_y(y);
+        let line_height = self.line_height(line);
+        let line_index = if line_height > 0.0 {
+            ((y - self.y_of_line(line)) / line_height).floor().max(0.0) as usize
+        } else {
+            0
+        };
+        (
+            line,
+            line_index.min(self.vline_count(line).saturating_sub(1)),
+        )
+    }
+}
+
+/// The lowest set bit of `i`.
+fn lowest_bit(i: usize) -> usize {
+    i & i.wrapping_neg()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LineHeights;
+
+    #[test]
+    fn positions() {
+        let mut heights = LineHeights::new(vec![10.0, 20.0, 0.0, 10.0, 15.0], vec![1; 5]);
+        assert_eq!(heights.total_height(), 55.0);
+        assert_eq!(heights.y_of_line(2), 30.0);
+        assert_eq!(heights.y_of_line(3), 30.0);
+        assert_eq!(heights.y_of_line(9), 55.0);
+
+        assert_eq!(heights.line_at_y(-5.0), 0);
+        assert_eq!(heights.line_at_y(9.9), 0);
+        assert_eq!(heights.line_at_y(10.0), 1);
+        // The hidden line 2 is skipped
+        assert_eq!(heights.line_at_y(30.0), 3);
+        assert_eq!(heights.line_at_y(54.0), 4);
+        assert_eq!(heights.line_at_y(100.0), 4);
+
+        // Line 1 wraps on three visual lines
+        heights.set_vline_count(1, 3);
+        assert_eq!(heights.total_height(), 95.0);
+        assert_eq!(heights.y_of_line(3), 70.0);
+        assert_eq!(heights.vline_at_y(55.0), (1, 2));
+        assert_eq!(heights.vline_at_y(75.0), (3, 0));
+    }
+
+    #[test]
+    fn hidden_last_lines() {
+        let heights = LineHeights::new(vec![10.0, 10.0, 0.0, 0.0], vec![1; 4]);
+        assert_eq!(heights.line_at_y(20.0), 1);
+        assert_eq!(heights.vline_at_y(25.0), (1, 0));
+    }
+
+    #[test]
+    fn splice() {
+        let mut heights = LineHeights::new(vec![10.0; 5], vec![1, 2, 1, 1, 1]);
+        heights.invalidate(4..5);
+
+        // Lines 1 and 2 are replaced by three lines
+        heights.splice(1, 2, 3);
+        assert_eq!(heights.len(), 6);
+        assert_eq!(heights.total_height(), 30.0);
+        assert_eq!(heights.y_of_line(4), 10.0);
+        assert_eq!(
+            heights.take_stale().into_iter().collect::<Vec<_>>(),
+            [1, 2, 3, 5]
+        );
+
+        for line in 1..4 {
+            heights.set_line(line, 20.0, 1);
+        }
+        assert_eq!(heights.total_height(), 90.0);
+        assert_eq!(heights.line_at_y(75.0), 4);
+
+        // Line 4 is removed, and a line is appended
+        heights.splice(4, 1, 0);
+        heights.resize(6);
+        assert_eq!(heights.total_height(), 80.0);
+        assert_eq!(heights.take_stale().into_iter().collect::<Vec<_>>(), [5]);
+    }
+}
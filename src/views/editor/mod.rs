@@ -2,15 +2,16 @@ use core::indent::IndentStyle;
 use std::{
     cell::{Cell, RefCell},
     cmp::Ordering,
-    collections::{hash_map::DefaultHasher, HashMap},
+    collections::{hash_map::DefaultHasher, BTreeSet, HashMap},
     hash::{Hash, Hasher},
+    ops::Range,
     rc::Rc,
     sync::Arc,
     time::Duration,
 };
 
 use crate::{
-    action::{exec_after, TimerToken},
+    action::{exec_after, ImePurpose, TimerToken},
     keyboard::Modifiers,
     kurbo::{Point, Rect, Vec2},
     peniko::color::palette,
@@ -18,7 +19,7 @@ use crate::{
     pointer::{PointerInputEvent, PointerMoveEvent},
     prop, prop_extractor,
     reactive::{batch, untrack, ReadSignal, RwSignal, Scope},
-    style::{CursorColor, StylePropValue, TextColor},
+    style::{CursorColor, SelectionCornerRadius, StylePropValue, TextColor},
     text::{Attrs, AttrsList, LineHeightValue, TextLayout, Wrap},
     view::{IntoView, View},
     views::text,
@@ -27,40 +28,55 @@ use floem_editor_core::{
     buffer::rope_text::{RopeText, RopeTextVal},
     command::MoveCommand,
     cursor::{ColPosition, Cursor, CursorAffinity, CursorMode},
-    mode::Mode,
+    editor::EditType,
+    mode::{Mode, VisualMode},
     movement::Movement,
     register::Register,
-    selection::Selection,
+    selection::{SelRegion, Selection},
     soft_tab::{snap_to_soft_tab_line_col, SnapDirection},
 };
-use floem_reactive::{SignalGet, SignalTrack, SignalUpdate, SignalWith, Trigger};
+use floem_reactive::{create_effect, SignalGet, SignalTrack, SignalUpdate, SignalWith, Trigger};
 use lapce_xi_rope::Rope;
 
 pub mod actions;
+pub mod anchor;
+pub mod bookmarks;
 pub mod color;
 pub mod command;
+pub mod comments;
+pub mod diff_hunk;
+pub mod encoding;
+pub mod folding;
 pub mod gutter;
+pub mod highlight;
 pub mod id;
 pub mod keypress;
 pub mod layout;
+pub mod line_heights;
 pub mod listener;
 pub mod movement;
+pub mod paste;
 pub mod phantom_text;
 pub mod text;
 pub mod text_document;
 pub mod view;
 pub mod visual_line;
+pub mod watched_file;
 
 pub use floem_editor_core as core;
 use peniko::Brush;
 
 use self::{
     command::Command,
+    folding::{FoldingRange, HiddenLines, FOLD_PLACEHOLDER},
+    gutter::{GutterMark, GutterMarksFn},
+    highlight::{HighlightLayer, HighlightStyle},
     id::EditorId,
     layout::TextLayoutLine,
-    phantom_text::PhantomTextLine,
-    text::{Document, Preedit, PreeditData, RenderWhitespace, Styling, WrapMethod},
-    view::{LineInfo, ScreenLines, ScreenLinesBase},
+    line_heights::LineHeights,
+    phantom_text::{PhantomText, PhantomTextKind, PhantomTextLine},
+    text::{CaretShape, Document, Preedit, PreeditData, RenderWhitespace, Styling, WrapMethod},
+    view::{LineInfo, ScreenLines, ScreenLinesBase, ScreenLinesChange},
     visual_line::{
         hit_position_aff, ConfigId, FontSizeCacheId, LayoutEvent, LineFontSizeProvider, Lines,
         RVLine, ResolvedWrap, TextLayoutProvider, VLine, VLineInfo,
@@ -75,6 +91,7 @@ impl StylePropValue for WrapMethod {
 }
 prop!(pub CursorSurroundingLines: usize {} = 1);
 prop!(pub ScrollBeyondLastLine: bool {} = false);
+prop!(pub LogicalLineMovement: bool {} = false);
 prop!(pub ShowIndentGuide: bool {} = false);
 prop!(pub Modal: bool {} = false);
 prop!(pub ModalRelativeLine: bool {} = false);
@@ -88,6 +105,15 @@ impl StylePropValue for RenderWhitespace {
         Some(crate::views::text(self).into_any())
     }
 }
+prop!(pub CaretShapeProp: CaretShape {} = CaretShape::Bar);
+prop!(pub NormalCaretShape: CaretShape {} = CaretShape::Block);
+prop!(pub OverwriteCaretShape: CaretShape {} = CaretShape::Underline);
+impl StylePropValue for CaretShape {
+    fn debug_view(&self) -> Option<Box<dyn View>> {
+        Some(crate::views::text(self).into_any())
+    }
+}
+prop!(pub CaretWidth: f64 {} = 2.0);
 prop!(pub IndentStyleProp: IndentStyle {} = IndentStyle::Spaces(4));
 impl StylePropValue for IndentStyle {
     fn debug_view(&self) -> Option<Box<dyn View>> {
@@ -98,6 +124,7 @@ prop!(pub DropdownShadow: Option<Color> {} = None);
 prop!(pub Foreground: Color { inherited } = Color::from_rgb8(0x38, 0x3A, 0x42));
 prop!(pub Focus: Option<Color> {} = None);
 prop!(pub SelectionColor: Color {} = palette::css::BLACK.with_alpha(0.5));
+prop!(pub SelectionBorderColor: Option<Color> {} = None);
 prop!(pub CurrentLineColor: Option<Color> {  } = None);
 prop!(pub Link: Option<Color> {} = None);
 prop!(pub VisibleWhitespaceColor: Color {} = palette::css::TRANSPARENT);
@@ -122,11 +149,20 @@ prop_extractor! {
         pub render_whitespace: RenderWhitespaceProp,
         pub indent_style: IndentStyleProp,
         pub caret: CursorColor,
+        // The shape of the carets in insert mode
+        pub caret_shape: CaretShapeProp,
+        pub normal_caret_shape: NormalCaretShape,
+        pub overwrite_caret_shape: OverwriteCaretShape,
+        pub caret_width: CaretWidth,
         pub selection: SelectionColor,
+        pub selection_border: SelectionBorderColor,
+        pub selection_corner_radius: SelectionCornerRadius,
         pub current_line: CurrentLineColor,
         pub visible_whitespace: VisibleWhitespaceColor,
         pub indent_guide: IndentGuideColor,
         pub scroll_beyond_last_line: ScrollBeyondLastLine,
+        // Whether up/down and home/end move by logical lines instead of wrapped lines
+        pub logical_line_movement: LogicalLineMovement,
     }
 }
 impl EditorStyle {
@@ -142,6 +178,40 @@ impl EditorStyle {
 
 pub(crate) const CHAR_WIDTH: f64 = 7.5;
 
+/// How [`Editor::link_scroll_with`] maps the scroll position of an editor to the other editor.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SyncMode {
+    /// The editors are scrolled by the same fraction of their scrollable height, for documents
+    /// of different lengths like a document and its preview
+    Proportional,
+    /// The editors show the same line at their top, for documents whose lines correspond like
+    /// the panes of a diff
+    LineAligned,
+}
+
+/// The unit a pointer drag extends the selection by, depending on the number of clicks which
+/// started the drag.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum DragUnit {
+    Word,
+    Line,
+    Paragraph,
+}
+
+/// The visual line last hit by the pointer with its text layout, which are reused while the
+/// pointer stays on the line rather than looked up for each pointer move.
+#[derive(Clone)]
+struct LineHit {
+    cache_rev: u64,
+    config_id: ConfigId,
+    /// The visual lines on the screen when the line was hit, the line is at the same position
+    /// as long as they are the same
+    screen_lines: Rc<Vec<RVLine>>,
+    y: f64,
+    info: VLineInfo<()>,
+    text_layout: Arc<TextLayoutLine>,
+}
+
 /// The main structure for the editor view itself.  
 /// This can be considered to be the data part of the `View`.
 /// It holds an `Rc<dyn Document>` within as the document it is a view into.  
@@ -153,6 +223,19 @@ pub struct Editor {
     id: EditorId,
 
     pub active: RwSignal<bool>,
+    /// The unit a pointer drag extends the selection by, with the range selected by the click
+    /// which started the drag
+    drag_selection: RwSignal<Option<(DragUnit, usize, usize)>>,
+    /// The point of an Alt+click with the selection before it, dragging the pointer from it
+    /// selects a block of columns
+    block_selection: RwSignal<Option<(Point, Selection)>>,
+    /// The last pointer move, which is handled at the next pass of the timers so that the moves
+    /// in between are dropped, see [`Editor::pointer_move`]
+    pending_pointer_move: RwSignal<Option<PointerMoveEvent>>,
+    /// Notified by the timer to handle the pending pointer move, in an effect which is disposed
+    /// with the editor
+    pointer_move_trigger: Trigger,
+    last_hit: RwSignal<Option<LineHit>>,
 
     /// Whether you can edit within this editor.
     pub read_only: RwSignal<bool>,
@@ -177,6 +260,9 @@ pub struct Editor {
     /// Holds the cache of the lines and provides many utility functions for them.
     lines: Rc<Lines>,
     pub screen_lines: RwSignal<ScreenLines>,
+    /// Recomputes the screen lines in the effect which tracks what
+    /// [`Document::compute_screen_lines`] reads, see [`Editor::update_screen_lines`]
+    screen_lines_trigger: Trigger,
 
     /// Modal mode register
     pub register: RwSignal<Register>,
@@ -188,11 +274,26 @@ pub struct Editor {
     /// Whether ime input is allowed.  
     /// Should not be set manually outside of the specific handling for ime.
     pub ime_allowed: RwSignal<bool>,
+    /// The kind of text typed into the editor, for the IME and the virtual keyboards.
+    pub ime_purpose: RwSignal<ImePurpose>,
 
     /// The Editor Style
     pub es: RwSignal<EditorStyle>,
 
     pub floem_style_id: RwSignal<u64>,
+
+    /// The marks painted in the gutter, added with [`Editor::add_gutter_marks`]
+    pub(crate) gutter_marks: RwSignal<Vec<GutterMarksFn>>,
+
+    /// The highlight layers painted behind the text, sorted by z index, see
+    /// [`Editor::add_highlight_layer`]
+    pub highlight_layers: RwSignal<Vec<HighlightLayer>>,
+
+    /// The first lines of the folded ranges, see [`Editor::toggle_fold`]
+    pub folded_lines: RwSignal<BTreeSet<usize>>,
+
+    /// The large paste which is being inserted in chunks, see [`paste::paste_in_chunks`]
+    pub active_paste: RwSignal<Option<paste::ChunkedPaste>>,
 }
 impl Editor {
     /// Create a new editor into the given document, using the styling.  
@@ -272,6 +373,11 @@ impl Editor {
             effects_cx: Cell::new(cx.create_child()),
             id,
             active: cx.create_rw_signal(false),
+            drag_selection: cx.create_rw_signal(None),
+            block_selection: cx.create_rw_signal(None),
+            pending_pointer_move: cx.create_rw_signal(None),
+            pointer_move_trigger: cx.create_trigger(),
+            last_hit: cx.create_rw_signal(None),
             read_only: cx.create_rw_signal(false),
             doc,
             style,
@@ -286,14 +392,21 @@ impl Editor {
             editor_view_id: cx.create_rw_signal(None),
             lines,
             screen_lines,
+            screen_lines_trigger: cx.create_trigger(),
             register: cx.create_rw_signal(Register::default()),
             cursor_info: CursorInfo::new(cx),
             last_movement: cx.create_rw_signal(Movement::Left),
             ime_allowed: cx.create_rw_signal(false),
+            ime_purpose: cx.create_rw_signal(ImePurpose::Normal),
             es: editor_style,
             floem_style_id: cx.create_rw_signal(0),
+            gutter_marks: cx.create_rw_signal(Vec::new()),
+            highlight_layers: cx.create_rw_signal(Vec::new()),
+            folded_lines: cx.create_rw_signal(BTreeSet::new()),
+            active_paste: cx.create_rw_signal(None),
         };
 
+        ed.doc().add_editor(&ed);
         create_view_effects(ed.effects_cx.get(), &ed);
 
         ed
@@ -343,6 +456,9 @@ impl Editor {
                 doc: self.doc.read_only(),
             });
             self.lines.clear(0, None);
+            self.lines.hidden_lines.borrow_mut().take();
+            self.doc().remove_editor(self);
+            doc.add_editor(self);
             self.doc.set(doc);
             if let Some(styling) = styling {
                 self.style.set(styling);
@@ -407,6 +523,12 @@ impl Editor {
             editor.register.set(self.register.get_untracked());
             editor.cursor_info = self.cursor_info.clone();
             editor.last_movement.set(self.last_movement.get_untracked());
+            editor.gutter_marks.set(self.gutter_marks.get_untracked());
+            editor.ime_purpose.set(self.ime_purpose.get_untracked());
+            editor
+                .highlight_layers
+                .set(self.highlight_layers.get_untracked());
+            editor.folded_lines.set(self.folded_lines.get_untracked());
             // ?
             // editor.ime_allowed.set(self.ime_allowed.get_untracked());
         });
@@ -416,6 +538,153 @@ impl Editor {
         editor
     }
 
+    /// Paint the marks returned by `marks` in the gutter, which is repainted when the signals
+    /// read by `marks` change.
+    pub fn add_gutter_marks(&self, marks: impl Fn() -> Vec<GutterMark> + 'static) {
+        self.gutter_marks
+            .update(|gutter_marks| gutter_marks.push(Rc::new(marks)));
+    }
+
+    /// Highlight `ranges` of the document with `style`, replacing the layer named `name` if
+    /// there's one, like `editor.add_highlight_layer("occurrences", ranges, style)`.
+    ///
+    /// The ranges aren't moved by edits, the layer is replaced when they change.
+    pub fn add_highlight_layer(
+        &self,
+        name: impl Into<String>,
+        ranges: impl Into<Rc<[Range<usize>]>>,
+        style: HighlightStyle,
+    ) {
+        let layer = HighlightLayer {
+            name: name.into(),
+            ranges: ranges.into(),
+            style,
+        };
+        self.highlight_layers
+            .update(|layers| highlight::insert_layer(layers, layer));
+    }
+
+    pub fn remove_highlight_layer(&self, name: &str) {
+        self.highlight_layers
+            .update(|layers| layers.retain(|layer| layer.name != name));
+    }
+
+    /// The ranges of the document which are folded in this editor.
+    pub fn folded_ranges(&self) -> Vec<FoldingRange> {
+        let ranges = self.doc().folding_ranges();
+        self.folded_lines.with_untracked(|folded| {
+            ranges
+                .into_iter()
+                .filter(|range| {
+                    range.start_line < range.end_line && folded.contains(&range.start_line)
+                })
+                .collect()
+        })
+    }
+
+    /// Whether the range of the document starting at `line` is folded in this editor.
+    pub fn is_folded(&self, line: usize) -> bool {
+        self.folded_lines
+            .with_untracked(|folded| folded.contains(&line))
+            && self
+                .doc()
+                .folding_ranges()
+                .iter()
+                .any(|range| range.start_line == line && range.start_line < range.end_line)
+    }
+
+    /// Folds or unfolds the range of the document starting at `line`. Does nothing if there's
+    /// no such range in [`Document::folding_ranges`].
+    ///
+    /// Only the layout of `line` changes, for the placeholder after its text, so the other
+    /// layouts are kept. The hidden lines and the screen lines are then updated by the effect
+    /// on [`Editor::folded_lines`].
+    pub fn toggle_fold(&self, line: usize) {
+        let Some(range) = self
+            .doc()
+            .folding_ranges()
+            .into_iter()
+            .find(|range| range.start_line == line && range.start_line < range.end_line)
+        else {
+            return;
+        };
+
+        self.lines.invalidate_line(line);
+        let folded = self.folded_lines.try_update(|folded| {
+            let folded_line = !folded.remove(&line);
+            if folded_line {
+                folded.insert(line);
+            }
+            folded_line
+        });
+
+        // Move the cursor out of the hidden lines, so that it stays visible
+        if folded == Some(true) {
+            let offset = self.cursor.with_untracked(|cursor| cursor.offset());
+            if range.hidden_lines().contains(&self.line_of_offset(offset)) {
+                let offset = self.offset_of_line_col(line, self.line_end_col(line, true));
+                self.cursor
+                    .update(|cursor| cursor.set_offset(offset, false, false));
+            }
+        }
+    }
+
+    /// The lines hidden by the folded ranges, sorted and merged. They're kept until the
+    /// document or the folded lines change.
+    pub(crate) fn hidden_lines(&self) -> Rc<[Range<usize>]> {
+        let cache_rev = self.doc().cache_rev().get_untracked();
+        let old = self.lines.hidden_lines.borrow().clone();
+        if let Some(old) = &old {
+            if old.cache_rev == cache_rev && !old.moved {
+                return old.lines.clone();
+            }
+        }
+        let hidden: Rc<[Range<usize>]> = folding::hidden_lines(&self.folded_ranges()).into();
+        *self.lines.hidden_lines.borrow_mut() = Some(HiddenLines {
+            cache_rev,
+            lines: hidden.clone(),
+            moved: false,
+        });
+        match old {
+            // The heights were moved with the hidden lines by the edits, so only the lines whose
+            // folded ranges didn't follow the edits are measured again
+            Some(old) if old.cache_rev == cache_rev => {
+                if old.lines != hidden {
+                    for lines in old.lines.iter().chain(hidden.iter()) {
+                        self.lines.invalidate_heights(lines.clone());
+                    }
+                }
+            }
+            // The heights may be for other hidden lines
+            Some(old) if !old.lines.is_empty() || !hidden.is_empty() => self.lines.clear_heights(),
+            _ => {}
+        }
+        hidden
+    }
+
+    /// Computes the hidden lines again after lines were folded or unfolded, and marks the
+    /// heights of the lines which were or are now hidden as stale.
+    fn update_hidden_lines(&self) {
+        let cache_rev = self.doc().cache_rev().get_untracked();
+        let old = self.lines.hidden_lines.borrow_mut().take();
+        let new = self.hidden_lines();
+        match old {
+            Some(HiddenLines {
+                cache_rev: rev,
+                lines: old,
+                ..
+            }) if rev == cache_rev => {
+                if old != new {
+                    for lines in old.iter().chain(new.iter()) {
+                        self.lines.invalidate_heights(lines.clone());
+                    }
+                }
+            }
+            // The heights may be for other hidden lines
+            _ => self.lines.clear_heights(),
+        }
+    }
+
     /// Get the styling untracked
     pub fn style(&self) -> Rc<dyn Styling> {
         self.style.get_untracked()
@@ -483,6 +752,45 @@ impl Editor {
         self.doc().compute_screen_lines(self, base)
     }
 
+    /// Updates the screen lines after `change`, recomputing them only when the change can have
+    /// altered the visual lines in the viewport or their positions.
+    ///
+    /// The screen lines are computed in an effect, which also recomputes them when anything
+    /// [`Document::compute_screen_lines`] reads through signals changes. This only needs to be
+    /// called for the changes which aren't read through signals.
+    pub fn update_screen_lines(&self, change: ScreenLinesChange) {
+        let recompute = match change {
+            ScreenLinesChange::Viewport(viewport) => {
+                let base = self.screen_lines.with_untracked(|sl| sl.base);
+                let active = base.with_untracked(|base| base.active_viewport);
+                if viewport != active {
+                    base.update(|base| base.active_viewport = viewport);
+                }
+                // The positions of the lines are relative to the top of the viewport
+                viewport.y0 != active.y0
+                    || viewport.y1 != active.y1
+                    || self.screen_lines.with_untracked(ScreenLines::is_empty)
+            }
+            ScreenLinesChange::CacheRev | ScreenLinesChange::Style => true,
+            ScreenLinesChange::CreatedLayout(line) => self
+                .screen_lines
+                .get_untracked()
+                .on_created_layout(self, line),
+        };
+        if !recompute {
+            return;
+        }
+
+        self.screen_lines_trigger.notify();
+
+        if let ScreenLinesChange::CreatedLayout(line) = change {
+            // Ensure that it is created even after the base/viewport signals have been updated.
+            // But we have to trigger an event since it could alter the screenlines
+            // TODO: this has some risk for infinite looping if we're unlucky.
+            self.text_layout_trigger(line, true);
+        }
+    }
+
     /// Default handler for `PointerDown` event
     pub fn pointer_down(&self, pointer_event: &PointerInputEvent) {
         if pointer_event.button.is_primary() {
@@ -490,6 +798,8 @@ impl Editor {
             self.left_click(pointer_event);
         } else if pointer_event.button.is_secondary() {
             self.right_click(pointer_event);
+        } else if pointer_event.button.is_auxiliary() {
+            self.middle_click(pointer_event);
         }
     }
 
@@ -504,26 +814,33 @@ impl Editor {
             3 => {
                 self.triple_click(pointer_event);
             }
+            4 => {
+                self.quadruple_click(pointer_event);
+            }
             _ => {}
         }
     }
 
-    pub fn single_click(&self, pointer_event: &PointerInputEvent) {
-        let mode = self.cursor.with_untracked(|c| c.get_mode());
-        let (new_offset, _) = self.offset_of_point(mode, pointer_event.pos);
-        self.cursor.update(|cursor| {
-            cursor.set_offset(
-                new_offset,
-                pointer_event.modifiers.shift(),
-                pointer_event.modifiers.alt(),
-            )
-        });
+    /// The range of the `unit` at `offset`.
+    fn drag_unit_range(&self, unit: DragUnit, offset: usize) -> (usize, usize) {
+        match unit {
+            DragUnit::Word => self.select_word(offset),
+            DragUnit::Line => {
+                let line = self.line_of_offset(offset);
+                (self.offset_of_line(line), self.offset_of_line(line + 1))
+            }
+            DragUnit::Paragraph => self.rope_text().select_paragraph(offset),
+        }
     }
 
-    pub fn double_click(&self, pointer_event: &PointerInputEvent) {
+    /// Selects the `unit` at the pointer, and extends the selection by whole `unit`s when the
+    /// pointer is dragged.
+    fn select_unit(&self, unit: DragUnit, pointer_event: &PointerInputEvent) {
         let mode = self.cursor.with_untracked(|c| c.get_mode());
         let (mouse_offset, _) = self.offset_of_point(mode, pointer_event.pos);
-        let (start, end) = self.select_word(mouse_offset);
+        let (start, end) = self.drag_unit_range(unit, mouse_offset);
+        self.drag_selection.set(Some((unit, start, end)));
+        self.block_selection.set(None);
 
         self.cursor.update(|cursor| {
             cursor.add_region(
@@ -535,34 +852,176 @@ impl Editor {
         });
     }
 
-    pub fn triple_click(&self, pointer_event: &PointerInputEvent) {
+    /// Moves the cursor to the pointer. Alt+click adds a caret instead, and dragging from it
+    /// selects a block of columns.
+    pub fn single_click(&self, pointer_event: &PointerInputEvent) {
+        self.drag_selection.set(None);
         let mode = self.cursor.with_untracked(|c| c.get_mode());
-        let (mouse_offset, _) = self.offset_of_point(mode, pointer_event.pos);
-        let line = self.line_of_offset(mouse_offset);
-        let start = self.offset_of_line(line);
-        let end = self.offset_of_line(line + 1);
-
+        let (new_offset, _) = self.offset_of_point(mode, pointer_event.pos);
+        let modifiers = pointer_event.modifiers;
+        let block_selection = self.cursor.with_untracked(|cursor| match &cursor.mode {
+            CursorMode::Insert(selection) if modifiers.alt() && !modifiers.shift() => {
+                Some((pointer_event.pos, selection.clone()))
+            }
+            _ => None,
+        });
+        self.block_selection.set(block_selection);
         self.cursor.update(|cursor| {
-            cursor.add_region(
-                start,
-                end,
+            cursor.set_offset(
+                new_offset,
                 pointer_event.modifiers.shift(),
                 pointer_event.modifiers.alt(),
             )
         });
     }
 
+    /// Selects the word at the pointer, dragging extends the selection by words.
+    pub fn double_click(&self, pointer_event: &PointerInputEvent) {
+        self.select_unit(DragUnit::Word, pointer_event);
+    }
+
+    /// Selects the line at the pointer, dragging extends the selection by lines.
+    pub fn triple_click(&self, pointer_event: &PointerInputEvent) {
+        self.select_unit(DragUnit::Line, pointer_event);
+    }
+
+    /// Selects the paragraph at the pointer, the lines around it which aren't blank, dragging
+    /// extends the selection by paragraphs.
+    pub fn quadruple_click(&self, pointer_event: &PointerInputEvent) {
+        self.select_unit(DragUnit::Paragraph, pointer_event);
+    }
+
+    /// Extends the selection to the pointer while it's dragged. Pointer moves are coalesced until
+    /// the next pass of the timers, which handles the last position of the pointer. The pending
+    /// move is dropped if the editor is disposed before.
     pub fn pointer_move(&self, pointer_event: &PointerMoveEvent) {
+        if !self.active.get_untracked() {
+            return;
+        }
+        let scheduled = self.pending_pointer_move.with_untracked(Option::is_some);
+        self.pending_pointer_move.set(Some(pointer_event.clone()));
+        if !scheduled {
+            let trigger = self.pointer_move_trigger;
+            exec_after(Duration::ZERO, move |_| trigger.notify());
+        }
+    }
+
+    /// Handles the pending pointer move, if there is one.
+    fn flush_pointer_move(&self) {
+        let Some(Some(pointer_event)) = self.pending_pointer_move.try_update(Option::take) else {
+            return;
+        };
+        if !self.active.get_untracked() {
+            return;
+        }
         let mode = self.cursor.with_untracked(|c| c.get_mode());
         let (offset, _is_inside) = self.offset_of_point(mode, pointer_event.pos);
-        if self.active.get_untracked() && self.cursor.with_untracked(|c| c.offset()) != offset {
+        if let Some((anchor, base)) = self.block_selection.get_untracked() {
+            let selection = self.column_block_selection(&base, anchor, pointer_event.pos);
+            self.cursor.update(|cursor| cursor.set_insert(selection));
+        } else if let Some((unit, start, end)) = self.drag_selection.get_untracked() {
+            let (unit_start, unit_end) = self.drag_unit_range(unit, offset);
+            // The selection keeps the units selected by the click, and grows towards the pointer
+            let (anchor, active) = if unit_start < start {
+                (end, unit_start)
+            } else {
+                (start, unit_end.max(end))
+            };
+            self.cursor.update(|cursor| match &cursor.mode {
+                CursorMode::Insert(selection) => {
+                    let mut selection = selection.clone();
+                    selection.replace_last_inserted_region(SelRegion::new(anchor, active, None));
+                    cursor.set_insert(selection);
+                }
+                _ => {
+                    // Visual selections include their end
+                    let (start, end) = if active >= anchor {
+                        (anchor, active.saturating_sub(1))
+                    } else {
+                        (anchor.saturating_sub(1), active)
+                    };
+                    cursor.mode = CursorMode::Visual {
+                        start,
+                        end,
+                        mode: VisualMode::Normal,
+                    };
+                }
+            });
+        } else if self.cursor.with_untracked(|c| c.offset()) != offset {
             self.cursor
                 .update(|cursor| cursor.set_offset(offset, true, pointer_event.modifiers.alt()));
         }
     }
 
+    /// `base` with a region on each line from `anchor` to `pos`, between the columns at the
+    /// horizontal positions of `anchor` and `pos`. The region on the line of `pos` is the last
+    /// inserted one.
+    fn column_block_selection(&self, base: &Selection, anchor: Point, pos: Point) -> Selection {
+        let ((anchor_line, _), _) = self.line_col_of_point(Mode::Insert, anchor);
+        let ((pos_line, _), _) = self.line_col_of_point(Mode::Insert, pos);
+        let mut lines: Vec<usize> =
+            (anchor_line.min(pos_line)..=anchor_line.max(pos_line)).collect();
+        if pos_line < anchor_line {
+            lines.reverse();
+        }
+
+        let mut selection = base.clone();
+        for line in lines {
+            let start = self.line_horiz_col(line, &ColPosition::Col(anchor.x), true);
+            let end = self.line_horiz_col(line, &ColPosition::Col(pos.x), true);
+            selection.add_region(SelRegion::new(
+                self.offset_of_line_col(line, start),
+                self.offset_of_line_col(line, end),
+                None,
+            ));
+        }
+        selection
+    }
+
     pub fn pointer_up(&self, _pointer_event: &PointerInputEvent) {
+        self.flush_pointer_move();
         self.active.set(false);
+        self.last_hit.set(None);
+        self.set_primary_selection();
+    }
+
+    /// Pastes the primary selection at the pointer, as is usual for a middle click on Linux.
+    pub fn middle_click(&self, pointer_event: &PointerInputEvent) {
+        if self.read_only.get_untracked() {
+            return;
+        }
+        let Ok(text) = crate::Clipboard::get_primary_contents() else {
+            return;
+        };
+        let mode = self.cursor.with_untracked(|c| c.get_mode());
+        let (offset, _) = self.offset_of_point(mode, pointer_event.pos);
+        self.doc()
+            .edit_single(Selection::caret(offset), &text, EditType::Paste);
+        self.cursor
+            .update(|cursor| cursor.set_offset(offset + text.len(), false, false));
+    }
+
+    /// Puts the selected text in the primary selection, so that it can be pasted with a
+    /// middle click. Does nothing on platforms without a primary selection.
+    pub fn set_primary_selection(&self) {
+        let doc = self.doc();
+        let text = self.cursor.with_untracked(|cursor| {
+            let selection = cursor.edit_selection(&doc.rope_text());
+            selection
+                .regions()
+                .iter()
+                .filter(|region| !region.is_caret())
+                .map(|region| {
+                    doc.text()
+                        .slice_to_cow(region.min()..region.max())
+                        .to_string()
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        });
+        if !text.is_empty() {
+            let _ = crate::Clipboard::set_primary_contents(text);
+        }
     }
 
     fn right_click(&self, pointer_event: &PointerInputEvent) {
@@ -581,10 +1040,11 @@ impl Editor {
     // TODO: should this have modifiers state in its api
     pub fn page_move(&self, down: bool, mods: Modifiers) {
         let viewport = self.viewport.get_untracked();
-        // TODO: don't assume line height is constant
-        let line_height = f64::from(self.line_height(0));
-        let lines = (viewport.height() / line_height / 2.0).round() as usize;
-        let distance = (lines as f64) * line_height;
+        // Half of the viewport, as whole visual lines from the top of the viewport
+        let top = self.rvline_at_y(viewport.y0);
+        let middle = self.rvline_at_y(viewport.y0 + viewport.height() / 2.0);
+        let lines = self.vline_of_rvline(middle).get() - self.vline_of_rvline(top).get();
+        let distance = self.y_of_rvline(middle) - self.y_of_rvline(top);
         self.scroll_delta
             .set(Vec2::new(0.0, if down { distance } else { -distance }));
         let cmd = if down {
@@ -598,14 +1058,15 @@ impl Editor {
 
     pub fn center_window(&self) {
         let viewport = self.viewport.get_untracked();
-        // TODO: don't assume line height is constant
-        let line_height = f64::from(self.line_height(0));
-        let offset = self.cursor.with_untracked(|cursor| cursor.offset());
-        let (line, _col) = self.offset_to_line_col(offset);
+        let (offset, affinity) = self
+            .cursor
+            .with_untracked(|cursor| (cursor.offset(), cursor.affinity));
+        let rvline = self.rvline_of_offset(offset, affinity);
+        let line_height = f64::from(self.line_height(rvline.line));
 
         let viewport_center = viewport.height() / 2.0;
 
-        let current_line_position = line as f64 * line_height;
+        let current_line_position = self.y_of_rvline(rvline);
 
         let desired_top = current_line_position - viewport_center + (line_height / 2.0);
 
@@ -616,12 +1077,10 @@ impl Editor {
 
     pub fn top_of_window(&self, scroll_off: usize) {
         let viewport = self.viewport.get_untracked();
-        // TODO: don't assume line height is constant
-        let line_height = f64::from(self.line_height(0));
         let offset = self.cursor.with_untracked(|cursor| cursor.offset());
         let (line, _col) = self.offset_to_line_col(offset);
 
-        let desired_top = (line.saturating_sub(scroll_off)) as f64 * line_height;
+        let desired_top = self.y_of_line(line.saturating_sub(scroll_off));
 
         let scroll_delta = desired_top - viewport.y0;
 
@@ -630,22 +1089,83 @@ impl Editor {
 
     pub fn bottom_of_window(&self, scroll_off: usize) {
         let viewport = self.viewport.get_untracked();
-        // TODO: don't assume line height is constant
-        let line_height = f64::from(self.line_height(0));
         let offset = self.cursor.with_untracked(|cursor| cursor.offset());
         let (line, _col) = self.offset_to_line_col(offset);
 
-        let desired_bottom = (line + scroll_off + 1) as f64 * line_height - viewport.height();
+        let desired_bottom = self.y_of_line(line + scroll_off + 1) - viewport.height();
 
         let scroll_delta = desired_bottom - viewport.y0;
 
         self.scroll_delta.set(Vec2::new(0.0, scroll_delta));
     }
 
+    /// The maximum vertical scroll offset of the editor.
+    fn max_scroll_y(&self) -> f64 {
+        (self.content_height() - self.viewport.get_untracked().height()).max(0.0)
+    }
+
+    /// The vertical scroll offset of `other` which matches the scroll offset of this editor.
+    fn synced_scroll_y(&self, other: &Editor, mode: SyncMode) -> f64 {
+        let y = self.viewport.get_untracked().y0;
+        let other_max = other.max_scroll_y();
+        let other_y = match mode {
+            SyncMode::Proportional => {
+                let max = self.max_scroll_y();
+                if max > 0.0 {
+                    y / max * other_max
+                } else {
+                    0.0
+                }
+            }
+            SyncMode::LineAligned => {
+                // The line at the top, with the fraction of it which is scrolled past
+                let line = self.rvline_at_y(y).line;
+                let top = self.y_of_line(line);
+                let height = self.y_of_line(line + 1) - top;
+                let fraction = if height > 0.0 {
+                    (y - top) / height
+                } else {
+                    0.0
+                };
+                let other_top = other.y_of_line(line);
+                other_top + fraction * (other.y_of_line(line + 1) - other_top)
+            }
+        };
+        other_y.clamp(0.0, other_max)
+    }
+
+    /// Keeps the vertical scroll position of this editor and `other` in sync, in both
+    /// directions, until the current scope is disposed. `other` is scrolled to match this editor
+    /// first.
+    pub fn link_scroll_with(&self, other: &Editor, mode: SyncMode) {
+        for (from, to, leads) in [
+            (self.clone(), other.clone(), true),
+            (other.clone(), self.clone(), false),
+        ] {
+            create_effect(move |first_run: Option<()>| {
+                let y = from.viewport.with(|viewport| viewport.y0);
+                if first_run.is_none() && !leads {
+                    return;
+                }
+                let to_viewport = to.viewport.get_untracked();
+                // Already in sync, either way around. This is the case when `from` was just
+                // scrolled to follow `to`, which keeps the editors from scrolling each other back
+                // and forth when one of them can't scroll as far as the other.
+                let target = from.synced_scroll_y(&to, mode);
+                if (target - to_viewport.y0).abs() < 1.0
+                    || (to.synced_scroll_y(&from, mode) - y).abs() < 1.0
+                {
+                    return;
+                }
+                to.scroll_to.set(Some(Vec2::new(to_viewport.x0, target)));
+            });
+        }
+    }
+
     pub fn scroll(&self, top_shift: f64, down: bool, count: usize, mods: Modifiers) {
         let viewport = self.viewport.get_untracked();
-        // TODO: don't assume line height is constant
-        let line_height = f64::from(self.line_height(0));
+        // The lines are scrolled by the height of the line at the top of the viewport
+        let line_height = f64::from(self.line_height(self.rvline_at_y(viewport.y0).line));
         let diff = line_height * count as f64;
         let diff = if down { diff } else { -diff };
 
@@ -654,15 +1174,21 @@ impl Editor {
         let top = viewport.y0 + diff + top_shift;
         let bottom = viewport.y0 + diff + viewport.height();
 
-        let new_line = if (line + 1) as f64 * line_height + line_height > bottom {
-            let line = (bottom / line_height).floor() as usize;
+        let new_line = if self.y_of_line(line + 2) > bottom {
+            let line = self.rvline_at_y(bottom).line;
             if line > 2 {
                 line - 2
             } else {
                 0
             }
-        } else if line as f64 * line_height - line_height < top {
-            let line = (top / line_height).ceil() as usize;
+        } else if line == 0 || self.y_of_line(line - 1) < top {
+            // The first line which starts in the viewport
+            let line = self.rvline_at_y(top).line;
+            let line = if self.y_of_line(line) < top {
+                line + 1
+            } else {
+                line
+            };
             line + 1
         } else {
             line
@@ -684,9 +1210,25 @@ impl Editor {
 
     // === Information ===
 
+    /// The phantom text of `line`, with the placeholder after the text when the range starting
+    /// at `line` is folded.
     pub fn phantom_text(&self, line: usize) -> PhantomTextLine {
-        self.doc()
-            .phantom_text(self.id(), &self.es.get_untracked(), line)
+        let mut phantom_text = self
+            .doc()
+            .phantom_text(self.id(), &self.es.get_untracked(), line);
+        if self.is_folded(line) {
+            phantom_text.text.push(PhantomText {
+                kind: PhantomTextKind::Placeholder,
+                text: FOLD_PLACEHOLDER.to_string(),
+                affinity: None,
+                col: self.line_end_col(line, true),
+                font_size: None,
+                fg: None,
+                bg: None,
+                under_line: None,
+            });
+        }
+        phantom_text
     }
 
     pub fn line_height(&self, line: usize) -> f32 {
@@ -761,6 +1303,67 @@ impl Editor {
         self.lines.last_vline(self.text_prov())
     }
 
+    /// The number of visual lines shown, without the lines hidden by folds.
+    pub fn shown_vline_count(&self) -> usize {
+        let hidden = self.hidden_lines();
+        self.last_vline().get() + 1 - folding::hidden_vline_count(self, &hidden)
+    }
+
+    /// Calls `f` with the heights of the lines, where the lines hidden by folds have no height.
+    ///
+    /// `f` must not create text layouts, see [`Lines::with_heights`].
+    pub fn with_line_heights<R>(&self, f: impl FnOnce(&LineHeights) -> R) -> R {
+        let cache_rev = self.doc().cache_rev().get_untracked();
+        self.lines.check_cache(cache_rev, self.config_id());
+        let hidden = self.hidden_lines();
+        let style = self.style();
+        let line_height = |line: usize| {
+            let i = hidden.partition_point(|lines| lines.end <= line);
+            if hidden.get(i).is_some_and(|lines| lines.contains(&line)) {
+                0.0
+            } else {
+                f64::from(style.line_height(self.id(), line))
+            }
+        };
+        self.lines.with_heights(self.text_prov(), line_height, f)
+    }
+
+    /// The y position of the top of the buffer `line`.
+    pub fn y_of_line(&self, line: usize) -> f64 {
+        self.with_line_heights(|heights| heights.y_of_line(line))
+    }
+
+    /// The y position of the top of the visual line `rvline`.
+    pub fn y_of_rvline(&self, rvline: RVLine) -> f64 {
+        self.with_line_heights(|heights| {
+            heights.y_of_line(rvline.line)
+                + rvline.line_index as f64 * heights.line_height(rvline.line)
+        })
+    }
+
+    /// The visual line at the height `y`, from the first to the last shown visual line.
+    ///
+    /// The lines which aren't laid out have an estimated height, and their first visual line is
+    /// at every height in it. Nothing is laid out.
+    pub fn rvline_at_y(&self, y: f64) -> RVLine {
+        let (line, line_index) = self.with_line_heights(|heights| heights.vline_at_y(y));
+        let cache_rev = self.doc().cache_rev().get_untracked();
+        if self
+            .lines
+            .try_get_text_layout(cache_rev, self.config_id(), line)
+            .is_some()
+        {
+            RVLine::new(line, line_index)
+        } else {
+            RVLine::new(line, 0)
+        }
+    }
+
+    /// The height of the shown lines.
+    pub fn content_height(&self) -> f64 {
+        self.with_line_heights(LineHeights::total_height)
+    }
+
     pub fn last_rvline(&self) -> RVLine {
         self.lines.last_rvline(self.text_prov())
     }
@@ -799,7 +1402,8 @@ impl Editor {
     }
 
     pub fn select_word(&self, offset: usize) -> (usize, usize) {
-        self.rope_text().select_word(offset)
+        let word_chars = self.style().word_chars(self.id());
+        self.rope_text().select_word_with(offset, &word_chars)
     }
 
     /// `affinity` decides whether an offset at a soft line break is considered to be on the
@@ -960,34 +1564,72 @@ impl Editor {
         (self.offset_of_line_col(line, col), is_inside)
     }
 
-    /// Get the actual (line, col) of a particular point within the editor.
-    pub fn line_col_of_point_with_phantom(&self, point: Point) -> (usize, usize) {
-        let line_height = f64::from(self.style().line_height(self.id(), 0));
+    /// The visual line at the height of `point`, with the text layout of its line.
+    fn line_hit(&self, point: Point) -> (VLineInfo<()>, Arc<TextLayoutLine>) {
+        let cache_rev = self.doc().cache_rev().get_untracked();
+        let config_id = self.config_id();
+        let screen_lines = self.screen_lines.with_untracked(|sl| sl.lines.clone());
+
+        // Dragging the pointer mostly hits the same line as the previous move
+        let last_hit = self.last_hit.with_untracked(|hit| {
+            hit.as_ref()
+                .filter(|hit| {
+                    hit.cache_rev == cache_rev
+                        && hit.config_id == config_id
+                        && Rc::ptr_eq(&hit.screen_lines, &screen_lines)
+                        && hit.y <= point.y
+                        && hit.y + f64::from(self.line_height(hit.info.rvline.line)) >= point.y
+                })
+                .map(|hit| (hit.info, hit.text_layout.clone()))
+        });
+        if let Some(hit) = last_hit {
+            return hit;
+        }
+
         let info = if point.y <= 0.0 {
-            Some(self.first_rvline_info())
+            Some((self.first_rvline_info(), f64::NAN))
         } else {
-            self.screen_lines
-                .with_untracked(|sl| {
-                    sl.iter_line_info().find(|info| {
+            self.screen_lines.with_untracked(|sl| {
+                sl.iter_line_info()
+                    .find(|info| {
+                        let line_height = f64::from(self.line_height(info.vline_info.rvline.line));
                         info.vline_y <= point.y && info.vline_y + line_height >= point.y
                     })
-                })
-                .map(|info| info.vline_info)
+                    .map(|info| (info.vline_info, info.vline_y))
+            })
         };
-        let info = info.unwrap_or_else(|| {
-            for (y_idx, info) in self.iter_rvlines(false, RVLine::default()).enumerate() {
-                let vline_y = y_idx as f64 * line_height;
-                if vline_y <= point.y && vline_y + line_height >= point.y {
-                    return info;
+        // The lines above the first line or below the last line aren't cached, as their
+        // height isn't known
+        let (info, y) = info.unwrap_or_else(|| {
+            if point.y < self.content_height() {
+                let rvline = self.rvline_at_y(point.y);
+                if let Some(info) = self.iter_rvlines(false, rvline).next() {
+                    return (info, self.y_of_rvline(rvline));
                 }
             }
 
-            self.last_rvline_info()
+            (self.last_rvline_info(), f64::NAN)
         });
+        let text_layout = self.text_layout(info.rvline.line);
+
+        if self.active.get_untracked() {
+            self.last_hit.set(Some(LineHit {
+                cache_rev,
+                config_id,
+                screen_lines,
+                y,
+                info,
+                text_layout: text_layout.clone(),
+            }));
+        }
+        (info, text_layout)
+    }
 
+    /// Get the actual (line, col) of a particular point within the editor.
+    pub fn line_col_of_point_with_phantom(&self, point: Point) -> (usize, usize) {
+        let (info, text_layout) = self.line_hit(point);
         let rvline = info.rvline;
         let line = rvline.line;
-        let text_layout = self.text_layout(line);
 
         let y = text_layout.get_layout_y(rvline.line_index).unwrap_or(0.0);
 
@@ -1000,33 +1642,9 @@ impl Editor {
     /// Points outside of vertical bounds will return the last line.
     /// Points outside of horizontal bounds will return the last column on the line.
     pub fn line_col_of_point(&self, mode: Mode, point: Point) -> ((usize, usize), bool) {
-        // TODO: this assumes that line height is constant!
-        let line_height = f64::from(self.style().line_height(self.id(), 0));
-        let info = if point.y <= 0.0 {
-            Some(self.first_rvline_info())
-        } else {
-            self.screen_lines
-                .with_untracked(|sl| {
-                    sl.iter_line_info().find(|info| {
-                        info.vline_y <= point.y && info.vline_y + line_height >= point.y
-                    })
-                })
-                .map(|info| info.vline_info)
-        };
-        let info = info.unwrap_or_else(|| {
-            for (y_idx, info) in self.iter_rvlines(false, RVLine::default()).enumerate() {
-                let vline_y = y_idx as f64 * line_height;
-                if vline_y <= point.y && vline_y + line_height >= point.y {
-                    return info;
-                }
-            }
-
-            self.last_rvline_info()
-        });
-
+        let (info, text_layout) = self.line_hit(point);
         let rvline = info.rvline;
         let line = rvline.line;
-        let text_layout = self.text_layout(line);
 
         let y = text_layout.get_layout_y(rvline.line_index).unwrap_or(0.0);
 
@@ -1078,15 +1696,21 @@ impl Editor {
             }
             ColPosition::End => self.line_end_col(line, caret),
             ColPosition::Start => 0,
-            ColPosition::FirstNonBlank => self.first_non_blank_character_on_line(line),
+            ColPosition::FirstNonBlank => {
+                self.first_non_blank_character_on_line(line) - self.offset_of_line(line)
+            }
         }
     }
 
     /// Advance to the right in the manner of the given mode.
     /// Get the column from a horizontal at a specific line index (in a text layout)
+    ///
+    /// The start, end and first non-blank positions are those of the visual line, so that
+    /// moving vertically after going to the end of a wrapped line stays at the end of each visual
+    /// line.
     pub fn rvline_horiz_col(
         &self,
-        RVLine { line, line_index }: RVLine,
+        rvline @ RVLine { line, line_index }: RVLine,
         horiz: &ColPosition,
         caret: bool,
     ) -> usize {
@@ -1106,8 +1730,18 @@ impl Editor {
 
                 col.min(self.line_end_col(line, caret))
             }
-            // Otherwise it is the same as the other function
-            _ => self.line_horiz_col(line, horiz, caret),
+            ColPosition::End => {
+                let info = self.rvline_info(rvline);
+                self.last_col(info, caret)
+                    .min(self.line_end_col(line, caret))
+            }
+            ColPosition::Start => self.first_col(self.rvline_info(rvline)),
+            ColPosition::FirstNonBlank => {
+                let info = self.rvline_info(rvline);
+                let non_blank = info.first_non_blank_character(&self.text_prov());
+                let col = non_blank - self.offset_of_line(line);
+                col.clamp(self.first_col(info), self.last_col(info, caret))
+            }
         }
     }
 
@@ -1233,7 +1867,6 @@ impl TextLayoutProvider for Editor {
         let edid = self.id();
         let text = self.rope_text();
         let style = self.style();
-        let doc = self.doc();
 
         let line_content_original = text.line_content(line);
 
@@ -1251,7 +1884,7 @@ impl TextLayoutProvider for Editor {
             line_content_original.to_string()
         };
         // Combine the phantom text with the line content
-        let phantom_text = doc.phantom_text(edid, &self.es.get_untracked(), line);
+        let phantom_text = self.phantom_text(line);
         let line_content = phantom_text.combine_with_text(&line_content);
 
         let family = style.font_family(edid, line);
@@ -1389,15 +2022,20 @@ impl LineFontSizeProvider for EditorFontSizes {
 /// Minimum width that we'll allow the view to be wrapped at.
 const MIN_WRAPPED_WIDTH: f32 = 100.0;
 
+/// The number of buffer lines before and after the screen lines which are laid out with them.
+///
+/// The text layouts are only created for the lines which are shown, and the other lines have an
+/// estimated height until they're laid out. Laying out a few more lines means the lines
+/// scrolled into view are usually laid out already, and that the wrapping of the lines just
+/// outside of the viewport is known before they're shown.
+const OVERSCAN_LINES: usize = 20;
+
 /// Create various reactive effects to update the screen lines whenever relevant parts of the view,
 /// doc, text layouts, viewport, etc. change.
-/// This tries to be smart to a degree.
+///
+/// Each effect observes one kind of [`ScreenLinesChange`] and passes it to
+/// [`Editor::update_screen_lines`], which decides how much to recompute.
 fn create_view_effects(cx: Scope, ed: &Editor) {
-    // Cloning is fun.
-    let ed2 = ed.clone();
-    let ed3 = ed.clone();
-    let ed4 = ed.clone();
-
     // Reset cursor blinking whenever the cursor changes
     {
         let cursor_info = ed.cursor_info.clone();
@@ -1408,92 +2046,93 @@ fn create_view_effects(cx: Scope, ed: &Editor) {
         });
     }
 
-    let update_screen_lines = |ed: &Editor| {
-        // This function should not depend on the viewport signal directly.
-
-        // This is wrapped in an update to make any updates-while-updating very obvious
-        // which they wouldn't be if we computed and then `set`.
-        ed.screen_lines.update(|screen_lines| {
-            let new_screen_lines = ed.compute_screen_lines(screen_lines.base);
-
-            *screen_lines = new_screen_lines;
+    // Update the hidden lines and the screen lines when lines are folded or unfolded
+    {
+        let ed = ed.clone();
+        cx.create_effect(move |prev: Option<()>| {
+            ed.folded_lines.track();
+            if prev.is_some() {
+                ed.update_hidden_lines();
+                ed.update_screen_lines(ScreenLinesChange::Style);
+            }
         });
-    };
+    }
 
-    // Listen for layout events, currently only when a layout is created, and update screen
-    // lines based on that
-    ed3.lines.layout_event.listen_with(cx, move |val| {
-        let ed = &ed2;
-        // TODO: Move this logic onto screen lines somehow, perhaps just an auxiliary
-        // function, to avoid getting confused about what is relevant where.
+    // Handle the pointer move scheduled by `Editor::pointer_move`
+    {
+        let ed = ed.clone();
+        cx.create_effect(move |prev: Option<()>| {
+            ed.pointer_move_trigger.track();
+            if prev.is_some() {
+                ed.flush_pointer_move();
+            }
+        });
+    }
 
-        match val {
+    // Listen for layout events, currently only when a layout is created
+    {
+        let ed = ed.clone();
+        ed.lines.layout_event.listen_with(cx, move |val| match val {
             LayoutEvent::CreatedLayout { line, .. } => {
-                let sl = ed.screen_lines.get_untracked();
-
-                // Intelligently update screen lines, avoiding recalculation if possible
-                let should_update = sl.on_created_layout(ed, line);
-
-                if should_update {
-                    untrack(|| {
-                        update_screen_lines(ed);
-                    });
-
-                    // Ensure that it is created even after the base/viewport signals have been
-                    // updated.
-                    // But we have to trigger an event since it could alter the screenlines
-                    // TODO: this has some risk for infinite looping if we're unlucky.
-                    ed2.text_layout_trigger(line, true);
-                }
+                ed.update_screen_lines(ScreenLinesChange::CreatedLayout(line));
             }
-        }
-    });
+        });
+    }
 
     // TODO: should we have some debouncing for editor width? Ideally we'll be fast enough to not
     // even need it, though we might not want to use a bunch of cpu whilst resizing anyway.
 
-    let viewport_changed_trigger = cx.create_trigger();
-
     // Watch for changes to the viewport so that we can alter the wrapping
-    // As well as updating the screen lines base
-    cx.create_effect(move |_| {
-        let ed = &ed3;
-
-        let viewport = ed.viewport.get();
-
-        let wrap = match ed.es.with(|s| s.wrap_method()) {
-            WrapMethod::None => ResolvedWrap::None,
-            WrapMethod::EditorWidth => {
-                ResolvedWrap::Width((viewport.width() as f32).max(MIN_WRAPPED_WIDTH))
-            }
-            WrapMethod::WrapColumn { .. } => todo!(),
-            WrapMethod::WrapWidth { width } => ResolvedWrap::Width(width),
-        };
-
-        ed.lines.set_wrap(wrap);
+    // As well as updating the screen lines base.
+    {
+        let ed = ed.clone();
+        cx.create_effect(move |_| {
+            let viewport = ed.viewport.get();
 
-        // Update the base
-        let base = ed.screen_lines.with_untracked(|sl| sl.base);
+            let wrap = match ed.es.with(|s| s.wrap_method()) {
+                WrapMethod::None => ResolvedWrap::None,
+                WrapMethod::EditorWidth => {
+                    ResolvedWrap::Width((viewport.width() as f32).max(MIN_WRAPPED_WIDTH))
+                }
+                WrapMethod::WrapColumn { .. } => todo!(),
+                WrapMethod::WrapWidth { width } => ResolvedWrap::Width(width),
+            };
+
+            let wrap_changed = ed.lines.wrap() != wrap;
+            ed.lines.set_wrap(wrap);
+
+            untrack(|| {
+                if wrap_changed {
+                    // Only the base is updated for the viewport, as the lines are recomputed
+                    // for the wrapping anyway
+                    let base = ed.screen_lines.with_untracked(|sl| sl.base);
+                    base.update(|base| base.active_viewport = viewport);
+                    ed.update_screen_lines(ScreenLinesChange::Style);
+                } else {
+                    ed.update_screen_lines(ScreenLinesChange::Viewport(viewport));
+                }
+            });
+        });
+    }
 
-        // TODO: should this be a with or with_untracked?
-        if viewport != base.with_untracked(|base| base.active_viewport) {
-            batch(|| {
-                base.update(|base| {
-                    base.active_viewport = viewport;
-                });
-                // TODO: Can I get rid of this and just call update screen lines with an
-                // untrack around it?
-                viewport_changed_trigger.notify();
+    // Compute the screen lines, tracking everything the document reads to compute them as
+    // well as edits, the document being replaced and the styling. This computes the initial
+    // screen lines, once the viewport effect above set the base.
+    {
+        let ed = ed.clone();
+        cx.create_effect(move |_| {
+            ed.screen_lines_trigger.track();
+            ed.doc.with(|doc| doc.cache_rev().track());
+            ed.style.track();
+            ed.floem_style_id.track();
+
+            // This is wrapped in an update to make any updates-while-updating very obvious
+            // which they wouldn't be if we computed and then `set`.
+            ed.screen_lines.update(|screen_lines| {
+                *screen_lines = ed.compute_screen_lines(screen_lines.base);
             });
-        }
-    });
-    // Watch for when the viewport as changed in a relevant manner
-    // and for anything that `update_screen_lines` tracks.
-    cx.create_effect(move |_| {
-        viewport_changed_trigger.track();
-
-        update_screen_lines(&ed4);
-    });
+        });
+    }
 }
 
 pub fn normal_compute_screen_lines(
@@ -1502,51 +2141,61 @@ pub fn normal_compute_screen_lines(
 ) -> ScreenLines {
     let lines = &editor.lines;
     let style = editor.style.get();
-    // TODO: don't assume universal line height!
-    let line_height = style.line_height(editor.id(), 0);
 
     let (y0, y1) = base.with_untracked(|base| (base.active_viewport.y0, base.active_viewport.y1));
-    // Get the start and end (visual) lines that are visible in the viewport
-    let min_vline = VLine((y0 / line_height as f64).floor() as usize);
-    let max_vline = VLine((y1 / line_height as f64).ceil() as usize);
 
     let cache_rev = editor.doc.get().cache_rev().get();
     editor.lines.check_cache_rev(cache_rev);
 
-    let min_info = editor.iter_vlines(false, min_vline).next();
-
     let mut rvlines = Vec::new();
     let mut info = HashMap::new();
 
-    let Some(min_info) = min_info else {
-        return ScreenLines {
-            lines: Rc::new(rvlines),
-            info: Rc::new(info),
-            diff_sections: None,
-            base,
-        };
-    };
-
-    // TODO: the original was min_line..max_line + 1, are we iterating too little now?
-    // the iterator is from min_vline..max_vline
-    let count = max_vline.get() - min_vline.get();
-    let iter = lines
-        .iter_rvlines_init(
+    // The visual line at the top of the viewport. Laying out the line at the top replaces its
+    // estimated height, which can move another line to the top, until it's laid out too.
+    let mut start = editor.rvline_at_y(y0);
+    while start.line <= editor.last_line()
+        && lines
+            .try_get_text_layout(cache_rev, editor.config_id(), start.line)
+            .is_none()
+    {
+        lines.get_init_text_layout(
+            cache_rev,
+            editor.config_id(),
+            editor.text_prov(),
+            start.line,
+            false,
+        );
+        start = editor.rvline_at_y(y0);
+    }
+    let mut vline_y = editor.y_of_rvline(start);
+    let hidden = editor.hidden_lines();
+    let iter: Box<dyn Iterator<Item = VLineInfo<()>> + '_> = if hidden.is_empty() {
+        Box::new(lines.iter_rvlines_init(
             editor.text_prov(),
             cache_rev,
             editor.config_id(),
-            min_info.rvline,
+            start,
             false,
-        )
-        .take(count);
-
-    for (i, vline_info) in iter.enumerate() {
-        rvlines.push(vline_info.rvline);
+        ))
+    } else {
+        // The lines hidden by folds are skipped, so the visual lines after them move up
+        Box::new(folding::iter_shown_rvlines(
+            editor, &hidden, start, cache_rev,
+        ))
+    };
 
+    for vline_info in iter {
+        if vline_y >= y1 {
+            break;
+        }
         let line_height = f64::from(style.line_height(editor.id(), vline_info.rvline.line));
+        // The viewport can be past the last line
+        if vline_y + line_height <= y0 {
+            vline_y += line_height;
+            continue;
+        }
+        rvlines.push(vline_info.rvline);
 
-        let y_idx = min_vline.get() + i;
-        let vline_y = y_idx as f64 * line_height;
         let line_y = vline_y - vline_info.rvline.line_index as f64 * line_height;
 
         // Add the information to make it cheap to get in the future.
@@ -1559,6 +2208,20 @@ pub fn normal_compute_screen_lines(
                 vline_info,
             },
         );
+        vline_y += line_height;
+    }
+
+    if let (Some(first), Some(last)) = (rvlines.first(), rvlines.last()) {
+        let before = first.line.saturating_sub(OVERSCAN_LINES)..first.line;
+        let after = last.line + 1..(last.line + 1 + OVERSCAN_LINES).min(editor.last_line() + 1);
+        // The layouts aren't shown yet, so their creation doesn't need to trigger an update
+        lines.init_line_interval(
+            cache_rev,
+            editor.config_id(),
+            &editor.text_prov(),
+            before.chain(after),
+            false,
+        );
     }
 
     ScreenLines {
@@ -1574,6 +2237,9 @@ pub fn normal_compute_screen_lines(
 #[derive(Clone)]
 pub struct CursorInfo {
     pub hidden: RwSignal<bool>,
+    /// Whether typed text replaces the characters after the carets, for documents which
+    /// implement an overwrite mode. The carets are then painted with [`OverwriteCaretShape`].
+    pub overwrite: RwSignal<bool>,
 
     pub blink_timer: RwSignal<TimerToken>,
     // TODO: should these just be rwsignals?
@@ -1585,6 +2251,7 @@ impl CursorInfo {
     pub fn new(cx: Scope) -> CursorInfo {
         CursorInfo {
             hidden: cx.create_rw_signal(false),
+            overwrite: cx.create_rw_signal(false),
 
             blink_timer: cx.create_rw_signal(TimerToken::INVALID),
             should_blink: Rc::new(|| true),
@@ -1610,6 +2277,15 @@ impl CursorInfo {
         }
     }
 
+    /// The shape of the carets of `cursor`, for its mode.
+    pub fn caret_shape(&self, cursor: &Cursor, es: &EditorStyle) -> CaretShape {
+        match cursor.mode {
+            CursorMode::Normal(_) | CursorMode::Visual { .. } => es.normal_caret_shape(),
+            CursorMode::Insert(_) if self.overwrite.get_untracked() => es.overwrite_caret_shape(),
+            CursorMode::Insert(_) => es.caret_shape(),
+        }
+    }
+
     pub fn reset(&self) {
         if self.hidden.get_untracked() {
             self.hidden.set(false);
@@ -1620,3 +2296,356 @@ impl CursorInfo {
         self.blink();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeSet, rc::Rc, sync::Arc};
+
+    use floem_editor_core::{
+        cursor::{Cursor, CursorAffinity, CursorMode},
+        editor::EditType,
+        selection::Selection,
+    };
+    use floem_reactive::{Scope, SignalGet, SignalUpdate, SignalWith};
+    use peniko::kurbo::{Point, Rect, Vec2};
+
+    use super::{
+        folding::FoldingRange,
+        id::EditorId,
+        text::{CaretShape, SimpleStyling, Styling},
+        text_document::TextDocument,
+        visual_line::RVLine,
+        Editor, EditorStyle, SyncMode, CHAR_WIDTH, OVERSCAN_LINES,
+    };
+
+    fn make_ed(cx: Scope, lines: usize) -> Editor {
+        let doc = Rc::new(TextDocument::new(cx, "\n".repeat(lines - 1)));
+        let editor = Editor::new(cx, doc, Rc::new(SimpleStyling::new()), false);
+        editor.viewport.set(Rect::new(0.0, 0.0, 100.0, 100.0));
+        editor
+    }
+
+    #[test]
+    fn linked_scroll() {
+        let cx = Scope::new();
+        let long = make_ed(cx, 100);
+        let short = make_ed(cx, 50);
+        let line_height = f64::from(long.line_height(0));
+        long.link_scroll_with(&short, SyncMode::LineAligned);
+        assert_eq!(short.scroll_to.get_untracked(), None);
+
+        long.viewport.set(Rect::new(
+            0.0,
+            10.0 * line_height,
+            100.0,
+            10.0 * line_height + 100.0,
+        ));
+        assert_eq!(
+            short.scroll_to.get_untracked(),
+            Some(Vec2::new(0.0, 10.0 * line_height))
+        );
+        // Following the other editor doesn't scroll it back
+        short.viewport.set(Rect::new(
+            0.0,
+            10.0 * line_height,
+            100.0,
+            10.0 * line_height + 100.0,
+        ));
+        assert_eq!(long.scroll_to.get_untracked(), None);
+
+        // The short editor can't scroll as far as the long one
+        let max = 50.0 * line_height - 100.0;
+        long.viewport.set(Rect::new(
+            0.0,
+            80.0 * line_height,
+            100.0,
+            80.0 * line_height + 100.0,
+        ));
+        assert_eq!(short.scroll_to.get_untracked(), Some(Vec2::new(0.0, max)));
+        short.viewport.set(Rect::new(0.0, max, 100.0, max + 100.0));
+        assert_eq!(long.scroll_to.get_untracked(), None);
+        cx.dispose();
+    }
+
+    #[test]
+    fn screen_lines_changes() {
+        let cx = Scope::new();
+        let ed = make_ed(cx, 100);
+        let line_height = f64::from(ed.line_height(0));
+        let screen_lines = || ed.screen_lines.with_untracked(|sl| sl.lines.clone());
+        let first_offset = || {
+            ed.screen_lines
+                .with_untracked(|sl| sl.iter_vline_info().next().unwrap().interval.start)
+        };
+
+        let lines = screen_lines();
+        assert_eq!(lines.len(), (100.0 / line_height).ceil() as usize);
+        assert_eq!(lines[0], RVLine::new(0, 0));
+
+        // Scrolling down recomputes the lines
+        let y0 = 10.0 * line_height;
+        ed.viewport.set(Rect::new(0.0, y0, 100.0, y0 + 100.0));
+        let lines = screen_lines();
+        assert_eq!(lines[0], RVLine::new(10, 0));
+        assert_eq!(first_offset(), 10);
+
+        // Scrolling horizontally only moves the base
+        ed.viewport.set(Rect::new(20.0, y0, 120.0, y0 + 100.0));
+        assert!(Rc::ptr_eq(&lines, &screen_lines()));
+        let base = ed.screen_lines.with_untracked(|sl| sl.base);
+        assert_eq!(base.with_untracked(|base| base.active_viewport.x0), 20.0);
+
+        // Edits and style changes recompute the lines
+        ed.doc()
+            .edit_single(Selection::caret(0), "ab\n", EditType::InsertChars);
+        assert!(!Rc::ptr_eq(&lines, &screen_lines()));
+        assert_eq!(first_offset(), 12);
+        let lines = screen_lines();
+        ed.floem_style_id.set(1);
+        assert!(!Rc::ptr_eq(&lines, &screen_lines()));
+        assert_eq!(screen_lines(), lines);
+        cx.dispose();
+    }
+
+    #[test]
+    fn folds() {
+        let cx = Scope::new();
+        let doc = Rc::new(TextDocument::new(cx, "fn a() {\n    b();\n    c();\n}\nd"));
+        doc.folding_ranges.set(vec![FoldingRange::new(0, 2)]);
+        let ed = Editor::new(cx, doc, Rc::new(SimpleStyling::new()), false);
+        ed.viewport.set(Rect::new(0.0, 0.0, 500.0, 500.0));
+        ed.cursor.set(Cursor::new(
+            CursorMode::Insert(Selection::caret(12)),
+            None,
+            None,
+        ));
+        let lines = || {
+            ed.screen_lines
+                .with_untracked(|sl| sl.iter_lines_y().map(|(line, _)| line).collect::<Vec<_>>())
+        };
+        assert_eq!(lines(), [0, 1, 2, 3, 4]);
+        let line_height = ed.y_of_line(1);
+        assert_eq!(ed.y_of_line(3), 3.0 * line_height);
+
+        ed.toggle_fold(0);
+        assert_eq!(lines(), [0, 3, 4]);
+        assert_eq!(ed.shown_vline_count(), 3);
+        // The hidden lines have no height
+        assert_eq!(ed.y_of_line(3), line_height);
+        assert_eq!(ed.phantom_text(0).text.len(), 1);
+        // The cursor moves out of the hidden lines
+        assert_eq!(ed.cursor.with_untracked(|cursor| cursor.offset()), 8);
+
+        ed.toggle_fold(0);
+        assert_eq!(lines(), [0, 1, 2, 3, 4]);
+        assert_eq!(ed.y_of_line(3), 3.0 * line_height);
+        assert!(ed.phantom_text(0).text.is_empty());
+
+        // Setting the folded lines directly folds the range too
+        ed.folded_lines.set(BTreeSet::from([0]));
+        assert_eq!(lines(), [0, 3, 4]);
+        assert_eq!(ed.y_of_line(3), line_height);
+        ed.folded_lines.set(BTreeSet::new());
+        // Only the first line of a range toggles it
+        ed.toggle_fold(1);
+        assert!(ed.folded_lines.with_untracked(BTreeSet::is_empty));
+
+        // The heights are moved by the edits, and the lines which the folded ranges don't
+        // follow are measured again
+        ed.toggle_fold(0);
+        ed.doc()
+            .edit_single(Selection::caret(0), "\n", EditType::InsertChars);
+        assert_eq!(lines(), [0, 3, 4, 5]);
+        assert_eq!(ed.y_of_line(3), line_height);
+        assert_eq!(ed.y_of_line(4), 2.0 * line_height);
+        cx.dispose();
+    }
+
+    #[test]
+    fn overscan() {
+        let cx = Scope::new();
+        let ed = make_ed(cx, 200);
+        let last_shown = ed
+            .screen_lines
+            .with_untracked(|sl| sl.lines.last().map(|rvline| rvline.line))
+            .unwrap();
+        let cache_rev = ed.doc().cache_rev().get_untracked();
+        let is_laid_out = |line| {
+            ed.lines
+                .try_get_text_layout(cache_rev, ed.config_id(), line)
+                .is_some()
+        };
+        assert!(is_laid_out(last_shown + OVERSCAN_LINES));
+        assert!(!is_laid_out(last_shown + OVERSCAN_LINES + 1));
+        assert!(!is_laid_out(150));
+        cx.dispose();
+    }
+
+    #[test]
+    fn lazy_layouts() {
+        let cx = Scope::new();
+        let mut text = "\n".repeat(999);
+        // A long line far below the viewport, which wraps at the width of the viewport
+        text.insert_str(900, &"x".repeat(500));
+        let doc = Rc::new(TextDocument::new(cx, text));
+        let ed = Editor::new(cx, doc, Rc::new(SimpleStyling::new()), false);
+        ed.viewport.set(Rect::new(0.0, 0.0, 100.0, 100.0));
+        let line_height = f64::from(ed.line_height(0));
+        let cache_rev = ed.doc().cache_rev().get_untracked();
+        let is_laid_out = |line| {
+            ed.lines
+                .try_get_text_layout(cache_rev, ed.config_id(), line)
+                .is_some()
+        };
+
+        // The height of the long line is estimated without laying it out
+        assert!(ed.content_height() > 1000.0 * line_height);
+        assert_eq!(ed.rvline_at_y(500.0 * line_height), RVLine::new(500, 0));
+        assert!(!is_laid_out(500));
+        assert!(!is_laid_out(900));
+
+        // The lines are laid out when they're scrolled into view
+        let y0 = ed.y_of_line(500);
+        ed.viewport.set(Rect::new(0.0, y0, 100.0, y0 + 100.0));
+        assert_eq!(
+            ed.screen_lines
+                .with_untracked(|sl| sl.lines.first().copied()),
+            Some(RVLine::new(500, 0))
+        );
+        assert!(is_laid_out(500));
+        assert!(!is_laid_out(100));
+        assert!(!is_laid_out(900));
+        cx.dispose();
+    }
+
+    #[test]
+    fn edits_keep_layouts() {
+        let cx = Scope::new();
+        let doc = Rc::new(TextDocument::new(cx, "abc\ndef\nghi"));
+        let ed = Editor::new(cx, doc.clone(), Rc::new(SimpleStyling::new()), false);
+        let other = Editor::new(cx, doc, Rc::new(SimpleStyling::new()), false);
+        ed.viewport.set(Rect::new(0.0, 0.0, 500.0, 500.0));
+        other.viewport.set(Rect::new(0.0, 0.0, 500.0, 500.0));
+        let layout = |ed: &Editor, line| {
+            let cache_rev = ed.doc().cache_rev().get_untracked();
+            ed.lines
+                .try_get_text_layout(cache_rev, ed.config_id(), line)
+                .unwrap()
+        };
+        let layouts = |ed: &Editor| [0, 1, 2].map(|line| layout(ed, line));
+        let (before, other_before) = (layouts(&ed), layouts(&other));
+
+        // Typing on the first line only lays it out again, in every editor of the document
+        ed.receive_char("x");
+        let (after, other_after) = (layouts(&ed), layouts(&other));
+        assert!(!Arc::ptr_eq(&before[0], &after[0]));
+        assert!(Arc::ptr_eq(&before[1], &after[1]));
+        assert!(Arc::ptr_eq(&before[2], &after[2]));
+        assert!(!Arc::ptr_eq(&other_before[0], &other_after[0]));
+        assert!(Arc::ptr_eq(&other_before[2], &other_after[2]));
+
+        // So do the edits made outside of the editors
+        ed.doc()
+            .edit_single(Selection::caret(6), "y", EditType::InsertChars);
+        let edited = layouts(&ed);
+        assert!(Arc::ptr_eq(&after[0], &edited[0]));
+        assert!(!Arc::ptr_eq(&after[1], &edited[1]));
+        assert!(Arc::ptr_eq(&other_after[0], &layout(&other, 0)));
+        cx.dispose();
+    }
+
+    /// Even lines are twice as tall as odd lines, like headings between paragraphs.
+    struct Headings;
+
+    impl Styling for Headings {
+        fn id(&self) -> u64 {
+            0
+        }
+
+        fn line_height(&self, _edid: EditorId, line: usize) -> f32 {
+            if line % 2 == 0 {
+                40.0
+            } else {
+                20.0
+            }
+        }
+    }
+
+    #[test]
+    fn variable_line_heights() {
+        let cx = Scope::new();
+        let doc = Rc::new(TextDocument::new(cx, "\n".repeat(9)));
+        let ed = Editor::new(cx, doc, Rc::new(Headings), false);
+        ed.viewport.set(Rect::new(0.0, 0.0, 100.0, 100.0));
+        assert_eq!(ed.content_height(), 300.0);
+        assert_eq!(ed.y_of_line(3), 100.0);
+        assert_eq!(ed.rvline_at_y(65.0), RVLine::new(2, 0));
+        assert_eq!(ed.rvline_at_y(1000.0), RVLine::new(9, 0));
+
+        let lines_y = || {
+            ed.screen_lines
+                .with_untracked(|sl| sl.iter_lines_y().collect::<Vec<_>>())
+        };
+        assert_eq!(lines_y(), [(0, 0.0), (1, 40.0), (2, 60.0)]);
+
+        ed.viewport.set(Rect::new(0.0, 110.0, 100.0, 210.0));
+        assert_eq!(lines_y(), [(3, 100.0), (4, 120.0), (5, 160.0), (6, 180.0)]);
+        cx.dispose();
+    }
+
+    #[test]
+    fn screen_lines_geometry() {
+        let cx = Scope::new();
+        let doc = Rc::new(TextDocument::new(cx, "abc\n\ndef"));
+        let ed = Editor::new(cx, doc, Rc::new(SimpleStyling::new()), false);
+        ed.viewport.set(Rect::new(0.0, 0.0, 500.0, 100.0));
+        let line_height = f64::from(ed.line_height(0));
+        let x = |line, col| {
+            ed.line_point_of_line_col(line, col, CursorAffinity::Forward, true)
+                .x
+        };
+
+        let rects = ed
+            .screen_lines
+            .with_untracked(|sl| sl.rects_for_range(&ed, 1..7));
+        assert_eq!(
+            rects,
+            vec![
+                Rect::new(x(0, 1), 0.0, x(0, 3) + CHAR_WIDTH, line_height),
+                Rect::new(0.0, line_height, CHAR_WIDTH, 2.0 * line_height),
+                Rect::new(0.0, 2.0 * line_height, x(2, 2), 3.0 * line_height),
+            ]
+        );
+
+        let offset_at = |x, y| {
+            ed.screen_lines
+                .with_untracked(|sl| sl.offset_at_point(&ed, Point::new(x, y)))
+        };
+        assert_eq!(offset_at(x(2, 2) + 0.1, 2.5 * line_height), Some(7));
+        assert_eq!(offset_at(0.0, 0.5 * line_height), Some(0));
+        assert_eq!(offset_at(0.0, 10.0 * line_height), None);
+        cx.dispose();
+    }
+    #[test]
+    fn caret_shapes() {
+        let cx = Scope::new();
+        let ed = make_ed(cx, 1);
+        let es = EditorStyle::default();
+        let shape = |mode| {
+            ed.cursor_info
+                .caret_shape(&Cursor::new(mode, None, None), &es)
+        };
+
+        assert_eq!(
+            shape(CursorMode::Insert(Selection::caret(0))),
+            CaretShape::Bar
+        );
+        assert_eq!(shape(CursorMode::Normal(0)), CaretShape::Block);
+        ed.cursor_info.overwrite.set(true);
+        assert_eq!(
+            shape(CursorMode::Insert(Selection::caret(0))),
+            CaretShape::Underline
+        );
+        cx.dispose();
+    }
+}
@@ -1,5 +1,7 @@
 //! Movement logic for the editor.
 
+use std::ops::Range;
+
 use floem_editor_core::{
     buffer::rope_text::{RopeText, RopeTextVal},
     command::MultiSelectionCommand,
@@ -9,7 +11,9 @@ use floem_editor_core::{
     register::Register,
     selection::{SelRegion, Selection},
     soft_tab::{snap_to_soft_tab, SnapDirection},
+    word::{get_char_property, CharClassification},
 };
+use lapce_xi_rope::Rope;
 
 use super::{
     actions::CommonAction,
@@ -97,6 +101,8 @@ pub fn move_offset(
     movement: &Movement,
     mode: Mode,
 ) -> (usize, Option<ColPosition>) {
+    // Whether vertical movement and home/end ignore the wrapping of lines
+    let logical = view.es.with_untracked(|es| es.logical_line_movement());
     let (new_offset, horiz) = match movement {
         Movement::Left => {
             let new_offset = move_left(view, offset, affinity, mode, count);
@@ -109,13 +115,20 @@ pub fn move_offset(
             (new_offset, None)
         }
         Movement::Up => {
-            let (new_offset, horiz) = move_up(view, offset, affinity, horiz.cloned(), mode, count);
+            let (new_offset, horiz) = if logical {
+                move_up_line(view, offset, affinity, horiz.cloned(), mode, count)
+            } else {
+                move_up(view, offset, affinity, horiz.cloned(), mode, count)
+            };
 
             (new_offset, Some(horiz))
         }
         Movement::Down => {
-            let (new_offset, horiz) =
-                move_down(view, offset, affinity, horiz.cloned(), mode, count);
+            let (new_offset, horiz) = if logical {
+                move_down_line(view, offset, affinity, horiz.cloned(), mode, count)
+            } else {
+                move_down(view, offset, affinity, horiz.cloned(), mode, count)
+            };
 
             (new_offset, Some(horiz))
         }
@@ -130,17 +143,17 @@ pub fn move_offset(
             (new_offset, Some(horiz))
         }
         Movement::FirstNonBlank => {
-            let (new_offset, horiz) = first_non_blank(view, affinity, offset);
+            let (new_offset, horiz) = first_non_blank(view, affinity, offset, logical);
 
             (new_offset, Some(horiz))
         }
         Movement::StartOfLine => {
-            let (new_offset, horiz) = start_of_line(view, affinity, offset);
+            let (new_offset, horiz) = start_of_line(view, affinity, offset, logical);
 
             (new_offset, Some(horiz))
         }
         Movement::EndOfLine => {
-            let (new_offset, horiz) = end_of_line(view, affinity, offset, mode);
+            let (new_offset, horiz) = end_of_line(view, affinity, offset, mode, logical);
 
             (new_offset, Some(horiz))
         }
@@ -154,17 +167,27 @@ pub fn move_offset(
             (new_offset, None)
         }
         Movement::WordEndForward => {
-            let new_offset =
-                view.rope_text()
-                    .move_n_wordends_forward(offset, count, mode == Mode::Insert);
+            let word_chars = view.style().word_chars(view.id());
+            let new_offset = view.rope_text().move_n_wordends_forward_with(
+                offset,
+                count,
+                mode == Mode::Insert,
+                &word_chars,
+            );
             (new_offset, None)
         }
         Movement::WordForward => {
-            let new_offset = view.rope_text().move_n_words_forward(offset, count);
+            let word_chars = view.style().word_chars(view.id());
+            let new_offset = view
+                .rope_text()
+                .move_n_words_forward_with(offset, count, &word_chars);
             (new_offset, None)
         }
         Movement::WordBackward => {
-            let new_offset = view.rope_text().move_n_words_backward(offset, count, mode);
+            let word_chars = view.style().word_chars(view.id());
+            let new_offset =
+                view.rope_text()
+                    .move_n_words_backward_with(offset, count, mode, &word_chars);
             (new_offset, None)
         }
         Movement::NextUnmatched(char) => {
@@ -363,6 +386,61 @@ fn move_up(
     (new_offset, horiz)
 }
 
+/// Move the offset up by `count` logical lines, ignoring the wrapping of lines.
+fn move_up_line(
+    view: &Editor,
+    offset: usize,
+    affinity: &mut CursorAffinity,
+    horiz: Option<ColPosition>,
+    mode: Mode,
+    count: usize,
+) -> (usize, ColPosition) {
+    let horiz =
+        horiz.unwrap_or_else(|| ColPosition::Col(view.line_point_of_offset(offset, *affinity).x));
+    let line = view.line_of_offset(offset);
+    if line == 0 && count > 0 {
+        *affinity = CursorAffinity::Backward;
+
+        return (0, horiz);
+    }
+
+    let line = line.saturating_sub(count);
+    let col = view.line_horiz_col(line, &horiz, mode != Mode::Normal);
+    *affinity = if col == 0 {
+        CursorAffinity::Forward
+    } else {
+        CursorAffinity::Backward
+    };
+
+    (view.offset_of_line_col(line, col), horiz)
+}
+
+/// Move the offset down by `count` logical lines, ignoring the wrapping of lines.
+fn move_down_line(
+    view: &Editor,
+    offset: usize,
+    affinity: &mut CursorAffinity,
+    horiz: Option<ColPosition>,
+    mode: Mode,
+    count: usize,
+) -> (usize, ColPosition) {
+    let line = view.line_of_offset(offset) + count;
+    if line > view.last_line() {
+        return move_down_last_rvline(view, offset, affinity, horiz, mode);
+    }
+
+    let horiz =
+        horiz.unwrap_or_else(|| ColPosition::Col(view.line_point_of_offset(offset, *affinity).x));
+    let col = view.line_horiz_col(line, &horiz, mode != Mode::Normal);
+    *affinity = if col == 0 {
+        CursorAffinity::Forward
+    } else {
+        CursorAffinity::Backward
+    };
+
+    (view.offset_of_line_col(line, col), horiz)
+}
+
 /// Move down for when the cursor is on the last visual line.
 fn move_down_last_rvline(
     view: &Editor,
@@ -477,14 +555,27 @@ fn document_end(
     (last_offset, ColPosition::End)
 }
 
+/// Move to the first non-blank character of the visual line, or of the logical line if
+/// `logical`.
 fn first_non_blank(
     view: &Editor,
     affinity: &mut CursorAffinity,
     offset: usize,
+    logical: bool,
 ) -> (usize, ColPosition) {
-    let info = view.rvline_info_of_offset(offset, *affinity);
-    let non_blank_offset = info.first_non_blank_character(&view.text_prov());
-    let start_line_offset = info.interval.start;
+    let (non_blank_offset, start_line_offset) = if logical {
+        let line = view.line_of_offset(offset);
+        (
+            view.first_non_blank_character_on_line(line),
+            view.offset_of_line(line),
+        )
+    } else {
+        let info = view.rvline_info_of_offset(offset, *affinity);
+        (
+            info.first_non_blank_character(&view.text_prov()),
+            info.interval.start,
+        )
+    };
     // TODO: is this always the correct affinity? It might be desirable for the very first character on a wrapped line?
     *affinity = CursorAffinity::Forward;
 
@@ -502,13 +593,18 @@ fn first_non_blank(
     }
 }
 
+/// Move to the start of the visual line, or of the logical line if `logical`.
 fn start_of_line(
     view: &Editor,
     affinity: &mut CursorAffinity,
     offset: usize,
+    logical: bool,
 ) -> (usize, ColPosition) {
-    let rvline = view.rvline_of_offset(offset, *affinity);
-    let new_offset = view.offset_of_rvline(rvline);
+    let new_offset = if logical {
+        view.offset_of_line(view.line_of_offset(offset))
+    } else {
+        view.offset_of_rvline(view.rvline_of_offset(offset, *affinity))
+    };
     // TODO(minor): if the line has zero characters, it should probably be forward affinity but
     // other cases might be better as backwards?
     *affinity = CursorAffinity::Forward;
@@ -516,21 +612,31 @@ fn start_of_line(
     (new_offset, ColPosition::Start)
 }
 
+/// Move to the end of the visual line, or of the logical line if `logical`.
 fn end_of_line(
     view: &Editor,
     affinity: &mut CursorAffinity,
     offset: usize,
     mode: Mode,
+    logical: bool,
 ) -> (usize, ColPosition) {
-    let info = view.rvline_info_of_offset(offset, *affinity);
-    let new_col = info.last_col(&view.text_prov(), mode != Mode::Normal);
+    let (line, new_col) = if logical {
+        let line = view.line_of_offset(offset);
+        (line, view.line_end_col(line, mode != Mode::Normal))
+    } else {
+        let info = view.rvline_info_of_offset(offset, *affinity);
+        (
+            info.rvline.line,
+            info.last_col(&view.text_prov(), mode != Mode::Normal),
+        )
+    };
     *affinity = if new_col == 0 {
         CursorAffinity::Forward
     } else {
         CursorAffinity::Backward
     };
 
-    let new_offset = view.offset_of_line_col(info.rvline.line, new_col);
+    let new_offset = view.offset_of_line_col(line, new_col);
 
     (new_offset, ColPosition::End)
 }
@@ -737,11 +843,73 @@ pub fn do_multi_selection(view: &Editor, cursor: &mut Cursor, cmd: &MultiSelecti
                 cursor.set_insert(new_selection);
             }
         }
+        SelectParagraph => {
+            if let CursorMode::Insert(selection) = cursor.mode.clone() {
+                let mut new_selection = Selection::new();
+                for region in selection.regions() {
+                    let (start, _) = rope_text.select_paragraph(region.min());
+                    // The end of a selection is on the line after the paragraph it ends
+                    let end_offset = if region.is_caret() {
+                        region.max()
+                    } else {
+                        region.max().saturating_sub(1).max(region.min())
+                    };
+                    let (_, end) = rope_text.select_paragraph(end_offset);
+                    new_selection.add_region(SelRegion::new(start, end, None));
+                }
+                cursor.set_insert(new_selection);
+            }
+        }
         SelectAllCurrent | SelectNextCurrent | SelectSkipCurrent => {
-            // TODO: How should we handle these?
-            // The specific common editor behavior is to use the editor's find
-            // to do these finds and use it for the selections.
-            // However, we haven't included a `find` in floem-editor
+            if let CursorMode::Insert(mut selection) = cursor.mode.clone() {
+                let Some(current) = selection.last_inserted().copied() else {
+                    return;
+                };
+                // Starting from a caret selects the word at the caret
+                let (start, end) = if current.is_caret() {
+                    view.select_word(current.start)
+                } else {
+                    (current.min(), current.max())
+                };
+                if start == end {
+                    return;
+                }
+                let text = view.text();
+                let needle = text.slice_to_cow(start..end);
+                // A whole word, like the word at a caret, only matches whole words, not the words
+                // which contain it
+                let word_chars = view.style().word_chars(view.id());
+                let whole_word = is_whole_word(&text, start, end, &word_chars);
+                let is_match = |offset: usize| {
+                    !whole_word || is_whole_word(&text, offset, offset + needle.len(), &word_chars)
+                };
+
+                if *cmd == SelectAllCurrent {
+                    let mut new_selection = Selection::new();
+                    find_occurrence(&text, &needle, 0..text.len(), |offset| {
+                        if is_match(offset) {
+                            new_selection.add_region(SelRegion::new(
+                                offset,
+                                offset + needle.len(),
+                                None,
+                            ));
+                        }
+                        false
+                    });
+                    selection = new_selection;
+                } else if current.is_caret() {
+                    selection.replace_last_inserted_region(SelRegion::new(start, end, None));
+                } else if let Some(next) =
+                    next_occurrence(&text, &needle, end, &selection, is_match)
+                {
+                    if *cmd == SelectSkipCurrent {
+                        selection.replace_last_inserted_region(next);
+                    } else {
+                        selection.add_region(next);
+                    }
+                }
+                cursor.set_insert(selection);
+            }
         }
         SelectAll => {
             let new_selection = Selection::region(0, rope_text.len());
@@ -750,6 +918,77 @@ pub fn do_multi_selection(view: &Editor, cursor: &mut Cursor, cmd: &MultiSelecti
     }
 }
 
+/// The first occurrence of `needle` from `offset` which `is_match` accepts and which isn't
+/// selected yet, wrapping around to the start of the text once.
+fn next_occurrence(
+    text: &Rope,
+    needle: &str,
+    offset: usize,
+    selection: &Selection,
+    is_match: impl Fn(usize) -> bool,
+) -> Option<SelRegion> {
+    let is_next = |start: usize| {
+        is_match(start)
+            && !selection
+                .regions()
+                .iter()
+                .any(|region| region.min() == start && region.max() == start + needle.len())
+    };
+    // The occurrences before `offset` may end after it
+    let wrapped_end = (offset + needle.len() - 1).min(text.len());
+    let start = find_occurrence(text, needle, offset..text.len(), is_next)
+        .or_else(|| find_occurrence(text, needle, 0..wrapped_end, is_next))?;
+    Some(SelRegion::new(start, start + needle.len(), None))
+}
+
+/// The first occurrence of `needle` in `range` of `text` for which `f` returns true, calling `f`
+/// with the occurrences in order. The text is searched chunk by chunk instead of being copied.
+fn find_occurrence(
+    text: &Rope,
+    needle: &str,
+    range: Range<usize>,
+    mut f: impl FnMut(usize) -> bool,
+) -> Option<usize> {
+    // The end of the chunks searched so far, which may hold the start of an occurrence, and
+    // the next chunk
+    let mut window = String::new();
+    let mut window_start = range.start;
+    for chunk in text.iter_chunks(range) {
+        window.push_str(chunk);
+        let found = window
+            .match_indices(needle)
+            .map(|(start, _)| window_start + start)
+            .find(|start| f(*start));
+        if found.is_some() {
+            return found;
+        }
+
+        // Only keep what is too short to hold an occurrence, so that none is found twice
+        let mut kept = window.len().saturating_sub(needle.len() - 1);
+        while !window.is_char_boundary(kept) {
+            kept += 1;
+        }
+        window.drain(..kept);
+        window_start += kept;
+    }
+    None
+}
+
+/// Whether `start..end` isn't preceded or followed by a character of the words, which are the
+/// letters, digits, `_` and the `word_chars`.
+fn is_whole_word(text: &Rope, start: usize, end: usize, word_chars: &str) -> bool {
+    let is_word_char = |c: Option<char>| {
+        c.is_some_and(|c| {
+            word_chars.contains(c) || get_char_property(c) == CharClassification::Other
+        })
+    };
+    let mut cursor = lapce_xi_rope::Cursor::new(text, start);
+    let before = cursor.prev_codepoint();
+    cursor.set(end);
+    let after = cursor.next_codepoint();
+    !is_word_char(before) && !is_word_char(after)
+}
+
 pub fn do_motion_mode(
     ed: &Editor,
     action: &dyn CommonAction,
@@ -781,15 +1020,20 @@ mod tests {
 
     use floem_editor_core::{
         buffer::rope_text::{RopeText, RopeTextVal},
-        cursor::{ColPosition, CursorAffinity},
+        command::MultiSelectionCommand,
+        cursor::{ColPosition, Cursor, CursorAffinity, CursorMode},
         mode::Mode,
+        selection::Selection,
     };
     use floem_reactive::{Scope, SignalUpdate};
     use lapce_xi_rope::Rope;
     use peniko::kurbo::{Rect, Size};
 
     use crate::views::editor::{
-        movement::{correct_crlf, end_of_line, move_down, move_up},
+        movement::{
+            correct_crlf, do_multi_selection, end_of_line, move_down, move_down_line, move_up,
+            move_up_line, start_of_line,
+        },
         text::SimpleStyling,
         text_document::TextDocument,
     };
@@ -833,35 +1077,35 @@ mod tests {
     fn test_end_of_line() {
         let ed = make_ed("abc\ndef\nghi");
         let mut aff = CursorAffinity::Backward;
-        assert_eq!(end_of_line(&ed, &mut aff, 0, Mode::Insert).0, 3);
+        assert_eq!(end_of_line(&ed, &mut aff, 0, Mode::Insert, false).0, 3);
         assert_eq!(aff, CursorAffinity::Backward);
-        assert_eq!(end_of_line(&ed, &mut aff, 1, Mode::Insert).0, 3);
+        assert_eq!(end_of_line(&ed, &mut aff, 1, Mode::Insert, false).0, 3);
         assert_eq!(aff, CursorAffinity::Backward);
-        assert_eq!(end_of_line(&ed, &mut aff, 3, Mode::Insert).0, 3);
+        assert_eq!(end_of_line(&ed, &mut aff, 3, Mode::Insert, false).0, 3);
         assert_eq!(aff, CursorAffinity::Backward);
 
-        assert_eq!(end_of_line(&ed, &mut aff, 4, Mode::Insert).0, 7);
-        assert_eq!(end_of_line(&ed, &mut aff, 5, Mode::Insert).0, 7);
-        assert_eq!(end_of_line(&ed, &mut aff, 7, Mode::Insert).0, 7);
+        assert_eq!(end_of_line(&ed, &mut aff, 4, Mode::Insert, false).0, 7);
+        assert_eq!(end_of_line(&ed, &mut aff, 5, Mode::Insert, false).0, 7);
+        assert_eq!(end_of_line(&ed, &mut aff, 7, Mode::Insert, false).0, 7);
 
         let ed = make_ed("abc\r\ndef\r\nghi");
         let mut aff = CursorAffinity::Forward;
-        assert_eq!(end_of_line(&ed, &mut aff, 0, Mode::Insert).0, 3);
+        assert_eq!(end_of_line(&ed, &mut aff, 0, Mode::Insert, false).0, 3);
         assert_eq!(aff, CursorAffinity::Backward);
 
-        assert_eq!(end_of_line(&ed, &mut aff, 1, Mode::Insert).0, 3);
+        assert_eq!(end_of_line(&ed, &mut aff, 1, Mode::Insert, false).0, 3);
         assert_eq!(aff, CursorAffinity::Backward);
-        assert_eq!(end_of_line(&ed, &mut aff, 3, Mode::Insert).0, 3);
+        assert_eq!(end_of_line(&ed, &mut aff, 3, Mode::Insert, false).0, 3);
         assert_eq!(aff, CursorAffinity::Backward);
 
-        assert_eq!(end_of_line(&ed, &mut aff, 5, Mode::Insert).0, 8);
-        assert_eq!(end_of_line(&ed, &mut aff, 6, Mode::Insert).0, 8);
-        assert_eq!(end_of_line(&ed, &mut aff, 7, Mode::Insert).0, 8);
-        assert_eq!(end_of_line(&ed, &mut aff, 8, Mode::Insert).0, 8);
+        assert_eq!(end_of_line(&ed, &mut aff, 5, Mode::Insert, false).0, 8);
+        assert_eq!(end_of_line(&ed, &mut aff, 6, Mode::Insert, false).0, 8);
+        assert_eq!(end_of_line(&ed, &mut aff, 7, Mode::Insert, false).0, 8);
+        assert_eq!(end_of_line(&ed, &mut aff, 8, Mode::Insert, false).0, 8);
 
         let ed = make_ed("testing\r\nAbout\r\nblah");
         let mut aff = CursorAffinity::Backward;
-        assert_eq!(end_of_line(&ed, &mut aff, 0, Mode::Insert).0, 7);
+        assert_eq!(end_of_line(&ed, &mut aff, 0, Mode::Insert, false).0, 7);
     }
 
     #[test]
@@ -903,4 +1147,123 @@ mod tests {
         // (approximately) position 1 on the next line with content they arrive at
         assert_eq!(offset, 1);
     }
+
+    #[test]
+    fn test_sticky_horiz() {
+        let ed = make_ed(
+            "abcdef
+  ab
+abcd",
+        );
+
+        let mut aff = CursorAffinity::Forward;
+        // Going down after going to the end of a line stays at the end of the lines
+        let (offset, horiz) = move_down(&ed, 6, &mut aff, Some(ColPosition::End), Mode::Insert, 1);
+        assert_eq!(offset, 11);
+        let (offset, _) = move_down(&ed, offset, &mut aff, Some(horiz), Mode::Insert, 1);
+        assert_eq!(offset, 16);
+
+        assert_eq!(ed.line_horiz_col(1, &ColPosition::FirstNonBlank, true), 2);
+        let (offset, _) = move_up(
+            &ed,
+            16,
+            &mut aff,
+            Some(ColPosition::FirstNonBlank),
+            Mode::Insert,
+            1,
+        );
+        assert_eq!(offset, 9);
+    }
+
+    #[test]
+    fn test_logical_lines() {
+        let ed = make_ed("abc\n\ndef");
+
+        let mut aff = CursorAffinity::Forward;
+        let (offset, horiz) = move_down_line(&ed, 2, &mut aff, None, Mode::Insert, 1);
+        assert_eq!(offset, 4);
+        let (offset, _) = move_down_line(&ed, offset, &mut aff, Some(horiz), Mode::Insert, 1);
+        assert_eq!(offset, 7);
+        assert_eq!(move_up_line(&ed, 7, &mut aff, None, Mode::Insert, 2).0, 2);
+        assert_eq!(move_up_line(&ed, 1, &mut aff, None, Mode::Insert, 1).0, 0);
+        assert_eq!(move_down_line(&ed, 5, &mut aff, None, Mode::Insert, 1).0, 8);
+
+        assert_eq!(end_of_line(&ed, &mut aff, 0, Mode::Insert, true).0, 3);
+        assert_eq!(start_of_line(&ed, &mut aff, 7, true).0, 5);
+    }
+
+    #[test]
+    fn test_select_paragraph() {
+        let ed = make_ed("a\nb\n\nc\nd\n\ne");
+
+        // A selection ending at the start of a line doesn't select the paragraph of that line
+        let mode = CursorMode::Insert(Selection::region(2, 4));
+        let mut cursor = Cursor::new(mode, None, None);
+        do_multi_selection(&ed, &mut cursor, &MultiSelectionCommand::SelectParagraph);
+        assert_eq!(cursor.edit_selection(&ed.rope_text()).min_offset(), 0);
+        assert_eq!(cursor.edit_selection(&ed.rope_text()).max_offset(), 4);
+
+        let mode = CursorMode::Insert(Selection::region(2, 6));
+        let mut cursor = Cursor::new(mode, None, None);
+        do_multi_selection(&ed, &mut cursor, &MultiSelectionCommand::SelectParagraph);
+        assert_eq!(cursor.edit_selection(&ed.rope_text()).max_offset(), 9);
+    }
+
+    #[test]
+    fn test_select_occurrences() {
+        let ed = make_ed("foo bar foo\nfoo");
+        let regions = |cursor: &Cursor| {
+            cursor
+                .edit_selection(&ed.rope_text())
+                .regions()
+                .iter()
+                .map(|region| (region.min(), region.max()))
+                .collect::<Vec<_>>()
+        };
+
+        let mut cursor = Cursor::new(CursorMode::Insert(Selection::caret(9)), None, None);
+        do_multi_selection(&ed, &mut cursor, &MultiSelectionCommand::SelectNextCurrent);
+        assert_eq!(regions(&cursor), [(8, 11)]);
+        do_multi_selection(&ed, &mut cursor, &MultiSelectionCommand::SelectNextCurrent);
+        assert_eq!(regions(&cursor), [(8, 11), (12, 15)]);
+        // The search wraps around to the start of the text
+        do_multi_selection(&ed, &mut cursor, &MultiSelectionCommand::SelectSkipCurrent);
+        assert_eq!(regions(&cursor), [(0, 3), (8, 11)]);
+
+        let mut cursor = Cursor::new(CursorMode::Insert(Selection::caret(1)), None, None);
+        do_multi_selection(&ed, &mut cursor, &MultiSelectionCommand::SelectAllCurrent);
+        assert_eq!(regions(&cursor), [(0, 3), (8, 11), (12, 15)]);
+    }
+
+    #[test]
+    fn test_select_whole_word_occurrences() {
+        let ed = make_ed("foo foobar foo");
+        let regions = |cursor: &Cursor| {
+            cursor
+                .edit_selection(&ed.rope_text())
+                .regions()
+                .iter()
+                .map(|region| (region.min(), region.max()))
+                .collect::<Vec<_>>()
+        };
+
+        // The word at the caret doesn't match the word which contains it
+        let mut cursor = Cursor::new(CursorMode::Insert(Selection::caret(1)), None, None);
+        do_multi_selection(&ed, &mut cursor, &MultiSelectionCommand::SelectNextCurrent);
+        assert_eq!(regions(&cursor), [(0, 3)]);
+        do_multi_selection(&ed, &mut cursor, &MultiSelectionCommand::SelectNextCurrent);
+        assert_eq!(regions(&cursor), [(0, 3), (11, 14)]);
+        // Every occurrence is selected, so there's no next one
+        do_multi_selection(&ed, &mut cursor, &MultiSelectionCommand::SelectNextCurrent);
+        assert_eq!(regions(&cursor), [(0, 3), (11, 14)]);
+
+        let mut cursor = Cursor::new(CursorMode::Insert(Selection::caret(12)), None, None);
+        do_multi_selection(&ed, &mut cursor, &MultiSelectionCommand::SelectAllCurrent);
+        assert_eq!(regions(&cursor), [(0, 3), (11, 14)]);
+
+        // Part of a word matches inside the other words
+        let mut cursor = Cursor::new(CursorMode::Insert(Selection::region(1, 3)), None, None);
+        do_multi_selection(&ed, &mut cursor, &MultiSelectionCommand::SelectNextCurrent);
+        assert_eq!(regions(&cursor), [(1, 3), (5, 7)]);
+    }
 }
@@ -0,0 +1,174 @@
+//! Pasting large texts in chunks, so that the editor stays responsive.
+//!
+//! Pastes of at least [`LARGE_PASTE`] bytes are inserted a chunk per frame, and can be canceled
+//! while they're inserted. The chunks are undone together, like a single paste.
+use std::{rc::Rc, time::Duration};
+
+use floem_editor_core::{
+    cursor::CursorMode,
+    editor::EditType,
+    selection::{SelRegion, Selection},
+};
+use floem_reactive::{RwSignal, SignalGet, SignalUpdate, SignalWith};
+
+use crate::action::exec_after;
+
+use super::Editor;
+
+/// The size in bytes from which pastes are inserted in chunks.
+pub const LARGE_PASTE: usize = 1024 * 1024;
+/// The size in bytes of the chunks inserted in each frame.
+const CHUNK_SIZE: usize = 256 * 1024;
+
+/// A paste inserted in chunks, see [`paste_in_chunks`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ChunkedPaste {
+    inserted: RwSignal<usize>,
+    total: usize,
+    canceled: RwSignal<bool>,
+}
+
+impl ChunkedPaste {
+    /// The part of the text which was inserted, from 0 to 1.
+    pub fn progress(&self) -> f64 {
+        if self.total == 0 {
+            return 1.0;
+        }
+        self.inserted.get() as f64 / self.total as f64
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.inserted.get() == self.total || self.canceled.get()
+    }
+
+    /// Stops inserting the text. The part which was already inserted stays.
+    pub fn cancel(&self) {
+        self.canceled.set(true);
+    }
+}
+
+/// The end of the next chunk of `text` starting at `start`, after a line ending when there's
+/// one close to the chunk size.
+fn chunk_end(text: &str, start: usize) -> usize {
+    if text.len() - start <= CHUNK_SIZE {
+        return text.len();
+    }
+    let mut end = start + CHUNK_SIZE;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    match text[start..end].rfind('\n') {
+        Some(newline) if newline >= CHUNK_SIZE / 2 => start + newline + 1,
+        _ => end,
+    }
+}
+
+/// Replaces the selections of `editor` with `text`, inserted one chunk per frame.
+///
+/// The paste is the [active paste](Editor::active_paste) of the editor until it's finished,
+/// and an active paste is canceled by a new one.
+pub fn paste_in_chunks(editor: &Editor, text: String) -> ChunkedPaste {
+    if let Some(previous) = editor.active_paste.get_untracked() {
+        previous.cancel();
+    }
+    let paste = ChunkedPaste {
+        inserted: RwSignal::new(0),
+        total: text.len(),
+        canceled: RwSignal::new(false),
+    };
+    let text: Rc<str> = text.into();
+    let selection = editor
+        .cursor
+        .with_untracked(|cursor| cursor.edit_selection(&editor.rope_text()));
+
+    // Each selection is replaced by the first chunk, and the following chunks are inserted
+    // after the previous ones, with each caret moved by the chunks inserted before it
+    let end = chunk_end(&text, 0);
+    let mut removed = 0;
+    let carets: Vec<usize> = selection
+        .regions()
+        .iter()
+        .enumerate()
+        .map(|(i, region)| {
+            let caret = region.min() - removed + (i + 1) * end;
+            removed += region.max() - region.min();
+            caret
+        })
+        .collect();
+    editor
+        .doc()
+        .edit_single(selection, &text[..end], EditType::Paste);
+    set_carets(editor, &carets);
+    paste.inserted.set(end);
+    editor.active_paste.set(Some(paste));
+
+    insert_next_chunk(editor.clone(), paste, text, carets);
+    paste
+}
+
+fn set_carets(editor: &Editor, carets: &[usize]) {
+    let mut selection = Selection::new();
+    for caret in carets {
+        selection.add_region(SelRegion::caret(*caret));
+    }
+    editor
+        .cursor
+        .update(|cursor| cursor.mode = CursorMode::Insert(selection));
+}
+
+fn insert_next_chunk(editor: Editor, paste: ChunkedPaste, text: Rc<str>, mut carets: Vec<usize>) {
+    let start = paste.inserted.get_untracked();
+    if start == text.len() || paste.canceled.get_untracked() {
+        editor.active_paste.set(None);
+        return;
+    }
+    exec_after(Duration::ZERO, move |_| {
+        if paste.canceled.try_get_untracked() != Some(false) {
+            editor.active_paste.try_update(|active| *active = None);
+            return;
+        }
+        let end = chunk_end(&text, start);
+        let mut selection = Selection::new();
+        for caret in &carets {
+            selection.add_region(SelRegion::caret(*caret));
+        }
+
+        // The lines before the first caret don't change, so their layouts are kept for the
+        // cache revision of the edit rather than laying out the document again
+        let first_line = editor.line_of_offset(carets[0]);
+        let cache_rev = editor.doc().cache_rev().get_untracked() + 1;
+        editor.lines.invalidate_from_line(first_line, cache_rev);
+
+        editor
+            .doc()
+            .edit_single(selection, &text[start..end], EditType::PasteContinuation);
+        for (i, caret) in carets.iter_mut().enumerate() {
+            *caret += (i + 1) * (end - start);
+        }
+        set_carets(&editor, &carets);
+        paste.inserted.set(end);
+
+        insert_next_chunk(editor, paste, text, carets);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{chunk_end, CHUNK_SIZE};
+
+    #[test]
+    fn chunks() {
+        let line = "a".repeat(99) + "\n";
+        let text = line.repeat(CHUNK_SIZE / 100 * 3);
+        let end = chunk_end(&text, 0);
+        assert!(end <= CHUNK_SIZE && end > CHUNK_SIZE - 100);
+        assert!(text[..end].ends_with('\n'));
+
+        // Without line endings, the chunks end at character boundaries
+        let text = "é".repeat(CHUNK_SIZE);
+        let end = chunk_end(&text, 0);
+        assert_eq!(end, CHUNK_SIZE);
+        assert_eq!(chunk_end(&text, end), 2 * CHUNK_SIZE);
+        assert_eq!(chunk_end("short", 0), 5);
+    }
+}
@@ -18,6 +18,7 @@ use floem_editor_core::{
     mode::MotionMode,
     register::{Clipboard, Register},
     selection::Selection,
+    util::DEFAULT_SURROUND_CHARS,
     word::WordCursor,
 };
 use floem_reactive::SignalGet;
@@ -27,7 +28,9 @@ use serde::{Deserialize, Serialize};
 
 use super::{
     actions::CommonAction,
+    anchor::{Anchor, Bias},
     command::{Command, CommandExecuted},
+    folding::FoldingRange,
     gutter::GutterClass,
     id::EditorId,
     layout::TextLayoutLine,
@@ -51,7 +54,6 @@ impl SystemClipboard {
         Self
     }
 
-    #[cfg(windows)]
     pub fn get_file_list() -> Option<Vec<std::path::PathBuf>> {
         crate::Clipboard::get_file_list().ok()
     }
@@ -155,6 +157,9 @@ pub trait Document: DocumentPhantom + Downcast {
     /// Note: you should typically *not* need to implement this, unless you have some custom
     /// behavior. Unfortunately this needs an `&self` to be a trait object. So don't call `.update`
     /// on `Self`
+    ///
+    /// This runs in an effect of the editor, so the screen lines are computed again when the
+    /// signals it reads change.
     fn compute_screen_lines(
         &self,
         editor: &Editor,
@@ -198,6 +203,35 @@ pub trait Document: DocumentPhantom + Downcast {
     /// ))
     /// ```
     fn edit(&self, iter: &mut dyn Iterator<Item = (Selection, &str)>, edit_type: EditType);
+
+    /// Create an [`Anchor`] at `offset` which is moved by the edits of the document, so that
+    /// diagnostics, bookmarks and other positions don't drift as the text changes.  
+    /// The anchor lives as long as the current reactive scope.  
+    /// Returns `None` if the document doesn't support anchors, which is the default.
+    fn create_anchor(&self, offset: usize, bias: Bias) -> Option<Anchor> {
+        let _ = (offset, bias);
+        None
+    }
+
+    /// The ranges of lines which can be folded, with a chevron in the gutter of the editors, see
+    /// [`Editor::toggle_fold`].  
+    /// Returns no ranges by default.
+    fn folding_ranges(&self) -> Vec<FoldingRange> {
+        Vec::new()
+    }
+
+    /// Called when `ed` starts showing the document, so that the edits made outside of it, like
+    /// with [`Document::edit`], only lay out the edited lines again in it too.  
+    /// Does nothing by default.
+    fn add_editor(&self, ed: &Editor) {
+        let _ = ed;
+    }
+
+    /// Called when `ed` stops showing the document, see [`Document::add_editor`].  
+    /// Does nothing by default.
+    fn remove_editor(&self, ed: &Editor) {
+        let _ = ed;
+    }
 }
 
 impl_downcast!(Document);
@@ -274,6 +308,25 @@ impl std::fmt::Display for RenderWhitespace {
     }
 }
 
+/// The shape of the carets of an editor.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum CaretShape {
+    /// A vertical bar between two characters, as wide as the caret width
+    #[default]
+    Bar,
+    /// A box over the character after the caret
+    Block,
+    /// A line under the character after the caret, as thick as the caret width
+    Underline,
+}
+impl std::fmt::Display for CaretShape {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("{self:?}"))
+    }
+}
+
 /// There's currently three stages of styling text:  
 /// - `Attrs`: This sets the default values for the text
 ///   - Default font size, font family, etc.
@@ -334,6 +387,18 @@ pub trait Styling {
         false
     }
 
+    /// The characters which are part of words besides letters, digits and `_`, like `-` in CSS
+    /// or `$` in shell scripts, for word motions and selecting words.
+    fn word_chars(&self, _edid: EditorId) -> Cow<str> {
+        Cow::Borrowed("")
+    }
+
+    /// The characters which wrap the selected text when typed, rather than replacing it, like
+    /// brackets and quotes, or `*` and `_` for emphasis in Markdown.
+    fn surround_chars(&self, _edid: EditorId) -> Cow<str> {
+        Cow::Borrowed(DEFAULT_SURROUND_CHARS)
+    }
+
     // TODO: get other style information based on EditorColor enum?
     // TODO: line_style equivalent?
 
@@ -513,6 +578,22 @@ where
     fn edit(&self, iter: &mut dyn Iterator<Item = (Selection, &str)>, edit_type: EditType) {
         self.doc.edit(iter, edit_type)
     }
+
+    fn create_anchor(&self, offset: usize, bias: Bias) -> Option<Anchor> {
+        self.doc.create_anchor(offset, bias)
+    }
+
+    fn folding_ranges(&self) -> Vec<FoldingRange> {
+        self.doc.folding_ranges()
+    }
+
+    fn add_editor(&self, ed: &Editor) {
+        self.doc.add_editor(ed)
+    }
+
+    fn remove_editor(&self, ed: &Editor) {
+        self.doc.remove_editor(ed)
+    }
 }
 impl<D, F> DocumentPhantom for ExtCmdDocument<D, F>
 where
@@ -584,6 +665,8 @@ pub struct SimpleStyling {
     stretch: Stretch,
     tab_width: usize,
     atomic_soft_tabs: bool,
+    word_chars: String,
+    surround_chars: String,
 }
 impl SimpleStyling {
     pub fn builder() -> SimpleStylingBuilder {
@@ -638,6 +721,14 @@ impl SimpleStyling {
         self.atomic_soft_tabs = atomic_soft_tabs;
         self.increment_id();
     }
+
+    pub fn set_word_chars(&mut self, word_chars: impl Into<String>) {
+        self.word_chars = word_chars.into();
+    }
+
+    pub fn set_surround_chars(&mut self, surround_chars: impl Into<String>) {
+        self.surround_chars = surround_chars.into();
+    }
 }
 impl Default for SimpleStyling {
     fn default() -> Self {
@@ -651,6 +742,8 @@ impl Default for SimpleStyling {
             stretch: Stretch::Normal,
             tab_width: 4,
             atomic_soft_tabs: false,
+            word_chars: String::new(),
+            surround_chars: DEFAULT_SURROUND_CHARS.to_string(),
         }
     }
 }
@@ -698,6 +791,14 @@ impl Styling for SimpleStyling {
         self.atomic_soft_tabs
     }
 
+    fn word_chars(&self, _edid: EditorId) -> Cow<str> {
+        Cow::Borrowed(&self.word_chars)
+    }
+
+    fn surround_chars(&self, _edid: EditorId) -> Cow<str> {
+        Cow::Borrowed(&self.surround_chars)
+    }
+
     fn apply_attr_styles(
         &self,
         _edid: EditorId,
@@ -729,6 +830,8 @@ pub struct SimpleStylingBuilder {
     indent_style: Option<IndentStyle>,
     tab_width: Option<usize>,
     atomic_soft_tabs: Option<bool>,
+    word_chars: Option<String>,
+    surround_chars: Option<String>,
     wrap: Option<WrapMethod>,
 }
 impl SimpleStylingBuilder {
@@ -795,6 +898,20 @@ impl SimpleStylingBuilder {
         self
     }
 
+    /// Set the characters which are part of words besides letters, digits and `_`
+    /// Default: none
+    pub fn word_chars(&mut self, word_chars: impl Into<String>) -> &mut Self {
+        self.word_chars = Some(word_chars.into());
+        self
+    }
+
+    /// Set the characters which wrap the selected text when typed, rather than replacing it
+    /// Default: brackets, quotes and backticks
+    pub fn surround_chars(&mut self, surround_chars: impl Into<String>) -> &mut Self {
+        self.surround_chars = Some(surround_chars.into());
+        self
+    }
+
     /// Set the wrapping method
     /// Default: `WrapMethod::EditorWidth`
     pub fn wrap(&mut self, wrap: WrapMethod) -> &mut Self {
@@ -815,6 +932,11 @@ impl SimpleStylingBuilder {
             stretch: self.stretch.unwrap_or(default.stretch),
             tab_width: self.tab_width.unwrap_or(default.tab_width),
             atomic_soft_tabs: self.atomic_soft_tabs.unwrap_or(default.atomic_soft_tabs),
+            word_chars: self.word_chars.clone().unwrap_or(default.word_chars),
+            surround_chars: self
+                .surround_chars
+                .clone()
+                .unwrap_or(default.surround_chars),
         }
     }
 }
@@ -2,7 +2,7 @@ use std::{
     cell::{Cell, RefCell},
     collections::HashMap,
     ops::Range,
-    rc::Rc,
+    rc::{Rc, Weak},
 };
 
 use floem_editor_core::{
@@ -10,6 +10,7 @@ use floem_editor_core::{
     command::EditCommand,
     cursor::Cursor,
     editor::{Action, EditConf, EditType},
+    line_ending::LineEnding,
     mode::{Mode, MotionMode},
     register::Register,
     selection::Selection,
@@ -25,10 +26,13 @@ use crate::keyboard::Modifiers;
 
 use super::{
     actions::{handle_command_default, CommonAction},
+    anchor::{Anchor, Anchors, Bias},
     command::{Command, CommandExecuted},
+    folding::FoldingRange,
     id::EditorId,
     phantom_text::{PhantomText, PhantomTextKind, PhantomTextLine},
     text::{Document, DocumentPhantom, PreeditData, SystemClipboard},
+    visual_line::Lines,
     Editor, EditorStyle,
 };
 
@@ -69,12 +73,22 @@ pub struct TextDocument {
 
     pub placeholders: RwSignal<HashMap<EditorId, String>>,
 
+    /// The ranges of lines which can be folded, like the blocks found by a language server.  
+    /// They aren't moved by edits, so they are set again by their owner after the edits.
+    pub folding_ranges: RwSignal<Vec<FoldingRange>>,
+
     // (cmd: &Command, count: Option<usize>, modifiers: ModifierState)
     /// Ran before a command is executed. If it says that it executed the command, then handlers
     /// after it will not be called.
     pre_command: Rc<RefCell<HashMap<EditorId, SmallVec<[PreCommandFn; 1]>>>>,
 
     on_updates: Rc<RefCell<SmallVec<[OnUpdateFn; 1]>>>,
+
+    /// The lines of the editors showing the document, whose layouts are kept for the unedited
+    /// lines on every edit, see [`Document::add_editor`]
+    editor_lines: Rc<RefCell<HashMap<EditorId, Weak<Lines>>>>,
+
+    anchors: Anchors,
 }
 impl TextDocument {
     pub fn new(cx: Scope, text: impl Into<Rope>) -> TextDocument {
@@ -87,9 +101,12 @@ impl TextDocument {
 
         let placeholders = cx.create_rw_signal(HashMap::new());
 
-        // Whenever the placeholders change, update the cache rev
+        let folding_ranges = cx.create_rw_signal(Vec::new());
+
+        // Whenever the placeholders or the folding ranges change, update the cache rev
         create_effect(move |_| {
             placeholders.track();
+            folding_ranges.track();
             cache_rev.try_update(|cache_rev| {
                 *cache_rev += 1;
             });
@@ -102,8 +119,11 @@ impl TextDocument {
             keep_indent: Cell::new(true),
             auto_indent: Cell::new(false),
             placeholders,
+            folding_ranges,
             pre_command: Rc::new(RefCell::new(HashMap::new())),
             on_updates: Rc::new(RefCell::new(SmallVec::new())),
+            editor_lines: Rc::new(RefCell::new(HashMap::new())),
+            anchors: Anchors::new(),
         }
     }
 
@@ -113,7 +133,28 @@ impl TextDocument {
         });
     }
 
+    /// Moves the layouts of the lines after the edits of `deltas` in every editor of the
+    /// document, so that only the edited lines are laid out again. This must be called before
+    /// the cache revision is updated for the edits.
+    fn apply_edits(&self, deltas: &[(Rope, RopeDelta, InvalLines)]) {
+        let cache_rev = self.cache_rev.get_untracked();
+        self.editor_lines.borrow_mut().retain(|_, lines| {
+            let Some(lines) = lines.upgrade() else {
+                return false;
+            };
+            lines.apply_edits(
+                deltas.iter().map(|(_, _, inval)| inval),
+                cache_rev,
+                cache_rev + 1,
+            );
+            true
+        });
+    }
+
     fn on_update(&self, ed: Option<&Editor>, deltas: &[(Rope, RopeDelta, InvalLines)]) {
+        for (_, delta, _) in deltas {
+            self.anchors.apply_delta(delta);
+        }
         let on_updates = self.on_updates.borrow();
         let data = OnUpdate { editor: ed, deltas };
         for on_update in on_updates.iter() {
@@ -144,6 +185,38 @@ impl TextDocument {
         self.on_updates.borrow_mut().clear();
     }
 
+    /// Whether the document wasn't edited since it was created or since the last call to
+    /// [`TextDocument::set_pristine`], like after it was saved.
+    pub fn is_pristine(&self) -> bool {
+        self.buffer.with(|buffer| buffer.is_pristine())
+    }
+
+    /// The line ending of the document, found when it's created or reloaded, which the line
+    /// endings of inserted text are changed to.
+    pub fn line_ending(&self) -> LineEnding {
+        self.buffer.with(|buffer| buffer.line_ending())
+    }
+
+    pub fn set_pristine(&self) {
+        self.buffer.update(|buffer| buffer.set_pristine());
+    }
+
+    /// Replaces the text of the document with `text`, as an edit which can be undone.
+    ///
+    /// The line ending of the document becomes the one of `text`. If `set_pristine` is true,
+    /// the document is then considered unedited, like after loading a file.
+    pub fn reload(&self, text: impl Into<Rope>, set_pristine: bool) {
+        let text = text.into();
+        let delta = self
+            .buffer
+            .try_update(|buffer| buffer.reload(text, set_pristine));
+        let deltas = delta.map(|x| [x]);
+        let deltas = deltas.as_ref().map(|x| x as &[_]).unwrap_or(&[]);
+
+        self.update_cache_rev();
+        self.on_update(None, deltas);
+    }
+
     pub fn add_placeholder(&self, editor_id: EditorId, placeholder: String) {
         self.placeholders.update(|placeholders| {
             placeholders.insert(editor_id, placeholder);
@@ -154,6 +227,33 @@ impl TextDocument {
         self.placeholders
             .with_untracked(|placeholders| placeholders.get(&editor_id).cloned())
     }
+
+    /// Inserts `text` at the selections of `cursor`, like typing it.
+    pub(crate) fn insert(&self, ed: &Editor, cursor: &mut Cursor, text: &str) {
+        let old_cursor_mode = cursor.mode.clone();
+        let surround_chars = ed.style().surround_chars(ed.id());
+        let deltas = self
+            .buffer
+            .try_update(|buffer| {
+                Action::insert(
+                    cursor,
+                    buffer,
+                    text,
+                    &|_, c, offset| WordCursor::new(&self.text(), offset).previous_unmatched(c),
+                    // TODO: ?
+                    false,
+                    &surround_chars,
+                )
+            })
+            .unwrap();
+        self.buffer.update(|buffer| {
+            buffer.set_cursor_before(old_cursor_mode);
+            buffer.set_cursor_after(cursor.mode.clone());
+        });
+        self.apply_edits(&deltas);
+        self.update_cache_rev();
+        self.on_update(Some(ed), &deltas);
+    }
 }
 impl Document for TextDocument {
     fn text(&self) -> Rope {
@@ -202,32 +302,7 @@ impl Document for TextDocument {
         let mode = ed.cursor.with_untracked(|c| c.get_mode());
         if mode == Mode::Insert {
             let mut cursor = ed.cursor.get_untracked();
-            {
-                let old_cursor_mode = cursor.mode.clone();
-                let deltas = self
-                    .buffer
-                    .try_update(|buffer| {
-                        Action::insert(
-                            &mut cursor,
-                            buffer,
-                            c,
-                            &|_, c, offset| {
-                                WordCursor::new(&self.text(), offset).previous_unmatched(c)
-                            },
-                            // TODO: ?
-                            false,
-                            false,
-                        )
-                    })
-                    .unwrap();
-                self.buffer.update(|buffer| {
-                    buffer.set_cursor_before(old_cursor_mode);
-                    buffer.set_cursor_after(cursor.mode.clone());
-                });
-                // TODO: line specific invalidation
-                self.update_cache_rev();
-                self.on_update(Some(ed), &deltas);
-            }
+            self.insert(ed, &mut cursor, c);
             ed.cursor.set(cursor);
         }
     }
@@ -239,9 +314,28 @@ impl Document for TextDocument {
         let deltas = deltas.map(|x| [x]);
         let deltas = deltas.as_ref().map(|x| x as &[_]).unwrap_or(&[]);
 
+        self.apply_edits(deltas);
         self.update_cache_rev();
         self.on_update(None, deltas);
     }
+
+    fn create_anchor(&self, offset: usize, bias: Bias) -> Option<Anchor> {
+        Some(self.anchors.create(offset, bias))
+    }
+
+    fn folding_ranges(&self) -> Vec<FoldingRange> {
+        self.folding_ranges.get_untracked()
+    }
+
+    fn add_editor(&self, ed: &Editor) {
+        self.editor_lines
+            .borrow_mut()
+            .insert(ed.id(), Rc::downgrade(&ed.lines));
+    }
+
+    fn remove_editor(&self, ed: &Editor) {
+        self.editor_lines.borrow_mut().remove(&ed.id());
+    }
 }
 impl DocumentPhantom for TextDocument {
     fn phantom_text(&self, edid: EditorId, styling: &EditorStyle, line: usize) -> PhantomTextLine {
@@ -352,6 +446,7 @@ impl CommonAction for TextDocument {
                 buffer.set_cursor_after(cursor.mode.clone());
             });
 
+            self.apply_edits(&deltas);
             self.update_cache_rev();
             self.on_update(Some(ed), &deltas);
         }
@@ -1,7 +1,11 @@
-use std::{collections::HashMap, ops::RangeInclusive, rc::Rc};
+use std::{
+    collections::HashMap,
+    ops::{Range, RangeInclusive},
+    rc::Rc,
+};
 
 use crate::{
-    action::{set_ime_allowed, set_ime_cursor_area},
+    action::set_ime_cursor_area,
     context::{LayoutCx, PaintCx, UpdateCx},
     event::{Event, EventListener, EventPropagation},
     id::ViewId,
@@ -14,7 +18,7 @@ use crate::{
     taffy::tree::NodeId,
     text::{Attrs, AttrsList, TextLayout},
     view::{IntoView, View},
-    views::{scroll, stack, Decorators},
+    views::{scroll, scroll::MiddleClickAutoscroll, stack, Decorators},
     Renderer,
 };
 use floem_editor_core::{
@@ -31,7 +35,7 @@ use crate::views::editor::{
     visual_line::{RVLine, VLineInfo},
 };
 
-use super::{Editor, CHAR_WIDTH};
+use super::{text::CaretShape, CursorInfo, Editor, CHAR_WIDTH};
 
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum DiffSectionKind {
@@ -222,6 +226,87 @@ impl ScreenLines {
             .copied()
     }
 
+    /// The offset in the document at `point`, in the coordinates of the editor, or `None` if
+    /// it isn't on one of the screen lines.
+    ///
+    /// Phantom text hit by the point gives the offset it's attached to.
+    pub fn offset_at_point(&self, ed: &Editor, point: Point) -> Option<usize> {
+        let on_screen = self.iter_line_info().any(|info| {
+            let line_height = f64::from(ed.line_height(info.vline_info.rvline.line));
+            info.vline_y <= point.y && point.y < info.vline_y + line_height
+        });
+        on_screen.then(|| ed.offset_of_point(Mode::Insert, point).0)
+    }
+
+    /// The rectangles covering the text of `range` on each of the screen lines, in the
+    /// coordinates of the editor, for overlays like selections and search results.
+    ///
+    /// The rectangles of the visual lines which continue on the next one extend past their
+    /// last character, and empty lines get a rectangle as wide as a character. Visual lines
+    /// which aren't on the screen have no rectangle.
+    pub fn rects_for_range(&self, ed: &Editor, range: Range<usize>) -> Vec<Rect> {
+        // A range starting at the end of a wrapped visual line starts on the next one, and a
+        // range ending at the start of a visual line ends on the previous one
+        let (start_rvline, start_col) =
+            ed.rvline_col_of_offset(range.start, CursorAffinity::Forward);
+        let (end_rvline, end_col) = ed.rvline_col_of_offset(range.end, CursorAffinity::Backward);
+
+        self.iter_line_info_r(start_rvline..=end_rvline)
+            .map(
+                |LineInfo {
+                     vline_y,
+                     vline_info: info,
+                     ..
+                 }| {
+                    let rvline = info.rvline;
+                    let line = rvline.line;
+
+                    let left_col = if rvline == start_rvline {
+                        start_col
+                    } else {
+                        ed.first_col(info)
+                    };
+                    let right_col = if rvline == end_rvline {
+                        end_col
+                    } else {
+                        ed.last_col(info, true)
+                    };
+
+                    let line_height = f64::from(ed.line_height(line));
+
+                    // Empty lines within the range
+                    if !info.is_empty_phantom() && left_col == right_col {
+                        return Rect::from_origin_size((0.0, vline_y), (CHAR_WIDTH, line_height));
+                    }
+
+                    // Lines with only phantom text are covered entirely
+                    if info.is_empty_phantom() {
+                        let text_layout = ed.text_layout(line);
+                        let width = text_layout
+                            .get_layout_x(rvline.line_index)
+                            .map(|(_, x1)| x1)
+                            .unwrap_or(0.0)
+                            .into();
+                        return Rect::from_origin_size((0.0, vline_y), (width, line_height));
+                    }
+
+                    let x0 = ed
+                        .line_point_of_line_col(line, left_col, CursorAffinity::Forward, true)
+                        .x;
+                    let x1 = ed
+                        .line_point_of_line_col(line, right_col, CursorAffinity::Backward, true)
+                        .x;
+                    let x1 = if rvline != end_rvline {
+                        x1 + CHAR_WIDTH
+                    } else {
+                        x1
+                    };
+                    Rect::new(x0, vline_y, x1, vline_y + line_height)
+                },
+            )
+            .collect()
+    }
+
     /// Ran on [`LayoutEvent::CreatedLayout`](super::visual_line::LayoutEvent::CreatedLayout) to update  [`ScreenLinesBase`] &
     /// the viewport if necessary.
     ///
@@ -246,8 +331,7 @@ impl ScreenLines {
         // base and viewport forward by the number of extra wrapped lines,
         // without needing to recompute the screen lines.
         if is_before {
-            // TODO: don't assume line height is constant
-            let line_height = f64::from(ed.line_height(0));
+            let line_height = f64::from(ed.line_height(line));
 
             // We could use `try_text_layout` here, but I believe this guards against a rare
             // crash (though it is hard to verify) wherein the style id has changed and so the
@@ -300,6 +384,26 @@ impl ScreenLines {
     }
 }
 
+/// A change which can make the [`ScreenLines`] of an editor stale, passed to
+/// [`Editor::update_screen_lines`] which recomputes only as much as the change requires.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ScreenLinesChange {
+    /// The viewport moved or was resized. The screen lines are recomputed when its vertical
+    /// extent changed, otherwise, like when scrolling horizontally, only the base is updated.
+    Viewport(Rect),
+    /// The cache revision of the document changed, or the document was replaced. The text
+    /// layouts were cleared, so every screen line is recomputed.
+    CacheRev,
+    /// The styling or the wrapping of the editor changed, which can change the visual lines
+    /// anywhere, so every screen line is recomputed. The signals read by
+    /// [`Document::compute_screen_lines`](super::text::Document::compute_screen_lines) are
+    /// tracked, so documents which hide or fold lines only send this for the changes they don't
+    /// read through signals.
+    Style,
+    /// The text layout of a line was created, see [`ScreenLines::on_created_layout`].
+    CreatedLayout(usize),
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct ScreenLinesBase {
     /// The current/previous viewport.  
@@ -329,6 +433,82 @@ impl LineInfo {
     }
 }
 
+/// The outlines of the `rects` of a range on consecutive visual lines, like the ones from
+/// [`ScreenLines::rects_for_range`], with the rectangles which touch merged into one shape and
+/// its corners rounded by `radius`.
+pub fn range_outlines(rects: &[Rect], radius: f64) -> Vec<BezPath> {
+    let rects: Vec<Rect> = rects.iter().copied().filter(|r| r.width() > 0.0).collect();
+    let mut outlines = Vec::new();
+    let mut start = 0;
+    for i in 1..=rects.len() {
+        let joined = rects.get(i).is_some_and(|rect| {
+            let prev = rects[i - 1];
+            (rect.y0 - prev.y1).abs() < 0.5 && rect.x0 < prev.x1 && prev.x0 < rect.x1
+        });
+        if !joined {
+            outlines.push(outline(&rects[start..i], radius));
+            start = i;
+        }
+    }
+    outlines
+}
+
+/// The outline of `rects` stacked on top of each other, with rounded corners.
+fn outline(rects: &[Rect], radius: f64) -> BezPath {
+    // The corners of the shape, clockwise from the top right one. The rectangles are snapped
+    // to the bottom of the previous one so that the sides they share are straight
+    let mut corners = Vec::with_capacity(rects.len() * 4);
+    let mut y0 = rects[0].y0;
+    for rect in rects {
+        corners.push(Point::new(rect.x1, y0));
+        corners.push(Point::new(rect.x1, rect.y1));
+        y0 = rect.y1;
+    }
+    for (i, rect) in rects.iter().enumerate().rev() {
+        let y0 = if i == 0 { rect.y0 } else { rects[i - 1].y1 };
+        corners.push(Point::new(rect.x0, rect.y1));
+        corners.push(Point::new(rect.x0, y0));
+    }
+    corners.dedup();
+    let corners: Vec<Point> = (0..corners.len())
+        .filter(|i| {
+            let prev = corners[(i + corners.len() - 1) % corners.len()];
+            let next = corners[(i + 1) % corners.len()];
+            let corner = corners[*i];
+            // Points in the middle of a straight side aren't corners
+            !((prev.x == corner.x && corner.x == next.x)
+                || (prev.y == corner.y && corner.y == next.y))
+        })
+        .map(|i| corners[i])
+        .collect();
+
+    let mut path = BezPath::new();
+    for (i, corner) in corners.iter().enumerate() {
+        let prev = corners[(i + corners.len() - 1) % corners.len()];
+        let next = corners[(i + 1) % corners.len()];
+        let radius = radius
+            .min(corner.distance(prev) / 2.0)
+            .min(corner.distance(next) / 2.0);
+        let from = *corner + (prev - *corner).normalize() * radius;
+        let to = *corner + (next - *corner).normalize() * radius;
+        if i == 0 {
+            path.move_to(from);
+        } else {
+            path.line_to(from);
+        }
+        path.quad_to(*corner, to);
+    }
+    path.close_path();
+    path
+}
+
+/// How the selections of ranges are painted.
+struct SelectionPaint {
+    color: Color,
+    border: Option<Color>,
+    corner_radius: f64,
+}
+
 pub struct EditorView {
     id: ViewId,
     editor: RwSignal<Editor>,
@@ -337,78 +517,20 @@ pub struct EditorView {
 }
 
 impl EditorView {
-    #[allow(clippy::too_many_arguments)]
     fn paint_normal_selection(
         cx: &mut PaintCx,
         ed: &Editor,
-        color: Color,
+        style: &SelectionPaint,
         screen_lines: &ScreenLines,
         start_offset: usize,
         end_offset: usize,
-        affinity: CursorAffinity,
     ) {
-        // TODO: selections should have separate start/end affinity
-        let (start_rvline, start_col) = ed.rvline_col_of_offset(start_offset, affinity);
-        let (end_rvline, end_col) = ed.rvline_col_of_offset(end_offset, affinity);
-
-        for LineInfo {
-            vline_y,
-            vline_info: info,
-            ..
-        } in screen_lines.iter_line_info_r(start_rvline..=end_rvline)
-        {
-            let rvline = info.rvline;
-            let line = rvline.line;
-
-            let left_col = if rvline == start_rvline {
-                start_col
-            } else {
-                ed.first_col(info)
-            };
-            let right_col = if rvline == end_rvline {
-                end_col
-            } else {
-                ed.last_col(info, true)
-            };
-
-            let line_height = f64::from(ed.line_height(line));
-
-            // Skip over empty selections
-            if !info.is_empty_phantom() && left_col == right_col {
-                let rect = Rect::from_origin_size((0.0, vline_y), (CHAR_WIDTH, line_height));
-                cx.fill(&rect, color, 0.0);
-
-                continue;
+        let rects = screen_lines.rects_for_range(ed, start_offset..end_offset);
+        for outline in range_outlines(&rects, style.corner_radius) {
+            cx.fill(&outline, style.color, 0.0);
+            if let Some(border) = style.border {
+                cx.stroke(&outline, border, &peniko::kurbo::Stroke::new(1.));
             }
-
-            // TODO: What affinity should these use?
-            let x0 = ed
-                .line_point_of_line_col(line, left_col, CursorAffinity::Forward, true)
-                .x;
-            let x1 = ed
-                .line_point_of_line_col(line, right_col, CursorAffinity::Backward, true)
-                .x;
-            // TODO(minor): Should this be line != end_line?
-            let x1 = if rvline != end_rvline {
-                x1 + CHAR_WIDTH
-            } else {
-                x1
-            };
-
-            let (x0, width) = if info.is_empty_phantom() {
-                let text_layout = ed.text_layout(line);
-                let width = text_layout
-                    .get_layout_x(rvline.line_index)
-                    .map(|(_, x1)| x1)
-                    .unwrap_or(0.0)
-                    .into();
-                (0.0, width)
-            } else {
-                (x0, x1 - x0)
-            };
-
-            let rect = Rect::from_origin_size((x0, vline_y), (width, line_height));
-            cx.fill(&rect, color, 0.0);
         }
     }
 
@@ -542,14 +664,80 @@ impl EditorView {
                 }
             }
 
+            EditorView::paint_highlight_layers(cx, ed, screen_lines, true);
             EditorView::paint_selection(cx, ed, screen_lines);
+            EditorView::paint_highlight_layers(cx, ed, screen_lines, false);
+        });
+    }
+
+    /// Paint the [highlight layers](Editor::add_highlight_layer) which are below the selection
+    /// if `below_selection`, or the ones between the selection and the text otherwise.
+    fn paint_highlight_layers(
+        cx: &mut PaintCx,
+        ed: &Editor,
+        screen_lines: &ScreenLines,
+        below_selection: bool,
+    ) {
+        let Some((first, last)) = screen_lines.rvline_range() else {
+            return;
+        };
+        let visible = ed.offset_of_line(first.line)..ed.offset_of_line(last.line + 1);
+        let viewport = ed.viewport.get_untracked();
+
+        ed.highlight_layers.with_untracked(|layers| {
+            let layers = layers
+                .iter()
+                .filter(|layer| (layer.style.z_index < 0) == below_selection);
+            for layer in layers {
+                let style = &layer.style;
+                for range in layer.visible_ranges(visible.clone()) {
+                    if style.full_line {
+                        let lines =
+                            ed.line_of_offset(range.start)..=ed.line_of_offset(range.end - 1);
+                        for info in screen_lines
+                            .iter_line_info()
+                            .filter(|info| lines.contains(&info.vline_info.rvline.line))
+                        {
+                            let line_height = ed.line_height(info.vline_info.rvline.line);
+                            let rect = Rect::from_origin_size(
+                                (viewport.x0, info.vline_y),
+                                (viewport.width(), f64::from(line_height)),
+                            );
+                            if let Some(background) = style.background {
+                                cx.fill(&rect, background, 0.0);
+                            }
+                        }
+                        continue;
+                    }
+
+                    let rects = screen_lines.rects_for_range(ed, range);
+                    for outline in range_outlines(&rects, style.corner_radius) {
+                        if let Some(background) = style.background {
+                            cx.fill(&outline, background, 0.0);
+                        }
+                        if let Some(border) = style.border {
+                            cx.stroke(&outline, border, &peniko::kurbo::Stroke::new(1.));
+                        }
+                    }
+                    if let Some(color) = style.wave_line {
+                        for rect in rects {
+                            let point = Point::new(rect.x0, rect.y1 - 4.0);
+                            EditorView::paint_wave_line(cx, rect.width(), point, color);
+                        }
+                    }
+                }
+            }
         });
     }
 
     pub fn paint_selection(cx: &mut PaintCx, ed: &Editor, screen_lines: &ScreenLines) {
         let cursor = ed.cursor;
 
-        let selection_color = ed.es.with_untracked(|es| es.selection());
+        let selection = ed.es.with_untracked(|es| SelectionPaint {
+            color: es.selection(),
+            border: es.selection_border(),
+            corner_radius: es.selection_corner_radius(),
+        });
 
         cursor.with_untracked(|cursor| match cursor.mode {
             CursorMode::Normal(_) => {}
@@ -564,11 +752,10 @@ impl EditorView {
                 EditorView::paint_normal_selection(
                     cx,
                     ed,
-                    selection_color,
+                    &selection,
                     screen_lines,
                     start_offset,
                     end_offset,
-                    cursor.affinity,
                 );
             }
             CursorMode::Visual {
@@ -579,7 +766,7 @@ impl EditorView {
                 EditorView::paint_linewise_selection(
                     cx,
                     ed,
-                    selection_color,
+                    selection.color,
                     screen_lines,
                     start.min(end),
                     start.max(end),
@@ -594,7 +781,7 @@ impl EditorView {
                 EditorView::paint_blockwise_selection(
                     cx,
                     ed,
-                    selection_color,
+                    selection.color,
                     screen_lines,
                     start.min(end),
                     start.max(end),
@@ -607,11 +794,10 @@ impl EditorView {
                     EditorView::paint_normal_selection(
                         cx,
                         ed,
-                        selection_color,
+                        &selection,
                         screen_lines,
                         start.min(end),
                         start.max(end),
-                        cursor.affinity,
                     );
                 }
             }
@@ -624,7 +810,6 @@ impl EditorView {
         is_active: bool,
         screen_lines: &ScreenLines,
     ) {
-        let cursor = ed.cursor;
         let hide_cursor = ed.cursor_info.hidden;
         let caret_color = ed.es.with_untracked(|es| es.ed_caret());
 
@@ -632,28 +817,48 @@ impl EditorView {
             return;
         }
 
-        cursor.with_untracked(|cursor| {
-            let style = ed.style();
-            for (_, end) in cursor.regions_iter() {
-                let is_block = match cursor.mode {
-                    CursorMode::Normal(_) | CursorMode::Visual { .. } => true,
-                    CursorMode::Insert(_) => false,
-                };
-                let LineRegion { x, width, rvline } =
-                    cursor_caret(ed, end, is_block, cursor.affinity);
+        for rect in Self::caret_rects(ed, screen_lines) {
+            cx.fill(&rect, &caret_color, 0.0);
+        }
+    }
 
-                if let Some(info) = screen_lines.info(rvline) {
+    /// The rects of the carets on the screen lines which are painted, with the
+    /// [shape](CursorInfo::caret_shape) of the carets in the mode of the cursor.
+    fn caret_rects(ed: &Editor, screen_lines: &ScreenLines) -> Vec<Rect> {
+        ed.cursor.with_untracked(|cursor| {
+            let style = ed.style();
+            let (shape, caret_width) = ed
+                .es
+                .with_untracked(|es| (ed.cursor_info.caret_shape(cursor, es), es.caret_width()));
+            cursor
+                .regions_iter()
+                .filter_map(|(_, end)| {
+                    let is_block = shape != CaretShape::Bar;
+                    let LineRegion { x, width, rvline } =
+                        cursor_caret(ed, end, is_block, cursor.affinity);
+
+                    let info = screen_lines.info(rvline)?;
                     if !style.paint_caret(ed.id(), rvline.line) {
-                        continue;
+                        return None;
                     }
 
-                    let line_height = ed.line_height(info.vline_info.rvline.line);
-                    let rect =
-                        Rect::from_origin_size((x, info.vline_y), (width, f64::from(line_height)));
-                    cx.fill(&rect, &caret_color, 0.0);
-                }
-            }
-        });
+                    let line_height = f64::from(ed.line_height(info.vline_info.rvline.line));
+                    Some(match shape {
+                        CaretShape::Bar => Rect::from_origin_size(
+                            (x + (width - caret_width) / 2.0, info.vline_y),
+                            (caret_width, line_height),
+                        ),
+                        CaretShape::Block => {
+                            Rect::from_origin_size((x, info.vline_y), (width, line_height))
+                        }
+                        CaretShape::Underline => Rect::from_origin_size(
+                            (x, info.vline_y + line_height - caret_width),
+                            (width, caret_width),
+                        ),
+                    })
+                })
+                .collect()
+        })
     }
 
     pub fn paint_wave_line(cx: &mut PaintCx, width: f64, point: Point, color: Color) {
@@ -847,11 +1052,10 @@ impl View for EditorView {
 
             let inner_node = self.inner_node.unwrap();
 
-            // TODO: don't assume there's a constant line height
-            let line_height = f64::from(editor.line_height(0));
+            let line_height = f64::from(editor.line_height(editor.last_line()));
 
             let width = editor.max_line_width().max(parent_size.width());
-            let last_line_height = line_height * (editor.last_vline().get() + 1) as f64;
+            let last_line_height = editor.content_height();
             let height = last_line_height.max(parent_size.height());
 
             let margin_bottom = if editor.es.with_untracked(|es| es.scroll_beyond_last_line()) {
@@ -935,36 +1139,59 @@ pub fn editor_view(
         id.request_layout();
     });
 
-    let hide_cursor = ed.cursor_info.hidden;
+    let highlight_layers = ed.highlight_layers;
     create_effect(move |_| {
-        hide_cursor.track();
+        highlight_layers.track();
         id.request_paint();
     });
 
+    // Blinking only repaints the carets, where they are now and where they were painted
+    // when they last blinked, in case the cursor moved since then
+    let hide_cursor = ed.cursor_info.hidden;
+    let overwrite = ed.cursor_info.overwrite;
+    create_effect(move |painted: Option<Vec<Rect>>| {
+        hide_cursor.track();
+        overwrite.track();
+        let carets = editor
+            .with_untracked(|ed| EditorView::caret_rects(ed, &ed.screen_lines.get_untracked()));
+        for rect in painted.iter().flatten().chain(&carets) {
+            id.request_paint_rect(*rect);
+        }
+        carets
+    });
+
     let editor_window_origin = ed.window_origin;
     let cursor = ed.cursor;
     let ime_allowed = ed.ime_allowed;
+    let ime_purpose = ed.ime_purpose;
     let editor_viewport = ed.viewport;
+    // The window takes the IME settings of the view when it gets the focus
+    create_effect(move |_| {
+        id.set_ime_allowed(ime_allowed.get());
+    });
+    create_effect(move |_| {
+        id.set_ime_purpose(ime_purpose.get());
+    });
     create_effect(move |_| {
         let active = is_active.get();
         if active {
             if !cursor.with(|c| c.is_insert()) {
                 if ime_allowed.get_untracked() {
                     ime_allowed.set(false);
-                    set_ime_allowed(false);
                 }
             } else {
                 if !ime_allowed.get_untracked() {
                     ime_allowed.set(true);
-                    set_ime_allowed(true);
                 }
                 let (offset, affinity) = cursor.with(|c| (c.offset(), c.affinity));
-                let (_, point_below) = ed.points_of_offset(offset, affinity);
+                let (point_above, point_below) = ed.points_of_offset(offset, affinity);
                 let window_origin = editor_window_origin.get();
                 let viewport = editor_viewport.get();
+                // Report the caret itself, the platform places the candidate window
+                // next to it
                 let pos =
-                    window_origin + (point_below.x - viewport.x0, point_below.y - viewport.y0);
-                set_ime_cursor_area(pos, Size::new(800.0, 600.0));
+                    window_origin + (point_above.x - viewport.x0, point_above.y - viewport.y0);
+                set_ime_cursor_area(pos, Size::new(1.0, point_below.y - point_above.y));
             }
         }
     });
@@ -1006,6 +1233,17 @@ pub fn editor_view(
         }
         EventPropagation::Stop
     })
+    .on_event_cont(EventListener::WindowScaleChanged, move |_| {
+        // Drop the text layouts of this editor so its lines are measured again for the new
+        // scale. The document is shared with the editors of other windows, so it's left alone.
+        editor.with_untracked(|ed| {
+            batch(|| {
+                ed.floem_style_id.update(|val| *val += 1);
+                let cache_rev = ed.doc().cache_rev().get_untracked();
+                ed.lines.clear(cache_rev, Some(ed.config_id()));
+            });
+        });
+    })
     .class(EditorViewClass)
 }
 
@@ -1125,7 +1363,17 @@ pub fn editor_gutter(editor: RwSignal<Editor>) -> impl IntoView {
         })
 }
 
-fn editor_content(
+/// Whether a middle click pastes the primary selection in editable editors, on the platforms
+/// which have one.
+const PASTES_PRIMARY_SELECTION: bool = cfg!(any(
+    target_os = "linux",
+    target_os = "freebsd",
+    target_os = "dragonfly",
+    target_os = "openbsd",
+    target_os = "netbsd"
+));
+
+pub(crate) fn editor_content(
     editor: RwSignal<Editor>,
     is_active: impl Fn(bool) -> bool + 'static + Copy,
     handle_key_event: impl Fn(&KeyPress, Modifiers) -> CommandExecuted + 'static,
@@ -1136,6 +1384,7 @@ fn editor_content(
     let scroll_to = ed.scroll_to;
     let window_origin = ed.window_origin;
     let viewport = ed.viewport;
+    let read_only = ed.read_only;
 
     scroll({
         let editor_content_view =
@@ -1151,11 +1400,22 @@ fn editor_content(
             .on_event_cont(EventListener::FocusLost, move |_| {
                 editor.with_untracked(|ed| ed.editor_view_focus_lost.notify())
             })
-            .on_event_cont(EventListener::PointerDown, move |event| {
-                if let Event::PointerDown(pointer_event) = event {
-                    id.request_active();
-                    id.request_focus();
-                    editor.get_untracked().pointer_down(pointer_event);
+            .on_event(EventListener::PointerDown, move |event| {
+                let Event::PointerDown(pointer_event) = event else {
+                    return EventPropagation::Continue;
+                };
+                id.request_active();
+                id.request_focus();
+                editor.get_untracked().pointer_down(pointer_event);
+                // A middle click which pastes the primary selection doesn't also start an
+                // autoscroll of the scroll view
+                if pointer_event.button.is_auxiliary()
+                    && PASTES_PRIMARY_SELECTION
+                    && !read_only.get_untracked()
+                {
+                    EventPropagation::Stop
+                } else {
+                    EventPropagation::Continue
                 }
             })
             .on_event_cont(EventListener::PointerMove, move |event| {
@@ -1216,14 +1476,9 @@ fn editor_content(
         let LineRegion { x, width, rvline } =
             cursor_caret(&editor, offset, !cursor.is_insert(), cursor.affinity);
 
-        // TODO: don't assume line-height is constant
-        let line_height = f64::from(editor.line_height(0));
-
-        // TODO: is there a good way to avoid the calculation of the vline here?
-        let vline = editor.vline_of_rvline(rvline);
-        let rect =
-            Rect::from_origin_size((x, vline.get() as f64 * line_height), (width, line_height))
-                .inflate(10.0, 1.0);
+        let line_height = f64::from(editor.line_height(rvline.line));
+        let rect = Rect::from_origin_size((x, editor.y_of_rvline(rvline)), (width, line_height))
+            .inflate(10.0, 1.0);
 
         let viewport = viewport.get_untracked();
         let smallest_distance = (viewport.y0 - rect.y0)
@@ -1249,7 +1504,7 @@ fn editor_content(
             rect
         }
     })
-    .style(|s| s.size_pct(100.0, 100.0))
+    .style(|s| s.size_pct(100.0, 100.0).set(MiddleClickAutoscroll, true))
 }
 
 #[cfg(test)]
@@ -1264,7 +1519,7 @@ mod tests {
         visual_line::{RVLine, VLineInfo},
     };
 
-    use super::{ScreenLines, ScreenLinesBase};
+    use super::{range_outlines, ScreenLines, ScreenLinesBase};
 
     #[test]
     fn iter_line_info_range() {
@@ -1330,4 +1585,30 @@ mod tests {
             4
         );
     }
+
+    #[test]
+    fn outlines() {
+        use peniko::kurbo::Shape;
+
+        // A selection from the middle of a line to the middle of the line after the next one
+        let rects = [
+            Rect::new(40.0, 0.0, 100.0, 20.0),
+            Rect::new(0.0, 20.0, 100.0, 40.0),
+            Rect::new(0.0, 40.0, 30.0, 60.0),
+        ];
+        let outlines = range_outlines(&rects, 4.0);
+        assert_eq!(outlines.len(), 1);
+        assert_eq!(outlines[0].bounding_box(), Rect::new(0.0, 0.0, 100.0, 60.0));
+        let area = rects.iter().map(Rect::area).sum::<f64>();
+        let rounded = outlines[0].area().abs();
+        assert!(rounded < area && rounded > area - 100.0);
+
+        // Rectangles which don't overlap horizontally aren't joined
+        let rects = [
+            Rect::new(60.0, 0.0, 100.0, 20.0),
+            Rect::new(0.0, 20.0, 50.0, 40.0),
+        ];
+        assert_eq!(range_outlines(&rects, 4.0).len(), 2);
+        assert!(range_outlines(&[], 4.0).is_empty());
+    }
 }
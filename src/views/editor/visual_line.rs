@@ -59,12 +59,16 @@ use std::{
     cell::{Cell, RefCell},
     cmp::Ordering,
     collections::HashMap,
+    ops::Range,
     rc::Rc,
     sync::Arc,
 };
 
 use floem_editor_core::{
-    buffer::rope_text::{RopeText, RopeTextVal},
+    buffer::{
+        rope_text::{RopeText, RopeTextVal},
+        InvalLines,
+    },
     cursor::CursorAffinity,
     word::WordCursor,
 };
@@ -73,7 +77,9 @@ use floem_renderer::text::{HitPosition, LayoutGlyph, TextLayout};
 use lapce_xi_rope::{Interval, Rope};
 use peniko::kurbo::Point;
 
-use super::{layout::TextLayoutLine, listener::Listener};
+use super::{
+    folding::HiddenLines, layout::TextLayoutLine, line_heights::LineHeights, listener::Listener,
+};
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ResolvedWrap {
@@ -139,6 +145,10 @@ impl ConfigId {
     }
 }
 
+/// The text layouts of the lines of a document, created as the lines are shown.
+///
+/// The layouts are for the `config_id` and `cache_rev`, and are cleared when either changes.
+/// Lines without a layout get an estimated height, see [`Lines::with_heights`].
 #[derive(Default)]
 pub struct TextLayoutCache {
     /// The id of the last config so that we can clear when the config changes
@@ -155,11 +165,25 @@ pub struct TextLayoutCache {
     pub layouts: Layouts,
     /// The maximum width seen so far, used to determine if we need to show horizontal scrollbar
     pub max_width: f64,
+    /// The heights of the lines, built when first needed, spliced by edits and dropped when
+    /// every layout is dropped. See [`Lines::with_heights`].
+    heights: Option<LineHeights>,
+    /// The width and the length in bytes of the lines laid out on a single visual line, which
+    /// give the average width of a character for the estimated heights of the lines which
+    /// aren't laid out
+    measured_width: f64,
+    measured_len: usize,
 }
 impl TextLayoutCache {
     pub fn clear(&mut self, cache_rev: u64, config_id: Option<ConfigId>) {
         self.layouts.clear();
+        self.heights = None;
         if let Some(config_id) = config_id {
+            if config_id != self.config_id {
+                // The font may have changed
+                self.measured_width = 0.0;
+                self.measured_len = 0;
+            }
             self.config_id = config_id;
         }
         self.cache_rev = cache_rev;
@@ -171,13 +195,67 @@ impl TextLayoutCache {
     /// Ex: Wrapping width changed, which does not change what the document holds.
     pub fn clear_unchanged(&mut self) {
         self.layouts.clear();
+        // The heights of the visual lines are the same, only their number changes
+        if let Some(heights) = &mut self.heights {
+            heights.invalidate_vline_counts();
+        }
         self.max_width = 0.0;
     }
 
+    /// Keep only the layouts of the lines before `line`, for the cache revision `cache_rev`.
+    pub fn retain_before(&mut self, line: usize, cache_rev: u64) {
+        for layouts in self.layouts.values_mut() {
+            layouts.retain(|l, _| *l < line);
+        }
+        if let Some(heights) = &mut self.heights {
+            heights.invalidate(line..heights.len());
+        }
+        self.cache_rev = cache_rev;
+    }
+
+    /// Drop the layouts of the `old_count` lines from `start_line` replaced by an edit, and move
+    /// the layouts of the lines after them by the difference with the `new_count` lines which
+    /// replaced them.
+    pub fn splice_lines(&mut self, start_line: usize, old_count: usize, new_count: usize) {
+        let old_end = start_line + old_count;
+        for layouts in self.layouts.values_mut() {
+            *layouts = std::mem::take(layouts)
+                .into_iter()
+                .filter_map(|(line, layout)| {
+                    if line < start_line {
+                        Some((line, layout))
+                    } else if line < old_end {
+                        None
+                    } else {
+                        Some((line - old_count + new_count, layout))
+                    }
+                })
+                .collect();
+        }
+        if let Some(heights) = &mut self.heights {
+            heights.splice(start_line, old_count, new_count);
+        }
+    }
+
+    /// Remove the layouts of `line`, for every font size.
+    pub fn remove_line(&mut self, line: usize) {
+        for layouts in self.layouts.values_mut() {
+            layouts.remove(&line);
+        }
+        if let Some(heights) = &mut self.heights {
+            heights.invalidate(line..line + 1);
+        }
+    }
+
     pub fn get(&self, font_size: usize, line: usize) -> Option<&Arc<TextLayoutLine>> {
         self.layouts.get(&font_size).and_then(|c| c.get(&line))
     }
 
+    /// The average width of a character in the lines laid out so far.
+    fn char_width(&self) -> Option<f64> {
+        (self.measured_len > 0).then(|| self.measured_width / self.measured_len as f64)
+    }
+
     pub fn get_mut(&mut self, font_size: usize, line: usize) -> Option<&mut Arc<TextLayoutLine>> {
         self.layouts
             .get_mut(&font_size)
@@ -307,6 +385,10 @@ pub struct Lines {
     font_size_cache_id: Cell<FontSizeCacheId>,
     last_vline: Rc<Cell<Option<VLine>>>,
     pub layout_event: Listener<LayoutEvent>,
+    /// The lines hidden by the folded ranges of the editor, see
+    /// [`Editor::hidden_lines`](super::Editor::hidden_lines). They're moved with the lines by
+    /// [`Lines::apply_edits`], like the heights of the lines.
+    pub(crate) hidden_lines: RefCell<Option<HiddenLines>>,
 }
 impl Lines {
     pub fn new(cx: Scope, font_sizes: RefCell<Rc<dyn LineFontSizeProvider>>) -> Lines {
@@ -318,6 +400,7 @@ impl Lines {
             font_size_cache_id: Cell::new(id),
             last_vline: Rc::new(Cell::new(None)),
             layout_event: Listener::new_empty(cx),
+            hidden_lines: RefCell::new(None),
         }
     }
 
@@ -376,11 +459,7 @@ impl Lines {
     ///
     /// Cached.
     pub fn last_vline(&self, text_prov: impl TextLayoutProvider) -> VLine {
-        let current_id = self.font_sizes.borrow().cache_id();
-        if current_id != self.font_size_cache_id.get() {
-            self.last_vline.set(None);
-            self.font_size_cache_id.set(current_id);
-        }
+        self.check_font_sizes();
 
         if let Some(last_vline) = self.last_vline.get() {
             last_vline
@@ -415,6 +494,103 @@ impl Lines {
         }
     }
 
+    /// Clear the values computed from the font sizes of the lines when they changed.
+    fn check_font_sizes(&self) {
+        let current_id = self.font_sizes.borrow().cache_id();
+        if current_id != self.font_size_cache_id.get() {
+            self.last_vline.set(None);
+            // The height of every line may change
+            self.text_layouts.borrow_mut().heights = None;
+            self.font_size_cache_id.set(current_id);
+        }
+    }
+
+    /// Calls `f` with the heights of the lines, where `line_height` gives the height of a visual
+    /// line of each buffer line, `0` for the lines which are hidden.
+    ///
+    /// The lines which aren't laid out get an estimated number of visual lines, from their
+    /// length and the wrapping width, so that the heights don't need every line to be laid out.
+    /// The estimate is replaced by the actual number once the line is laid out.
+    ///
+    /// The heights are built on the first call, and kept up to date as text layouts are created.
+    /// Edits and invalidated layouts only update the heights of the lines they change, the
+    /// heights are built again when every layout is cleared. Callers must mark the lines whose
+    /// `line_height` changes otherwise with [`Lines::invalidate_heights`], or clear every height
+    /// with [`Lines::clear_heights`].
+    ///
+    /// `f` must not create text layouts, as the layouts are borrowed while it runs.
+    pub fn with_heights<R>(
+        &self,
+        text_prov: impl TextLayoutProvider,
+        line_height: impl Fn(usize) -> f64,
+        f: impl FnOnce(&LineHeights) -> R,
+    ) -> R {
+        self.check_font_sizes();
+
+        let rope_text = text_prov.rope_text();
+        let line_count = rope_text.num_lines();
+        let vline_count = |line: usize| {
+            let layouts = self.text_layouts.borrow();
+            match layouts.get(self.font_size(line), line) {
+                Some(layout) => layout.line_count(),
+                None => self.estimated_vline_count(&layouts, &rope_text, line),
+            }
+        };
+        // The layouts aren't borrowed while `line_height` runs
+        let heights = self.text_layouts.borrow_mut().heights.take();
+        let heights = match heights {
+            Some(mut heights) => {
+                heights.resize(line_count);
+                if heights.has_stale_vline_counts() {
+                    heights.set_vline_counts(&vline_count);
+                }
+                for line in heights.take_stale() {
+                    heights.set_line(line, line_height(line), vline_count(line));
+                }
+                heights
+            }
+            None => LineHeights::new(
+                (0..line_count).map(&line_height).collect(),
+                (0..line_count).map(&vline_count).collect(),
+            ),
+        };
+        self.text_layouts.borrow_mut().heights = Some(heights);
+
+        f(self.text_layouts.borrow().heights.as_ref().unwrap())
+    }
+
+    /// The estimated number of visual lines of a `line` which isn't laid out, from its length
+    /// and the average width of the characters of the lines laid out so far.
+    fn estimated_vline_count(
+        &self,
+        layouts: &TextLayoutCache,
+        rope_text: &impl RopeText,
+        line: usize,
+    ) -> usize {
+        let ResolvedWrap::Width(width) = self.wrap.get() else {
+            return 1;
+        };
+        let len = rope_text.offset_of_line(line + 1) - rope_text.offset_of_line(line);
+        // Roughly the advance of a character of a monospace font
+        let char_width = layouts
+            .char_width()
+            .unwrap_or_else(|| self.font_size(line) as f64 * 0.6);
+        ((len as f64 * char_width / f64::from(width)).ceil() as usize).max(1)
+    }
+
+    /// Mark the heights of `lines` as stale, so that only they are measured again when the
+    /// heights are next needed.
+    pub fn invalidate_heights(&self, lines: Range<usize>) {
+        if let Some(heights) = &mut self.text_layouts.borrow_mut().heights {
+            heights.invalidate(lines);
+        }
+    }
+
+    /// Clear the heights of the lines, so that they're built again when next needed.
+    pub fn clear_heights(&self) {
+        self.text_layouts.borrow_mut().heights = None;
+    }
+
     /// Clear the cache for the last vline
     pub fn clear_last_vline(&self) {
         self.last_vline.set(None);
@@ -925,6 +1101,59 @@ impl Lines {
         self.text_layouts.borrow_mut().clear_unchanged();
         self.last_vline.set(None);
     }
+
+    /// Keep the text layouts of the lines before `line` for the cache revision `cache_rev`,
+    /// instead of clearing every layout when the cache revision changes.
+    ///
+    /// This is for edits which don't change the text before `line`, like appending text, and
+    /// must be called before the layouts are used with the new cache revision.
+    pub fn invalidate_from_line(&self, line: usize, cache_rev: u64) {
+        self.text_layouts
+            .borrow_mut()
+            .retain_before(line, cache_rev);
+        self.last_vline.set(None);
+    }
+
+    /// Update the text layouts for the edits of `invals`, which changed the cache revision from
+    /// `prev_cache_rev` to `cache_rev`, instead of clearing every layout when the cache revision
+    /// changes. Only the layouts of the edited lines are dropped, the layouts of the lines after
+    /// them are moved to their new line.
+    ///
+    /// This must be called before the layouts are used with the new cache revision. If the
+    /// layouts aren't for `prev_cache_rev`, they are cleared like for any other change.
+    pub fn apply_edits<'a>(
+        &self,
+        invals: impl IntoIterator<Item = &'a InvalLines>,
+        prev_cache_rev: u64,
+        cache_rev: u64,
+    ) {
+        let mut text_layouts = self.text_layouts.borrow_mut();
+        if text_layouts.cache_rev != prev_cache_rev {
+            return;
+        }
+        let mut hidden = self.hidden_lines.borrow_mut();
+        let mut hidden_lines = hidden
+            .as_mut()
+            .filter(|hidden_lines| hidden_lines.cache_rev == prev_cache_rev);
+        for inval in invals {
+            text_layouts.splice_lines(inval.start_line, inval.inval_count, inval.new_count);
+            if let Some(hidden_lines) = &mut hidden_lines {
+                hidden_lines.splice(inval.start_line, inval.inval_count, inval.new_count);
+            }
+        }
+        if let Some(hidden_lines) = hidden_lines {
+            hidden_lines.cache_rev = cache_rev;
+        }
+        text_layouts.cache_rev = cache_rev;
+        self.last_vline.set(None);
+    }
+
+    /// Clear the text layouts of `line` without changing the cache revision, for changes which
+    /// only alter the layout of that line, like folding the lines after it.
+    pub fn invalidate_line(&self, line: usize) {
+        self.text_layouts.borrow_mut().remove_line(line);
+        self.last_vline.set(None);
+    }
 }
 
 /// This is a separate function as a hacky solution to lifetimes.
@@ -986,6 +1215,16 @@ fn get_init_text_layout(
             if width > cache.max_width {
                 cache.max_width = width;
             }
+            if text_layout.line_count() == 1 {
+                let rope_text = text_prov.rope_text();
+                cache.measured_width += width;
+                cache.measured_len +=
+                    rope_text.line_end_offset(line, true) - rope_text.offset_of_line(line);
+            }
+            // Only the height of the line changes, so the heights are kept
+            if let Some(heights) = &mut cache.heights {
+                heights.set_vline_count(line, text_layout.line_count());
+            }
             cache
                 .layouts
                 .get_mut(&font_size)
@@ -2059,7 +2298,10 @@ mod tests {
     use std::{borrow::Cow, cell::RefCell, collections::HashMap, rc::Rc, sync::Arc};
 
     use floem_editor_core::{
-        buffer::rope_text::{RopeText, RopeTextRef, RopeTextVal},
+        buffer::{
+            rope_text::{RopeText, RopeTextRef, RopeTextVal},
+            InvalLines,
+        },
         cursor::CursorAffinity,
     };
     use floem_reactive::Scope;
@@ -2075,7 +2317,7 @@ mod tests {
 
     use super::{
         find_vline_init_info_forward, find_vline_init_info_rv_backward, ConfigId, FontSizeCacheId,
-        LineFontSizeProvider, Lines, RVLine, ResolvedWrap, TextLayoutProvider, VLine,
+        HiddenLines, LineFontSizeProvider, Lines, RVLine, ResolvedWrap, TextLayoutProvider, VLine,
     };
 
     /// For most of the logic we standardize on a specific font size.
@@ -3589,6 +3831,70 @@ mod tests {
         assert_eq!(eor(&lines, &text_prov, RVLine::new(1, 0)), 39);
     }
 
+    #[test]
+    fn invalidate_from_line() {
+        let text = Rope::from("aaaa\nbb bb cc\ncc dddd eeee ff\nff gggg");
+        let (_, lines) = make_lines(&text, 2., true);
+        let config_id = ConfigId::new(0, 0);
+        lines.invalidate_from_line(2, 1);
+
+        assert!(lines.try_get_text_layout(1, config_id, 0).is_some());
+        assert!(lines.try_get_text_layout(1, config_id, 1).is_some());
+        assert!(lines.try_get_text_layout(1, config_id, 2).is_none());
+        // Another cache revision still clears everything
+        assert!(lines.try_get_text_layout(2, config_id, 0).is_none());
+    }
+
+    #[test]
+    fn apply_edits() {
+        let text = Rope::from("aaaa\nbb bb cc\ncc dddd eeee ff\nff gggg");
+        let (_, lines) = make_lines(&text, 2., true);
+        let config_id = ConfigId::new(0, 0);
+        let layout_of = |cache_rev, line| lines.try_get_text_layout(cache_rev, config_id, line);
+        let last = layout_of(0, 3).unwrap();
+
+        // Line 1 was replaced by two lines
+        let inval = InvalLines {
+            start_line: 1,
+            inval_count: 1,
+            new_count: 2,
+            old_text: text.clone(),
+        };
+        *lines.hidden_lines.borrow_mut() = Some(HiddenLines {
+            cache_rev: 0,
+            lines: Rc::new([2..3]),
+            moved: false,
+        });
+        lines.apply_edits([&inval], 0, 1);
+        assert!(layout_of(1, 0).is_some());
+        assert!(layout_of(1, 1).is_none());
+        assert!(layout_of(1, 2).is_none());
+        assert!(layout_of(1, 3).is_some());
+        assert!(Arc::ptr_eq(&layout_of(1, 4).unwrap(), &last));
+        // The hidden lines are moved with the lines
+        let hidden = lines.hidden_lines.borrow().clone().unwrap();
+        assert_eq!(
+            (hidden.cache_rev, &*hidden.lines, hidden.moved),
+            (1, &[3..4][..], true)
+        );
+
+        // The layouts which aren't for the previous revision are cleared
+        lines.apply_edits([&inval], 0, 2);
+        assert!(layout_of(2, 0).is_none());
+    }
+
+    #[test]
+    fn invalidate_line() {
+        let text = Rope::from("aaaa\nbb bb cc\ncc dddd eeee ff\nff gggg");
+        let (_, lines) = make_lines(&text, 2., true);
+        let config_id = ConfigId::new(0, 0);
+        lines.invalidate_line(1);
+
+        assert!(lines.try_get_text_layout(0, config_id, 0).is_some());
+        assert!(lines.try_get_text_layout(0, config_id, 1).is_none());
+        assert!(lines.try_get_text_layout(0, config_id, 2).is_some());
+    }
+
     #[test]
     fn equivalence() {
         // Extra tests that the visual lines you get when initting are equivalent to the ones you
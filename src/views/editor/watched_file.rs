@@ -0,0 +1,395 @@
+//! A [`TextDocument`] backed by a file, which notices when the file is changed by another
+//! program.
+//!
+//! When the file changes while the document has unsaved edits, the [`DiskState`] of the
+//! [`WatchedFile`] becomes a conflict, which an application resolves like the usual "file
+//! changed on disk" dialog: by reloading the file, by keeping the edits, or by merging both
+//! with [`WatchedFile::merge`].
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    rc::Rc,
+    time::{Duration, SystemTime},
+};
+
+use floem_reactive::{RwSignal, Scope, SignalGet, SignalUpdate, SignalWith};
+
+use crate::action::exec_after;
+
+use super::{encoding::FileEncoding, text::Document, text_document::TextDocument};
+
+/// How the file of a [`WatchedFile`] compares to the text it was loaded with or last saved.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DiskState {
+    /// The file wasn't changed by another program
+    InSync,
+    /// The file was changed by another program. If the document has unsaved edits, this is a
+    /// conflict.
+    Changed { conflict: bool },
+    /// The file was deleted or can't be read anymore
+    Deleted,
+}
+
+/// The result of a three-way merge, see [`merge3`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Merged {
+    pub text: String,
+    /// The number of conflicts marked in the text
+    pub conflicts: usize,
+}
+
+/// A [`TextDocument`] loaded from a file, which can check whether the file was changed.
+#[derive(Clone)]
+pub struct WatchedFile {
+    doc: Rc<TextDocument>,
+    path: Rc<PathBuf>,
+    /// The text of the file when it was loaded or saved, the base of merges
+    base: RwSignal<String>,
+    /// The text of the file when it was changed by another program
+    disk: RwSignal<Option<String>>,
+    modified: RwSignal<Option<SystemTime>>,
+    state: RwSignal<DiskState>,
+    encoding: RwSignal<FileEncoding>,
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|meta| meta.modified()).ok()
+}
+
+impl WatchedFile {
+    /// Loads the file at `path` into a new document, finding its encoding with
+    /// [`FileEncoding::detect`].
+    pub fn open(cx: Scope, path: impl Into<PathBuf>) -> io::Result<Self> {
+        let path = path.into();
+        let (text, encoding) = FileEncoding::detect(&fs::read(&path)?, None);
+        let doc = Rc::new(TextDocument::new(cx, text.as_str()));
+        let file = Self::new(cx, doc, path, text);
+        file.encoding.set(encoding);
+        Ok(file)
+    }
+
+    /// Watches the file at `path` for `doc`, whose text was loaded from `text`, the content of
+    /// the file in UTF-8.
+    pub fn new(cx: Scope, doc: Rc<TextDocument>, path: impl Into<PathBuf>, text: String) -> Self {
+        let path = path.into();
+        Self {
+            doc,
+            modified: cx.create_rw_signal(modified_time(&path)),
+            path: Rc::new(path),
+            base: cx.create_rw_signal(text),
+            disk: cx.create_rw_signal(None),
+            state: cx.create_rw_signal(DiskState::InSync),
+            encoding: cx.create_rw_signal(FileEncoding::default()),
+        }
+    }
+
+    /// The encoding the file is read and saved with.
+    pub fn encoding(&self) -> FileEncoding {
+        self.encoding.get()
+    }
+
+    /// Sets the encoding the file is saved with, to convert it to another encoding.
+    pub fn set_encoding(&self, encoding: FileEncoding) {
+        self.encoding.set(encoding);
+    }
+
+    /// Reads the file again with `encoding`, for files whose encoding was guessed wrong. The
+    /// unsaved edits are dropped.
+    pub fn reopen_with_encoding(&self, encoding: FileEncoding) -> io::Result<()> {
+        let text = encoding.decode(&fs::read(&*self.path)?);
+        self.encoding.set(encoding);
+        self.doc.reload(text.as_str(), true);
+        self.base.set(text);
+        self.disk.set(None);
+        self.update_state();
+        Ok(())
+    }
+
+    fn read(&self) -> Option<String> {
+        let bytes = fs::read(&*self.path).ok()?;
+        Some(self.encoding.get_untracked().decode(&bytes))
+    }
+
+    pub fn doc(&self) -> Rc<TextDocument> {
+        self.doc.clone()
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// How the file compares to the document. This is tracked, so that a dialog can be shown
+    /// when it changes.
+    pub fn state(&self) -> DiskState {
+        self.state.get()
+    }
+
+    /// Checks the file every `interval` while the scope of the file is alive.
+    ///
+    /// The file is only read when its modification time changes. When it changes while the
+    /// document has no unsaved edits and `auto_reload` is true, the document is reloaded
+    /// right away instead of reporting the change.
+    pub fn watch(&self, interval: Duration, auto_reload: bool) {
+        let file = self.clone();
+        exec_after(interval, move |_| {
+            if file.state.try_get_untracked().is_none() {
+                return;
+            }
+            file.check();
+            if auto_reload && file.state.get_untracked() == (DiskState::Changed { conflict: false })
+            {
+                file.reload();
+            }
+            file.watch(interval, auto_reload);
+        });
+    }
+
+    /// Checks whether the file was changed since it was loaded or saved, updating the
+    /// [state](WatchedFile::state).
+    pub fn check(&self) {
+        let modified = modified_time(&self.path);
+        if modified.is_some() && modified == self.modified.get_untracked() {
+            // The document could have been saved or edited since the last check
+            self.update_state();
+            return;
+        }
+        self.modified.set(modified);
+        // Touching the file without changing it isn't a change
+        let disk = self
+            .read()
+            .filter(|disk| self.base.with_untracked(|base| base != disk));
+        self.disk.set(disk);
+        self.update_state();
+    }
+
+    fn update_state(&self) {
+        let state = if modified_time(&self.path).is_none() {
+            DiskState::Deleted
+        } else if self.disk.with_untracked(Option::is_some) {
+            DiskState::Changed {
+                conflict: !self.doc.is_pristine(),
+            }
+        } else {
+            DiskState::InSync
+        };
+        if self.state.get_untracked() != state {
+            self.state.set(state);
+        }
+    }
+
+    /// Replaces the text of the document with the text of the file, dropping the unsaved
+    /// edits. The reload can be undone.
+    pub fn reload(&self) {
+        let disk = self.disk.get_untracked().or_else(|| self.read());
+        let Some(disk) = disk else {
+            return;
+        };
+        self.doc.reload(disk.as_str(), true);
+        self.base.set(disk);
+        self.disk.set(None);
+        self.update_state();
+    }
+
+    /// Keeps the text of the document, which overwrites the changes of the file when it's
+    /// saved. The document is considered edited.
+    pub fn keep_mine(&self) {
+        if let Some(disk) = self.disk.get_untracked() {
+            self.base.set(disk);
+        }
+        self.disk.set(None);
+        self.update_state();
+    }
+
+    /// Merges the edits of the document and the changes of the file, since the text the file
+    /// was loaded with, see [`merge3`]. The document isn't changed, the merged text can be
+    /// loaded into it with [`TextDocument::reload`] before calling [`WatchedFile::keep_mine`].
+    pub fn merge(&self) -> Option<Merged> {
+        let disk = self.disk.get_untracked()?;
+        let mine = self.doc.text().to_string();
+        Some(self.base.with_untracked(|base| merge3(base, &mine, &disk)))
+    }
+
+    /// Writes the text of the document to the file, in the [encoding](WatchedFile::encoding)
+    /// of the file.
+    pub fn save(&self) -> io::Result<()> {
+        let text = self.doc.text().to_string();
+        fs::write(&*self.path, self.encoding.get_untracked().encode(&text))?;
+        self.doc.set_pristine();
+        self.base.set(text);
+        self.disk.set(None);
+        self.modified.set(modified_time(&self.path));
+        self.update_state();
+        Ok(())
+    }
+}
+
+/// The lines of `text`, with their line endings.
+fn lines(text: &str) -> Vec<&str> {
+    text.split_inclusive('\n').collect()
+}
+
+/// For each line of `a`, the line of `b` it's matched with by a longest common subsequence of
+/// their lines.
+fn match_lines(a: &[&str], b: &[&str]) -> Vec<Option<usize>> {
+    let mut matches = vec![None; a.len()];
+    // Common lines at the start and the end are matched without the quadratic search
+    let prefix = a.iter().zip(b).take_while(|(a, b)| a == b).count();
+    let suffix = a[prefix..]
+        .iter()
+        .rev()
+        .zip(b[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    for (i, m) in matches.iter_mut().enumerate().take(prefix) {
+        *m = Some(i);
+    }
+    for i in 0..suffix {
+        matches[a.len() - 1 - i] = Some(b.len() - 1 - i);
+    }
+
+    let a_mid = &a[prefix..a.len() - suffix];
+    let b_mid = &b[prefix..b.len() - suffix];
+    // lengths[i][j] is the length of the longest common subsequence of a_mid[i..], b_mid[j..]
+    let mut lengths = vec![vec![0u32; b_mid.len() + 1]; a_mid.len() + 1];
+    for i in (0..a_mid.len()).rev() {
+        for j in (0..b_mid.len()).rev() {
+            lengths[i][j] = if a_mid[i] == b_mid[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+    let (mut i, mut j) = (0, 0);
+    while i < a_mid.len() && j < b_mid.len() {
+        if a_mid[i] == b_mid[j] {
+            matches[prefix + i] = Some(prefix + j);
+            i += 1;
+            j += 1;
+        } else if lengths[i + 1][j] >= lengths[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    matches
+}
+
+/// Merges the changes made to `base` in `mine` and in `theirs`, line by line.
+///
+/// Where both changed the same lines differently, the merged text contains both versions
+/// between conflict markers, like git:
+/// ```text
+/// <<<<<<< mine
+/// the lines of mine
+/// =======
+/// the lines of theirs
+/// >>>>>>> theirs
+/// ```
+pub fn merge3(base: &str, mine: &str, theirs: &str) -> Merged {
+    let (base, mine, theirs) = (lines(base), lines(mine), lines(theirs));
+    let to_mine = match_lines(&base, &mine);
+    let to_theirs = match_lines(&base, &theirs);
+
+    let mut merged = Merged {
+        text: String::new(),
+        conflicts: 0,
+    };
+    let push_lines = |text: &mut String, lines: &[&str]| {
+        for line in lines {
+            text.push_str(line);
+        }
+    };
+    let (mut i, mut m, mut t) = (0, 0, 0);
+    loop {
+        // The next line of the base which is kept by both
+        let stable = (i..base.len()).find_map(|k| Some((k, to_mine[k]?, to_theirs[k]?)));
+        let (k, mk, tk) = stable.unwrap_or((base.len(), mine.len(), theirs.len()));
+        if (k, mk, tk) != (i, m, t) {
+            let (b_chunk, m_chunk, t_chunk) = (&base[i..k], &mine[m..mk], &theirs[t..tk]);
+            if m_chunk == b_chunk || m_chunk == t_chunk {
+                push_lines(&mut merged.text, t_chunk);
+            } else if t_chunk == b_chunk {
+                push_lines(&mut merged.text, m_chunk);
+            } else {
+                merged.conflicts += 1;
+                let text = &mut merged.text;
+                text.push_str("<<<<<<< mine\n");
+                push_lines(text, m_chunk);
+                if !text.ends_with('\n') {
+                    text.push('\n');
+                }
+                text.push_str("=======\n");
+                push_lines(text, t_chunk);
+                if !text.ends_with('\n') {
+                    text.push('\n');
+                }
+                text.push_str(">>>>>>> theirs\n");
+            }
+        }
+        if stable.is_none() {
+            break;
+        }
+        merged.text.push_str(base[k]);
+        (i, m, t) = (k + 1, mk + 1, tk + 1);
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use floem_reactive::Scope;
+
+    use super::{merge3, DiskState, WatchedFile};
+    use crate::views::editor::text::Document;
+
+    #[test]
+    fn merge() {
+        let base = "a\nb\nc\nd\n";
+        let merged = merge3(base, "a\nB\nc\nd\n", "a\nb\nc\nD\n");
+        assert_eq!(merged.text, "a\nB\nc\nD\n");
+        assert_eq!(merged.conflicts, 0);
+
+        let merged = merge3(base, "a\nmine\nc\nd\n", "a\ntheirs\nc\nd\ne\n");
+        assert_eq!(
+            merged.text,
+            "a\n<<<<<<< mine\nmine\n=======\ntheirs\n>>>>>>> theirs\nc\nd\ne\n"
+        );
+        assert_eq!(merged.conflicts, 1);
+
+        // The same change on both sides isn't a conflict
+        let merged = merge3(base, "a\nc\nd\n", "a\nc\nd\n");
+        assert_eq!(merged.text, "a\nc\nd\n");
+        assert_eq!(merged.conflicts, 0);
+    }
+
+    #[test]
+    fn conflict() {
+        let path = std::env::temp_dir().join(format!("floem-watched-{}.txt", std::process::id()));
+        fs::write(&path, "one\ntwo\n").unwrap();
+        let cx = Scope::new();
+        let file = WatchedFile::open(cx, &path).unwrap();
+        file.check();
+        assert_eq!(file.state(), DiskState::InSync);
+
+        let doc = file.doc();
+        doc.reload("one\n2\n", false);
+        fs::write(&path, "1\ntwo\n").unwrap();
+        // The modification time may not have changed on coarse file systems
+        file.modified.set(None);
+        file.check();
+        assert_eq!(file.state(), DiskState::Changed { conflict: true });
+        assert_eq!(file.merge().unwrap().text, "1\n2\n");
+
+        file.reload();
+        assert_eq!(file.state(), DiskState::InSync);
+        assert_eq!(doc.text().to_string(), "1\ntwo\n");
+
+        fs::remove_file(&path).unwrap();
+        file.check();
+        assert_eq!(file.state(), DiskState::Deleted);
+        cx.dispose();
+    }
+}
@@ -0,0 +1,636 @@
+//! A text input built on the [`Editor`], so it edits text like the code editor does.
+use std::{
+    cell::{Cell, RefCell},
+    ops::Range,
+    rc::Rc,
+};
+
+use floem_editor_core::{
+    command::EditCommand,
+    cursor::Cursor,
+    editor::EditType,
+    mode::{Mode, MotionMode},
+    register::{Clipboard, Register},
+    selection::Selection,
+};
+use floem_reactive::{
+    create_effect, create_updater, with_scope, RwSignal, Scope, SignalGet, SignalUpdate, SignalWith,
+};
+use lapce_xi_rope::{DeltaElement, Rope, RopeDelta};
+
+use crate::{
+    action::ImePurpose,
+    id::ViewId,
+    keyboard::Modifiers,
+    style::Style,
+    style_class,
+    view::{IntoView, View},
+    views::editor::{
+        actions::{handle_command_default, CommonAction},
+        anchor::{Anchor, Bias},
+        command::{Command, CommandExecuted},
+        id::EditorId,
+        keypress::default_key_handler,
+        phantom_text::PhantomTextLine,
+        text::{Document, DocumentPhantom, PreeditData, SimpleStyling, Styling, SystemClipboard},
+        text_document::TextDocument,
+        view::{editor_content, EditorViewClass},
+        CurrentLineColor, Editor, EditorStyle, ScrollBeyondLastLine, WrapProp,
+    },
+};
+
+use super::{
+    editor::text::WrapMethod,
+    scroll::{HideBars, ScrollClass},
+    Decorators, EditorCustomStyle,
+};
+
+style_class!(
+    /// The style class of [`editor_input`] views.
+    pub EditorInputClass
+);
+
+/// The character shown in place of each character of a secure input.
+const MASK: char = '\u{2022}';
+
+/// The text of a secure input, whose document only holds a mask character per character.
+#[derive(Default)]
+struct SecureText {
+    enabled: Cell<bool>,
+    text: RefCell<String>,
+    /// The text being inserted into the document, as mask characters
+    pending: RefCell<Option<String>>,
+}
+
+impl SecureText {
+    /// Applies an edit of the masked document to the text.
+    fn apply(&self, delta: &RopeDelta) {
+        let chars: Vec<char> = self.text.borrow().chars().collect();
+        let pending = self.pending.borrow();
+        let mut text = String::new();
+        for el in &delta.els {
+            match el {
+                DeltaElement::Copy(start, end) => {
+                    let mask_len = MASK.len_utf8();
+                    text.extend(&chars[start / mask_len..end / mask_len]);
+                }
+                DeltaElement::Insert(node) => {
+                    let inserted = node.slice_to_cow(..);
+                    match pending.as_deref() {
+                        Some(pending) if pending.chars().count() == inserted.chars().count() => {
+                            text.push_str(pending)
+                        }
+                        _ => text.push_str(&inserted),
+                    }
+                }
+            }
+        }
+        *self.text.borrow_mut() = text;
+    }
+}
+
+fn mask(text: &str) -> String {
+    text.chars().map(|_| MASK).collect()
+}
+
+/// The document of an [`EditorInput`].
+///
+/// This wraps a [`TextDocument`], and limits the text which can be typed or pasted into it
+/// to a single line and a maximum length. A secure document shows a mask character in place
+/// of each character, and doesn't allow copying the text or undoing edits.
+pub struct InputDocument {
+    doc: TextDocument,
+    multiline: RwSignal<bool>,
+    max_length: Cell<Option<usize>>,
+    secure: Rc<SecureText>,
+}
+
+impl InputDocument {
+    pub fn new(cx: Scope, text: impl Into<String>) -> InputDocument {
+        let text = text.into();
+        let doc = TextDocument::new(cx, text.as_str());
+        let secure = Rc::new(SecureText::default());
+        {
+            let secure = secure.clone();
+            doc.add_on_update(move |update| {
+                if secure.enabled.get() {
+                    for delta in update.deltas() {
+                        secure.apply(delta);
+                    }
+                }
+            });
+        }
+
+        InputDocument {
+            doc,
+            multiline: cx.create_rw_signal(false),
+            max_length: Cell::new(None),
+            secure,
+        }
+    }
+
+    pub fn text_document(&self) -> &TextDocument {
+        &self.doc
+    }
+
+    /// The text of the input, which for a secure input isn't the text of the document.
+    pub fn value(&self) -> String {
+        if self.secure.enabled.get() {
+            self.secure.text.borrow().clone()
+        } else {
+            self.doc.text().to_string()
+        }
+    }
+
+    /// Replaces the text of the input.
+    pub fn set_value(&self, value: &str) {
+        let selection = Selection::region(0, self.doc.text().len());
+        if self.secure.enabled.get() {
+            *self.secure.pending.borrow_mut() = Some(value.to_string());
+            self.doc
+                .edit_single(selection, &mask(value), EditType::Other);
+            *self.secure.pending.borrow_mut() = None;
+        } else {
+            self.doc.edit_single(selection, value, EditType::Other);
+        }
+    }
+
+    pub fn is_multiline(&self) -> bool {
+        self.multiline.get_untracked()
+    }
+
+    /// Sets whether new lines can be inserted. Otherwise new lines in pasted text are turned
+    /// into spaces.
+    pub fn set_multiline(&self, multiline: bool) {
+        self.multiline.set(multiline);
+    }
+
+    pub fn max_length(&self) -> Option<usize> {
+        self.max_length.get()
+    }
+
+    /// Sets the maximum number of characters which can be typed or pasted into the input.
+    pub fn set_max_length(&self, max_length: Option<usize>) {
+        self.max_length.set(max_length);
+    }
+
+    pub fn is_secure(&self) -> bool {
+        self.secure.enabled.get()
+    }
+
+    /// Sets whether the text is hidden. A secure input is always a single line.
+    pub fn set_secure(&self, secure: bool) {
+        if self.secure.enabled.get() == secure {
+            return;
+        }
+        let value = self.value();
+        *self.secure.text.borrow_mut() = value.clone();
+        self.secure.enabled.set(secure);
+        self.set_value(&value);
+    }
+
+    fn single_line(&self) -> bool {
+        !self.multiline.get_untracked() || self.secure.enabled.get()
+    }
+
+    /// Limits text inserted at the selections of `cursor` to a single line and the maximum
+    /// length.
+    fn filter(&self, cursor: &Cursor, text: &str) -> String {
+        let mut text = if self.single_line() {
+            text.replace("\r\n", " ").replace(['\r', '\n'], " ")
+        } else {
+            text.to_string()
+        };
+        if let Some(max_length) = self.max_length.get() {
+            let rope = self.doc.text();
+            let char_count =
+                |start: usize, end: usize| rope.slice_to_cow(start..end).chars().count();
+            let regions: Vec<(usize, usize)> = cursor.regions_iter().collect();
+            let selected: usize = regions
+                .iter()
+                .map(|&(start, end)| char_count(start.min(end), start.max(end)))
+                .sum();
+            let length = char_count(0, rope.len()) - selected;
+            let allowed = max_length.saturating_sub(length) / regions.len().max(1);
+            if let Some((idx, _)) = text.char_indices().nth(allowed) {
+                text.truncate(idx);
+            }
+        }
+        text
+    }
+
+    fn insert(&self, ed: &Editor, cursor: &mut Cursor, text: &str) -> bool {
+        let text = self.filter(cursor, text);
+        if text.is_empty() {
+            return false;
+        }
+        if self.secure.enabled.get() {
+            *self.secure.pending.borrow_mut() = Some(text.clone());
+            self.doc.insert(ed, cursor, &mask(&text));
+            *self.secure.pending.borrow_mut() = None;
+        } else {
+            self.doc.insert(ed, cursor, &text);
+        }
+        true
+    }
+}
+
+impl Document for InputDocument {
+    fn text(&self) -> Rope {
+        self.doc.text()
+    }
+
+    fn cache_rev(&self) -> RwSignal<u64> {
+        self.doc.cache_rev()
+    }
+
+    fn preedit(&self) -> PreeditData {
+        self.doc.preedit()
+    }
+
+    fn run_command(
+        &self,
+        ed: &Editor,
+        cmd: &Command,
+        count: Option<usize>,
+        modifiers: Modifiers,
+    ) -> CommandExecuted {
+        handle_command_default(ed, self, cmd, count, modifiers)
+    }
+
+    fn receive_char(&self, ed: &Editor, c: &str) {
+        if ed.read_only.get_untracked() {
+            return;
+        }
+
+        let mode = ed.cursor.with_untracked(|c| c.get_mode());
+        if mode == Mode::Insert {
+            let mut cursor = ed.cursor.get_untracked();
+            self.insert(ed, &mut cursor, c);
+            ed.cursor.set(cursor);
+        }
+    }
+
+    fn edit(&self, iter: &mut dyn Iterator<Item = (Selection, &str)>, edit_type: EditType) {
+        self.doc.edit(iter, edit_type)
+    }
+
+    fn create_anchor(&self, offset: usize, bias: Bias) -> Option<Anchor> {
+        self.doc.create_anchor(offset, bias)
+    }
+}
+
+impl DocumentPhantom for InputDocument {
+    fn phantom_text(&self, edid: EditorId, styling: &EditorStyle, line: usize) -> PhantomTextLine {
+        self.doc.phantom_text(edid, styling, line)
+    }
+
+    fn has_multiline_phantom(&self, edid: EditorId, styling: &EditorStyle) -> bool {
+        self.doc.has_multiline_phantom(edid, styling)
+    }
+}
+
+impl CommonAction for InputDocument {
+    fn exec_motion_mode(
+        &self,
+        ed: &Editor,
+        cursor: &mut Cursor,
+        motion_mode: MotionMode,
+        range: Range<usize>,
+        is_vertical: bool,
+        register: &mut Register,
+    ) {
+        self.doc
+            .exec_motion_mode(ed, cursor, motion_mode, range, is_vertical, register)
+    }
+
+    fn do_edit(
+        &self,
+        ed: &Editor,
+        cursor: &mut Cursor,
+        cmd: &EditCommand,
+        modal: bool,
+        register: &mut Register,
+        smart_tab: bool,
+    ) -> bool {
+        match cmd {
+            EditCommand::InsertNewLine
+            | EditCommand::NewLineAbove
+            | EditCommand::NewLineBelow
+            | EditCommand::InsertTab
+                if self.single_line() =>
+            {
+                false
+            }
+            // The text of a secure input is never put on the clipboard, and undoing would bring
+            // back the mask characters without the text they stand for.
+            EditCommand::ClipboardCopy
+            | EditCommand::ClipboardCut
+            | EditCommand::Yank
+            | EditCommand::Undo
+            | EditCommand::Redo
+                if self.secure.enabled.get() =>
+            {
+                false
+            }
+            EditCommand::ClipboardPaste => {
+                if ed.read_only.get_untracked() {
+                    return false;
+                }
+                let Some(text) = SystemClipboard::new().get_string() else {
+                    return false;
+                };
+                self.insert(ed, cursor, &text)
+            }
+            _ => self
+                .doc
+                .do_edit(ed, cursor, cmd, modal, register, smart_tab),
+        }
+    }
+}
+
+/// A text input view, editing the text like the [`text_editor`](super::text_editor) does.
+pub struct EditorInput {
+    id: ViewId,
+    editor: Editor,
+    doc: Rc<InputDocument>,
+}
+
+/// Creates a text input editing the text of `buffer`.
+///
+/// The input is a single line by default, see [`EditorInput::multiline`].
+/// ```rust
+/// use floem::{reactive::RwSignal, views::editor_input};
+///
+/// let password = RwSignal::new(String::new());
+/// editor_input(password)
+///     .placeholder("Password")
+///     .max_length(64)
+///     .secure(true);
+/// ```
+pub fn editor_input(buffer: RwSignal<String>) -> EditorInput {
+    let id = ViewId::new();
+    let cx = Scope::current();
+
+    let doc = Rc::new(InputDocument::new(cx, buffer.get_untracked()));
+    let style = Rc::new(SimpleStyling::new());
+    let editor = Editor::new(cx, doc.clone(), style, false);
+    let editor_sig = cx.create_rw_signal(editor.clone());
+
+    // Keep the buffer and the document in sync, the document is only replaced when the buffer
+    // is changed from outside the input.
+    {
+        let weak_doc = Rc::downgrade(&doc);
+        doc.text_document().add_on_update(move |_| {
+            if let Some(doc) = weak_doc.upgrade() {
+                let value = doc.value();
+                if buffer.with_untracked(|buffer| buffer != &value) {
+                    buffer.set(value);
+                }
+            }
+        });
+    }
+    {
+        let doc = doc.clone();
+        let cursor = editor.cursor;
+        create_effect(move |_| {
+            let value = buffer.get();
+            if doc.value() != value {
+                doc.set_value(&value);
+                let offset = doc.text().len();
+                cursor.update(|cursor| cursor.set_offset(offset, false, false));
+            }
+        });
+    }
+
+    // The input only takes the keyboard and shows its caret while it's focused
+    let focused = cx.create_rw_signal(false);
+    let focus_gained = editor.editor_view_focused;
+    let focus_lost = editor.editor_view_focus_lost;
+    let ime_allowed = editor.ime_allowed;
+    create_effect(move |ran: Option<()>| {
+        focus_gained.track();
+        if ran.is_some() {
+            focused.set(true);
+        }
+    });
+    create_effect(move |ran: Option<()>| {
+        focus_lost.track();
+        if ran.is_some() {
+            focused.set(false);
+            ime_allowed.set(false);
+        }
+    });
+
+    let multiline = doc.multiline;
+    let child = with_scope(cx, || {
+        editor_content(
+            editor_sig,
+            move |_| focused.get(),
+            default_key_handler(editor_sig),
+        )
+        .style(move |s| {
+            let line_height = editor_sig.with_untracked(|ed| ed.line_height(0)) as f64;
+            s.width_full()
+                .apply_if(!multiline.get(), |s| s.height(line_height))
+                .apply_if(multiline.get(), |s| s.height_full())
+        })
+        .on_cleanup(move || {
+            editor_sig.get_untracked().cx.get().dispose();
+        })
+    })
+    .into_view();
+    id.set_children(vec![child]);
+
+    EditorInput { id, editor, doc }
+        .style(move |s| {
+            s.class(EditorViewClass, |s| {
+                s.set(
+                    WrapProp,
+                    if multiline.get() {
+                        WrapMethod::EditorWidth
+                    } else {
+                        WrapMethod::None
+                    },
+                )
+                .set(ScrollBeyondLastLine, false)
+                .set(CurrentLineColor, None)
+            })
+            .apply_if(!multiline.get(), |s| {
+                s.class(ScrollClass, |s| s.set(HideBars, true))
+            })
+        })
+        .class(EditorInputClass)
+}
+
+impl View for EditorInput {
+    fn id(&self) -> ViewId {
+        self.id
+    }
+
+    fn view_style(&self) -> Option<Style> {
+        Some(Style::new().min_width(25))
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "Editor Input".into()
+    }
+}
+
+impl EditorInput {
+    /// Sets whether the input edits multiple lines, which wrap to its width.
+    ///
+    /// Default: `false`
+    pub fn multiline(self, multiline: bool) -> Self {
+        self.doc.set_multiline(multiline);
+        self
+    }
+
+    /// Set the placeholder text that is displayed when the input is empty.
+    pub fn placeholder(self, text: impl Into<String>) -> Self {
+        self.doc
+            .text_document()
+            .add_placeholder(self.editor.id(), text.into());
+        self
+    }
+
+    /// Sets the maximum number of characters which can be typed or pasted into the input.
+    pub fn max_length(self, max_length: usize) -> Self {
+        self.doc.set_max_length(Some(max_length));
+        self
+    }
+
+    /// Sets whether the text is hidden, for passwords.
+    ///
+    /// A secure input is a single line and its text can't be copied.
+    pub fn secure(self, secure: bool) -> Self {
+        self.doc.set_secure(secure);
+        self.editor.ime_purpose.set(if secure {
+            ImePurpose::Password
+        } else {
+            ImePurpose::Normal
+        });
+        self
+    }
+
+    /// Sets the kind of text typed into the input, for the IME and the virtual keyboards.
+    ///
+    /// Default: [`ImePurpose::Normal`], or [`ImePurpose::Password`] for a secure input
+    pub fn ime_purpose(self, purpose: ImePurpose) -> Self {
+        self.editor.ime_purpose.set(purpose);
+        self
+    }
+
+    /// Set the input to read only.
+    pub fn read_only(self) -> Self {
+        self.editor.read_only.set(true);
+        self
+    }
+
+    /// Change the [`Styling`] used for the text.
+    pub fn styling(self, styling: impl Styling + 'static) -> Self {
+        self.editor.update_styling(Rc::new(styling));
+        self
+    }
+
+    /// Sets the custom style properties of the editor of the input.
+    pub fn editor_style(
+        self,
+        style: impl Fn(EditorCustomStyle) -> EditorCustomStyle + 'static,
+    ) -> Self {
+        let id = self.id();
+        let view_state = id.state();
+        let offset = view_state.borrow_mut().style.next_offset();
+        let style = create_updater(
+            move || style(EditorCustomStyle(Style::new())),
+            move |style| id.update_style(offset, style.0),
+        );
+        view_state.borrow_mut().style.push(style.0);
+        self
+    }
+
+    pub fn editor(&self) -> &Editor {
+        &self.editor
+    }
+
+    pub fn doc(&self) -> Rc<InputDocument> {
+        self.doc.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use floem_editor_core::command::EditCommand;
+    use floem_reactive::Scope;
+
+    use crate::{
+        keyboard::Modifiers,
+        views::editor::{
+            command::Command,
+            text::{Document, SimpleStyling},
+            Editor,
+        },
+    };
+
+    use super::InputDocument;
+
+    fn make_ed(text: &str) -> (Rc<InputDocument>, Editor) {
+        let cx = Scope::new();
+        let doc = Rc::new(InputDocument::new(cx, text));
+        let style = Rc::new(SimpleStyling::new());
+        let editor = Editor::new(cx, doc.clone(), style, false);
+        (doc, editor)
+    }
+
+    #[test]
+    fn single_line() {
+        let (doc, ed) = make_ed("");
+        ed.receive_char("one\ntwo");
+        assert_eq!(doc.value(), "one two");
+
+        ed.doc().run_command(
+            &ed,
+            &Command::Edit(EditCommand::InsertNewLine),
+            None,
+            Modifiers::empty(),
+        );
+        assert_eq!(doc.value(), "one two");
+
+        doc.set_multiline(true);
+        ed.receive_char("\nthree");
+        assert_eq!(doc.value(), "one two\nthree");
+    }
+
+    #[test]
+    fn max_length() {
+        let (doc, ed) = make_ed("");
+        doc.set_max_length(Some(5));
+        ed.receive_char("abc");
+        ed.receive_char("défgh");
+        assert_eq!(doc.value(), "abcdé");
+        ed.receive_char("i");
+        assert_eq!(doc.value(), "abcdé");
+    }
+
+    #[test]
+    fn secure() {
+        let (doc, ed) = make_ed("");
+        doc.set_secure(true);
+        ed.receive_char("pässword");
+        assert_eq!(doc.value(), "pässword");
+        assert_eq!(doc.text().to_string(), "\u{2022}".repeat(8));
+
+        ed.doc().run_command(
+            &ed,
+            &Command::Edit(EditCommand::DeleteBackward),
+            None,
+            Modifiers::empty(),
+        );
+        assert_eq!(doc.value(), "pässwor");
+
+        doc.set_secure(false);
+        assert_eq!(doc.text().to_string(), "pässwor");
+    }
+}
@@ -153,15 +153,30 @@ pub struct Img {
 /// });
 /// ```
 pub fn img(image: impl Fn() -> Vec<u8> + 'static) -> Img {
-    let image = image::load_from_memory(&image()).ok();
-    let width = image.as_ref().map_or(0, |img| img.width());
-    let height = image.as_ref().map_or(0, |img| img.height());
-    let data = Arc::new(image.map_or(Default::default(), |img| img.into_rgba8().into_vec()));
-    let blob = Blob::new(data);
-    let image = peniko::Image::new(blob, peniko::ImageFormat::Rgba8, width, height);
+    let image = decode_image(&image()).unwrap_or_else(|| {
+        peniko::Image::new(
+            Blob::new(Arc::new(Vec::new())),
+            peniko::ImageFormat::Rgba8,
+            0,
+            0,
+        )
+    });
     img_dynamic(move || image.clone())
 }
 
+/// Decodes an encoded image, like the content of a PNG file, or `None` when it can't be decoded.
+pub(crate) fn decode_image(data: &[u8]) -> Option<peniko::Image> {
+    let image = image::load_from_memory(data).ok()?;
+    let (width, height) = (image.width(), image.height());
+    let blob = Blob::new(Arc::new(image.into_rgba8().into_vec()));
+    Some(peniko::Image::new(
+        blob,
+        peniko::ImageFormat::Rgba8,
+        width,
+        height,
+    ))
+}
+
 /// A view that can display an image and controls its position.
 ///
 /// It takes function that returns [`PathBuf`] and will convert it into [`Image`](peniko::Image).
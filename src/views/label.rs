@@ -7,8 +7,9 @@ use crate::{
     keyboard::KeyEvent,
     prop_extractor,
     style::{
-        CursorColor, CustomStylable, FontProps, LineHeight, Selectable, SelectionCornerRadius,
-        SelectionStyle, Style, TextColor, TextOverflow, TextOverflowProp,
+        CursorColor, CustomStylable, FontProps, FontSizeRange, LineHeight, Selectable,
+        SelectionCornerRadius, SelectionStyle, Style, TextAutoShrink, TextColor, TextOverflow,
+        TextOverflowProp,
     },
     style_class,
     text::{Attrs, AttrsList, FamilyOwned, TextLayout},
@@ -32,6 +33,7 @@ prop_extractor! {
     Extractor {
         color: TextColor,
         text_overflow: TextOverflowProp,
+        text_auto_shrink: TextAutoShrink,
         line_height: LineHeight,
         text_selectable: Selectable,
     }
@@ -61,6 +63,9 @@ pub struct Label {
     available_text: Option<String>,
     available_width: Option<f32>,
     available_text_layout: Option<TextLayout>,
+    /// The text shrunk with `text_auto_shrink` to fit the available width, with the available
+    /// width and the font size
+    shrunk_text_layout: Option<(f32, f32, TextLayout)>,
     text_overflow_listener: Option<TextOverflowListener>,
     selection_state: SelectionState,
     selection_range: Option<(Cursor, Cursor)>,
@@ -79,6 +84,7 @@ impl Label {
             available_text: None,
             available_width: None,
             available_text_layout: None,
+            shrunk_text_layout: None,
             text_overflow_listener: None,
             selection_state: SelectionState::None,
             selection_range: None,
@@ -92,6 +98,10 @@ impl Label {
     fn effectve_text_layout(&self) -> &TextLayout {
         self.available_text_layout
             .as_ref()
+            .or(self
+                .shrunk_text_layout
+                .as_ref()
+                .map(|(_, _, layout)| layout))
             .unwrap_or_else(|| self.text_layout.as_ref().unwrap())
     }
 }
@@ -146,9 +156,27 @@ impl Label {
         self
     }
 
+    /// The font size of the text before it's shrunk to fit.
+    fn natural_font_size(&self) -> Option<f32> {
+        self.style
+            .text_auto_shrink()
+            .map(|range| range.max)
+            .or(self.font.size())
+    }
+
+    /// The attributes of the text shown, which is shrunk to fit with `text_auto_shrink`.
     fn get_attrs_list(&self) -> AttrsList {
+        let font_size = self
+            .shrunk_text_layout
+            .as_ref()
+            .map(|(_, font_size, _)| *font_size)
+            .or(self.natural_font_size());
+        self.attrs_list(font_size)
+    }
+
+    fn attrs_list(&self, font_size: Option<f32>) -> AttrsList {
         let mut attrs = Attrs::new().color(self.style.color().unwrap_or(palette::css::BLACK));
-        if let Some(font_size) = self.font.size() {
+        if let Some(font_size) = font_size {
             attrs = attrs.font_size(font_size);
         }
         if let Some(font_style) = self.font.style() {
@@ -172,17 +200,36 @@ impl Label {
 
     fn set_text_layout(&mut self) {
         let mut text_layout = TextLayout::new();
-        let attrs_list = self.get_attrs_list();
-        text_layout.set_text(self.label.as_str(), attrs_list.clone());
+        text_layout.set_text(
+            self.label.as_str(),
+            self.attrs_list(self.natural_font_size()),
+        );
         self.text_layout = Some(text_layout);
 
         if let Some(new_text) = self.available_text.as_ref() {
             let mut text_layout = TextLayout::new();
-            text_layout.set_text(new_text, attrs_list);
+            text_layout.set_text(new_text, self.get_attrs_list());
             self.available_text_layout = Some(text_layout);
         }
     }
 
+    /// The largest font size of `range` at which the text fits in `available_width`, in steps
+    /// of half a pixel, with the text laid out at that size.
+    fn shrink_to_fit(&self, range: FontSizeRange, available_width: f32) -> (f32, TextLayout) {
+        let mut font_size = range.max;
+        let mut text_layout = self.text_layout.clone().unwrap();
+        let mut width = text_layout.size().width as f32;
+        while width > available_width && font_size > range.min {
+            // The width of the text is about proportional to the font size
+            let fitting = (font_size * available_width / width * 2.0).floor() / 2.0;
+            font_size = fitting.min(font_size - 0.5).max(range.min);
+            text_layout = TextLayout::new();
+            text_layout.set_text(self.label.as_str(), self.attrs_list(Some(font_size)));
+            width = text_layout.size().width as f32;
+        }
+        (font_size, text_layout)
+    }
+
     fn get_hit_point(&self, point: Point) -> Option<Cursor> {
         let text_node = self.text_node?;
         let location = self
@@ -296,6 +343,11 @@ impl View for Label {
         format!("Label: {:?}", self.label).into()
     }
 
+    fn accessibility(&self, node: &mut accesskit::Node) {
+        node.set_role(accesskit::Role::Label);
+        node.set_label(self.label.clone());
+    }
+
     fn update(&mut self, _cx: &mut UpdateCx, state: Box<dyn Any>) {
         if let Ok(state) = state.downcast() {
             self.label = *state;
@@ -303,6 +355,7 @@ impl View for Label {
             self.available_text = None;
             self.available_width = None;
             self.available_text_layout = None;
+            self.shrunk_text_layout = None;
             self.id.request_layout();
         }
     }
@@ -364,6 +417,7 @@ impl View for Label {
             self.available_text = None;
             self.available_width = None;
             self.available_text_layout = None;
+            self.shrunk_text_layout = None;
             self.id.request_layout();
         }
         if self.selection_style.read(cx) {
@@ -432,9 +486,30 @@ impl View for Label {
             let text_overflow = style.text_overflow();
             (text_overflow, padding_left + padding_right)
         };
-        let text_layout = self.text_layout.as_ref().unwrap();
-        let width = text_layout.size().width as f32;
         let available_width = layout.size.width - padding;
+        let natural_width = self.text_layout.as_ref().unwrap().size().width as f32;
+        match self.style.text_auto_shrink() {
+            Some(range) if natural_width > available_width => {
+                let shrunk_width = self.shrunk_text_layout.as_ref().map(|(width, ..)| *width);
+                if shrunk_width != Some(available_width) {
+                    let (font_size, text_layout) = self.shrink_to_fit(range, available_width);
+                    self.shrunk_text_layout = Some((available_width, font_size, text_layout));
+                    // The overflow of the shrunk text is computed again
+                    self.available_width = None;
+                }
+            }
+            _ => {
+                if self.shrunk_text_layout.take().is_some() {
+                    self.available_width = None;
+                }
+            }
+        }
+        let text_layout = self
+            .shrunk_text_layout
+            .as_ref()
+            .map(|(_, _, layout)| layout)
+            .unwrap_or_else(|| self.text_layout.as_ref().unwrap());
+        let width = text_layout.size().width as f32;
         if text_overflow == TextOverflow::Ellipsis {
             if width > available_width {
                 if self.available_width != Some(available_width) {
@@ -0,0 +1,123 @@
+//! Views whose content is only created once it's shown, like the content of tabs which
+//! aren't active yet.
+use std::any::Any;
+
+use floem_reactive::{as_child_of_current_scope, Scope};
+use peniko::kurbo::Rect;
+
+use crate::{
+    context::{ComputeLayoutCx, UpdateCx},
+    id::ViewId,
+    view::{default_compute_layout, AnyView, IntoView, View},
+};
+
+type ViewFn = Box<dyn Fn(()) -> (AnyView, Scope)>;
+
+enum LazyMessage {
+    Shown,
+    Hidden,
+}
+
+/// A view creating its content when it's first shown, see [`lazy`].
+pub struct Lazy {
+    id: ViewId,
+    view_fn: ViewFn,
+    child: Option<(ViewId, Scope)>,
+    keep_alive: bool,
+    /// Whether a message to create or remove the child was sent and not handled yet
+    pending: bool,
+}
+
+/// Creates the view returned by `view_fn` the first time it's shown, rather than when the
+/// `lazy` view is created.
+///
+/// This avoids building the content of tabs, collapsed sections and other views which may
+/// never be shown. A view is shown when neither it nor its ancestors are hidden, like the
+/// inactive children of a [`tab`](crate::views::tab).
+///
+/// Once created, the content is kept alive while it's hidden, so that its state, like scroll
+/// positions or the cursor of an editor, is still there when it's shown again. Use
+/// [`Lazy::keep_alive`] to drop hidden content instead.
+/// ```rust
+/// use floem::{reactive::{RwSignal, SignalGet}, views::{label, lazy, tab}};
+///
+/// let active = RwSignal::new(0);
+/// tab(
+///     move || active.get(),
+///     move || vec!["Settings", "Logs"],
+///     |name| *name,
+///     // The logs are only loaded when their tab is shown, and dropped when it's hidden
+///     |name| lazy(move || label(move || format!("{name} content"))).keep_alive(name != "Logs"),
+/// );
+/// ```
+pub fn lazy<V: IntoView + 'static>(view_fn: impl Fn() -> V + 'static) -> Lazy {
+    Lazy {
+        id: ViewId::new(),
+        view_fn: Box::new(as_child_of_current_scope(move |_| view_fn().into_any())),
+        child: None,
+        keep_alive: true,
+        pending: false,
+    }
+}
+
+impl Lazy {
+    /// Sets whether the content is kept alive while it's hidden, `true` by default.
+    ///
+    /// When `false`, the content is dropped, along with its signals and effects, when the view
+    /// is hidden, and created again when it's shown.
+    pub fn keep_alive(mut self, keep_alive: bool) -> Self {
+        self.keep_alive = keep_alive;
+        self
+    }
+}
+
+impl View for Lazy {
+    fn id(&self) -> ViewId {
+        self.id
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "Lazy".into()
+    }
+
+    fn update(&mut self, cx: &mut UpdateCx, state: Box<dyn Any>) {
+        let Ok(message) = state.downcast::<LazyMessage>() else {
+            return;
+        };
+        self.pending = false;
+        match *message {
+            LazyMessage::Shown if self.child.is_none() => {
+                let (child, scope) = (self.view_fn)(());
+                self.child = Some((child.id(), scope));
+                self.id.set_children(vec![child]);
+                self.id.request_all();
+            }
+            LazyMessage::Hidden if !self.keep_alive => {
+                if let Some((child, scope)) = self.child.take() {
+                    self.id.set_children(Vec::<AnyView>::new());
+                    cx.app_state_mut().remove_view(child);
+                    scope.dispose();
+                    self.id.request_all();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn compute_layout(&mut self, cx: &mut ComputeLayoutCx) -> Option<Rect> {
+        // The children of views like tabs are hidden without being skipped by the layout
+        let hidden = self.id.is_hidden_recursive();
+        let message = match (hidden, self.child.is_some()) {
+            (false, false) => Some(LazyMessage::Shown),
+            (true, true) if !self.keep_alive => Some(LazyMessage::Hidden),
+            _ => None,
+        };
+        if let Some(message) = message {
+            if !self.pending {
+                self.pending = true;
+                self.id.update_state(message);
+            }
+        }
+        default_compute_layout(self.id, cx)
+    }
+}
@@ -1,4 +1,7 @@
+use std::cell::RefCell;
+
 use super::{v_stack_from_iter, Decorators};
+use crate::accessibility::view_text;
 use crate::context::StyleCx;
 use crate::event::EventPropagation;
 use crate::id::ViewId;
@@ -8,7 +11,8 @@ use crate::style_class;
 use crate::view::IntoView;
 use crate::{
     event::{Event, EventListener},
-    keyboard::{Key, NamedKey},
+    keyboard::{Key, Modifiers, NamedKey},
+    nav::Typeahead,
     view::View,
 };
 use floem_reactive::{create_rw_signal, RwSignal, SignalGet, SignalTrack, SignalUpdate};
@@ -58,7 +62,7 @@ impl List {
 
 /// A list of views built from an iterator which remains static and always contains the same elements in the same order.
 ///
-/// A list is like a [stack](super::stack()) but also has built-in support for the selection of items: up and down using arrow keys, top and bottom control using the home and end keys, and for the "acceptance" of an item using the Enter key.
+/// A list is like a [stack](super::stack()) but also has built-in support for the selection of items: up and down using arrow keys, top and bottom control using the home and end keys, typing the start of an item's text, and for the "acceptance" of an item using the Enter key.
 ///
 /// ## Example
 /// ```rust
@@ -101,6 +105,7 @@ where
     .style(|s| s.width_full().height_full());
     let length = stack.id().children().len();
     let child = stack.id();
+    let typeahead = RefCell::new(Typeahead::default());
     list_id.set_children(vec![stack]);
     List {
         id: list_id,
@@ -166,6 +171,22 @@ where
                     }
                     EventPropagation::Stop
                 }
+                Key::Character(ref text)
+                    if !key_event
+                        .modifiers
+                        .intersects(Modifiers::CONTROL | Modifiers::ALT | Modifiers::META) =>
+                {
+                    let items: Vec<String> = child.children().into_iter().map(view_text).collect();
+                    let found =
+                        typeahead
+                            .borrow_mut()
+                            .search(text, selection.get_untracked(), &items);
+                    if let Some(index) = found {
+                        selection.set(Some(index));
+                        list_id.update_state(ListUpdate::ScrollToSelected);
+                    }
+                    EventPropagation::Stop
+                }
                 _ => EventPropagation::Continue,
             }
         } else {
@@ -180,6 +201,10 @@ impl View for List {
         self.id
     }
 
+    fn accessibility(&self, node: &mut accesskit::Node) {
+        node.set_role(accesskit::Role::ListBox);
+    }
+
     fn update(&mut self, _cx: &mut crate::context::UpdateCx, state: Box<dyn std::any::Any>) {
         if let Ok(change) = state.downcast::<ListUpdate>() {
             match *change {
@@ -214,6 +239,12 @@ impl View for Item {
         "Item".into()
     }
 
+    fn accessibility(&self, node: &mut accesskit::Node) {
+        node.set_role(accesskit::Role::ListBoxOption);
+        node.set_selected(self.selection.get_untracked() == Some(self.index));
+        node.add_action(accesskit::Action::Click);
+    }
+
     fn style_pass(&mut self, cx: &mut StyleCx<'_>) {
         let selected = self.selection.get_untracked();
         if Some(self.index) == selected {
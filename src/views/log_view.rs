@@ -0,0 +1,479 @@
+//! A view showing a log which grows while it's shown, like the output of a process.
+//!
+//! The lines are kept in a [`LogBuffer`], which can be filled from a channel, and shown by a
+//! [`log_view`] which only creates views for the visible lines, so that logs of millions of
+//! lines stay fast.
+use std::{cell::RefCell, ops::Range, rc::Rc, sync::mpsc::Receiver, time::Duration};
+
+use floem_reactive::{RwSignal, SignalGet, SignalUpdate, SignalWith};
+use floem_renderer::text::{Attrs, AttrsList, FamilyOwned, LineHeightValue, TextLayout, Weight};
+use peniko::{color::palette, kurbo::Point, Color};
+
+use crate::{
+    action::exec_after,
+    event::{Event, EventListener},
+    ext_event::{on_channel, Backpressure},
+    style_class,
+    view::IntoView,
+    views::{rich_text, scroll, virtual_stack, Decorators, Scroll},
+};
+
+style_class!(
+    /// Style class of a [`log_view`]
+    pub LogViewClass
+);
+
+/// The height of a line of a [`log_view`].
+const LINE_HEIGHT: f64 = 18.0;
+
+/// The level of a log line, found in its text.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum LogLevel {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl LogLevel {
+    const ALL: [LogLevel; 5] = [
+        LogLevel::Trace,
+        LogLevel::Debug,
+        LogLevel::Info,
+        LogLevel::Warn,
+        LogLevel::Error,
+    ];
+
+    /// The level of the first level name found as a word of `text`, like `WARN` or `error`.
+    pub fn find(text: &str) -> Option<LogLevel> {
+        text.split(|c: char| !c.is_ascii_alphabetic())
+            .find_map(|word| match word.to_ascii_uppercase().as_str() {
+                "TRACE" => Some(LogLevel::Trace),
+                "DEBUG" => Some(LogLevel::Debug),
+                "INFO" => Some(LogLevel::Info),
+                "WARN" | "WARNING" => Some(LogLevel::Warn),
+                "ERROR" => Some(LogLevel::Error),
+                _ => None,
+            })
+    }
+
+    /// The color of lines of this level which have no color of their own.
+    fn color(self) -> Color {
+        match self {
+            LogLevel::Trace | LogLevel::Debug => palette::css::GRAY,
+            LogLevel::Info => palette::css::BLACK,
+            LogLevel::Warn => Color::from_rgb8(175, 105, 0),
+            LogLevel::Error => Color::from_rgb8(205, 49, 49),
+        }
+    }
+}
+
+/// The style of a part of a line, set by ANSI escape codes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct AnsiStyle {
+    color: Option<Color>,
+    bold: bool,
+}
+
+const ANSI_COLORS: [Color; 16] = [
+    Color::from_rgb8(0, 0, 0),
+    Color::from_rgb8(205, 49, 49),
+    Color::from_rgb8(0, 135, 0),
+    Color::from_rgb8(175, 135, 0),
+    Color::from_rgb8(4, 81, 165),
+    Color::from_rgb8(188, 5, 188),
+    Color::from_rgb8(5, 152, 188),
+    Color::from_rgb8(85, 85, 85),
+    Color::from_rgb8(102, 102, 102),
+    Color::from_rgb8(241, 76, 76),
+    Color::from_rgb8(22, 198, 12),
+    Color::from_rgb8(181, 186, 0),
+    Color::from_rgb8(59, 142, 234),
+    Color::from_rgb8(214, 112, 214),
+    Color::from_rgb8(41, 184, 219),
+    Color::from_rgb8(128, 128, 128),
+];
+
+/// The color `n` of the 256 colors palette of terminals.
+fn ansi_256(n: u8) -> Color {
+    match n {
+        0..=15 => ANSI_COLORS[n as usize],
+        16..=231 => {
+            let level = |v: u8| if v == 0 { 0 } else { 55 + v * 40 };
+            let n = n - 16;
+            Color::from_rgb8(level(n / 36), level(n / 6 % 6), level(n % 6))
+        }
+        _ => {
+            let gray = 8 + (n - 232) * 10;
+            Color::from_rgb8(gray, gray, gray)
+        }
+    }
+}
+
+/// Applies the parameters of an SGR escape code to `style`.
+fn apply_sgr(style: &mut AnsiStyle, params: &str) {
+    let mut params = params.split(';').map(|p| p.parse::<u8>().unwrap_or(0));
+    while let Some(param) = params.next() {
+        match param {
+            0 => *style = AnsiStyle::default(),
+            1 => style.bold = true,
+            22 => style.bold = false,
+            30..=37 => style.color = Some(ANSI_COLORS[(param - 30) as usize]),
+            90..=97 => style.color = Some(ANSI_COLORS[(param - 90 + 8) as usize]),
+            39 => style.color = None,
+            38 => match params.next() {
+                Some(5) => style.color = params.next().map(ansi_256),
+                Some(2) => {
+                    let (r, g, b) = (params.next(), params.next(), params.next());
+                    style.color = Some(Color::from_rgb8(
+                        r.unwrap_or(0),
+                        g.unwrap_or(0),
+                        b.unwrap_or(0),
+                    ));
+                }
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+}
+
+/// Removes the ANSI escape codes of `line`, returning the text and the styled ranges of it.
+fn parse_ansi(line: &str) -> (String, Vec<(Range<usize>, AnsiStyle)>) {
+    let mut text = String::with_capacity(line.len());
+    let mut spans = Vec::new();
+    let mut style = AnsiStyle::default();
+    let mut span_start = 0;
+    let mut rest = line;
+    while let Some(escape) = rest.find('\x1b') {
+        text.push_str(&rest[..escape]);
+        rest = &rest[escape + 1..];
+        let Some(params) = rest.strip_prefix('[') else {
+            continue;
+        };
+        // A control sequence ends at its first letter, only `m` changes the style
+        let Some(end) = params.find(|c: char| c.is_ascii_alphabetic()) else {
+            rest = "";
+            break;
+        };
+        if params[end..].starts_with('m') {
+            if style != AnsiStyle::default() && span_start < text.len() {
+                spans.push((span_start..text.len(), style));
+            }
+            span_start = text.len();
+            apply_sgr(&mut style, &params[..end]);
+        }
+        rest = &params[end + 1..];
+    }
+    text.push_str(rest);
+    if style != AnsiStyle::default() && span_start < text.len() {
+        spans.push((span_start..text.len(), style));
+    }
+    (text, spans)
+}
+
+/// A line of a [`LogBuffer`], without its ANSI escape codes.
+#[derive(Clone, Debug)]
+pub struct LogLine {
+    id: u64,
+    text: Rc<str>,
+    level: Option<LogLevel>,
+    spans: Rc<[(Range<usize>, AnsiStyle)]>,
+}
+
+impl LogLine {
+    fn new(id: u64, line: &str) -> Self {
+        let (text, spans) = parse_ansi(line);
+        LogLine {
+            id,
+            level: LogLevel::find(&text),
+            text: text.into(),
+            spans: spans.into(),
+        }
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn level(&self) -> Option<LogLevel> {
+        self.level
+    }
+
+    fn text_layout(&self) -> TextLayout {
+        let family = [FamilyOwned::Monospace];
+        let color = self
+            .level
+            .map(LogLevel::color)
+            .unwrap_or(palette::css::BLACK);
+        let attrs = Attrs::new()
+            .family(&family)
+            .color(color)
+            .font_size(13.0)
+            .line_height(LineHeightValue::Px(LINE_HEIGHT as f32));
+        let mut attrs_list = AttrsList::new(attrs);
+        for (range, style) in self.spans.iter() {
+            let mut attrs = attrs;
+            if let Some(color) = style.color {
+                attrs = attrs.color(color);
+            }
+            if style.bold {
+                attrs = attrs.weight(Weight::BOLD);
+            }
+            attrs_list.add_span(range.clone(), attrs);
+        }
+        let mut layout = TextLayout::new();
+        layout.set_text(&self.text, attrs_list);
+        layout
+    }
+}
+
+/// The lines of a [`log_view`], with the filters and the scrolling of the view.
+#[derive(Clone, Copy)]
+pub struct LogBuffer {
+    lines: RwSignal<im::Vector<LogLine>>,
+    next_id: RwSignal<u64>,
+    max_lines: RwSignal<usize>,
+    /// Lines received from a channel, which are added together
+    pending: RwSignal<Option<Rc<RefCell<Vec<String>>>>>,
+    /// Whether the view stays scrolled to the last line as lines are added. It's turned off
+    /// when the view is scrolled up, and on again when it's scrolled to the end.
+    pub follow_tail: RwSignal<bool>,
+    /// The levels of the lines which are hidden. Lines without a level are always shown.
+    pub hidden_levels: RwSignal<Vec<LogLevel>>,
+    /// Only the lines containing this text, ignoring case, are shown when it isn't empty
+    pub search: RwSignal<String>,
+}
+
+impl Default for LogBuffer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LogBuffer {
+    /// An empty log which keeps its last 100 000 lines.
+    pub fn new() -> Self {
+        Self {
+            lines: RwSignal::new(im::Vector::new()),
+            next_id: RwSignal::new(0),
+            max_lines: RwSignal::new(100_000),
+            pending: RwSignal::new(None),
+            follow_tail: RwSignal::new(true),
+            hidden_levels: RwSignal::new(Vec::new()),
+            search: RwSignal::new(String::new()),
+        }
+    }
+
+    /// A log with the lines received from `rx`.
+    ///
+    /// The lines received while the UI thread is busy are added at once, so that a fast
+    /// producer doesn't update the view for every line.
+    pub fn from_channel(rx: Receiver<String>) -> Self {
+        let log = Self::new();
+        log.append_channel(rx);
+        log
+    }
+
+    /// Appends the lines received from `rx`, see [`LogBuffer::from_channel`].
+    pub fn append_channel(&self, rx: Receiver<String>) {
+        let log = *self;
+        on_channel(rx, Backpressure::Unbounded, move |line| {
+            if let Some(pending) = log.pending.get_untracked() {
+                pending.borrow_mut().push(line);
+                return;
+            }
+            let pending = Rc::new(RefCell::new(vec![line]));
+            log.pending.set(Some(pending.clone()));
+            exec_after(Duration::ZERO, move |_| {
+                log.pending.set(None);
+                log.extend(pending.take());
+            });
+        });
+    }
+
+    /// Sets how many lines are kept, the oldest lines are dropped past it.
+    pub fn set_max_lines(&self, max_lines: usize) {
+        self.max_lines.set(max_lines);
+        self.truncate();
+    }
+
+    fn truncate(&self) {
+        let max_lines = self.max_lines.get_untracked();
+        if self.lines.with_untracked(|lines| lines.len() > max_lines) {
+            self.lines.update(|lines| {
+                let excess = lines.len() - max_lines;
+                *lines = lines.split_off(excess);
+            });
+        }
+    }
+
+    pub fn push(&self, line: &str) {
+        self.extend([line]);
+    }
+
+    /// Appends `lines`, each of them can contain ANSI escape codes.
+    pub fn extend<S: AsRef<str>>(&self, lines: impl IntoIterator<Item = S>) {
+        let mut id = self.next_id.get_untracked();
+        self.lines.update(|log_lines| {
+            for line in lines {
+                log_lines.push_back(LogLine::new(id, line.as_ref()));
+                id += 1;
+            }
+        });
+        self.next_id.set(id);
+        self.truncate();
+    }
+
+    pub fn clear(&self) {
+        self.lines.set(im::Vector::new());
+    }
+
+    /// Shows or hides the lines of `level`.
+    pub fn set_level_shown(&self, level: LogLevel, shown: bool) {
+        self.hidden_levels.update(|hidden| {
+            hidden.retain(|hidden| *hidden != level);
+            if !shown {
+                hidden.push(level);
+            }
+        });
+    }
+
+    /// The number of lines of each level, in the order of [`LogLevel`].
+    pub fn level_counts(&self) -> [(LogLevel, usize); 5] {
+        self.lines.with(|lines| {
+            LogLevel::ALL.map(|level| {
+                let count = lines
+                    .iter()
+                    .filter(|line| line.level == Some(level))
+                    .count();
+                (level, count)
+            })
+        })
+    }
+
+    /// The lines which pass the level filter and the search.
+    pub fn visible_lines(&self) -> im::Vector<LogLine> {
+        let hidden = self.hidden_levels.get();
+        let search = self.search.with(|search| search.to_lowercase());
+        let lines = self.lines.get();
+        if hidden.is_empty() && search.is_empty() {
+            return lines;
+        }
+        lines
+            .into_iter()
+            .filter(|line| line.level.is_none_or(|level| !hidden.contains(&level)))
+            .filter(|line| search.is_empty() || line.text.to_lowercase().contains(&search))
+            .collect()
+    }
+}
+
+/// A view showing the lines of `log`, colored by their ANSI escape codes or their level.
+///
+/// ```rust
+/// use std::sync::mpsc::channel;
+/// use floem::views::{log_view, LogBuffer, LogLevel};
+///
+/// let (tx, rx) = channel();
+/// let log = LogBuffer::from_channel(rx);
+/// log.set_max_lines(10_000);
+/// log.set_level_shown(LogLevel::Trace, false);
+/// std::thread::spawn(move || {
+///     tx.send("\x1b[32mINFO\x1b[0m server started".to_string()).unwrap();
+/// });
+/// log_view(log);
+/// ```
+pub fn log_view(log: LogBuffer) -> Scroll {
+    let content_height = RwSignal::new(0.0);
+    scroll(
+        virtual_stack(
+            move || log.visible_lines(),
+            |line| line.id,
+            |line| {
+                let layout = line.text_layout();
+                rich_text(move || layout.clone()).style(|s| s.height(LINE_HEIGHT))
+            },
+        )
+        .item_size_fixed(|| LINE_HEIGHT)
+        .on_resize(move |rect| {
+            if content_height.get_untracked() != rect.height() {
+                content_height.set(rect.height());
+            }
+        })
+        .style(|s| s.flex_col().min_width_full()),
+    )
+    .scroll_to(move || {
+        log.follow_tail
+            .get()
+            .then(|| Point::new(0.0, content_height.get()))
+    })
+    .on_scroll(move |viewport| {
+        // Scrolling up stops following the tail, scrolling to the end follows it again
+        let at_end = viewport.y1 >= content_height.get_untracked() - 1.0;
+        if log.follow_tail.get_untracked() != at_end {
+            log.follow_tail.set(at_end);
+        }
+    })
+    .on_event_cont(EventListener::PointerWheel, move |event| {
+        if let Event::PointerWheel(wheel) = event {
+            if wheel.delta.y < 0.0 && log.follow_tail.get_untracked() {
+                log.follow_tail.set(false);
+            }
+        }
+    })
+    .class(LogViewClass)
+}
+
+#[cfg(test)]
+mod tests {
+    use floem_reactive::SignalUpdate;
+    use peniko::Color;
+
+    use super::{parse_ansi, AnsiStyle, LogBuffer, LogLevel, ANSI_COLORS};
+
+    #[test]
+    fn ansi() {
+        let (text, spans) = parse_ansi("\x1b[1;31mERROR\x1b[0m: \x1b[38;2;1;2;3mdone\x1b[K");
+        assert_eq!(text, "ERROR: done");
+        assert_eq!(
+            spans,
+            [
+                (
+                    0..5,
+                    AnsiStyle {
+                        color: Some(ANSI_COLORS[1]),
+                        bold: true
+                    }
+                ),
+                (
+                    7..11,
+                    AnsiStyle {
+                        color: Some(Color::from_rgb8(1, 2, 3)),
+                        bold: false
+                    }
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn filters() {
+        let log = LogBuffer::new();
+        log.set_max_lines(3);
+        log.extend(["[INFO] a", "[warn] b", "c", "[ERROR] d"]);
+        let texts = |log: &LogBuffer| {
+            log.visible_lines()
+                .iter()
+                .map(|line| line.text().to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(texts(&log), ["[warn] b", "c", "[ERROR] d"]);
+
+        log.set_level_shown(LogLevel::Warn, false);
+        assert_eq!(texts(&log), ["c", "[ERROR] d"]);
+
+        log.search.set("D".to_string());
+        assert_eq!(texts(&log), ["[ERROR] d"]);
+    }
+}
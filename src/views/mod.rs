@@ -91,6 +91,9 @@ pub use dyn_container::*;
 mod dyn_view;
 pub use dyn_view::*;
 
+mod switch;
+pub use switch::*;
+
 mod value_container;
 pub use value_container::*;
 
@@ -112,15 +115,73 @@ pub use scroll::{scroll, Scroll, ScrollExt};
 mod tab;
 pub use tab::*;
 
+mod lazy;
+pub use lazy::*;
+
 mod tooltip;
 pub use tooltip::*;
 
+mod portal;
+pub use portal::*;
+
+mod command_palette;
+pub use command_palette::*;
+
+mod status_bar;
+pub use status_bar::*;
+
+mod toolbar;
+pub use toolbar::*;
+
+mod segmented;
+pub use segmented::*;
+
+mod rating;
+pub use rating::*;
+
+mod stepper;
+pub use stepper::*;
+
+mod pill_switch;
+pub use pill_switch::*;
+
+mod skeleton;
+pub use skeleton::*;
+
+mod animated_number;
+pub use animated_number::*;
+
+#[cfg(feature = "qr-code")]
+mod qr_code;
+#[cfg(feature = "qr-code")]
+pub use qr_code::*;
+
+mod avatar;
+pub use avatar::*;
+
+mod log_view;
+pub use log_view::*;
+
+mod chat_list;
+pub use chat_list::*;
+
+mod property_grid;
+pub use property_grid::*;
+
+#[cfg(feature = "serde")]
+mod data_inspector;
+#[cfg(feature = "serde")]
+pub use data_inspector::*;
+
 mod stack;
 pub use stack::*;
 
 mod text_input;
 pub use text_input::*;
 
+mod number_input;
+pub use number_input::*;
+
 mod empty;
 pub use empty::*;
 
@@ -130,6 +191,9 @@ pub use drag_window_area::*;
 mod drag_resize_window_area;
 pub use drag_resize_window_area::*;
 
+mod window_controls;
+pub use window_controls::*;
+
 mod img;
 pub use img::*;
 
@@ -144,6 +208,16 @@ pub mod text_editor;
 #[cfg(feature = "editor")]
 pub use text_editor::*;
 
+#[cfg(feature = "editor")]
+mod editor_input;
+#[cfg(feature = "editor")]
+pub use editor_input::*;
+
+#[cfg(feature = "editor")]
+mod document_preview;
+#[cfg(feature = "editor")]
+pub use document_preview::*;
+
 pub mod dropdown;
 
 pub mod slider;
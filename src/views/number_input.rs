@@ -0,0 +1,342 @@
+//! Formatting and parsing of numbers typed into text inputs.
+use floem_reactive::{
+    create_effect, untrack, RwSignal, SignalGet, SignalTrack, SignalUpdate, SignalWith,
+};
+
+use crate::{
+    event::EventListener,
+    sound::{play_sound, SystemSound},
+};
+
+use super::{text_input, Decorators, TextInput};
+
+/// How a number is shown in and read from a text input: its decimal and thousands
+/// separators, number of decimals, and the currency symbol or unit around it.
+///
+/// ```rust
+/// use floem::views::NumberFormat;
+///
+/// let price = NumberFormat::for_locale("de-DE").decimals(2).suffix(" €");
+/// assert_eq!(price.format(1234.5), "1.234,50 €");
+/// assert_eq!(price.parse("1.234,5 €"), Some(1234.5));
+///
+/// let distance = NumberFormat::new().decimals(1).unit("km");
+/// assert_eq!(distance.format(12.34), "12.3 km");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct NumberFormat {
+    decimal_separator: char,
+    group_separator: Option<char>,
+    decimals: Option<usize>,
+    prefix: String,
+    suffix: String,
+    /// Whether the separators are those of the current locale
+    follow_locale: bool,
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        Self {
+            decimal_separator: '.',
+            group_separator: Some(','),
+            decimals: None,
+            prefix: String::new(),
+            suffix: String::new(),
+            follow_locale: false,
+        }
+    }
+}
+
+impl NumberFormat {
+    /// A format with a `.` decimal separator and `,` thousands separator, showing as many
+    /// decimals as needed.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The separators used by a locale, given as a language tag like `"fr"` or `"de-CH"`.
+    ///
+    /// Unknown locales use the separators of [`NumberFormat::new`].
+    pub fn for_locale(locale: &str) -> Self {
+        let mut parts = locale.split(['-', '_']);
+        let language = parts.next().unwrap_or_default().to_ascii_lowercase();
+        let region = parts.next().unwrap_or_default().to_ascii_uppercase();
+        let (decimal_separator, group_separator) = match (language.as_str(), region.as_str()) {
+            ("de" | "it" | "fr", "CH") | (_, "LI") => ('.', '\''),
+            (
+                "de" | "es" | "it" | "nl" | "pt" | "id" | "tr" | "da" | "el" | "ro" | "hr" | "sl"
+                | "sr" | "vi",
+                _,
+            ) => (',', '.'),
+            (
+                "fr" | "ru" | "pl" | "cs" | "sk" | "sv" | "fi" | "nb" | "no" | "uk" | "hu" | "bg"
+                | "lt" | "lv" | "et",
+                _,
+            ) => (',', '\u{a0}'),
+            _ => ('.', ','),
+        };
+        Self {
+            decimal_separator,
+            group_separator: Some(group_separator),
+            ..Self::default()
+        }
+    }
+
+    /// The separators of the current [locale](crate::l10n::locale), following it when the
+    /// language is switched.
+    ///
+    /// Text created with [`create_number_text`] using this format is reformatted when the
+    /// locale changes.
+    pub fn localized() -> Self {
+        Self {
+            follow_locale: true,
+            ..Self::default()
+        }
+    }
+
+    pub fn decimal_separator(mut self, separator: char) -> Self {
+        self.decimal_separator = separator;
+        self.follow_locale = false;
+        self
+    }
+
+    /// Sets the separator between groups of thousands, `None` to not group digits.
+    pub fn group_separator(mut self, separator: Option<char>) -> Self {
+        self.group_separator = separator;
+        self.follow_locale = false;
+        self
+    }
+
+    /// Sets the number of decimals numbers are rounded to when formatted.
+    pub fn decimals(mut self, decimals: usize) -> Self {
+        self.decimals = Some(decimals);
+        self
+    }
+
+    /// Sets the text shown before the number, like a currency symbol.
+    pub fn prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// Sets the text shown after the number.
+    pub fn suffix(mut self, suffix: impl Into<String>) -> Self {
+        self.suffix = suffix.into();
+        self
+    }
+
+    /// Shows a currency symbol before the number, with two decimals.
+    pub fn currency(self, symbol: impl Into<String>) -> Self {
+        self.prefix(symbol).decimals(2)
+    }
+
+    /// Shows a unit after the number, separated by a space.
+    pub fn unit(self, unit: impl AsRef<str>) -> Self {
+        self.suffix(format!(" {}", unit.as_ref()))
+    }
+
+    /// The decimal and group separators, read from the current locale if the format follows it.
+    fn separators(&self) -> (char, Option<char>) {
+        if self.follow_locale {
+            let locale = Self::for_locale(&crate::l10n::locale().get());
+            (locale.decimal_separator, locale.group_separator)
+        } else {
+            (self.decimal_separator, self.group_separator)
+        }
+    }
+
+    pub fn format(&self, value: f64) -> String {
+        let (decimal_separator, group_separator) = self.separators();
+        let digits = match self.decimals {
+            Some(decimals) => format!("{:.*}", decimals, value.abs()),
+            None => value.abs().to_string(),
+        };
+        let (integer, fraction) = digits.split_once('.').unwrap_or((&digits, ""));
+        let negative = value < 0.0 && digits.chars().any(|c| c.is_ascii_digit() && c != '0');
+
+        let mut text = String::new();
+        if negative {
+            text.push('-');
+        }
+        text.push_str(&self.prefix);
+        for (i, c) in integer.chars().enumerate() {
+            if i > 0 && (integer.len() - i) % 3 == 0 {
+                if let Some(separator) = group_separator {
+                    text.push(separator);
+                }
+            }
+            text.push(c);
+        }
+        if !fraction.is_empty() {
+            text.push(decimal_separator);
+            text.push_str(fraction);
+        }
+        text.push_str(&self.suffix);
+        text
+    }
+
+    /// Reads a number typed with this format. The prefix, suffix and thousands separators
+    /// are optional.
+    pub fn parse(&self, text: &str) -> Option<f64> {
+        let (decimal_separator, group_separator) = self.separators();
+        let mut text = text.trim();
+        let suffix = self.suffix.trim();
+        if !suffix.is_empty() {
+            text = text.strip_suffix(suffix).unwrap_or(text);
+        }
+        // The prefix can come after a minus sign
+        let prefix = self.prefix.trim();
+        let text = if prefix.is_empty() {
+            text.to_string()
+        } else {
+            text.replacen(prefix, "", 1)
+        };
+
+        let mut number = String::new();
+        for c in text.chars() {
+            match c {
+                '0'..='9' => number.push(c),
+                c if c == decimal_separator => number.push('.'),
+                '-' | '\u{2212}' if number.is_empty() => number.push('-'),
+                '+' if number.is_empty() => {}
+                c if Some(c) == group_separator || c.is_whitespace() => {}
+                _ => return None,
+            }
+        }
+        if !number.chars().any(|c| c.is_ascii_digit()) {
+            return None;
+        }
+        number.parse().ok()
+    }
+}
+
+/// Creates the text of a number formatted with `format`, to be edited by a text input.
+///
+/// The text is updated when the number changes, and the number is updated when the text is
+/// edited into a different number, while text which can't be parsed leaves the number as is.
+/// ```rust
+/// use floem::{reactive::RwSignal, views::{create_number_text, editor_input, NumberFormat}};
+///
+/// let width = RwSignal::new(120.0);
+/// editor_input(create_number_text(width, NumberFormat::new().unit("px")));
+/// ```
+pub fn create_number_text(value: RwSignal<f64>, format: NumberFormat) -> RwSignal<String> {
+    let text = RwSignal::new(format.format(value.get_untracked()));
+
+    {
+        let format = format.clone();
+        create_effect(move |_| {
+            let value = value.get();
+            if text.with_untracked(|text| format.parse(text)) != Some(value) {
+                text.set(format.format(value));
+            }
+        });
+    }
+    create_effect(move |_| {
+        text.track();
+        // Only edits of the text are tracked, not the locale of a localized format: the effect
+        // above reformats the text for a new locale
+        untrack(|| {
+            let Some(parsed) = text.with_untracked(|text| format.parse(text)) else {
+                return;
+            };
+            let current = value.get_untracked();
+            // Text rounded to the shown decimals doesn't change the number
+            if parsed != current && format.format(parsed) != format.format(current) {
+                value.set(parsed);
+            }
+        });
+    });
+
+    text
+}
+
+/// A text input editing a number, shown with `format`.
+///
+/// The text is formatted again when the input loses focus, text which isn't a number is
+/// replaced by the value with an [error sound](SystemSound::Error).
+pub fn number_input(value: RwSignal<f64>, format: NumberFormat) -> TextInput {
+    let text = create_number_text(value, format.clone());
+    text_input(text).on_event_cont(EventListener::FocusLost, move |_| {
+        if text.with_untracked(|text| format.parse(text)).is_none() {
+            play_sound(SystemSound::Error);
+        }
+        text.set(format.format(value.get_untracked()));
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use floem_reactive::{with_scope, RwSignal, Scope, SignalGet, SignalUpdate};
+
+    use super::{create_number_text, NumberFormat};
+
+    #[test]
+    fn format() {
+        let format = NumberFormat::new();
+        assert_eq!(format.format(1234567.25), "1,234,567.25");
+        assert_eq!(format.format(-12.0), "-12");
+        assert_eq!(format.format(-0.001), "-0.001");
+        assert_eq!(format.clone().decimals(1).format(-0.01), "0.0");
+        assert_eq!(format.currency("$").format(-1234.5), "-$1,234.50");
+
+        let format = NumberFormat::for_locale("fr_FR").decimals(2);
+        assert_eq!(format.format(1234.5), "1\u{a0}234,50");
+        let format = NumberFormat::for_locale("de-CH").group_separator(None);
+        assert_eq!(format.format(1234.5), "1234.5");
+    }
+
+    #[test]
+    fn parse() {
+        let format = NumberFormat::for_locale("de").currency("€");
+        assert_eq!(format.parse("€1.234,5"), Some(1234.5));
+        assert_eq!(format.parse(" -€ 1234 "), Some(-1234.0));
+        assert_eq!(format.parse("1234,"), Some(1234.0));
+        assert_eq!(format.parse("12a"), None);
+        assert_eq!(format.parse("-"), None);
+
+        let format = NumberFormat::new().unit("kg");
+        assert_eq!(format.parse("2.5 kg"), Some(2.5));
+        assert_eq!(format.parse("2.5kg"), Some(2.5));
+        assert_eq!(format.parse("+3"), Some(3.0));
+    }
+
+    #[test]
+    fn number_text_sync() {
+        let cx = Scope::new();
+        with_scope(cx, || {
+            let value = RwSignal::new(1.0);
+            let text = create_number_text(value, NumberFormat::new().decimals(2));
+            assert_eq!(text.get(), "1.00");
+
+            text.set("1,500.5".to_string());
+            assert_eq!(value.get(), 1500.5);
+            // The text isn't reformatted while it's edited
+            assert_eq!(text.get(), "1,500.5");
+
+            text.set("oops".to_string());
+            assert_eq!(value.get(), 1500.5);
+
+            value.set(0.126);
+            assert_eq!(text.get(), "0.13");
+            assert_eq!(value.get(), 0.126);
+        });
+        cx.dispose();
+    }
+
+    #[test]
+    fn localized_number_text() {
+        let cx = Scope::new();
+        with_scope(cx, || {
+            crate::l10n::set_locale("en-US");
+            let value = RwSignal::new(1500.5);
+            let text = create_number_text(value, NumberFormat::localized());
+            assert_eq!(text.get(), "1,500.5");
+
+            crate::l10n::set_locale("de-DE");
+            assert_eq!(text.get(), "1.500,5");
+            assert_eq!(value.get(), 1500.5);
+        });
+        cx.dispose();
+    }
+}
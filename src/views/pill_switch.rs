@@ -0,0 +1,56 @@
+//! A switch showing the labels of both of its states, with the current one highlighted.
+use accesskit::Role;
+use floem_reactive::{RwSignal, SignalGet, SignalUpdate};
+
+use crate::{
+    style_class,
+    views::{h_stack, label, Decorators, Stack},
+};
+
+style_class!(
+    /// Style class of a pill switch
+    pub PillSwitchClass
+);
+
+style_class!(
+    /// Style class of the two labels of a pill switch
+    pub PillSwitchOptionClass
+);
+
+style_class!(
+    /// Style class of the label of the current state of a pill switch
+    pub PillSwitchActiveClass
+);
+
+/// Renders a pill-shaped switch showing an `off` and an `on` label side by side, with the
+/// label of the current state of `on` highlighted. Clicking the switch, or pressing
+/// <kbd>Space</kbd> while it's focused, toggles it.
+/// ```rust
+/// use floem::{reactive::RwSignal, views::pill_switch};
+///
+/// let dark_mode = RwSignal::new(false);
+/// pill_switch(dark_mode, "Light", "Dark");
+/// ```
+pub fn pill_switch(
+    on: RwSignal<bool>,
+    off_label: impl Into<String>,
+    on_label: impl Into<String>,
+) -> Stack {
+    let off_label: String = off_label.into();
+    let on_label: String = on_label.into();
+    let accessible_label = format!("{off_label} / {on_label}");
+    h_stack((
+        label(move || off_label.clone())
+            .class(PillSwitchOptionClass)
+            .class_if(move || !on.get(), PillSwitchActiveClass),
+        label(move || on_label.clone())
+            .class(PillSwitchOptionClass)
+            .class_if(move || on.get(), PillSwitchActiveClass),
+    ))
+    .class(PillSwitchClass)
+    .keyboard_navigable()
+    .accessibility_role(Role::Switch)
+    .accessibility_label(move || accessible_label.clone())
+    .accessibility_toggled(move || on.get())
+    .on_click_stop(move |_| on.update(|on| *on = !*on))
+}
@@ -0,0 +1,79 @@
+//! A view showing its child in an overlay of the window, above the other views.
+use std::{cell::Cell, rc::Rc};
+
+use floem_reactive::{RwSignal, SignalGet, SignalUpdate};
+use peniko::kurbo::{Point, Rect};
+
+use crate::{
+    action::{add_overlay, remove_overlay},
+    context::ComputeLayoutCx,
+    id::ViewId,
+    view::{default_compute_layout, AnyView, IntoView, View},
+    views::{container, Decorators},
+};
+
+/// A view showing its child in an overlay of the window, see [`portal`].
+pub struct Portal {
+    id: ViewId,
+    /// The child, until it's moved to the overlay
+    child: Option<AnyView>,
+    overlay: Rc<Cell<Option<ViewId>>>,
+    /// Where the portal is in the window, which is where the child is shown
+    origin: RwSignal<Point>,
+}
+
+/// Shows `child` in an overlay of the window, at the position of the `portal` view, so that
+/// it's drawn above the other views and isn't clipped by its ancestors, like the clipping of
+/// a [`scroll`](crate::views::scroll) view.
+///
+/// The child is created where `portal` is called, so its signals and effects belong to the
+/// enclosing scope as usual, while it's shown by the overlay. The overlay is removed with the
+/// `portal` view. The `portal` view itself takes no space.
+/// ```rust
+/// use floem::views::{label, portal, scroll, Decorators};
+///
+/// scroll(portal(label(|| "Not clipped by the scroll view".to_string())))
+///     .style(|s| s.height(10.0));
+/// ```
+pub fn portal<V: IntoView + 'static>(child: V) -> Portal {
+    let overlay = Rc::new(Cell::new(None));
+    Portal {
+        id: ViewId::new(),
+        child: Some(child.into_any()),
+        overlay: overlay.clone(),
+        origin: RwSignal::new(Point::ZERO),
+    }
+    .on_cleanup(move || {
+        if let Some(overlay) = overlay.take() {
+            remove_overlay(overlay);
+        }
+    })
+}
+
+impl View for Portal {
+    fn id(&self) -> ViewId {
+        self.id
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "Portal".into()
+    }
+
+    fn compute_layout(&mut self, cx: &mut ComputeLayoutCx) -> Option<Rect> {
+        if self.origin.get_untracked() != cx.window_origin {
+            self.origin.set(cx.window_origin);
+        }
+        // The overlay is added once the portal is in a window
+        if let Some(child) = self.child.take() {
+            let origin = self.origin;
+            let overlay = add_overlay(Point::ZERO, move |_| {
+                container(child).style(move |s| {
+                    let origin = origin.get();
+                    s.margin_left(origin.x).margin_top(origin.y)
+                })
+            });
+            self.overlay.set(Some(overlay));
+        }
+        default_compute_layout(self.id, cx)
+    }
+}
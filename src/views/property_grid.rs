@@ -0,0 +1,367 @@
+//! A grid of labeled editors for a set of properties, for settings dialogs and inspectors.
+use std::{fmt::Display, rc::Rc};
+
+use floem_reactive::{
+    create_effect, untrack, RwSignal, SignalGet, SignalTrack, SignalUpdate, SignalWith,
+};
+use peniko::Color;
+
+use crate::{
+    style_class,
+    view::{AnyView, IntoView},
+    views::{
+        dropdown::Dropdown, empty, label, number_input, scroll, text_input, v_stack_from_iter,
+        Checkbox, Decorators, NumberFormat, Stack, StackExt,
+    },
+};
+
+style_class!(
+    /// Style class of a [`property_grid`]
+    pub PropertyGridClass
+);
+
+style_class!(
+    /// Style class of the category headers of a [`property_grid`]
+    pub PropertyCategoryClass
+);
+
+style_class!(
+    /// Style class of the rows of a [`property_grid`]
+    pub PropertyRowClass
+);
+
+style_class!(
+    /// Style class of the buttons resetting a property of a [`property_grid`] to its default
+    pub PropertyResetClass
+);
+
+/// The category of properties which weren't given one.
+const DEFAULT_CATEGORY: &str = "General";
+
+/// Parses colors written as `#rgb`, `#rrggbb` or `#rrggbbaa`, the `#` being optional.
+fn parse_hex_color(text: &str) -> Option<Color> {
+    let hex = text.trim().trim_start_matches('#');
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let byte = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    match hex.len() {
+        3 => {
+            let digit = |i: usize| u8::from_str_radix(&hex[i..i + 1], 16).ok().map(|d| d * 17);
+            Some(Color::from_rgb8(digit(0)?, digit(1)?, digit(2)?))
+        }
+        6 => Some(Color::from_rgb8(byte(0)?, byte(2)?, byte(4)?)),
+        8 => Some(Color::from_rgba8(byte(0)?, byte(2)?, byte(4)?, byte(6)?)),
+        _ => None,
+    }
+}
+
+/// Formats `color` as `#rrggbb`, or `#rrggbbaa` when it's translucent.
+fn hex_color(color: Color) -> String {
+    let rgba = color.to_rgba8();
+    if rgba.a == 255 {
+        format!("#{:02x}{:02x}{:02x}", rgba.r, rgba.g, rgba.b)
+    } else {
+        format!("#{:02x}{:02x}{:02x}{:02x}", rgba.r, rgba.g, rgba.b, rgba.a)
+    }
+}
+
+type EditorFn = Rc<dyn Fn() -> AnyView>;
+
+/// A property edited by a [`property_grid`], bound to a signal.
+///
+/// The default value of a property, which the reset button of its row restores, is the value
+/// of its signal when the property is created.
+pub struct Property {
+    label: String,
+    category: String,
+    description: Option<String>,
+    editor: EditorFn,
+    is_default: Rc<dyn Fn() -> bool>,
+    reset: Rc<dyn Fn()>,
+}
+
+impl Property {
+    fn new<T: Clone + PartialEq + 'static>(
+        label: impl Into<String>,
+        value: RwSignal<T>,
+        editor: impl Fn() -> AnyView + 'static,
+    ) -> Self {
+        let default = value.get_untracked();
+        let reset_default = default.clone();
+        Self {
+            label: label.into(),
+            category: DEFAULT_CATEGORY.to_string(),
+            description: None,
+            editor: Rc::new(editor),
+            is_default: Rc::new(move || value.with(|value| *value == default)),
+            reset: Rc::new(move || value.set(reset_default.clone())),
+        }
+    }
+
+    /// A property edited by a text input.
+    pub fn text(label: impl Into<String>, value: RwSignal<String>) -> Self {
+        Self::new(label, value, move || {
+            text_input(value).style(|s| s.width_full()).into_any()
+        })
+    }
+
+    /// A property edited by a [`number_input`], showing the number with `format`.
+    pub fn number(label: impl Into<String>, value: RwSignal<f64>, format: NumberFormat) -> Self {
+        Self::new(label, value, move || {
+            number_input(value, format.clone())
+                .style(|s| s.width_full())
+                .into_any()
+        })
+    }
+
+    /// A property edited by a checkbox.
+    pub fn bool(label: impl Into<String>, value: RwSignal<bool>) -> Self {
+        Self::new(label, value, move || Checkbox::new_rw(value).into_any())
+    }
+
+    /// A property choosing one of `options` in a dropdown.
+    pub fn choice<T: Clone + PartialEq + Display + 'static>(
+        label: impl Into<String>,
+        value: RwSignal<T>,
+        options: impl IntoIterator<Item = T>,
+    ) -> Self {
+        let options: Vec<T> = options.into_iter().collect();
+        Self::new(label, value, move || {
+            Dropdown::new_rw(value, options.clone())
+                .style(|s| s.width_full())
+                .into_any()
+        })
+    }
+
+    /// A property edited as the hexadecimal text of a color, like `#1e90ff`, next to a swatch
+    /// of the color.
+    pub fn color(label: impl Into<String>, value: RwSignal<Color>) -> Self {
+        Self::new(label, value, move || {
+            let text = RwSignal::new(hex_color(value.get_untracked()));
+            create_effect(move |_| {
+                let color = value.get();
+                if text.with_untracked(|text| parse_hex_color(text)) != Some(color) {
+                    text.set(hex_color(color));
+                }
+            });
+            create_effect(move |_| {
+                text.track();
+                untrack(|| {
+                    let parsed = text.with_untracked(|text| parse_hex_color(text));
+                    if let Some(color) = parsed.filter(|color| *color != value.get_untracked()) {
+                        value.set(color);
+                    }
+                });
+            });
+            (
+                empty().style(move |s| {
+                    s.size(16.0, 16.0)
+                        .flex_shrink(0.0)
+                        .border(1.0)
+                        .border_radius(3.0)
+                        .background(value.get())
+                }),
+                text_input(text).style(|s| s.flex_grow(1.0).min_width(0.0)),
+            )
+                .h_stack()
+                .style(|s| s.width_full().items_center().gap(6.0))
+                .into_any()
+        })
+    }
+
+    /// A property edited by a custom view, with the reset button of its row restoring the
+    /// value `value` had when the property was created.
+    pub fn custom<T: Clone + PartialEq + 'static, V: IntoView + 'static>(
+        label: impl Into<String>,
+        value: RwSignal<T>,
+        editor: impl Fn(RwSignal<T>) -> V + 'static,
+    ) -> Self {
+        Self::new(label, value, move || editor(value).into_any())
+    }
+
+    /// The category the property is listed under, properties without a category being
+    /// listed under "General".
+    pub fn category(mut self, category: impl Into<String>) -> Self {
+        self.category = category.into();
+        self
+    }
+
+    /// A description shown under the label, which is also matched by the search.
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    fn matches(&self, search: &str) -> bool {
+        search.is_empty()
+            || self.label.to_lowercase().contains(search)
+            || self.category.to_lowercase().contains(search)
+            || self
+                .description
+                .as_ref()
+                .is_some_and(|description| description.to_lowercase().contains(search))
+    }
+}
+
+/// The properties grouped by category, the categories in the order of their first property.
+fn group_by_category(properties: Vec<Property>) -> Vec<(String, Vec<Property>)> {
+    let mut categories: Vec<(String, Vec<Property>)> = Vec::new();
+    for property in properties {
+        match categories
+            .iter_mut()
+            .find(|(category, _)| *category == property.category)
+        {
+            Some((_, properties)) => properties.push(property),
+            None => categories.push((property.category.clone(), vec![property])),
+        }
+    }
+    categories
+}
+
+/// A two-column grid of the labels of `properties` and their editors, grouped by category,
+/// with a search field filtering the properties by their label, category and description.
+///
+/// Each row has a button resetting its property to its default value, shown when the property
+/// has a different value.
+/// ```rust
+/// use floem::{
+///     peniko::Color,
+///     reactive::RwSignal,
+///     views::{property_grid, NumberFormat, Property},
+/// };
+///
+/// let font_size = RwSignal::new(14.0);
+/// let wrap = RwSignal::new(false);
+/// let theme = RwSignal::new("Light");
+/// let accent = RwSignal::new(Color::from_rgb8(30, 144, 255));
+/// property_grid([
+///     Property::number("Font Size", font_size, NumberFormat::new().unit("px"))
+///         .category("Editor"),
+///     Property::bool("Word Wrap", wrap)
+///         .category("Editor")
+///         .description("Wrap lines at the width of the editor"),
+///     Property::choice("Theme", theme, ["Light", "Dark"]).category("Appearance"),
+///     Property::color("Accent", accent).category("Appearance"),
+/// ]);
+/// ```
+pub fn property_grid(properties: impl IntoIterator<Item = Property>) -> Stack {
+    let search = RwSignal::new(String::new());
+    let search_text = move || search.with(|search| search.trim().to_lowercase());
+
+    let categories = group_by_category(properties.into_iter().collect());
+    let category_views = categories.into_iter().map(move |(category, properties)| {
+        let properties: Vec<Rc<Property>> = properties.into_iter().map(Rc::new).collect();
+        let shown = {
+            let properties = properties.clone();
+            move || {
+                let search = search_text();
+                properties.iter().any(|property| property.matches(&search))
+            }
+        };
+        let rows = properties.into_iter().map(move |property| {
+            let label_text = property.label.clone();
+            let description = property.description.clone();
+            let is_default = property.is_default.clone();
+            let reset = property.reset.clone();
+            let shown = {
+                let property = property.clone();
+                move || property.matches(&search_text())
+            };
+            (
+                v_stack_from_iter(
+                    std::iter::once(label(move || label_text.clone()).into_any()).chain(
+                        description.map(|description| {
+                            label(move || description.clone())
+                                .style(|s| s.font_size(11.0).color(Color::from_rgb8(110, 110, 110)))
+                                .into_any()
+                        }),
+                    ),
+                )
+                .style(|s| s.width_pct(40.0).flex_shrink(0.0)),
+                (property.editor)().style(|s| s.flex_grow(1.0).min_width(0.0)),
+                label(|| "↺")
+                    .class(PropertyResetClass)
+                    .on_click_stop(move |_| reset())
+                    .style(move |s| s.apply_if(is_default(), |s| s.hide())),
+            )
+                .h_stack()
+                .class(PropertyRowClass)
+                .style(move |s| {
+                    s.items_center()
+                        .gap(8.0)
+                        .width_full()
+                        .apply_if(!shown(), |s| s.hide())
+                })
+        });
+        v_stack_from_iter(
+            std::iter::once(
+                label(move || category.clone())
+                    .class(PropertyCategoryClass)
+                    .into_any(),
+            )
+            .chain(rows.map(|row| row.into_any())),
+        )
+        .style(move |s| s.gap(4.0).width_full().apply_if(!shown(), |s| s.hide()))
+    });
+
+    (
+        text_input(search)
+            .placeholder("Search settings")
+            .style(|s| s.width_full()),
+        scroll(v_stack_from_iter(category_views).style(|s| s.gap(12.0).width_full()))
+            .style(|s| s.flex_grow(1.0).min_height(0.0)),
+    )
+        .v_stack()
+        .class(PropertyGridClass)
+        .style(|s| s.gap(8.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use floem_reactive::{RwSignal, Scope, SignalGet, SignalUpdate};
+    use peniko::Color;
+
+    use super::{group_by_category, hex_color, parse_hex_color, Property};
+
+    #[test]
+    fn hex_colors() {
+        assert_eq!(
+            parse_hex_color("#1e90ff"),
+            Some(Color::from_rgb8(30, 144, 255))
+        );
+        assert_eq!(
+            parse_hex_color("fff"),
+            Some(Color::from_rgb8(255, 255, 255))
+        );
+        assert_eq!(parse_hex_color("#12345"), None);
+        assert_eq!(parse_hex_color("#gg0000"), None);
+        assert_eq!(hex_color(Color::from_rgba8(1, 2, 3, 4)), "#01020304");
+    }
+
+    #[test]
+    fn categories_and_reset() {
+        let cx = Scope::new();
+        let size = cx.create_rw_signal(12.0);
+        let name = cx.create_rw_signal(String::from("floem"));
+        let categories = group_by_category(vec![
+            Property::text("Name", name),
+            Property::text("Size", RwSignal::new(String::new())).category("Layout"),
+            Property::number("Font Size", size, Default::default()).description("In points"),
+        ]);
+        let names: Vec<_> = categories
+            .iter()
+            .map(|(category, properties)| (category.as_str(), properties.len()))
+            .collect();
+        assert_eq!(names, [("General", 2), ("Layout", 1)]);
+
+        let font_size = &categories[0].1[1];
+        assert!(font_size.matches("points"));
+        assert!(!font_size.matches("layout"));
+        size.set(20.0);
+        assert!(!(font_size.is_default)());
+        (font_size.reset)();
+        assert_eq!(size.get(), 12.0);
+        cx.dispose();
+    }
+}
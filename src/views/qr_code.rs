@@ -0,0 +1,185 @@
+//! QR codes, like the codes scanned with a phone to sign in or to pair a device.
+use accesskit::Role;
+use floem_reactive::create_effect;
+use peniko::{
+    color::palette,
+    kurbo::{BezPath, Rect, Shape},
+    Color,
+};
+use qrcode::{Color as ModuleColor, QrCode as Code};
+
+use crate::{
+    context::{PaintCx, StyleCx, UpdateCx},
+    id::ViewId,
+    prop, prop_extractor, style_class,
+    view::View,
+    views::Decorators,
+};
+
+prop!(
+    /// The color of the dark modules of a QR code, which are painted over the background of
+    /// the view
+    pub QrCodeColor: Color {} = palette::css::BLACK
+);
+
+prop_extractor! {
+    QrCodeStyle {
+        color: QrCodeColor,
+    }
+}
+
+style_class!(
+    /// Style class of QR codes
+    pub QrCodeClass
+);
+
+/// The width of the light margin around the modules, in modules, which scanners need to find
+/// the code.
+const QUIET_ZONE: usize = 4;
+
+/// The modules of an encoded QR code, row by row, `true` for the dark ones.
+struct Modules {
+    width: usize,
+    dark: Vec<bool>,
+}
+
+/// A QR code. See [`qr_code`].
+pub struct QrCode {
+    id: ViewId,
+    modules: Option<Modules>,
+    style: QrCodeStyle,
+}
+
+/// A QR code encoding the data returned by `data`, like a URL or a pairing token.
+///
+/// The code is painted as a path, so it stays crisp at any size and scale. It's square and
+/// centered in the view, with the quiet zone scanners need around it. The dark modules are
+/// painted with [`QrCodeColor`] over the background of the view, which is white by default.
+/// Nothing is painted when the data is too long for a QR code.
+/// ```rust,ignore
+/// use floem::{
+///     reactive::{RwSignal, SignalGet},
+///     views::{qr_code, Decorators},
+/// };
+///
+/// let pairing_url = RwSignal::new("https://example.com/pair?token=1234".to_string());
+/// qr_code(move || pairing_url.get()).style(|s| s.size(200.0, 200.0));
+/// ```
+pub fn qr_code<D: AsRef<[u8]>>(data: impl Fn() -> D + 'static) -> QrCode {
+    let id = ViewId::new();
+    create_effect(move |_| {
+        let modules = Code::new(data()).ok().map(|code| Modules {
+            width: code.width(),
+            dark: code
+                .to_colors()
+                .into_iter()
+                .map(|color| color == ModuleColor::Dark)
+                .collect(),
+        });
+        id.update_state(modules);
+    });
+
+    QrCode {
+        id,
+        modules: None,
+        style: Default::default(),
+    }
+    .class(QrCodeClass)
+    .accessibility_role(Role::Image)
+    .style(|s| s.size(160.0, 160.0).background(palette::css::WHITE))
+}
+
+/// The path of the dark modules, as a rectangle for each horizontal run of dark modules, with
+/// modules of `module_size` from `origin`.
+fn modules_path(modules: &Modules, origin: (f64, f64), module_size: f64) -> BezPath {
+    let mut path = BezPath::new();
+    for (y, row) in modules.dark.chunks(modules.width).enumerate() {
+        let mut x = 0;
+        while x < row.len() {
+            if !row[x] {
+                x += 1;
+                continue;
+            }
+            let start = x;
+            while x < row.len() && row[x] {
+                x += 1;
+            }
+            let rect = Rect::new(
+                origin.0 + start as f64 * module_size,
+                origin.1 + y as f64 * module_size,
+                origin.0 + x as f64 * module_size,
+                origin.1 + (y + 1) as f64 * module_size,
+            );
+            path.extend(rect.path_elements(0.1));
+        }
+    }
+    path
+}
+
+impl View for QrCode {
+    fn id(&self) -> ViewId {
+        self.id
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "QR Code".into()
+    }
+
+    fn update(&mut self, _cx: &mut UpdateCx, state: Box<dyn std::any::Any>) {
+        if let Ok(modules) = state.downcast::<Option<Modules>>() {
+            self.modules = *modules;
+            self.id.request_paint();
+        }
+    }
+
+    fn style_pass(&mut self, cx: &mut StyleCx<'_>) {
+        if self.style.read(cx) {
+            self.id.request_paint();
+        }
+    }
+
+    fn paint(&mut self, cx: &mut PaintCx) {
+        let Some(modules) = &self.modules else {
+            return;
+        };
+        let Some(layout) = self.id.get_layout() else {
+            return;
+        };
+        let (width, height) = (layout.size.width as f64, layout.size.height as f64);
+        let side = width.min(height);
+        let module_size = side / (modules.width + 2 * QUIET_ZONE) as f64;
+        let origin = (
+            (width - side) / 2.0 + QUIET_ZONE as f64 * module_size,
+            (height - side) / 2.0 + QUIET_ZONE as f64 * module_size,
+        );
+        // The modules are a single path, so that no seams show between adjacent modules
+        let path = modules_path(modules, origin, module_size);
+        cx.fill(&path, self.style.color(), 0.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use peniko::kurbo::{PathEl, Shape};
+
+    use super::{modules_path, Modules};
+
+    #[test]
+    fn runs() {
+        let modules = Modules {
+            width: 3,
+            dark: vec![true, true, false, false, false, true],
+        };
+        let path = modules_path(&modules, (10.0, 0.0), 2.0);
+        let rects = path
+            .elements()
+            .iter()
+            .filter(|el| matches!(el, PathEl::MoveTo(_)))
+            .count();
+        assert_eq!(rects, 2);
+        assert_eq!(
+            path.bounding_box(),
+            peniko::kurbo::Rect::new(10.0, 0.0, 16.0, 4.0)
+        );
+    }
+}
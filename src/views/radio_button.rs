@@ -1,10 +1,16 @@
+use std::{fmt::Display, rc::Rc};
+
 use crate::{
+    event::{Event, EventListener, EventPropagation},
+    keyboard::{Key, NamedKey},
+    nav::FocusGroup,
     style_class,
-    view::View,
-    views::{self, container, empty, h_stack, Decorators},
+    view::{AnyView, View},
+    views::{self, container, empty, h_stack, v_stack_from_iter, Decorators, Stack},
     IntoView,
 };
-use floem_reactive::{SignalGet, SignalUpdate};
+use accesskit::Role;
+use floem_reactive::{RwSignal, SignalGet, SignalUpdate};
 
 use super::{create_value_container_signals, value_container, ValueContainer};
 
@@ -17,12 +23,15 @@ fn radio_button_svg<T>(represented_value: T, actual_value: impl SignalGet<T> + '
 where
     T: Eq + PartialEq + Clone + 'static,
 {
+    let selected_value = represented_value.clone();
     container(empty().class(RadioButtonDotClass).style(move |s| {
         s.apply_if(actual_value.get() != represented_value, |s| {
             s.display(taffy::style::Display::None)
         })
     }))
     .class(RadioButtonClass)
+    .accessibility_role(Role::RadioButton)
+    .accessibility_toggled(move || actual_value.get() == selected_value)
 }
 
 /// The `RadioButton` struct provides various methods to create and manage radio buttons.
@@ -189,6 +198,76 @@ where
     RadioButton::new_labeled(represented_value, actual_value, label)
 }
 
+/// Renders a column of labeled radio buttons, one for each of the `options`, of which the
+/// one whose value is in `value` is selected.
+///
+/// Like native radio groups, <kbd>Tab</kbd> stops at the group once, and the arrow keys
+/// select the previous or next option and focus it, <kbd>Home</kbd> and <kbd>End</kbd> the
+/// first or last option.
+/// ```rust
+/// use floem::{reactive::RwSignal, views::radio_group};
+///
+/// #[derive(Clone, Copy, PartialEq, Eq)]
+/// enum Size {
+///     Small,
+///     Large,
+/// }
+///
+/// let size = RwSignal::new(Size::Small);
+/// radio_group(size, [(Size::Small, "Small"), (Size::Large, "Large")]);
+/// ```
+pub fn radio_group<T, S>(value: RwSignal<T>, options: impl IntoIterator<Item = (T, S)>) -> Stack
+where
+    T: Eq + PartialEq + Clone + 'static,
+    S: Display + Clone + 'static,
+{
+    let (values, views): (Vec<T>, Vec<_>) = options
+        .into_iter()
+        .map(|(option, label)| {
+            let view = RadioButton::new_labeled_rw(option.clone(), value, move || label.clone())
+                .into_any();
+            (option, view)
+        })
+        .unzip();
+    v_stack_from_iter(select_with_arrows(value, values, views))
+        .focus_group(FocusGroup::both().wrap(true))
+}
+
+/// Makes the arrow keys, <kbd>Home</kbd> and <kbd>End</kbd> select and focus the previous,
+/// next, first or last of a group of views, which select the `values` of the same index.
+pub(crate) fn select_with_arrows<T: Clone + 'static>(
+    value: RwSignal<T>,
+    values: Vec<T>,
+    views: Vec<AnyView>,
+) -> Vec<AnyView> {
+    let values = Rc::new(values);
+    let ids = Rc::new(views.iter().map(|view| view.id()).collect::<Vec<_>>());
+    views
+        .into_iter()
+        .enumerate()
+        .map(move |(i, view)| {
+            let values = values.clone();
+            let ids = ids.clone();
+            view.on_event(EventListener::KeyDown, move |event| {
+                let Event::KeyDown(event) = event else {
+                    return EventPropagation::Continue;
+                };
+                let len = values.len();
+                let target = match event.key.logical_key {
+                    Key::Named(NamedKey::ArrowDown | NamedKey::ArrowRight) => (i + 1) % len,
+                    Key::Named(NamedKey::ArrowUp | NamedKey::ArrowLeft) => (i + len - 1) % len,
+                    Key::Named(NamedKey::Home) => 0,
+                    Key::Named(NamedKey::End) => len - 1,
+                    _ => return EventPropagation::Continue,
+                };
+                value.set(values[target].clone());
+                ids[target].request_focus();
+                EventPropagation::Stop
+            })
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -233,6 +312,13 @@ mod test {
         assert_eq!(actual_value.get(), "OptionB");
     }
 
+    #[test]
+    fn test_radio_group_keeps_value() {
+        let actual_value = create_rw_signal(2);
+        let _group = radio_group(actual_value, [(1, "One"), (2, "Two"), (3, "Three")]);
+        assert_eq!(actual_value.get(), 2);
+    }
+
     #[test]
     fn test_radio_button_new_get() {
         let actual_value = create_rw_signal(String::from("Option1"));
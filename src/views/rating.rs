@@ -0,0 +1,107 @@
+//! A star rating control.
+use accesskit::Role;
+use floem_reactive::{RwSignal, SignalGet, SignalUpdate};
+
+use crate::{
+    event::{Event, EventListener, EventPropagation},
+    keyboard::{Key, NamedKey},
+    style_class,
+    views::{h_stack_from_iter, label, Decorators, Stack},
+};
+
+style_class!(
+    /// Style class of a rating control
+    pub RatingClass
+);
+
+style_class!(
+    /// Style class of the stars of a rating control
+    pub RatingStarClass
+);
+
+style_class!(
+    /// Style class of the stars of a rating control up to its value, or up to the hovered
+    /// star
+    pub RatingStarFilledClass
+);
+
+/// The rating after a key press, or `None` if the key doesn't change ratings.
+fn rating_for_key(key: &Key, value: u32, max: u32) -> Option<u32> {
+    match key {
+        Key::Named(NamedKey::ArrowRight | NamedKey::ArrowUp) => Some((value + 1).min(max)),
+        Key::Named(NamedKey::ArrowLeft | NamedKey::ArrowDown) => Some(value.saturating_sub(1)),
+        Key::Named(NamedKey::Home) => Some(0),
+        Key::Named(NamedKey::End) => Some(max),
+        Key::Character(c) => c.parse::<u32>().ok().filter(|n| *n <= max),
+        _ => None,
+    }
+}
+
+/// Renders a row of `max` stars, of which the first `value` are filled.
+///
+/// Hovering a star previews the rating, and clicking it sets the rating, or clears it if the
+/// rating is already that star. When focused, the arrow keys change the rating by one star,
+/// and digits set it.
+/// ```rust
+/// use floem::{reactive::RwSignal, views::rating};
+///
+/// let stars = RwSignal::new(3);
+/// rating(stars, 5);
+/// ```
+pub fn rating(value: RwSignal<u32>, max: u32) -> Stack {
+    let hovered = RwSignal::new(None::<u32>);
+    h_stack_from_iter((1..=max).map(move |star| {
+        label(|| "★".to_string())
+            .class(RatingStarClass)
+            .class_if(
+                move || hovered.get().unwrap_or_else(|| value.get()) >= star,
+                RatingStarFilledClass,
+            )
+            .on_event_cont(EventListener::PointerEnter, move |_| {
+                hovered.set(Some(star));
+            })
+            .on_click_stop(move |_| {
+                let current = value.get_untracked();
+                value.set(if current == star { 0 } else { star });
+            })
+    }))
+    .class(RatingClass)
+    .keyboard_navigable()
+    .accessibility_role(Role::Slider)
+    .accessibility_value(move || format!("{} of {max}", value.get()))
+    .on_event_cont(EventListener::PointerLeave, move |_| hovered.set(None))
+    .on_event(EventListener::KeyDown, move |event| {
+        let Event::KeyDown(event) = event else {
+            return EventPropagation::Continue;
+        };
+        match rating_for_key(&event.key.logical_key, value.get_untracked(), max) {
+            Some(rating) => {
+                value.set(rating);
+                EventPropagation::Stop
+            }
+            None => EventPropagation::Continue,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::keyboard::{Key, NamedKey};
+
+    use super::rating_for_key;
+
+    #[test]
+    fn keys() {
+        let right = Key::Named(NamedKey::ArrowRight);
+        assert_eq!(rating_for_key(&right, 2, 5), Some(3));
+        assert_eq!(rating_for_key(&right, 5, 5), Some(5));
+        assert_eq!(
+            rating_for_key(&Key::Named(NamedKey::ArrowDown), 0, 5),
+            Some(0)
+        );
+        assert_eq!(rating_for_key(&Key::Named(NamedKey::End), 1, 5), Some(5));
+        assert_eq!(rating_for_key(&Key::Character("4".into()), 1, 5), Some(4));
+        assert_eq!(rating_for_key(&Key::Character("7".into()), 1, 5), None);
+        assert_eq!(rating_for_key(&Key::Named(NamedKey::Tab), 1, 5), None);
+    }
+}
@@ -1,17 +1,25 @@
 #![deny(missing_docs)]
 //! Scroll View
 
+use std::time::Duration;
+
 use floem_reactive::create_effect;
-use peniko::kurbo::{Point, Rect, Size, Stroke, Vec2};
+use peniko::kurbo::{BezPath, Circle, Point, Rect, Size, Stroke, Vec2};
 use peniko::{Brush, Color};
 
-use crate::style::{BorderRightColor, CustomStylable, OverflowX, OverflowY};
+use crate::style::{
+    BorderRightColor, CustomStylable, Direction, DirectionProp, OverflowX, OverflowY,
+    StylePropValue,
+};
 use crate::unit::PxPct;
 use crate::{
+    action::{exec_after, TimerToken},
     app_state::AppState,
     context::{ComputeLayoutCx, PaintCx},
     event::{Event, EventPropagation},
     id::ViewId,
+    pointer::PointerButton,
+    preferences::prefers_reduced_motion,
     prop, prop_extractor,
     style::{Background, BorderRadius, Style, StyleSelector},
     style_class,
@@ -28,12 +36,41 @@ enum ScrollState {
     ScrollTo(Point),
     ScrollToPercent(f32),
     ScrollToView(ViewId),
+    AutoScrollStep(TimerToken),
+    FadeBars(TimerToken),
+    SpringBack(TimerToken),
 }
 
 /// Minimum length for any scrollbar to be when measured on that
-/// scrollbar's primary axis.
+/// scrollbar's primary axis, unless set with [`MinThumbSize`].
 const SCROLLBAR_MIN_SIZE: f64 = 10.0;
 
+/// How long auto-hidden bars stay visible after the last scroll.
+const AUTO_HIDE_DELAY: Duration = Duration::from_millis(1000);
+
+/// How long auto-hidden bars take to fade out.
+const FADE_DURATION: Duration = Duration::from_millis(200);
+
+/// The interval between the steps of the fade out of auto-hidden bars.
+const FADE_INTERVAL: Duration = Duration::from_millis(16);
+
+/// The furthest distance the content can be stretched past its edges.
+const MAX_OVERSCROLL: f64 = 120.0;
+
+/// The part of the stretch past the edges which is kept at each step of the spring back.
+const SPRING_BACK_DECAY: f64 = 0.75;
+
+/// The interval between the steps of the spring back of an overscroll.
+const SPRING_BACK_INTERVAL: Duration = Duration::from_millis(16);
+
+/// The distance the content is moved by a stretch past its edges of `stretch`, which is less
+/// the further it is stretched, up to [`MAX_OVERSCROLL`].
+fn rubber_band(stretch: f64, resistance: f64) -> f64 {
+    let give = (1.0 - resistance).clamp(0.0, 1.0);
+    let distance = stretch.abs() * give;
+    stretch.signum() * MAX_OVERSCROLL * (1.0 - 1.0 / (distance / MAX_OVERSCROLL + 1.0))
+}
+
 /// Denotes which scrollbar, if any, is currently being dragged.
 #[derive(Debug, Copy, Clone)]
 enum BarHeldState {
@@ -47,6 +84,54 @@ enum BarHeldState {
     Horizontal(f64, Vec2),
 }
 
+/// The distance a touch has to move before it scrolls instead of pressing the content.
+const TOUCH_SLOP: f64 = 8.0;
+
+/// A touch on the content, which scrolls it once it is dragged.
+#[derive(Debug, Copy, Clone)]
+struct TouchDrag {
+    start: Point,
+    start_offset: Vec2,
+    scrolling: bool,
+}
+
+/// The distance from the origin of an autoscroll within which the content doesn't scroll.
+const AUTOSCROLL_DEAD_ZONE: f64 = 8.0;
+
+/// The speed of an autoscroll, in pixels per second for each pixel between the pointer and
+/// the dead zone around the origin.
+const AUTOSCROLL_SPEED: f64 = 6.0;
+
+/// The interval between the steps of an autoscroll.
+const AUTOSCROLL_INTERVAL: Duration = Duration::from_millis(16);
+
+/// The radius of the marker painted at the origin of an autoscroll.
+const AUTOSCROLL_MARKER_RADIUS: f64 = 12.0;
+
+/// A scroll started with a middle click, which scrolls the content towards the pointer at a
+/// speed proportional to its distance from the origin of the click.
+#[derive(Debug, Copy, Clone)]
+struct AutoScroll {
+    origin: Point,
+    pointer: Point,
+    /// Whether the pointer was dragged with the button held, in which case releasing the
+    /// button stops the scroll. Otherwise the next click stops it.
+    dragged: bool,
+    timer: TimerToken,
+}
+
+impl AutoScroll {
+    /// The speed of the scroll in pixels per second on each axis.
+    fn velocity(&self) -> Vec2 {
+        let speed = |distance: f64| {
+            let beyond = (distance.abs() - AUTOSCROLL_DEAD_ZONE).max(0.0);
+            beyond * AUTOSCROLL_SPEED * distance.signum()
+        };
+        let offset = self.pointer - self.origin;
+        Vec2::new(speed(offset.x), speed(offset.y))
+    }
+}
+
 style_class!(
     /// Style class that will be applied to the handles of the scroll view
     pub Handle
@@ -105,18 +190,113 @@ prop!(
     pub VerticalScrollAsHorizontal: bool {} = false
 );
 
+prop!(
+    /// When true, dragging the content with a touch scrolls it.
+    pub TouchScroll: bool {} = true
+);
+
 prop!(
     /// Enables clipping of overflowing content when set to true.
     pub OverflowClip: bool {} = true
 );
 
+prop!(
+    /// When true, pressing the middle button starts scrolling towards the pointer, faster as it
+    /// gets further from where it was pressed (defaults to false). Children which handle the
+    /// middle click themselves don't start an autoscroll.
+    pub MiddleClickAutoscroll: bool {} = false
+);
+
+prop!(
+    /// When true, scrolling past the edges of the content stretches it, and it springs back
+    /// once released. The pointer wheel events which stretch the content aren't propagated.
+    pub ElasticOverscroll: bool {} = false
+);
+
+prop!(
+    /// How hard the content is to stretch past its edges with [`ElasticOverscroll`], from 0
+    /// where it follows the pointer until it gets far from the edge, to 1 where it doesn't
+    /// stretch at all.
+    pub OverscrollResistance: f64 {} = 0.5
+);
+
+prop!(
+    /// Extra space the content can be scrolled by above its top, like the space under a
+    /// floating header. The content starts below this space.
+    pub ContentInsetTop: Px {} = Px(0.0)
+);
+
+prop!(
+    /// Extra space the content can be scrolled by below its bottom, so that its end can be
+    /// scrolled up from the bottom edge of the view.
+    pub ContentInsetBottom: Px {} = Px(0.0)
+);
+
+prop!(
+    /// The minimum length of the scroll handles in pixels, so that they stay easy to grab
+    /// when the content is long.
+    pub MinThumbSize: Px {} = Px(SCROLLBAR_MIN_SIZE)
+);
+
+prop!(
+    /// When true, the bars are only shown while scrolling or while the pointer is over them,
+    /// and fade out after a delay.
+    pub AutoHideBars: bool {} = false
+);
+
+/// How the scroll bars are laid out with the content.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ScrollbarMode {
+    /// The bars are painted over the content.
+    #[default]
+    Overlay,
+    /// Space for the vertical bar is reserved next to the content, even while the bar isn't
+    /// shown, so the content doesn't move when it appears.
+    Gutter,
+}
+
+impl StylePropValue for ScrollbarMode {}
+
+prop!(
+    /// Whether the scroll bars are painted over the content or next to it.
+    pub ScrollbarModeProp: ScrollbarMode {} = ScrollbarMode::Overlay
+);
+
+/// What a click on a scroll track, outside of its handle, does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TrackClick {
+    /// Scrolls so that the handle is centered on the click, and starts dragging it.
+    #[default]
+    Jump,
+    /// Scrolls by the size of the viewport towards the click.
+    Page,
+}
+
+impl StylePropValue for TrackClick {}
+
+prop!(
+    /// What a click on a scroll track, outside of its handle, does.
+    pub TrackClickProp: TrackClick {} = TrackClick::Jump
+);
+
 prop_extractor!(ScrollStyle {
     vertical_bar_inset: VerticalInset,
     horizontal_bar_inset: HorizontalInset,
     hide_bar: HideBars,
     propagate_pointer_wheel: PropagatePointerWheel,
     vertical_scroll_as_horizontal: VerticalScrollAsHorizontal,
+    touch_scroll: TouchScroll,
     overflow_clip: OverflowClip,
+    middle_click_autoscroll: MiddleClickAutoscroll,
+    elastic_overscroll: ElasticOverscroll,
+    overscroll_resistance: OverscrollResistance,
+    content_inset_top: ContentInsetTop,
+    content_inset_bottom: ContentInsetBottom,
+    min_thumb_size: MinThumbSize,
+    auto_hide_bars: AutoHideBars,
+    mode: ScrollbarModeProp,
+    track_click: TrackClickProp,
+    direction: DirectionProp,
 });
 
 const HANDLE_COLOR: Brush = Brush::Solid(Color::from_rgba8(0, 0, 0, 120));
@@ -148,6 +328,15 @@ pub struct Scroll {
 
     onscroll: Option<Box<dyn Fn(Rect)>>,
     held: BarHeldState,
+    touch_drag: Option<TouchDrag>,
+    autoscroll: Option<AutoScroll>,
+    /// How far past the edges of the content it was scrolled, before the resistance of the
+    /// elastic overscroll.
+    stretch: Vec2,
+    spring_timer: Option<TimerToken>,
+    /// The opacity of the bars when they are auto-hidden.
+    bar_opacity: f64,
+    fade_timer: Option<TimerToken>,
     v_handle_hover: bool,
     h_handle_hover: bool,
     v_track_hover: bool,
@@ -177,6 +366,12 @@ pub fn scroll<V: IntoView + 'static>(child: V) -> Scroll {
         computed_child_viewport: Rect::ZERO,
         onscroll: None,
         held: BarHeldState::None,
+        touch_drag: None,
+        autoscroll: None,
+        stretch: Vec2::ZERO,
+        spring_timer: None,
+        bar_opacity: 0.0,
+        fade_timer: None,
         v_handle_hover: false,
         h_handle_hover: false,
         v_track_hover: false,
@@ -288,6 +483,204 @@ impl Scroll {
         self.clamp_child_viewport(app_state, self.child_viewport.with_origin(origin));
     }
 
+    fn can_scroll(&self) -> (bool, bool) {
+        let viewport = self.child_viewport.size();
+        let extent = self.scroll_extent().size();
+        (
+            extent.width > viewport.width,
+            extent.height > viewport.height,
+        )
+    }
+
+    /// The area of the child the viewport can be scrolled over, which is the child with the
+    /// content insets.
+    fn scroll_extent(&self) -> Rect {
+        Rect::new(
+            0.0,
+            -self.scroll_style.content_inset_top().0,
+            self.child_size.width,
+            self.child_size.height + self.scroll_style.content_inset_bottom().0,
+        )
+    }
+
+    /// The distance the content is moved by past its edges.
+    fn overscroll(&self) -> Vec2 {
+        let resistance = self.scroll_style.overscroll_resistance();
+        Vec2::new(
+            rubber_band(self.stretch.x, resistance),
+            rubber_band(self.stretch.y, resistance),
+        )
+    }
+
+    /// Sets how far past the edges of the content it was scrolled, on the axes it can scroll,
+    /// and starts springing back. Returns whether the content moved.
+    fn set_stretch(&mut self, stretch: Vec2) -> bool {
+        if !self.scroll_style.elastic_overscroll() || prefers_reduced_motion() {
+            return false;
+        }
+        let (horizontal, vertical) = self.can_scroll();
+        let stretch = Vec2::new(
+            if horizontal { stretch.x } else { 0.0 },
+            if vertical { stretch.y } else { 0.0 },
+        );
+        let before = self.overscroll();
+        self.stretch = stretch;
+        if self.overscroll() == before {
+            return false;
+        }
+        self.id.request_paint();
+        if self.spring_timer.is_none() {
+            self.spring_timer = Some(self.schedule_spring_back());
+        }
+        true
+    }
+
+    fn schedule_spring_back(&self) -> TimerToken {
+        let id = self.id;
+        exec_after(SPRING_BACK_INTERVAL, move |token| {
+            id.update_state(ScrollState::SpringBack(token));
+        })
+    }
+
+    fn spring_back(&mut self, token: TimerToken) {
+        if self.spring_timer != Some(token) {
+            return;
+        }
+        // The content stays stretched while a touch holds it
+        if !self.touch_drag.is_some_and(|drag| drag.scrolling) {
+            self.stretch *= SPRING_BACK_DECAY;
+            let overscroll = self.overscroll();
+            if overscroll.x.abs() < 0.5 && overscroll.y.abs() < 0.5 {
+                self.stretch = Vec2::ZERO;
+            }
+            self.id.request_paint();
+        }
+        self.spring_timer = (self.stretch != Vec2::ZERO).then(|| self.schedule_spring_back());
+    }
+
+    fn schedule_autoscroll_step(&self) -> TimerToken {
+        let id = self.id;
+        exec_after(AUTOSCROLL_INTERVAL, move |token| {
+            id.update_state(ScrollState::AutoScrollStep(token));
+        })
+    }
+
+    fn start_autoscroll(&mut self, pos: Point) {
+        self.autoscroll = Some(AutoScroll {
+            origin: pos,
+            pointer: pos,
+            dragged: false,
+            timer: self.schedule_autoscroll_step(),
+        });
+        self.id.request_paint();
+    }
+
+    fn stop_autoscroll(&mut self) {
+        self.autoscroll = None;
+        self.id.request_paint();
+    }
+
+    fn autoscroll_step(&mut self, app_state: &mut AppState, token: TimerToken) {
+        let Some(autoscroll) = self.autoscroll.filter(|a| a.timer == token) else {
+            return;
+        };
+        let delta = autoscroll.velocity() * AUTOSCROLL_INTERVAL.as_secs_f64();
+        if delta != Vec2::ZERO {
+            self.do_scroll_delta(app_state, delta);
+        }
+        let timer = self.schedule_autoscroll_step();
+        if let Some(autoscroll) = self.autoscroll.as_mut() {
+            autoscroll.timer = timer;
+        }
+    }
+
+    /// Paints a circle at the origin of the autoscroll, with arrows towards the directions in
+    /// which the content can scroll.
+    fn draw_autoscroll_marker(&self, cx: &mut PaintCx, origin: Point) {
+        let color = Color::from_rgba8(0, 0, 0, 160);
+        let circle = Circle::new(origin, AUTOSCROLL_MARKER_RADIUS);
+        cx.fill(&circle, Color::from_rgba8(255, 255, 255, 200), 0.0);
+        cx.stroke(&circle, color, &Stroke::new(1.0));
+        cx.fill(&Circle::new(origin, 2.0), color, 0.0);
+
+        let (horizontal, vertical) = self.can_scroll();
+        let mut directions = Vec::new();
+        if vertical {
+            directions.extend([Vec2::new(0.0, -1.0), Vec2::new(0.0, 1.0)]);
+        }
+        if horizontal {
+            directions.extend([Vec2::new(-1.0, 0.0), Vec2::new(1.0, 0.0)]);
+        }
+        for direction in directions {
+            let tip = origin + direction * (AUTOSCROLL_MARKER_RADIUS - 3.0);
+            let base = origin + direction * (AUTOSCROLL_MARKER_RADIUS - 7.0);
+            let side = Vec2::new(-direction.y, direction.x) * 3.5;
+            let mut arrow = BezPath::new();
+            arrow.move_to(tip);
+            arrow.line_to(base + side);
+            arrow.line_to(base - side);
+            arrow.close_path();
+            cx.fill(&arrow, color, 0.0);
+        }
+    }
+
+    /// The opacity the bars are painted with.
+    fn bar_opacity(&self) -> f64 {
+        if self.scroll_style.auto_hide_bars() {
+            self.bar_opacity
+        } else {
+            1.0
+        }
+    }
+
+    /// Shows auto-hidden bars, and schedules them to fade out after a delay.
+    fn show_bars(&mut self) {
+        if !self.scroll_style.auto_hide_bars() {
+            return;
+        }
+        if self.bar_opacity < 1.0 {
+            self.bar_opacity = 1.0;
+            self.id.request_paint();
+        }
+        let id = self.id;
+        self.fade_timer = Some(exec_after(AUTO_HIDE_DELAY, move |token| {
+            id.update_state(ScrollState::FadeBars(token));
+        }));
+    }
+
+    fn fade_bars(&mut self, token: TimerToken) {
+        if self.fade_timer != Some(token) {
+            return;
+        }
+        if self.are_bars_held() || self.v_track_hover || self.h_track_hover {
+            // The bars stay visible while they are used
+            self.show_bars();
+            return;
+        }
+        let step = if prefers_reduced_motion() {
+            1.0
+        } else {
+            FADE_INTERVAL.as_secs_f64() / FADE_DURATION.as_secs_f64()
+        };
+        self.bar_opacity = (self.bar_opacity - step).max(0.0);
+        self.id.request_paint();
+        let id = self.id;
+        self.fade_timer = (self.bar_opacity > 0.0).then(|| {
+            exec_after(FADE_INTERVAL, move |token| {
+                id.update_state(ScrollState::FadeBars(token));
+            })
+        });
+    }
+
+    /// The width reserved for the vertical bar in [`ScrollbarMode::Gutter`].
+    fn gutter_width(&self) -> f64 {
+        if self.scroll_style.mode() == ScrollbarMode::Gutter && !self.scroll_style.hide_bar() {
+            self.handle_style.thickness().0 + self.scroll_style.vertical_bar_inset().0 * 2.0
+        } else {
+            0.0
+        }
+    }
+
     /// Ensure that an entire area is visible in the scroll view.
     // TODO: remove duplilcation between this method and pan_to_visible
     pub fn ensure_area_visible(&mut self, app_state: &mut AppState, rect: Rect) {
@@ -436,27 +829,30 @@ impl Scroll {
         let actual_size = actual_rect.size();
         let width = actual_rect.width();
         let height = actual_rect.height();
-        let child_size = self.child_size;
+        let extent = self.scroll_extent();
 
         let mut child_viewport = child_viewport;
-        if width >= child_size.width {
-            child_viewport.x0 = 0.0;
-        } else if child_viewport.x0 > child_size.width - width {
-            child_viewport.x0 = child_size.width - width;
-        } else if child_viewport.x0 < 0.0 {
-            child_viewport.x0 = 0.0;
+        if width >= extent.width() {
+            child_viewport.x0 = extent.x0;
+        } else if child_viewport.x0 > extent.x1 - width {
+            child_viewport.x0 = extent.x1 - width;
+        } else if child_viewport.x0 < extent.x0 {
+            child_viewport.x0 = extent.x0;
         }
 
-        if height >= child_size.height {
-            child_viewport.y0 = 0.0;
-        } else if child_viewport.y0 > child_size.height - height {
-            child_viewport.y0 = child_size.height - height;
-        } else if child_viewport.y0 < 0.0 {
-            child_viewport.y0 = 0.0;
+        if height >= extent.height() {
+            child_viewport.y0 = extent.y0;
+        } else if child_viewport.y0 > extent.y1 - height {
+            child_viewport.y0 = extent.y1 - height;
+        } else if child_viewport.y0 < extent.y0 {
+            child_viewport.y0 = extent.y0;
         }
         child_viewport = child_viewport.with_size(actual_size);
 
         if child_viewport != self.child_viewport {
+            if child_viewport.origin() != self.child_viewport.origin() {
+                self.show_bars();
+            }
             self.child.set_viewport(child_viewport);
             app_state.request_compute_layout_recursive(self.id());
             app_state.request_paint(self.id());
@@ -498,6 +894,10 @@ impl Scroll {
     }
 
     fn draw_bars(&self, cx: &mut PaintCx) {
+        let opacity = self.bar_opacity() as f32;
+        if opacity <= 0.0 {
+            return;
+        }
         let scroll_offset = self.child_viewport.origin().to_vec2();
         let radius = |style: &ScrollTrackStyle, rect: Rect, vertical| {
             if style.rounded() {
@@ -527,14 +927,16 @@ impl Scroll {
                 let mut bounds = bounds - scroll_offset;
                 bounds.y0 = self.total_rect.y0;
                 bounds.y1 = self.total_rect.y1;
-                cx.fill(&bounds, &color, 0.0);
+                cx.fill(&bounds, &color.multiply_alpha(opacity), 0.0);
             }
             let edge_width = style.border().0;
             let rect = (bounds - scroll_offset).inset(-edge_width / 2.0);
             let rect = rect.to_rounded_rect(radius(style, rect, true));
-            cx.fill(&rect, &style.color().unwrap_or(HANDLE_COLOR), 0.0);
+            let color = style.color().unwrap_or(HANDLE_COLOR);
+            cx.fill(&rect, &color.multiply_alpha(opacity), 0.0);
             if edge_width > 0.0 {
-                cx.stroke(&rect, &style.border_color(), &Stroke::new(edge_width));
+                let border_color = style.border_color().multiply_alpha(opacity);
+                cx.stroke(&rect, &border_color, &Stroke::new(edge_width));
             }
         }
 
@@ -552,21 +954,24 @@ impl Scroll {
                 let mut bounds = bounds - scroll_offset;
                 bounds.x0 = self.total_rect.x0;
                 bounds.x1 = self.total_rect.x1;
-                cx.fill(&bounds, &color, 0.0);
+                cx.fill(&bounds, &color.multiply_alpha(opacity), 0.0);
             }
             let edge_width = style.border().0;
             let rect = (bounds - scroll_offset).inset(-edge_width / 2.0);
             let rect = rect.to_rounded_rect(radius(style, rect, false));
-            cx.fill(&rect, &style.color().unwrap_or(HANDLE_COLOR), 0.0);
+            let color = style.color().unwrap_or(HANDLE_COLOR);
+            cx.fill(&rect, &color.multiply_alpha(opacity), 0.0);
             if edge_width > 0.0 {
-                cx.stroke(&rect, &style.border_color(), &Stroke::new(edge_width));
+                let border_color = style.border_color().multiply_alpha(opacity);
+                cx.stroke(&rect, &border_color, &Stroke::new(edge_width));
             }
         }
     }
 
     fn calc_vertical_bar_bounds(&self, _app_state: &mut AppState) -> Option<Rect> {
         let viewport_size = self.child_viewport.size();
-        let content_size = self.child_size;
+        let extent = self.scroll_extent();
+        let content_size = extent.size();
         let scroll_offset = self.child_viewport.origin().to_vec2();
 
         if viewport_size.height >= content_size.height - 1. {
@@ -579,19 +984,26 @@ impl Scroll {
         let bar_pad = self.scroll_style.vertical_bar_inset().0;
 
         let percent_visible = viewport_size.height / content_size.height;
-        let percent_scrolled = scroll_offset.y / (content_size.height - viewport_size.height);
+        let percent_scrolled =
+            (scroll_offset.y - extent.y0) / (content_size.height - viewport_size.height);
 
         let length = (percent_visible * self.total_rect.height()).ceil();
         // Vertical scroll bar must have ast least the same height as it's width
-        let length = length.max(style.thickness().0);
+        let length = length
+            .max(self.scroll_style.min_thumb_size().0)
+            .max(style.thickness().0);
 
         let top_y_offset = ((self.total_rect.height() - length) * percent_scrolled).ceil();
         let bottom_y_offset = top_y_offset + length;
 
-        let x0 = scroll_offset.x + self.total_rect.width() - bar_width - bar_pad;
+        let x0 = if self.bars_on_left() {
+            scroll_offset.x + bar_pad
+        } else {
+            scroll_offset.x + self.total_rect.width() - bar_width - bar_pad
+        };
         let y0 = scroll_offset.y + top_y_offset;
 
-        let x1 = scroll_offset.x + self.total_rect.width() - bar_pad;
+        let x1 = x0 + bar_width;
         let y1 = scroll_offset.y + bottom_y_offset;
 
         Some(Rect::new(x0, y0, x1, y1))
@@ -599,7 +1011,8 @@ impl Scroll {
 
     fn calc_horizontal_bar_bounds(&self, _app_state: &mut AppState) -> Option<Rect> {
         let viewport_size = self.child_viewport.size();
-        let content_size = self.child_size;
+        let extent = self.scroll_extent();
+        let content_size = extent.size();
         let scroll_offset = self.child_viewport.origin().to_vec2();
 
         if viewport_size.width >= content_size.width - 1. {
@@ -612,10 +1025,11 @@ impl Scroll {
         let bar_pad = self.scroll_style.horizontal_bar_inset().0;
 
         let percent_visible = viewport_size.width / content_size.width;
-        let percent_scrolled = scroll_offset.x / (content_size.width - viewport_size.width);
+        let percent_scrolled =
+            (scroll_offset.x - extent.x0) / (content_size.width - viewport_size.width);
 
         let length = (percent_visible * self.total_rect.width()).ceil();
-        let length = length.max(SCROLLBAR_MIN_SIZE);
+        let length = length.max(self.scroll_style.min_thumb_size().0);
 
         let horizontal_padding = if viewport_size.height >= content_size.height {
             0.0
@@ -623,8 +1037,12 @@ impl Scroll {
             bar_pad + bar_pad + bar_width
         };
 
-        let left_x_offset =
+        let mut left_x_offset =
             ((self.total_rect.width() - length - horizontal_padding) * percent_scrolled).ceil();
+        if self.bars_on_left() {
+            // Leave room for the vertical bar on the left
+            left_x_offset += horizontal_padding;
+        }
         let right_x_offset = left_x_offset + length;
 
         let x0 = scroll_offset.x + left_x_offset;
@@ -636,8 +1054,14 @@ impl Scroll {
         Some(Rect::new(x0, y0, x1, y1))
     }
 
+    /// The vertical bar is on the left in right-to-left layout.
+    fn bars_on_left(&self) -> bool {
+        self.scroll_style.direction() == Direction::Rtl
+    }
+
     fn click_vertical_bar_area(&mut self, app_state: &mut AppState, pos: Point) {
-        let new_y = (pos.y / self.content_rect.height()) * self.child_size.height
+        let extent = self.scroll_extent();
+        let new_y = extent.y0 + (pos.y / self.content_rect.height()) * extent.height()
             - self.content_rect.height() / 2.0;
         let mut new_origin = self.child_viewport.origin();
         new_origin.y = new_y;
@@ -645,18 +1069,41 @@ impl Scroll {
     }
 
     fn click_horizontal_bar_area(&mut self, app_state: &mut AppState, pos: Point) {
-        let new_x = (pos.x / self.content_rect.width()) * self.child_size.width
+        let extent = self.scroll_extent();
+        let new_x = extent.x0 + (pos.x / self.content_rect.width()) * extent.width()
             - self.content_rect.width() / 2.0;
         let mut new_origin = self.child_viewport.origin();
         new_origin.x = new_x;
         self.do_scroll_to(app_state, new_origin);
     }
 
+    /// Scrolls by a page towards `pos`, which is relative to the child.
+    fn page_vertical_bar_area(&mut self, app_state: &mut AppState, pos: Point) {
+        if let Some(bounds) = self.calc_vertical_bar_bounds(app_state) {
+            let page = self.child_viewport.height();
+            let delta = if pos.y < bounds.y0 { -page } else { page };
+            self.do_scroll_delta(app_state, Vec2::new(0.0, delta));
+        }
+    }
+
+    /// Scrolls by a page towards `pos`, which is relative to the child.
+    fn page_horizontal_bar_area(&mut self, app_state: &mut AppState, pos: Point) {
+        if let Some(bounds) = self.calc_horizontal_bar_bounds(app_state) {
+            let page = self.child_viewport.width();
+            let delta = if pos.x < bounds.x0 { -page } else { page };
+            self.do_scroll_delta(app_state, Vec2::new(delta, 0.0));
+        }
+    }
+
     fn point_hits_vertical_bar(&self, app_state: &mut AppState, pos: Point) -> bool {
         if let Some(mut bounds) = self.calc_vertical_bar_bounds(app_state) {
             // Stretch hitbox to edge of widget
             let scroll_offset = self.child_viewport.origin().to_vec2();
-            bounds.x1 = self.total_rect.x1 + scroll_offset.x;
+            if self.bars_on_left() {
+                bounds.x0 = self.total_rect.x0 + scroll_offset.x;
+            } else {
+                bounds.x1 = self.total_rect.x1 + scroll_offset.x;
+            }
             pos.x >= bounds.x0 && pos.x <= bounds.x1
         } else {
             false
@@ -678,7 +1125,11 @@ impl Scroll {
         if let Some(mut bounds) = self.calc_vertical_bar_bounds(app_state) {
             // Stretch hitbox to edge of widget
             let scroll_offset = self.child_viewport.origin().to_vec2();
-            bounds.x1 = self.total_rect.x1 + scroll_offset.x;
+            if self.bars_on_left() {
+                bounds.x0 = self.total_rect.x0 + scroll_offset.x;
+            } else {
+                bounds.x1 = self.total_rect.x1 + scroll_offset.x;
+            }
             bounds.contains(pos)
         } else {
             false
@@ -724,6 +1175,9 @@ impl Scroll {
             self.h_track_hover = hover;
             app_state.request_paint(self.id());
         }
+        if self.v_track_hover || self.h_track_hover {
+            self.show_bars();
+        }
     }
 
     fn do_scroll_to_view(
@@ -780,11 +1234,20 @@ impl View for Scroll {
     }
 
     fn view_style(&self) -> Option<Style> {
+        let gutter = self.gutter_width();
+        let bars_on_left = self.bars_on_left();
         Some(
             Style::new()
                 .items_start()
                 .set(OverflowX, taffy::Overflow::Scroll)
-                .set(OverflowY, taffy::Overflow::Scroll),
+                .set(OverflowY, taffy::Overflow::Scroll)
+                .apply_if(gutter > 0.0, |s| {
+                    if bars_on_left {
+                        s.padding_left(gutter)
+                    } else {
+                        s.padding_right(gutter)
+                    }
+                }),
         )
     }
 
@@ -801,14 +1264,24 @@ impl View for Scroll {
                     self.do_scroll_to(cx.app_state, origin);
                 }
                 ScrollState::ScrollToPercent(percent) => {
-                    let mut child_size = self.child_size;
-                    child_size *= percent as f64;
-                    let point = child_size.to_vec2().to_point();
+                    let extent = self.scroll_extent();
+                    let point = extent.origin() + extent.size().to_vec2() * percent as f64;
                     self.do_scroll_to(cx.app_state, point);
                 }
                 ScrollState::ScrollToView(id) => {
                     self.do_scroll_to_view(cx.app_state, id, None);
                 }
+                ScrollState::AutoScrollStep(token) => {
+                    self.autoscroll_step(cx.app_state, token);
+                }
+                ScrollState::FadeBars(token) => {
+                    self.fade_bars(token);
+                    return;
+                }
+                ScrollState::SpringBack(token) => {
+                    self.spring_back(token);
+                    return;
+                }
             }
             self.id.request_layout();
         }
@@ -824,6 +1297,7 @@ impl View for Scroll {
 
     fn style_pass(&mut self, cx: &mut crate::context::StyleCx<'_>) {
         let style = cx.style();
+        let gutter = self.gutter_width();
 
         self.scroll_style.read(cx);
 
@@ -843,6 +1317,11 @@ impl View for Scroll {
         self.track_hover_style
             .read_style(cx, &track_style.apply_selectors(&[StyleSelector::Hover]));
 
+        // The gutter is reserved by the padding of `view_style`, which was already computed
+        if self.gutter_width() != gutter {
+            self.id.request_style();
+        }
+
         cx.style_view(self.child);
     }
 
@@ -861,9 +1340,29 @@ impl View for Scroll {
     ) -> EventPropagation {
         let viewport_size = self.child_viewport.size();
         let scroll_offset = self.child_viewport.origin().to_vec2();
-        let content_size = self.child_size;
+        let content_size = self.scroll_extent().size();
 
         match &event {
+            Event::PointerDown(_) if self.autoscroll.is_some() => {
+                // Any click stops an autoscroll which isn't dragged
+                self.stop_autoscroll();
+                return EventPropagation::Stop;
+            }
+            Event::PointerUp(event) if self.autoscroll.is_some() => {
+                if self.autoscroll.is_some_and(|a| a.dragged) && event.button.is_auxiliary() {
+                    self.stop_autoscroll();
+                }
+                return EventPropagation::Stop;
+            }
+            Event::PointerMove(event) if self.autoscroll.is_some() => {
+                if let Some(autoscroll) = self.autoscroll.as_mut() {
+                    autoscroll.pointer = event.pos;
+                    if (event.pos - autoscroll.origin).hypot() > AUTOSCROLL_DEAD_ZONE {
+                        autoscroll.dragged = true;
+                    }
+                }
+                return EventPropagation::Stop;
+            }
             Event::PointerDown(event) => {
                 if !self.scroll_style.hide_bar() && event.button.is_primary() {
                     self.held = BarHeldState::None;
@@ -882,6 +1381,10 @@ impl View for Scroll {
                             self.id.request_paint();
                             return EventPropagation::Stop;
                         }
+                        if self.scroll_style.track_click() == TrackClick::Page {
+                            self.page_vertical_bar_area(cx.app_state, pos);
+                            return EventPropagation::Stop;
+                        }
                         self.click_vertical_bar_area(cx.app_state, event.pos);
                         let scroll_offset = self.child_viewport.origin().to_vec2();
                         self.held = BarHeldState::Vertical(
@@ -903,6 +1406,10 @@ impl View for Scroll {
                             cx.app_state.request_paint(self.id());
                             return EventPropagation::Stop;
                         }
+                        if self.scroll_style.track_click() == TrackClick::Page {
+                            self.page_horizontal_bar_area(cx.app_state, pos);
+                            return EventPropagation::Stop;
+                        }
                         self.click_horizontal_bar_area(cx.app_state, event.pos);
                         let scroll_offset = self.child_viewport.origin().to_vec2();
                         self.held = BarHeldState::Horizontal(
@@ -914,8 +1421,20 @@ impl View for Scroll {
                         return EventPropagation::Stop;
                     }
                 }
+                if self.scroll_style.touch_scroll()
+                    && matches!(event.button, PointerButton::Touch { .. })
+                {
+                    self.touch_drag = Some(TouchDrag {
+                        start: event.pos,
+                        start_offset: scroll_offset,
+                        scrolling: false,
+                    });
+                }
             }
             Event::PointerUp(_event) => {
+                if self.touch_drag.take().is_some_and(|drag| drag.scrolling) {
+                    return EventPropagation::Stop;
+                }
                 if self.are_bars_held() {
                     self.held = BarHeldState::None;
                     // Force a repaint.
@@ -992,7 +1511,13 @@ impl View for Scroll {
             } else {
                 delta
             };
-            let any_change = self.clamp_child_viewport(cx.app_state, self.child_viewport + delta);
+            let target = self.child_viewport.origin() + delta;
+            let mut any_change =
+                self.clamp_child_viewport(cx.app_state, self.child_viewport + delta);
+            let past_edges = target - self.child_viewport.origin();
+            if past_edges != Vec2::ZERO && self.set_stretch(self.stretch + past_edges) {
+                any_change = Some(());
+            }
 
             // Check if the scroll bars now hover
             self.update_hover_states(cx.app_state, pointer_event.pos);
@@ -1004,6 +1529,39 @@ impl View for Scroll {
             };
         }
 
+        // Started after the children, so that a child which handles the middle click wins
+        if let Event::PointerDown(pointer_event) = &event {
+            if pointer_event.button.is_auxiliary()
+                && self.scroll_style.middle_click_autoscroll()
+                && self.can_scroll() != (false, false)
+            {
+                self.start_autoscroll(pointer_event.pos);
+                cx.update_active(self.id());
+                return EventPropagation::Stop;
+            }
+        }
+
+        // Handled after the children, so nested scroll views get to scroll first
+        if let Event::PointerMove(pointer_event) = &event {
+            if let Some(drag) = self.touch_drag.as_mut() {
+                let delta = drag.start - pointer_event.pos;
+                if !drag.scrolling && delta.hypot() > TOUCH_SLOP {
+                    drag.scrolling = true;
+                    // Take the touch from the pressed content
+                    cx.update_active(self.id());
+                }
+                if drag.scrolling {
+                    let origin = (drag.start_offset + delta).to_point();
+                    self.clamp_child_viewport(
+                        cx.app_state,
+                        self.child_viewport.with_origin(origin),
+                    );
+                    self.set_stretch(origin - self.child_viewport.origin());
+                    return EventPropagation::Stop;
+                }
+            }
+        }
+
         EventPropagation::Continue
     }
 
@@ -1021,13 +1579,17 @@ impl View for Scroll {
                 cx.clip(&self.total_rect);
             }
         }
-        cx.offset((-self.child_viewport.x0, -self.child_viewport.y0));
+        let origin = self.child_viewport.origin() + self.overscroll();
+        cx.offset((-origin.x, -origin.y));
         cx.paint_view(self.child);
         cx.restore();
 
         if !self.scroll_style.hide_bar() {
             self.draw_bars(cx);
         }
+        if let Some(autoscroll) = self.autoscroll {
+            self.draw_autoscroll_marker(cx, autoscroll.origin);
+        }
     }
 }
 /// Represents a custom style for a `Scroll`.
@@ -1152,18 +1714,76 @@ impl ScrollCustomStyle {
         self
     }
 
+    /// Sets whether scrolling past the edges of the content stretches it.
+    pub fn elastic_overscroll(mut self, elastic: impl Into<bool>) -> Self {
+        self = Self(self.0.set(ElasticOverscroll, elastic));
+        self
+    }
+
+    /// Sets how hard the content is to stretch past its edges, from 0 to 1.
+    pub fn overscroll_resistance(mut self, resistance: f64) -> Self {
+        self = Self(self.0.set(OverscrollResistance, resistance));
+        self
+    }
+
+    /// Sets the extra space the content can be scrolled by above its top and below its bottom.
+    pub fn content_insets(mut self, top: impl Into<Px>, bottom: impl Into<Px>) -> Self {
+        self = Self(
+            self.0
+                .set(ContentInsetTop, top)
+                .set(ContentInsetBottom, bottom),
+        );
+        self
+    }
+
+    /// Sets the minimum length of the handles.
+    pub fn min_thumb_size(mut self, size: impl Into<Px>) -> Self {
+        self = Self(self.0.set(MinThumbSize, size));
+        self
+    }
+
+    /// Sets whether the bars are only shown while scrolling or hovered, and fade out after.
+    pub fn auto_hide_bars(mut self, auto_hide: impl Into<bool>) -> Self {
+        self = Self(self.0.set(AutoHideBars, auto_hide));
+        self
+    }
+
+    /// Sets whether the bars are painted over the content or next to it.
+    pub fn scrollbar_mode(mut self, mode: ScrollbarMode) -> Self {
+        self = Self(self.0.set(ScrollbarModeProp, mode));
+        self
+    }
+
+    /// Sets what a click on a track, outside of its handle, does.
+    pub fn track_click(mut self, track_click: TrackClick) -> Self {
+        self = Self(self.0.set(TrackClickProp, track_click));
+        self
+    }
+
     /// Controls the visibility of the scroll bars.
     pub fn hide_bars(mut self, hide: impl Into<bool>) -> Self {
         self = Self(self.0.set(HideBars, hide));
         self
     }
 
+    /// Sets whether dragging the content with a touch scrolls it.
+    pub fn touch_scroll(mut self, touch_scroll: impl Into<bool>) -> Self {
+        self = Self(self.0.set(TouchScroll, touch_scroll));
+        self
+    }
+
     /// Sets whether the pointer wheel events should be propagated.
     pub fn propagate_pointer_wheel(mut self, propagate: impl Into<bool>) -> Self {
         self = Self(self.0.set(PropagatePointerWheel, propagate));
         self
     }
 
+    /// Sets whether pressing the middle button starts scrolling towards the pointer.
+    pub fn middle_click_autoscroll(mut self, autoscroll: impl Into<bool>) -> Self {
+        self = Self(self.0.set(MiddleClickAutoscroll, autoscroll));
+        self
+    }
+
     /// Sets whether vertical scrolling should be interpreted as horizontal scrolling.
     pub fn vertical_scroll_as_horizontal(mut self, vert_as_horiz: impl Into<bool>) -> Self {
         self = Self(self.0.set(VerticalScrollAsHorizontal, vert_as_horiz));
@@ -1182,3 +1802,54 @@ impl<T: IntoView + 'static> ScrollExt for T {
         scroll(self)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::Cell, rc::Rc, time::Duration};
+
+    use peniko::kurbo::{Point, Rect};
+
+    use crate::{
+        event::{EventListener, EventPropagation},
+        pointer::MouseButton,
+        testing::TestHarness,
+        views::{empty, Decorators},
+    };
+
+    use super::scroll;
+
+    /// Presses the middle button in a scroll view and moves the pointer down, and returns the
+    /// viewport once the autoscroll had time to step.
+    fn middle_click_scroll(child_handles_click: bool) -> Rect {
+        let viewport = Rc::new(Cell::new(Rect::ZERO));
+        let mut harness = TestHarness::new({
+            let viewport = viewport.clone();
+            move || {
+                scroll(empty().style(|s| s.size(100.0, 1000.0)).on_event(
+                    EventListener::PointerDown,
+                    move |_| {
+                        if child_handles_click {
+                            EventPropagation::Stop
+                        } else {
+                            EventPropagation::Continue
+                        }
+                    },
+                ))
+                .scroll_style(|s| s.middle_click_autoscroll(true))
+                .on_scroll(move |rect| viewport.set(rect))
+                .style(|s| s.size(100.0, 100.0))
+            }
+        });
+        harness.pointer_down(Point::new(50.0, 20.0), MouseButton::Auxiliary);
+        harness.pointer_move(Point::new(50.0, 80.0));
+        harness.advance_time(Duration::from_millis(100));
+        viewport.get()
+    }
+
+    #[test]
+    fn middle_click_autoscroll() {
+        assert!(middle_click_scroll(false).y0 > 0.0);
+        // The child which handles the middle click wins
+        assert_eq!(middle_click_scroll(true).y0, 0.0);
+    }
+}
@@ -0,0 +1,115 @@
+//! Button groups, whose buttons share their borders, and segmented controls selecting one of
+//! their segments.
+use std::fmt::Display;
+
+use accesskit::Role;
+use floem_reactive::{RwSignal, SignalUpdate, SignalWith};
+
+use crate::{
+    nav::FocusGroup,
+    style_class,
+    view::IntoView,
+    views::{clip, h_stack_from_iter, label, Clip, Decorators},
+};
+
+use super::radio_button::select_with_arrows;
+
+style_class!(
+    /// Style class of a button group or segmented control, which draws the border around
+    /// its buttons
+    pub ButtonGroupClass
+);
+
+style_class!(
+    /// Style class of the segments of a segmented control
+    pub SegmentClass
+);
+
+style_class!(
+    /// Style class of the selected segment of a segmented control
+    pub SegmentSelectedClass
+);
+
+/// Renders views, usually buttons, side by side inside a single rounded border.
+///
+/// The borders and corner radius of the views are removed, and a line separates each view
+/// from the previous one.
+/// ```rust
+/// use floem::views::{button, button_group};
+///
+/// button_group([button("Cut"), button("Copy"), button("Paste")]);
+/// ```
+pub fn button_group<V: IntoView + 'static>(children: impl IntoIterator<Item = V>) -> Clip {
+    clip(h_stack_from_iter(children.into_iter().enumerate().map(
+        |(i, child)| {
+            child.into_view().style(move |s| {
+                s.border(0.0)
+                    .border_radius(0.0)
+                    .apply_if(i > 0, |s| s.border_left(1.0))
+            })
+        },
+    )))
+    .class(ButtonGroupClass)
+}
+
+/// Renders a segmented control: a [`button_group`] of labeled segments, of which the one
+/// whose value is in `selected` is highlighted. Clicking a segment selects its value.
+///
+/// <kbd>Tab</kbd> stops at the control once, and the arrow keys select the previous or next
+/// segment.
+/// ```rust
+/// use floem::{reactive::RwSignal, views::segmented};
+///
+/// #[derive(Clone, Copy, PartialEq)]
+/// enum Layout {
+///     List,
+///     Grid,
+/// }
+///
+/// let layout = RwSignal::new(Layout::List);
+/// segmented(layout, [(Layout::List, "List"), (Layout::Grid, "Grid")]);
+/// ```
+pub fn segmented<T, S>(selected: RwSignal<T>, options: impl IntoIterator<Item = (T, S)>) -> Clip
+where
+    T: PartialEq + Clone + 'static,
+    S: Display + Clone + 'static,
+{
+    let (values, segments): (Vec<T>, Vec<_>) = options
+        .into_iter()
+        .map(|(value, text)| {
+            let is_selected = {
+                let value = value.clone();
+                move || selected.with(|selected| *selected == value)
+            };
+            let segment = label(move || text.clone())
+                .class(SegmentClass)
+                .class_if(is_selected.clone(), SegmentSelectedClass)
+                .keyboard_navigable()
+                .accessibility_role(Role::RadioButton)
+                .accessibility_toggled(is_selected)
+                .on_click_stop({
+                    let value = value.clone();
+                    move |_| selected.set(value.clone())
+                })
+                .into_any();
+            (value, segment)
+        })
+        .unzip();
+    button_group(select_with_arrows(selected, values, segments))
+        .focus_group(FocusGroup::horizontal().wrap(true))
+        .accessibility_role(Role::RadioGroup)
+}
+
+#[cfg(test)]
+mod tests {
+    use floem_reactive::{RwSignal, SignalGet};
+
+    use super::segmented;
+
+    #[test]
+    fn segmented_keeps_selection() {
+        let selected = RwSignal::new("b");
+        let _control = segmented(selected, [("a", "A"), ("b", "B")]);
+        assert_eq!(selected.get(), "b");
+    }
+}
@@ -0,0 +1,91 @@
+//! Skeleton placeholders shown while content is loading, and [`suspense`] to swap them for
+//! the content once it's ready.
+use floem_reactive::{ReadSignal, SignalGet};
+use peniko::{color::palette, Color};
+
+use crate::{
+    style_class,
+    unit::{DurationUnitExt, PxPctAuto, UnitExt},
+    view::{AnyView, IntoView},
+    views::{dyn_container, empty, v_stack_from_iter, Decorators, DynamicContainer, Empty, Stack},
+};
+
+style_class!(
+    /// Style class of skeleton placeholders, whose background shimmers
+    pub SkeletonClass
+);
+
+/// The color the background of skeletons shimmers towards.
+const SHIMMER_COLOR: Color = palette::css::WHITE_SMOKE;
+
+/// An empty view with the [`SkeletonClass`] and a shimmer animation.
+pub fn skeleton() -> Empty {
+    empty().class(SkeletonClass).animation(|a| {
+        a.keyframe(0, |f| f.computed_style())
+            .keyframe(100, |f| {
+                f.style(|s| s.background(SHIMMER_COLOR)).ease_in_out()
+            })
+            .duration(800.millis())
+            .repeat(true)
+            .auto_reverse(true)
+    })
+}
+
+/// A skeleton standing for a line of text, as wide as its container.
+pub fn skeleton_line() -> Empty {
+    skeleton().style(|s| s.width_full().height(12.0).border_radius(4.0))
+}
+
+/// Skeletons standing for a paragraph of `lines` lines of text, the last one shorter.
+pub fn skeleton_lines(lines: usize) -> Stack {
+    v_stack_from_iter((0..lines).map(move |i| {
+        skeleton_line()
+            .style(move |s| s.apply_if(i + 1 == lines && lines > 1, |s| s.width(60.pct())))
+    }))
+    .style(|s| s.width_full().row_gap(8.0))
+}
+
+/// A round skeleton, standing for an avatar or an icon.
+pub fn skeleton_circle(diameter: f64) -> Empty {
+    skeleton().style(move |s| s.size(diameter, diameter).border_radius(50.pct()))
+}
+
+/// A rectangular skeleton, standing for an image or a card.
+pub fn skeleton_rect(
+    width: impl Into<PxPctAuto> + Copy + 'static,
+    height: impl Into<PxPctAuto> + Copy + 'static,
+) -> Empty {
+    skeleton().style(move |s| s.width(width).height(height).border_radius(4.0))
+}
+
+/// Shows the view created by `skeleton` until `resource` has a value, then the view created
+/// by `content` from the value.
+///
+/// ```rust,ignore
+/// let user = create_resource(move || user_id.get(), |id| async move { fetch_user(id).await });
+/// suspense(
+///     user,
+///     || (skeleton_circle(32.0), skeleton_lines(2)).h_stack(),
+///     |user| label(move || user.name.clone()),
+/// );
+/// ```
+pub fn suspense<T, S, C>(
+    resource: ReadSignal<Option<T>>,
+    skeleton: impl Fn() -> S + 'static,
+    content: impl Fn(T) -> C + 'static,
+) -> DynamicContainer<Option<T>>
+where
+    T: Clone + 'static,
+    S: IntoView + 'static,
+    C: IntoView + 'static,
+{
+    dyn_container(
+        move || resource.get(),
+        move |value| -> AnyView {
+            match value {
+                Some(value) => content(value).into_any(),
+                None => skeleton().into_any(),
+            }
+        },
+    )
+}
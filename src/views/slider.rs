@@ -1,5 +1,6 @@
 //! A toggle button widget. An example can be found in widget-gallery/button in the floem examples.
 
+use accesskit::{Action, ActionData, Role};
 use floem_reactive::{create_updater, SignalGet, SignalUpdate};
 use peniko::color::palette;
 use peniko::kurbo::{Circle, Point, RoundedRect};
@@ -152,6 +153,18 @@ impl View for Slider {
                 self.held = false;
                 false
             }
+            crate::event::Event::AccessibilityAction(request) => {
+                self.id.request_layout();
+                match (request.action, &request.data) {
+                    (Action::Increment, _) => self.percent += 10.,
+                    (Action::Decrement, _) => self.percent -= 10.,
+                    (Action::SetValue, Some(ActionData::NumericValue(value))) => {
+                        self.percent = *value
+                    }
+                    _ => {}
+                }
+                true
+            }
             crate::event::Event::KeyDown(event) => {
                 if event.key.logical_key == Key::Named(NamedKey::ArrowLeft) {
                     self.id.request_layout();
@@ -182,6 +195,17 @@ impl View for Slider {
         EventPropagation::Continue
     }
 
+    fn accessibility(&self, node: &mut accesskit::Node) {
+        node.set_role(Role::Slider);
+        node.set_numeric_value(self.percent);
+        node.set_min_numeric_value(0.);
+        node.set_max_numeric_value(100.);
+        node.set_numeric_value_step(10.);
+        node.add_action(Action::Increment);
+        node.add_action(Action::Decrement);
+        node.add_action(Action::SetValue);
+    }
+
     fn style_pass(&mut self, cx: &mut crate::context::StyleCx<'_>) {
         let style = cx.style();
         let mut paint = false;
@@ -586,6 +610,7 @@ mod test {
             pos: Point::new(75.0, 10.0),
             button: PointerButton::Mouse(MouseButton::Primary),
             modifiers: Default::default(),
+            pen: None,
         });
 
         slider.event_before_children(&mut cx, &pointer_down);
@@ -612,6 +637,7 @@ mod test {
             button: PointerButton::Mouse(MouseButton::Primary),
             count: 1,
             modifiers: Default::default(),
+            pen: None,
         });
 
         slider.event_before_children(&mut cx, &pointer_down);
@@ -622,6 +648,7 @@ mod test {
         let pointer_move = Event::PointerMove(PointerMoveEvent {
             pos: Point::new(75.0, 10.0),
             modifiers: Default::default(),
+            pen: None,
         });
 
         slider.event_before_children(&mut cx, &pointer_move);
@@ -633,6 +660,7 @@ mod test {
             button: PointerButton::Mouse(MouseButton::Primary),
             count: 1,
             modifiers: Default::default(),
+            pen: None,
         });
 
         slider.event_before_children(&mut cx, &pointer_up);
@@ -663,6 +691,7 @@ mod test {
             button: PointerButton::Mouse(MouseButton::Primary),
             count: 1,
             modifiers: Default::default(),
+            pen: None,
         });
 
         slider.event_before_children(&mut cx, &pointer_event);
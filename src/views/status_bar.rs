@@ -0,0 +1,285 @@
+//! A status bar with items aligned to its left, center and right, where the items which
+//! don't fit are moved to an overflow menu.
+use std::rc::Rc;
+
+use floem_reactive::{RwSignal, SignalGet, SignalUpdate};
+use peniko::kurbo::Rect;
+
+use crate::{
+    context::ComputeLayoutCx,
+    id::ViewId,
+    menu::{Menu, MenuItem},
+    style::CursorStyle,
+    style_class,
+    view::{default_compute_layout, IntoView, View},
+    views::{h_stack_from_iter, label, Decorators},
+};
+
+style_class!(
+    /// Style class of a status bar
+    pub StatusBarClass
+);
+
+style_class!(
+    /// Style class of the items of a status bar, including its overflow button
+    pub StatusBarItemClass
+);
+
+/// The width assumed for the overflow button before it was first shown.
+const OVERFLOW_WIDTH: f64 = 24.0;
+
+/// Where an item is placed in a [`status_bar`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StatusAlign {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
+
+/// An item of a [`status_bar`], created with [`status_item`].
+pub struct StatusItem {
+    text: Rc<dyn Fn() -> String>,
+    priority: i32,
+    align: StatusAlign,
+    on_click: Option<Rc<dyn Fn()>>,
+}
+
+/// An item of a [`status_bar`] showing a reactive text.
+pub fn status_item(text: impl Fn() -> String + 'static) -> StatusItem {
+    StatusItem {
+        text: Rc::new(text),
+        priority: 0,
+        align: StatusAlign::Left,
+        on_click: None,
+    }
+}
+
+impl StatusItem {
+    /// Sets the priority of the item, `0` by default. When the status bar is too narrow, items
+    /// with a lower priority are moved to the overflow menu first.
+    pub fn priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    pub fn align(mut self, align: StatusAlign) -> Self {
+        self.align = align;
+        self
+    }
+
+    pub fn left(self) -> Self {
+        self.align(StatusAlign::Left)
+    }
+
+    pub fn center(self) -> Self {
+        self.align(StatusAlign::Center)
+    }
+
+    pub fn right(self) -> Self {
+        self.align(StatusAlign::Right)
+    }
+
+    /// Sets a function called when the item, or its entry in the overflow menu, is clicked.
+    pub fn on_click(mut self, on_click: impl Fn() + 'static) -> Self {
+        self.on_click = Some(Rc::new(on_click));
+        self
+    }
+}
+
+struct ItemState {
+    id: ViewId,
+    priority: i32,
+    /// The width of the item when it was last shown
+    width: f64,
+    hidden: RwSignal<bool>,
+}
+
+/// A status bar, see [`status_bar`].
+pub struct StatusBar {
+    id: ViewId,
+    items: Vec<ItemState>,
+    overflow: ViewId,
+    overflow_width: f64,
+}
+
+/// A status bar showing items in three groups, aligned to its left, center and right.
+///
+/// When the items don't fit, those with the lowest [priority](StatusItem::priority) are hidden
+/// and listed in a menu opened by a `…` button at the end of the bar.
+/// ```rust
+/// use floem::views::{status_bar, status_item};
+///
+/// status_bar([
+///     status_item(|| "main".to_string()).priority(2),
+///     status_item(|| "Ln 12, Col 4".to_string()).right().priority(1),
+///     status_item(|| "UTF-8".to_string()).right(),
+///     status_item(|| "Rust".to_string())
+///         .right()
+///         .on_click(|| println!("select language")),
+/// ]);
+/// ```
+pub fn status_bar(items: impl IntoIterator<Item = StatusItem>) -> StatusBar {
+    let id = ViewId::new();
+    let mut groups = [Vec::new(), Vec::new(), Vec::new()];
+    let mut states = Vec::new();
+    let mut entries = Vec::new();
+    for item in items {
+        let hidden = RwSignal::new(false);
+        let text = item.text.clone();
+        let mut view = label(move || text())
+            .class(StatusBarItemClass)
+            .style(move |s| s.flex_shrink(0.0).apply_if(hidden.get(), |s| s.hide()));
+        if let Some(on_click) = item.on_click.clone() {
+            view = view
+                .style(|s| s.cursor(CursorStyle::Pointer))
+                .on_click_stop(move |_| on_click());
+        }
+        states.push(ItemState {
+            id: view.id(),
+            priority: item.priority,
+            width: 0.0,
+            hidden,
+        });
+        entries.push((hidden, item.text, item.on_click));
+        let group = match item.align {
+            StatusAlign::Left => 0,
+            StatusAlign::Center => 1,
+            StatusAlign::Right => 2,
+        };
+        groups[group].push(view.into_any());
+    }
+    let [left, center, right] = groups;
+
+    let entries = Rc::new(entries);
+    let overflow = label(|| "…".to_string())
+        .class(StatusBarItemClass)
+        .style({
+            let entries = entries.clone();
+            move |s| {
+                let any_hidden = entries.iter().any(|(hidden, _, _)| hidden.get());
+                s.flex_shrink(0.0)
+                    .cursor(CursorStyle::Pointer)
+                    .apply_if(!any_hidden, |s| s.hide())
+            }
+        })
+        .popout_menu(move || {
+            entries
+                .iter()
+                .filter(|(hidden, _, _)| hidden.get_untracked())
+                .fold(Menu::new(""), |menu, (_, text, on_click)| {
+                    let item = MenuItem::new(text());
+                    let item = match on_click.clone() {
+                        Some(on_click) => item.action(move || on_click()),
+                        None => item.enabled(false),
+                    };
+                    menu.entry(item)
+                })
+        });
+    let overflow_id = overflow.id();
+
+    id.set_children(vec![
+        h_stack_from_iter(left)
+            .style(|s| s.flex_grow(1.0).flex_basis(0.0).items_center())
+            .into_any(),
+        h_stack_from_iter(center)
+            .style(|s| s.items_center())
+            .into_any(),
+        h_stack_from_iter(right)
+            .style(|s| {
+                s.flex_grow(1.0)
+                    .flex_basis(0.0)
+                    .items_center()
+                    .justify_end()
+            })
+            .into_any(),
+        overflow.into_any(),
+    ]);
+    StatusBar {
+        id,
+        items: states,
+        overflow: overflow_id,
+        overflow_width: OVERFLOW_WIDTH,
+    }
+    .class(StatusBarClass)
+    .style(|s| s.flex_row().items_center().width_full())
+}
+
+/// Picks the items to hide so that the others fit in `available` space, given the priority
+/// and width of each item. Lower priorities are hidden first, and among items of the same
+/// priority the last ones.
+fn items_to_hide(items: &[(i32, f64)], available: f64, overflow_width: f64) -> Vec<bool> {
+    let mut hide = vec![false; items.len()];
+    let total: f64 = items.iter().map(|(_, width)| width).sum();
+    if total <= available {
+        return hide;
+    }
+    let mut excess = total + overflow_width - available;
+    let mut order: Vec<usize> = (0..items.len()).collect();
+    order.sort_by_key(|&i| (items[i].0, std::cmp::Reverse(i)));
+    for i in order {
+        if excess <= 0.0 {
+            break;
+        }
+        hide[i] = true;
+        excess -= items[i].1;
+    }
+    hide
+}
+
+impl View for StatusBar {
+    fn id(&self) -> ViewId {
+        self.id
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "StatusBar".into()
+    }
+
+    fn compute_layout(&mut self, cx: &mut ComputeLayoutCx) -> Option<Rect> {
+        for item in &mut self.items {
+            if !item.hidden.get_untracked() {
+                item.width = item.id.get_size().unwrap_or_default().width;
+            }
+        }
+        if let Some(size) = self.overflow.get_size().filter(|size| size.width > 0.0) {
+            self.overflow_width = size.width;
+        }
+
+        let widths: Vec<_> = self
+            .items
+            .iter()
+            .map(|item| (item.priority, item.width))
+            .collect();
+        let available = self.id.get_content_rect().width();
+        let hide = items_to_hide(&widths, available, self.overflow_width);
+        for (item, hide) in self.items.iter().zip(hide) {
+            if item.hidden.get_untracked() != hide {
+                item.hidden.set(hide);
+            }
+        }
+
+        default_compute_layout(self.id, cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::items_to_hide;
+
+    #[test]
+    fn priority_truncation() {
+        let items = [(1, 50.0), (0, 30.0), (2, 40.0), (0, 20.0)];
+        assert_eq!(items_to_hide(&items, 140.0, 10.0), [false; 4]);
+        // The last of the lowest priority items goes first, making room for the overflow button
+        assert_eq!(
+            items_to_hide(&items, 130.0, 10.0),
+            [false, false, false, true]
+        );
+        assert_eq!(
+            items_to_hide(&items, 100.0, 10.0),
+            [false, true, false, true]
+        );
+        assert_eq!(items_to_hide(&items, 30.0, 10.0), [true, true, true, true]);
+    }
+}
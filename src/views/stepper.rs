@@ -0,0 +1,124 @@
+//! A numeric stepper: a number input between buttons decrementing and incrementing it.
+use std::ops::RangeInclusive;
+
+use floem_reactive::{create_effect, RwSignal, SignalGet, SignalUpdate};
+
+use crate::{
+    event::{Event, EventListener, EventPropagation},
+    keyboard::{Key, NamedKey},
+    style_class,
+    views::{button, create_number_text, h_stack, text_input, Decorators, NumberFormat, Stack},
+};
+
+style_class!(
+    /// Style class of a stepper
+    pub StepperClass
+);
+
+style_class!(
+    /// Style class of the decrement and increment buttons of a stepper
+    pub StepperButtonClass
+);
+
+/// Adds `steps` times `step` to a value, rounded to a multiple of `step` and kept in `range`.
+fn step_value(value: f64, steps: f64, step: f64, range: &RangeInclusive<f64>) -> f64 {
+    let stepped = if step > 0.0 {
+        // Dividing the rounded number of steps avoids floating point errors like 0.1 + 0.2
+        ((value + steps * step) / step).round() / (1.0 / step)
+    } else {
+        value
+    };
+    stepped.clamp(*range.start(), *range.end())
+}
+
+/// Renders a number input between `−` and `+` buttons changing `value` by `step`, keeping it
+/// in `range`.
+///
+/// The arrow keys also step the value while the input is focused, and <kbd>Page Up</kbd> and
+/// <kbd>Page Down</kbd> by ten steps. The buttons are disabled at the ends of the range.
+/// ```rust
+/// use floem::{reactive::RwSignal, views::{stepper, NumberFormat}};
+///
+/// let quantity = RwSignal::new(1.0);
+/// stepper(quantity, 1.0, 0.0..=99.0, NumberFormat::new().decimals(0));
+/// ```
+pub fn stepper(
+    value: RwSignal<f64>,
+    step: f64,
+    range: RangeInclusive<f64>,
+    format: NumberFormat,
+) -> Stack {
+    let step_by = {
+        let range = range.clone();
+        move |steps: f64| {
+            value.update(|value| *value = step_value(*value, steps, step, &range));
+        }
+    };
+    // Values typed outside of the range are clamped
+    {
+        let range = range.clone();
+        create_effect(move |_| {
+            let current = value.get();
+            let clamped = current.clamp(*range.start(), *range.end());
+            if clamped != current {
+                value.set(clamped);
+            }
+        });
+    }
+
+    let text = create_number_text(value, format.clone());
+    let input = text_input(text)
+        .on_event_cont(EventListener::FocusLost, move |_| {
+            text.set(format.format(value.get_untracked()));
+        })
+        .on_event(EventListener::KeyDown, {
+            let step_by = step_by.clone();
+            move |event| {
+                let Event::KeyDown(event) = event else {
+                    return EventPropagation::Continue;
+                };
+                let steps = match event.key.logical_key {
+                    Key::Named(NamedKey::ArrowUp) => 1.0,
+                    Key::Named(NamedKey::ArrowDown) => -1.0,
+                    Key::Named(NamedKey::PageUp) => 10.0,
+                    Key::Named(NamedKey::PageDown) => -10.0,
+                    _ => return EventPropagation::Continue,
+                };
+                step_by(steps);
+                EventPropagation::Stop
+            }
+        })
+        .style(|s| s.width(64.0).border_radius(0.0));
+
+    let (min, max) = (*range.start(), *range.end());
+    let decrement = button("−")
+        .class(StepperButtonClass)
+        .action({
+            let step_by = step_by.clone();
+            move || step_by(-1.0)
+        })
+        .disabled(move || value.get() <= min);
+    let increment = button("+")
+        .class(StepperButtonClass)
+        .action(move || step_by(1.0))
+        .disabled(move || value.get() >= max);
+
+    h_stack((decrement, input, increment))
+        .class(StepperClass)
+        .style(|s| s.items_center())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::step_value;
+
+    #[test]
+    fn stepping() {
+        let range = 0.0..=1.0;
+        assert_eq!(step_value(0.1, 2.0, 0.1, &range), 0.3);
+        assert_eq!(step_value(0.95, 1.0, 0.1, &range), 1.0);
+        assert_eq!(step_value(0.05, -1.0, 0.1, &range), 0.0);
+        assert_eq!(step_value(0.26, 1.0, 0.25, &range), 0.5);
+        assert_eq!(step_value(0.5, 1.0, 0.0, &range), 0.5);
+    }
+}
@@ -0,0 +1,283 @@
+//! Views showing content depending on a condition or a value, with transitions between them.
+use std::{any::Any, rc::Rc};
+
+use floem_reactive::{
+    as_child_of_current_scope, create_updater, with_scope, RwSignal, Scope, SignalGet, SignalUpdate,
+};
+use peniko::kurbo::Rect;
+
+use crate::{
+    animate::Animation,
+    context::{animations_on_create, ComputeLayoutCx, UpdateCx},
+    id::ViewId,
+    view::{default_compute_layout, AnyView, IntoView, View},
+    view_state::IsHiddenState,
+    views::{empty, Decorators},
+};
+
+type ArmFn<T> = dyn Fn(T) -> (AnyView, Scope);
+type TransitionFn = dyn Fn(Animation) -> Animation;
+
+enum SwitchMessage<T> {
+    /// The value changed
+    Value(T),
+    /// The exit transitions finished, so the arm of the next value can be shown
+    Show,
+}
+
+/// A view created for a value of a [`switch`].
+struct Arm<T> {
+    value: T,
+    id: ViewId,
+    scope: Scope,
+}
+
+/// A view showing one of several views depending on a value, see [`switch`] and [`show`].
+pub struct Switch<T: 'static> {
+    id: ViewId,
+    arm_fn: Box<ArmFn<T>>,
+    arms: Vec<Arm<T>>,
+    /// The arm which is shown, the other arms are hidden
+    shown: RwSignal<Option<ViewId>>,
+    /// The value whose arm is shown once the exit transition of the previous arm finished
+    next: Option<T>,
+    keep_alive: bool,
+    transition: Option<Rc<TransitionFn>>,
+    /// Whether a message to show the next arm was sent and not handled yet
+    pending: bool,
+}
+
+/// Shows the view returned by `arms` for the current value of `value`, and replaces it with
+/// the view for the new value whenever `value` changes to a different value.
+///
+/// Unlike a [`dyn_container`](crate::views::dyn_container), the views can be kept alive while
+/// they're hidden with [`Switch::keep_alive`], and [`Switch::transition`] animates them when
+/// they're shown and hidden.
+/// ```rust
+/// use floem::{
+///     reactive::{RwSignal, SignalGet},
+///     views::{label, switch, text_input},
+///     IntoView,
+/// };
+///
+/// #[derive(Clone, Copy, PartialEq)]
+/// enum Page {
+///     Search,
+///     About,
+/// }
+///
+/// let page = RwSignal::new(Page::Search);
+/// switch(move || page.get(), |page| match page {
+///     // The query is still there when coming back to the search page
+///     Page::Search => text_input(RwSignal::new(String::new())).into_any(),
+///     Page::About => label(|| "About").into_any(),
+/// })
+/// .keep_alive(true)
+/// .transition(|a| a.scale_effect());
+/// ```
+pub fn switch<T, V>(value: impl Fn() -> T + 'static, arms: impl Fn(T) -> V + 'static) -> Switch<T>
+where
+    T: PartialEq + Clone + 'static,
+    V: IntoView + 'static,
+{
+    let id = ViewId::new();
+    let initial = create_updater(value, move |value| {
+        id.update_state(SwitchMessage::Value(value))
+    });
+    // The first arm is created on the first layout, so that it gets the transition
+    Switch {
+        id,
+        arm_fn: Box::new(as_child_of_current_scope(move |value| {
+            arms(value).into_any()
+        })),
+        arms: Vec::new(),
+        shown: RwSignal::new(None),
+        next: Some(initial),
+        keep_alive: false,
+        transition: None,
+        pending: false,
+    }
+}
+
+/// Shows the view returned by `view_fn` while `when` returns `true`, like a [`switch`] whose
+/// view is empty when `when` returns `false`.
+/// ```rust
+/// use floem::{reactive::{RwSignal, SignalGet}, views::{label, show}};
+///
+/// let details = RwSignal::new(false);
+/// show(move || details.get(), || label(|| "Details"))
+///     .transition(|a| a.scale_effect());
+/// ```
+pub fn show<V: IntoView + 'static>(
+    when: impl Fn() -> bool + 'static,
+    view_fn: impl Fn() -> V + 'static,
+) -> Switch<bool> {
+    switch(when, move |shown| {
+        if shown {
+            view_fn().into_any()
+        } else {
+            empty().into_any()
+        }
+    })
+}
+
+impl<T: 'static> Switch<T> {
+    /// Sets whether the views which aren't shown are kept alive, `false` by default.
+    ///
+    /// When `false`, a view is dropped, along with its signals and effects, when the value
+    /// changes, and created again when the value changes back. When `true`, the view is hidden
+    /// and shown again with its state, like a scroll position or the text of an input.
+    pub fn keep_alive(mut self, keep_alive: bool) -> Self {
+        self.keep_alive = keep_alive;
+        self
+    }
+
+    /// Adds an animation to the views, which is run when they're shown and hidden if it's set
+    /// to run on creation and removal, like the animations built with
+    /// [`Animation::view_transition`].
+    ///
+    /// The new view is shown once the transition of the previous view finished.
+    pub fn transition(mut self, transition: impl Fn(Animation) -> Animation + 'static) -> Self {
+        self.transition = Some(Rc::new(transition));
+        self
+    }
+}
+
+impl<T: PartialEq + Clone + 'static> Switch<T> {
+    fn create_arm(&mut self, value: T) -> ViewId {
+        let (child, scope) = (self.arm_fn)(value.clone());
+        let child_id = child.id();
+        let shown = self.shown;
+        let transition = self.transition.clone();
+        let child = with_scope(scope, move || {
+            let child =
+                child.style(move |s| s.apply_if(shown.get() != Some(child_id), |s| s.hide()));
+            match transition {
+                Some(transition) => child.animation(move |a| transition(a)),
+                None => child,
+            }
+        });
+        self.id.add_child(child);
+        self.arms.push(Arm {
+            value,
+            id: child_id,
+            scope,
+        });
+        animations_on_create(child_id);
+        child_id
+    }
+
+    fn show_next(&mut self, cx: &mut UpdateCx) {
+        let Some(value) = self.next.take() else {
+            return;
+        };
+        if !self.keep_alive {
+            for arm in self.arms.drain(..) {
+                cx.app_state_mut().remove_view(arm.id);
+                arm.scope.dispose();
+            }
+            self.id.set_children_ids(Vec::new());
+        }
+        let arm = match self.arms.iter().find(|arm| arm.value == value) {
+            // The arm runs its transition when it's no longer hidden
+            Some(arm) => arm.id,
+            None => self.create_arm(value),
+        };
+        self.shown.set(Some(arm));
+        self.id.request_all();
+    }
+}
+
+impl<T: PartialEq + Clone + 'static> View for Switch<T> {
+    fn id(&self) -> ViewId {
+        self.id
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "Switch".into()
+    }
+
+    fn update(&mut self, cx: &mut UpdateCx, state: Box<dyn Any>) {
+        let Ok(message) = state.downcast::<SwitchMessage<T>>() else {
+            return;
+        };
+        match *message {
+            SwitchMessage::Value(value) => {
+                let shown = self.shown.get_untracked();
+                let is_shown = self
+                    .arms
+                    .iter()
+                    .any(|arm| Some(arm.id) == shown && arm.value == value);
+                if is_shown {
+                    self.next = None;
+                } else {
+                    // Hiding the shown arm starts its exit transition
+                    self.shown.set(None);
+                    self.next = Some(value);
+                    self.id.request_all();
+                }
+            }
+            SwitchMessage::Show => {
+                self.pending = false;
+                self.show_next(cx);
+            }
+        }
+    }
+
+    fn compute_layout(&mut self, cx: &mut ComputeLayoutCx) -> Option<Rect> {
+        if self.next.is_some() && !self.pending {
+            let animating_out = self.arms.iter().any(|arm| {
+                matches!(
+                    arm.id.state().borrow().is_hidden_state,
+                    IsHiddenState::AnimatingOut(_)
+                )
+            });
+            if !animating_out {
+                self.pending = true;
+                self.id.update_state(SwitchMessage::<T>::Show);
+            }
+        }
+        default_compute_layout(self.id, cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::Cell, rc::Rc};
+
+    use floem_reactive::{RwSignal, SignalGet, SignalUpdate};
+
+    use crate::{testing::TestHarness, views::empty};
+
+    use super::switch;
+
+    fn creations(keep_alive: bool) -> usize {
+        let created = Rc::new(Cell::new(0));
+        let value = RwSignal::new(0);
+        let mut harness = TestHarness::new({
+            let created = created.clone();
+            move || {
+                switch(
+                    move || value.get(),
+                    move |_| {
+                        created.set(created.get() + 1);
+                        empty()
+                    },
+                )
+                .keep_alive(keep_alive)
+            }
+        });
+        assert_eq!(harness.root().children().len(), 1);
+        for i in [1, 0, 1] {
+            value.set(i);
+            harness.update();
+        }
+        created.get()
+    }
+
+    #[test]
+    fn keep_alive() {
+        assert_eq!(creations(false), 4);
+        assert_eq!(creations(true), 2);
+    }
+}
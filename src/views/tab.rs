@@ -101,6 +101,10 @@ impl<T> View for Tab<T> {
         format!("Tab: {}", self.active).into()
     }
 
+    fn accessibility(&self, node: &mut accesskit::Node) {
+        node.set_role(accesskit::Role::TabPanel);
+    }
+
     fn update(&mut self, cx: &mut UpdateCx, state: Box<dyn std::any::Any>) {
         if let Ok(state) = state.downcast::<TabState<T>>() {
             match *state {
@@ -9,10 +9,11 @@ use lapce_xi_rope::Rope;
 use crate::{
     id::ViewId,
     keyboard::Modifiers,
-    style::{CursorColor, Style},
+    style::{CursorColor, SelectionCornerRadius, Style},
     view::{IntoView, View},
     views::editor::{
         command::CommandExecuted,
+        comments::CommentThreads,
         id::EditorId,
         keypress::default_key_handler,
         text::{Document, SimpleStyling, Styling},
@@ -25,12 +26,13 @@ use crate::{
 use super::editor::{
     gutter::{DimColor, GutterClass, LeftOfCenterPadding, RightOfCenterPadding},
     keypress::press::KeyPress,
-    text::{RenderWhitespace, WrapMethod},
+    text::{CaretShape, RenderWhitespace, WrapMethod},
     view::EditorViewClass,
-    CurrentLineColor, CursorSurroundingLines, IndentGuideColor, IndentStyleProp, Modal,
-    ModalRelativeLine, PhantomColor, PlaceholderColor, PreeditUnderlineColor, RenderWhitespaceProp,
-    ScrollBeyondLastLine, SelectionColor, ShowIndentGuide, SmartTab, VisibleWhitespaceColor,
-    WrapProp,
+    CaretShapeProp, CaretWidth, CurrentLineColor, CursorSurroundingLines, IndentGuideColor,
+    IndentStyleProp, LogicalLineMovement, Modal, ModalRelativeLine, NormalCaretShape,
+    OverwriteCaretShape, PhantomColor, PlaceholderColor, PreeditUnderlineColor,
+    RenderWhitespaceProp, ScrollBeyondLastLine, SelectionBorderColor, SelectionColor,
+    ShowIndentGuide, SmartTab, VisibleWhitespaceColor, WrapProp,
 };
 
 /// A text editor view.
@@ -205,6 +207,22 @@ impl EditorCustomStyle {
         self
     }
 
+    /// Sets the color of the border drawn around selected text, which has no border by default.
+    pub fn selection_border_color(mut self, color: Color) -> Self {
+        self.0 = self.0.class(EditorViewClass, |s| {
+            s.set(SelectionBorderColor, Some(color))
+        });
+        self
+    }
+
+    /// Sets the radius of the rounded corners of selections.
+    pub fn selection_corner_radius(mut self, radius: f64) -> Self {
+        self.0 = self
+            .0
+            .class(EditorViewClass, |s| s.set(SelectionCornerRadius, radius));
+        self
+    }
+
     /// Sets the indent style.
     pub fn indent_style(mut self, indent_style: IndentStyle) -> Self {
         self.0 = self
@@ -234,6 +252,36 @@ impl EditorCustomStyle {
         self
     }
 
+    /// Sets the shape of the carets in insert mode.
+    pub fn caret_shape(mut self, shape: CaretShape) -> Self {
+        self.0 = self
+            .0
+            .class(EditorViewClass, |s| s.set(CaretShapeProp, shape));
+        self
+    }
+
+    /// Sets the shape of the carets in the normal and visual modes of modal editors.
+    pub fn normal_caret_shape(mut self, shape: CaretShape) -> Self {
+        self.0 = self
+            .0
+            .class(EditorViewClass, |s| s.set(NormalCaretShape, shape));
+        self
+    }
+
+    /// Sets the shape of the carets in overwrite mode.
+    pub fn overwrite_caret_shape(mut self, shape: CaretShape) -> Self {
+        self.0 = self
+            .0
+            .class(EditorViewClass, |s| s.set(OverwriteCaretShape, shape));
+        self
+    }
+
+    /// Sets the width of bar carets, or the thickness of underline carets.
+    pub fn caret_width(mut self, width: f64) -> Self {
+        self.0 = self.0.class(EditorViewClass, |s| s.set(CaretWidth, width));
+        self
+    }
+
     /// Allow scrolling beyond the last line of the document.
     pub fn scroll_beyond_last_line(mut self, scroll_beyond: bool) -> Self {
         self.0 = self.0.class(EditorViewClass, |s| {
@@ -242,6 +290,15 @@ impl EditorCustomStyle {
         self
     }
 
+    /// Sets whether up/down and home/end move by logical lines, ignoring the wrapping of the
+    /// lines. Default: `false`, moving by visual lines.
+    pub fn logical_line_movement(mut self, logical: bool) -> Self {
+        self.0 = self
+            .0
+            .class(EditorViewClass, |s| s.set(LogicalLineMovement, logical));
+        self
+    }
+
     /// Sets the background color of the current line.
     pub fn current_line_color(mut self, color: Color) -> Self {
         self.0 = self
@@ -529,4 +586,17 @@ impl TextEditor {
         }
         self
     }
+
+    /// Keep the anchors of the comment threads on their lines as the editor is edited.  
+    /// The threads are shown by an
+    /// [`editor_comment_margin`](super::editor::comments::editor_comment_margin) put beside the
+    /// editor.  
+    /// Note: only works for the default backing [`TextDocument`] doc
+    pub fn comment_threads(self, threads: CommentThreads) -> Self {
+        self.update(move |update| {
+            for delta in update.deltas() {
+                threads.apply_delta(delta);
+            }
+        })
+    }
 }
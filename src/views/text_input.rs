@@ -1,9 +1,10 @@
-use crate::action::exec_after;
+use crate::action::{exec_after, set_ime_allowed, set_ime_cursor_area, ImePurpose};
 use crate::event::{EventListener, EventPropagation};
 use crate::id::ViewId;
 use crate::keyboard::{self, KeyEvent, Modifiers};
-use crate::pointer::{MouseButton, PointerButton, PointerInputEvent};
+use crate::pointer::PointerButton;
 use crate::reactive::{create_effect, RwSignal};
+use crate::sound::{play_sound, SystemSound};
 use crate::style::{FontFamily, FontProps, PaddingLeft, SelectionStyle};
 use crate::style::{FontStyle, FontWeight, TextColor};
 use crate::unit::{PxPct, PxPctAuto};
@@ -111,6 +112,16 @@ pub struct TextInput {
     is_focused: bool,
     last_pointer_down: Point,
     last_cursor_action_on: Instant,
+    // The text being composed by the IME, shown at the cursor until it's committed
+    preedit: Option<Preedit>,
+    // The caret rect last reported to the IME, in window coordinates
+    ime_cursor_area: Option<Rect>,
+}
+
+struct Preedit {
+    text: String,
+    // Byte range of the IME cursor (or selection) within `text`
+    cursor: Option<(usize, usize)>,
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -130,6 +141,7 @@ pub enum TextDirection {
 pub fn text_input(buffer: RwSignal<String>) -> TextInput {
     let id = ViewId::new();
     let is_focused = create_rw_signal(false);
+    id.set_ime_allowed(true);
 
     {
         create_effect(move |_| {
@@ -167,6 +179,8 @@ pub fn text_input(buffer: RwSignal<String>) -> TextInput {
         is_focused: false,
         last_pointer_down: Point::ZERO,
         last_cursor_action_on: Instant::now(),
+        preedit: None,
+        ime_cursor_area: None,
     }
     .keyboard_navigable()
     .on_event_stop(EventListener::FocusGained, move |_| {
@@ -247,6 +261,14 @@ impl TextInput {
         self.placeholder_text = Some(text.into());
         self
     }
+
+    /// Sets the kind of text typed into the input, for the IME and the virtual keyboards.
+    ///
+    /// Default: [`ImePurpose::Normal`]
+    pub fn ime_purpose(self, purpose: ImePurpose) -> Self {
+        self.id.set_ime_purpose(purpose);
+        self
+    }
 }
 
 impl TextInput {
@@ -327,7 +349,7 @@ impl TextInput {
     fn clip_text(&mut self, node_layout: &Layout) {
         let virt_text = self.text_buf.as_mut().unwrap();
         let node_width = node_layout.size.width as f64;
-        let cursor_text_loc = Cursor::new(0, self.cursor_glyph_idx);
+        let cursor_text_loc = Cursor::new(0, self.display_cursor_idx());
         let layout_cursor = virt_text.layout_cursor(cursor_text_loc);
         let cursor_glyph_pos = virt_text.hit_position(layout_cursor.glyph);
         let cursor_x = cursor_glyph_pos.point.x;
@@ -354,8 +376,7 @@ impl TextInput {
             .index;
 
         let new_text = self
-            .buffer
-            .get_untracked()
+            .display_text()
             .chars()
             .skip(clip_start)
             .take(clip_end - clip_start)
@@ -502,12 +523,35 @@ impl TextInput {
         };
     }
 
+    /// The buffer with the IME preedit text inserted at the cursor.
+    fn display_text(&self) -> String {
+        let mut text = self.buffer.get_untracked();
+        if let Some(preedit) = &self.preedit {
+            text.insert_str(self.cursor_glyph_idx, &preedit.text);
+        }
+        text
+    }
+
+    /// The index of the cursor in [`Self::display_text`], which is the IME cursor while
+    /// composing.
+    fn display_cursor_idx(&self) -> usize {
+        match &self.preedit {
+            Some(preedit) => {
+                self.cursor_glyph_idx
+                    + preedit
+                        .cursor
+                        .map(|(start, _)| start)
+                        .unwrap_or(preedit.text.len())
+            }
+            None => self.cursor_glyph_idx,
+        }
+    }
+
     fn update_text_layout(&mut self) {
         let mut text_layout = TextLayout::new();
         let attrs_list = self.get_text_attrs();
 
-        self.buffer
-            .with_untracked(|buff| text_layout.set_text(buff, attrs_list.clone()));
+        text_layout.set_text(&self.display_text(), attrs_list.clone());
 
         let glyph_max_size = self.get_font_glyph_max_size();
         self.height = glyph_max_size.height as f32;
@@ -734,6 +778,8 @@ impl TextInput {
                     );
 
                     if self.cursor_glyph_idx == prev_cursor_idx {
+                        // Nothing is left to delete
+                        play_sound(SystemSound::Beep);
                         return false;
                     }
 
@@ -761,6 +807,7 @@ impl TextInput {
                 );
 
                 if self.cursor_glyph_idx == prev_cursor_idx {
+                    play_sound(SystemSound::Beep);
                     return false;
                 }
 
@@ -964,6 +1011,44 @@ impl TextInput {
     }
 }
 
+impl TextInput {
+    /// Report the caret rect to the platform IME, so that the candidate window is shown
+    /// next to the caret.
+    fn update_ime_cursor_area(&mut self, node_layout: &Layout) {
+        let window_origin = self.id.state().borrow().window_origin;
+        let cursor_rect = self.get_cursor_rect(node_layout) + window_origin.to_vec2();
+        if self.ime_cursor_area != Some(cursor_rect) {
+            self.ime_cursor_area = Some(cursor_rect);
+            set_ime_cursor_area(cursor_rect.origin(), cursor_rect.size());
+        }
+    }
+
+    fn paint_preedit_underline(&self, node_layout: &Layout, cx: &mut crate::context::PaintCx) {
+        let Some(preedit) = &self.preedit else {
+            return;
+        };
+        let text_buf = self.text_buf.as_ref().unwrap();
+        let start = text_buf.hit_position(self.cursor_glyph_idx).point.x;
+        let end = text_buf
+            .hit_position(self.cursor_glyph_idx + preedit.text.len())
+            .point
+            .x;
+        let location = node_layout.location;
+        let y = location.y as f64 + self.height as f64 - 1.0;
+        let color = self.style.color().unwrap_or(palette::css::BLACK);
+        let underline = Rect::new(
+            start - self.clip_start_x + location.x as f64,
+            y,
+            end - self.clip_start_x + location.x as f64,
+            y + 1.0,
+        );
+        cx.save();
+        cx.clip(&self.id.get_content_rect());
+        cx.fill(&underline, color, 0.0);
+        cx.restore();
+    }
+}
+
 fn replace_range(buff: &mut String, del_range: Range<usize>, replacement: Option<&str>) {
     assert!(del_range.start <= del_range.end);
     if !buff.is_char_boundary(del_range.end) {
@@ -1041,6 +1126,15 @@ impl View for TextInput {
         format!("TextInput: {:?}", self.buffer.get_untracked()).into()
     }
 
+    fn accessibility(&self, node: &mut accesskit::Node) {
+        node.set_role(accesskit::Role::TextInput);
+        node.set_value(self.buffer.last_buffer.clone());
+        if let Some(placeholder) = &self.placeholder_text {
+            node.set_placeholder(placeholder.clone());
+        }
+        node.add_action(accesskit::Action::SetValue);
+    }
+
     fn update(&mut self, cx: &mut UpdateCx, state: Box<dyn Any>) {
         if let Ok(state) = state.downcast::<(String, bool)>() {
             let (value, is_focused) = *state;
@@ -1068,13 +1162,14 @@ impl View for TextInput {
         }
 
         let is_handled = match &event {
-            // match on pointer primary button press
-            Event::PointerDown(
-                event @ PointerInputEvent {
-                    button: PointerButton::Mouse(MouseButton::Primary),
-                    ..
-                },
-            ) => {
+            // match on pointer primary button press, or a touch
+            Event::PointerDown(event) if event.button.is_primary() => {
+                if matches!(event.button, PointerButton::Touch { .. })
+                    && cx.app_state.is_focused(&self.id)
+                {
+                    // The virtual keyboard might have been dismissed while staying focused
+                    set_ime_allowed(true);
+                }
                 cx.update_active(self.id);
                 self.id.request_layout();
                 self.last_pointer_down = event.pos;
@@ -1106,6 +1201,28 @@ impl View for TextInput {
                 false
             }
             Event::KeyDown(event) => self.handle_key_down(cx, event),
+            Event::ImePreedit { text, cursor } if cx.app_state.is_focused(&self.id) => {
+                self.preedit = (!text.is_empty()).then(|| Preedit {
+                    text: text.clone(),
+                    cursor: *cursor,
+                });
+                true
+            }
+            Event::ImeCommit(text) if cx.app_state.is_focused(&self.id) => {
+                self.preedit = None;
+                self.insert_text(&SmolStr::from(text.as_str()))
+            }
+            Event::AccessibilityAction(accesskit::ActionRequest {
+                action: accesskit::Action::SetValue,
+                data: Some(accesskit::ActionData::Value(value)),
+                ..
+            }) => {
+                self.buffer.update(|buf| *buf = value.to_string());
+                self.cursor_glyph_idx = value.len();
+                self.selection = None;
+                self.update_text_layout();
+                true
+            }
             _ => false,
         };
 
@@ -1242,15 +1359,21 @@ impl View for TextInput {
                 .text_buf
                 .as_ref()
                 .unwrap()
-                .hit_position(self.cursor_glyph_idx);
+                .hit_position(self.display_cursor_idx());
             self.cursor_x = hit_pos.point.x;
         }
 
+        if self.is_focused {
+            self.update_ime_cursor_area(&node_layout);
+        } else {
+            self.ime_cursor_area = None;
+        }
+
         None
     }
 
     fn paint(&mut self, cx: &mut crate::context::PaintCx) {
-        if self.buffer.with_untracked(|buff| buff.is_empty()) {
+        if self.buffer.with_untracked(|buff| buff.is_empty()) && self.preedit.is_none() {
             if let Some(placeholder_buff) = &self.placeholder_buff {
                 self.paint_placeholder_text(placeholder_buff, cx);
             }
@@ -1268,7 +1391,7 @@ impl View for TextInput {
         let location = node_layout.location;
         let text_start_point = Point::new(location.x as f64, location.y as f64);
 
-        if self.buffer.with_untracked(|b| !b.is_empty()) {
+        if self.buffer.with_untracked(|b| !b.is_empty()) || self.preedit.is_some() {
             if let Some(clip_txt) = self.clip_txt_buf.as_mut() {
                 cx.draw_text(
                     clip_txt,
@@ -1302,6 +1425,10 @@ impl View for TextInput {
             self.paint_selection_rect(&node_layout, cx);
         }
 
+        if self.preedit.is_some() {
+            self.paint_preedit_underline(&node_layout, cx);
+        }
+
         let id = self.id();
         exec_after(Duration::from_millis(CURSOR_BLINK_INTERVAL_MS), move |_| {
             id.request_paint();
@@ -101,6 +101,16 @@ impl View for ToggleButton {
         "Toggle Button".into()
     }
 
+    fn accessibility(&self, node: &mut accesskit::Node) {
+        node.set_role(accesskit::Role::Switch);
+        node.set_toggled(if self.state {
+            accesskit::Toggled::True
+        } else {
+            accesskit::Toggled::False
+        });
+        node.add_action(accesskit::Action::Click);
+    }
+
     fn update(&mut self, _cx: &mut crate::context::UpdateCx, state: Box<dyn std::any::Any>) {
         if let Ok(state) = state.downcast::<bool>() {
             if self.held == ToggleState::Nothing {
@@ -191,6 +201,14 @@ impl View for ToggleButton {
             crate::event::Event::FocusLost => {
                 self.held = ToggleState::Nothing;
             }
+            crate::event::Event::AccessibilityAction(request)
+                if request.action == accesskit::Action::Click =>
+            {
+                if let Some(ontoggle) = &self.ontoggle {
+                    ontoggle(!self.state);
+                }
+                return EventPropagation::Stop;
+            }
             crate::event::Event::KeyDown(event) => {
                 if event.key.logical_key == Key::Named(NamedKey::Enter) {
                     if let Some(ontoggle) = &self.ontoggle {
@@ -0,0 +1,395 @@
+//! A toolbar of icon buttons, toggles and groups of toggles, where the items which don't fit
+//! are moved to an overflow menu.
+use std::rc::Rc;
+
+use accesskit::Role;
+use floem_reactive::{RwSignal, SignalGet, SignalUpdate, SignalWith};
+use peniko::kurbo::{Point, Rect};
+
+use crate::{
+    action::show_context_menu,
+    context::ComputeLayoutCx,
+    event::{EventListener, EventPropagation},
+    id::ViewId,
+    menu::{Menu, MenuItem},
+    nav::FocusGroup,
+    style::CursorStyle,
+    style_class,
+    view::{default_compute_layout, AnyView, IntoView, View},
+    views::{empty, h_stack_from_iter, label, tooltip, ContainerExt, Decorators},
+};
+
+style_class!(
+    /// Style class of a toolbar
+    pub ToolbarClass
+);
+
+style_class!(
+    /// Style class of the buttons of a toolbar, including its overflow button
+    pub ToolbarButtonClass
+);
+
+style_class!(
+    /// Style class of the toolbar buttons which are checked
+    pub ToolbarCheckedClass
+);
+
+style_class!(
+    /// Style class of a group of toggles in a toolbar
+    pub ToolbarGroupClass
+);
+
+style_class!(
+    /// Style class of the separators of a toolbar
+    pub ToolbarSeparatorClass
+);
+
+/// The width assumed for the overflow button before it was first shown.
+const OVERFLOW_WIDTH: f64 = 24.0;
+
+/// A button of a [`toolbar`], see [`tool_button`] and [`tool_toggle`].
+pub struct ToolButton {
+    icon: AnyView,
+    label: String,
+    action: Option<Rc<dyn Fn()>>,
+    checked: Option<Rc<dyn Fn() -> bool>>,
+}
+
+/// A toolbar button showing an icon, with a label shown in its tooltip and in the overflow
+/// menu.
+pub fn tool_button(icon: impl IntoView + 'static, label: impl Into<String>) -> ToolButton {
+    ToolButton {
+        icon: icon.into_any(),
+        label: label.into(),
+        action: None,
+        checked: None,
+    }
+}
+
+/// A toolbar button switching a signal on and off, shown as checked while it's on.
+pub fn tool_toggle(
+    icon: impl IntoView + 'static,
+    label: impl Into<String>,
+    checked: RwSignal<bool>,
+) -> ToolButton {
+    tool_button(icon, label)
+        .action(move || checked.update(|checked| *checked = !*checked))
+        .checked(move || checked.get())
+}
+
+impl ToolButton {
+    /// Sets the function called when the button, or its entry in the overflow menu, is clicked.
+    pub fn action(mut self, action: impl Fn() + 'static) -> Self {
+        self.action = Some(Rc::new(action));
+        self
+    }
+
+    /// Sets a reactive function deciding whether the button is shown as checked.
+    pub fn checked(mut self, checked: impl Fn() -> bool + 'static) -> Self {
+        self.checked = Some(Rc::new(checked));
+        self
+    }
+
+    fn build(self) -> (AnyView, MenuEntryFn) {
+        let ToolButton {
+            icon,
+            label: text,
+            action,
+            checked,
+        } = self;
+        let mut view = icon
+            .container()
+            .class(ToolbarButtonClass)
+            .keyboard_navigable()
+            .accessibility_role(Role::Button)
+            .style(|s| s.cursor(CursorStyle::Pointer));
+        if let Some(action) = action.clone() {
+            view = view.on_click_stop(move |_| action());
+        }
+        if let Some(checked) = checked.clone() {
+            view = view.class_if(move || checked(), ToolbarCheckedClass);
+        }
+        let tip = text.clone();
+        let view = tooltip(view, move || {
+            let tip = tip.clone();
+            label(move || tip.clone())
+        })
+        .into_any();
+
+        let entry: MenuEntryFn = Rc::new(move || {
+            let item = MenuItem::new(text.clone())
+                .checked(checked.as_ref().is_some_and(|checked| checked()));
+            match action.clone() {
+                Some(action) => item.action(move || action()),
+                None => item.enabled(false),
+            }
+        });
+        (view, entry)
+    }
+}
+
+type MenuEntryFn = Rc<dyn Fn() -> MenuItem>;
+
+enum ItemKind {
+    Buttons(Vec<ToolButton>),
+    Separator,
+}
+
+/// An item of a [`toolbar`]: a button, a group of toggles or a separator.
+pub struct ToolbarItem(ItemKind);
+
+impl From<ToolButton> for ToolbarItem {
+    fn from(button: ToolButton) -> Self {
+        ToolbarItem(ItemKind::Buttons(vec![button]))
+    }
+}
+
+/// A separator between the items of a [`toolbar`].
+pub fn tool_separator() -> ToolbarItem {
+    ToolbarItem(ItemKind::Separator)
+}
+
+/// A group of toolbar buttons of which one is checked: the one whose value is in `selected`.
+/// Clicking a button selects its value.
+/// ```rust
+/// use floem::{reactive::RwSignal, views::{tool_button, tool_toggle_group}};
+///
+/// let align = RwSignal::new("left");
+/// tool_toggle_group(
+///     align,
+///     [
+///         ("left", tool_button("⇤", "Align Left")),
+///         ("center", tool_button("↔", "Center")),
+///         ("right", tool_button("⇥", "Align Right")),
+///     ],
+/// );
+/// ```
+pub fn tool_toggle_group<T: PartialEq + Clone + 'static>(
+    selected: RwSignal<T>,
+    options: impl IntoIterator<Item = (T, ToolButton)>,
+) -> ToolbarItem {
+    let buttons = options
+        .into_iter()
+        .map(|(value, button)| {
+            let checked_value = value.clone();
+            button
+                .action(move || selected.set(value.clone()))
+                .checked(move || selected.with(|selected| *selected == checked_value))
+        })
+        .collect();
+    ToolbarItem(ItemKind::Buttons(buttons))
+}
+
+struct ItemState {
+    id: ViewId,
+    separator: bool,
+    /// The width of the item when it was last shown
+    width: f64,
+    hidden: RwSignal<bool>,
+}
+
+/// A toolbar, see [`toolbar`].
+pub struct Toolbar {
+    id: ViewId,
+    items: Vec<ItemState>,
+    overflow: ViewId,
+    overflow_width: f64,
+}
+
+/// A row of toolbar items. The items which don't fit are hidden and listed in a menu opened
+/// by a `»` button at the end of the toolbar.
+///
+/// The toolbar is a [focus group](FocusGroup): the left and right arrows move the focus
+/// between its buttons.
+/// ```rust
+/// use floem::{reactive::RwSignal, views::{tool_button, tool_separator, tool_toggle, toolbar}};
+///
+/// let bold = RwSignal::new(false);
+/// toolbar([
+///     tool_button("↶", "Undo").action(|| println!("undo")).into(),
+///     tool_button("↷", "Redo").action(|| println!("redo")).into(),
+///     tool_separator(),
+///     tool_toggle("B", "Bold", bold).into(),
+/// ]);
+/// ```
+pub fn toolbar(items: impl IntoIterator<Item = ToolbarItem>) -> Toolbar {
+    let id = ViewId::new();
+    let mut children = Vec::new();
+    let mut states = Vec::new();
+    // The menu entries of each item, `None` for separators
+    let mut entries: Vec<(RwSignal<bool>, Option<Vec<MenuEntryFn>>)> = Vec::new();
+    for ToolbarItem(kind) in items {
+        let hidden = RwSignal::new(false);
+        let (view, item_entries) = match kind {
+            ItemKind::Buttons(buttons) => {
+                let group = buttons.len() > 1;
+                let (views, item_entries): (Vec<_>, Vec<_>) =
+                    buttons.into_iter().map(ToolButton::build).unzip();
+                let view = if group {
+                    h_stack_from_iter(views).class(ToolbarGroupClass).into_any()
+                } else {
+                    views
+                        .into_iter()
+                        .next()
+                        .unwrap_or_else(|| empty().into_any())
+                };
+                (view, Some(item_entries))
+            }
+            ItemKind::Separator => (empty().class(ToolbarSeparatorClass).into_any(), None),
+        };
+        let view = view.style(move |s| s.flex_shrink(0.0).apply_if(hidden.get(), |s| s.hide()));
+        states.push(ItemState {
+            id: view.id(),
+            separator: item_entries.is_none(),
+            width: 0.0,
+            hidden,
+        });
+        entries.push((hidden, item_entries));
+        children.push(view);
+    }
+
+    let entries = Rc::new(entries);
+    let menu = {
+        let entries = entries.clone();
+        move || {
+            let mut menu = Menu::new("");
+            let mut needs_separator = false;
+            let mut first = true;
+            for (hidden, item_entries) in entries.iter() {
+                if !hidden.get_untracked() {
+                    continue;
+                }
+                match item_entries {
+                    Some(item_entries) => {
+                        if needs_separator && !first {
+                            menu = menu.separator();
+                        }
+                        needs_separator = false;
+                        for entry in item_entries {
+                            menu = menu.entry(entry());
+                            first = false;
+                        }
+                    }
+                    None => needs_separator = true,
+                }
+            }
+            menu
+        }
+    };
+    let overflow = label(|| "»".to_string())
+        .class(ToolbarButtonClass)
+        .keyboard_navigable()
+        .accessibility_role(Role::Button)
+        .style({
+            let entries = entries.clone();
+            move |s| {
+                let any_hidden = entries.iter().any(|(hidden, _)| hidden.get());
+                s.flex_shrink(0.0)
+                    .cursor(CursorStyle::Pointer)
+                    .apply_if(!any_hidden, |s| s.hide())
+            }
+        });
+    let overflow_id = overflow.id();
+    let menu = Rc::new(menu);
+    let overflow = overflow
+        .popout_menu({
+            let menu = menu.clone();
+            move || menu()
+        })
+        .on_event(EventListener::KeyDown, move |event| {
+            if !event.is_keyboard_trigger() {
+                return EventPropagation::Continue;
+            }
+            let layout = overflow_id.layout_rect();
+            show_context_menu(menu(), Some(Point::new(layout.x0, layout.y1)));
+            EventPropagation::Stop
+        });
+    children.push(overflow.into_any());
+
+    id.set_children(children);
+    Toolbar {
+        id,
+        items: states,
+        overflow: overflow_id,
+        overflow_width: OVERFLOW_WIDTH,
+    }
+    .class(ToolbarClass)
+    .focus_group(FocusGroup::horizontal())
+    .style(|s| s.flex_row().items_center().width_full())
+}
+
+/// The number of items shown, in order, so that they fit in `available` space with the
+/// overflow button when some items are hidden.
+fn visible_items(widths: &[f64], available: f64, overflow_width: f64) -> usize {
+    if widths.iter().sum::<f64>() <= available {
+        return widths.len();
+    }
+    let available = available - overflow_width;
+    let mut used = 0.0;
+    widths
+        .iter()
+        .take_while(|width| {
+            used += *width;
+            used <= available
+        })
+        .count()
+}
+
+impl View for Toolbar {
+    fn id(&self) -> ViewId {
+        self.id
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "Toolbar".into()
+    }
+
+    fn compute_layout(&mut self, cx: &mut ComputeLayoutCx) -> Option<Rect> {
+        // The width of an item includes the gap and margins before it
+        let mut edge = self.id.get_content_rect().x0;
+        for item in &mut self.items {
+            if !item.hidden.get_untracked() {
+                if let Some(layout) = item.id.get_layout() {
+                    let right = (layout.location.x + layout.size.width) as f64;
+                    item.width = right - edge;
+                    edge = right;
+                }
+            }
+        }
+        if let Some(size) = self.overflow.get_size().filter(|size| size.width > 0.0) {
+            self.overflow_width = size.width;
+        }
+
+        let widths: Vec<_> = self.items.iter().map(|item| item.width).collect();
+        let available = self.id.get_content_rect().width();
+        let mut visible = visible_items(&widths, available, self.overflow_width);
+        if visible < self.items.len() {
+            // Separators aren't left before the overflow button
+            while visible > 0 && self.items[visible - 1].separator {
+                visible -= 1;
+            }
+        }
+        for (i, item) in self.items.iter().enumerate() {
+            let hide = i >= visible;
+            if item.hidden.get_untracked() != hide {
+                item.hidden.set(hide);
+            }
+        }
+
+        default_compute_layout(self.id, cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::visible_items;
+
+    #[test]
+    fn overflow() {
+        let widths = [30.0, 30.0, 10.0, 30.0];
+        assert_eq!(visible_items(&widths, 100.0, 20.0), 4);
+        assert_eq!(visible_items(&widths, 99.0, 20.0), 3);
+        assert_eq!(visible_items(&widths, 85.0, 20.0), 2);
+        assert_eq!(visible_items(&widths, 40.0, 20.0), 0);
+    }
+}
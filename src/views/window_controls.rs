@@ -0,0 +1,122 @@
+use std::{cell::Cell, rc::Rc};
+
+use floem_reactive::{create_rw_signal, SignalGet, SignalUpdate};
+use peniko::kurbo::Rect;
+
+use crate::{
+    action::{
+        minimize_window, request_close_window, set_window_maximize_button_area,
+        toggle_window_maximized,
+    },
+    event::EventListener,
+    style::CursorStyle,
+    style_class,
+    views::{container, h_stack, svg, Container, Decorators, Stack},
+    View, WindowIdExt,
+};
+
+style_class!(
+    /// The class of the buttons created by [`window_minimize_button`], [`window_maximize_button`]
+    /// and [`window_close_button`].
+    pub WindowControlButtonClass
+);
+style_class!(
+    /// The class of the button created by [`window_close_button`].
+    pub WindowCloseButtonClass
+);
+
+const MINIMIZE_ICON: &str = r#"<svg width="10" height="10" viewBox="0 0 10 10" xmlns="http://www.w3.org/2000/svg"><path d="M0 5h10" stroke="currentColor" stroke-width="1"/></svg>"#;
+const MAXIMIZE_ICON: &str = r#"<svg width="10" height="10" viewBox="0 0 10 10" xmlns="http://www.w3.org/2000/svg"><rect x="0.5" y="0.5" width="9" height="9" fill="none" stroke="currentColor" stroke-width="1"/></svg>"#;
+const RESTORE_ICON: &str = r#"<svg width="10" height="10" viewBox="0 0 10 10" xmlns="http://www.w3.org/2000/svg"><rect x="0.5" y="2.5" width="7" height="7" fill="none" stroke="currentColor" stroke-width="1"/><path d="M2.5 2.5V0.5h7v7h-2" fill="none" stroke="currentColor" stroke-width="1"/></svg>"#;
+const CLOSE_ICON: &str = r#"<svg width="10" height="10" viewBox="0 0 10 10" xmlns="http://www.w3.org/2000/svg"><path d="M0 0l10 10M10 0L0 10" stroke="currentColor" stroke-width="1"/></svg>"#;
+
+fn window_control_button(icon: impl Fn() -> &'static str + 'static) -> Container {
+    container(svg(icon).style(|s| s.size(10.0, 10.0)))
+        .class(WindowControlButtonClass)
+        .style(|s| {
+            s.width(46.0)
+                .height(32.0)
+                .items_center()
+                .justify_center()
+                .cursor(CursorStyle::Default)
+        })
+}
+
+/// A button which minimizes the window.
+pub fn window_minimize_button() -> Container {
+    window_control_button(|| MINIMIZE_ICON).on_click_stop(|_| minimize_window())
+}
+
+/// A button which toggles the maximized state of the window.
+///
+/// The icon shows whether clicking the button maximizes or restores the window.
+///
+/// The area of the button is reported with [`set_window_maximize_button_area`], so that it shows
+/// the snap layout flyout on Windows 11.
+pub fn window_maximize_button() -> Container {
+    let maximized = create_rw_signal(false);
+    let button = window_control_button(move || {
+        if maximized.get() {
+            RESTORE_ICON
+        } else {
+            MAXIMIZE_ICON
+        }
+    });
+    let id = button.id();
+    let area = Rc::new(Cell::new(Rect::ZERO));
+    button
+        .on_move({
+            let area = area.clone();
+            move |origin| {
+                let rect = area.get().with_origin(origin);
+                area.set(rect);
+                set_window_maximize_button_area(Some(rect));
+            }
+        })
+        .on_resize(move |rect| {
+            let rect = rect.with_origin(area.get().origin());
+            area.set(rect);
+            set_window_maximize_button_area(Some(rect));
+        })
+        .on_cleanup(|| set_window_maximize_button_area(None))
+        .on_click_stop(|_| toggle_window_maximized())
+        .on_event_cont(EventListener::WindowMaximizeChanged, move |_| {
+            if let Some(window_id) = id.window_id() {
+                maximized.set(window_id.is_maximized());
+            }
+        })
+        .on_event_cont(EventListener::WindowResized, move |_| {
+            if let Some(window_id) = id.window_id() {
+                let is_maximized = window_id.is_maximized();
+                if maximized.get_untracked() != is_maximized {
+                    maximized.set(is_maximized);
+                }
+            }
+        })
+}
+
+/// A button which closes the window, see [`request_close_window`].
+pub fn window_close_button() -> Container {
+    window_control_button(|| CLOSE_ICON)
+        .class(WindowCloseButtonClass)
+        .on_click_stop(|_| request_close_window())
+}
+
+/// The minimize, maximize and close buttons of a window, for use in a custom title bar.
+///
+/// Combine this with a [drag area](Decorators::drag_window_area) and
+/// [`WindowConfig::show_titlebar(false)`](crate::window::WindowConfig::show_titlebar) to build
+/// a custom title bar.
+///
+/// ## Platform-specific
+///
+/// - **Windows:** Hovering the maximize button shows the snap layout flyout of Windows 11, and
+///   snapping by dragging the window to the screen edges works with
+///   [`drag_window_area`](Decorators::drag_window_area).
+pub fn window_controls() -> Stack {
+    h_stack((
+        window_minimize_button(),
+        window_maximize_button(),
+        window_close_button(),
+    ))
+}
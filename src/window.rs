@@ -10,9 +10,22 @@ pub use winit::window::WindowId;
 pub use winit::window::WindowLevel;
 
 use crate::app::{add_app_update_event, AppUpdateEvent};
+use crate::keyboard::Key;
+use crate::style::Direction;
 use crate::view::IntoView;
 use crate::AnyView;
 
+/// The renderer drawing a window, see [`WindowConfig::renderer`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RendererBackend {
+    /// Renders on the GPU, falling back to rendering on the CPU if the GPU can't be used,
+    /// e.g. in virtual machines, over remote desktop or in CI.
+    #[default]
+    Auto,
+    /// Renders on the CPU with tiny-skia without trying to use the GPU.
+    Software,
+}
+
 pub struct WindowCreation {
     pub(crate) view_fn: Box<dyn FnOnce(WindowId) -> AnyView>,
     pub(crate) config: Option<WindowConfig>,
@@ -37,6 +50,12 @@ pub struct WindowConfig {
     pub(crate) window_level: WindowLevel,
     pub(crate) apply_default_theme: bool,
     pub(crate) font_embolden: f32,
+    pub(crate) hide_on_close: bool,
+    pub(crate) inspector_overlay_key: Option<Key>,
+    pub(crate) frame_profiler: bool,
+    pub(crate) renderer: RendererBackend,
+    pub(crate) safe_area_padding: bool,
+    pub(crate) direction: Option<Direction>,
     #[allow(dead_code)]
     pub(crate) mac_os_config: Option<MacOSWindowConfig>,
     pub(crate) web_config: Option<WebWindowConfig>,
@@ -61,6 +80,12 @@ impl Default for WindowConfig {
             window_level: WindowLevel::Normal,
             apply_default_theme: true,
             font_embolden: if cfg!(target_os = "macos") { 0.2 } else { 0. },
+            hide_on_close: false,
+            inspector_overlay_key: None,
+            frame_profiler: false,
+            renderer: RendererBackend::Auto,
+            safe_area_padding: true,
+            direction: None,
             mac_os_config: None,
             web_config: None,
         }
@@ -210,6 +235,79 @@ impl WindowConfig {
         self
     }
 
+    /// Sets whether the window should be hidden instead of closed when the user closes it.
+    ///
+    /// The window can be shown again with [`WindowIdExt::set_visible`](crate::WindowIdExt::set_visible),
+    /// e.g. from the menu of a [tray icon](crate::tray). The application keeps running while the
+    /// window is hidden.
+    ///
+    /// The default is `false`.
+    #[inline]
+    pub fn hide_on_close(mut self, hide_on_close: bool) -> Self {
+        self.hide_on_close = hide_on_close;
+        self
+    }
+
+    /// Sets a key which shows or hides the inspector overlay, which outlines the view under
+    /// the cursor and shows its id, layout and resolved style.
+    ///
+    /// The overlay can also be toggled with
+    /// [`toggle_inspector_overlay`](crate::action::toggle_inspector_overlay).
+    #[inline]
+    pub fn inspector_overlay_key(mut self, key: impl Into<Option<Key>>) -> Self {
+        self.inspector_overlay_key = key.into();
+        self
+    }
+
+    /// If set to true, the window starts with the frame profiler overlay shown, which shows
+    /// the frame rate and the time spent on events, updates, layout, text shaping and painting.
+    ///
+    /// The overlay can also be toggled with
+    /// [`toggle_frame_profiler`](crate::action::toggle_frame_profiler).
+    ///
+    /// The default is `false`.
+    #[inline]
+    pub fn frame_profiler(mut self, frame_profiler: bool) -> Self {
+        self.frame_profiler = frame_profiler;
+        self
+    }
+
+    /// Sets the renderer drawing the window.
+    ///
+    /// The software renderer is also used when the `FLOEM_FORCE_TINY_SKIA` environment
+    /// variable is set to `1`.
+    ///
+    /// The default is [`RendererBackend::Auto`].
+    #[inline]
+    pub fn renderer(mut self, renderer: RendererBackend) -> Self {
+        self.renderer = renderer;
+        self
+    }
+
+    /// If set to true, the content of the window is padded to stay out of the areas covered
+    /// by notches, rounded screen corners and system bars on mobile platforms.
+    ///
+    /// Disable it to draw behind these areas, and use
+    /// [`window_safe_area`](crate::monitor::window_safe_area) to place the content.
+    ///
+    /// The default is `true`.
+    #[inline]
+    pub fn safe_area_padding(mut self, safe_area_padding: bool) -> Self {
+        self.safe_area_padding = safe_area_padding;
+        self
+    }
+
+    /// Sets the layout direction of the window's views.
+    ///
+    /// By default the window follows the direction of the
+    /// [current locale](crate::l10n::direction), and is mirrored when switching between
+    /// left-to-right and right-to-left languages.
+    #[inline]
+    pub fn direction(mut self, direction: Direction) -> Self {
+        self.direction = Some(direction);
+        self
+    }
+
     /// Set up Mac-OS specific configuration.  The passed closure will only be
     /// called on macOS.
     #[allow(unused_variables, unused_mut)] // build will complain on non-macOS's otherwise
@@ -5,42 +5,56 @@ use std::time::{Duration, Instant};
 #[cfg(target_arch = "wasm32")]
 use web_time::{Duration, Instant};
 
-use floem_reactive::{with_scope, RwSignal, Scope, SignalGet, SignalUpdate};
+use floem_reactive::{
+    create_effect, untrack, with_scope, RwSignal, Scope, SignalGet, SignalUpdate, SignalWith,
+};
+use floem_renderer::display_list::DisplayList;
 use floem_renderer::gpu_resources::GpuResources;
 use floem_renderer::Renderer;
 use peniko::color::palette;
-use peniko::kurbo::{Affine, Point, Rect, Size, Vec2};
+use peniko::kurbo::{Affine, Insets, Point, Rect, Size, Vec2};
 use winit::{
     dpi::{LogicalPosition, LogicalSize},
     event::{ButtonSource, ElementState, Ime, MouseScrollDelta, TouchPhase},
     keyboard::{Key, ModifiersState, NamedKey},
-    window::{CursorIcon, Window, WindowId},
+    window::{CursorGrabMode, Window, WindowId},
 };
 
-#[cfg(any(target_os = "linux", target_os = "freebsd"))]
-use crate::reactive::SignalWith;
+#[cfg(not(target_os = "macos"))]
+use crate::menu::MenuAccelerator;
 #[cfg(any(target_os = "linux", target_os = "freebsd"))]
 use crate::unit::UnitExt;
 #[cfg(any(target_os = "linux", target_os = "freebsd"))]
 use crate::views::{container, stack};
 use crate::{
-    app::UserEvent,
+    action::ImePurpose,
+    app::{add_app_update_event, AppUpdateEvent, UserEvent},
     app_state::AppState,
     context::{
         ComputeLayoutCx, EventCx, FrameUpdate, LayoutCx, PaintCx, PaintState, StyleCx, UpdateCx,
     },
+    cursor::platform_cursor,
     dropped_file::DroppedFileEvent,
     event::{Event, EventListener},
+    gamepad::GamepadEvent,
     id::ViewId,
     inspector::{self, Capture, CaptureState, CapturedView},
     keyboard::{KeyEvent, Modifiers},
     menu::Menu,
-    nav::view_arrow_navigation,
-    pointer::{PointerButton, PointerInputEvent, PointerMoveEvent, PointerWheelEvent},
-    profiler::Profile,
-    style::{CursorStyle, Style, StyleSelector},
+    monitor::{
+        remove_window_monitor, remove_window_safe_area, store_window_monitor,
+        store_window_safe_area, MonitorInfo,
+    },
+    nav::{focus_group_navigation, view_arrow_navigation},
+    pointer::{
+        FingerId, Force, PenState, PointerButton, PointerInputEvent, PointerMoveEvent,
+        PointerWheelEvent, TouchEvent,
+    },
+    profiler::{FrameProfiler, FrameTimings, Profile},
+    style::{CursorStyle, Direction, Style, StyleSelector},
     theme::{default_theme, Theme},
     touchpad::PinchGestureEvent,
+    ui_scale::ui_scale_untracked,
     update::{
         UpdateMessage, CENTRAL_DEFERRED_UPDATE_MESSAGES, CENTRAL_UPDATE_MESSAGES,
         CURRENT_RUNNING_VIEW_HANDLE, DEFERRED_UPDATE_MESSAGES, UPDATE_MESSAGES,
@@ -48,6 +62,7 @@ use crate::{
     view::{default_compute_layout, view_tab_navigation, IntoView, View},
     view_state::ChangeFlags,
     views::Decorators,
+    window::RendererBackend,
     window_tracking::{remove_window_id_mapping, store_window_id_mapping},
     Application,
 };
@@ -70,20 +85,76 @@ pub(crate) struct WindowHandle {
     size: RwSignal<Size>,
     theme: Option<Theme>,
     pub(crate) profile: Option<Profile>,
+    /// Collects frame timings for the frame profiler overlay, if it's shown
+    pub(crate) frame_profiler: Option<FrameProfiler>,
+    /// The next frame repaints the whole window instead of just the damaged parts
+    paint_all: bool,
+    /// Whether a view was dragged in the last frame
+    painted_dragging: bool,
+    /// Paints views into images or display lists once they are laid out, see
+    /// [`ViewId::capture_image`] and [`ViewId::record_display_list`]
+    pending_captures: Vec<Box<dyn FnOnce(&mut WindowHandle)>>,
     os_theme: RwSignal<Option<winit::window::Theme>>,
+    /// The monitor the window is on, see [`crate::monitor::window_monitor`]
+    monitor: RwSignal<Option<MonitorInfo>>,
+    /// The insets of the window obscured by the screen or system bars
+    safe_area: RwSignal<Insets>,
     is_maximized: bool,
     transparent: bool,
+    /// Hide the window instead of closing it when the user requests it to be closed
+    pub(crate) hide_on_close: bool,
+    /// Show the inspector overlay for the view under the cursor
+    inspector_overlay: bool,
+    /// The key toggling the inspector overlay
+    pub(crate) inspector_overlay_key: Option<Key>,
     pub(crate) scale: f64,
+    /// The zoom set with [`set_window_scale`](crate::action::set_window_scale), which is
+    /// multiplied by the [UI scale](crate::ui_scale) of the application
+    window_scale: f64,
+    /// The IME settings set on the window, either by the focused view or by
+    /// [`set_ime_allowed`](crate::action::set_ime_allowed)
+    pub(crate) ime_allowed: bool,
+    ime_purpose: ImePurpose,
+    /// The IME settings set with [`set_ime_allowed`](crate::action::set_ime_allowed) and
+    /// [`set_ime_purpose`](crate::action::set_ime_purpose), which take precedence over the
+    /// settings of the focused view until the focus changes
+    ime_override: ImeOverride,
+    /// The area of the custom maximize button, see
+    /// [`set_window_maximize_button_area`](crate::action::set_window_maximize_button_area)
+    maximize_button_area: Option<Rect>,
     pub(crate) modifiers: Modifiers,
+    /// The state of the pen reported with the pointer events. winit doesn't report tablet
+    /// tools yet, so this is only set by the [test harness](crate::testing::TestHarness).
+    pub(crate) pen: Option<PenState>,
     pub(crate) cursor_position: Point,
     pub(crate) window_position: Point,
     pub(crate) last_pointer_down: Option<(u8, Point, Instant)>,
     #[cfg(any(target_os = "linux", target_os = "freebsd"))]
     pub(crate) context_menu: RwSignal<Option<(Menu, Point, bool)>>,
+    /// The window menu shown by the in-window menu bar
+    #[cfg(any(target_os = "linux", target_os = "freebsd"))]
+    window_menu: RwSignal<Option<Menu>>,
+    #[cfg(any(target_os = "windows", target_os = "macos"))]
+    platform_window_menu: Option<muda::Menu>,
+    /// Accelerators of the window menu which aren't handled by the platform
+    #[cfg(not(target_os = "macos"))]
+    menu_accelerators: Vec<(MenuAccelerator, String)>,
     dropper_file: Option<PathBuf>,
+    #[cfg(feature = "accesskit")]
+    accessibility: Option<crate::accessibility::AccessibilityAdapter>,
+}
+
+/// IME settings set for the whole window while a view is focused, see
+/// [`WindowHandle::ime_override`].
+#[derive(Clone, Copy, Default)]
+struct ImeOverride {
+    focus: Option<ViewId>,
+    allowed: Option<bool>,
+    purpose: Option<ImePurpose>,
 }
 
 impl WindowHandle {
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn new(
         window: Box<dyn winit::window::Window>,
         view_fn: impl FnOnce(winit::window::WindowId) -> Box<dyn View> + 'static,
@@ -91,6 +162,9 @@ impl WindowHandle {
         apply_default_theme: bool,
         size: Option<LogicalSize<f64>>,
         font_embolden: f32,
+        renderer: RendererBackend,
+        safe_area_padding: bool,
+        direction: Option<Direction>,
     ) -> Self {
         let scope = Scope::new();
         let window_id = window.id();
@@ -100,12 +174,22 @@ impl WindowHandle {
         let size = Size::new(size.width, size.height);
         let size = scope.create_rw_signal(Size::new(size.width, size.height));
         let theme = scope.create_rw_signal(window.theme());
+        let monitor = scope.create_rw_signal(
+            window
+                .current_monitor()
+                .map(|monitor| MonitorInfo::from_handle(&monitor)),
+        );
+        store_window_monitor(window_id, monitor);
+        let safe_area = scope.create_rw_signal(safe_area_insets(&*window, scale));
+        store_window_safe_area(window_id, safe_area);
         let is_maximized = window.is_maximized();
 
         set_current_view(id);
 
         #[cfg(any(target_os = "linux", target_os = "freebsd"))]
         let context_menu = scope.create_rw_signal(None);
+        #[cfg(any(target_os = "linux", target_os = "freebsd"))]
+        let window_menu = scope.create_rw_signal(None);
 
         #[cfg(not(any(target_os = "linux", target_os = "freebsd")))]
         let view = with_scope(scope, move || {
@@ -121,10 +205,12 @@ impl WindowHandle {
             (
                 main_view_id,
                 stack((
-                    container(main_view).style(|s| s.size(100.pct(), 100.pct())),
+                    window_menu_bar_view(window_menu),
+                    container(main_view)
+                        .style(|s| s.width(100.pct()).flex_grow(1.0).flex_basis(0.0)),
                     context_menu_view(scope, context_menu, size),
                 ))
-                .style(|s| s.size(100.pct(), 100.pct()))
+                .style(|s| s.flex_col().size(100.pct(), 100.pct()))
                 .into_any(),
             )
         });
@@ -132,17 +218,35 @@ impl WindowHandle {
         let (main_view_id, widget) = view;
         id.set_children(vec![widget]);
 
-        let view = WindowView { id };
+        let view = WindowView {
+            id,
+            safe_area: safe_area_padding.then_some(safe_area),
+            direction,
+        };
         id.set_view(view.into_any());
+        if direction.is_none() {
+            follow_locale_direction(scope, id);
+        }
 
         let window: Arc<dyn Window> = window.into();
         store_window_id_mapping(id, window_id, &window);
-        let gpu_resources = GpuResources::request(
-            move |window_id| {
-                Application::send_proxy_event(UserEvent::GpuResourcesUpdate { window_id });
-            },
-            window.clone(),
-        );
+        #[cfg(feature = "accesskit")]
+        let accessibility =
+            crate::accessibility::AccessibilityAdapter::new(window_id, window.as_ref());
+        let software = renderer == RendererBackend::Software
+            || std::env::var("FLOEM_FORCE_TINY_SKIA").is_ok_and(|val| val == "1");
+        let gpu_resources = if software {
+            // Initialize the renderer once the event loop runs, like when GPU resources arrive
+            Application::send_proxy_event(UserEvent::GpuResourcesUpdate { window_id });
+            None
+        } else {
+            Some(GpuResources::request(
+                move |window_id| {
+                    Application::send_proxy_event(UserEvent::GpuResourcesUpdate { window_id });
+                },
+                window.clone(),
+            ))
+        };
         let paint_state = PaintState::new(
             window.clone(),
             gpu_resources,
@@ -161,25 +265,164 @@ impl WindowHandle {
             size,
             theme: apply_default_theme.then(default_theme),
             os_theme: theme,
+            monitor,
+            safe_area,
             is_maximized,
             transparent,
+            hide_on_close: false,
+            inspector_overlay: false,
+            inspector_overlay_key: None,
             profile: None,
+            frame_profiler: None,
+            paint_all: true,
+            painted_dragging: false,
+            pending_captures: Vec::new(),
             scale,
+            window_scale: 1.0,
+            ime_allowed: false,
+            ime_purpose: ImePurpose::Normal,
+            ime_override: ImeOverride::default(),
+            maximize_button_area: None,
             modifiers: Modifiers::default(),
+            pen: None,
             cursor_position: Point::ZERO,
             window_position: Point::ZERO,
             #[cfg(any(target_os = "linux", target_os = "freebsd"))]
             context_menu,
+            #[cfg(any(target_os = "linux", target_os = "freebsd"))]
+            window_menu,
+            #[cfg(any(target_os = "windows", target_os = "macos"))]
+            platform_window_menu: None,
+            #[cfg(not(target_os = "macos"))]
+            menu_accelerators: Vec::new(),
             last_pointer_down: None,
             dropper_file: None,
+            #[cfg(feature = "accesskit")]
+            accessibility,
         };
         window_handle.app_state.set_root_size(size.get_untracked());
+        window_handle.apply_zoom();
         if let Some(theme) = theme.get_untracked() {
             window_handle.event(Event::ThemeChanged(theme));
         }
         window_handle
     }
 
+    /// Creates a window handle without a window or a renderer, for [`crate::offscreen`].
+    ///
+    /// Painting runs the paint code of the views, but doesn't draw anything.
+    pub(crate) fn new_headless(
+        view_fn: impl FnOnce(winit::window::WindowId) -> Box<dyn View> + 'static,
+        size: Size,
+        scale: f64,
+    ) -> Self {
+        static HEADLESS_WINDOWS: std::sync::atomic::AtomicUsize =
+            std::sync::atomic::AtomicUsize::new(0);
+
+        let scope = Scope::new();
+        let window_id = WindowId::from_raw(
+            usize::MAX - HEADLESS_WINDOWS.fetch_add(1, std::sync::atomic::Ordering::Relaxed),
+        );
+        let id = ViewId::new();
+        let size = scope.create_rw_signal(size);
+        let os_theme = scope.create_rw_signal(None);
+        let monitor = scope.create_rw_signal(None);
+
+        set_current_view(id);
+
+        let (main_view_id, main_view) = with_scope(scope, move || {
+            let main_view = view_fn(window_id);
+            (main_view.id(), main_view)
+        });
+        id.set_children(vec![main_view]);
+        id.set_view(
+            WindowView {
+                id,
+                safe_area: None,
+                direction: None,
+            }
+            .into_any(),
+        );
+        follow_locale_direction(scope, id);
+
+        let paint_state = PaintState::Initialized {
+            renderer: crate::renderer::Renderer::Uninitialized {
+                scale,
+                size: size.get_untracked() * scale,
+            },
+        };
+        let mut window_handle = Self {
+            window: None,
+            window_id,
+            id,
+            main_view: main_view_id,
+            scope,
+            app_state: AppState::new(id),
+            paint_state,
+            size,
+            theme: Some(default_theme()),
+            os_theme,
+            monitor,
+            safe_area: scope.create_rw_signal(Insets::ZERO),
+            is_maximized: false,
+            transparent: false,
+            hide_on_close: false,
+            inspector_overlay: false,
+            inspector_overlay_key: None,
+            profile: None,
+            frame_profiler: None,
+            paint_all: true,
+            painted_dragging: false,
+            pending_captures: Vec::new(),
+            scale,
+            window_scale: 1.0,
+            ime_allowed: false,
+            ime_purpose: ImePurpose::Normal,
+            ime_override: ImeOverride::default(),
+            maximize_button_area: None,
+            modifiers: Modifiers::default(),
+            pen: None,
+            cursor_position: Point::ZERO,
+            window_position: Point::ZERO,
+            #[cfg(any(target_os = "linux", target_os = "freebsd"))]
+            context_menu: scope.create_rw_signal(None),
+            #[cfg(any(target_os = "linux", target_os = "freebsd"))]
+            window_menu: scope.create_rw_signal(None),
+            #[cfg(any(target_os = "windows", target_os = "macos"))]
+            platform_window_menu: None,
+            #[cfg(not(target_os = "macos"))]
+            menu_accelerators: Vec::new(),
+            last_pointer_down: None,
+            dropper_file: None,
+            #[cfg(feature = "accesskit")]
+            accessibility: None,
+        };
+        window_handle
+            .app_state
+            .update_screen_size_bp(size.get_untracked());
+        window_handle.app_state.set_root_size(size.get_untracked());
+        window_handle.render_frame();
+        window_handle
+    }
+
+    pub(crate) fn main_view(&self) -> ViewId {
+        self.main_view
+    }
+
+    /// Paints a frame of a headless window handle with a software renderer, and returns
+    /// it as an image.
+    pub(crate) fn render_offscreen(&mut self) -> Option<peniko::Image> {
+        let renderer = self.paint_state.renderer();
+        if let crate::renderer::Renderer::Uninitialized { scale, size } = *renderer {
+            // Text isn't emboldened on any platform, so images are the same everywhere
+            self.paint_state = PaintState::Initialized {
+                renderer: crate::renderer::Renderer::new_offscreen(scale, size, 0.0),
+            };
+        }
+        self.process_update_no_paint();
+        self.paint()
+    }
+
     pub(crate) fn init_renderer(&mut self) {
         self.paint_state.init_renderer();
         // On the web, we need to get the canvas size once. The size will be updated automatically
@@ -208,9 +451,26 @@ impl WindowHandle {
     }
 
     pub fn event(&mut self, event: Event) {
+        let start = self.frame_profiler.is_some().then(Instant::now);
         set_current_view(self.id);
         let event = event.transform(Affine::scale(self.app_state.scale));
 
+        if let Event::KeyDown(key) = &event {
+            if self.app_state.pointer_lock.is_some()
+                && key.key.logical_key == Key::Named(NamedKey::Escape)
+            {
+                self.release_pointer_lock();
+                self.process_update();
+                return;
+            }
+            if !key.key.repeat && self.inspector_overlay_key.as_ref() == Some(&key.key.logical_key)
+            {
+                self.toggle_inspector_overlay();
+                self.process_update();
+                return;
+            }
+        }
+
         let mut cx = EventCx {
             app_state: &mut self.app_state,
         };
@@ -232,6 +492,9 @@ impl WindowHandle {
         };
 
         let is_pointer_down = matches!(&event, Event::PointerDown(_));
+        #[cfg_attr(target_os = "macos", allow(unused_mut))]
+        let mut menu_action: Option<String> = None;
+        let mut shortcut_command = None;
         let was_focused = if is_pointer_down {
             cx.app_state.clicking.clear();
             cx.app_state.focus.take()
@@ -260,8 +523,27 @@ impl WindowHandle {
                 }
 
                 if !processed {
+                    #[cfg(not(target_os = "macos"))]
+                    if let Event::KeyDown(key_event) = &event {
+                        menu_action = self
+                            .menu_accelerators
+                            .iter()
+                            .find(|(accelerator, _)| accelerator.matches(key_event))
+                            .map(|(_, id)| id.clone());
+                    }
+                }
+
+                if !processed && menu_action.is_none() {
+                    if let Event::KeyDown(key_event) = &event {
+                        shortcut_command = crate::command::command_for_key(key_event);
+                    }
+                }
+
+                if !processed && menu_action.is_none() && shortcut_command.is_none() {
                     if let Event::KeyDown(KeyEvent { key, modifiers }) = &event {
-                        if key.logical_key == Key::Named(NamedKey::Tab)
+                        if focus_group_navigation(cx.app_state, &key.logical_key, *modifiers) {
+                            // The focus moved inside a focus group
+                        } else if key.logical_key == Key::Named(NamedKey::Tab)
                             && (modifiers.is_empty() || *modifiers == Modifiers::SHIFT)
                         {
                             let backwards = modifiers.contains(Modifiers::SHIFT);
@@ -284,6 +566,15 @@ impl WindowHandle {
                             }
                         }
                     }
+                    // The d-pad moves the focus like the arrow keys, for apps used from a sofa
+                    if let Event::Gamepad(GamepadEvent::ButtonDown { button, .. }) = &event {
+                        if let Some(arrow) = button.arrow_key() {
+                            let key = Key::Named(arrow);
+                            if !focus_group_navigation(cx.app_state, &key, Modifiers::empty()) {
+                                view_arrow_navigation(arrow, cx.app_state, self.id);
+                            }
+                        }
+                    }
 
                     let keyboard_trigger_end = cx.app_state.keyboard_navigation
                         && event.is_keyboard_trigger()
@@ -300,6 +591,12 @@ impl WindowHandle {
                     }
                 }
             }
+        } else if let Some(id) = event
+            .touch()
+            .and_then(|touch| cx.app_state.touch_target(touch.finger_id))
+        {
+            let transform = directed_event_transform(id);
+            cx.unconditional_view_event(id, event.clone().transform(transform), true);
         } else if cx.app_state.active.is_some() && event.is_pointer() {
             if cx.app_state.is_dragging() {
                 cx.unconditional_view_event(self.id, event.clone(), false);
@@ -307,16 +604,8 @@ impl WindowHandle {
 
             let id = cx.app_state.active.unwrap();
 
-            {
-                let window_origin = id.state().borrow().window_origin;
-                let layout = id.get_layout().unwrap_or_default();
-                let viewport = id.state().borrow().viewport.unwrap_or_default();
-                let transform = Affine::translate((
-                    window_origin.x - layout.location.x as f64 + viewport.x0,
-                    window_origin.y - layout.location.y as f64 + viewport.y0,
-                ));
-                cx.unconditional_view_event(id, event.clone().transform(transform), true);
-            }
+            let transform = directed_event_transform(id);
+            cx.unconditional_view_event(id, event.clone().transform(transform), true);
 
             if let Event::PointerUp(_) = &event {
                 // To remove the styles applied by the Active selector
@@ -334,6 +623,9 @@ impl WindowHandle {
             cx.app_state.drag_start = None;
         }
         if is_pointer_move {
+            if self.inspector_overlay {
+                cx.app_state.request_paint = true;
+            }
             let hovered = &cx.app_state.hovered.clone();
             for id in was_hovered.unwrap().symmetric_difference(hovered) {
                 let view_state = id.state();
@@ -407,14 +699,167 @@ impl WindowHandle {
             }
         }
 
+        if let Some(id) = menu_action {
+            if let Some(action) = self.app_state.window_menu.get(&id) {
+                (*action)();
+            }
+        }
+        if let Some(command) = shortcut_command {
+            command.run();
+        }
+
+        if let Some((profiler, start)) = self.frame_profiler.as_mut().zip(start) {
+            profiler.current.event += start.elapsed();
+        }
+
         self.process_update();
     }
 
     pub(crate) fn scale(&mut self, scale: f64) {
         self.scale = scale;
+        self.update_monitor();
+        self.apply_maximize_button_area();
+        self.event(Event::WindowScaleChanged(scale));
+        // Text and other cached layouts are measured against the scale, so everything
+        // needs to be resolved again.
+        self.id.request_all();
+        self.id.request_style_recursive();
+        if let Some(window) = self.window.as_ref() {
+            // The physical size might stay the same, in which case no resize event is
+            // sent, but the logical size still changes.
+            let size: LogicalSize<f64> = window.surface_size().to_logical(scale);
+            self.size(Size::new(size.width, size.height));
+        } else {
+            let scale = self.scale * self.app_state.scale;
+            self.paint_state.set_scale(scale);
+            self.schedule_repaint();
+        }
+    }
+
+    /// Applies the window scale times the UI scale of the application to the layout and
+    /// painting of the window.
+    pub(crate) fn apply_zoom(&mut self) {
+        self.app_state.scale = self.window_scale * ui_scale_untracked();
+        self.id.request_layout();
         let scale = self.scale * self.app_state.scale;
         self.paint_state.set_scale(scale);
         self.schedule_repaint();
+        self.apply_maximize_button_area();
+    }
+
+    /// Reports the area of the custom maximize button to the platform, in physical pixels.
+    fn apply_maximize_button_area(&self) {
+        #[cfg(target_os = "windows")]
+        if let Some(window) = self.window.as_ref() {
+            let scale = self.scale * self.app_state.scale;
+            crate::snap_layout::set_maximize_button_area(
+                &**window,
+                self.maximize_button_area
+                    .map(|area| area.scale_from_origin(scale)),
+            );
+        }
+    }
+
+    /// Locks the pointer to `id`, which then receives the relative motion of the pointer.
+    fn lock_pointer(&mut self, id: ViewId) {
+        if self.app_state.pointer_lock == Some(id) {
+            return;
+        }
+        self.release_pointer_lock();
+        let Some(window) = self.window.as_ref() else {
+            return;
+        };
+        // Platforms which can't lock the cursor in place can at least keep it in the window,
+        // the relative motion is reported either way
+        if window.set_cursor_grab(CursorGrabMode::Locked).is_err()
+            && window.set_cursor_grab(CursorGrabMode::Confined).is_err()
+        {
+            return;
+        }
+        window.set_cursor_visible(false);
+        self.app_state.pointer_lock = Some(id);
+    }
+
+    /// Releases the pointer lock, and lets the view which held it know.
+    pub(crate) fn release_pointer_lock(&mut self) {
+        let Some(id) = self.app_state.pointer_lock.take() else {
+            return;
+        };
+        if let Some(window) = self.window.as_ref() {
+            let _ = window.set_cursor_grab(CursorGrabMode::None);
+            window.set_cursor_visible(true);
+        }
+        // The view could have been removed
+        if id.root() == Some(self.id) {
+            let mut cx = EventCx {
+                app_state: &mut self.app_state,
+            };
+            cx.unconditional_view_event(id, Event::PointerLockReleased, true);
+        }
+    }
+
+    /// Sends the relative motion of the pointer device to the view holding the pointer lock.
+    pub(crate) fn pointer_delta(&mut self, delta: Vec2) {
+        let Some(id) = self.app_state.pointer_lock else {
+            return;
+        };
+        set_current_view(self.id);
+        // The motion of the device is in physical pixels, without the pointer acceleration on
+        // most platforms
+        let delta = delta / (self.scale * self.app_state.scale);
+        let mut cx = EventCx {
+            app_state: &mut self.app_state,
+        };
+        cx.unconditional_view_event(id, Event::PointerDelta(delta), true);
+        self.process_update();
+    }
+
+    /// The IME settings set for the window while `focus` is focused, which are dropped when the
+    /// focus changed since they were set.
+    fn ime_override(&mut self, focus: Option<ViewId>) -> &mut ImeOverride {
+        if self.ime_override.focus != focus {
+            self.ime_override = ImeOverride {
+                focus,
+                ..Default::default()
+            };
+        }
+        &mut self.ime_override
+    }
+
+    /// Applies the IME settings to the window. Composition is disabled for passwords, and the
+    /// platforms without a numeric IME purpose get the normal one for digits.
+    fn apply_ime(&self) {
+        let Some(window) = self.window.as_ref() else {
+            return;
+        };
+        let purpose = match self.ime_purpose {
+            ImePurpose::Normal | ImePurpose::Digits => winit::window::ImePurpose::Normal,
+            ImePurpose::Password => winit::window::ImePurpose::Password,
+        };
+        window.set_ime_purpose(purpose);
+        window.set_ime_allowed(self.ime_allowed && self.ime_purpose != ImePurpose::Password);
+    }
+
+    /// Switches the default theme to or from its high-contrast colors.
+    pub(crate) fn high_contrast_changed(&mut self) {
+        if self.theme.is_some() {
+            self.theme = Some(default_theme());
+            self.id.request_style_recursive();
+            self.schedule_repaint();
+        }
+    }
+
+    /// Updates the monitor signal if the window moved to another monitor.
+    fn update_monitor(&mut self) {
+        let Some(window) = self.window.as_ref() else {
+            return;
+        };
+        let monitor = window
+            .current_monitor()
+            .map(|monitor| MonitorInfo::from_handle(&monitor));
+        if self.monitor.with_untracked(|current| current != &monitor) {
+            self.monitor.set(monitor);
+        }
     }
 
     pub(crate) fn os_theme_changed(&mut self, theme: winit::window::Theme) {
@@ -436,6 +881,13 @@ impl WindowHandle {
                 self.is_maximized = is_maximized;
                 self.event(Event::WindowMaximizeChanged(is_maximized));
             }
+            // The safe area changes with the orientation of the screen, which also resizes
+            // the window.
+            let safe_area = safe_area_insets(&**window, self.scale);
+            if self.safe_area.get_untracked() != safe_area {
+                self.safe_area.set(safe_area);
+                self.id.request_style();
+            }
         }
 
         self.style();
@@ -446,6 +898,7 @@ impl WindowHandle {
 
     pub(crate) fn position(&mut self, point: Point) {
         self.window_position = point;
+        self.update_monitor();
         self.event(Event::WindowMoved(point));
     }
 
@@ -481,6 +934,7 @@ impl WindowHandle {
             let event = PointerMoveEvent {
                 pos,
                 modifiers: self.modifiers,
+                pen: self.pen,
             };
             self.event(Event::PointerMove(event));
         }
@@ -555,6 +1009,7 @@ impl WindowHandle {
             button,
             modifiers: self.modifiers,
             count,
+            pen: self.pen,
         };
         match state {
             ElementState::Pressed => {
@@ -566,15 +1021,74 @@ impl WindowHandle {
         }
     }
 
+    /// Sends a touch of a finger, see [`Event::TouchStart`].
+    pub(crate) fn touch(
+        &mut self,
+        phase: TouchPhase,
+        finger_id: FingerId,
+        pos: Point,
+        force: Option<Force>,
+    ) {
+        if phase == TouchPhase::Started {
+            self.app_state.touches.insert(finger_id, None);
+        } else if !self.app_state.touches.contains_key(&finger_id) {
+            return;
+        }
+        let touch = TouchEvent {
+            finger_id,
+            pos,
+            force,
+            modifiers: self.modifiers,
+        };
+        self.event(match phase {
+            TouchPhase::Started => Event::TouchStart(touch),
+            TouchPhase::Moved => Event::TouchMove(touch),
+            TouchPhase::Ended => Event::TouchEnd(touch),
+            TouchPhase::Cancelled => Event::TouchCancel(touch),
+        });
+        if matches!(phase, TouchPhase::Ended | TouchPhase::Cancelled) {
+            self.app_state.touches.remove(&finger_id);
+        }
+    }
+
     pub(crate) fn pinch_gesture(&mut self, delta: f64, phase: TouchPhase) {
         let event = PinchGestureEvent { delta, phase };
         self.event(Event::PinchGesture(event));
     }
 
+    /// Sends the event of a game controller to the window if it's focused, like key events.
+    #[cfg(all(
+        feature = "gamepad",
+        not(any(target_arch = "wasm32", target_os = "android", target_os = "ios"))
+    ))]
+    pub(crate) fn gamepad(&mut self, event: GamepadEvent) {
+        if self
+            .window
+            .as_ref()
+            .is_some_and(|window| window.has_focus())
+        {
+            self.event(Event::Gamepad(event));
+        }
+    }
+
     pub(crate) fn focused(&mut self, focused: bool) {
+        // The menu bar is shared by all windows on macOS, so it shows the menu of the
+        // focused window.
+        #[cfg(target_os = "macos")]
+        if focused {
+            if let Some(menu) = self.platform_window_menu.as_ref() {
+                menu.init_for_nsapp();
+            }
+        }
+        #[cfg(feature = "accesskit")]
+        if let Some(adapter) = self.accessibility.as_mut() {
+            adapter.focus_changed(focused);
+        }
         if focused {
             self.event(Event::WindowGotFocus);
         } else {
+            // The pointer isn't given back to other apps while it's locked
+            self.release_pointer_lock();
             self.event(Event::WindowLostFocus);
         }
     }
@@ -614,16 +1128,22 @@ impl WindowHandle {
 
     pub(crate) fn render_frame(&mut self) {
         // Processes updates scheduled on this frame.
-        for update in mem::take(&mut self.app_state.scheduled_updates) {
-            match update {
-                FrameUpdate::Style(id) => id.request_style(),
-                FrameUpdate::Layout(id) => id.request_layout(),
-                FrameUpdate::Paint(id) => self.app_state.request_paint(id),
-            }
-        }
+        self.profile_frame_phase(
+            |timings| &mut timings.update,
+            |this| {
+                for update in mem::take(&mut this.app_state.scheduled_updates) {
+                    match update {
+                        FrameUpdate::Style(id) => id.request_style(),
+                        FrameUpdate::Layout(id) => id.request_layout(),
+                        FrameUpdate::Paint(id) => this.app_state.request_paint(id),
+                    }
+                }
+            },
+        );
 
         self.process_update_no_paint();
         self.paint();
+        self.update_accessibility();
 
         // Request a new frame if there's any scheduled updates.
         if !self.app_state.scheduled_updates.is_empty() {
@@ -632,6 +1152,19 @@ impl WindowHandle {
     }
 
     pub fn paint(&mut self) -> Option<peniko::Image> {
+        let start = self.frame_profiler.is_some().then(Instant::now);
+        let dragging = self.app_state.dragging.is_some();
+        // The overlays and dragged views aren't covered by the damage of views
+        let paint_all = mem::take(&mut self.paint_all)
+            || self.inspector_overlay
+            || self.frame_profiler.is_some()
+            || dragging
+            || mem::replace(&mut self.painted_dragging, dragging);
+        let damage = self
+            .app_state
+            .damage
+            .take()
+            .filter(|_| !paint_all && self.app_state.capture.is_none());
         let mut cx = PaintCx {
             app_state: &mut self.app_state,
             paint_state: &mut self.paint_state,
@@ -642,9 +1175,14 @@ impl WindowHandle {
             saved_clips: Vec::new(),
             saved_z_indexes: Vec::new(),
         };
-        cx.paint_state
-            .renderer_mut()
-            .begin(cx.app_state.capture.is_some());
+        let damage = damage.filter(|damage| cx.paint_state.renderer_mut().begin_partial(*damage));
+        match damage {
+            Some(damage) => cx.clip(&damage),
+            None => cx
+                .paint_state
+                .renderer_mut()
+                .begin(cx.app_state.capture.is_some()),
+        }
         if !self.transparent {
             let scale = cx.app_state.scale;
             let color = self
@@ -665,12 +1203,118 @@ impl WindowHandle {
             );
         }
         cx.paint_view(self.id);
+        if self.inspector_overlay {
+            inspector::paint_overlay(&mut cx, self.id);
+        }
+        if let Some(profiler) = &self.frame_profiler {
+            profiler.paint(&mut cx, self.id.layout_rect());
+        }
         if let Some(window) = self.window.as_ref() {
             if cx.app_state.capture.is_none() {
                 window.pre_present_notify();
             }
         }
-        cx.paint_state.renderer_mut().finish()
+        let frame = cx.paint_state.renderer_mut().finish();
+        if let Some((profiler, start)) = self.frame_profiler.as_mut().zip(start) {
+            profiler.end_frame(start.elapsed());
+        }
+        frame
+    }
+
+    /// Paints a view and its children with a software renderer, and returns it as an image
+    /// of the view size times `scale`.
+    pub(crate) fn capture_image(&mut self, id: ViewId, scale: f64) -> Option<image::RgbaImage> {
+        let rect = id.get_size()?.to_rect();
+        self.capture_image_rect(id, rect, scale)
+    }
+
+    /// Paints the part `rect` of a view and its children, in the coordinates of the view.
+    pub(crate) fn capture_image_rect(
+        &mut self,
+        id: ViewId,
+        rect: Rect,
+        scale: f64,
+    ) -> Option<image::RgbaImage> {
+        let size = rect.size() * scale;
+        // Text isn't emboldened on any platform, so images are the same everywhere
+        let renderer = crate::renderer::Renderer::new_offscreen(
+            scale,
+            Size::new(size.width.ceil(), size.height.ceil()),
+            0.0,
+        );
+        let (_, frame) = self.paint_detached(id, rect.origin(), renderer);
+        let frame = frame?;
+        image::RgbaImage::from_raw(frame.width, frame.height, frame.data.data().to_vec())
+    }
+
+    /// Records the draw commands of a view and its children, in the coordinates of the view.
+    pub(crate) fn record_display_list(&mut self, id: ViewId) -> Option<DisplayList> {
+        let size = id.get_size()?;
+        let renderer = crate::renderer::Renderer::Recording(DisplayList::new(size));
+        match self.paint_detached(id, Point::ZERO, renderer) {
+            (crate::renderer::Renderer::Recording(display_list), _) => Some(display_list),
+            _ => None,
+        }
+    }
+
+    /// Paints a view and its children with `renderer` as if `origin` in the view was at the
+    /// origin, without painting the window.
+    fn paint_detached(
+        &mut self,
+        id: ViewId,
+        origin: Point,
+        renderer: crate::renderer::Renderer,
+    ) -> (crate::renderer::Renderer, Option<peniko::Image>) {
+        let location = id.get_layout().map_or(Point::ZERO, |layout| {
+            Point::new(layout.location.x as f64, layout.location.y as f64)
+        });
+        let mut paint_state = PaintState::Initialized { renderer };
+        let mut cx = PaintCx {
+            app_state: &mut self.app_state,
+            paint_state: &mut paint_state,
+            // Cancels the offset of the view in its parent
+            transform: Affine::translate(-(location.to_vec2() + origin.to_vec2())),
+            clip: None,
+            z_index: None,
+            saved_transforms: Vec::new(),
+            saved_clips: Vec::new(),
+            saved_z_indexes: Vec::new(),
+        };
+        cx.paint_state.renderer_mut().begin(false);
+        cx.paint_view(id);
+        let frame = cx.paint_state.renderer_mut().finish();
+        match paint_state {
+            PaintState::Initialized { renderer } => (renderer, frame),
+            PaintState::PendingGpuResources { .. } => unreachable!(),
+        }
+    }
+
+    /// Adds the time taken by `f` to a phase of the current frame, if the frame profiler is
+    /// shown.
+    fn profile_frame_phase<R>(
+        &mut self,
+        phase: fn(&mut FrameTimings) -> &mut Duration,
+        f: impl FnOnce(&mut Self) -> R,
+    ) -> R {
+        let start = self.frame_profiler.is_some().then(Instant::now);
+        let result = f(self);
+        if let Some((profiler, start)) = self.frame_profiler.as_mut().zip(start) {
+            *phase(&mut profiler.current) += start.elapsed();
+        }
+        result
+    }
+
+    fn toggle_frame_profiler(&mut self) {
+        self.frame_profiler = match self.frame_profiler {
+            Some(_) => None,
+            None => Some(FrameProfiler::new()),
+        };
+        self.app_state.request_paint = true;
+    }
+
+    fn toggle_inspector_overlay(&mut self) {
+        self.inspector_overlay = !self.inspector_overlay;
+        self.app_state.request_paint = true;
     }
 
     pub(crate) fn capture(&mut self) -> Capture {
@@ -742,6 +1386,40 @@ impl WindowHandle {
         capture
     }
 
+    /// Sends the accessibility tree to assistive technology, if any is listening.
+    pub(crate) fn update_accessibility(&mut self) {
+        #[cfg(feature = "accesskit")]
+        if let Some(adapter) = self.accessibility.as_mut() {
+            let scale = self.scale * self.app_state.scale;
+            adapter.update_if_active(|| {
+                crate::accessibility::tree_update(self.id, &self.app_state, scale)
+            });
+        }
+    }
+
+    /// Performs an action requested by assistive technology.
+    pub(crate) fn accessibility_action(&mut self, request: accesskit::ActionRequest) {
+        set_current_view(self.id);
+        let id = crate::accessibility::view_id(request.target);
+        // The view could have been removed since the tree was sent
+        if id.root() == Some(self.id) {
+            if request.action == accesskit::Action::Focus {
+                id.request_focus();
+            } else {
+                let action = request.action;
+                let event = Event::AccessibilityAction(request);
+                let mut cx = EventCx {
+                    app_state: &mut self.app_state,
+                };
+                let (processed, _) = cx.unconditional_view_event(id, event.clone(), true);
+                if processed.is_continue() && action == accesskit::Action::Click {
+                    id.apply_event(&EventListener::Click, &event);
+                }
+            }
+        }
+        self.process_update();
+    }
+
     pub(crate) fn process_update(&mut self) {
         if self.process_update_no_paint() {
             self.schedule_repaint();
@@ -755,7 +1433,10 @@ impl WindowHandle {
 
         loop {
             loop {
-                self.process_update_messages();
+                self.profile_frame_phase(
+                    |timings| &mut timings.update,
+                    Self::process_update_messages,
+                );
                 if !self.needs_layout()
                     && !self.needs_style()
                     && !self.app_state.request_compute_layout
@@ -765,28 +1446,37 @@ impl WindowHandle {
 
                 if self.needs_style() {
                     paint = true;
-                    self.style();
+                    self.profile_frame_phase(|timings| &mut timings.layout, Self::style);
                 }
 
                 if self.needs_layout() {
                     paint = true;
-                    self.layout();
+                    self.profile_frame_phase(|timings| &mut timings.layout, Self::layout);
                 }
 
                 if self.app_state.request_compute_layout {
-                    self.compute_layout();
+                    self.profile_frame_phase(|timings| &mut timings.layout, Self::compute_layout);
                 }
             }
             if !self.has_deferred_update_messages() {
                 break;
             }
-            self.process_deferred_update_messages();
+            self.profile_frame_phase(
+                |timings| &mut timings.update,
+                Self::process_deferred_update_messages,
+            );
+        }
+
+        for capture in mem::take(&mut self.pending_captures) {
+            capture(self);
         }
 
         self.set_cursor();
 
         // TODO: This should only use `self.app_state.request_paint)`
-        paint || mem::take(&mut self.app_state.request_paint)
+        let paint_all = paint || mem::take(&mut self.app_state.request_paint);
+        self.paint_all |= paint_all;
+        paint_all || self.app_state.damage.is_some()
     }
 
     fn process_central_messages(&self) {
@@ -856,8 +1546,11 @@ impl WindowHandle {
                     app_state: &mut self.app_state,
                 };
                 match msg {
-                    UpdateMessage::RequestPaint => {
-                        cx.app_state.request_paint = true;
+                    UpdateMessage::RequestPaint(id) => {
+                        cx.app_state.request_paint(id);
+                    }
+                    UpdateMessage::RequestPaintRect { id, rect } => {
+                        cx.app_state.request_paint_rect(id, rect);
                     }
                     UpdateMessage::Focus(id) => {
                         if cx.app_state.focus != Some(id) {
@@ -984,6 +1677,21 @@ impl WindowHandle {
                             window.set_minimized(true);
                         }
                     }
+                    UpdateMessage::RequestCloseWindow => {
+                        if self.hide_on_close {
+                            if let Some(window) = self.window.as_ref() {
+                                window.set_visible(false);
+                            }
+                        } else {
+                            add_app_update_event(AppUpdateEvent::CloseWindow {
+                                window_id: self.window_id,
+                            });
+                        }
+                    }
+                    UpdateMessage::SetMaximizeButtonArea(area) => {
+                        self.maximize_button_area = area;
+                        self.apply_maximize_button_area();
+                    }
                     UpdateMessage::SetWindowDelta(delta) => {
                         if let Some(window) = self.window.as_ref() {
                             let pos = self.window_position + delta;
@@ -993,7 +1701,8 @@ impl WindowHandle {
                         }
                     }
                     UpdateMessage::WindowScale(scale) => {
-                        cx.app_state.scale = scale;
+                        self.window_scale = scale;
+                        cx.app_state.scale = scale * ui_scale_untracked();
                         self.id.request_layout();
                         let scale = self.scale * cx.app_state.scale;
                         self.paint_state.set_scale(scale);
@@ -1012,9 +1721,7 @@ impl WindowHandle {
                         self.show_context_menu(menu, pos);
                     }
                     UpdateMessage::WindowMenu { menu } => {
-                        // let platform_menu = menu.platform_menu();
                         self.update_window_menu(menu);
-                        // self.handle.set_menu(platform_menu);
                     }
                     UpdateMessage::SetWindowTitle { title } => {
                         if let Some(window) = self.window.as_ref() {
@@ -1022,10 +1729,40 @@ impl WindowHandle {
                         }
                     }
                     UpdateMessage::SetImeAllowed { allowed } => {
-                        if let Some(window) = self.window.as_ref() {
-                            window.set_ime_allowed(allowed);
+                        let focus = cx.app_state.focus;
+                        self.ime_override(focus).allowed = Some(allowed);
+                        self.ime_allowed = allowed;
+                        self.apply_ime();
+                    }
+                    UpdateMessage::SetImePurpose { purpose } => {
+                        let focus = cx.app_state.focus;
+                        self.ime_override(focus).purpose = Some(purpose);
+                        self.ime_purpose = purpose;
+                        self.apply_ime();
+                    }
+                    UpdateMessage::PointerLock(id) => {
+                        self.lock_pointer(id);
+                    }
+                    UpdateMessage::ReleasePointerLock(id) => {
+                        if cx.app_state.pointer_lock == Some(id) {
+                            self.release_pointer_lock();
                         }
                     }
+                    UpdateMessage::FocusedIme => {
+                        let focus = cx.app_state.focus;
+                        let (allowed, purpose) = match focus {
+                            Some(id) => {
+                                let state = id.state();
+                                let state = state.borrow();
+                                (state.ime_allowed, state.ime_purpose)
+                            }
+                            None => (false, ImePurpose::Normal),
+                        };
+                        let ime_override = *self.ime_override(focus);
+                        self.ime_allowed = ime_override.allowed.unwrap_or(allowed);
+                        self.ime_purpose = ime_override.purpose.unwrap_or(purpose);
+                        self.apply_ime();
+                    }
                     UpdateMessage::SetImeCursorArea { position, size } => {
                         if let Some(window) = self.window.as_ref() {
                             window.set_ime_cursor_area(
@@ -1043,6 +1780,30 @@ impl WindowHandle {
                     UpdateMessage::Inspect => {
                         inspector::capture(self.window_id);
                     }
+                    UpdateMessage::ToggleInspectorOverlay => {
+                        self.toggle_inspector_overlay();
+                    }
+                    UpdateMessage::ToggleFrameProfiler => {
+                        self.toggle_frame_profiler();
+                    }
+                    UpdateMessage::CaptureImage {
+                        id,
+                        scale,
+                        on_capture,
+                    } => {
+                        self.pending_captures.push(Box::new(move |handle| {
+                            if let Some(image) = handle.capture_image(id, scale) {
+                                on_capture(image);
+                            }
+                        }));
+                    }
+                    UpdateMessage::RecordDisplayList { id, on_record } => {
+                        self.pending_captures.push(Box::new(move |handle| {
+                            if let Some(display_list) = handle.record_display_list(id) {
+                                on_record(display_list);
+                            }
+                        }));
+                    }
                     UpdateMessage::AddOverlay { id, position, view } => {
                         let scope = self.scope.create_child();
 
@@ -1123,50 +1884,65 @@ impl WindowHandle {
         })
     }
 
-    fn update_window_menu(&mut self, _menu: Menu) {
-        // if let Some(action) = menu.item.action.take() {
-        //     self.window_menu.insert(menu.item.id as u32, action);
-        // }
-        // for child in menu.children {
-        //     match child {
-        //         crate::menu::MenuEntry::Separator => {}
-        //         crate::menu::MenuEntry::Item(mut item) => {
-        //             if let Some(action) = item.action.take() {
-        //                 self.window_menu.insert(item.id as u32, action);
-        //             }
-        //         }
-        //         crate::menu::MenuEntry::SubMenu(m) => {
-        //             self.update_window_menu(m);
-        //         }
-        //     }
-        // }
+    fn update_window_menu(&mut self, mut menu: Menu) {
+        self.app_state.update_window_menu(&mut menu);
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            self.menu_accelerators = menu.accelerators();
+        }
+
+        #[cfg(target_os = "macos")]
+        {
+            let platform_menu = menu.platform_menu();
+            let is_focused = self
+                .window
+                .as_ref()
+                .map(|window| window.has_focus())
+                .unwrap_or(false);
+            if is_focused || self.platform_window_menu.is_none() {
+                platform_menu.init_for_nsapp();
+            }
+            self.platform_window_menu = Some(platform_menu);
+        }
+
+        #[cfg(target_os = "windows")]
+        {
+            use raw_window_handle::HasWindowHandle;
+            use raw_window_handle::RawWindowHandle;
+
+            let platform_menu = menu.platform_menu();
+            if let Some(window) = self.window.as_ref() {
+                if let RawWindowHandle::Win32(handle) = window.window_handle().unwrap().as_raw() {
+                    let hwnd = isize::from(handle.hwnd);
+                    unsafe {
+                        if let Some(old_menu) = self.platform_window_menu.take() {
+                            let _ = old_menu.remove_for_hwnd(hwnd);
+                        }
+                        let _ = platform_menu.init_for_hwnd(hwnd);
+                    }
+                }
+            }
+            self.platform_window_menu = Some(platform_menu);
+        }
+
+        #[cfg(any(target_os = "linux", target_os = "freebsd"))]
+        self.window_menu.set(Some(menu.display_copy()));
     }
 
     fn set_cursor(&mut self) {
-        let cursor = match self.app_state.cursor {
-            Some(CursorStyle::Default) => CursorIcon::Default,
-            Some(CursorStyle::Pointer) => CursorIcon::Pointer,
-            Some(CursorStyle::Text) => CursorIcon::Text,
-            Some(CursorStyle::ColResize) => CursorIcon::ColResize,
-            Some(CursorStyle::RowResize) => CursorIcon::RowResize,
-            Some(CursorStyle::WResize) => CursorIcon::WResize,
-            Some(CursorStyle::EResize) => CursorIcon::EResize,
-            Some(CursorStyle::NwResize) => CursorIcon::NwResize,
-            Some(CursorStyle::NeResize) => CursorIcon::NeResize,
-            Some(CursorStyle::SwResize) => CursorIcon::SwResize,
-            Some(CursorStyle::SeResize) => CursorIcon::SeResize,
-            Some(CursorStyle::SResize) => CursorIcon::SResize,
-            Some(CursorStyle::NResize) => CursorIcon::NResize,
-            Some(CursorStyle::NeswResize) => CursorIcon::NeswResize,
-            Some(CursorStyle::NwseResize) => CursorIcon::NwseResize,
-            None => CursorIcon::Default,
+        let cursor = self.app_state.cursor.unwrap_or(CursorStyle::Default);
+        if cursor == self.app_state.last_cursor {
+            return;
+        }
+        // A custom cursor might not be created yet, it's set on a later pointer move
+        let Some(platform_cursor) = platform_cursor(cursor) else {
+            return;
         };
-        if cursor != self.app_state.last_cursor {
-            if let Some(window) = self.window.as_ref() {
-                window.set_cursor(cursor.into());
-            }
-            self.app_state.last_cursor = cursor;
+        if let Some(window) = self.window.as_ref() {
+            window.set_cursor(platform_cursor);
         }
+        self.app_state.last_cursor = cursor;
     }
 
     fn schedule_repaint(&self) {
@@ -1179,6 +1955,8 @@ impl WindowHandle {
         self.event(Event::WindowClosed);
         self.scope.dispose();
         remove_window_id_mapping(&self.id, &self.window_id);
+        remove_window_monitor(&self.window_id);
+        remove_window_safe_area(&self.window_id);
     }
 
     #[cfg(target_os = "macos")]
@@ -1288,6 +2066,18 @@ pub(crate) fn set_current_view(id: ViewId) {
     });
 }
 
+/// The transform of an event in window coordinates sent directly to `id`, rather than through
+/// its parents, which offsets the event by the layout of the view afterwards.
+fn directed_event_transform(id: ViewId) -> Affine {
+    let window_origin = id.state().borrow().window_origin;
+    let layout = id.get_layout().unwrap_or_default();
+    let viewport = id.state().borrow().viewport.unwrap_or_default();
+    Affine::translate((
+        window_origin.x - layout.location.x as f64 + viewport.x0,
+        window_origin.y - layout.location.y as f64 + viewport.y0,
+    ))
+}
+
 #[cfg(any(target_os = "linux", target_os = "freebsd"))]
 fn context_menu_view(
     cx: Scope,
@@ -1298,7 +2088,8 @@ fn context_menu_view(
     use peniko::Color;
 
     use crate::{
-        app::{add_app_update_event, AppUpdateEvent},
+        event::EventPropagation,
+        nav::FocusGroup,
         views::{dyn_stack, empty, svg, text},
     };
 
@@ -1308,7 +2099,9 @@ fn context_menu_view(
         Item {
             id: Option<String>,
             enabled: bool,
+            checked: Option<bool>,
             title: String,
+            accelerator: Option<String>,
             children: Option<Vec<MenuDisplay>>,
         },
     }
@@ -1322,13 +2115,21 @@ fn context_menu_view(
                 crate::menu::MenuEntry::Item(i) => MenuDisplay::Item {
                     id: Some(i.id.clone()),
                     enabled: i.enabled,
+                    checked: i.checked,
                     title: i.title.clone(),
+                    accelerator: i
+                        .accelerator
+                        .as_deref()
+                        .and_then(MenuAccelerator::parse)
+                        .map(|accelerator| accelerator.display()),
                     children: None,
                 },
                 crate::menu::MenuEntry::SubMenu(m) => MenuDisplay::Item {
                     id: None,
                     enabled: m.item.enabled,
+                    checked: None,
                     title: m.item.title.clone(),
+                    accelerator: None,
                     children: Some(format_menu(m)),
                 },
             })
@@ -1352,7 +2153,9 @@ fn context_menu_view(
             MenuDisplay::Item {
                 id,
                 enabled,
+                checked,
                 title,
+                accelerator,
                 children,
             } => {
                 let menu_width = create_rw_signal(0.0);
@@ -1360,11 +2163,25 @@ fn context_menu_view(
                 let on_submenu = create_rw_signal(false);
                 let on_child_submenu = create_rw_signal(false);
                 let has_submenu = children.is_some();
+                let activate = move || {
+                    context_menu.set(None);
+                    if let Some(id) = id.clone() {
+                        add_app_update_event(AppUpdateEvent::MenuAction { action_id: id });
+                    }
+                };
                 let submenu_svg = r#"<svg width="16" height="16" viewBox="0 0 16 16" xmlns="http://www.w3.org/2000/svg" fill="currentColor"><path fill-rule="evenodd" clip-rule="evenodd" d="M10.072 8.024L5.715 3.667l.618-.62L11 7.716v.618L6.333 13l-.618-.619 4.357-4.357z"/></svg>"#;
                 container(
                     stack((
                         stack((
-                            text(title).style(|s| s.selectable(false)),
+                            text(if checked == Some(true) { "✓" } else { "" })
+                                .style(|s| s.selectable(false).width(16.0)),
+                            text(title).style(|s| s.selectable(false).flex_grow(1.0)),
+                            text(accelerator.clone().unwrap_or_default()).style(move |s| {
+                                s.selectable(false)
+                                    .margin_left(20.0)
+                                    .color(Color::from_rgb8(140, 140, 140))
+                                    .apply_if(accelerator.is_none(), |s| s.hide())
+                            }),
                             svg(submenu_svg).style(move |s| {
                                 s.size(20.0, 20.0)
                                     .color(Color::from_rgb8(201, 201, 201))
@@ -1396,15 +2213,23 @@ fn context_menu_view(
                                 }
                             });
                         })
-                        .on_event_stop(EventListener::PointerUp, move |_| {
-                            if has_submenu {
-                                // don't handle the click if there's submenu
-                                return;
+                        .on_event_stop(EventListener::PointerUp, {
+                            let activate = activate.clone();
+                            move |_| {
+                                if has_submenu {
+                                    // don't handle the click if there's submenu
+                                    return;
+                                }
+                                activate();
                             }
-                            context_menu.set(None);
-                            if let Some(id) = id.clone() {
-                                add_app_update_event(AppUpdateEvent::MenuAction { action_id: id });
+                        })
+                        .keyboard_navigable()
+                        .on_event(EventListener::KeyDown, move |event| {
+                            if has_submenu || !event.is_keyboard_trigger() {
+                                return EventPropagation::Continue;
                             }
+                            activate();
+                            EventPropagation::Stop
                         })
                         .disabled(move || !enabled)
                         .style(|s| {
@@ -1417,6 +2242,10 @@ fn context_menu_view(
                                     s.border_radius(10.0)
                                         .background(Color::from_rgb8(65, 65, 65))
                                 })
+                                .focus_visible(|s| {
+                                    s.border_radius(10.0)
+                                        .background(Color::from_rgb8(65, 65, 65))
+                                })
                                 .active(|s| {
                                     s.border_radius(10.0)
                                         .background(Color::from_rgb8(92, 92, 92))
@@ -1429,6 +2258,7 @@ fn context_menu_view(
                             move |menu| view_fn(menu, context_menu, on_child_submenu),
                         )
                         .keyboard_navigable()
+                        .focus_group(FocusGroup::vertical().wrap(true))
                         .on_event_stop(EventListener::KeyDown, move |event| {
                             if let Event::KeyDown(event) = event {
                                 if event.key.logical_key == Key::Named(NamedKey::Escape) {
@@ -1510,6 +2340,7 @@ fn context_menu_view(
     })
     .on_event_stop(EventListener::PointerMove, move |_| {})
     .keyboard_navigable()
+    .focus_group(FocusGroup::vertical().wrap(true))
     .on_event_stop(EventListener::KeyDown, move |event| {
         if let Event::KeyDown(event) = event {
             if event.key.logical_key == Key::Named(NamedKey::Escape) {
@@ -1556,6 +2387,98 @@ fn context_menu_view(
     view
 }
 
+/// The in-window fallback of the native menu bar.
+///
+/// Each top-level submenu of the window menu is shown as a title which opens the submenu
+/// as a popout menu below it.
+#[cfg(any(target_os = "linux", target_os = "freebsd"))]
+fn window_menu_bar_view(window_menu: RwSignal<Option<Menu>>) -> impl IntoView {
+    use peniko::Color;
+
+    use crate::{
+        menu::MenuEntry,
+        nav::FocusGroup,
+        views::{dyn_stack, text},
+    };
+
+    let titles = move || {
+        window_menu.with(|menu| {
+            menu.as_ref()
+                .map(|menu| {
+                    menu.children
+                        .iter()
+                        .enumerate()
+                        .filter_map(|(index, entry)| match entry {
+                            MenuEntry::Separator => None,
+                            MenuEntry::Item(item) => {
+                                Some((index, item.title.clone(), item.enabled, false))
+                            }
+                            MenuEntry::SubMenu(menu) => {
+                                Some((index, menu.item.title.clone(), menu.item.enabled, true))
+                            }
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default()
+        })
+    };
+
+    dyn_stack(
+        titles,
+        |title| title.clone(),
+        move |(index, title, enabled, is_submenu)| {
+            let entry_id = move || {
+                window_menu.with_untracked(|menu| match menu.as_ref()?.children.get(index)? {
+                    MenuEntry::Item(item) => Some(item.id.clone()),
+                    _ => None,
+                })
+            };
+            let view = text(title)
+                .disabled(move || !enabled)
+                .on_click_stop(move |_| {
+                    if let Some(id) = entry_id() {
+                        add_app_update_event(AppUpdateEvent::MenuAction { action_id: id });
+                    }
+                })
+                .keyboard_navigable()
+                .style(|s| {
+                    s.selectable(false)
+                        .padding_horiz(10.0)
+                        .padding_vert(4.0)
+                        .border_radius(6.0)
+                        .cursor(CursorStyle::Default)
+                        .hover(|s| s.background(Color::from_rgb8(65, 65, 65)))
+                        .focus_visible(|s| s.background(Color::from_rgb8(65, 65, 65)))
+                        .active(|s| s.background(Color::from_rgb8(92, 92, 92)))
+                        .disabled(|s| s.color(Color::from_rgb8(92, 92, 92)))
+                });
+            if is_submenu {
+                view.popout_menu(move || {
+                    window_menu
+                        .with_untracked(|menu| match menu.as_ref()?.children.get(index)? {
+                            MenuEntry::SubMenu(menu) => Some(menu.display_copy()),
+                            _ => None,
+                        })
+                        .unwrap_or_else(|| Menu::new(""))
+                })
+                .into_any()
+            } else {
+                view.into_any()
+            }
+        },
+    )
+    .focus_group(FocusGroup::horizontal().wrap(true))
+    .style(move |s| {
+        let has_menu = window_menu.with(|menu| menu.is_some());
+        s.width(100.pct())
+            .items_center()
+            .padding(2.0)
+            .background(Color::from_rgb8(44, 44, 44))
+            .color(Color::from_rgb8(201, 201, 201))
+            .apply_if(!has_menu, |s| s.hide())
+    })
+}
+
 struct OverlayView {
     id: ViewId,
     child: ViewId,
@@ -1612,9 +2535,33 @@ impl View for OverlayView {
     }
 }
 
+/// The insets of the window outside of its safe area, in logical pixels.
+fn safe_area_insets(window: &dyn Window, scale: f64) -> Insets {
+    let insets = window.safe_area().to_logical::<f64>(scale);
+    Insets::new(insets.left, insets.top, insets.right, insets.bottom)
+}
+
+/// Restyles the window when the locale switches between left-to-right and right-to-left
+/// languages, so its views are mirrored.
+fn follow_locale_direction(scope: Scope, id: ViewId) {
+    with_scope(scope, || {
+        create_effect(move |prev: Option<Direction>| {
+            let direction = crate::l10n::direction();
+            if prev.is_some_and(|prev| prev != direction) {
+                id.request_style_recursive();
+            }
+            direction
+        });
+    });
+}
+
 /// A view representing a window which manages the main window view and any overlays.
 struct WindowView {
     id: ViewId,
+    /// The safe area the content is padded to, if enabled
+    safe_area: Option<RwSignal<Insets>>,
+    /// The layout direction, or `None` to follow the locale
+    direction: Option<Direction>,
 }
 
 impl View for WindowView {
@@ -1623,7 +2570,21 @@ impl View for WindowView {
     }
 
     fn view_style(&self) -> Option<crate::style::Style> {
-        Some(Style::new().width_full().height_full())
+        let direction = self
+            .direction
+            .unwrap_or_else(|| untrack(crate::l10n::direction));
+        let style = Style::new().width_full().height_full().direction(direction);
+        let Some(safe_area) = self.safe_area else {
+            return Some(style);
+        };
+        let insets = safe_area.get_untracked();
+        Some(
+            style
+                .padding_left(insets.x0)
+                .padding_top(insets.y0)
+                .padding_right(insets.x1)
+                .padding_bottom(insets.y1),
+        )
     }
 
     fn debug_name(&self) -> std::borrow::Cow<'static, str> {
@@ -1,4 +1,5 @@
 use crate::{
+    monitor::MonitorInfo,
     screen_layout::screen_layout_for_window,
     window_tracking::{force_window_repaint, with_window},
     ScreenLayout, ViewId,
@@ -6,13 +7,13 @@ use crate::{
 use std::{cell::RefCell, collections::HashMap, sync::Arc};
 
 use super::window_tracking::{
-    monitor_bounds, root_view_id, window_inner_screen_bounds, window_inner_screen_position,
-    window_outer_screen_bounds, window_outer_screen_position,
+    monitor_bounds, monitor_bounds_for_monitor, root_view_id, window_inner_screen_bounds,
+    window_inner_screen_position, window_outer_screen_bounds, window_outer_screen_position,
 };
 use peniko::kurbo::{Point, Rect, Size};
 use winit::{
     dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize, Pixel},
-    window::{UserAttentionType, Window, WindowId},
+    window::{Fullscreen, UserAttentionType, Window, WindowId, WindowLevel},
 };
 
 // Using thread_local for consistency with static vars in updates.rs, but I suspect these
@@ -38,6 +39,13 @@ enum WindowUpdate {
     RequestAttention(Option<UserAttentionType>),
     Minimize(bool),
     Maximize(bool),
+    Level(WindowLevel),
+    Fullscreen(Option<Fullscreen>),
+    ToggleFullscreen,
+    Opacity(f64),
+    MinContentSize(Option<Size>),
+    MaxContentSize(Option<Size>),
+    CenterOnMonitor,
     // macOS only
     #[allow(unused_variables)] // seen as unused on linux, etc.
     DocumentEdited(bool),
@@ -119,6 +127,10 @@ pub trait WindowIdExt: WindowIdExtSealed {
     fn position_of_content_on_screen(&self) -> Option<Point>;
     /// Get the logical bounds of the monitor this window is on.
     fn monitor_bounds(&self) -> Option<Rect>;
+    /// Get information about the monitor this window is on.
+    fn current_monitor(&self) -> Option<MonitorInfo>;
+    /// Get information about all the monitors connected to the system.
+    fn available_monitors(&self) -> Vec<MonitorInfo>;
     /// Determine if this window is currently visible.  Note that if a
     /// call to set a window visible which is invisible has happened within
     /// the current event loop cycle, the state returned will not reflect that.
@@ -135,6 +147,10 @@ pub trait WindowIdExt: WindowIdExtSealed {
     /// returned will not reflect that.
     fn is_maximized(&self) -> bool;
 
+    /// Determine if this window is currently fullscreen, either borderless or
+    /// in an exclusive video mode.
+    fn is_fullscreen(&self) -> bool;
+
     /// Determine if the window decorations should indicate an edited, unsaved
     /// document.  Platform-dependent: Will only ever return `true` on macOS.
     fn is_document_edited(&self) -> bool;
@@ -174,6 +190,59 @@ pub trait WindowIdExt: WindowIdExtSealed {
         self.add_window_update(WindowUpdate::Minimize(minimized))
     }
 
+    /// Change whether this window is kept above all other windows.
+    fn set_always_on_top(&self, always_on_top: bool) {
+        self.set_window_level(if always_on_top {
+            WindowLevel::AlwaysOnTop
+        } else {
+            WindowLevel::Normal
+        })
+    }
+
+    /// Change the level of this window relative to other windows.
+    fn set_window_level(&self, level: WindowLevel) {
+        self.add_window_update(WindowUpdate::Level(level))
+    }
+
+    /// Change this window's fullscreen state, `None` leaves fullscreen.
+    fn set_fullscreen(&self, fullscreen: Option<Fullscreen>) {
+        self.add_window_update(WindowUpdate::Fullscreen(fullscreen))
+    }
+
+    /// Switch this window between borderless fullscreen on its current monitor
+    /// and its previous state.
+    fn toggle_fullscreen(&self) {
+        self.add_window_update(WindowUpdate::ToggleFullscreen)
+    }
+
+    /// Change the opacity of the whole window, including its decorations, from
+    /// `0.0` (invisible) to `1.0` (opaque).
+    ///
+    /// Platform support:
+    /// - Windows: Yes
+    /// - macOS: Yes
+    /// - Linux: No, this has no effect
+    fn set_opacity(&self, opacity: f64) {
+        self.add_window_update(WindowUpdate::Opacity(opacity.clamp(0.0, 1.0)))
+    }
+
+    /// Change the minimum size of this window's *content*, `None` removes the
+    /// constraint.
+    fn set_min_content_size(&self, size: Option<Size>) {
+        self.add_window_update(WindowUpdate::MinContentSize(size))
+    }
+
+    /// Change the maximum size of this window's *content*, `None` removes the
+    /// constraint.
+    fn set_max_content_size(&self, size: Option<Size>) {
+        self.add_window_update(WindowUpdate::MaxContentSize(size))
+    }
+
+    /// Move this window to the center of the monitor on which it currently resides.
+    fn center_on_monitor(&self) {
+        self.add_window_update(WindowUpdate::CenterOnMonitor)
+    }
+
     /// Change this window's minimized state.
     fn set_outer_location(&self, location: Point) {
         self.add_window_update(WindowUpdate::OuterLocation(location))
@@ -242,6 +311,25 @@ impl WindowIdExt for WindowId {
         monitor_bounds(self)
     }
 
+    fn current_monitor(&self) -> Option<MonitorInfo> {
+        with_window(self, |window| {
+            window
+                .current_monitor()
+                .map(|monitor| MonitorInfo::from_handle(&monitor))
+        })
+        .flatten()
+    }
+
+    fn available_monitors(&self) -> Vec<MonitorInfo> {
+        with_window(self, |window| {
+            window
+                .available_monitors()
+                .map(|monitor| MonitorInfo::from_handle(&monitor))
+                .collect()
+        })
+        .unwrap_or_default()
+    }
+
     fn is_visible(&self) -> bool {
         with_window(self, |window| window.is_visible().unwrap_or(false)).unwrap_or(false)
     }
@@ -254,6 +342,10 @@ impl WindowIdExt for WindowId {
         with_window(self, |window| window.is_maximized()).unwrap_or(false)
     }
 
+    fn is_fullscreen(&self) -> bool {
+        with_window(self, |window| window.fullscreen().is_some()).unwrap_or(false)
+    }
+
     #[cfg(target_os = "macos")]
     #[allow(dead_code)]
     fn is_document_edited(&self) -> bool {
@@ -343,6 +435,56 @@ pub(crate) fn process_window_updates(id: &WindowId) -> bool {
                 WindowUpdate::Maximize(maximize) => {
                     with_window(id, |window| window.set_maximized(maximize));
                 }
+                WindowUpdate::Level(level) => {
+                    with_window(id, |window| window.set_window_level(level));
+                }
+                WindowUpdate::Fullscreen(fullscreen) => {
+                    with_window(id, |window| window.set_fullscreen(fullscreen));
+                }
+                WindowUpdate::ToggleFullscreen => {
+                    with_window(id, |window| {
+                        let fullscreen = if window.fullscreen().is_some() {
+                            None
+                        } else {
+                            Some(Fullscreen::Borderless(window.current_monitor()))
+                        };
+                        window.set_fullscreen(fullscreen);
+                    });
+                }
+                WindowUpdate::Opacity(opacity) => {
+                    with_window(id, |window| set_window_opacity(window, opacity));
+                }
+                WindowUpdate::MinContentSize(size) => {
+                    with_window(id, |window| {
+                        window.set_min_surface_size(
+                            size.map(|size| LogicalSize::new(size.width, size.height).into()),
+                        )
+                    });
+                }
+                WindowUpdate::MaxContentSize(size) => {
+                    with_window(id, |window| {
+                        window.set_max_surface_size(
+                            size.map(|size| LogicalSize::new(size.width, size.height).into()),
+                        )
+                    });
+                }
+                WindowUpdate::CenterOnMonitor => {
+                    with_window(id, |window| {
+                        let Some(monitor) = window.current_monitor() else {
+                            return;
+                        };
+                        let monitor = monitor_bounds_for_monitor(window, &monitor);
+                        let size =
+                            winit_phys_size_to_size(window.outer_size(), window.scale_factor());
+                        window.set_outer_position(
+                            LogicalPosition::new(
+                                monitor.x0 + ((monitor.width() - size.width) / 2.0).max(0.0),
+                                monitor.y0 + ((monitor.height() - size.height) / 2.0).max(0.0),
+                            )
+                            .into(),
+                        );
+                    });
+                }
                 WindowUpdate::OuterLocation(outer) => {
                     with_window(id, |window| {
                         window.set_outer_position(LogicalPosition::new(outer.x, outer.y).into());
@@ -433,6 +575,46 @@ fn bounds_to_logical_outer_position_and_inner_size(
     }
 }
 
+#[cfg(target_os = "windows")]
+fn set_window_opacity(window: &Arc<dyn Window>, opacity: f64) {
+    use raw_window_handle::{HasWindowHandle, RawWindowHandle};
+    use windows_sys::Win32::UI::WindowsAndMessaging::{
+        GetWindowLongW, SetLayeredWindowAttributes, SetWindowLongW, GWL_EXSTYLE, LWA_ALPHA,
+        WS_EX_LAYERED,
+    };
+
+    let Ok(handle) = window.window_handle() else {
+        return;
+    };
+    if let RawWindowHandle::Win32(handle) = handle.as_raw() {
+        let hwnd = isize::from(handle.hwnd) as _;
+        unsafe {
+            let style = GetWindowLongW(hwnd, GWL_EXSTYLE);
+            SetWindowLongW(hwnd, GWL_EXSTYLE, style | WS_EX_LAYERED as i32);
+            SetLayeredWindowAttributes(hwnd, 0, (opacity * 255.0).round() as u8, LWA_ALPHA);
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn set_window_opacity(window: &Arc<dyn Window>, opacity: f64) {
+    use objc2_app_kit::NSView;
+    use raw_window_handle::{HasWindowHandle, RawWindowHandle};
+
+    let Ok(handle) = window.window_handle() else {
+        return;
+    };
+    if let RawWindowHandle::AppKit(handle) = handle.as_raw() {
+        let view = unsafe { handle.ns_view.cast::<NSView>().as_ref() };
+        if let Some(ns_window) = view.window() {
+            ns_window.setAlphaValue(opacity);
+        }
+    }
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn set_window_opacity(_window: &Arc<dyn Window>, _opacity: f64) {}
+
 /// Some operations - notably minimize and restoring visibility - don't take
 /// effect on macOS until something triggers a repaint in the target window - the
 /// issue is below the level of floem's event loops and seems to be in winit or
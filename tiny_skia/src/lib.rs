@@ -17,6 +17,7 @@ use softbuffer::{Context, Surface};
 use std::collections::HashMap;
 use std::num::NonZeroU32;
 use std::rc::Rc;
+use std::sync::Arc;
 use tiny_skia::{LineCap, LineJoin};
 
 macro_rules! try_ret {
@@ -40,9 +41,16 @@ struct CacheColor(bool);
 
 pub struct TinySkiaRenderer<W> {
     #[allow(unused)]
-    context: Context<W>,
-    surface: Surface<W, W>,
+    context: Option<Context<W>>,
+    /// `None` when rendering offscreen
+    surface: Option<Surface<W, W>>,
     pixmap: Pixmap,
+    /// Whether the next frame is returned as an image
+    capture: bool,
+    /// Whether the pixmap holds the previous frame, which partial frames paint over
+    has_frame: bool,
+    /// The part of the pixmap repainted by a partial frame, in physical pixels
+    damage: Option<Rect>,
     mask: Mask,
     scale: f64,
     transform: Affine,
@@ -82,9 +90,38 @@ impl<W: raw_window_handle::HasWindowHandle + raw_window_handle::HasDisplayHandle
         let mask = Mask::new(width, height).ok_or_else(|| anyhow!("unable to create mask"))?;
 
         Ok(Self {
-            context,
-            surface,
+            context: Some(context),
+            surface: Some(surface),
             pixmap,
+            capture: false,
+            has_frame: false,
+            damage: None,
+            mask,
+            scale,
+            transform: Affine::IDENTITY,
+            clip: None,
+            cache_color: CacheColor(false),
+            image_cache: Default::default(),
+            glyph_cache: Default::default(),
+            swash_scaler: SwashScaler::new(font_embolden),
+        })
+    }
+
+    /// Creates a renderer which doesn't present to a window, but returns every frame as an
+    /// image from [`Renderer::finish`].
+    pub fn new_offscreen(width: u32, height: u32, scale: f64, font_embolden: f32) -> Result<Self> {
+        let pixmap =
+            Pixmap::new(width, height).ok_or_else(|| anyhow!("unable to create pixmap"))?;
+
+        let mask = Mask::new(width, height).ok_or_else(|| anyhow!("unable to create mask"))?;
+
+        Ok(Self {
+            context: None,
+            surface: None,
+            pixmap,
+            capture: false,
+            has_frame: false,
+            damage: None,
             mask,
             scale,
             transform: Affine::IDENTITY,
@@ -98,19 +135,25 @@ impl<W: raw_window_handle::HasWindowHandle + raw_window_handle::HasDisplayHandle
 
     pub fn resize(&mut self, width: u32, height: u32, scale: f64) {
         if width != self.pixmap.width() || height != self.pixmap.width() {
-            self.surface
-                .resize(
-                    NonZeroU32::new(width).unwrap_or(NonZeroU32::new(1).unwrap()),
-                    NonZeroU32::new(height).unwrap_or(NonZeroU32::new(1).unwrap()),
-                )
-                .expect("failed to resize surface");
+            if let Some(surface) = self.surface.as_mut() {
+                surface
+                    .resize(
+                        NonZeroU32::new(width).unwrap_or(NonZeroU32::new(1).unwrap()),
+                        NonZeroU32::new(height).unwrap_or(NonZeroU32::new(1).unwrap()),
+                    )
+                    .expect("failed to resize surface");
+            }
             self.pixmap = Pixmap::new(width, height).expect("unable to create pixmap");
             self.mask = Mask::new(width, height).expect("unable to create mask");
+            self.has_frame = false;
         }
         self.scale = scale;
     }
 
     pub fn set_scale(&mut self, scale: f64) {
+        if scale != self.scale {
+            self.has_frame = false;
+        }
         self.scale = scale;
     }
 
@@ -128,11 +171,38 @@ fn to_color(color: Color) -> tiny_skia::Color {
     tiny_skia::Color::from_rgba8(c.r, c.g, c.b, c.a)
 }
 
+fn damage_rect(rect: Rect) -> Option<softbuffer::Rect> {
+    Some(softbuffer::Rect {
+        x: rect.x0 as u32,
+        y: rect.y0 as u32,
+        width: NonZeroU32::new(rect.width() as u32)?,
+        height: NonZeroU32::new(rect.height() as u32)?,
+    })
+}
+
 fn to_point(point: Point) -> tiny_skia::Point {
     tiny_skia::Point::from_xy(point.x as f32, point.y as f32)
 }
 
 impl<W> TinySkiaRenderer<W> {
+    fn pixmap_image(&self) -> peniko::Image {
+        let data = self
+            .pixmap
+            .pixels()
+            .iter()
+            .flat_map(|pixel| {
+                let pixel = pixel.demultiply();
+                [pixel.red(), pixel.green(), pixel.blue(), pixel.alpha()]
+            })
+            .collect::<Vec<u8>>();
+        peniko::Image::new(
+            peniko::Blob::new(Arc::new(data)),
+            peniko::ImageFormat::Rgba8,
+            self.pixmap.width(),
+            self.pixmap.height(),
+        )
+    }
+
     fn shape_to_path(&self, shape: &impl Shape) -> Option<Path> {
         let mut builder = PathBuilder::new();
         for element in shape.path_elements(0.1) {
@@ -372,10 +442,42 @@ impl<W> TinySkiaRenderer<W> {
 impl<W: raw_window_handle::HasWindowHandle + raw_window_handle::HasDisplayHandle> Renderer
     for TinySkiaRenderer<W>
 {
-    fn begin(&mut self, _capture: bool) {
+    fn begin(&mut self, capture: bool) {
+        self.capture = capture;
         self.transform = Affine::IDENTITY;
         self.pixmap.fill(tiny_skia::Color::WHITE);
         self.clip = None;
+        self.damage = None;
+    }
+
+    fn begin_partial(&mut self, damage: Rect) -> bool {
+        if !self.has_frame {
+            return false;
+        }
+        let bounds = Rect::new(
+            0.0,
+            0.0,
+            self.pixmap.width() as f64,
+            self.pixmap.height() as f64,
+        );
+        let damage = damage
+            .scale_from_origin(self.scale)
+            .expand()
+            .intersect(bounds);
+        self.capture = false;
+        self.transform = Affine::IDENTITY;
+        self.clip = None;
+        self.damage = Some(damage);
+        if let Some(rect) = self.rect(damage) {
+            let paint = Paint {
+                shader: Shader::SolidColor(tiny_skia::Color::WHITE),
+                blend_mode: tiny_skia::BlendMode::Source,
+                ..Default::default()
+            };
+            self.pixmap
+                .fill_rect(rect, &paint, Transform::identity(), None);
+        }
+        true
     }
 
     fn stroke<'b, 's>(
@@ -604,20 +706,38 @@ impl<W: raw_window_handle::HasWindowHandle + raw_window_handle::HasDisplayHandle
         // Swap the cache color.
         self.cache_color = CacheColor(!self.cache_color.0);
 
-        let mut buffer = self
-            .surface
+        self.has_frame = true;
+        let damage = self.damage.take();
+
+        let surface = match self.surface.as_mut() {
+            Some(surface) if !self.capture => surface,
+            // Offscreen or captured frames aren't presented
+            _ => return Some(self.pixmap_image()),
+        };
+        let damage = match damage {
+            Some(damage) => match damage_rect(damage) {
+                Some(damage) => Some(damage),
+                // Nothing visible changed
+                None => return None,
+            },
+            None => None,
+        };
+        let mut buffer = surface
             .buffer_mut()
             .expect("failed to get the surface buffer");
 
         // Copy from `tiny_skia::Pixmap` to the format specified by `softbuffer::Buffer`.
+        // The whole frame is copied since the buffer may hold an older frame than the last one.
         for (out_pixel, pixel) in (buffer.iter_mut()).zip(self.pixmap.pixels().iter()) {
             *out_pixel =
                 (pixel.red() as u32) << 16 | (pixel.green() as u32) << 8 | (pixel.blue() as u32);
         }
 
-        buffer
-            .present()
-            .expect("failed to present the surface buffer");
+        match damage {
+            Some(damage) => buffer.present_with_damage(&[damage]),
+            None => buffer.present(),
+        }
+        .expect("failed to present the surface buffer");
 
         None
     }